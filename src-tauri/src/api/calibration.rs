@@ -0,0 +1,245 @@
+//! Calibration API functions
+//! 
+//! This module runs `SHAPER_CALIBRATE`/`TEST_RESONANCES` on a printer over
+//! the websocket and collects the results: the recommended shapers and data
+//! files Klipper reports in its console output, and the CSV/PNG files that
+//! tools like Shake&Tune write into the config folder. It also runs
+//! `PID_CALIBRATE` for a heater and reads the PID values.
+
+use std::time::Duration;
+use crate::api::websocket::MoonrakerSocket;
+use crate::error::{MoonrakerError, MoonrakerResult};
+use crate::api::client::{
+    build_moonraker_url, create_client, get_moonraker_endpoint, read_body_limited, read_error_text, with_host_headers,
+};
+use crate::api::host_limiter::acquire_host_permit;
+use crate::api::printer::ensure_not_printing;
+use crate::models::calibration::{CalibrationFile, CalibrationKind, PidValues};
+use crate::models::config::MAX_DOWNLOAD_BYTES;
+
+/// Upper bound for one calibration run
+const CALIBRATION_TIMEOUT_SECONDS: u64 = 1800;
+
+/// Upper bound for one PID calibration; large beds oscillate slowly
+const PID_CALIBRATION_TIMEOUT_SECONDS: u64 = 3600;
+
+/// Highest PID calibration target accepted, above any common hotend
+const MAX_PID_TARGET: f64 = 350.0;
+
+/// Name fragments of calibration result files
+const RESULT_FILE_MARKERS: [&str; 4] = ["calibration_data", "resonances", "shaper", "shaketune"];
+
+/// Runs a resonance calibration and waits until Klipper finishes it
+/// 
+/// The script is sent over the websocket, which delivers the console output
+/// while the calibration runs and answers once it has completed.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `kind` - Calibration command
+/// * `axis` - "x" or "y" to test one axis (both axes when omitted)
+/// 
+/// # Returns
+/// * Console lines written during the calibration
+pub async fn run_calibration(host: &str, kind: CalibrationKind, axis: Option<&str>) -> MoonrakerResult<Vec<String>> {
+    let axis = axis.map(|a| a.trim().to_uppercase()).filter(|a| !a.is_empty());
+    if let Some(axis) = &axis {
+        if axis != "X" && axis != "Y" {
+            return Err(MoonrakerError::Api(format!("Invalid axis: {}", axis)));
+        }
+    }
+
+    ensure_not_printing(host).await?;
+
+    // TEST_RESONANCES needs an axis, so both axes are tested one after the other
+    let script = match (kind, &axis) {
+        (_, Some(axis)) => format!("{} AXIS={}", kind.command(), axis),
+        (CalibrationKind::ShaperCalibrate, None) => kind.command().to_string(),
+        (CalibrationKind::TestResonances, None) => "TEST_RESONANCES AXIS=X\nTEST_RESONANCES AXIS=Y".to_string(),
+    };
+
+    let timeout = Duration::from_secs(CALIBRATION_TIMEOUT_SECONDS);
+    let mut socket = MoonrakerSocket::connect(host).await?;
+    tokio::time::timeout(timeout, socket.run_gcode_script(&script))
+        .await
+        .map_err(|_| MoonrakerError::Timeout(timeout))?
+}
+
+/// Runs a PID calibration of a heater and waits until Klipper finishes it
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `heater` - Heater name, e.g. "extruder", "heater_bed" or a `heater_generic` name
+/// * `target` - Target temperature in °C
+pub async fn run_pid_calibration(host: &str, heater: &str, target: f64) -> MoonrakerResult<()> {
+    validate_heater_name(heater)?;
+    if !(target > 0.0 && target <= MAX_PID_TARGET) {
+        return Err(MoonrakerError::Api(format!("Invalid target temperature: {}", target)));
+    }
+
+    ensure_not_printing(host).await?;
+
+    let script = format!("PID_CALIBRATE HEATER={} TARGET={:.0}", heater, target);
+    run_script_and_wait(host, &script, PID_CALIBRATION_TIMEOUT_SECONDS).await
+}
+
+/// Gets the current temperature of a heater
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `heater` - Heater name as used by `PID_CALIBRATE`
+pub async fn get_heater_temperature(host: &str, heater: &str) -> MoonrakerResult<f64> {
+    validate_heater_name(heater)?;
+    let object = heater_object_name(heater);
+    let query = url::form_urlencoded::byte_serialize(object.as_bytes()).collect::<String>();
+    let data = get_moonraker_endpoint(host, &format!("printer/objects/query?{}=temperature", query)).await?;
+    data.pointer("/result/status")
+        .and_then(|status| status.get(&object))
+        .and_then(|heater| heater.get("temperature"))
+        .and_then(|t| t.as_f64())
+        .ok_or_else(|| MoonrakerError::Api(format!("Unknown heater: {}", heater)))
+}
+
+/// Gets the PID values a calibration wrote to the console after a point in time
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `since` - Unix timestamp of the calibration start
+pub async fn get_pid_result(host: &str, since: f64) -> MoonrakerResult<PidValues> {
+    console_lines_since(host, since)
+        .await?
+        .iter()
+        .rev()
+        .find_map(|line| PidValues::parse(line))
+        .ok_or_else(|| MoonrakerError::Api("No PID values found in the console".to_string()))
+}
+
+/// Klipper object of a heater: extruders and the bed are named like their
+/// heater, other heaters are `heater_generic` objects
+fn heater_object_name(heater: &str) -> String {
+    if heater == "heater_bed" || heater.starts_with("extruder") {
+        heater.to_string()
+    } else {
+        format!("heater_generic {}", heater)
+    }
+}
+
+/// Checks that a heater name can be used as a G-code parameter
+fn validate_heater_name(heater: &str) -> MoonrakerResult<()> {
+    if heater.is_empty() || !heater.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(MoonrakerError::Api(format!("Invalid heater: {}", heater)));
+    }
+    Ok(())
+}
+
+/// Sends a G-code script and waits for it to complete
+/// 
+/// Moonraker answers the request only after the script has completed, so
+/// the timeout must cover the whole script. The request takes no host
+/// permit: it keeps a connection open while Klipper works, and holding a
+/// permit for up to an hour would stall every status request meanwhile.
+async fn run_script_and_wait(host: &str, script: &str, timeout_seconds: u64) -> MoonrakerResult<()> {
+    let client = create_client().await?;
+    let url = build_moonraker_url(host, "printer/gcode/script");
+    let response = with_host_headers(client.post(&url), host)
+        .timeout(Duration::from_secs(timeout_seconds))
+        .json(&serde_json::json!({ "script": script }))
+        .send()
+        .await
+        .map_err(MoonrakerError::Network)?;
+
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(MoonrakerError::Http { status, message: read_error_text(response).await })
+    }
+}
+
+/// Gets the console lines written after a point in time, without the "//" prefix
+async fn console_lines_since(host: &str, since: f64) -> MoonrakerResult<Vec<String>> {
+    let data = get_moonraker_endpoint(host, "server/gcode_store?count=200").await?;
+    let entries = data
+        .pointer("/result/gcode_store")
+        .and_then(|s| s.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(entries
+        .iter()
+        .filter(|e| e.get("time").and_then(|t| t.as_f64()).unwrap_or(0.0) >= since)
+        .filter_map(|e| e.get("message").and_then(|m| m.as_str()))
+        .flat_map(|m| m.lines())
+        .map(|l| l.trim_start_matches("//").trim().to_string())
+        .collect())
+}
+
+/// Lists calibration result files in the config root written after a point in time
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `since` - Unix timestamp of the calibration start
+pub async fn list_calibration_files(host: &str, since: f64) -> MoonrakerResult<Vec<CalibrationFile>> {
+    let data = get_moonraker_endpoint(host, "server/files/list?root=config").await?;
+    let files = data
+        .get("result")
+        .and_then(|r| r.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut results: Vec<CalibrationFile> = files
+        .iter()
+        .filter_map(|f| {
+            let path = f.get("path")?.as_str()?.to_string();
+            let modified = f.get("modified").and_then(|m| m.as_f64()).unwrap_or(0.0);
+            let lower = path.to_lowercase();
+            let is_result = (lower.ends_with(".csv") || lower.ends_with(".png"))
+                && RESULT_FILE_MARKERS.iter().any(|m| lower.contains(m));
+            (is_result && modified >= since).then(|| CalibrationFile {
+                root: "config".to_string(),
+                path,
+                size: f.get("size").and_then(|s| s.as_u64()).unwrap_or(0),
+                modified,
+            })
+        })
+        .collect();
+    results.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(results)
+}
+
+/// Downloads a file through the Moonraker file API
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `root` - File root (e.g. "config")
+/// * `path` - Path relative to the root
+/// 
+/// # Returns
+/// * File content
+pub async fn download_file(host: &str, root: &str, path: &str) -> MoonrakerResult<Vec<u8>> {
+    if path.split('/').any(|segment| segment == "..") {
+        return Err(MoonrakerError::Api("Invalid file path".to_string()));
+    }
+
+    let mut url = url::Url::parse(&build_moonraker_url(host, "server/files"))
+        .map_err(|e| MoonrakerError::Api(format!("Invalid URL: {}", e)))?;
+    url.path_segments_mut()
+        .map_err(|_| MoonrakerError::Api("Invalid URL".to_string()))?
+        .push(root)
+        .extend(path.split('/').filter(|s| !s.is_empty()));
+
+    let _permit = acquire_host_permit(host).await;
+    let client = create_client().await?;
+    let response = with_host_headers(client.get(url), host)
+        .timeout(Duration::from_secs(60))
+        .send()
+        .await
+        .map_err(MoonrakerError::Network)?;
+
+    let status = response.status();
+    if status.is_success() {
+        read_body_limited(response, MAX_DOWNLOAD_BYTES).await
+    } else {
+        Err(MoonrakerError::Http { status, message: read_error_text(response).await })
+    }
+}
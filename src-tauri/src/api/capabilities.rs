@@ -0,0 +1,96 @@
+//! Printer object list cache and capability detection
+//!
+//! Which features a printer has (exclude_object, the timelapse plugin,
+//! several extruders, LEDs, ...) follows from its Klipper object list. The
+//! list is fetched on first contact with a host and kept until Klippy
+//! restarts, so the UI and the Telegram bot only offer what the printer
+//! supports and no requests are sent for objects it does not have.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex as StdMutex};
+use crate::api::client::get_moonraker_endpoint;
+use crate::error::{MoonrakerError, MoonrakerResult};
+use crate::models::api::extruder_index;
+use crate::models::host::PrinterCapabilities;
+
+/// Object lists keyed by host address
+static OBJECT_LISTS: StdMutex<Option<HashMap<String, Arc<PrinterObjectList>>>> = StdMutex::new(None);
+
+/// Klipper object list of a printer with the capabilities derived from it
+#[derive(Debug)]
+pub struct PrinterObjectList {
+    /// Object names, e.g. "extruder" or "neopixel caselight"
+    pub objects: Vec<String>,
+    pub capabilities: PrinterCapabilities,
+}
+
+/// Gets the Klipper object list of a host, fetching it on first use
+///
+/// Lists are only cached while Klippy is ready; until then every call
+/// asks Moonraker again.
+///
+/// # Arguments
+/// * `host` - Host IP address
+pub async fn get_printer_object_list(host: &str) -> MoonrakerResult<Arc<PrinterObjectList>> {
+    if let Some(list) = cached_object_list(host) {
+        return Ok(list);
+    }
+
+    let data = get_moonraker_endpoint(host, "printer/objects/list").await?;
+    let objects: Vec<String> = data
+        .pointer("/result/objects")
+        .and_then(|o| o.as_array())
+        .ok_or_else(|| MoonrakerError::Api("Printer object list not available".to_string()))?
+        .iter()
+        .filter_map(|o| o.as_str())
+        .map(str::to_string)
+        .collect();
+    let list = Arc::new(PrinterObjectList {
+        capabilities: PrinterCapabilities::from_objects(&objects),
+        objects,
+    });
+
+    // Klippy lists only a few objects while it is starting up
+    if list.objects.iter().any(|o| o == "toolhead") {
+        let mut cache = OBJECT_LISTS.lock().unwrap_or_else(|e| e.into_inner());
+        cache.get_or_insert_with(HashMap::new).insert(host.to_string(), list.clone());
+    }
+    Ok(list)
+}
+
+/// Gets the capabilities of a host, see [`get_printer_object_list`]
+pub async fn get_printer_capabilities(host: &str) -> MoonrakerResult<PrinterCapabilities> {
+    Ok(get_printer_object_list(host).await?.capabilities.clone())
+}
+
+/// Builds the query string of the tool objects a host has besides `extruder`
+///
+/// # Returns
+/// * e.g. "&extruder1&toolchanger"; empty for single-extruder printers and
+///   when the object list is not available
+pub async fn extra_tool_objects_query(host: &str) -> String {
+    let Ok(list) = get_printer_object_list(host).await else {
+        return String::new();
+    };
+    list.objects
+        .iter()
+        .filter(|o| extruder_index(o).is_some_and(|index| index > 0) || *o == "toolchanger")
+        .map(|o| format!("&{}", o))
+        .collect()
+}
+
+/// Drops the cached object list of a host, e.g. when Klippy restarts with
+/// a possibly changed configuration
+pub fn invalidate_printer_objects(host: &str) {
+    if let Ok(mut cache) = OBJECT_LISTS.lock() {
+        if let Some(lists) = cache.as_mut() {
+            lists.remove(host);
+        }
+    }
+}
+
+/// Gets the cached object list of a host
+fn cached_object_list(host: &str) -> Option<Arc<PrinterObjectList>> {
+    let cache = OBJECT_LISTS.lock().ok()?;
+    cache.as_ref()?.get(host).cloned()
+}
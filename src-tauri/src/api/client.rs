@@ -1,22 +1,201 @@
 //! HTTP client utilities for Moonraker API communication
 //! 
 //! This module provides a configured HTTP client and utility functions
-//! for making requests to Moonraker printers.
+//! for making requests to Moonraker printers. Per-host connection overrides
+//! (reverse-proxy base URL, custom headers) are applied here so every
+//! request, including webcam snapshots, goes to the right place.
+//!
+//! Response bodies are read with a size limit so a misbehaving endpoint
+//! cannot exhaust memory, and GET responses carrying an ETag or
+//! Last-Modified header are kept so repeated requests can be answered with
+//! "304 Not Modified", which saves bandwidth on remote/VPN hosts.
+//!
+//! Every HTTP client of the application (Moonraker, GitHub updater,
+//! Telegram) is configured through `configure_http_client`, which applies
+//! the user's proxy and CA bundle settings and sends one User-Agent so
+//! farm operators can tell MHS traffic apart in reverse-proxy logs.
 
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
-use reqwest::Client;
+use reqwest::header::{HeaderMap, HeaderName, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{Certificate, Client, ClientBuilder, NoProxy, Proxy, RequestBuilder, Response, StatusCode};
+use crate::api::host_limiter::acquire_host_permit;
 use crate::error::{MoonrakerError, MoonrakerResult};
-use crate::models::config::{DEFAULT_TIMEOUT_SECONDS, MOONRAKER_PORT};
+use crate::models::config::{
+    AppSettings, HostConnectionSettings, OutboundHttpSettings, CONDITIONAL_CACHE_MAX_BYTES, CONDITIONAL_CACHE_MAX_ENTRIES,
+    DEFAULT_TIMEOUT_SECONDS, DEFAULT_USER_AGENT, MAX_RESPONSE_BYTES, WEB_UI_PORTS, WEB_UI_PROBE_TIMEOUT_MS,
+};
+use crate::network::ip_utils::split_host_port;
+
+/// Cached per-host connection overrides, loaded from settings on first use
+static HOST_CONNECTIONS: RwLock<Option<HashMap<String, HostConnectionSettings>>> = RwLock::new(None);
+
+/// Last GET responses with validators, keyed by URL
+static CONDITIONAL_CACHE: RwLock<Option<HashMap<String, ConditionalEntry>>> = RwLock::new(None);
+
+/// GET response kept for conditional requests
+#[derive(Clone)]
+struct ConditionalEntry {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    data: serde_json::Value,
+}
+
+/// Outbound HTTP settings, loaded from settings on first use
+static OUTBOUND_HTTP: RwLock<Option<Arc<OutboundHttp>>> = RwLock::new(None);
+
+/// Outbound HTTP settings with the certificates read from the CA bundle
+struct OutboundHttp {
+    settings: OutboundHttpSettings,
+    certificates: Vec<Certificate>,
+    user_agent: String,
+}
+
+impl Default for OutboundHttp {
+    fn default() -> Self {
+        Self {
+            settings: OutboundHttpSettings::default(),
+            certificates: Vec::new(),
+            user_agent: render_user_agent(DEFAULT_USER_AGENT),
+        }
+    }
+}
+
+/// Fills the `{version}` and `{host}` placeholders of a User-Agent template
+pub fn render_user_agent(template: &str) -> String {
+    template
+        .replace("{version}", env!("CARGO_PKG_VERSION"))
+        .replace("{host}", &computer_name())
+}
+
+/// Gets the name of this computer, "unknown" when it cannot be determined
+fn computer_name() -> String {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Longest error body included in error messages
+const MAX_ERROR_TEXT_BYTES: usize = 4 * 1024;
+
+/// What a client talks to, which decides whether the proxy is used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpTarget {
+    /// Moonraker, webcams and web interfaces of printers
+    Printer,
+    /// GitHub, Telegram and other internet services
+    Internet,
+}
+
+/// Reads the CA bundle and checks the proxy URL of outbound HTTP settings
+fn load_outbound_http(settings: OutboundHttpSettings) -> Result<OutboundHttp, String> {
+    let certificates = match settings.ca_bundle_path.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        Some(path) => {
+            let pem = std::fs::read(path).map_err(|e| format!("Failed to read CA bundle {}: {}", path, e))?;
+            let certificates =
+                Certificate::from_pem_bundle(&pem).map_err(|e| format!("Invalid CA bundle {}: {}", path, e))?;
+            if certificates.is_empty() {
+                return Err(format!("CA bundle {} contains no certificates", path));
+            }
+            certificates
+        }
+        None => Vec::new(),
+    };
+    if let Some(url) = settings.proxy_url.as_deref().filter(|u| !u.trim().is_empty()) {
+        Proxy::all(url.trim()).map_err(|e| format!("Invalid proxy URL: {}", e))?;
+    }
+    let template = settings.user_agent.as_deref().map(str::trim).filter(|ua| !ua.is_empty());
+    let user_agent = render_user_agent(template.unwrap_or(DEFAULT_USER_AGENT));
+    reqwest::header::HeaderValue::from_str(&user_agent).map_err(|_| format!("Invalid User-Agent: {}", user_agent))?;
+    Ok(OutboundHttp { settings, certificates, user_agent })
+}
+
+fn outbound_http() -> Arc<OutboundHttp> {
+    if let Ok(cache) = OUTBOUND_HTTP.read() {
+        if let Some(outbound) = cache.as_ref() {
+            return outbound.clone();
+        }
+    }
+
+    let settings = AppSettings::load().map(|s| s.outbound_http).unwrap_or_default();
+    let outbound = Arc::new(load_outbound_http(settings).unwrap_or_else(|e| {
+        eprintln!("Ignoring outbound HTTP settings: {}", e);
+        OutboundHttp::default()
+    }));
+    if let Ok(mut cache) = OUTBOUND_HTTP.write() {
+        *cache = Some(outbound.clone());
+    }
+    outbound
+}
+
+/// Validates and applies outbound HTTP settings, e.g. after they were saved
+///
+/// Clients created afterwards use the new settings; the Telegram bot picks
+/// them up when it is restarted.
+pub fn set_outbound_http_settings(settings: OutboundHttpSettings) -> Result<(), String> {
+    let outbound = load_outbound_http(settings)?;
+    if let Ok(mut cache) = OUTBOUND_HTTP.write() {
+        *cache = Some(Arc::new(outbound));
+    }
+    Ok(())
+}
+
+/// Gets the User-Agent sent with every request
+pub fn user_agent() -> String {
+    outbound_http().user_agent.clone()
+}
+
+/// Checks outbound HTTP settings without applying them
+pub fn validate_outbound_http_settings(settings: &OutboundHttpSettings) -> Result<(), String> {
+    load_outbound_http(settings.clone()).map(|_| ())
+}
+
+/// Applies the user's User-Agent, proxy and CA bundle settings to an HTTP client
+///
+/// Without a configured proxy the environment variables are used as
+/// before. Printer requests bypass a configured proxy unless the user
+/// chose to send them through it.
+///
+/// # Arguments
+/// * `builder` - Client builder to extend
+/// * `target` - What the client talks to
+pub fn configure_http_client(mut builder: ClientBuilder, target: HttpTarget) -> ClientBuilder {
+    let outbound = outbound_http();
+    builder = builder.user_agent(outbound.user_agent.clone());
+    for certificate in &outbound.certificates {
+        builder = builder.add_root_certificate(certificate.clone());
+    }
+
+    let settings = &outbound.settings;
+    let Some(url) = settings.proxy_url.as_deref().map(str::trim).filter(|u| !u.is_empty()) else {
+        return builder;
+    };
+    if target == HttpTarget::Printer && !settings.proxy_printers {
+        return builder.no_proxy();
+    }
+    match Proxy::all(url) {
+        Ok(proxy) => builder.proxy(proxy.no_proxy(NoProxy::from_string(&settings.no_proxy))),
+        Err(_) => builder,
+    }
+}
 
 /// Creates a configured HTTP client for Moonraker API requests
 /// 
 /// The client is configured with:
 /// - 5 second timeout for all requests
 /// - Proper headers for JSON communication
+/// - Transparent gzip decompression
 /// - Connection pooling for efficiency
+/// - The user's User-Agent, proxy and CA bundle settings
 pub async fn create_client() -> MoonrakerResult<Client> {
-    Client::builder()
+    configure_http_client(Client::builder(), HttpTarget::Printer)
         .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS))
+        .gzip(true)
         .build()
         .map_err(MoonrakerError::Network)
 }
@@ -24,17 +203,134 @@ pub async fn create_client() -> MoonrakerResult<Client> {
 /// Builds a Moonraker API URL for a given host and endpoint
 /// 
 /// # Arguments
-/// * `host` - Host IP address or hostname
+/// * `host` - Host IP address or hostname, with ":port" for additional instances
 /// * `endpoint` - API endpoint (e.g., "server/info", "printer/info")
 /// 
 /// # Returns
 /// * Full URL for the API request
 pub fn build_moonraker_url(host: &str, endpoint: &str) -> String {
-    format!("http://{}:{}/{}", host, MOONRAKER_PORT, endpoint)
+    match host_base_url(host) {
+        Some(base_url) => format!("{}/{}", base_url, endpoint),
+        None => {
+            let (address, port) = split_host_port(host);
+            format!("http://{}:{}/{}", address, port, endpoint)
+        }
+    }
+}
+
+/// Builds a URL for the web server (nginx) in front of Moonraker
+/// 
+/// # Arguments
+/// * `host` - Host IP address or hostname
+/// * `path` - Path without leading slash (e.g., "webcam/?action=snapshot")
+/// 
+/// # Returns
+/// * Full URL for the request
+pub fn build_web_url(host: &str, path: &str) -> String {
+    match host_base_url(host) {
+        Some(base_url) => format!("{}/{}", base_url, path),
+        None => format!("http://{}/{}", split_host_port(host).0, path),
+    }
+}
+
+/// Builds the URL of the Mainsail/Fluidd web interface of a host
+/// 
+/// # Arguments
+/// * `host` - Host IP address or hostname
+/// * `web_port` - Web interface port detected during the scan (None: port 80)
+/// 
+/// # Returns
+/// * URL to open in a browser
+pub fn build_web_ui_url(host: &str, web_port: Option<u16>) -> String {
+    let address = split_host_port(host).0;
+    match (host_base_url(host), web_port) {
+        (Some(base_url), _) => format!("{}/", base_url),
+        (None, None | Some(80)) => format!("http://{}/", address),
+        (None, Some(port)) => format!("http://{}:{}/", address, port),
+    }
+}
+
+/// Finds the port the Mainsail/Fluidd web interface answers on
+/// 
+/// # Arguments
+/// * `host` - Host IP address or hostname
+/// 
+/// # Returns
+/// * First of `WEB_UI_PORTS` answering with a success status, None if none does
+pub async fn detect_web_port(host: &str) -> Option<u16> {
+    let client = configure_http_client(Client::builder(), HttpTarget::Printer)
+        .timeout(Duration::from_millis(WEB_UI_PROBE_TIMEOUT_MS))
+        .build()
+        .ok()?;
+
+    for port in WEB_UI_PORTS {
+        let url = format!("http://{}:{}/", split_host_port(host).0, port);
+        if let Ok(response) = with_host_headers(client.get(&url), host).send().await {
+            if response.status().is_success() {
+                return Some(port);
+            }
+        }
+    }
+    None
+}
+
+/// Gets the connection overrides configured for a host
+pub fn host_connection(host: &str) -> Option<HostConnectionSettings> {
+    if let Ok(cache) = HOST_CONNECTIONS.read() {
+        if let Some(connections) = cache.as_ref() {
+            return connections.get(host).cloned();
+        }
+    }
+
+    let connections = AppSettings::load().map(|s| s.host_connections).unwrap_or_default();
+    let connection = connections.get(host).cloned();
+    set_host_connections(connections);
+    connection
+}
+
+/// Replaces the cached connection overrides, e.g. after settings were saved
+pub fn set_host_connections(connections: HashMap<String, HostConnectionSettings>) {
+    if let Ok(mut cache) = HOST_CONNECTIONS.write() {
+        *cache = Some(connections);
+    }
+}
+
+/// Checks whether a host is reached through a configured base URL
+pub fn has_custom_base_url(host: &str) -> bool {
+    host_base_url(host).is_some()
+}
+
+/// Adds the host's custom headers to a request
+/// 
+/// # Arguments
+/// * `request` - Request to extend
+/// * `host` - Host the request is sent to
+/// 
+/// # Returns
+/// * Request with the configured headers
+pub fn with_host_headers(mut request: RequestBuilder, host: &str) -> RequestBuilder {
+    if let Some(connection) = host_connection(host) {
+        for (name, value) in &connection.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+    }
+    request
+}
+
+/// Gets the configured base URL of a host without trailing slash
+fn host_base_url(host: &str) -> Option<String> {
+    host_connection(host)
+        .and_then(|c| c.base_url)
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty())
 }
 
 /// Makes a GET request to a Moonraker API endpoint
 /// 
+/// The request is conditional when an earlier response for the same URL
+/// carried an ETag or Last-Modified header; "304 Not Modified" answers are
+/// served from that response.
+/// 
 /// # Arguments
 /// * `host` - Host IP address
 /// * `endpoint` - API endpoint
@@ -42,27 +338,41 @@ pub fn build_moonraker_url(host: &str, endpoint: &str) -> String {
 /// # Returns
 /// * JSON response as serde_json::Value
 pub async fn get_moonraker_endpoint(host: &str, endpoint: &str) -> MoonrakerResult<serde_json::Value> {
+    let _permit = acquire_host_permit(host).await;
     let client = create_client().await?;
     let url = build_moonraker_url(host, endpoint);
-    
-    let response = client
-        .get(&url)
+    let cached = conditional_entry(&url);
+
+    let mut request = with_host_headers(client.get(&url), host);
+    if let Some(entry) = &cached {
+        if let Some(etag) = &entry.etag {
+            request = request.header(IF_NONE_MATCH, etag.as_str());
+        }
+        if let Some(last_modified) = &entry.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified.as_str());
+        }
+    }
+
+    let response = request
         .send()
         .await
         .map_err(MoonrakerError::Network)?;
 
-    if response.status().is_success() {
-        let data: serde_json::Value = response
-            .json()
-            .await
-            .map_err(MoonrakerError::Network)?;
+    let status = response.status();
+    if status == StatusCode::NOT_MODIFIED {
+        if let Some(entry) = cached {
+            return Ok(entry.data);
+        }
+    }
+
+    if status.is_success() {
+        let headers = response.headers().clone();
+        let body = read_body_limited(response, MAX_RESPONSE_BYTES).await?;
+        let data = parse_json(&body)?;
+        store_conditional_entry(&url, &headers, &data, body.len());
         Ok(data)
     } else {
-        Err(MoonrakerError::Api(format!(
-            "HTTP {}: {}",
-            response.status(),
-            response.text().await.unwrap_or_else(|_| "Unknown error".to_string())
-        )))
+        Err(MoonrakerError::Http { status, message: read_error_text(response).await })
     }
 }
 
@@ -80,10 +390,15 @@ pub async fn post_moonraker_endpoint(
     endpoint: &str,
     body: Option<serde_json::Value>,
 ) -> MoonrakerResult<serde_json::Value> {
+    // An emergency stop must never wait behind other requests
+    let _permit = match endpoint {
+        "printer/emergency_stop" => None,
+        _ => acquire_host_permit(host).await,
+    };
     let client = create_client().await?;
     let url = build_moonraker_url(host, endpoint);
     
-    let mut request = client.post(&url);
+    let mut request = with_host_headers(client.post(&url), host);
     
     if let Some(body_data) = body {
         request = request.json(&body_data);
@@ -96,20 +411,80 @@ pub async fn post_moonraker_endpoint(
 
     let status = response.status();
     if status.is_success() {
-        let data: serde_json::Value = response
-            .json()
-            .await
-            .map_err(MoonrakerError::Network)?;
-        Ok(data)
+        let body = read_body_limited(response, MAX_RESPONSE_BYTES).await?;
+        parse_json(&body)
     } else {
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        Err(MoonrakerError::Api(format!(
-            "HTTP {}: {}",
-            status,
-            error_text
-        )))
+        Err(MoonrakerError::Http { status, message: read_error_text(response).await })
+    }
+}
+
+/// Reads a response body, failing once it grows beyond a limit
+/// 
+/// # Arguments
+/// * `response` - Response to read
+/// * `limit` - Maximum body size in bytes (after decompression)
+/// 
+/// # Returns
+/// * Body bytes
+pub async fn read_body_limited(mut response: Response, limit: usize) -> MoonrakerResult<Vec<u8>> {
+    let too_large = || MoonrakerError::Api(format!("Response exceeds {} bytes", limit));
+
+    // Content-Length describes the compressed body, so it is only a first check
+    if response.content_length().is_some_and(|length| length > limit as u64) {
+        return Err(too_large());
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(MoonrakerError::Network)? {
+        if body.len() + chunk.len() > limit {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Reads the body of an error response for use in an error message
+pub async fn read_error_text(response: Response) -> String {
+    match read_body_limited(response, MAX_ERROR_TEXT_BYTES).await {
+        Ok(body) => String::from_utf8_lossy(&body).into_owned(),
+        Err(_) => "Unknown error".to_string(),
+    }
+}
+
+/// Parses a JSON response body
+fn parse_json(body: &[u8]) -> MoonrakerResult<serde_json::Value> {
+    serde_json::from_slice(body).map_err(|e| MoonrakerError::Api(format!("Invalid JSON response: {}", e)))
+}
+
+/// Gets the stored response for a URL
+fn conditional_entry(url: &str) -> Option<ConditionalEntry> {
+    CONDITIONAL_CACHE.read().ok()?.as_ref()?.get(url).cloned()
+}
+
+/// Stores a GET response that carries an ETag or Last-Modified header
+fn store_conditional_entry(url: &str, headers: &HeaderMap, data: &serde_json::Value, size: usize) {
+    let header = |name: HeaderName| headers.get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+    let etag = header(ETAG);
+    let last_modified = header(LAST_MODIFIED);
+
+    let Ok(mut cache) = CONDITIONAL_CACHE.write() else {
+        return;
+    };
+    let entries = cache.get_or_insert_with(HashMap::new);
+
+    if (etag.is_none() && last_modified.is_none()) || size > CONDITIONAL_CACHE_MAX_BYTES {
+        entries.remove(url);
+        return;
+    }
+
+    // Rarely reached; starting over is simpler than tracking usage
+    if entries.len() >= CONDITIONAL_CACHE_MAX_ENTRIES && !entries.contains_key(url) {
+        entries.clear();
     }
+    entries.insert(url.to_string(), ConditionalEntry {
+        etag,
+        last_modified,
+        data: data.clone(),
+    });
 }
@@ -0,0 +1,121 @@
+//! Printer configuration file API functions
+//!
+//! This module reads and writes the files in the Moonraker `config` root
+//! (`printer.cfg`, included configs, `moonraker.conf`) and restarts the
+//! service that reads them, so configs can be tweaked without SSH.
+
+use std::time::Duration;
+use crate::error::{MoonrakerError, MoonrakerResult};
+use crate::api::calibration::download_file;
+use crate::api::capabilities::invalidate_printer_objects;
+use crate::api::client::{get_moonraker_endpoint, post_moonraker_endpoint};
+use crate::api::files::upload_file;
+use crate::api::printer::{ensure_not_printing, wait_for_klippy_ready};
+use crate::models::api::KlippyState;
+use crate::models::config_file::{ConfigFile, ConfigService};
+use crate::network::status_cache::invalidate_host_status;
+
+/// Extensions of the text configs that can be edited
+const CONFIG_EXTENSIONS: [&str; 3] = [".cfg", ".conf", ".txt"];
+
+/// Lists every file in the `config` root of a printer, including
+/// calibration results and Klipper's config backups
+///
+/// # Arguments
+/// * `host` - Host IP address
+pub async fn list_config_directory(host: &str) -> MoonrakerResult<Vec<ConfigFile>> {
+    let data = get_moonraker_endpoint(host, "server/files/list?root=config").await?;
+    Ok(data
+        .get("result")
+        .and_then(|r| r.as_array())
+        .map(|files| {
+            files
+                .iter()
+                .filter_map(|f| {
+                    Some(ConfigFile {
+                        path: f.get("path")?.as_str()?.to_string(),
+                        size: f.get("size").and_then(|s| s.as_u64()).unwrap_or(0),
+                        modified: f.get("modified").and_then(|m| m.as_f64()).unwrap_or(0.0),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Lists the configuration files of a printer
+///
+/// # Arguments
+/// * `host` - Host IP address
+///
+/// # Returns
+/// * Config files sorted by path, `printer.cfg` first
+pub async fn list_config_files(host: &str) -> MoonrakerResult<Vec<ConfigFile>> {
+    let mut files: Vec<ConfigFile> = list_config_directory(host)
+        .await?
+        .into_iter()
+        .filter(|f| is_config_path(&f.path))
+        .collect();
+    files.sort_by(|a, b| (a.path != "printer.cfg", &a.path).cmp(&(b.path != "printer.cfg", &b.path)));
+    Ok(files)
+}
+
+/// Reads a configuration file
+///
+/// # Arguments
+/// * `host` - Host IP address
+/// * `path` - Path relative to the `config` root
+pub async fn read_config_file(host: &str, path: &str) -> MoonrakerResult<String> {
+    validate_config_path(path)?;
+    let content = download_file(host, "config", path).await?;
+    String::from_utf8(content).map_err(|_| MoonrakerError::Api(format!("{} is not a text file", path)))
+}
+
+/// Writes a configuration file
+///
+/// The running services keep the old configuration until they restart.
+///
+/// # Arguments
+/// * `host` - Host IP address
+/// * `path` - Path relative to the `config` root
+/// * `content` - New content
+pub async fn write_config_file(host: &str, path: &str, content: &str) -> MoonrakerResult<()> {
+    validate_config_path(path)?;
+    upload_file(host, "config", path, content.as_bytes().to_vec()).await?;
+    Ok(())
+}
+
+/// Restarts the service that reads a configuration and waits until Klippy
+/// is ready again
+///
+/// # Arguments
+/// * `host` - Host IP address
+/// * `service` - Klipper or Moonraker
+/// * `timeout` - Maximum time to wait for Klippy
+///
+/// # Returns
+/// * Final Klippy state ("ready", "error" or "shutdown"); "error" usually
+///   means the new configuration is invalid
+pub async fn restart_config_service(host: &str, service: ConfigService, timeout: Duration) -> MoonrakerResult<KlippyState> {
+    ensure_not_printing(host).await?;
+    post_moonraker_endpoint(host, service.restart_endpoint(), None).await?;
+    let state = wait_for_klippy_ready(host, timeout).await;
+    invalidate_host_status(host);
+    invalidate_printer_objects(host);
+    state
+}
+
+/// Checks whether a path is an editable config file
+fn is_config_path(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    CONFIG_EXTENSIONS.iter().any(|ext| lower.ends_with(ext))
+}
+
+/// Checks that a path stays inside the `config` root and names an editable config
+fn validate_config_path(path: &str) -> MoonrakerResult<()> {
+    let escapes = path.starts_with('/') || path.split(['/', '\\']).any(|segment| segment == "..");
+    if path.is_empty() || escapes || !is_config_path(path) {
+        return Err(MoonrakerError::Api(format!("Invalid config file path: {}", path)));
+    }
+    Ok(())
+}
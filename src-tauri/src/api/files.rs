@@ -0,0 +1,81 @@
+//! File management API functions
+//! 
+//! This module provides functions for uploading files to Moonraker and
+//! starting prints from uploaded g-code files.
+
+use std::time::Duration;
+use reqwest::multipart::{Form, Part};
+use crate::api::host_limiter::acquire_transfer_permit;
+use crate::error::{MoonrakerError, MoonrakerResult};
+use crate::api::client::{
+    build_moonraker_url, create_client, post_moonraker_endpoint, read_body_limited, read_error_text, with_host_headers,
+};
+use crate::models::config::MAX_RESPONSE_BYTES;
+
+/// Timeout for file uploads, which can take much longer than API calls
+const UPLOAD_TIMEOUT_SECONDS: u64 = 300;
+
+/// Uploads a g-code file to the `gcodes` root of a host
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `file_name` - Target file name (may include a subdirectory)
+/// * `content` - File content
+/// 
+/// # Returns
+/// * API response as JSON
+pub async fn upload_gcode(host: &str, file_name: &str, content: Vec<u8>) -> MoonrakerResult<serde_json::Value> {
+    upload_file(host, "gcodes", file_name, content).await
+}
+
+/// Uploads a file to a Moonraker file root
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `root` - File root, e.g. "gcodes" or "config"
+/// * `file_name` - Target file name (may include a subdirectory)
+/// * `content` - File content
+/// 
+/// # Returns
+/// * API response as JSON
+pub async fn upload_file(host: &str, root: &str, file_name: &str, content: Vec<u8>) -> MoonrakerResult<serde_json::Value> {
+    let _permit = acquire_transfer_permit(host).await;
+    let client = create_client().await?;
+    let url = build_moonraker_url(host, "server/files/upload");
+
+    let part = Part::bytes(content)
+        .file_name(file_name.to_string())
+        .mime_str("application/octet-stream")
+        .map_err(MoonrakerError::Network)?;
+    let form = Form::new()
+        .text("root", root.to_string())
+        .part("file", part);
+
+    let response = with_host_headers(client.post(&url), host)
+        .timeout(Duration::from_secs(UPLOAD_TIMEOUT_SECONDS))
+        .multipart(form)
+        .send()
+        .await
+        .map_err(MoonrakerError::Network)?;
+
+    let status = response.status();
+    if status.is_success() {
+        let body = read_body_limited(response, MAX_RESPONSE_BYTES).await?;
+        serde_json::from_slice(&body).map_err(|e| MoonrakerError::Api(format!("Invalid JSON response: {}", e)))
+    } else {
+        Err(MoonrakerError::Http { status, message: read_error_text(response).await })
+    }
+}
+
+/// Starts printing a file that is already on the host
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `file_name` - File path relative to the `gcodes` root
+/// 
+/// # Returns
+/// * API response as JSON
+pub async fn start_print_file(host: &str, file_name: &str) -> MoonrakerResult<serde_json::Value> {
+    let body = serde_json::json!({ "filename": file_name });
+    post_moonraker_endpoint(host, "printer/print/start", Some(body)).await
+}
@@ -0,0 +1,88 @@
+//! Job history API functions
+//! 
+//! This module provides functions for reading the Moonraker job history,
+//! such as cumulative print time used for maintenance reminders,
+//! lifetime cost statistics, previous runs of a file and the running job.
+
+use crate::error::{MoonrakerError, MoonrakerResult};
+use crate::models::api::{HistoryJob, JobTotals, MoonrakerJobList, MoonrakerJobTotals};
+use crate::models::config::{AppSettings, JOB_HISTORY_LOOKUP_LIMIT};
+use crate::models::print_info::HistoryStats;
+use crate::api::client::get_moonraker_endpoint;
+
+/// Gets cumulative job statistics from the printer history
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Job totals (requires the Moonraker `history` component)
+pub async fn get_job_totals(host: &str) -> MoonrakerResult<JobTotals> {
+    let data = get_moonraker_endpoint(host, "server/history/totals").await?;
+    let totals: MoonrakerJobTotals = serde_json::from_value(data)
+        .map_err(|e| MoonrakerError::Api(format!("Failed to parse job totals: {}", e)))?;
+    Ok(totals.result.job_totals)
+}
+
+/// Gets the cumulative print time of a host in hours
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Total print hours recorded in the history
+pub async fn get_print_hours(host: &str) -> MoonrakerResult<f64> {
+    let totals = get_job_totals(host).await?;
+    Ok(totals.total_print_time / 3600.0)
+}
+
+/// Gets lifetime job statistics with an estimated total cost
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Job totals and their estimated cost
+pub async fn get_history_stats(host: &str) -> MoonrakerResult<HistoryStats> {
+    let totals = get_job_totals(host).await?;
+    let estimated_cost = AppSettings::load()
+        .ok()
+        .and_then(|s| s.costs.estimate(host, totals.total_filament_used, totals.total_print_time));
+    Ok(HistoryStats { totals, estimated_cost })
+}
+
+/// Gets the previous runs of a file from the printer history
+/// 
+/// Only the most recent jobs are searched, the history has no filename filter.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `filename` - File path as reported by `print_stats`
+/// 
+/// # Returns
+/// * Jobs of the file, newest first, in any status
+pub async fn get_previous_runs(host: &str, filename: &str) -> MoonrakerResult<Vec<HistoryJob>> {
+    let jobs = get_recent_jobs(host, JOB_HISTORY_LOOKUP_LIMIT).await?;
+    Ok(jobs.into_iter().filter(|job| job.filename == filename).collect())
+}
+
+/// Gets the job the printer is running, from the printer history
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * The newest job if it is still in progress
+pub async fn get_current_job(host: &str) -> MoonrakerResult<Option<HistoryJob>> {
+    let jobs = get_recent_jobs(host, 1).await?;
+    Ok(jobs.into_iter().next().filter(|job| job.status == "in_progress"))
+}
+
+/// Gets the most recent jobs of the printer history, newest first
+async fn get_recent_jobs(host: &str, limit: u32) -> MoonrakerResult<Vec<HistoryJob>> {
+    let endpoint = format!("server/history/list?limit={}&order=desc", limit);
+    let data = get_moonraker_endpoint(host, &endpoint).await?;
+    let list: MoonrakerJobList = serde_json::from_value(data)
+        .map_err(|e| MoonrakerError::Api(format!("Failed to parse job history: {}", e)))?;
+    Ok(list.result.jobs)
+}
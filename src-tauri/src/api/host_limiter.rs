@@ -0,0 +1,147 @@
+//! Per-host API request limiting
+//!
+//! The desktop UI, the Telegram bot and the background monitor may all
+//! talk to the same printer at once, which a Raspberry Pi Zero cannot keep
+//! up with. Every request to a printer first takes a permit of that
+//! printer's semaphore; requests beyond the configured number wait in line.
+//!
+//! A permit is only held for one short request. Uploads, which may take
+//! minutes, queue on a semaphore of their own, one upload per printer, and
+//! requests that merely wait for a long G-code script to finish (calibration)
+//! take no permit at all, so status requests are not held up by either.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use crate::models::config::{AppSettings, DEFAULT_HOST_REQUEST_CONCURRENCY};
+
+/// Marker for "limit not loaded from settings yet"
+const LIMIT_UNSET: u32 = u32::MAX;
+
+/// Simultaneous requests per host, loaded from settings on first use (0: unlimited)
+static HOST_REQUEST_CONCURRENCY: AtomicU32 = AtomicU32::new(LIMIT_UNSET);
+
+/// Semaphores keyed by host
+type HostSemaphores = StdMutex<Option<HashMap<String, Arc<Semaphore>>>>;
+
+/// Request semaphores keyed by host
+static HOST_SEMAPHORES: HostSemaphores = StdMutex::new(None);
+
+/// Upload semaphores keyed by host, one permit each
+static HOST_TRANSFER_SEMAPHORES: HostSemaphores = StdMutex::new(None);
+
+/// Waits until a request to a host may be sent
+///
+/// The returned permit must be kept until the response has been read.
+///
+/// # Arguments
+/// * `host` - Host the request is sent to
+///
+/// # Returns
+/// * Permit, or None when requests are not limited
+pub async fn acquire_host_permit(host: &str) -> Option<OwnedSemaphorePermit> {
+    match host_request_concurrency() {
+        0 => None,
+        limit => acquire(&HOST_SEMAPHORES, host, limit as usize).await,
+    }
+}
+
+/// Waits until a file may be uploaded to a host
+///
+/// Uploads wait for each other, not for the host's request permits.
+///
+/// # Arguments
+/// * `host` - Host the file is uploaded to
+///
+/// # Returns
+/// * Permit, or None when requests are not limited
+pub async fn acquire_transfer_permit(host: &str) -> Option<OwnedSemaphorePermit> {
+    match host_request_concurrency() {
+        0 => None,
+        _ => acquire(&HOST_TRANSFER_SEMAPHORES, host, 1).await,
+    }
+}
+
+async fn acquire(semaphores: &HostSemaphores, host: &str, permits: usize) -> Option<OwnedSemaphorePermit> {
+    let semaphore = {
+        let mut semaphores = semaphores.lock().unwrap_or_else(|e| e.into_inner());
+        semaphores
+            .get_or_insert_with(HashMap::new)
+            .entry(host.to_string())
+            .or_insert_with(|| Arc::new(Semaphore::new(permits)))
+            .clone()
+    };
+    semaphore.acquire_owned().await.ok()
+}
+
+/// Drops the semaphores of hosts that are no longer known and not in use
+///
+/// # Arguments
+/// * `is_known` - Whether a host is still in the host list
+pub fn prune_host_semaphores(is_known: impl Fn(&str) -> bool) {
+    for semaphores in [&HOST_SEMAPHORES, &HOST_TRANSFER_SEMAPHORES] {
+        let mut semaphores = semaphores.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(semaphores) = semaphores.as_mut() {
+            // Held and awaited permits keep a reference to their semaphore
+            semaphores.retain(|host, semaphore| is_known(host) || Arc::strong_count(semaphore) > 1);
+        }
+    }
+}
+
+/// Replaces the per-host limit, e.g. after settings were saved
+///
+/// Requests already running keep their permits of the previous limit.
+pub fn set_host_request_concurrency(limit: u32) {
+    HOST_REQUEST_CONCURRENCY.store(limit, Ordering::Relaxed);
+    for semaphores in [&HOST_SEMAPHORES, &HOST_TRANSFER_SEMAPHORES] {
+        *semaphores.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+/// Gets the current per-host limit
+fn host_request_concurrency() -> u32 {
+    let limit = HOST_REQUEST_CONCURRENCY.load(Ordering::Relaxed);
+    if limit != LIMIT_UNSET {
+        return limit;
+    }
+    let limit = AppSettings::load()
+        .map(|s| s.host_request_concurrency)
+        .unwrap_or(DEFAULT_HOST_REQUEST_CONCURRENCY);
+    HOST_REQUEST_CONCURRENCY.store(limit, Ordering::Relaxed);
+    limit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn requests_beyond_the_limit_wait_for_a_permit() {
+        set_host_request_concurrency(1);
+
+        let permit = acquire_host_permit("limiter-test-a").await;
+        assert!(permit.is_some());
+        let queued = tokio::time::timeout(Duration::from_millis(50), acquire_host_permit("limiter-test-a")).await;
+        assert!(queued.is_err());
+        assert!(acquire_host_permit("limiter-test-b").await.is_some());
+
+        drop(permit);
+        assert!(acquire_host_permit("limiter-test-a").await.is_some());
+
+        // Uploads do not take request permits
+        let upload = acquire_transfer_permit("limiter-test-a").await;
+        assert!(upload.is_some());
+        assert!(acquire_host_permit("limiter-test-a").await.is_some());
+
+        prune_host_semaphores(|host| host == "limiter-test-b");
+        let semaphores = HOST_SEMAPHORES.lock().unwrap();
+        let hosts = semaphores.as_ref().unwrap();
+        assert!(!hosts.contains_key("limiter-test-a") && hosts.contains_key("limiter-test-b"));
+        assert!(HOST_TRANSFER_SEMAPHORES.lock().unwrap().as_ref().unwrap().contains_key("limiter-test-a"));
+        drop(semaphores);
+        drop(upload);
+        set_host_request_concurrency(DEFAULT_HOST_REQUEST_CONCURRENCY);
+    }
+}
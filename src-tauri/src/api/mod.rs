@@ -7,8 +7,25 @@ pub mod client;
 pub mod moonraker;
 pub mod printer;
 pub mod print_info;
+pub mod history;
+pub mod files;
+pub mod calibration;
+pub mod config_files;
+pub mod power;
+pub mod host_limiter;
+pub mod capabilities;
+pub mod peripherals;
+pub mod websocket;
 
 pub use client::*;
 pub use moonraker::*;
 pub use printer::*;
 pub use print_info::*;
+pub use history::*;
+pub use files::*;
+pub use calibration::*;
+pub use config_files::*;
+pub use power::*;
+pub use host_limiter::*;
+pub use capabilities::*;
+pub use peripherals::*;
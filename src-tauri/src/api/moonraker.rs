@@ -3,14 +3,30 @@
 //! This module contains functions for communicating with Moonraker API endpoints,
 //! including server info, printer info, and status queries.
 
-use crate::error::MoonrakerResult;
+use crate::error::{MoonrakerError, MoonrakerResult};
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
 use crate::models::api::{
+    Announcement,
+    MoonrakerAnnouncements,
     MoonrakerServerInfo,
     MoonrakerPrinterInfo,
     MoonrakerPrinterObjects,
+    ObjectField,
+    ObjectValueKind,
     PrinterFlags,
 };
+use crate::models::host::{MachineIdentity, PrinterModel};
+use crate::api::calibration::download_file;
 use crate::api::client::get_moonraker_endpoint;
+use crate::models::config::ANNOUNCEMENT_CACHE_TTL_MS;
+
+/// Announcement list per host with the time it was fetched
+type AnnouncementCache = HashMap<String, (Instant, Vec<Announcement>)>;
+
+/// Last announcement list per host
+static ANNOUNCEMENT_CACHE: StdMutex<Option<AnnouncementCache>> = StdMutex::new(None);
 
 /// Checks if Moonraker API is available on the specified host
 /// 
@@ -29,7 +45,7 @@ pub async fn check_moonraker_api(host: &str) -> MoonrakerResult<MoonrakerServerI
             Ok(MoonrakerServerInfo {
                 result: crate::models::api::ServerInfoResult {
                     klippy_connected: false,
-                    klippy_state: "disconnected".to_string(),
+                    klippy_state: crate::models::api::KlippyState::Disconnected,
                     components: vec![],
                     failed_components: vec![],
                     registered_directories: vec![],
@@ -45,6 +61,39 @@ pub async fn check_moonraker_api(host: &str) -> MoonrakerResult<MoonrakerServerI
     }
 }
 
+/// Gets the announcements of a host, reusing the last list for a few minutes
+/// 
+/// Status checks run every few seconds while Moonraker refreshes its
+/// announcement feeds only once an hour.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Announcements, including dismissed ones
+pub async fn get_announcements(host: &str) -> MoonrakerResult<Vec<Announcement>> {
+    let ttl = Duration::from_millis(ANNOUNCEMENT_CACHE_TTL_MS);
+    if let Ok(cache) = ANNOUNCEMENT_CACHE.lock() {
+        if let Some((fetched_at, entries)) = cache.as_ref().and_then(|c| c.get(host)) {
+            if fetched_at.elapsed() < ttl {
+                return Ok(entries.clone());
+            }
+        }
+    }
+
+    let data = get_moonraker_endpoint(host, "server/announcements/list").await?;
+    let announcements: MoonrakerAnnouncements = serde_json::from_value(data)
+        .map_err(|e| MoonrakerError::Api(format!("Invalid announcement list: {}", e)))?;
+    let entries = announcements.result.entries;
+
+    if let Ok(mut cache) = ANNOUNCEMENT_CACHE.lock() {
+        cache
+            .get_or_insert_with(HashMap::new)
+            .insert(host.to_string(), (Instant::now(), entries.clone()));
+    }
+    Ok(entries)
+}
+
 /// Gets printer information from Moonraker API
 /// 
 /// # Arguments
@@ -76,6 +125,75 @@ pub async fn get_printer_info(host: &str) -> MoonrakerResult<MoonrakerPrinterInf
     }
 }
 
+/// Gets the error text Klipper reports for a printer in error state
+/// 
+/// Combines the Klippy state message of `printer/info` (shutdown and startup
+/// errors) with `print_stats.message` (errors that aborted the print).
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Error text, None when Klipper reports no error
+pub async fn get_printer_error_message(host: &str) -> MoonrakerResult<Option<String>> {
+    let info = get_printer_info(host).await?;
+    let mut messages = Vec::new();
+    if matches!(info.result.state.as_str(), "shutdown" | "error") {
+        messages.push(info.result.state_message.trim().to_string());
+    }
+
+    // print_stats is unavailable while Klippy is shut down
+    if let Ok(data) = get_moonraker_endpoint(host, "printer/objects/query?print_stats=message").await {
+        if let Some(message) = data.pointer("/result/status/print_stats/message").and_then(|m| m.as_str()) {
+            messages.push(message.trim().to_string());
+        }
+    }
+
+    messages.retain(|m| !m.is_empty());
+    messages.dedup();
+    Ok((!messages.is_empty()).then(|| messages.join("\n")))
+}
+
+/// Gets the printer model from the Klipper configuration
+/// 
+/// Kinematics and build volume come from `configfile.settings`; a model name
+/// is taken from a `# model: ...` comment in printer.cfg when there is one.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Printer model information
+pub async fn get_printer_model(host: &str) -> MoonrakerResult<PrinterModel> {
+    let data = get_moonraker_endpoint(host, "printer/objects/query?configfile=settings").await?;
+    let settings = data
+        .pointer("/result/status/configfile/settings")
+        .ok_or_else(|| MoonrakerError::Api("Config settings not available".to_string()))?;
+    let mut model = PrinterModel::from_config_settings(settings);
+
+    // printer.cfg is optional for the model, its absence is not an error
+    if let Ok(config) = download_file(host, "config", "printer.cfg").await {
+        model.name = PrinterModel::parse_name(&String::from_utf8_lossy(&config));
+    }
+
+    Ok(model)
+}
+
+/// Gets the instance UUID and network interfaces of the machine running Moonraker
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Identity with the interfaces' MAC and IPv4 addresses
+pub async fn get_machine_identity(host: &str) -> MoonrakerResult<MachineIdentity> {
+    let data = get_moonraker_endpoint(host, "machine/system_info").await?;
+    let system_info = data
+        .pointer("/result/system_info")
+        .ok_or_else(|| MoonrakerError::Api("System information not available".to_string()))?;
+    Ok(MachineIdentity::from_system_info(system_info))
+}
+
 /// Gets printer objects from Moonraker API
 /// 
 /// # Arguments
@@ -99,6 +217,19 @@ pub async fn get_printer_objects(host: &str) -> MoonrakerResult<MoonrakerPrinter
     }
 }
 
+/// Checks whether Klipper has configuration changes not yet saved with
+/// `SAVE_CONFIG`, e.g. results of a PID or bed mesh calibration
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+pub async fn get_save_config_pending(host: &str) -> MoonrakerResult<bool> {
+    let data = get_moonraker_endpoint(host, "printer/objects/query?configfile=save_config_pending").await?;
+    Ok(data
+        .pointer("/result/status/configfile/save_config_pending")
+        .and_then(|p| p.as_bool())
+        .unwrap_or(false))
+}
+
 /// Gets printer status flags from Moonraker API
 /// 
 /// # Arguments
@@ -183,3 +314,62 @@ pub async fn get_comprehensive_printer_status(host: &str) -> MoonrakerResult<ser
     
     Ok(status)
 }
+
+/// Queries arbitrary printer objects and flattens them into typed fields
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `objects` - Klipper object names (e.g. "heater_bed", "fan", "gcode_move")
+/// 
+/// # Returns
+/// * Fields of all returned objects, sorted by object and key
+pub async fn query_printer_objects(host: &str, objects: &[String]) -> MoonrakerResult<Vec<ObjectField>> {
+    let query: Vec<String> = objects
+        .iter()
+        .map(|o| o.trim())
+        .filter(|o| !o.is_empty())
+        .map(|o| url::form_urlencoded::byte_serialize(o.as_bytes()).collect())
+        .collect();
+    if query.is_empty() {
+        return Err(MoonrakerError::Api("No printer objects to query".to_string()));
+    }
+
+    let data = get_moonraker_endpoint(host, &format!("printer/objects/query?{}", query.join("&"))).await?;
+    let status = data
+        .get("result")
+        .and_then(|r| r.get("status"))
+        .and_then(|s| s.as_object())
+        .ok_or_else(|| MoonrakerError::Api("Invalid object query response".to_string()))?;
+
+    let mut fields = Vec::new();
+    for (object, value) in status {
+        flatten_object_value(object, "", value, &mut fields);
+    }
+    fields.sort_by(|a, b| a.object.cmp(&b.object).then_with(|| a.key.cmp(&b.key)));
+    Ok(fields)
+}
+
+/// Appends the leaf values of a JSON value as object fields
+fn flatten_object_value(object: &str, key: &str, value: &serde_json::Value, fields: &mut Vec<ObjectField>) {
+    let kind = match value {
+        serde_json::Value::Object(map) => {
+            for (child, child_value) in map {
+                let child_key = if key.is_empty() { child.clone() } else { format!("{}.{}", key, child) };
+                flatten_object_value(object, &child_key, child_value, fields);
+            }
+            return;
+        }
+        serde_json::Value::Number(_) => ObjectValueKind::Number,
+        serde_json::Value::String(_) => ObjectValueKind::Text,
+        serde_json::Value::Bool(_) => ObjectValueKind::Boolean,
+        serde_json::Value::Array(_) => ObjectValueKind::List,
+        serde_json::Value::Null => ObjectValueKind::Null,
+    };
+
+    fields.push(ObjectField {
+        object: object.to_string(),
+        key: key.to_string(),
+        kind,
+        value: value.clone(),
+    });
+}
@@ -0,0 +1,97 @@
+//! Peripheral enumeration functions
+//!
+//! This module lists the CAN nodes a host can see, to find the UUID of a new
+//! toolhead board or check that a board is still on the bus. Moonraker
+//! answers this itself since `machine/peripherals/canbus` was added; for
+//! older installations Klipper's `canbus_query.py` is run over SSH.
+
+use std::time::Duration;
+use crate::api::client::get_moonraker_endpoint;
+use crate::error::{MoonrakerError, MoonrakerResult};
+use crate::models::api::{CanDevice, CanQuerySource, CanbusDevices};
+use crate::network::ssh::run_ssh_command;
+
+/// CAN interface queried when none is given
+pub const DEFAULT_CAN_INTERFACE: &str = "can0";
+
+/// Seconds the SSH fallback may take, including the query's own wait for answers
+const CANBUS_SSH_TIMEOUT_SECONDS: u64 = 30;
+
+/// Lists the unassigned CAN nodes on an interface of a host
+///
+/// Nodes already used by Klipper do not answer the query.
+///
+/// # Arguments
+/// * `host` - Host IP address
+/// * `interface` - CAN interface, e.g. "can0"
+/// * `ssh_user` - User for the SSH fallback (None: Moonraker only)
+pub async fn get_canbus_devices(host: &str, interface: &str, ssh_user: Option<&str>) -> MoonrakerResult<CanbusDevices> {
+    if interface.is_empty() || !interface.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(MoonrakerError::Api(format!("Invalid CAN interface: {}", interface)));
+    }
+
+    let endpoint = format!("machine/peripherals/canbus?interface={}", interface);
+    let error = match get_moonraker_endpoint(host, &endpoint).await {
+        Ok(data) => {
+            let devices = data
+                .pointer("/result/can_uuids")
+                .cloned()
+                .map(serde_json::from_value::<Vec<CanDevice>>)
+                .transpose()
+                .map_err(|e| MoonrakerError::Api(format!("Invalid CAN query result: {}", e)))?
+                .unwrap_or_default();
+            return Ok(CanbusDevices { interface: interface.to_string(), source: CanQuerySource::Moonraker, devices });
+        }
+        Err(e) => e,
+    };
+    let Some(user) = ssh_user else {
+        return Err(error);
+    };
+
+    let devices = query_canbus_over_ssh(host, user, interface).await?;
+    Ok(CanbusDevices { interface: interface.to_string(), source: CanQuerySource::Ssh, devices })
+}
+
+/// Runs `canbus_query.py` on the host over SSH
+async fn query_canbus_over_ssh(host: &str, user: &str, interface: &str) -> MoonrakerResult<Vec<CanDevice>> {
+    let script = format!("~/klippy-env/bin/python ~/klipper/scripts/canbus_query.py {}", interface);
+    let output = run_ssh_command(host, user, &script, Duration::from_secs(CANBUS_SSH_TIMEOUT_SECONDS)).await?;
+    Ok(parse_canbus_query_output(&output))
+}
+
+/// Parses the output of `canbus_query.py`
+///
+/// # Arguments
+/// * `output` - Lines like "Found canbus_uuid=11aa22bb33cc, Application: Klipper"
+pub fn parse_canbus_query_output(output: &str) -> Vec<CanDevice> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix("Found canbus_uuid=")?;
+            let (uuid, application) = match rest.split_once(',') {
+                Some((uuid, details)) => (uuid, details.trim().strip_prefix("Application:").map(|a| a.trim().to_string())),
+                None => (rest, None),
+            };
+            Some(CanDevice { uuid: uuid.trim().to_string(), application })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canbus_query_output_is_parsed() {
+        let output = "Found canbus_uuid=11aa22bb33cc, Application: Klipper\n\
+                      Found canbus_uuid=44dd55ee66ff, Application: Katapult\n\
+                      Found canbus_uuid=778899aabbcc\n\
+                      Total 3 uuids found\n";
+
+        let devices = parse_canbus_query_output(output);
+        assert_eq!(devices.len(), 3);
+        assert_eq!(devices[0], CanDevice { uuid: "11aa22bb33cc".to_string(), application: Some("Klipper".to_string()) });
+        assert_eq!(devices[1].application.as_deref(), Some("Katapult"));
+        assert_eq!(devices[2].application, None);
+    }
+}
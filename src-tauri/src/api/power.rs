@@ -0,0 +1,35 @@
+//! Power device API functions
+//! 
+//! This module provides functions for Moonraker power devices (smart plugs,
+//! relays) used to switch printers off after a finished print.
+
+use crate::error::{MoonrakerError, MoonrakerResult};
+use crate::api::client::{get_moonraker_endpoint, post_moonraker_endpoint};
+use crate::models::api::{MoonrakerPowerDevices, PowerDevice};
+
+/// Gets the power devices configured in Moonraker
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Power devices (requires the Moonraker `power` component)
+pub async fn get_power_devices(host: &str) -> MoonrakerResult<Vec<PowerDevice>> {
+    let data = get_moonraker_endpoint(host, "machine/device_power/devices").await?;
+    let devices: MoonrakerPowerDevices = serde_json::from_value(data)
+        .map_err(|e| MoonrakerError::Api(format!("Failed to parse power devices: {}", e)))?;
+    Ok(devices.result.devices)
+}
+
+/// Switches a power device on or off
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `device` - Power device name
+/// * `on` - true to switch on, false to switch off
+pub async fn set_power_device(host: &str, device: &str, on: bool) -> MoonrakerResult<serde_json::Value> {
+    let device = url::form_urlencoded::byte_serialize(device.as_bytes()).collect::<String>();
+    let action = if on { "on" } else { "off" };
+    let endpoint = format!("machine/device_power/device?device={}&action={}", device, action);
+    post_moonraker_endpoint(host, &endpoint, None).await
+}
@@ -4,8 +4,14 @@
 //! print job information and progress data.
 
 use crate::error::{MoonrakerResult, MoonrakerError};
+use crate::models::config::{AppSettings, MAX_RESPONSE_BYTES};
+use crate::models::api::ToolsStatus;
 use crate::models::print_info::{PrinterObjectsQuery, PrintJobInfo, PrintProgress};
-use crate::api::client::create_client;
+use crate::api::client::{build_moonraker_url, create_client, read_body_limited, with_host_headers};
+use crate::api::capabilities::extra_tool_objects_query;
+use crate::api::host_limiter::acquire_host_permit;
+use crate::i18n::tf;
+use crate::network::ip_utils::split_host_port;
 
 /// Gets comprehensive print information from printer objects
 /// 
@@ -16,13 +22,20 @@ use crate::api::client::create_client;
 /// # Returns
 /// * PrintJobInfo with current print status and progress
 pub async fn get_print_info(host: &str, port: Option<u16>) -> MoonrakerResult<Option<PrintJobInfo>> {
-    let port = port.unwrap_or(7125);
+    // Further extruders and the toolchanger are only queried where they exist.
+    // Looked up before taking the permit: fetching the object list takes one too.
+    let endpoint = format!(
+        "printer/objects/query?print_stats&virtual_sdcard&toolhead&extruder{}",
+        extra_tool_objects_query(host).await
+    );
+    let _permit = acquire_host_permit(host).await;
     let client = create_client().await?;
+    let url = match port {
+        Some(port) => format!("http://{}:{}/{}", split_host_port(host).0, port, endpoint),
+        None => build_moonraker_url(host, &endpoint),
+    };
     
-    let url = format!("http://{}:{}/printer/objects/query?print_stats&virtual_sdcard&toolhead&extruder", host, port);
-    
-    let response = client
-        .get(&url)
+    let response = with_host_headers(client.get(&url), host)
         .send()
         .await
         .map_err(|e| format!("Failed to query printer objects: {}", e))?;
@@ -31,10 +44,12 @@ pub async fn get_print_info(host: &str, port: Option<u16>) -> MoonrakerResult<Op
         return Ok(None);
     }
     
-    let response_text = response.text().await
-        .map_err(|e| format!("Failed to get response text: {}", e))?;
+    let body = read_body_limited(response, MAX_RESPONSE_BYTES).await?;
     
-    let data: PrinterObjectsQuery = serde_json::from_str(&response_text)
+    let raw: serde_json::Value = serde_json::from_slice(&body)
+        .map_err(|e| MoonrakerError::Api(format!("Failed to parse printer objects: {}", e)))?;
+    let tools = raw.pointer("/result/status").map(ToolsStatus::from_status).unwrap_or_default();
+    let data: PrinterObjectsQuery = serde_json::from_value(raw)
         .map_err(|e| MoonrakerError::Api(format!("Failed to parse printer objects: {}", e)))?;
     
     // Extract print information
@@ -67,14 +82,26 @@ pub async fn get_print_info(host: &str, port: Option<u16>) -> MoonrakerResult<Op
             total_height: None, // Not available in basic API
         };
         
+        let remaining_seconds = progress_info.remaining_seconds();
+
+        // Estimate cost from filament used and time printed so far
+        let filament_used = stats.filament_used;
+        let estimated_cost = AppSettings::load()
+            .ok()
+            .and_then(|s| s.costs.estimate(host, filament_used.unwrap_or(0.0), print_duration));
+        
         // Create print job info
         let print_job = PrintJobInfo {
             filename,
             total_size: sdcard.file_size, // Available in virtual_sdcard
             progress: progress_info,
             start_time: 0.0, // Not available in this API
-            estimated_completion: None,
+            estimated_completion: remaining_seconds
+                .map(|remaining| chrono::Utc::now().timestamp() as f64 + remaining),
             status: stats.state.clone().unwrap_or_else(|| "printing".to_string()),
+            filament_used,
+            estimated_cost,
+            tools,
         };
         
         Ok(Some(print_job))
@@ -107,17 +134,38 @@ pub async fn get_print_progress(host: &str, port: Option<u16>) -> MoonrakerResul
 /// * `seconds` - Duration in seconds
 /// 
 /// # Returns
-/// * Formatted duration string (e.g., "2h 15m 30s")
+/// * Formatted duration string in the configured language (e.g., "2h 15m 30s",
+///   or "1d 3h 20m" for durations of a day and longer)
 pub fn format_duration(seconds: f64) -> String {
+    let seconds = seconds.max(0.0);
+    let days = (seconds / 86400.0) as u32;
     let hours = (seconds / 3600.0) as u32;
     let minutes = ((seconds % 3600.0) / 60.0) as u32;
     let secs = (seconds % 60.0) as u32;
     
-    if hours > 0 {
-        format!("{}h {}m {}s", hours, minutes, secs)
+    if days > 0 {
+        tf("duration.dhm", &[("d", &days), ("h", &(hours % 24)), ("m", &minutes)])
+    } else if hours > 0 {
+        tf("duration.hms", &[("h", &hours), ("m", &minutes), ("s", &secs)])
     } else if minutes > 0 {
-        format!("{}m {}s", minutes, secs)
+        tf("duration.ms", &[("m", &minutes), ("s", &secs)])
+    } else {
+        tf("duration.s", &[("s", &secs)])
+    }
+}
+
+/// Formats an estimate in whole minutes, e.g. "2h 10m"
+/// 
+/// # Arguments
+/// * `seconds` - Duration in seconds
+pub fn format_duration_approx(seconds: f64) -> String {
+    let minutes = (seconds.max(0.0) / 60.0).round().max(1.0) as u32;
+    let (days, hours) = (minutes / 1440, minutes / 60);
+    if days > 0 {
+        tf("duration.dhm", &[("d", &days), ("h", &(hours % 24)), ("m", &(minutes % 60))])
+    } else if hours > 0 {
+        tf("duration.hm", &[("h", &hours), ("m", &(minutes % 60))])
     } else {
-        format!("{}s", secs)
+        tf("duration.m", &[("m", &minutes)])
     }
 }
@@ -1,10 +1,39 @@
 //! Printer control functions
 //! 
 //! This module contains functions for controlling 3D printers through
-//! the Moonraker API, including print operations and emergency controls.
+//! the Moonraker API, including print operations, emergency controls,
+//! fans, LEDs, basic motion, Z offset babystepping, heater temperatures,
+//! additional sensors and telemetry samples.
 
+use std::time::{Duration, Instant};
 use crate::error::{MoonrakerError, MoonrakerResult};
-use crate::api::client::post_moonraker_endpoint;
+use crate::api::capabilities::{extra_tool_objects_query, get_printer_object_list, invalidate_printer_objects};
+use crate::api::client::{get_moonraker_endpoint, post_moonraker_endpoint};
+use crate::api::moonraker::check_moonraker_api;
+use crate::network::status_cache::invalidate_host_status;
+use crate::models::api::{
+    is_heater_object, is_mcu_object, AuxiliarySensor, FilamentSensor, HeaterReading, HeaterTemperatures, KlippyState, McuStats, PrinterState, ToolsStatus,
+    FILAMENT_SENSOR_PREFIXES, LED_OBJECT_PREFIXES, SENSOR_OBJECT_PREFIXES,
+};
+use crate::models::telemetry::TelemetrySample;
+
+/// Largest allowed single jog move in mm
+const MAX_JOG_DISTANCE: f64 = 100.0;
+
+/// Largest allowed jog feedrate in mm/min
+const MAX_JOG_FEEDRATE: f64 = 30000.0;
+
+/// Largest allowed single Z offset adjustment in mm
+const MAX_Z_ADJUST: f64 = 0.5;
+
+/// Seconds to wait for Klippy to become ready after a firmware restart
+pub const FIRMWARE_RESTART_TIMEOUT_SECONDS: u64 = 60;
+
+/// Interval between Klippy state polls while waiting for a restart
+const READY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Time after which a "ready" state is trusted even if the restart was not observed
+const RESTART_SETTLE_TIME: Duration = Duration::from_secs(5);
 
 /// Available printer control actions
 #[derive(Debug, Clone, Copy)]
@@ -56,7 +85,10 @@ impl PrinterAction {
 /// * API response as JSON
 pub async fn control_printer(host: &str, action: PrinterAction) -> MoonrakerResult<serde_json::Value> {
     let endpoint = action.to_endpoint();
-    post_moonraker_endpoint(host, endpoint, None).await
+    let result = post_moonraker_endpoint(host, endpoint, None).await;
+    // The printer state is about to change, so the cached one is outdated
+    invalidate_host_status(host);
+    result
 }
 
 /// Controls the printer using a string action
@@ -71,3 +103,465 @@ pub async fn control_printer_with_string(host: &str, action: &str) -> MoonrakerR
     let printer_action = PrinterAction::from_string(action)?;
     control_printer(host, printer_action).await
 }
+
+/// Runs a G-code script on the printer
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `script` - G-code, multiple commands separated by newlines
+/// 
+/// # Returns
+/// * API response as JSON
+pub async fn run_gcode(host: &str, script: &str) -> MoonrakerResult<serde_json::Value> {
+    let body = serde_json::json!({ "script": script });
+    post_moonraker_endpoint(host, "printer/gcode/script", Some(body)).await
+}
+
+/// Checks that a Klipper config name can be used as a G-code parameter
+fn validate_object_name(name: &str) -> MoonrakerResult<()> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(MoonrakerError::Api(format!("Invalid object name: {}", name)));
+    }
+    Ok(())
+}
+
+/// Sets the speed of a fan
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `fan` - `[fan_generic]` name (e.g. a chamber fan), or None for the part cooling fan
+/// * `speed` - Speed in percent (0 - 100)
+/// 
+/// # Returns
+/// * API response as JSON
+pub async fn set_fan_speed(host: &str, fan: Option<&str>, speed: f64) -> MoonrakerResult<serde_json::Value> {
+    if !(0.0..=100.0).contains(&speed) {
+        return Err(MoonrakerError::Api("Fan speed must be between 0 and 100".to_string()));
+    }
+
+    let script = match fan {
+        Some(name) => {
+            validate_object_name(name)?;
+            format!("SET_FAN_SPEED FAN={} SPEED={:.2}", name, speed / 100.0)
+        }
+        None if speed == 0.0 => "M107".to_string(),
+        None => format!("M106 S{}", (speed * 255.0 / 100.0).round() as u8),
+    };
+    run_gcode(host, &script).await
+}
+
+/// Sets the color of a Klipper-configured LED
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `led` - LED config name (without the "neopixel "/"led " prefix)
+/// * `red`, `green`, `blue` - Color channels (0.0 - 1.0)
+/// * `white` - White channel for RGBW LEDs
+/// 
+/// # Returns
+/// * API response as JSON
+pub async fn set_led(
+    host: &str,
+    led: &str,
+    red: f64,
+    green: f64,
+    blue: f64,
+    white: Option<f64>,
+) -> MoonrakerResult<serde_json::Value> {
+    validate_object_name(led)?;
+    let channels = [Some(red), Some(green), Some(blue), white];
+    if channels.iter().flatten().any(|v| !(0.0..=1.0).contains(v)) {
+        return Err(MoonrakerError::Api("LED values must be between 0 and 1".to_string()));
+    }
+
+    let mut script = format!("SET_LED LED={} RED={:.3} GREEN={:.3} BLUE={:.3}", led, red, green, blue);
+    if let Some(white) = white {
+        script.push_str(&format!(" WHITE={:.3}", white));
+    }
+    run_gcode(host, &script).await
+}
+
+/// Finds the first LED configured in Klipper
+/// 
+/// # Returns
+/// * Object name (e.g. "neopixel caselight") and LED name ("caselight")
+pub async fn find_led(host: &str) -> MoonrakerResult<Option<(String, String)>> {
+    let list = get_printer_object_list(host).await?;
+    Ok(list.capabilities.led.as_ref().and_then(|object| {
+        LED_OBJECT_PREFIXES.iter().find_map(|prefix| {
+            object.strip_prefix(prefix).map(|name| (object.clone(), name.to_string()))
+        })
+    }))
+}
+
+/// Switches the first configured LED fully on or off
+/// 
+/// # Returns
+/// * Whether the LED is on afterwards
+pub async fn toggle_led(host: &str) -> MoonrakerResult<bool> {
+    let (object, name) = find_led(host)
+        .await?
+        .ok_or_else(|| MoonrakerError::Api("No LEDs configured".to_string()))?;
+
+    let query = url::form_urlencoded::byte_serialize(object.as_bytes()).collect::<String>();
+    let data = get_moonraker_endpoint(host, &format!("printer/objects/query?{}", query)).await?;
+    let is_on = data
+        .pointer("/result/status")
+        .and_then(|s| s.get(&object))
+        .and_then(|o| o.get("color_data"))
+        .and_then(|c| c.as_array())
+        .map(|leds| {
+            leds.iter()
+                .filter_map(|led| led.as_array())
+                .flatten()
+                .any(|v| v.as_f64().unwrap_or(0.0) > 0.0)
+        })
+        .unwrap_or(false);
+
+    let value = if is_on { 0.0 } else { 1.0 };
+    set_led(host, &name, value, value, value, None).await?;
+    Ok(!is_on)
+}
+
+/// Switches the part cooling fan between off and full speed
+/// 
+/// # Returns
+/// * Whether the fan is on afterwards
+pub async fn toggle_part_fan(host: &str) -> MoonrakerResult<bool> {
+    let data = get_moonraker_endpoint(host, "printer/objects/query?fan").await?;
+    let is_on = data
+        .pointer("/result/status/fan/speed")
+        .and_then(|s| s.as_f64())
+        .ok_or_else(|| MoonrakerError::Api("No part cooling fan configured".to_string()))?
+        > 0.0;
+
+    set_fan_speed(host, None, if is_on { 0.0 } else { 100.0 }).await?;
+    Ok(!is_on)
+}
+
+/// Gets the current hotend and bed temperatures
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Temperatures of the primary extruder, the heated bed and every tool
+pub async fn get_heater_temperatures(host: &str) -> MoonrakerResult<HeaterTemperatures> {
+    let query = format!("printer/objects/query?extruder&heater_bed&toolhead=extruder{}", extra_tool_objects_query(host).await);
+    let data = get_moonraker_endpoint(host, &query).await?;
+    let extruder = data
+        .pointer("/result/status/extruder/temperature")
+        .and_then(|t| t.as_f64())
+        .ok_or_else(|| MoonrakerError::Api("Extruder temperature not available".to_string()))?;
+    let bed = data.pointer("/result/status/heater_bed/temperature").and_then(|t| t.as_f64());
+    let tools = data.pointer("/result/status").map(ToolsStatus::from_status).unwrap_or_default();
+    Ok(HeaterTemperatures { extruder, bed, tools })
+}
+
+/// Gets temperature, target and PWM power of every heater of a printer
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Readings sorted by heater name
+pub async fn get_heater_readings(host: &str) -> MoonrakerResult<Vec<HeaterReading>> {
+    let list = get_printer_object_list(host).await?;
+    let objects: Vec<String> = list
+        .objects
+        .iter()
+        .filter(|o| is_heater_object(o))
+        .map(|o| format!("{}=temperature,target,power", url::form_urlencoded::byte_serialize(o.as_bytes()).collect::<String>()))
+        .collect();
+    if objects.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let data = get_moonraker_endpoint(host, &format!("printer/objects/query?{}", objects.join("&"))).await?;
+    let status = data.pointer("/result/status").cloned().unwrap_or_default();
+    Ok(HeaterReading::from_status(&status))
+}
+
+/// Gets the connection statistics of every micro-controller of a printer
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Statistics sorted by MCU name
+pub async fn get_mcu_stats(host: &str) -> MoonrakerResult<Vec<McuStats>> {
+    let list = get_printer_object_list(host).await?;
+    let objects: Vec<String> = list
+        .objects
+        .iter()
+        .filter(|o| is_mcu_object(o))
+        .map(|o| url::form_urlencoded::byte_serialize(o.as_bytes()).collect::<String>())
+        .collect();
+    if objects.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let data = get_moonraker_endpoint(host, &format!("printer/objects/query?{}", objects.join("&"))).await?;
+    let status = data.pointer("/result/status").cloned().unwrap_or_default();
+    Ok(McuStats::from_status(&status))
+}
+
+/// Gets a telemetry sample of a printer: heater temperatures and targets,
+/// and the progress of the active job
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `state` - Printer state from the latest status check
+pub async fn get_telemetry_sample(host: &str, state: PrinterState) -> MoonrakerResult<TelemetrySample> {
+    let query = format!(
+        "printer/objects/query?extruder&heater_bed&print_stats&virtual_sdcard&toolhead=extruder{}",
+        extra_tool_objects_query(host).await
+    );
+    let data = get_moonraker_endpoint(host, &query).await?;
+    let status = data.pointer("/result/status").cloned().unwrap_or_default();
+    Ok(TelemetrySample::from_status(host, state, &status))
+}
+
+/// Gets the readings of the printer's additional temperature and humidity sensors
+/// 
+/// Covers `temperature_sensor`, `temperature_fan` and the humidity sensors
+/// Klipper supports (BME280, HTU21D, AHT10, SHT3x), not the heaters.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Sensors sorted by name (empty when none is configured)
+pub async fn get_sensors(host: &str) -> MoonrakerResult<Vec<AuxiliarySensor>> {
+    let list = get_printer_object_list(host).await?;
+    if !list.capabilities.sensors {
+        return Ok(Vec::new());
+    }
+    let objects: Vec<String> = list
+        .objects
+        .iter()
+        .filter(|o| SENSOR_OBJECT_PREFIXES.iter().any(|prefix| o.starts_with(prefix)))
+        .map(|o| url::form_urlencoded::byte_serialize(o.as_bytes()).collect::<String>())
+        .collect();
+    if objects.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let data = get_moonraker_endpoint(host, &format!("printer/objects/query?{}", objects.join("&"))).await?;
+    let status = data.pointer("/result/status").cloned().unwrap_or_default();
+    Ok(AuxiliarySensor::from_status(&status))
+}
+
+/// Gets the filament runout sensors of a printer
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Sensors sorted by name, empty if the printer has none
+pub async fn get_filament_sensors(host: &str) -> MoonrakerResult<Vec<FilamentSensor>> {
+    let list = get_printer_object_list(host).await?;
+    let objects: Vec<String> = list
+        .objects
+        .iter()
+        .filter(|o| FILAMENT_SENSOR_PREFIXES.iter().any(|prefix| o.starts_with(prefix)))
+        .map(|o| url::form_urlencoded::byte_serialize(o.as_bytes()).collect::<String>())
+        .collect();
+    if objects.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let data = get_moonraker_endpoint(host, &format!("printer/objects/query?{}", objects.join("&"))).await?;
+    let status = data.pointer("/result/status").cloned().unwrap_or_default();
+    Ok(FilamentSensor::from_status(&status))
+}
+
+/// Gets the file of the current or last print job
+/// 
+/// `print_stats` keeps the file after a job ended, until the next one starts.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * None when no job ran since Klipper started
+pub async fn get_last_print_filename(host: &str) -> MoonrakerResult<Option<String>> {
+    let data = get_moonraker_endpoint(host, "printer/objects/query?print_stats=filename").await?;
+    Ok(data
+        .pointer("/result/status/print_stats/filename")
+        .and_then(|f| f.as_str())
+        .filter(|f| !f.is_empty())
+        .map(str::to_string))
+}
+
+/// Fails if the printer is running or paused in a print
+pub async fn ensure_not_printing(host: &str) -> MoonrakerResult<()> {
+    let data = get_moonraker_endpoint(host, "printer/objects/query?print_stats").await?;
+    let state = data
+        .pointer("/result/status/print_stats/state")
+        .and_then(|s| s.as_str())
+        .unwrap_or("");
+    if state == "printing" || state == "paused" {
+        return Err(MoonrakerError::Api("Motion commands are not allowed while a print is running".to_string()));
+    }
+    Ok(())
+}
+
+/// Homes the given axes
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `axes` - Axes to home, e.g. "xy" (all axes when empty)
+/// 
+/// # Returns
+/// * API response as JSON
+pub async fn home_axes(host: &str, axes: &str) -> MoonrakerResult<serde_json::Value> {
+    let mut selected: Vec<char> = axes.to_uppercase().chars().filter(|c| !c.is_whitespace() && *c != ',').collect();
+    selected.dedup();
+    if let Some(invalid) = selected.iter().find(|c| !matches!(c, 'X' | 'Y' | 'Z')) {
+        return Err(MoonrakerError::Api(format!("Invalid axis: {}", invalid)));
+    }
+
+    ensure_not_printing(host).await?;
+
+    let mut script = "G28".to_string();
+    for axis in selected {
+        script.push(' ');
+        script.push(axis);
+    }
+    run_gcode(host, &script).await
+}
+
+/// Moves one axis relative to its current position
+/// 
+/// The G-code state is saved and restored, so the move does not change
+/// absolute/relative positioning of the printer.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `axis` - "x", "y", "z" or "e" (extruder)
+/// * `distance` - Distance in mm (negative moves towards the minimum)
+/// * `feedrate` - Speed in mm/min
+/// 
+/// # Returns
+/// * API response as JSON
+pub async fn jog(host: &str, axis: &str, distance: f64, feedrate: f64) -> MoonrakerResult<serde_json::Value> {
+    let axis = axis.trim().to_uppercase();
+    if !matches!(axis.as_str(), "X" | "Y" | "Z" | "E") {
+        return Err(MoonrakerError::Api(format!("Invalid axis: {}", axis)));
+    }
+    if distance == 0.0 || distance.abs() > MAX_JOG_DISTANCE || !distance.is_finite() {
+        return Err(MoonrakerError::Api(format!("Jog distance must be between 0 and {} mm", MAX_JOG_DISTANCE)));
+    }
+    if feedrate <= 0.0 || feedrate > MAX_JOG_FEEDRATE || !feedrate.is_finite() {
+        return Err(MoonrakerError::Api(format!("Feedrate must be between 0 and {} mm/min", MAX_JOG_FEEDRATE)));
+    }
+
+    ensure_not_printing(host).await?;
+
+    let script = format!(
+        "SAVE_GCODE_STATE NAME=mhs_jog\nG91\n{} {}{:.3} F{:.0}\nRESTORE_GCODE_STATE NAME=mhs_jog",
+        if axis == "E" { "G1" } else { "G0" },
+        axis,
+        distance,
+        feedrate
+    );
+    run_gcode(host, &script).await
+}
+
+/// Gets the current G-code Z offset (babystep) in mm
+pub async fn get_z_offset(host: &str) -> MoonrakerResult<f64> {
+    let data = get_moonraker_endpoint(host, "printer/objects/query?gcode_move=homing_origin").await?;
+    data.pointer("/result/status/gcode_move/homing_origin/2")
+        .and_then(|z| z.as_f64())
+        .ok_or_else(|| MoonrakerError::Api("Z offset not available".to_string()))
+}
+
+/// Adjusts the Z offset by a small amount, also during a print
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `delta` - Change in mm (positive raises the nozzle)
+/// 
+/// # Returns
+/// * Z offset after the adjustment
+pub async fn adjust_z_offset(host: &str, delta: f64) -> MoonrakerResult<f64> {
+    if delta == 0.0 || delta.abs() > MAX_Z_ADJUST || !delta.is_finite() {
+        return Err(MoonrakerError::Api(format!("Z adjustment must be between 0 and {} mm", MAX_Z_ADJUST)));
+    }
+
+    run_gcode(host, &format!("SET_GCODE_OFFSET Z_ADJUST={:.3} MOVE=1", delta)).await?;
+    get_z_offset(host).await
+}
+
+/// Waits until Klippy has restarted and reports a final state
+/// 
+/// Moonraker may still report the old "ready" state right after a restart
+/// request, so "ready" is only accepted once the restart was seen (Klippy
+/// disconnected or starting) or after a short settle time.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `timeout` - Maximum time to wait
+/// 
+/// # Returns
+/// * Final Klippy state ("ready", "error" or "shutdown")
+pub async fn wait_for_klippy_ready(host: &str, timeout: Duration) -> MoonrakerResult<KlippyState> {
+    let started = Instant::now();
+    let mut saw_restart = false;
+
+    while started.elapsed() < timeout {
+        tokio::time::sleep(READY_POLL_INTERVAL).await;
+
+        let state = match check_moonraker_api(host).await {
+            Ok(info) if info.result.klippy_connected => info.result.klippy_state,
+            _ => {
+                saw_restart = true;
+                continue;
+            }
+        };
+
+        let settled = saw_restart || started.elapsed() >= RESTART_SETTLE_TIME;
+        match state {
+            KlippyState::Ready | KlippyState::Error | KlippyState::Shutdown if settled => return Ok(state),
+            KlippyState::Ready => {}
+            _ => saw_restart = true,
+        }
+    }
+
+    Err(MoonrakerError::Timeout(timeout))
+}
+
+/// Saves pending configuration changes with `SAVE_CONFIG`, e.g. calibration
+/// results, and waits until Klippy has restarted with them
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `timeout` - Maximum time to wait for Klippy
+/// 
+/// # Returns
+/// * Final Klippy state ("ready", "error" or "shutdown")
+pub async fn save_config_and_wait(host: &str, timeout: Duration) -> MoonrakerResult<KlippyState> {
+    ensure_not_printing(host).await?;
+    run_gcode(host, "SAVE_CONFIG").await?;
+    let state = wait_for_klippy_ready(host, timeout).await;
+    // The cached status still reports the changes as pending
+    invalidate_host_status(host);
+    invalidate_printer_objects(host);
+    state
+}
+
+/// Restarts the firmware and waits until Klippy is ready again
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `timeout` - Maximum time to wait for Klippy
+/// 
+/// # Returns
+/// * Final Klippy state ("ready", "error" or "shutdown")
+pub async fn firmware_restart_and_wait(host: &str, timeout: Duration) -> MoonrakerResult<KlippyState> {
+    post_moonraker_endpoint(host, "printer/firmware_restart", None).await?;
+    let state = wait_for_klippy_ready(host, timeout).await;
+    invalidate_printer_objects(host);
+    state
+}
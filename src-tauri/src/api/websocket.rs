@@ -0,0 +1,166 @@
+//! Moonraker websocket client
+//!
+//! Long-running G-code scripts such as resonance calibrations are sent over
+//! Moonraker's JSON-RPC websocket instead of a single HTTP request: the
+//! console output Klipper writes while the script runs arrives as
+//! `notify_gcode_response` notifications on the same connection, and the
+//! answer to the request marks the end of the script.
+
+use futures::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use crate::api::client::{build_moonraker_url, host_connection};
+use crate::error::{MoonrakerError, MoonrakerResult};
+
+/// Open websocket connection to a Moonraker instance
+pub struct MoonrakerSocket {
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    next_id: u64,
+}
+
+impl MoonrakerSocket {
+    /// Connects to the websocket of a host
+    ///
+    /// The host's base URL and custom headers apply as for HTTP requests.
+    ///
+    /// # Arguments
+    /// * `host` - Host IP address, with ":port" for additional instances
+    pub async fn connect(host: &str) -> MoonrakerResult<Self> {
+        let url = websocket_url(&build_moonraker_url(host, "websocket"))?;
+        let mut request = url
+            .as_str()
+            .into_client_request()
+            .map_err(|e| MoonrakerError::WebSocket(e.to_string()))?;
+        if let Some(connection) = host_connection(host) {
+            for (name, value) in &connection.headers {
+                let (Ok(name), Ok(value)) = (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) else {
+                    continue;
+                };
+                request.headers_mut().insert(name, value);
+            }
+        }
+        let (stream, _) = connect_async(request).await.map_err(|e| MoonrakerError::WebSocket(e.to_string()))?;
+        Ok(Self { stream, next_id: 1 })
+    }
+
+    /// Runs a G-code script and collects the console output until it completes
+    ///
+    /// # Arguments
+    /// * `script` - G-code script, one command per line
+    ///
+    /// # Returns
+    /// * Console lines Klipper wrote while the script ran, without the "//" prefix
+    pub async fn run_gcode_script(&mut self, script: &str) -> MoonrakerResult<Vec<String>> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "printer.gcode.script",
+            "params": { "script": script },
+            "id": id,
+        });
+        self.stream
+            .send(Message::text(request.to_string()))
+            .await
+            .map_err(|e| MoonrakerError::WebSocket(e.to_string()))?;
+
+        let mut lines = Vec::new();
+        while let Some(message) = self.stream.next().await {
+            let text = match message.map_err(|e| MoonrakerError::WebSocket(e.to_string()))? {
+                Message::Text(text) => text,
+                Message::Close(_) => break,
+                _ => continue,
+            };
+            let Ok(message) = serde_json::from_str::<serde_json::Value>(&text) else {
+                continue;
+            };
+            match read_message(&message, id) {
+                SocketMessage::GcodeResponse(response) => lines.extend(console_lines(response)),
+                SocketMessage::Result => return Ok(lines),
+                SocketMessage::Error(error) => return Err(MoonrakerError::Api(error)),
+                SocketMessage::Other => {}
+            }
+        }
+        Err(MoonrakerError::WebSocket("Connection closed before the script completed".to_string()))
+    }
+}
+
+/// Message received while waiting for a request
+#[derive(Debug, PartialEq)]
+enum SocketMessage<'a> {
+    /// Console output of Klipper
+    GcodeResponse(&'a str),
+    /// Successful answer to the request
+    Result,
+    /// Failed request, with Klipper's error message
+    Error(String),
+    /// Other notifications and answers
+    Other,
+}
+
+/// Classifies a JSON-RPC message received while waiting for request `id`
+fn read_message(message: &serde_json::Value, id: u64) -> SocketMessage<'_> {
+    if message.get("method").and_then(|m| m.as_str()) == Some("notify_gcode_response") {
+        return match message.pointer("/params/0").and_then(|p| p.as_str()) {
+            Some(response) => SocketMessage::GcodeResponse(response),
+            None => SocketMessage::Other,
+        };
+    }
+    if message.get("id").and_then(|i| i.as_u64()) != Some(id) {
+        return SocketMessage::Other;
+    }
+    match message.get("error") {
+        Some(error) => SocketMessage::Error(
+            error.get("message").and_then(|m| m.as_str()).unwrap_or("Unknown error").to_string(),
+        ),
+        None => SocketMessage::Result,
+    }
+}
+
+/// Splits a G-code response into console lines without the "//" prefix
+fn console_lines(response: &str) -> impl Iterator<Item = String> + '_ {
+    response.lines().map(|l| l.trim_start_matches("//").trim().to_string()).filter(|l| !l.is_empty())
+}
+
+/// Turns the HTTP URL of the websocket endpoint into a ws:// or wss:// URL
+fn websocket_url(http_url: &str) -> MoonrakerResult<url::Url> {
+    let mut url = url::Url::parse(http_url).map_err(|e| MoonrakerError::Api(format!("Invalid URL: {}", e)))?;
+    let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+    url.set_scheme(scheme).map_err(|_| MoonrakerError::Api("Invalid URL".to_string()))?;
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcode_responses_and_the_answer_are_told_apart() {
+        let response = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "notify_gcode_response",
+            "params": ["// Recommended shaper is mzv @ 40.0 Hz"],
+        });
+        assert_eq!(read_message(&response, 3), SocketMessage::GcodeResponse("// Recommended shaper is mzv @ 40.0 Hz"));
+        assert_eq!(console_lines("// Recommended shaper is mzv @ 40.0 Hz\n//").collect::<Vec<_>>(), ["Recommended shaper is mzv @ 40.0 Hz"]);
+
+        assert_eq!(read_message(&serde_json::json!({ "jsonrpc": "2.0", "result": "ok", "id": 3 }), 3), SocketMessage::Result);
+        assert_eq!(read_message(&serde_json::json!({ "jsonrpc": "2.0", "result": "ok", "id": 2 }), 3), SocketMessage::Other);
+        assert_eq!(
+            read_message(&serde_json::json!({ "jsonrpc": "2.0", "error": { "code": 400, "message": "Must home axis first" }, "id": 3 }), 3),
+            SocketMessage::Error("Must home axis first".to_string())
+        );
+    }
+
+    #[test]
+    fn websocket_urls_follow_the_http_scheme() {
+        assert_eq!(websocket_url("http://192.168.1.50:7125/websocket").unwrap().as_str(), "ws://192.168.1.50:7125/websocket");
+        assert_eq!(
+            websocket_url("https://proxy.example.com/printers/voron/websocket").unwrap().as_str(),
+            "wss://proxy.example.com/printers/voron/websocket"
+        );
+    }
+}
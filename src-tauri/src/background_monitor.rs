@@ -1,25 +1,202 @@
 //! Background monitoring functionality
 //! 
-//! This module provides functions for monitoring printers in the background
+//! This module provides functions for monitoring printers in the background,
+//! keeps rolling per-host health metrics for every status check, runs the
+//! optional snapshot-based print failure detector, warns about prints running
+//! slower than their previous runs, archives snapshots of running prints,
+//! reports Klippy shutdowns with their reason, announces finished jobs with
+//! their estimated cost and a GIF of the print, tells when a finished part has
+//! cooled down, runs scheduled pause/cancel rules, powers printers off after
+//! finished prints, sends print-hour based maintenance reminders, records
+//! telemetry samples, alerts about heaters at full power that do not heat up,
+//! heaters left on while idle and MCU links that keep retransmitting, and
+//! runs the user's automation rules. Alerts the user snoozed or acknowledged
+//! (see [`crate::notifications::snooze`]) are still tracked but not sent, and
+//! critical alerts nobody acknowledged go to secondary contacts.
+//! 
+//! The monitoring cycle itself only depends on a [`MonitorContext`], so it is
+//! shared between the desktop application and the headless daemon.
 
+use std::collections::{HashMap, HashSet};
 use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
-use tauri::AppHandle;
-use tokio::time::{sleep, Duration};
+use futures::StreamExt;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Duration, Instant};
+
+use crate::api::client::create_client;
+use crate::config_backup::{backup_host_configs, is_backup_due};
+use crate::config_backup::github::push_backup_to_github;
+use crate::api::history::{get_previous_runs, get_print_hours};
+use crate::api::print_info::{format_duration, get_print_info};
+use crate::api::host_limiter::prune_host_semaphores;
+use crate::api::power::set_power_device;
+use crate::api::printer::{control_printer_with_string, get_heater_readings, get_heater_temperatures, get_mcu_stats, get_sensors, get_telemetry_sample, run_gcode};
+use crate::commands::telegram::TelegramBotState;
+use crate::diagnostics::remediation;
+use crate::job_comparison::{JobComparator, PrintSlowdown};
+use crate::models::{
+    HostHealth, HostHealthStats, HostInfo, HostRelink, HostStatusResponse, HostWarning, HostWarningKind, KlippyState, LocalNetwork, MaintenanceReminder,
+    PrintJobInfo, PrinterState, ScheduleDecision, ScheduleStatus, ScheduledAction, ScheduledActionKind, ScheduledActions, SubnetConfig,
+};
+use crate::models::config::{
+    AckEscalationSettings, AlertKind, AppSettings, CompletionGifSettings, CompletionWebhook, ConfigBackupSettings, ErrorCaptureSettings, EscalationLevel, FailureDetectionSettings, HeaterIdleAction, JobComparisonSettings, NotificationSettings,
+    OfflineEscalationRule, ScanProfile, SnapshotArchiveSettings, TelemetrySettings, COMPLETION_GIF_FRAME_INTERVAL_MS,
+    STATUS_REFRESH_CONCURRENCY,
+};
+use crate::models::rules::{AutomationRule, AutomationRules, NotifyChannel, RuleAction, RuleMetrics};
+use crate::models::telemetry::TelemetrySample;
+use crate::network::scanner::scan_and_merge;
+use crate::network::status_cache::get_host_status;
+use crate::network::watcher::{local_networks, NetworkWatcher};
+use crate::heater_watch::{HeaterIdleWatch, HeaterWatchdog};
+use crate::mcu_watch::McuLinkWatch;
+use crate::i18n::{format_finish_time, status_name, t, tf};
+use crate::notifications::do_not_disturb::refresh_do_not_disturb;
+use crate::notifications::snooze::release_acknowledgement;
+use crate::notifications::unacknowledged::{send_escalation_webhook, PendingAlert, UnacknowledgedAlerts};
+use crate::notifications::webhook::post_webhook;
+use crate::notifications::system::{
+    flush_deferred_notifications, host_moved_body, send_critical_host_notification, send_critical_notification,
+    send_host_notification, send_notification, send_notification_with_image, status_change_body,
+};
+use crate::notifications::template::{render_or_default, TemplateValues};
+use crate::telegram::TelegramBot;
+use crate::rules::{collect_metrics, send_rule_webhook, RuleEngine};
+use crate::telemetry::record_sample;
+use crate::webcam::{
+    build_timelapse, capture_error_burst, completion_gif_from_archive, completion_gif_from_snapshots, decode_frame, fetch_snapshot, FailureDetector,
+    PrintFailureWarning, SnapshotArchiver,
+};
+
+/// Shared per-host health metrics keyed by IP address
+pub type HealthRegistry = Arc<Mutex<HashMap<String, HostHealthStats>>>;
+
+/// Offline escalation progress per host IP address
+pub type OfflineEscalations = Arc<Mutex<HashMap<String, OfflineEscalation>>>;
+
+/// Warnings last reported per host IP address
+pub type KnownWarnings = Arc<Mutex<HashMap<String, HashSet<HostWarning>>>>;
+
+/// Outage of one host and the escalation steps already sent for it
+#[derive(Debug, Clone)]
+pub struct OfflineEscalation {
+    /// When the host was first seen offline
+    since: Instant,
+    /// Number of escalation rules already fired, in threshold order
+    fired: usize,
+}
+
+/// Minimum time between print-hour refreshes of one host
+const MAINTENANCE_CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
 
-use crate::models::{HostInfo, HostStatusResponse};
+/// Distance in °C or % a sensor reading must be back within its limit to count as recovered
+const SENSOR_ALERT_HYSTERESIS: f64 = 1.0;
+
+/// Everything a monitoring cycle needs, independent of the Tauri runtime
+#[derive(Clone)]
+pub struct MonitorContext {
+    /// Hosts to monitor
+    pub hosts: Arc<Mutex<Vec<HostInfo>>>,
+    /// Rolling health metrics
+    pub health: HealthRegistry,
+    /// Snapshot failure detector
+    pub detector: Arc<Mutex<FailureDetector>>,
+    /// Last print-hour refresh per host
+    pub maintenance_checks: Arc<Mutex<HashMap<String, Instant>>>,
+    /// States of the hosts that had a job during the previous cycle
+    pub printing_hosts: Arc<Mutex<HashMap<String, PrinterState>>>,
+    /// Hosts with a finished print waiting to cool down before being powered off
+    pub power_off_pending: Arc<Mutex<HashSet<String>>>,
+    /// Hosts with a finished print waiting to cool down before the part can be removed
+    pub cooldown_pending: Arc<Mutex<HashSet<String>>>,
+    /// Sensor readings currently outside their limits, as "host/sensor/kind"
+    pub sensor_alerts: Arc<Mutex<HashSet<String>>>,
+    /// Hosts whose config backup is running
+    pub config_backups: Arc<Mutex<HashSet<String>>>,
+    /// Last telemetry sample per host
+    pub telemetry_samples: Arc<Mutex<HashMap<String, Instant>>>,
+    /// Automation rule state between cycles
+    pub rule_engine: Arc<Mutex<RuleEngine>>,
+    /// Heaters at full power between cycles
+    pub heater_watchdog: Arc<Mutex<HeaterWatchdog>>,
+    /// Idle printers with heaters on between cycles
+    pub heater_idle: Arc<Mutex<HeaterIdleWatch>>,
+    /// MCU retransmit counters between cycles
+    pub mcu_watch: Arc<Mutex<McuLinkWatch>>,
+    /// Snapshot archives of running prints
+    pub archiver: Arc<Mutex<SnapshotArchiver>>,
+    /// Hosts currently offline and their escalation progress
+    pub offline_escalations: OfflineEscalations,
+    /// Critical alerts waiting to be acknowledged
+    pub unacknowledged_alerts: Arc<Mutex<UnacknowledgedAlerts>>,
+    /// Networks of the machine, to notice it moving to another network
+    pub network_watcher: Arc<Mutex<NetworkWatcher>>,
+    /// Moonraker warnings already announced
+    pub known_warnings: KnownWarnings,
+    /// Previous runs of the running jobs
+    pub job_comparator: Arc<Mutex<JobComparator>>,
+    /// Telegram bot used for remote notifications
+    pub telegram_bot: Arc<Mutex<Option<TelegramBot>>>,
+    /// HTTP client for snapshots
+    pub client: reqwest::Client,
+    /// Application handle for UI events (None in headless mode)
+    pub app_handle: Option<AppHandle>,
+    /// Whether the monitor owns host state and status notifications.
+    /// The desktop frontend does this itself, the headless daemon relies on the monitor.
+    pub standalone: bool,
+}
 
 /// Background monitor state
 pub struct BackgroundMonitorState {
-    is_running: AtomicBool,
+    is_running: Arc<AtomicBool>,
     task_handle: tokio::sync::Mutex<Option<tokio::task::JoinHandle<()>>>,
+    health: HealthRegistry,
+    detector: Arc<Mutex<FailureDetector>>,
+    maintenance_checks: Arc<Mutex<HashMap<String, Instant>>>,
+    printing_hosts: Arc<Mutex<HashMap<String, PrinterState>>>,
+    power_off_pending: Arc<Mutex<HashSet<String>>>,
+    cooldown_pending: Arc<Mutex<HashSet<String>>>,
+    sensor_alerts: Arc<Mutex<HashSet<String>>>,
+    config_backups: Arc<Mutex<HashSet<String>>>,
+    telemetry_samples: Arc<Mutex<HashMap<String, Instant>>>,
+    rule_engine: Arc<Mutex<RuleEngine>>,
+    heater_watchdog: Arc<Mutex<HeaterWatchdog>>,
+    heater_idle: Arc<Mutex<HeaterIdleWatch>>,
+    mcu_watch: Arc<Mutex<McuLinkWatch>>,
+    archiver: Arc<Mutex<SnapshotArchiver>>,
+    offline_escalations: OfflineEscalations,
+    unacknowledged_alerts: Arc<Mutex<UnacknowledgedAlerts>>,
+    network_watcher: Arc<Mutex<NetworkWatcher>>,
+    known_warnings: KnownWarnings,
+    job_comparator: Arc<Mutex<JobComparator>>,
 }
 
 impl BackgroundMonitorState {
     /// Creates a new background monitor state
     pub fn new() -> Self {
         Self {
-            is_running: AtomicBool::new(false),
+            is_running: Arc::new(AtomicBool::new(false)),
             task_handle: tokio::sync::Mutex::new(None),
+            health: Arc::new(Mutex::new(HashMap::new())),
+            detector: Arc::new(Mutex::new(FailureDetector::new())),
+            maintenance_checks: Arc::new(Mutex::new(HashMap::new())),
+            printing_hosts: Arc::new(Mutex::new(HashMap::new())),
+            power_off_pending: Arc::new(Mutex::new(HashSet::new())),
+            cooldown_pending: Arc::new(Mutex::new(HashSet::new())),
+            sensor_alerts: Arc::new(Mutex::new(HashSet::new())),
+            config_backups: Arc::new(Mutex::new(HashSet::new())),
+            telemetry_samples: Arc::new(Mutex::new(HashMap::new())),
+            rule_engine: Arc::new(Mutex::new(RuleEngine::new())),
+            heater_watchdog: Arc::new(Mutex::new(HeaterWatchdog::new())),
+            heater_idle: Arc::new(Mutex::new(HeaterIdleWatch::new())),
+            mcu_watch: Arc::new(Mutex::new(McuLinkWatch::new())),
+            archiver: Arc::new(Mutex::new(SnapshotArchiver::new())),
+            offline_escalations: Arc::new(Mutex::new(HashMap::new())),
+            unacknowledged_alerts: Arc::new(Mutex::new(UnacknowledgedAlerts::new())),
+            network_watcher: Arc::new(Mutex::new(NetworkWatcher::new())),
+            known_warnings: Arc::new(Mutex::new(HashMap::new())),
+            job_comparator: Arc::new(Mutex::new(JobComparator::new())),
         }
     }
 
@@ -30,31 +207,49 @@ impl BackgroundMonitorState {
 
     /// Starts the background monitoring process
     pub async fn start(&self, app_handle: AppHandle, interval_seconds: u64) -> Result<(), String> {
+        let telegram = app_handle.state::<TelegramBotState>();
+        let context = MonitorContext {
+            hosts: telegram.hosts.clone(),
+            health: self.health.clone(),
+            detector: self.detector.clone(),
+            maintenance_checks: self.maintenance_checks.clone(),
+            printing_hosts: self.printing_hosts.clone(),
+            power_off_pending: self.power_off_pending.clone(),
+            cooldown_pending: self.cooldown_pending.clone(),
+            sensor_alerts: self.sensor_alerts.clone(),
+            config_backups: self.config_backups.clone(),
+            telemetry_samples: self.telemetry_samples.clone(),
+            rule_engine: self.rule_engine.clone(),
+            heater_watchdog: self.heater_watchdog.clone(),
+            heater_idle: self.heater_idle.clone(),
+            mcu_watch: self.mcu_watch.clone(),
+            archiver: self.archiver.clone(),
+            offline_escalations: self.offline_escalations.clone(),
+            unacknowledged_alerts: self.unacknowledged_alerts.clone(),
+            network_watcher: self.network_watcher.clone(),
+            known_warnings: self.known_warnings.clone(),
+            job_comparator: self.job_comparator.clone(),
+            telegram_bot: telegram.bot.clone(),
+            client: create_client().await.map_err(|e| e.to_string())?,
+            app_handle: Some(app_handle.clone()),
+            standalone: false,
+        };
+        self.start_with_context(context, interval_seconds).await
+    }
+
+    /// Starts the background monitoring process with an explicit context
+    pub async fn start_with_context(&self, context: MonitorContext, interval_seconds: u64) -> Result<(), String> {
         if self.is_running.load(Ordering::Relaxed) {
             return Err("Background monitoring is already running".to_string());
         }
 
         self.is_running.store(true, Ordering::Relaxed);
-        let is_running_arc = Arc::new(AtomicBool::new(true));
-        let app_handle_clone = app_handle.clone();
+        let is_running_arc = self.is_running.clone();
 
         let handle = tokio::spawn(async move {
             while is_running_arc.load(Ordering::Relaxed) {
                 println!("Background monitor: Checking hosts...");
-                // In a real implementation, this would fetch hosts from persistent storage
-                // and then check their status, sending notifications as needed.
-                let hosts = Self::get_hosts_from_storage(&app_handle_clone).await.unwrap_or_default();
-                for host in hosts {
-                    match Self::check_host_status(&host).await {
-                        Ok(status) => {
-                            println!("Host {}: Status: {}", host.hostname, status.status);
-                            // Compare with previous status and send notification if changed
-                        },
-                        Err(e) => {
-                            eprintln!("Error checking host {}: {}", host.hostname, e);
-                        }
-                    }
-                }
+                Self::run_cycle(&context).await;
                 sleep(Duration::from_secs(interval_seconds)).await;
             }
             println!("Background monitor stopped.");
@@ -69,26 +264,1832 @@ impl BackgroundMonitorState {
         self.is_running.store(false, Ordering::Relaxed);
     }
 
-    /// Gets hosts from storage
-    async fn get_hosts_from_storage(_app_handle: &AppHandle) -> Result<Vec<HostInfo>, String> {
-        // In a real implementation, this would fetch hosts from persistent storage
-        // For now, return empty vector as hosts are managed by the frontend
-        Ok(vec![])
+    /// Runs one monitoring pass over all hosts
+    pub async fn run_cycle(context: &MonitorContext) {
+        let settings = AppSettings::load().unwrap_or_default();
+        Self::check_network(context, &settings).await;
+        refresh_do_not_disturb().await;
+        let all_hosts = context.hosts.lock().await.clone();
+
+        prune_host_semaphores(|host| all_hosts.iter().any(|h| h.ip_address == host));
+
+        // Hosts removed from the list can no longer finish their print
+        let printing_before = {
+            let mut printing_hosts = context.printing_hosts.lock().await;
+            printing_hosts.retain(|ip, _| all_hosts.iter().any(|h| &h.ip_address == ip));
+            printing_hosts.clone()
+        };
+
+        // Hosts in a network the machine has left are unreachable, not offline
+        let hosts: Vec<HostInfo> = {
+            let watcher = context.network_watcher.lock().await;
+            all_hosts.into_iter().filter(|h| !watcher.is_on_left_network(&h.ip_address)).collect()
+        };
+
+        // Rules are read once per cycle, not for every host
+        let rules = match AutomationRules::load() {
+            Ok(rules) => {
+                context.rule_engine.lock().await.retain_rules(&rules.rules);
+                Some(rules)
+            }
+            Err(e) => {
+                eprintln!("Failed to load automation rules: {}", e);
+                None
+            }
+        };
+
+        let mut states: HashMap<String, Option<PrinterState>> = HashMap::new();
+        for host in &hosts {
+            let status = Self::check_and_record(&context.health, &host.ip_address).await;
+            println!("Host {}: Status: {}", host.hostname, status.status);
+            states.insert(host.ip_address.clone(), status.printer_state);
+
+            if context.standalone {
+                Self::apply_status(context, host, &status, &settings).await;
+            }
+
+            Self::run_failure_detection(context, host, &status, &settings.failure_detection).await;
+            Self::run_job_comparison(context, host, &status, &settings.job_comparison).await;
+            Self::run_snapshot_archive(context, host, &status, &settings.snapshot_archive).await;
+            let was_printing = printing_before.contains_key(&host.ip_address);
+            Self::run_error_capture(context, host, &status, &settings.error_capture, was_printing).await;
+            Self::run_scheduled_actions(context, host, &status).await;
+            Self::track_job_completion(context, host, &status, &settings).await;
+            Self::run_cooldown_check(context, host, &status, &settings).await;
+            Self::run_sensor_alerts(context, host, &status, &settings).await;
+            Self::run_heater_watch(context, host, &status, &settings).await;
+            Self::run_heater_idle(context, host, &status, &settings).await;
+            Self::run_mcu_watch(context, host, &status, &settings).await;
+            Self::run_auto_power_off(context, host, &status, &settings).await;
+            Self::run_maintenance_check(context, host, &status, &settings).await;
+            Self::run_config_backup(context, host, &status, &settings.config_backup).await;
+            Self::run_telemetry(context, host, &status, &settings.telemetry).await;
+            if let Some(rules) = &rules {
+                Self::run_rules(context, host, &status, rules).await;
+            }
+            Self::run_offline_escalation(context, host, &status, &settings).await;
+            Self::run_warning_check(context, host, &status, &settings.notifications).await;
+            Self::run_ack_escalation(context, host, &status, &settings).await;
+            Self::release_cleared_alerts(context, host, &status, &settings).await;
+        }
+
+        Self::announce_fleet_idle(context, &hosts, &printing_before, &states, &settings.notifications).await;
+        flush_deferred_notifications();
+
+        // Statuses applied above must not wait for the bot's cache to expire
+        if context.standalone {
+            if let Some(bot) = context.telegram_bot.lock().await.as_ref() {
+                bot.refresh_host_cache().await;
+            }
+        }
+    }
+
+    /// Detects the machine joining another network and scans it when configured
+    ///
+    /// The desktop frontend owns its host list, so there the change is only
+    /// reported and the frontend suggests or runs the scan.
+    async fn check_network(context: &MonitorContext, settings: &AppSettings) {
+        let Some(change) = context.network_watcher.lock().await.update(local_networks()) else {
+            return;
+        };
+        let subnets = |networks: &[LocalNetwork]| networks.iter().map(|n| n.subnet.as_str()).collect::<Vec<_>>().join(", ");
+        println!("Network changed: joined [{}], left [{}]", subnets(&change.joined), subnets(&change.left));
+
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("network-changed", &change);
+        }
+
+        if settings.network_change.auto_scan && context.standalone && !change.joined.is_empty() {
+            let subnets: Vec<SubnetConfig> = change
+                .joined
+                .iter()
+                .map(|network| SubnetConfig {
+                    name: network.interface.clone(),
+                    range: network.subnet.clone(),
+                    enabled: true,
+                    profile: ScanProfile::Lan,
+                })
+                .collect();
+            for relink in scan_and_merge(&subnets, &context.hosts).await {
+                Self::report_host_moved(context, &relink).await;
+            }
+            context.network_watcher.lock().await.dismiss_change();
+        }
+    }
+
+    /// Tells the user that a known printer got a new address
+    ///
+    /// The frontend offers the DHCP reservation for the new address.
+    async fn report_host_moved(context: &MonitorContext, relink: &HostRelink) {
+        let title = t("notify.host_moved.title");
+        let body = host_moved_body(relink);
+
+        send_notification(title, &body);
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("host-relinked", relink.clone());
+        }
+
+        let bot_guard = context.telegram_bot.lock().await;
+        if let Some(ref bot) = *bot_guard {
+            if let Err(e) = bot.send_notification_to_all_users(title, &body, Some(&relink.address)).await {
+                eprintln!("Failed to send address change to Telegram: {}", e);
+            }
+        }
+    }
+
+    /// Notifies once when the last running print of the fleet has ended
+    ///
+    /// # Arguments
+    /// * `hosts` - Hosts of this cycle
+    /// * `printing_before` - Hosts that were printing before this cycle
+    /// * `states` - Printer states of this cycle by host IP address
+    async fn announce_fleet_idle(
+        context: &MonitorContext,
+        hosts: &[HostInfo],
+        printing_before: &HashMap<String, PrinterState>,
+        states: &HashMap<String, Option<PrinterState>>,
+        notifications: &NotificationSettings,
+    ) {
+        if printing_before.is_empty() || !context.printing_hosts.lock().await.is_empty() {
+            return;
+        }
+        // A printer that went offline or into an error did not finish, and one
+        // on a left network was not checked at all
+        let all_standby = printing_before
+            .keys()
+            .all(|ip| states.get(ip).is_some_and(|state| *state == Some(PrinterState::Standby)));
+        if !all_standby {
+            return;
+        }
+
+        let finished: Vec<&str> = hosts
+            .iter()
+            .filter(|h| printing_before.contains_key(&h.ip_address))
+            .map(|h| h.hostname.as_str())
+            .collect();
+        let title = t("notify.fleet_idle.title");
+        let body = tf("notify.fleet_idle.body", &[("hosts", &finished.join(", "))]);
+
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("fleet-idle", &finished);
+        }
+
+        let channels = &notifications.fleet_idle;
+        if channels.system {
+            send_notification(title, &body);
+        }
+        if channels.telegram {
+            let bot_guard = context.telegram_bot.lock().await;
+            if let Some(ref bot) = *bot_guard {
+                if let Err(e) = bot.send_notification_to_all_users(title, &body, None).await {
+                    eprintln!("Failed to send fleet idle notification to Telegram: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Sends the escalation steps that became due while a host stays offline
+    ///
+    /// Each rule fires once per outage. When several become due in the same
+    /// cycle (e.g. the monitor was started late), only the last one is sent.
+    async fn run_offline_escalation(
+        context: &MonitorContext,
+        host: &HostInfo,
+        status: &HostStatusResponse,
+        app_settings: &AppSettings,
+    ) {
+        let settings = &app_settings.offline_escalation;
+        let mut escalations = context.offline_escalations.lock().await;
+        if !settings.enabled || host.maintenance || status.status != "offline" {
+            escalations.remove(&host.ip_address);
+            return;
+        }
+
+        let escalation = escalations
+            .entry(host.ip_address.clone())
+            .or_insert_with(|| OfflineEscalation { since: Instant::now(), fired: 0 });
+        let offline_for = escalation.since.elapsed();
+
+        let mut rules: Vec<&OfflineEscalationRule> = settings.rules.iter().collect();
+        rules.sort_by_key(|rule| rule.after_minutes);
+        let due: Vec<&OfflineEscalationRule> = rules
+            .into_iter()
+            .skip(escalation.fired)
+            .take_while(|rule| offline_for >= Duration::from_secs(rule.after_minutes.saturating_mul(60)))
+            .collect();
+        let Some(rule) = due.last().copied() else {
+            return;
+        };
+        escalation.fired += due.len();
+        drop(escalations);
+        // Snoozed steps count as fired, so they are not all sent once the snooze ends
+        let silenced = app_settings.is_alert_silenced(&host.ip_address, AlertKind::Offline, chrono::Utc::now());
+
+        let title = match rule.level {
+            EscalationLevel::Normal => t("notify.offline_escalation.title"),
+            EscalationLevel::Critical => t("notify.offline_escalation.critical_title"),
+        };
+        let body = tf("notify.offline_escalation.body", &[
+            ("host", &host.hostname),
+            ("ip", &host.ip_address),
+            ("duration", &format_duration(offline_for.as_secs_f64())),
+        ]);
+
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("host-offline-escalation", serde_json::json!({
+                "host": host.ip_address,
+                "hostname": host.hostname,
+                "level": rule.level,
+                "offline_seconds": offline_for.as_secs(),
+                "silenced": silenced,
+            }));
+        }
+        if silenced {
+            return;
+        }
+        match rule.level {
+            EscalationLevel::Normal => send_host_notification(&host.ip_address, title, &body),
+            EscalationLevel::Critical => {
+                send_critical_host_notification(&host.ip_address, title, &body);
+                context.unacknowledged_alerts.lock().await.raise(&host.ip_address, AlertKind::Offline, title, &body, Instant::now());
+            }
+        }
+
+        if rule.telegram {
+            let bot_guard = context.telegram_bot.lock().await;
+            if let Some(ref bot) = *bot_guard {
+                if let Err(e) = bot.send_alert_to_all_users(title, &body, &host.ip_address, AlertKind::Offline).await {
+                    eprintln!("Failed to send offline escalation to Telegram: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Ends the acknowledgements of a host's alerts that have cleared,
+    /// so the next occurrence notifies again
+    async fn release_cleared_alerts(context: &MonitorContext, host: &HostInfo, status: &HostStatusResponse, settings: &AppSettings) {
+        let Some(silences) = settings.alert_silences.get(&host.ip_address) else {
+            return;
+        };
+        for kind in silences.iter().filter(|s| s.until.is_none()).map(|s| s.kind) {
+            if Self::is_alert_active(context, host, status, kind).await {
+                continue;
+            }
+            if let Err(e) = release_acknowledgement(&host.ip_address, kind) {
+                eprintln!("Failed to release acknowledged {} alert of {}: {}", kind.as_str(), host.hostname, e);
+            }
+        }
+    }
+
+    /// Whether the condition behind an alert of a host still holds
+    async fn is_alert_active(context: &MonitorContext, host: &HostInfo, status: &HostStatusResponse, kind: AlertKind) -> bool {
+        match kind {
+            AlertKind::Offline => status.status == "offline",
+            AlertKind::Sensor => {
+                let prefix = format!("{}/", host.ip_address);
+                context.sensor_alerts.lock().await.iter().any(|key| key.starts_with(&prefix))
+            }
+            AlertKind::HeaterIdle => context.heater_idle.lock().await.is_reported(&host.ip_address),
+            AlertKind::HeaterWatch => context.heater_watchdog.lock().await.is_alarmed(&host.ip_address),
+            AlertKind::McuLink => context.mcu_watch.lock().await.is_alarmed(&host.ip_address),
+        }
+    }
+
+    /// Sends critical alerts of a host nobody acknowledged or snoozed in time to the secondary contacts
+    async fn run_ack_escalation(context: &MonitorContext, host: &HostInfo, status: &HostStatusResponse, settings: &AppSettings) {
+        let now = chrono::Utc::now();
+        let pending = context.unacknowledged_alerts.lock().await.pending_kinds(&host.ip_address);
+        for kind in pending {
+            if settings.is_alert_silenced(&host.ip_address, kind, now) || !Self::is_alert_active(context, host, status, kind).await {
+                context.unacknowledged_alerts.lock().await.resolve(&host.ip_address, kind);
+            }
+        }
+
+        let escalation = &settings.ack_escalation;
+        if !escalation.enabled {
+            return;
+        }
+        let wait = Duration::from_secs(escalation.after_minutes.saturating_mul(60));
+        let overdue = context.unacknowledged_alerts.lock().await.take_overdue(&host.ip_address, wait, Instant::now());
+        for alert in overdue {
+            Self::escalate_unacknowledged(context, host, &alert, escalation).await;
+        }
+    }
+
+    /// Sends one unacknowledged alert to the secondary Telegram users and the webhook
+    async fn escalate_unacknowledged(
+        context: &MonitorContext,
+        host: &HostInfo,
+        alert: &PendingAlert,
+        settings: &AckEscalationSettings,
+    ) {
+        let minutes = (alert.raised.elapsed().as_secs() / 60).to_string();
+        let title = t("notify.unacknowledged.title");
+        let body = tf("notify.unacknowledged.body", &[("minutes", &minutes), ("title", &alert.title), ("body", &alert.body)]);
+
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("alert-escalated", serde_json::json!({
+                "host": host.ip_address,
+                "hostname": host.hostname,
+                "alert": alert.kind,
+                "unacknowledged_seconds": alert.raised.elapsed().as_secs(),
+            }));
+        }
+
+        if !settings.telegram_user_ids.is_empty() {
+            let bot_guard = context.telegram_bot.lock().await;
+            if let Some(ref bot) = *bot_guard {
+                if let Err(e) = bot.send_alert_to_users(&settings.telegram_user_ids, title, &body, &host.ip_address, alert.kind).await {
+                    eprintln!("Failed to send unacknowledged alert to Telegram: {}", e);
+                }
+            }
+        }
+        if let Some(url) = settings.webhook_url.as_deref().filter(|url| !url.trim().is_empty()) {
+            if let Err(e) = send_escalation_webhook(url, alert, &host.hostname).await {
+                eprintln!("Failed to send unacknowledged alert of {} to webhook: {}", host.hostname, e);
+            }
+        }
+    }
+
+    /// Notifies about Moonraker warnings and announcements a host did not report before,
+    /// e.g. a component that loaded fine until now failing after an update
+    ///
+    /// Offline hosts keep their known warnings, so a reboot does not repeat them.
+    async fn run_warning_check(
+        context: &MonitorContext,
+        host: &HostInfo,
+        status: &HostStatusResponse,
+        notifications: &NotificationSettings,
+    ) {
+        if !status.success {
+            return;
+        }
+        let new_warnings: Vec<&HostWarning> = {
+            let mut known = context.known_warnings.lock().await;
+            let previous = known
+                .insert(host.ip_address.clone(), status.warnings.iter().cloned().collect())
+                .unwrap_or_default();
+            status.warnings.iter().filter(|w| !previous.contains(*w)).collect()
+        };
+        if new_warnings.is_empty() || !notifications.host_warnings || host.maintenance {
+            return;
+        }
+
+        let lines: Vec<String> = new_warnings
+            .iter()
+            .map(|warning| match (warning.kind, &warning.url) {
+                (HostWarningKind::FailedComponent, _) => format!(
+                    "{}\n{}",
+                    tf("notify.host_warning.failed_component", &[("component", &warning.message)]),
+                    remediation(warning.kind, &warning.message)
+                ),
+                (HostWarningKind::MissingKlippyRequirement, _) => format!(
+                    "{}\n{}",
+                    tf("notify.host_warning.missing_requirement", &[("section", &warning.message)]),
+                    remediation(warning.kind, &warning.message)
+                ),
+                (HostWarningKind::Announcement, Some(url)) => format!("{} ({})", warning.message, url),
+                _ => warning.message.clone(),
+            })
+            .collect();
+        let title = t("notify.host_warning.title");
+        let body = tf("notify.host_warning.body", &[("host", &host.hostname), ("warnings", &lines.join("\n"))]);
+
+        send_notification(title, &body);
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("host-warnings", serde_json::json!({
+                "host": host.ip_address,
+                "hostname": host.hostname,
+                "warnings": new_warnings,
+            }));
+        }
+
+        let bot_guard = context.telegram_bot.lock().await;
+        if let Some(ref bot) = *bot_guard {
+            if let Err(e) = bot.send_notification_to_all_users(title, &body, Some(&host.ip_address)).await {
+                eprintln!("Failed to send host warning to Telegram: {}", e);
+            }
+        }
+    }
+
+    /// Checks a host status and records the result in the health registry
+    pub async fn check_and_record(health: &HealthRegistry, ip: &str) -> HostStatusResponse {
+        let lookup = get_host_status(ip).await;
+
+        // Cached results were already recorded when they were fetched
+        if let Some(latency_ms) = lookup.latency_ms {
+            let mut registry = health.lock().await;
+            registry
+                .entry(ip.to_string())
+                .or_insert_with(HostHealthStats::new)
+                .record(lookup.status.success, Some(latency_ms));
+        }
+
+        lookup.status
+    }
+
+    /// Checks many hosts concurrently (bounded) and records the results in the health metrics
+    /// 
+    /// # Arguments
+    /// * `health` - Health registry to record the results in
+    /// * `ips` - Host IP addresses
+    /// 
+    /// # Returns
+    /// * Status responses keyed by IP address
+    pub async fn check_all_and_record(health: &HealthRegistry, ips: Vec<String>) -> HashMap<String, HostStatusResponse> {
+        futures::stream::iter(ips)
+            .map(|ip| async move {
+                let status = Self::check_and_record(health, &ip).await;
+                (ip, status)
+            })
+            .buffer_unordered(STATUS_REFRESH_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    /// Returns the shared health registry
+    pub fn health_registry(&self) -> HealthRegistry {
+        self.health.clone()
     }
 
-    /// Checks host status
-    async fn check_host_status(_host: &HostInfo) -> Result<HostStatusResponse, String> {
-        // This would use the existing check_host_status_command logic
-        // For now, return a placeholder response
-        Ok(HostStatusResponse {
-            success: true,
-            status: "online".to_string(),
-            device_status: Some("online".to_string()),
-            moonraker_version: Some("1.0".to_string()),
-            klippy_state: Some("ready".to_string()),
-            printer_state: Some("ready".to_string()),
-            printer_flags: None,
-        })
+    /// Returns the shared failure detector
+    pub fn failure_detector(&self) -> Arc<Mutex<FailureDetector>> {
+        self.detector.clone()
     }
 
+    /// Returns the shared print-hour refresh timestamps
+    pub fn maintenance_checks(&self) -> Arc<Mutex<HashMap<String, Instant>>> {
+        self.maintenance_checks.clone()
+    }
+
+    /// Returns the shared states of hosts seen with a job
+    pub fn printing_hosts(&self) -> Arc<Mutex<HashMap<String, PrinterState>>> {
+        self.printing_hosts.clone()
+    }
+
+    /// Returns the shared set of hosts waiting for an automatic power off
+    pub fn power_off_pending(&self) -> Arc<Mutex<HashSet<String>>> {
+        self.power_off_pending.clone()
+    }
+
+    /// Returns the shared offline escalation progress
+    pub fn offline_escalations(&self) -> OfflineEscalations {
+        self.offline_escalations.clone()
+    }
+
+    /// Returns the shared unacknowledged critical alerts
+    pub fn unacknowledged_alerts(&self) -> Arc<Mutex<UnacknowledgedAlerts>> {
+        self.unacknowledged_alerts.clone()
+    }
+
+    /// Returns the shared network change detection
+    pub fn network_watcher(&self) -> Arc<Mutex<NetworkWatcher>> {
+        self.network_watcher.clone()
+    }
+
+    /// Returns the shared set of Moonraker warnings already announced
+    pub fn known_warnings(&self) -> KnownWarnings {
+        self.known_warnings.clone()
+    }
+
+    /// Returns the shared comparator of running jobs with their previous runs
+    pub fn job_comparator(&self) -> Arc<Mutex<JobComparator>> {
+        self.job_comparator.clone()
+    }
+
+    /// Returns the shared set of hosts waiting for the cooldown notification
+    pub fn cooldown_pending(&self) -> Arc<Mutex<HashSet<String>>> {
+        self.cooldown_pending.clone()
+    }
+
+    /// Returns the shared set of sensor readings outside their limits
+    pub fn sensor_alerts(&self) -> Arc<Mutex<HashSet<String>>> {
+        self.sensor_alerts.clone()
+    }
+
+    /// Returns the shared set of hosts being backed up
+    pub fn config_backups(&self) -> Arc<Mutex<HashSet<String>>> {
+        self.config_backups.clone()
+    }
+
+    /// Returns the shared last telemetry sample times
+    pub fn telemetry_samples(&self) -> Arc<Mutex<HashMap<String, Instant>>> {
+        self.telemetry_samples.clone()
+    }
+
+    /// Returns the shared automation rule state
+    pub fn rule_engine(&self) -> Arc<Mutex<RuleEngine>> {
+        self.rule_engine.clone()
+    }
+
+    /// Returns the shared heater power cross-check state
+    pub fn heater_watchdog(&self) -> Arc<Mutex<HeaterWatchdog>> {
+        self.heater_watchdog.clone()
+    }
+
+    /// Returns the shared idle heater state
+    pub fn heater_idle(&self) -> Arc<Mutex<HeaterIdleWatch>> {
+        self.heater_idle.clone()
+    }
+
+    /// Returns the shared MCU link counters
+    pub fn mcu_watch(&self) -> Arc<Mutex<McuLinkWatch>> {
+        self.mcu_watch.clone()
+    }
+
+    /// Returns the shared snapshot archiver
+    pub fn archiver(&self) -> Arc<Mutex<SnapshotArchiver>> {
+        self.archiver.clone()
+    }
+
+    /// Gets health summaries for one host or for every tracked host
+    pub async fn get_health(&self, host: Option<&str>) -> Vec<HostHealth> {
+        let registry = self.health.lock().await;
+        let mut result: Vec<HostHealth> = registry
+            .iter()
+            .filter(|(ip, _)| host.is_none() || host == Some(ip.as_str()))
+            .map(|(ip, stats)| stats.summary(ip))
+            .collect();
+        result.sort_by(|a, b| a.host.cmp(&b.host));
+        result
+    }
+
+    /// Stores a fresh status in the host list and notifies about changes
+    async fn apply_status(
+        context: &MonitorContext,
+        host: &HostInfo,
+        status: &HostStatusResponse,
+        settings: &AppSettings,
+    ) {
+        let notifications = &settings.notifications;
+        let new_state = status.state();
+        let previous_state = host.printer_state;
+
+        {
+            let mut hosts = context.hosts.lock().await;
+            if let Some(stored) = hosts.iter_mut().find(|h| h.ip_address == host.ip_address) {
+                stored.apply_status(status);
+            }
+        }
+
+        let klippy_announced = Self::announce_klippy_transition(context, host, status, notifications).await;
+
+        // A Klippy shutdown already produced a more specific message
+        if previous_state == Some(new_state) || (klippy_announced && new_state == PrinterState::Error) {
+            return;
+        }
+
+        // Planned downtime should not page anyone
+        if host.maintenance && new_state.is_fault() {
+            return;
+        }
+
+        // Outages the user snoozed or acknowledged
+        let went_offline = new_state == PrinterState::Offline;
+        if went_offline && settings.is_alert_silenced(&host.ip_address, AlertKind::Offline, chrono::Utc::now()) {
+            return;
+        }
+
+        let enabled = match new_state {
+            PrinterState::Printing => notifications.printing,
+            PrinterState::Paused => notifications.paused,
+            PrinterState::Error => notifications.error,
+            PrinterState::Cancelling => notifications.cancelling,
+            PrinterState::Standby => notifications.standby,
+            PrinterState::Offline => true,
+        };
+        if !enabled {
+            return;
+        }
+
+        let finish = match new_state {
+            PrinterState::Printing => Self::finish_time_detail(&host.ip_address).await,
+            _ => None,
+        };
+        let detail = match new_state {
+            PrinterState::Error => status.error_message.as_deref(),
+            _ => finish.as_deref(),
+        };
+        // A host without a state yet shows its connection status, e.g. "online"
+        let old_state = previous_state.map_or(host.status.as_str(), |state| state.as_str());
+        let new_state = new_state.as_str();
+        let body = match notifications.templates.status_change.as_deref() {
+            Some(template) => {
+                let values = TemplateValues::for_status_change(&host.ip_address, &host.hostname, old_state, new_state).await;
+                let mut body = render_or_default(Some(template), &values, || status_change_body(&host.hostname, old_state, new_state, None));
+                if let Some(detail) = detail {
+                    body.push_str(&format!("\n{}", detail));
+                }
+                body
+            }
+            None => status_change_body(&host.hostname, old_state, new_state, detail),
+        };
+        send_host_notification(&host.ip_address, t("notify.status_changed.title"), &body);
+
+        let bot_guard = context.telegram_bot.lock().await;
+        if let Some(ref bot) = *bot_guard {
+            let title = t("notify.status_changed.title");
+            let sent = if went_offline {
+                bot.send_alert_to_all_users(title, &body, &host.ip_address, AlertKind::Offline).await
+            } else {
+                bot.send_notification_to_all_users(title, &body, Some(&host.ip_address)).await
+            };
+            if let Err(e) = sent {
+                eprintln!("Failed to send status change to Telegram: {}", e);
+            }
+        }
+    }
+
+    /// Builds the "finishes at" line for a running job, if it can be estimated
+    async fn finish_time_detail(ip: &str) -> Option<String> {
+        let job = get_print_info(ip, None).await.ok().flatten()?;
+        job.progress.remaining_seconds().map(format_finish_time)
+    }
+
+    /// Notifies about Klippy entering or leaving a shutdown/error state
+    ///
+    /// The reason Klippy reports (e.g. "MCU 'mcu' shutdown: Timer too close")
+    /// is included, so the message says more than the generic printer error.
+    /// Returns whether a notification was sent.
+    async fn announce_klippy_transition(
+        context: &MonitorContext,
+        host: &HostInfo,
+        status: &HostStatusResponse,
+        notifications: &NotificationSettings,
+    ) -> bool {
+        let (Some(from), Some(to)) = (host.klippy_state, status.klippy_state) else {
+            return false;
+        };
+        if from == to || !(to.is_fault() || (from.is_fault() && to == KlippyState::Ready)) {
+            return false;
+        }
+
+        let message = status.error_message.clone().filter(|_| to.is_fault());
+
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("klippy-state-changed", serde_json::json!({
+                "host": host.ip_address,
+                "hostname": host.hostname,
+                "from": from,
+                "to": to,
+                "message": message,
+            }));
+        }
+
+        if host.maintenance || !notifications.error {
+            return false;
+        }
+
+        let title = to.label();
+        let body = match &message {
+            Some(message) => format!("{}: {}", host.hostname, message),
+            None => tf("notify.status_changed.body", &[("host", &host.hostname), ("from", &from), ("to", &to)]),
+        };
+
+        send_host_notification(&host.ip_address, title, &body);
+
+        let bot_guard = context.telegram_bot.lock().await;
+        if let Some(ref bot) = *bot_guard {
+            if let Err(e) = bot.send_notification_to_all_users(title, &body, Some(&host.ip_address)).await {
+                eprintln!("Failed to send Klippy state change to Telegram: {}", e);
+            }
+        }
+        true
+    }
+
+    /// Captures a snapshot of a printing host and runs the failure heuristics
+    async fn run_failure_detection(
+        context: &MonitorContext,
+        host: &HostInfo,
+        status: &HostStatusResponse,
+        settings: &FailureDetectionSettings,
+    ) {
+        if !settings.enabled || host.maintenance {
+            return;
+        }
+
+        if status.printer_state != Some(PrinterState::Printing) {
+            context.detector.lock().await.reset(&host.ip_address);
+            return;
+        }
+
+        if !context.detector.lock().await.should_capture(&host.ip_address, settings.interval_seconds) {
+            return;
+        }
+
+        let image_data = match fetch_snapshot(&host.ip_address, &context.client).await {
+            Ok(data) => data,
+            Err(e) => {
+                eprintln!("Failure detection: snapshot from {} failed: {}", host.ip_address, e);
+                return;
+            }
+        };
+
+        let decoded = tokio::task::spawn_blocking(move || (decode_frame(&image_data), image_data)).await;
+        let (frame, image_data) = match decoded {
+            Ok((Ok(frame), image_data)) => (frame, image_data),
+            Ok((Err(e), _)) => {
+                eprintln!("Failure detection: {}", e);
+                return;
+            }
+            Err(e) => {
+                eprintln!("Failure detection: decoding the snapshot of {} failed: {}", host.ip_address, e);
+                return;
+            }
+        };
+
+        let result = context.detector.lock().await.analyze(&host.ip_address, frame, settings);
+        if let (_, Some(warning)) = result {
+            Self::report_failure_warning(context, host, &warning, &image_data).await;
+        }
+    }
+
+    /// Compares a running print with the previous runs of its file
+    ///
+    /// The history is read once per job; paused prints keep their baseline.
+    async fn run_job_comparison(
+        context: &MonitorContext,
+        host: &HostInfo,
+        status: &HostStatusResponse,
+        settings: &JobComparisonSettings,
+    ) {
+        if !settings.enabled || host.maintenance {
+            return;
+        }
+
+        match status.printer_state {
+            Some(PrinterState::Printing) => {}
+            Some(PrinterState::Paused) => return,
+            _ => {
+                context.job_comparator.lock().await.reset(&host.ip_address);
+                return;
+            }
+        }
+
+        let job = match get_print_info(&host.ip_address, None).await {
+            Ok(Some(job)) => job,
+            _ => return,
+        };
+
+        if context.job_comparator.lock().await.needs_baseline(&host.ip_address, &job.filename) {
+            let history = match get_previous_runs(&host.ip_address, &job.filename).await {
+                Ok(history) => history,
+                Err(e) => {
+                    eprintln!("Job comparison: history of {} unavailable: {}", host.ip_address, e);
+                    Vec::new()
+                }
+            };
+            context.job_comparator.lock().await.set_baseline(&host.ip_address, &job.filename, &history);
+        }
+
+        let slowdown = context.job_comparator.lock().await.check(&host.ip_address, &job.progress, settings);
+        if let Some(slowdown) = slowdown {
+            Self::report_slowdown(context, host, &slowdown).await;
+        }
+    }
+
+    /// Sends the "print slower than usual" warning
+    async fn report_slowdown(context: &MonitorContext, host: &HostInfo, slowdown: &PrintSlowdown) {
+        let title = t("notify.print_slowdown.title");
+        let body = tf("notify.print_slowdown.body", &[
+            ("host", &host.hostname),
+            ("file", &slowdown.filename),
+            ("percent", &format!("{:.0}", slowdown.slowdown_percent)),
+            ("progress", &format!("{:.0}", slowdown.progress)),
+            ("elapsed", &format_duration(slowdown.print_duration)),
+            ("expected", &format_duration(slowdown.expected_duration)),
+            ("runs", &slowdown.previous_runs),
+        ]);
+
+        send_notification(title, &body);
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("print-slowdown", slowdown.clone());
+        }
+
+        let bot_guard = context.telegram_bot.lock().await;
+        if let Some(ref bot) = *bot_guard {
+            if let Err(e) = bot.send_notification_to_all_users(title, &body, Some(&host.ip_address)).await {
+                eprintln!("Failed to send slowdown warning to Telegram: {}", e);
+            }
+        }
+    }
+
+    /// Captures a snapshot burst when a print stops with an error
+    ///
+    /// The burst runs in its own task so the other hosts are not held up.
+    ///
+    /// # Arguments
+    /// * `was_printing` - Whether the host had an active job before this cycle
+    async fn run_error_capture(
+        context: &MonitorContext,
+        host: &HostInfo,
+        status: &HostStatusResponse,
+        settings: &ErrorCaptureSettings,
+        was_printing: bool,
+    ) {
+        let failed = status.printer_state == Some(PrinterState::Error) || status.klippy_state.is_some_and(|state| state.is_fault());
+        if !settings.enabled || !was_printing || !failed {
+            return;
+        }
+
+        // print_stats keeps the failed job until the next one starts
+        let filename = get_print_info(&host.ip_address, None).await.ok().flatten().map(|job| job.filename);
+        let (ip, hostname, error_message) = (host.ip_address.clone(), host.hostname.clone(), status.error_message.clone());
+        let (client, app_handle, settings) = (context.client.clone(), context.app_handle.clone(), settings.clone());
+        tokio::spawn(async move {
+            match capture_error_burst(&ip, &hostname, filename, error_message, &client, &settings).await {
+                Ok(capture) => {
+                    println!("Saved {} error snapshot(s) of {}", capture.frames.len(), hostname);
+                    if let Some(app_handle) = app_handle {
+                        let _ = app_handle.emit("error-captured", capture);
+                    }
+                }
+                Err(e) => eprintln!("Error capture of {} failed: {}", hostname, e),
+            }
+        });
+    }
+
+    /// Backs up the config directory of an idle host once its last backup is
+    /// older than the backup interval, and pushes it to GitHub if configured
+    async fn run_config_backup(
+        context: &MonitorContext,
+        host: &HostInfo,
+        status: &HostStatusResponse,
+        settings: &ConfigBackupSettings,
+    ) {
+        let printing = status.printer_state.is_some_and(|state| state.has_active_job());
+        if !settings.enabled || !status.success || printing || !is_backup_due(settings, &host.ip_address).await {
+            return;
+        }
+        if !context.config_backups.lock().await.insert(host.ip_address.clone()) {
+            return;
+        }
+
+        let (ip, hostname) = (host.ip_address.clone(), host.hostname.clone());
+        let (running, app_handle, settings) = (context.config_backups.clone(), context.app_handle.clone(), settings.clone());
+        tokio::spawn(async move {
+            match backup_host_configs(&ip, &hostname, &settings).await {
+                Ok(backup) => {
+                    println!("Backed up {} config file(s) of {}", backup.files.len(), hostname);
+                    if settings.github.enabled {
+                        match push_backup_to_github(&backup, &settings.github).await {
+                            Ok(push) => match push.commit_sha {
+                                Some(sha) => println!("Pushed config of {} to GitHub ({})", hostname, sha),
+                                None => println!("Config of {} is unchanged on GitHub", hostname),
+                            },
+                            Err(e) => eprintln!("GitHub push of {}'s config failed: {}", hostname, e),
+                        }
+                    }
+                    if let Some(app_handle) = app_handle {
+                        let _ = app_handle.emit("config-backup-created", backup);
+                    }
+                }
+                Err(e) => eprintln!("Config backup of {} failed: {}", hostname, e),
+            }
+            running.lock().await.remove(&ip);
+        });
+    }
+
+    /// Records a telemetry sample of a host once the sample interval has passed
+    ///
+    /// Offline hosts are recorded without readings, so outages show up in the data.
+    async fn run_telemetry(context: &MonitorContext, host: &HostInfo, status: &HostStatusResponse, settings: &TelemetrySettings) {
+        if !settings.enabled {
+            return;
+        }
+        {
+            let interval = Duration::from_secs(settings.sample_interval_seconds);
+            let mut samples = context.telemetry_samples.lock().await;
+            if samples.get(&host.ip_address).is_some_and(|t| t.elapsed() < interval) {
+                return;
+            }
+            samples.insert(host.ip_address.clone(), Instant::now());
+        }
+
+        let state = status.state();
+        let sample = if status.success {
+            get_telemetry_sample(&host.ip_address, state)
+                .await
+                .unwrap_or_else(|_| TelemetrySample::state_only(&host.ip_address, state))
+        } else {
+            TelemetrySample::state_only(&host.ip_address, state)
+        };
+        let (path, retention) = (settings.database_path(), settings.retention());
+        let recorded = tokio::task::spawn_blocking(move || record_sample(&path, &sample, retention))
+            .await
+            .unwrap_or_else(|e| Err(e.to_string()));
+        if let Err(e) = recorded {
+            eprintln!("Telemetry of {} not recorded: {}", host.hostname, e);
+        }
+    }
+
+    /// Evaluates the automation rules of a host and runs the actions of the
+    /// rules whose conditions just started to hold
+    async fn run_rules(context: &MonitorContext, host: &HostInfo, status: &HostStatusResponse, rules: &AutomationRules) {
+        let applying: Vec<&AutomationRule> = rules.rules.iter().filter(|rule| rule.applies_to(&host.ip_address)).collect();
+        if applying.is_empty() {
+            return;
+        }
+
+        let mut metrics = collect_metrics(&host.ip_address, status, &applying).await;
+        let triggered: Vec<&AutomationRule> = {
+            let mut engine = context.rule_engine.lock().await;
+            metrics.stalled_minutes = engine.record_progress(&host.ip_address, metrics.state, metrics.progress);
+            applying
+                .into_iter()
+                .filter(|rule| engine.update(&rule.id, &host.ip_address, rule.matches(&metrics)))
+                .collect()
+        };
+        if triggered.is_empty() {
+            return;
+        }
+
+        for rule in &triggered {
+            println!("Rule \"{}\" triggered for {}", rule.name, host.hostname);
+            for action in &rule.actions {
+                if let Err(e) = Self::run_rule_action(context, host, rule, action, &metrics).await {
+                    eprintln!("Rule \"{}\" failed on {}: {}", rule.name, host.hostname, e);
+                }
+            }
+            if let Some(app_handle) = &context.app_handle {
+                let _ = app_handle.emit("rule-triggered", serde_json::json!({
+                    "rule_id": rule.id,
+                    "rule": rule.name,
+                    "host": host.ip_address,
+                    "hostname": host.hostname,
+                    "metrics": metrics,
+                }));
+            }
+        }
+
+        // Rules may have been edited while the actions ran
+        let now = chrono::Utc::now().to_rfc3339();
+        if let Ok(mut current) = AutomationRules::load() {
+            for rule in current.rules.iter_mut().filter(|rule| triggered.iter().any(|t| t.id == rule.id)) {
+                rule.last_triggered = Some(now.clone());
+            }
+            if let Err(e) = current.save() {
+                eprintln!("Failed to save automation rules: {}", e);
+            }
+        }
+    }
+
+    /// Runs one action of a triggered rule
+    async fn run_rule_action(
+        context: &MonitorContext,
+        host: &HostInfo,
+        rule: &AutomationRule,
+        action: &RuleAction,
+        metrics: &RuleMetrics,
+    ) -> Result<(), String> {
+        match action {
+            RuleAction::Notify { channel, message } => {
+                let title = t("notify.rule.title");
+                let body = match message.as_deref().map(str::trim).filter(|m| !m.is_empty()) {
+                    Some(message) => format!("{}: {}", host.hostname, message),
+                    None => tf("notify.rule.body", &[("host", &host.hostname), ("rule", &rule.name)]),
+                };
+                if *channel != NotifyChannel::Telegram {
+                    send_notification(title, &body);
+                }
+                if *channel != NotifyChannel::Desktop {
+                    if let Some(bot) = context.telegram_bot.lock().await.as_ref() {
+                        bot.send_notification_to_all_users(title, &body, Some(&host.ip_address))
+                            .await
+                            .map_err(|e| e.to_string())?;
+                    }
+                }
+                Ok(())
+            }
+            RuleAction::Webhook { url, include_snapshot } => {
+                let snapshot = if *include_snapshot {
+                    Self::capture_snapshot(&host.ip_address, &context.client).await
+                } else {
+                    None
+                };
+                send_rule_webhook(url, rule, host, metrics, snapshot).await
+            }
+            RuleAction::RunMacro { gcode } => run_gcode(&host.ip_address, gcode).await.map(|_| ()).map_err(|e| e.to_string()),
+            RuleAction::PowerDevice { device, on } => {
+                set_power_device(&host.ip_address, device, *on).await.map(|_| ()).map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    /// Saves snapshots of a running print and builds the timelapse when it ends
+    async fn run_snapshot_archive(
+        context: &MonitorContext,
+        host: &HostInfo,
+        status: &HostStatusResponse,
+        settings: &SnapshotArchiveSettings,
+    ) {
+        let printing = status.printer_state.is_some_and(|state| state.has_active_job());
+
+        if !printing {
+            // Offline hosts may still be printing; only close the archive on a definite state
+            if !status.success {
+                return;
+            }
+            let finished = context.archiver.lock().await.finish(&host.ip_address);
+            if let Some(job) = finished {
+                Self::finish_snapshot_archive(context, job, settings).await;
+            }
+            return;
+        }
+
+        if !settings.enabled {
+            return;
+        }
+
+        if !context.archiver.lock().await.should_capture(&host.ip_address, settings.interval_minutes.max(1)) {
+            return;
+        }
+
+        let job_name = match get_print_info(&host.ip_address, None).await {
+            Ok(Some(job)) => job.filename,
+            _ => "print".to_string(),
+        };
+        let image_data = match fetch_snapshot(&host.ip_address, &context.client).await {
+            Ok(data) => data,
+            Err(e) => {
+                eprintln!("Snapshot archive: snapshot from {} failed: {}", host.ip_address, e);
+                return;
+            }
+        };
+
+        let base_dir = settings.archive_dir();
+        let result = context.archiver.lock().await.save_frame(&base_dir, &host.ip_address, &job_name, &image_data);
+        if let Err(e) = result {
+            eprintln!("Snapshot archive: {}", e);
+        }
+    }
+
+    /// Stitches the snapshots of a finished print into a timelapse
+    async fn finish_snapshot_archive(context: &MonitorContext, mut job: crate::webcam::ArchivedJob, settings: &SnapshotArchiveSettings) {
+        if settings.create_timelapse && job.frame_count >= 2 {
+            let directory = std::path::PathBuf::from(&job.directory);
+            let delay = settings.timelapse_frame_delay_ms;
+            let max_width = settings.timelapse_max_width;
+            let result = tokio::task::spawn_blocking(move || build_timelapse(&directory, delay, max_width)).await;
+            match result {
+                Ok(Ok(path)) => {
+                    println!("Timelapse created: {}", path.display());
+                    job.timelapse = Some(path.to_string_lossy().to_string());
+                }
+                Ok(Err(e)) => eprintln!("Timelapse for {} failed: {}", job.host, e),
+                Err(e) => eprintln!("Timelapse task failed: {}", e),
+            }
+        }
+
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("print-archived", job);
+        }
+    }
+
+    /// Sends a "print may have failed" warning to the UI, the system and Telegram
+    async fn report_failure_warning(context: &MonitorContext, host: &HostInfo, warning: &PrintFailureWarning, snapshot: &[u8]) {
+        let title = t("notify.print_failure.title");
+        let body = format!("{}: {}", host.hostname, warning.message);
+
+        send_notification_with_image(title, &body, Some(snapshot));
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("print-failure-warning", warning.clone());
+        }
+
+        let bot_guard = context.telegram_bot.lock().await;
+        if let Some(ref bot) = *bot_guard {
+            if let Err(e) = bot.send_notification_to_all_users(title, &body, Some(&host.ip_address)).await {
+                eprintln!("Failed to send failure warning to Telegram: {}", e);
+            }
+        }
+    }
+
+    /// Announces a job that finished since the previous cycle, with its estimated cost,
+    /// and queues the host for the cooldown notification and automatic power off
+    async fn track_job_completion(
+        context: &MonitorContext,
+        host: &HostInfo,
+        status: &HostStatusResponse,
+        settings: &AppSettings,
+    ) {
+        let state = status.printer_state.unwrap_or(PrinterState::Offline);
+        let job_ended = {
+            let mut printing_hosts = context.printing_hosts.lock().await;
+            if state.has_active_job() {
+                printing_hosts.insert(host.ip_address.clone(), state);
+                return;
+            }
+            state.ends_job(printing_hosts.remove(&host.ip_address))
+        };
+
+        let auto_power_off = settings.auto_power_off.contains_key(&host.ip_address);
+        let cooldown = settings.cooldown.enabled;
+        let webhooks: Vec<CompletionWebhook> =
+            settings.completion_webhooks.iter().filter(|webhook| webhook.applies_to(&host.ip_address)).cloned().collect();
+        if !job_ended || (!settings.notifications.completed && !auto_power_off && !cooldown && webhooks.is_empty()) {
+            return;
+        }
+
+        // print_stats keeps the finished job until the next one starts
+        let job = match get_print_info(&host.ip_address, None).await {
+            Ok(Some(job)) if job.status == "complete" => job,
+            _ => return,
+        };
+
+        if auto_power_off {
+            context.power_off_pending.lock().await.insert(host.ip_address.clone());
+        }
+        if cooldown {
+            context.cooldown_pending.lock().await.insert(host.ip_address.clone());
+        }
+
+        let title = t("notify.print_finished.title");
+        let values = TemplateValues {
+            hostname: host.hostname.clone(),
+            ip: host.ip_address.clone(),
+            status: status_name("complete").to_string(),
+            previous_status: PrinterState::Printing.label().to_string(),
+            progress: Some(job.progress.progress),
+            eta: None,
+            filename: Some(job.filename.clone()),
+        };
+        let mut body = render_or_default(settings.notifications.templates.print_finished.as_deref(), &values, || {
+            format!("{}: {} ({})", host.hostname, job.filename, format_duration(job.progress.print_duration))
+        });
+        if let Some(cost) = &job.estimated_cost {
+            body.push_str(&format!("\n{}", tf("notify.cost", &[("cost", &cost.summary())])));
+        }
+
+        if !webhooks.is_empty() {
+            Self::send_completion_webhooks(context, host, &job, &body, webhooks);
+        }
+        if !settings.notifications.completed {
+            return;
+        }
+
+        // The desktop frontend already notifies about the status change to complete
+        if context.standalone {
+            let snapshot = Self::capture_snapshot(&host.ip_address, &context.client).await;
+            send_notification_with_image(title, &body, snapshot.as_deref());
+        }
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("print-completed", job.clone());
+        }
+
+        let animation = match settings.completion_gif.get(&host.ip_address) {
+            Some(gif_settings) if context.telegram_bot.lock().await.is_some() => {
+                Self::build_completion_gif(context, host, &job.filename, gif_settings).await
+            }
+            _ => None,
+        };
+
+        let bot_guard = context.telegram_bot.lock().await;
+        if let Some(ref bot) = *bot_guard {
+            let result = match animation {
+                Some(animation) => bot.send_animation_to_all_users(title, &body, animation).await,
+                None => bot.send_notification_to_all_users(title, &body, Some(&host.ip_address)).await,
+            };
+            if let Err(e) = result {
+                eprintln!("Failed to send completion notification to Telegram: {}", e);
+            }
+        }
+    }
+
+    /// Posts a finished job to the completion webhooks, with the final snapshot where wanted
+    ///
+    /// The payload carries the notification text, rendered from the user's
+    /// template, as `message` and as `content`, which Discord shows.
+    /// Runs in the background, so slow receivers do not hold up the monitoring cycle.
+    fn send_completion_webhooks(context: &MonitorContext, host: &HostInfo, job: &PrintJobInfo, message: &str, webhooks: Vec<CompletionWebhook>) {
+        let payload = serde_json::json!({
+            "event": "print_complete",
+            "host": host.ip_address,
+            "hostname": host.hostname,
+            "message": message,
+            "content": message,
+            "job": job,
+            "finished_at": chrono::Utc::now().to_rfc3339(),
+        });
+        let (ip, client) = (host.ip_address.clone(), context.client.clone());
+        tokio::spawn(async move {
+            let snapshot = if webhooks.iter().any(|webhook| webhook.include_snapshot) {
+                Self::capture_snapshot(&ip, &client).await
+            } else {
+                None
+            };
+            for webhook in &webhooks {
+                let image = snapshot.clone().filter(|_| webhook.include_snapshot);
+                if let Err(e) = post_webhook(&webhook.url, &payload, image).await {
+                    eprintln!("Completion webhook {} for {} failed: {}", webhook.url, ip, e);
+                }
+            }
+        });
+    }
+
+    /// Takes a webcam snapshot to post with a webhook or show in a notification
+    async fn capture_snapshot(ip: &str, client: &reqwest::Client) -> Option<Vec<u8>> {
+        match fetch_snapshot(ip, client).await {
+            Ok(snapshot) => Some(snapshot),
+            Err(e) => {
+                eprintln!("Snapshot from {} failed: {}", ip, e);
+                None
+            }
+        }
+    }
+
+    /// Builds the GIF of a finished print from its archived snapshots, or
+    /// from a few snapshots taken now when the print was not archived
+    async fn build_completion_gif(
+        context: &MonitorContext,
+        host: &HostInfo,
+        filename: &str,
+        settings: &CompletionGifSettings,
+    ) -> Option<Vec<u8>> {
+        let archived = context.archiver.lock().await.take_finished(&host.ip_address, filename);
+        let gif_settings = settings.clone();
+        let result = match archived {
+            Some(directory) => {
+                tokio::task::spawn_blocking(move || completion_gif_from_archive(&directory, &gif_settings)).await
+            }
+            None => {
+                let mut snapshots = Vec::new();
+                for i in 0..settings.end_frames {
+                    if i > 0 {
+                        sleep(Duration::from_millis(COMPLETION_GIF_FRAME_INTERVAL_MS)).await;
+                    }
+                    match fetch_snapshot(&host.ip_address, &context.client).await {
+                        Ok(data) => snapshots.push(data),
+                        Err(e) => {
+                            eprintln!("Completion GIF: snapshot from {} failed: {}", host.ip_address, e);
+                            break;
+                        }
+                    }
+                }
+                tokio::task::spawn_blocking(move || completion_gif_from_snapshots(&snapshots, &gif_settings)).await
+            }
+        };
+
+        match result {
+            Ok(Ok(gif)) => Some(gif),
+            Ok(Err(e)) => {
+                eprintln!("Completion GIF for {} failed: {}", host.ip_address, e);
+                None
+            }
+            Err(e) => {
+                eprintln!("Completion GIF task failed: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Sends the pause/cancel rules of a host that are due and expires those of ended prints
+    async fn run_scheduled_actions(context: &MonitorContext, host: &HostInfo, status: &HostStatusResponse) {
+        if !status.success {
+            return;
+        }
+
+        let mut schedules = match ScheduledActions::load() {
+            Ok(schedules) if schedules.has_pending(&host.ip_address) => schedules,
+            _ => return,
+        };
+
+        // Without a readable job state nothing can be decided
+        let job = match get_print_info(&host.ip_address, None).await {
+            Ok(job) => job.filter(|j| j.status == "printing" || j.status == "paused"),
+            Err(_) => return,
+        };
+
+        let now = chrono::Utc::now();
+        let mut fired = Vec::new();
+        let mut finished = Vec::new();
+        for action in schedules
+            .actions
+            .iter_mut()
+            .filter(|a| a.host == host.ip_address && a.status == ScheduleStatus::Pending)
+        {
+            let job = match &job {
+                Some(job) if job.filename == action.filename => job,
+                _ => {
+                    action.finish(ScheduleStatus::Expired, None);
+                    finished.push(action.clone());
+                    continue;
+                }
+            };
+
+            match action.evaluate(now, job.progress.progress) {
+                ScheduleDecision::Wait => {}
+                ScheduleDecision::Skip => {
+                    action.finish(ScheduleStatus::Skipped, None);
+                    finished.push(action.clone());
+                }
+                ScheduleDecision::Run => {
+                    let already_paused = action.action == ScheduledActionKind::Pause && job.status == "paused";
+                    let result = if already_paused {
+                        Ok(())
+                    } else {
+                        control_printer_with_string(&host.ip_address, action.action.as_str()).await.map(|_| ())
+                    };
+                    match result {
+                        Ok(()) => action.finish(ScheduleStatus::Executed, None),
+                        Err(e) => action.finish(ScheduleStatus::Failed, Some(e.to_string())),
+                    }
+                    finished.push(action.clone());
+                    fired.push((action.clone(), job.progress.progress));
+                }
+            }
+        }
+
+        // The file may have changed while the printer was asked; only the
+        // finished actions are written back
+        if !finished.is_empty() {
+            if let Err(e) = ScheduledActions::update(|schedules| schedules.merge_finished(&finished)) {
+                eprintln!("Failed to save scheduled actions: {}", e);
+            }
+        }
+
+        for (action, progress) in fired {
+            Self::announce_scheduled_action(context, host, &action, progress).await;
+        }
+    }
+
+    /// Notifies about a scheduled action that was sent or failed
+    async fn announce_scheduled_action(context: &MonitorContext, host: &HostInfo, action: &ScheduledAction, progress: f64) {
+        let progress = format!("{:.1}", progress);
+        let body = match &action.error {
+            Some(error) => tf("notify.scheduled_action.failed", &[
+                ("host", &host.hostname),
+                ("action", &action.action.as_str()),
+                ("file", &action.filename),
+                ("error", error),
+            ]),
+            None => tf(&format!("notify.scheduled_action.{}", action.action.as_str()), &[
+                ("host", &host.hostname),
+                ("file", &action.filename),
+                ("progress", &progress),
+            ]),
+        };
+        let title = t("notify.scheduled_action.title");
+
+        send_notification(title, &body);
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("scheduled-action-fired", action.clone());
+        }
+
+        let bot_guard = context.telegram_bot.lock().await;
+        if let Some(ref bot) = *bot_guard {
+            if let Err(e) = bot.send_notification_to_all_users(title, &body, Some(&host.ip_address)).await {
+                eprintln!("Failed to send scheduled action to Telegram: {}", e);
+            }
+        }
+    }
+
+    /// Notifies once the hotend and bed of a finished print are cool enough to remove the part
+    async fn run_cooldown_check(context: &MonitorContext, host: &HostInfo, status: &HostStatusResponse, settings: &AppSettings) {
+        if !context.cooldown_pending.lock().await.contains(&host.ip_address) {
+            return;
+        }
+
+        // Disabled meanwhile, or a new print started before the bed cooled down
+        let printing = status.printer_state.is_some_and(|state| state.has_active_job());
+        if !settings.cooldown.enabled || printing {
+            context.cooldown_pending.lock().await.remove(&host.ip_address);
+            return;
+        }
+
+        if !status.success {
+            return;
+        }
+
+        let temperatures = match get_heater_temperatures(&host.ip_address).await {
+            Ok(temperatures) => temperatures,
+            Err(_) => return,
+        };
+        // Every tool of a multi-extruder printer has to cool down
+        let cooled = temperatures.hottest_hotend() < settings.cooldown.hotend_threshold
+            && temperatures.bed.is_none_or(|bed| bed < settings.cooldown.bed_threshold);
+        if !cooled {
+            return;
+        }
+
+        context.cooldown_pending.lock().await.remove(&host.ip_address);
+
+        let hotend = format!("{:.0}", temperatures.hottest_hotend());
+        let body = match temperatures.bed {
+            Some(bed) => tf("notify.cooldown.body", &[
+                ("host", &host.hostname),
+                ("hotend", &hotend),
+                ("bed", &format!("{:.0}", bed)),
+            ]),
+            None => tf("notify.cooldown.body_hotend", &[("host", &host.hostname), ("hotend", &hotend)]),
+        };
+        let title = t("notify.cooldown.title");
+
+        send_notification(title, &body);
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("cooldown-complete", serde_json::json!({
+                "host": host.ip_address,
+                "hostname": host.hostname,
+                "temperatures": temperatures,
+            }));
+        }
+
+        let bot_guard = context.telegram_bot.lock().await;
+        if let Some(ref bot) = *bot_guard {
+            if let Err(e) = bot.send_notification_to_all_users(title, &body, Some(&host.ip_address)).await {
+                eprintln!("Failed to send cooldown notification to Telegram: {}", e);
+            }
+        }
+    }
+
+    /// Notifies when an additional sensor reading leaves its configured limits,
+    /// e.g. a filament dryer getting too humid, and again once it is back
+    async fn run_sensor_alerts(context: &MonitorContext, host: &HostInfo, status: &HostStatusResponse, settings: &AppSettings) {
+        let Some(configs) = settings.sensors.get(&host.ip_address) else {
+            return;
+        };
+        if !status.success || !configs.values().any(|config| config.has_thresholds()) {
+            return;
+        }
+        let sensors = match get_sensors(&host.ip_address).await {
+            Ok(sensors) => sensors,
+            Err(_) => return,
+        };
+        let silenced = settings.is_alert_silenced(&host.ip_address, AlertKind::Sensor, chrono::Utc::now());
+
+        for mut sensor in sensors {
+            let Some(config) = configs.get(&sensor.name) else {
+                continue;
+            };
+            sensor.label = config.label.clone();
+
+            // (kind, reading, limit, whether the limit is an upper one)
+            let checks = [
+                ("temperature_high", sensor.temperature, config.max_temperature, true),
+                ("temperature_low", sensor.temperature, config.min_temperature, false),
+                ("humidity_high", sensor.humidity, config.max_humidity, true),
+            ];
+            for (kind, value, limit, upper) in checks {
+                let key = format!("{}/{}/{}", host.ip_address, sensor.name, kind);
+                let (Some(value), Some(limit)) = (value, limit) else {
+                    context.sensor_alerts.lock().await.remove(&key);
+                    continue;
+                };
+                let outside = if upper { value > limit } else { value < limit };
+                let recovered = if upper {
+                    value <= limit - SENSOR_ALERT_HYSTERESIS
+                } else {
+                    value >= limit + SENSOR_ALERT_HYSTERESIS
+                };
+
+                let value_text = format!("{:.1}", value);
+                let body = {
+                    let mut alerts = context.sensor_alerts.lock().await;
+                    if outside && alerts.insert(key.clone()) {
+                        tf(&format!("notify.sensor_alert.{}", kind), &[
+                            ("host", &host.hostname),
+                            ("sensor", &sensor.display_name()),
+                            ("value", &value_text),
+                            ("limit", &format!("{:.1}", limit)),
+                        ])
+                    } else if recovered && alerts.remove(&key) {
+                        tf("notify.sensor_alert.recovered", &[
+                            ("host", &host.hostname),
+                            ("sensor", &sensor.display_name()),
+                            ("value", &value_text),
+                        ])
+                    } else {
+                        continue;
+                    }
+                };
+                let title = t("notify.sensor_alert.title");
+
+                if let Some(app_handle) = &context.app_handle {
+                    let _ = app_handle.emit("sensor-alert", serde_json::json!({
+                        "host": host.ip_address,
+                        "hostname": host.hostname,
+                        "sensor": sensor,
+                        "kind": kind,
+                        "active": outside,
+                        "silenced": silenced,
+                    }));
+                }
+                if silenced {
+                    continue;
+                }
+                send_notification(title, &body);
+
+                let bot_guard = context.telegram_bot.lock().await;
+                if let Some(ref bot) = *bot_guard {
+                    let sent = if outside {
+                        bot.send_alert_to_all_users(title, &body, &host.ip_address, AlertKind::Sensor).await
+                    } else {
+                        bot.send_notification_to_all_users(title, &body, Some(&host.ip_address)).await
+                    };
+                    if let Err(e) = sent {
+                        eprintln!("Failed to send sensor alert to Telegram: {}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Raises a critical alert when a heater runs at full power without heating up,
+    /// independently of Klipper's own heater verification
+    async fn run_heater_watch(context: &MonitorContext, host: &HostInfo, status: &HostStatusResponse, app_settings: &AppSettings) {
+        let settings = &app_settings.heater_watch;
+        if !settings.enabled || !status.success || status.klippy_state != Some(KlippyState::Ready) {
+            context.heater_watchdog.lock().await.forget_host(&host.ip_address);
+            return;
+        }
+        let readings = match get_heater_readings(&host.ip_address).await {
+            Ok(readings) => readings,
+            Err(_) => return,
+        };
+
+        let now = Instant::now();
+        let alarms: Vec<_> = {
+            let mut watchdog = context.heater_watchdog.lock().await;
+            readings
+                .iter()
+                .filter_map(|reading| watchdog.check(&host.ip_address, reading, settings, now))
+                .collect()
+        };
+        for alarm in alarms {
+            let title = t("notify.heater_watch.title");
+            let body = tf("notify.heater_watch.body", &[
+                ("host", &host.hostname),
+                ("heater", &alarm.heater),
+                ("power", &format!("{:.0}", alarm.power * 100.0)),
+                ("seconds", &alarm.seconds.to_string()),
+                ("from", &format!("{:.1}", alarm.start_temperature)),
+                ("to", &format!("{:.1}", alarm.temperature)),
+            ]);
+
+            let silenced = app_settings.is_alert_silenced(&host.ip_address, AlertKind::HeaterWatch, chrono::Utc::now());
+            if let Some(app_handle) = &context.app_handle {
+                let _ = app_handle.emit("heater-alarm", serde_json::json!({
+                    "host": host.ip_address,
+                    "hostname": host.hostname,
+                    "heater": alarm.heater,
+                    "power": alarm.power,
+                    "start_temperature": alarm.start_temperature,
+                    "temperature": alarm.temperature,
+                    "seconds": alarm.seconds,
+                    "silenced": silenced,
+                }));
+            }
+            if !silenced {
+                Self::send_host_alert(context, host, AlertKind::HeaterWatch, title, &body, true).await;
+            }
+        }
+    }
+
+    /// Reports heaters left on while a host is idle and turns them off if configured
+    async fn run_heater_idle(context: &MonitorContext, host: &HostInfo, status: &HostStatusResponse, app_settings: &AppSettings) {
+        let settings = &app_settings.heater_idle;
+        let idle = status.success
+            && status.klippy_state == Some(KlippyState::Ready)
+            && status.printer_state == Some(PrinterState::Standby);
+        if !settings.enabled || !idle {
+            context.heater_idle.lock().await.forget_host(&host.ip_address);
+            return;
+        }
+        let readings = match get_heater_readings(&host.ip_address).await {
+            Ok(readings) => readings,
+            Err(_) => return,
+        };
+        let found = context.heater_idle.lock().await.check(&host.ip_address, idle, &readings, settings, Instant::now());
+        let Some(idle_heaters) = found else {
+            return;
+        };
+
+        let heaters = idle_heaters.heaters.join(", ");
+        let minutes = idle_heaters.minutes.to_string();
+        let args: [(&str, &(dyn std::fmt::Display + Sync)); 3] = [("host", &host.hostname), ("heaters", &heaters), ("minutes", &minutes)];
+        let (body, turned_off) = match settings.action {
+            HeaterIdleAction::Notify => (tf("notify.heater_idle.body", &args), false),
+            HeaterIdleAction::TurnOff => match run_gcode(&host.ip_address, "TURN_OFF_HEATERS").await {
+                Ok(_) => (tf("notify.heater_idle.turned_off", &args), true),
+                Err(e) => {
+                    eprintln!("Failed to turn off the heaters of {}: {}", host.hostname, e);
+                    let error = e.to_string();
+                    (tf("notify.heater_idle.turn_off_failed", &[("host", &host.hostname), ("heaters", &heaters), ("minutes", &minutes), ("error", &error)]), false)
+                }
+            },
+        };
+
+        // Heaters are still turned off while the warning is snoozed
+        let silenced = app_settings.is_alert_silenced(&host.ip_address, AlertKind::HeaterIdle, chrono::Utc::now());
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("heater-idle", serde_json::json!({
+                "host": host.ip_address,
+                "hostname": host.hostname,
+                "heaters": idle_heaters.heaters,
+                "minutes": idle_heaters.minutes,
+                "turned_off": turned_off,
+                "silenced": silenced,
+            }));
+        }
+        if !silenced {
+            Self::send_host_alert(context, host, AlertKind::HeaterIdle, t("notify.heater_idle.title"), &body, false).await;
+        }
+    }
+
+    /// Sends an alert about a host as notification and to Telegram, with snooze buttons
+    ///
+    /// Critical alerts are repeated until they are acknowledged.
+    async fn send_host_alert(context: &MonitorContext, host: &HostInfo, kind: AlertKind, title: &str, body: &str, critical: bool) {
+        if critical {
+            send_critical_notification(title, body);
+            context.unacknowledged_alerts.lock().await.raise(&host.ip_address, kind, title, body, Instant::now());
+        } else {
+            send_notification(title, body);
+        }
+
+        let bot_guard = context.telegram_bot.lock().await;
+        if let Some(ref bot) = *bot_guard {
+            if let Err(e) = bot.send_alert_to_all_users(title, body, &host.ip_address, kind).await {
+                eprintln!("Failed to send {} alert of {} to Telegram: {}", kind.as_str(), host.hostname, e);
+            }
+        }
+    }
+
+    /// Warns when the link to one of a host's micro-controllers keeps retransmitting,
+    /// before Klipper loses communication with it
+    async fn run_mcu_watch(context: &MonitorContext, host: &HostInfo, status: &HostStatusResponse, app_settings: &AppSettings) {
+        let settings = &app_settings.mcu_watch;
+        if !settings.enabled || !status.success || status.klippy_state != Some(KlippyState::Ready) {
+            context.mcu_watch.lock().await.forget_host(&host.ip_address);
+            return;
+        }
+        let mcus = match get_mcu_stats(&host.ip_address).await {
+            Ok(mcus) => mcus,
+            Err(_) => return,
+        };
+
+        let now = Instant::now();
+        let alarms: Vec<_> = {
+            let mut watch = context.mcu_watch.lock().await;
+            mcus.iter()
+                .filter_map(|mcu| watch.check(&host.ip_address, mcu, settings, now))
+                .collect()
+        };
+        for alarm in alarms {
+            let title = t("notify.mcu_watch.title");
+            let body = tf("notify.mcu_watch.body", &[
+                ("host", &host.hostname),
+                ("mcu", &alarm.mcu),
+                ("rate", &format!("{:.0}", alarm.per_minute)),
+                ("invalid", &alarm.bytes_invalid.to_string()),
+            ]);
+
+            let silenced = app_settings.is_alert_silenced(&host.ip_address, AlertKind::McuLink, chrono::Utc::now());
+            if let Some(app_handle) = &context.app_handle {
+                let _ = app_handle.emit("mcu-link-alert", serde_json::json!({
+                    "host": host.ip_address,
+                    "hostname": host.hostname,
+                    "mcu": alarm.mcu,
+                    "retransmits": alarm.retransmits,
+                    "per_minute": alarm.per_minute,
+                    "bytes_invalid": alarm.bytes_invalid,
+                    "silenced": silenced,
+                }));
+            }
+            if !silenced {
+                Self::send_host_alert(context, host, AlertKind::McuLink, title, &body, false).await;
+            }
+        }
+    }
+
+    /// Switches a host with a finished print off once its nozzle has cooled down
+    async fn run_auto_power_off(context: &MonitorContext, host: &HostInfo, status: &HostStatusResponse, settings: &AppSettings) {
+        if !context.power_off_pending.lock().await.contains(&host.ip_address) {
+            return;
+        }
+
+        // Without an explicit device the wrong plug could be switched off
+        let config = settings.auto_power_off.get(&host.ip_address);
+        let device = config.and_then(|c| c.device.clone()).filter(|d| !d.trim().is_empty());
+        let (Some(config), Some(device)) = (config, device) else {
+            context.power_off_pending.lock().await.remove(&host.ip_address);
+            return;
+        };
+
+        if !status.success {
+            return;
+        }
+
+        // A new print started before the printer cooled down
+        if status.printer_state.is_some_and(|state| state.has_active_job()) {
+            context.power_off_pending.lock().await.remove(&host.ip_address);
+            return;
+        }
+
+        let temperature = match get_heater_temperatures(&host.ip_address).await {
+            Ok(temperatures) if temperatures.hottest_hotend() < config.temperature_threshold => temperatures.hottest_hotend(),
+            _ => return,
+        };
+
+        // One attempt only, a failing plug should not be switched every cycle
+        context.power_off_pending.lock().await.remove(&host.ip_address);
+
+        let result = set_power_device(&host.ip_address, &device, false).await.map(|_| ()).map_err(|e| e.to_string());
+
+        let temperature = format!("{:.0}", temperature);
+        let body = match &result {
+            Ok(()) => tf("notify.power_off.body", &[
+                ("host", &host.hostname),
+                ("device", &device),
+                ("temperature", &temperature),
+            ]),
+            Err(error) => tf("notify.power_off.failed", &[("host", &host.hostname), ("error", error)]),
+        };
+        let title = t("notify.power_off.title");
+
+        send_notification(title, &body);
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("printer-powered-off", serde_json::json!({
+                "host": host.ip_address,
+                "hostname": host.hostname,
+                "device": device,
+                "success": result.is_ok(),
+                "message": body,
+            }));
+        }
+
+        let bot_guard = context.telegram_bot.lock().await;
+        if let Some(ref bot) = *bot_guard {
+            if let Err(e) = bot.send_notification_to_all_users(title, &body, Some(&host.ip_address)).await {
+                eprintln!("Failed to send power off notification to Telegram: {}", e);
+            }
+        }
+    }
+
+    /// Refreshes the print hours of a host with maintenance tasks and reminds about due ones
+    async fn run_maintenance_check(context: &MonitorContext, host: &HostInfo, status: &HostStatusResponse, settings: &AppSettings) {
+        if !status.success {
+            return;
+        }
+
+        {
+            let mut checks = context.maintenance_checks.lock().await;
+            if checks.get(&host.ip_address).is_some_and(|t| t.elapsed() < MAINTENANCE_CHECK_INTERVAL) {
+                return;
+            }
+            checks.insert(host.ip_address.clone(), Instant::now());
+        }
+
+        let has_tasks = settings.maintenance_schedules.get(&host.ip_address).is_some_and(|m| !m.tasks.is_empty());
+        if !has_tasks {
+            return;
+        }
+
+        let print_hours = match get_print_hours(&host.ip_address).await {
+            Ok(hours) => hours,
+            Err(e) => {
+                eprintln!("Maintenance: failed to read print hours of {}: {}", host.ip_address, e);
+                return;
+            }
+        };
+
+        let updated = AppSettings::update(|settings| {
+            settings.maintenance_schedules
+                .get_mut(&host.ip_address)
+                .map(|schedule| schedule.update_print_hours(&host.ip_address, print_hours))
+                .unwrap_or_default()
+        });
+        let reminders = match updated {
+            Ok(reminders) => reminders,
+            Err(e) => {
+                eprintln!("Maintenance: {}", e);
+                return;
+            }
+        };
+
+        for reminder in &reminders {
+            Self::report_maintenance_reminder(context, host, reminder).await;
+        }
+    }
+
+    /// Sends a maintenance reminder to the UI, the system and Telegram
+    async fn report_maintenance_reminder(context: &MonitorContext, host: &HostInfo, reminder: &MaintenanceReminder) {
+        let title = t("notify.maintenance_due.title");
+        let body = tf("notify.maintenance_due.body", &[
+            ("host", &host.hostname),
+            ("task", &reminder.task_name),
+            ("hours", &format!("{:.0}", reminder.print_hours)),
+        ]);
+
+        send_notification(title, &body);
+        if let Some(app_handle) = &context.app_handle {
+            let _ = app_handle.emit("maintenance-reminder", reminder.clone());
+        }
+
+        let bot_guard = context.telegram_bot.lock().await;
+        if let Some(ref bot) = *bot_guard {
+            if let Err(e) = bot.send_notification_to_all_users(title, &body, None).await {
+                eprintln!("Failed to send maintenance reminder to Telegram: {}", e);
+            }
+        }
+    }
 }
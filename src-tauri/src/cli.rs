@@ -0,0 +1,227 @@
+//! Command line interface
+//!
+//! This module defines the command line arguments accepted by the binary.
+//! Without arguments the desktop application starts as usual; subcommands
+//! reuse the library modules for scripting and cron jobs, e.g.
+//! `mhs scan 192.168.1.0/24 --json` or `mhs pause 192.168.1.50`.
+
+use clap::{Parser, Subcommand};
+use crate::api::print_info::{format_duration, get_print_info};
+use crate::i18n::format_finish_time;
+use crate::api::printer::{control_printer, PrinterAction};
+use crate::models::config::ScanProfile;
+use crate::models::SubnetConfig;
+use crate::network::scanner::{check_host_status, scan_network};
+
+/// Exit code for a failed command
+pub(crate) const EXIT_FAILURE: i32 = 1;
+
+/// Exit code for `status` when the host is offline
+const EXIT_OFFLINE: i32 = 2;
+
+/// Moonraker Host Scanner command line arguments
+#[derive(Debug, Parser)]
+#[command(name = "mhs", version, about = "Moonraker Host Scanner")]
+pub struct Cli {
+    /// Run without a window: scanning, monitoring, Telegram and notifications only
+    #[arg(long)]
+    pub headless: bool,
+
+    /// Subnet to scan in headless mode, e.g. 192.168.1.0/24 (repeatable, overrides settings)
+    #[arg(long = "subnet", value_name = "CIDR")]
+    pub subnets: Vec<String>,
+
+    /// Run a single command and exit instead of starting the application
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+}
+
+/// Scripting subcommands
+#[derive(Debug, Subcommand)]
+pub enum CliCommand {
+    /// Scan subnets for Moonraker hosts
+    Scan {
+        /// Subnets in CIDR notation
+        #[arg(required = true, value_name = "CIDR")]
+        subnets: Vec<String>,
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show the status of a host (exit code 2 when offline)
+    Status {
+        /// Host IP address
+        ip: String,
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show the current print job of a host
+    Job {
+        /// Host IP address
+        ip: String,
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Pause the current print
+    Pause {
+        /// Host IP address
+        ip: String,
+    },
+    /// Resume a paused print
+    Resume {
+        /// Host IP address
+        ip: String,
+    },
+    /// Cancel the current print
+    Cancel {
+        /// Host IP address
+        ip: String,
+    },
+    /// Emergency stop the printer
+    EmergencyStop {
+        /// Host IP address
+        ip: String,
+    },
+}
+
+/// Parses command line arguments
+///
+/// Help and version requests print and exit. Unknown arguments (e.g. ones
+/// injected by the OS when launching an app bundle) fall back to the
+/// default desktop mode instead of aborting the launch.
+pub fn parse_args() -> Cli {
+    match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => match e.kind() {
+            clap::error::ErrorKind::DisplayHelp
+            | clap::error::ErrorKind::DisplayVersion
+            | clap::error::ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand => e.exit(),
+            // A recognized subcommand with bad arguments is a user error, not an OS quirk
+            _ if std::env::args().nth(1).is_some_and(|arg| is_subcommand(&arg)) => e.exit(),
+            _ => Cli {
+                headless: false,
+                subnets: Vec::new(),
+                command: None,
+            },
+        },
+    }
+}
+
+/// Checks whether an argument names one of the subcommands
+fn is_subcommand(arg: &str) -> bool {
+    use clap::CommandFactory;
+    Cli::command().get_subcommands().any(|c| c.get_name() == arg)
+}
+
+/// Runs a subcommand on a new Tokio runtime
+///
+/// # Returns
+/// * Process exit code
+pub fn run_blocking(command: CliCommand) -> i32 {
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to create Tokio runtime: {}", e);
+            return EXIT_FAILURE;
+        }
+    };
+
+    match runtime.block_on(run_command(command)) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            EXIT_FAILURE
+        }
+    }
+}
+
+/// Runs a subcommand
+///
+/// # Returns
+/// * Process exit code
+pub async fn run_command(command: CliCommand) -> Result<i32, String> {
+    match command {
+        CliCommand::Scan { subnets, json } => {
+            let subnets = subnets
+                .into_iter()
+                .map(|range| SubnetConfig {
+                    name: range.clone(),
+                    range,
+                    enabled: true,
+                    profile: ScanProfile::Lan,
+                })
+                .collect();
+            let result = scan_network(subnets).await.map_err(|e| e.to_string())?;
+
+            if json {
+                print_json(&result)?;
+            } else {
+                println!("IP               HOSTNAME                 STATE        MOONRAKER");
+                for host in &result.hosts {
+                    println!(
+                        "{:<16} {:<24} {:<12} {}",
+                        host.ip_address,
+                        host.hostname,
+                        host.printer_state.map_or("-", |s| s.as_str()),
+                        host.moonraker_version.as_deref().unwrap_or("-"),
+                    );
+                }
+                println!("{} host(s) found, {} address(es) scanned", result.hosts_found, result.total_scanned);
+            }
+            Ok(0)
+        }
+        CliCommand::Status { ip, json } => {
+            let status = check_host_status(&ip).await;
+            if json {
+                print_json(&status)?;
+            } else {
+                println!("Host:      {}", ip);
+                println!("Status:    {}", status.status);
+                println!("Printer:   {}", status.printer_state.map_or("-", |s| s.as_str()));
+                println!("Klippy:    {}", status.klippy_state.map(|s| s.as_str()).unwrap_or("-"));
+                println!("Moonraker: {}", status.moonraker_version.as_deref().unwrap_or("-"));
+            }
+            Ok(if status.success { 0 } else { EXIT_OFFLINE })
+        }
+        CliCommand::Job { ip, json } => {
+            let job = get_print_info(&ip, None).await.map_err(|e| e.to_string())?;
+            if json {
+                print_json(&job)?;
+            } else {
+                match job {
+                    Some(job) => {
+                        println!("File:     {}", job.filename);
+                        println!("State:    {}", job.status);
+                        println!("Progress: {:.1}%", job.progress.progress);
+                        println!("Elapsed:  {}", format_duration(job.progress.print_duration));
+                        if let Some(remaining) = job.progress.remaining_seconds() {
+                            println!("Left:     {} ({})", format_duration(remaining), format_finish_time(remaining));
+                        }
+                    }
+                    None => println!("No print job information available"),
+                }
+            }
+            Ok(0)
+        }
+        CliCommand::Pause { ip } => run_action(&ip, PrinterAction::Pause).await,
+        CliCommand::Resume { ip } => run_action(&ip, PrinterAction::Resume).await,
+        CliCommand::Cancel { ip } => run_action(&ip, PrinterAction::Cancel).await,
+        CliCommand::EmergencyStop { ip } => run_action(&ip, PrinterAction::EmergencyStop).await,
+    }
+}
+
+/// Sends a printer action and reports the result
+async fn run_action(ip: &str, action: PrinterAction) -> Result<i32, String> {
+    control_printer(ip, action).await.map_err(|e| e.to_string())?;
+    println!("{:?} sent to {}", action, ip);
+    Ok(0)
+}
+
+/// Prints a value as pretty JSON
+fn print_json<T: serde::Serialize>(value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    println!("{}", json);
+    Ok(())
+}
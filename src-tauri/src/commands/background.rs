@@ -2,8 +2,18 @@
 //! 
 //! This module contains Tauri commands for managing background monitoring
 
+use std::collections::HashMap;
 use tauri::{AppHandle, State};
 use crate::background_monitor::BackgroundMonitorState;
+use crate::models::HostHealth;
+use crate::models::config::{
+    AckEscalationSettings, AlertKind, AlertSilence, AppSettings, CompletionGifSettings, CompletionWebhook, CooldownSettings, FailureDetectionSettings, FleetIdleNotificationSettings, JobComparisonSettings,
+    ErrorCaptureSettings, HeaterIdleSettings, HeaterWatchSettings, McuWatchSettings, NotificationTemplates, OfflineEscalationSettings, SnapshotArchiveSettings,
+};
+use crate::notifications::do_not_disturb::{do_not_disturb_state, set_respect_do_not_disturb, DoNotDisturbState};
+use crate::notifications::snooze::{acknowledge_alert, active_alert_silences, snooze_alert, unsilence_alert};
+use crate::notifications::template::{render_template, validate_template, TemplateValues};
+use crate::webcam::error_capture::{delete_error_capture, list_error_captures, ErrorCapture};
 
 /// Starts the background monitoring process
 #[tauri::command]
@@ -31,3 +41,529 @@ pub fn get_background_monitoring_status_command(
 ) -> Result<bool, String> {
     Ok(state.is_running())
 }
+
+/// Gets rolling health metrics (success rate, latency, uptime) for hosts
+/// 
+/// # Arguments
+/// * `host` - Host IP address (optional, all tracked hosts when omitted)
+/// 
+/// # Returns
+/// * Health summaries sorted by host
+#[tauri::command]
+pub async fn get_host_health_command(
+    state: State<'_, BackgroundMonitorState>,
+    host: Option<String>,
+) -> Result<Vec<HostHealth>, String> {
+    Ok(state.get_health(host.as_deref()).await)
+}
+
+/// Gets the print failure detection settings
+/// 
+/// # Returns
+/// * Current FailureDetectionSettings
+#[tauri::command]
+pub fn get_failure_detection_settings_command() -> Result<FailureDetectionSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.failure_detection)
+}
+
+/// Saves the print failure detection settings
+/// 
+/// # Arguments
+/// * `failure_detection` - New detection settings
+#[tauri::command]
+pub fn update_failure_detection_settings_command(
+    failure_detection: FailureDetectionSettings,
+) -> Result<(), String> {
+    AppSettings::update(|settings| {
+        settings.failure_detection = failure_detection;
+    })?;
+    Ok(())
+}
+
+/// Gets the settings of the comparison with previous runs
+/// 
+/// # Returns
+/// * Current JobComparisonSettings
+#[tauri::command]
+pub fn get_job_comparison_settings_command() -> Result<JobComparisonSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.job_comparison)
+}
+
+/// Saves the settings of the comparison with previous runs
+/// 
+/// # Arguments
+/// * `job_comparison` - New comparison settings
+#[tauri::command]
+pub fn update_job_comparison_settings_command(
+    job_comparison: JobComparisonSettings,
+) -> Result<(), String> {
+    if job_comparison.slowdown_percent <= 0.0 {
+        return Err("Slowdown threshold must be greater than 0%".to_string());
+    }
+    if !(0.0..100.0).contains(&job_comparison.min_progress) {
+        return Err("Minimum progress must be between 0% and 100%".to_string());
+    }
+    AppSettings::update(|settings| {
+        settings.job_comparison = job_comparison;
+    })?;
+    Ok(())
+}
+
+/// Gets the completion GIF settings of a host
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Settings, or None when the completion message has no GIF
+#[tauri::command]
+pub fn get_completion_gif_command(host: String) -> Result<Option<CompletionGifSettings>, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.completion_gif.get(&host).cloned())
+}
+
+/// Enables or disables the GIF in the Telegram completion message of a host
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `config` - GIF settings, or None to disable
+#[tauri::command]
+pub fn set_completion_gif_command(host: String, config: Option<CompletionGifSettings>) -> Result<(), String> {
+    AppSettings::try_update(|settings| {
+        match config {
+            Some(config) => {
+                if config.max_frames < 2 {
+                    return Err("A GIF needs at least two frames".to_string());
+                }
+                settings.completion_gif.insert(host, config);
+            }
+            None => {
+                settings.completion_gif.remove(&host);
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Gets the endpoints finished prints are posted to
+/// 
+/// # Returns
+/// * Completion webhooks
+#[tauri::command]
+pub fn get_completion_webhooks_command() -> Result<Vec<CompletionWebhook>, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.completion_webhooks)
+}
+
+/// Saves the endpoints finished prints are posted to
+/// 
+/// # Arguments
+/// * `webhooks` - Completion webhooks, replacing the current ones
+#[tauri::command]
+pub fn update_completion_webhooks_command(webhooks: Vec<CompletionWebhook>) -> Result<(), String> {
+    if let Some(webhook) = webhooks.iter().find(|w| !w.url.starts_with("http://") && !w.url.starts_with("https://")) {
+        return Err(format!("Invalid webhook URL: {}", webhook.url));
+    }
+    AppSettings::update(|settings| {
+        settings.completion_webhooks = webhooks;
+    })
+}
+
+/// Gets the snapshot archive and timelapse settings
+/// 
+/// # Returns
+/// * Current SnapshotArchiveSettings
+#[tauri::command]
+pub fn get_snapshot_archive_settings_command() -> Result<SnapshotArchiveSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.snapshot_archive)
+}
+
+/// Saves the snapshot archive and timelapse settings
+/// 
+/// # Arguments
+/// * `snapshot_archive` - New archive settings
+#[tauri::command]
+pub fn update_snapshot_archive_settings_command(
+    snapshot_archive: SnapshotArchiveSettings,
+) -> Result<(), String> {
+    if snapshot_archive.interval_minutes == 0 {
+        return Err("Snapshot interval must be at least one minute".to_string());
+    }
+    AppSettings::update(|settings| {
+        settings.snapshot_archive = snapshot_archive;
+    })?;
+    Ok(())
+}
+
+/// Gets the settings of snapshot bursts taken when a print fails
+/// 
+/// # Returns
+/// * Current ErrorCaptureSettings
+#[tauri::command]
+pub fn get_error_capture_settings_command() -> Result<ErrorCaptureSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.error_capture)
+}
+
+/// Saves the settings of snapshot bursts taken when a print fails
+/// 
+/// # Arguments
+/// * `error_capture` - New capture settings
+#[tauri::command]
+pub fn update_error_capture_settings_command(error_capture: ErrorCaptureSettings) -> Result<(), String> {
+    if error_capture.frames == 0 {
+        return Err("A burst needs at least one snapshot".to_string());
+    }
+    AppSettings::update(|settings| {
+        settings.error_capture = error_capture;
+    })?;
+    Ok(())
+}
+
+/// Lists the snapshot bursts of failed prints, newest first
+/// 
+/// # Arguments
+/// * `host` - Only captures of this host (None: all hosts)
+#[tauri::command]
+pub fn get_error_captures_command(host: Option<String>) -> Result<Vec<ErrorCapture>, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(list_error_captures(&settings.error_capture.capture_dir(), host.as_deref()))
+}
+
+/// Deletes the snapshot burst of a failed print
+/// 
+/// # Arguments
+/// * `directory` - Capture folder, as listed by `get_error_captures_command`
+#[tauri::command]
+pub fn delete_error_capture_command(directory: String) -> Result<(), String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    delete_error_capture(&settings.error_capture.capture_dir(), &directory)
+}
+
+/// Gets the cooldown-complete notification settings
+/// 
+/// # Returns
+/// * Current CooldownSettings
+#[tauri::command]
+pub fn get_cooldown_settings_command() -> Result<CooldownSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.cooldown)
+}
+
+/// Saves the cooldown-complete notification settings
+/// 
+/// # Arguments
+/// * `cooldown` - New cooldown settings
+#[tauri::command]
+pub fn update_cooldown_settings_command(cooldown: CooldownSettings) -> Result<(), String> {
+    if cooldown.hotend_threshold <= 0.0 || cooldown.bed_threshold <= 0.0 {
+        return Err("Cooldown thresholds must be positive".to_string());
+    }
+    AppSettings::update(|settings| {
+        settings.cooldown = cooldown;
+    })?;
+    Ok(())
+}
+
+/// Gets the heater power cross-check settings
+/// 
+/// # Returns
+/// * Current HeaterWatchSettings
+#[tauri::command]
+pub fn get_heater_watch_settings_command() -> Result<HeaterWatchSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.heater_watch)
+}
+
+/// Saves the heater power cross-check settings
+/// 
+/// # Arguments
+/// * `heater_watch` - New heater check settings
+#[tauri::command]
+pub fn update_heater_watch_settings_command(heater_watch: HeaterWatchSettings) -> Result<(), String> {
+    if heater_watch.min_power <= 0.0 || heater_watch.min_power > 1.0 {
+        return Err("The full power threshold must be between 0 and 1".to_string());
+    }
+    if heater_watch.window_seconds == 0 || heater_watch.min_rise <= 0.0 {
+        return Err("Window and expected rise must be greater than 0".to_string());
+    }
+    AppSettings::update(|settings| {
+        settings.heater_watch = heater_watch;
+    })?;
+    Ok(())
+}
+
+/// Gets the idle heater timeout settings
+/// 
+/// # Returns
+/// * Current HeaterIdleSettings
+#[tauri::command]
+pub fn get_heater_idle_settings_command() -> Result<HeaterIdleSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.heater_idle)
+}
+
+/// Saves the idle heater timeout settings
+/// 
+/// # Arguments
+/// * `heater_idle` - New idle timeout and action
+#[tauri::command]
+pub fn update_heater_idle_settings_command(heater_idle: HeaterIdleSettings) -> Result<(), String> {
+    if heater_idle.idle_minutes == 0 {
+        return Err("The idle time must be at least 1 minute".to_string());
+    }
+    AppSettings::update(|settings| {
+        settings.heater_idle = heater_idle;
+    })?;
+    Ok(())
+}
+
+/// Snoozes an alert of a host, e.g. offline alerts for 2 hours
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `kind` - Alert to snooze
+/// * `minutes` - Length of the snooze
+/// 
+/// # Returns
+/// * When the snooze ends, as RFC 3339 timestamp
+#[tauri::command]
+pub fn snooze_alert_command(host: String, kind: AlertKind, minutes: u64) -> Result<String, String> {
+    snooze_alert(&host, kind, minutes).map(|until| until.to_rfc3339())
+}
+
+/// Acknowledges an alert of a host; it stays quiet until it clears
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `kind` - Alert to acknowledge
+#[tauri::command]
+pub fn acknowledge_alert_command(host: String, kind: AlertKind) -> Result<(), String> {
+    acknowledge_alert(&host, kind)
+}
+
+/// Ends the snooze or acknowledgement of an alert of a host
+/// 
+/// # Returns
+/// * Whether the alert was silenced
+#[tauri::command]
+pub fn unsilence_alert_command(host: String, kind: AlertKind) -> Result<bool, String> {
+    unsilence_alert(&host, kind)
+}
+
+/// Gets the alerts currently snoozed or acknowledged
+/// 
+/// # Returns
+/// * Silenced alerts keyed by host IP address
+#[tauri::command]
+pub fn get_alert_silences_command() -> Result<HashMap<String, Vec<AlertSilence>>, String> {
+    active_alert_silences()
+}
+
+/// Gets the escalation settings of unacknowledged critical alerts
+/// 
+/// # Returns
+/// * Current AckEscalationSettings
+#[tauri::command]
+pub fn get_ack_escalation_settings_command() -> Result<AckEscalationSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.ack_escalation)
+}
+
+/// Saves the escalation settings of unacknowledged critical alerts
+/// 
+/// # Arguments
+/// * `ack_escalation` - Waiting time and secondary contacts
+#[tauri::command]
+pub fn update_ack_escalation_settings_command(ack_escalation: AckEscalationSettings) -> Result<(), String> {
+    if ack_escalation.after_minutes == 0 {
+        return Err("The waiting time must be at least 1 minute".to_string());
+    }
+    if let Some(url) = ack_escalation.webhook_url.as_deref().filter(|url| !url.trim().is_empty()) {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(format!("Invalid URL: {}", url));
+        }
+    }
+    AppSettings::update(|settings| {
+        settings.ack_escalation = ack_escalation;
+    })?;
+    Ok(())
+}
+
+/// Gets the MCU link check settings
+/// 
+/// # Returns
+/// * Current McuWatchSettings
+#[tauri::command]
+pub fn get_mcu_watch_settings_command() -> Result<McuWatchSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.mcu_watch)
+}
+
+/// Saves the MCU link check settings
+/// 
+/// # Arguments
+/// * `mcu_watch` - New MCU link check settings
+#[tauri::command]
+pub fn update_mcu_watch_settings_command(mcu_watch: McuWatchSettings) -> Result<(), String> {
+    if mcu_watch.retransmits_per_minute <= 0.0 {
+        return Err("The retransmit threshold must be greater than 0".to_string());
+    }
+    AppSettings::update(|settings| {
+        settings.mcu_watch = mcu_watch;
+    })?;
+    Ok(())
+}
+
+/// Gets the offline escalation settings
+/// 
+/// # Returns
+/// * Current OfflineEscalationSettings
+#[tauri::command]
+pub fn get_offline_escalation_settings_command() -> Result<OfflineEscalationSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.offline_escalation)
+}
+
+/// Saves the offline escalation settings
+/// 
+/// # Arguments
+/// * `offline_escalation` - New escalation settings
+#[tauri::command]
+pub fn update_offline_escalation_settings_command(offline_escalation: OfflineEscalationSettings) -> Result<(), String> {
+    if offline_escalation.rules.iter().any(|rule| rule.after_minutes == 0) {
+        return Err("Escalation thresholds must be at least one minute".to_string());
+    }
+    AppSettings::update(|settings| {
+        settings.offline_escalation = offline_escalation;
+    })?;
+    Ok(())
+}
+
+/// Gets the channels of the "all printers idle" notification
+/// 
+/// # Returns
+/// * Current FleetIdleNotificationSettings
+#[tauri::command]
+pub fn get_fleet_idle_notification_settings_command() -> Result<FleetIdleNotificationSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.notifications.fleet_idle)
+}
+
+/// Saves the channels of the "all printers idle" notification
+/// 
+/// # Arguments
+/// * `fleet_idle` - New channel settings
+#[tauri::command]
+pub fn update_fleet_idle_notification_settings_command(fleet_idle: FleetIdleNotificationSettings) -> Result<(), String> {
+    AppSettings::update(|settings| {
+        settings.notifications.fleet_idle = fleet_idle;
+    })?;
+    Ok(())
+}
+
+/// Gets the user-defined notification templates
+/// 
+/// # Returns
+/// * Current NotificationTemplates
+#[tauri::command]
+pub fn get_notification_templates_command() -> Result<NotificationTemplates, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.notifications.templates)
+}
+
+/// Saves the user-defined notification templates
+/// 
+/// Empty templates are stored as unset, so the built-in text is used.
+/// 
+/// # Arguments
+/// * `templates` - New templates
+#[tauri::command]
+pub fn update_notification_templates_command(templates: NotificationTemplates) -> Result<(), String> {
+    let normalize = |template: Option<String>| -> Result<Option<String>, String> {
+        match template.filter(|t| !t.trim().is_empty()) {
+            Some(template) => validate_template(&template).map(|_| Some(template)),
+            None => Ok(None),
+        }
+    };
+    let templates = NotificationTemplates {
+        status_change: normalize(templates.status_change).map_err(|e| format!("Status change template: {}", e))?,
+        print_finished: normalize(templates.print_finished).map_err(|e| format!("Print finished template: {}", e))?,
+    };
+
+    AppSettings::update(|settings| {
+        settings.notifications.templates = templates;
+    })?;
+    Ok(())
+}
+
+/// Gets whether Do Not Disturb is on and how many notifications wait for it to end
+#[tauri::command]
+pub async fn get_do_not_disturb_state_command() -> Result<DoNotDisturbState, String> {
+    Ok(do_not_disturb_state().await)
+}
+
+/// Sets whether non-critical system notifications wait for Do Not Disturb to end
+/// 
+/// # Arguments
+/// * `respect` - true to hold notifications back and summarize them afterwards
+#[tauri::command]
+pub fn set_respect_do_not_disturb_command(respect: bool) -> Result<(), String> {
+    AppSettings::update(|settings| {
+        settings.notifications.respect_do_not_disturb = respect;
+    })?;
+    set_respect_do_not_disturb(respect);
+    Ok(())
+}
+
+/// Checks a notification template without saving it
+/// 
+/// # Arguments
+/// * `template` - Template text
+#[tauri::command]
+pub fn validate_notification_template_command(template: String) -> Result<(), String> {
+    validate_template(&template)
+}
+
+/// Renders a notification template with example values
+/// 
+/// # Arguments
+/// * `template` - Template text
+/// 
+/// # Returns
+/// * Text the notification would show
+#[tauri::command]
+pub fn preview_notification_template_command(template: String) -> Result<String, String> {
+    render_template(&template, &TemplateValues::sample())
+}
+
+/// Renders the user's status change template for a host
+/// 
+/// The desktop app detects status changes itself and uses this text for
+/// its system and Telegram notifications, as the background monitor does.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `hostname` - Host display name
+/// * `previous_status` - Status before the change, e.g. "standby"
+/// * `status` - Status after the change, e.g. "printing"
+/// 
+/// # Returns
+/// * Notification body, None when no template is set
+#[tauri::command]
+pub async fn render_status_change_notification_command(
+    host: String,
+    hostname: String,
+    previous_status: String,
+    status: String,
+) -> Result<Option<String>, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    let Some(template) = settings.notifications.templates.status_change.filter(|t| !t.trim().is_empty()) else {
+        return Ok(None);
+    };
+    let values = TemplateValues::for_status_change(&host, &hostname, &previous_status, &status).await;
+    render_template(&template, &values).map(Some)
+}
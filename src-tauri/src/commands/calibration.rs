@@ -0,0 +1,344 @@
+//! Calibration Tauri commands
+//! 
+//! This module contains Tauri commands for running input shaper calibrations,
+//! resonance tests and heater PID calibrations remotely and retrieving their
+//! results.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Mutex;
+use crate::api::calibration::{
+    download_file, get_heater_temperature, get_pid_result, list_calibration_files, run_calibration, run_pid_calibration,
+};
+use crate::commands::telegram::TelegramBotState;
+use crate::error::error_to_string;
+use crate::models::{CalibrationKind, CalibrationRun, CalibrationStatus, PidCalibrationRun};
+use crate::i18n::{t, tf};
+use crate::notifications::system::send_notification;
+use crate::telegram::TelegramBot;
+
+/// Seconds subtracted from the start time when looking for results,
+/// to allow for clock differences between the app and the printer
+const CLOCK_SKEW_SECONDS: f64 = 60.0;
+
+/// Interval between heater readings of a running PID calibration
+const PID_PROGRESS_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Latest calibration runs per host
+pub struct CalibrationState {
+    pub runs: Arc<Mutex<HashMap<String, CalibrationRun>>>,
+    pub pid_runs: Arc<Mutex<HashMap<String, PidCalibrationRun>>>,
+}
+
+impl Default for CalibrationState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CalibrationState {
+    pub fn new() -> Self {
+        Self {
+            runs: Arc::new(Mutex::new(HashMap::new())),
+            pid_runs: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+/// Starts an input shaper calibration or resonance test in the background
+/// 
+/// A "calibration-finished" event and a notification are sent when it ends.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `kind` - "shaper_calibrate" or "test_resonances"
+/// * `axis` - "x" or "y" (both axes when omitted)
+/// 
+/// # Returns
+/// * The started run
+#[tauri::command]
+pub async fn start_calibration_command(
+    app_handle: AppHandle,
+    host: String,
+    kind: CalibrationKind,
+    axis: Option<String>,
+    state: State<'_, CalibrationState>,
+    telegram: State<'_, TelegramBotState>,
+) -> Result<CalibrationRun, String> {
+    if state.pid_runs.lock().await.get(&host).is_some_and(|r| r.status == CalibrationStatus::Running) {
+        return Err("A calibration is already running on this host".to_string());
+    }
+    let mut runs = state.runs.lock().await;
+    if runs.get(&host).is_some_and(|r| r.status == CalibrationStatus::Running) {
+        return Err("A calibration is already running on this host".to_string());
+    }
+
+    let run = CalibrationRun {
+        host: host.clone(),
+        kind,
+        axis: axis.clone(),
+        status: CalibrationStatus::Running,
+        started_at: chrono::Utc::now().to_rfc3339(),
+        finished_at: None,
+        recommendations: Vec::new(),
+        files: Vec::new(),
+        data_files: Vec::new(),
+        error: None,
+    };
+    runs.insert(host.clone(), run.clone());
+    drop(runs);
+
+    let started = run.clone();
+    let runs = state.runs.clone();
+    let telegram_bot = telegram.bot.clone();
+    let since = chrono::Utc::now().timestamp() as f64 - CLOCK_SKEW_SECONDS;
+    tokio::spawn(async move {
+        let result = run_calibration(&host, kind, axis.as_deref()).await;
+
+        let mut finished = runs.lock().await.get(&host).cloned().unwrap_or(run);
+        finished.finished_at = Some(chrono::Utc::now().to_rfc3339());
+        match result {
+            Ok(lines) => {
+                finished.status = CalibrationStatus::Completed;
+                finished.read_console(&lines);
+                finished.files = list_calibration_files(&host, since).await.unwrap_or_default();
+            }
+            Err(e) => {
+                finished.status = CalibrationStatus::Failed;
+                finished.error = Some(error_to_string(e));
+            }
+        }
+        runs.lock().await.insert(host.clone(), finished.clone());
+
+        report_calibration_finished(&app_handle, &telegram_bot, &finished).await;
+    });
+
+    Ok(started)
+}
+
+/// Gets the latest calibration run of a host
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * The run, or None if no calibration was started since the app started
+#[tauri::command]
+pub async fn get_calibration_run_command(
+    host: String,
+    state: State<'_, CalibrationState>,
+) -> Result<Option<CalibrationRun>, String> {
+    Ok(state.runs.lock().await.get(&host).cloned())
+}
+
+/// Starts a PID calibration of a heater in the background
+/// 
+/// The heater temperature is read while Klipper calibrates and sent as
+/// "pid-calibration-progress" events. A "pid-calibration-finished" event and
+/// a notification with the PID values are sent when it ends; the values are
+/// kept by running `SAVE_CONFIG` (`save_config_command`).
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `heater` - Heater name, e.g. "extruder" or "heater_bed"
+/// * `target` - Target temperature in °C
+/// 
+/// # Returns
+/// * The started run
+#[tauri::command]
+pub async fn start_pid_calibration_command(
+    app_handle: AppHandle,
+    host: String,
+    heater: String,
+    target: f64,
+    state: State<'_, CalibrationState>,
+    telegram: State<'_, TelegramBotState>,
+) -> Result<PidCalibrationRun, String> {
+    if state.runs.lock().await.get(&host).is_some_and(|r| r.status == CalibrationStatus::Running) {
+        return Err("A calibration is already running on this host".to_string());
+    }
+    let mut pid_runs = state.pid_runs.lock().await;
+    if pid_runs.get(&host).is_some_and(|r| r.status == CalibrationStatus::Running) {
+        return Err("A calibration is already running on this host".to_string());
+    }
+
+    let run = PidCalibrationRun {
+        host: host.clone(),
+        heater: heater.clone(),
+        target,
+        status: CalibrationStatus::Running,
+        started_at: chrono::Utc::now().to_rfc3339(),
+        finished_at: None,
+        temperature: None,
+        cycles: 0,
+        progress: 0.0,
+        result: None,
+        error: None,
+    };
+    pid_runs.insert(host.clone(), run.clone());
+    drop(pid_runs);
+
+    let started = run.clone();
+    let pid_runs = state.pid_runs.clone();
+    let telegram_bot = telegram.bot.clone();
+    let since = chrono::Utc::now().timestamp() as f64 - CLOCK_SKEW_SECONDS;
+    tokio::spawn(async move {
+        let calibration = run_pid_calibration(&host, &heater, target);
+        tokio::pin!(calibration);
+        let result = loop {
+            tokio::select! {
+                result = &mut calibration => break result,
+                _ = tokio::time::sleep(PID_PROGRESS_INTERVAL) => {
+                    let Ok(temperature) = get_heater_temperature(&host, &heater).await else {
+                        continue;
+                    };
+                    let mut runs = pid_runs.lock().await;
+                    if let Some(run) = runs.get_mut(&host) {
+                        run.record_temperature(temperature);
+                        let _ = app_handle.emit("pid-calibration-progress", run.clone());
+                    }
+                }
+            }
+        };
+
+        let mut finished = pid_runs.lock().await.get(&host).cloned().unwrap_or(run);
+        finished.finished_at = Some(chrono::Utc::now().to_rfc3339());
+        let result = match result {
+            Ok(()) => get_pid_result(&host, since).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(values) => {
+                finished.status = CalibrationStatus::Completed;
+                finished.progress = 1.0;
+                finished.result = Some(values);
+            }
+            Err(e) => {
+                finished.status = CalibrationStatus::Failed;
+                finished.error = Some(error_to_string(e));
+            }
+        }
+        pid_runs.lock().await.insert(host.clone(), finished.clone());
+
+        report_pid_calibration_finished(&app_handle, &telegram_bot, &finished).await;
+    });
+
+    Ok(started)
+}
+
+/// Gets the latest PID calibration run of a host
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * The run, or None if no PID calibration was started since the app started
+#[tauri::command]
+pub async fn get_pid_calibration_run_command(
+    host: String,
+    state: State<'_, CalibrationState>,
+) -> Result<Option<PidCalibrationRun>, String> {
+    Ok(state.pid_runs.lock().await.get(&host).cloned())
+}
+
+/// Downloads a calibration result file from the printer
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `root` - Moonraker file root (e.g. "config")
+/// * `path` - Path relative to the root
+/// * `destination` - Local file to write
+#[tauri::command]
+pub async fn download_calibration_file_command(
+    host: String,
+    root: String,
+    path: String,
+    destination: String,
+) -> Result<(), String> {
+    let content = download_file(&host, &root, &path)
+        .await
+        .map_err(error_to_string)?;
+    tokio::fs::write(&destination, content)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", destination, e))
+}
+
+/// Notifies the UI, the system and Telegram that a calibration ended
+async fn report_calibration_finished(
+    app_handle: &AppHandle,
+    telegram_bot: &Arc<Mutex<Option<TelegramBot>>>,
+    run: &CalibrationRun,
+) {
+    let command = run.kind.command();
+    let (title, body) = match run.status {
+        CalibrationStatus::Completed if run.recommendations.is_empty() => (
+            t("notify.calibration_finished.title"),
+            tf("notify.calibration_finished.files", &[("command", &command), ("host", &run.host), ("count", &(run.files.len() + run.data_files.len()))]),
+        ),
+        CalibrationStatus::Completed => (
+            t("notify.calibration_finished.title"),
+            tf("notify.calibration_finished.body", &[
+                ("command", &command),
+                ("host", &run.host),
+                ("recommendations", &run.recommendations.join("\n")),
+            ]),
+        ),
+        _ => (
+            t("notify.calibration_failed.title"),
+            tf("notify.calibration_failed.body", &[
+                ("command", &command),
+                ("host", &run.host),
+                ("error", &run.error.as_deref().unwrap_or(t("notify.unknown_error"))),
+            ]),
+        ),
+    };
+
+    send_notification(title, &body);
+    let _ = app_handle.emit("calibration-finished", run.clone());
+
+    let bot_guard = telegram_bot.lock().await;
+    if let Some(ref bot) = *bot_guard {
+        if let Err(e) = bot.send_notification_to_all_users(title, &body, Some(&run.host)).await {
+            eprintln!("Failed to send calibration result to Telegram: {}", e);
+        }
+    }
+}
+
+/// Notifies the UI, the system and Telegram that a PID calibration ended
+async fn report_pid_calibration_finished(
+    app_handle: &AppHandle,
+    telegram_bot: &Arc<Mutex<Option<TelegramBot>>>,
+    run: &PidCalibrationRun,
+) {
+    let (title, body) = match (run.status, run.result) {
+        (CalibrationStatus::Completed, Some(values)) => (
+            t("notify.pid_calibration_finished.title"),
+            tf("notify.pid_calibration_finished.body", &[
+                ("heater", &run.heater),
+                ("host", &run.host),
+                ("kp", &format!("{:.3}", values.kp)),
+                ("ki", &format!("{:.3}", values.ki)),
+                ("kd", &format!("{:.3}", values.kd)),
+            ]),
+        ),
+        _ => (
+            t("notify.calibration_failed.title"),
+            tf("notify.calibration_failed.body", &[
+                ("command", &format!("PID_CALIBRATE {}", run.heater)),
+                ("host", &run.host),
+                ("error", &run.error.as_deref().unwrap_or(t("notify.unknown_error"))),
+            ]),
+        ),
+    };
+
+    send_notification(title, &body);
+    let _ = app_handle.emit("pid-calibration-finished", run.clone());
+
+    let bot_guard = telegram_bot.lock().await;
+    if let Some(ref bot) = *bot_guard {
+        if let Err(e) = bot.send_notification_to_all_users(title, &body, Some(&run.host)).await {
+            eprintln!("Failed to send PID calibration result to Telegram: {}", e);
+        }
+    }
+}
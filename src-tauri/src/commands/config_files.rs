@@ -0,0 +1,241 @@
+//! Printer configuration Tauri commands
+//! 
+//! This module contains Tauri commands for viewing and editing `printer.cfg`
+//! and the other files in the Moonraker `config` root, and for backing up
+//! and restoring the config directories of the fleet.
+
+use std::time::Duration;
+use tauri::State;
+use crate::api::config_files::{list_config_files, read_config_file, restart_config_service, write_config_file};
+use crate::api::printer::FIRMWARE_RESTART_TIMEOUT_SECONDS;
+use crate::commands::telegram::TelegramBotState;
+use crate::config_backup::{
+    backup_host_configs, delete_config_backup, list_config_backups, load_config_backup, restore_config_backup, ConfigBackup,
+    ConfigBackupRun,
+};
+use crate::config_backup::github::{push_backup_to_github, GitHubPush};
+use crate::error::error_to_string;
+use crate::models::api::KlippyState;
+use crate::models::config::{AppSettings, ConfigBackupSettings};
+use crate::models::config_file::{diff_lines, ConfigFile, ConfigSaveResult, ConfigService, DiffLine, DIFF_CONTEXT_LINES};
+
+/// Lists the configuration files of a printer
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Config files, `printer.cfg` first
+#[tauri::command]
+pub async fn list_config_files_command(host: String) -> Result<Vec<ConfigFile>, String> {
+    list_config_files(&host).await.map_err(error_to_string)
+}
+
+/// Reads a configuration file
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `path` - Path relative to the `config` root, e.g. "printer.cfg"
+/// 
+/// # Returns
+/// * File content
+#[tauri::command]
+pub async fn read_config_file_command(host: String, path: String) -> Result<String, String> {
+    read_config_file(&host, &path).await.map_err(error_to_string)
+}
+
+/// Compares edited content with the file on the printer
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `path` - Path relative to the `config` root
+/// * `content` - Edited content
+/// 
+/// # Returns
+/// * Changed lines with context, empty when nothing changed
+#[tauri::command]
+pub async fn diff_config_file_command(host: String, path: String, content: String) -> Result<Vec<DiffLine>, String> {
+    let current = read_config_file(&host, &path).await.map_err(error_to_string)?;
+    Ok(diff_lines(&current, &content, DIFF_CONTEXT_LINES))
+}
+
+/// Saves an edited configuration file
+/// 
+/// The file is not written when it was changed on the printer since it was
+/// opened (e.g. by SAVE_CONFIG), so those changes are not overwritten.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `path` - Path relative to the `config` root
+/// * `content` - Edited content
+/// * `original` - Content the edit started from
+/// 
+/// # Returns
+/// * The changes and the service to restart for them to apply
+#[tauri::command]
+pub async fn save_config_file_command(
+    host: String,
+    path: String,
+    content: String,
+    original: Option<String>,
+) -> Result<ConfigSaveResult, String> {
+    let current = read_config_file(&host, &path).await.map_err(error_to_string)?;
+    if original.is_some_and(|original| original != current) {
+        return Err(format!("{} was changed on the printer since it was opened", path));
+    }
+
+    let changes = diff_lines(&current, &content, DIFF_CONTEXT_LINES);
+    if changes.is_empty() {
+        return Ok(ConfigSaveResult { path, changes, restart: None });
+    }
+    write_config_file(&host, &path, &content).await.map_err(error_to_string)?;
+    Ok(ConfigSaveResult { restart: Some(ConfigService::for_path(&path)), path, changes })
+}
+
+/// Restarts Klipper or Moonraker to apply configuration changes
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `service` - "klipper" or "moonraker"
+/// * `timeout_seconds` - Maximum wait (60 seconds when omitted)
+/// 
+/// # Returns
+/// * Final Klippy state; "error" usually means the configuration is invalid
+#[tauri::command]
+pub async fn restart_config_service_command(
+    host: String,
+    service: ConfigService,
+    timeout_seconds: Option<u64>,
+) -> Result<KlippyState, String> {
+    let timeout = Duration::from_secs(timeout_seconds.unwrap_or(FIRMWARE_RESTART_TIMEOUT_SECONDS));
+    restart_config_service(&host, service, timeout)
+        .await
+        .map_err(error_to_string)
+}
+
+/// Gets the scheduled config backup settings
+/// 
+/// # Returns
+/// * Current ConfigBackupSettings
+#[tauri::command]
+pub fn get_config_backup_settings_command() -> Result<ConfigBackupSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.config_backup)
+}
+
+/// Saves the scheduled config backup settings
+/// 
+/// # Arguments
+/// * `config_backup` - New backup settings
+#[tauri::command]
+pub fn update_config_backup_settings_command(config_backup: ConfigBackupSettings) -> Result<(), String> {
+    if config_backup.interval_hours == 0 {
+        return Err("The backup interval must be at least one hour".to_string());
+    }
+    let github = &config_backup.github;
+    if github.enabled && (github.repository.trim().is_empty() || github.branch.trim().is_empty()) {
+        return Err("GitHub backups need a repository and a branch".to_string());
+    }
+    AppSettings::update(|settings| {
+        settings.config_backup = config_backup;
+    })
+}
+
+/// Backs up config directories right away, and pushes them to GitHub if configured
+/// 
+/// Hosts that fail are skipped; their errors are returned with the backups
+/// that succeeded.
+/// 
+/// # Arguments
+/// * `host` - Host IP address (None: every online host)
+#[tauri::command]
+pub async fn backup_configs_command(
+    host: Option<String>,
+    telegram: State<'_, TelegramBotState>,
+) -> Result<ConfigBackupRun, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    let known = telegram.hosts.lock().await.clone();
+    let hosts: Vec<(String, String)> = match host {
+        Some(ip) => {
+            let hostname = known.iter().find(|h| h.ip_address == ip).map_or(ip.clone(), |h| h.hostname.clone());
+            vec![(ip, hostname)]
+        }
+        None => known
+            .iter()
+            .filter(|h| h.status == "online")
+            .map(|h| (h.ip_address.clone(), h.hostname.clone()))
+            .collect(),
+    };
+
+    let mut run = ConfigBackupRun::default();
+    for (ip, hostname) in hosts {
+        match backup_host_configs(&ip, &hostname, &settings.config_backup).await {
+            Ok(backup) => {
+                if settings.config_backup.github.enabled {
+                    if let Err(e) = push_backup_to_github(&backup, &settings.config_backup.github).await {
+                        run.errors.push(format!("{}: {}", hostname, e));
+                    }
+                }
+                run.backups.push(backup);
+            }
+            Err(e) => run.errors.push(format!("{}: {}", hostname, e)),
+        }
+    }
+    Ok(run)
+}
+
+/// Commits a saved backup to the configured GitHub repository
+/// 
+/// # Arguments
+/// * `directory` - Backup folder, as listed by `get_config_backups_command`
+/// 
+/// # Returns
+/// * The commit, without one when the repository already had these files
+#[tauri::command]
+pub async fn push_config_backup_command(directory: String) -> Result<GitHubPush, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    let backup = load_config_backup(&settings.config_backup.backup_dir(), &directory)?;
+    push_backup_to_github(&backup, &settings.config_backup.github).await
+}
+
+/// Lists the saved config backups, newest first
+/// 
+/// # Arguments
+/// * `host` - Only backups of this host (None: all hosts)
+#[tauri::command]
+pub fn get_config_backups_command(host: Option<String>) -> Result<Vec<ConfigBackup>, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(list_config_backups(&settings.config_backup.backup_dir(), host.as_deref()))
+}
+
+/// Restores a config backup to a printer
+/// 
+/// Klipper keeps the old configuration until it restarts
+/// (`restart_config_service_command`).
+/// 
+/// # Arguments
+/// * `directory` - Backup folder, as listed by `get_config_backups_command`
+/// * `host` - Host IP address to restore to
+/// * `files` - Files to restore (None: every file of the backup)
+/// 
+/// # Returns
+/// * Restored files
+#[tauri::command]
+pub async fn restore_config_backup_command(
+    directory: String,
+    host: String,
+    files: Option<Vec<String>>,
+) -> Result<Vec<String>, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    restore_config_backup(&settings.config_backup.backup_dir(), &directory, &host, files).await
+}
+
+/// Deletes a config backup
+/// 
+/// # Arguments
+/// * `directory` - Backup folder, as listed by `get_config_backups_command`
+#[tauri::command]
+pub fn delete_config_backup_command(directory: String) -> Result<(), String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    delete_config_backup(&settings.config_backup.backup_dir(), &directory)
+}
@@ -0,0 +1,22 @@
+//! G-code file Tauri commands
+//! 
+//! This module contains Tauri commands for inspecting local g-code files.
+
+use std::path::PathBuf;
+use crate::gcode::parse_gcode_file;
+use crate::models::GcodeMetadata;
+
+/// Reads slicer metadata (time, filament, layers, thumbnails) from a local g-code file
+/// 
+/// # Arguments
+/// * `file_path` - Local path of the g-code file
+/// 
+/// # Returns
+/// * Parsed metadata
+#[tauri::command]
+pub async fn get_gcode_metadata_command(file_path: String) -> Result<GcodeMetadata, String> {
+    let path = PathBuf::from(file_path);
+    tokio::task::spawn_blocking(move || parse_gcode_file(&path))
+        .await
+        .map_err(|e| format!("Failed to parse g-code file: {}", e))?
+}
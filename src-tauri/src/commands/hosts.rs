@@ -0,0 +1,313 @@
+//! Host configuration Tauri commands
+//! 
+//! This module contains Tauri commands for per-host settings that are
+//! stored in the backend, such as connection overrides and display names,
+//! for exporting and importing the host inventory, and for the printer
+//! farm overview.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::State;
+use crate::api::client::set_host_connections;
+use crate::commands::telegram::TelegramBotState;
+use crate::diagnostics::get_diagnostics;
+use crate::fleet::{get_availability_forecast, get_fleet_overview};
+use crate::inventory::{add_remote_host, export_hosts, import_entries, read_import_file, remove_remote_host, ImportResult, InventoryFormat, RemoteHostAuth};
+use crate::models::config::{AppSettings, HostConnectionSettings};
+use crate::models::{AvailabilityForecast, FleetOverview, HostDiagnostics, HostInfo};
+
+/// Gets connection overrides for every configured host
+/// 
+/// # Returns
+/// * Connection overrides keyed by host address
+#[tauri::command]
+pub async fn get_host_connections_command() -> Result<HashMap<String, HostConnectionSettings>, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.host_connections)
+}
+
+/// Sets or removes the connection overrides of a host
+/// 
+/// # Arguments
+/// * `host` - Host address the overrides apply to
+/// * `connection` - New overrides, or None to remove them
+#[tauri::command]
+pub async fn update_host_connection_command(
+    host: String,
+    connection: Option<HostConnectionSettings>,
+) -> Result<(), String> {
+    if let Some(connection) = &connection {
+        if let Some(base_url) = connection.base_url.as_deref().filter(|u| !u.trim().is_empty()) {
+            if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+                return Err("Base URL must start with http:// or https://".to_string());
+            }
+        }
+        let templates = [&connection.snapshot_url, &connection.stream_url];
+        if let Some(url) = templates.into_iter().flatten().map(|u| u.trim()).find(|u| !u.is_empty() && !u.starts_with("http://") && !u.starts_with("https://")) {
+            return Err(format!("Webcam URL must start with http:// or https://: {}", url));
+        }
+    }
+
+    let connections = AppSettings::update(|settings| {
+        match connection {
+            Some(connection) => {
+                settings.host_connections.insert(host, connection);
+            }
+            None => {
+                settings.host_connections.remove(&host);
+            }
+        }
+        settings.host_connections.clone()
+    })?;
+    set_host_connections(connections);
+    Ok(())
+}
+
+/// Gets the custom display names of all renamed hosts
+/// 
+/// # Returns
+/// * Display names keyed by host IP address
+#[tauri::command]
+pub async fn get_host_names_command() -> Result<HashMap<String, String>, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.host_names)
+}
+
+/// Renames a host and persists the display name
+/// 
+/// The name is used by the Telegram bot, notifications and future scans.
+/// 
+/// # Arguments
+/// * `ip` - Host IP address
+/// * `name` - New display name, or None/empty to restore the printer hostname
+#[tauri::command]
+pub async fn rename_host_command(
+    ip: String,
+    name: Option<String>,
+    telegram: State<'_, TelegramBotState>,
+) -> Result<(), String> {
+    let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+
+    AppSettings::update(|settings| {
+        match &name {
+            Some(name) => {
+                settings.host_names.insert(ip.clone(), name.clone());
+            }
+            None => {
+                settings.host_names.remove(&ip);
+            }
+        }
+    })?;
+
+    let mut hosts = telegram.hosts.lock().await;
+    if let Some(host) = hosts.iter_mut().find(|h| h.ip_address == ip) {
+        host.hostname = name.unwrap_or_else(|| host.original_hostname.clone());
+    }
+
+    Ok(())
+}
+
+/// Turns maintenance mode of a host on or off
+/// 
+/// Hosts in maintenance mode do not produce offline/error notifications
+/// and are marked in Telegram and the UI.
+/// 
+/// # Arguments
+/// * `ip` - Host IP address
+/// * `enabled` - Whether the host is in maintenance
+#[tauri::command]
+pub async fn set_host_maintenance_command(
+    ip: String,
+    enabled: bool,
+    telegram: State<'_, TelegramBotState>,
+) -> Result<(), String> {
+    AppSettings::update(|settings| {
+        if enabled {
+            settings.maintenance_hosts.insert(ip.clone());
+        } else {
+            settings.maintenance_hosts.remove(&ip);
+        }
+    })?;
+
+    let mut hosts = telegram.hosts.lock().await;
+    if let Some(host) = hosts.iter_mut().find(|h| h.ip_address == ip) {
+        host.maintenance = enabled;
+    }
+
+    Ok(())
+}
+
+/// Sets the tags/groups of a host
+/// 
+/// Tags are used to match hosts to print queue jobs.
+/// 
+/// # Arguments
+/// * `ip` - Host IP address
+/// * `tags` - New tags (empty removes all tags)
+#[tauri::command]
+pub async fn set_host_tags_command(
+    ip: String,
+    tags: Vec<String>,
+    telegram: State<'_, TelegramBotState>,
+) -> Result<(), String> {
+    let mut tags: Vec<String> = tags.into_iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+
+    AppSettings::update(|settings| {
+        if tags.is_empty() {
+            settings.host_tags.remove(&ip);
+        } else {
+            settings.host_tags.insert(ip.clone(), tags.clone());
+        }
+    })?;
+
+    let mut hosts = telegram.hosts.lock().await;
+    if let Some(host) = hosts.iter_mut().find(|h| h.ip_address == ip) {
+        host.tags = tags;
+    }
+
+    Ok(())
+}
+
+/// Exports the current host inventory (name, IP, versions, status, tags, last seen)
+/// 
+/// # Arguments
+/// * `format` - "csv" or "json"
+/// * `path` - Output file
+/// 
+/// # Returns
+/// * Number of exported hosts
+#[tauri::command]
+pub async fn export_hosts_command(
+    format: InventoryFormat,
+    path: String,
+    telegram: State<'_, TelegramBotState>,
+) -> Result<usize, String> {
+    let hosts = telegram.hosts.lock().await.clone();
+    export_hosts(&hosts, format, &PathBuf::from(path))
+}
+
+/// Imports hosts from a CSV/JSON file of IPs, hostnames or Moonraker URLs
+/// 
+/// Each entry is probed and reachable printers are added to the host list
+/// without a subnet scan.
+/// 
+/// # Arguments
+/// * `path` - Import file (e.g. a DHCP lease export or Fluidd instance list)
+/// 
+/// # Returns
+/// * Added printers, unreachable addresses and unreadable entries
+#[tauri::command]
+pub async fn import_hosts_command(
+    path: String,
+    telegram: State<'_, TelegramBotState>,
+) -> Result<ImportResult, String> {
+    let (entries, invalid) = read_import_file(&PathBuf::from(path))?;
+    let mut result = import_entries(entries).await?;
+    result.invalid = invalid;
+
+    let mut hosts = telegram.hosts.lock().await;
+    for imported in &result.hosts {
+        match hosts.iter_mut().find(|h| h.ip_address == imported.ip_address) {
+            Some(existing) => *existing = imported.clone(),
+            None => hosts.push(imported.clone()),
+        }
+    }
+
+    Ok(result)
+}
+
+/// Registers a printer by its Moonraker URL instead of scanning for it
+/// 
+/// For printers exposed through a tunnel or reverse proxy. The printer is
+/// monitored, notified about and controllable from Telegram like a
+/// scanned one.
+/// 
+/// # Arguments
+/// * `url` - Moonraker URL, e.g. `https://printer.example.com`
+/// * `name` - Display name
+/// * `auth` - Moonraker API key or access token
+/// 
+/// # Returns
+/// * The added printer
+#[tauri::command]
+pub async fn add_remote_host_command(
+    url: String,
+    name: Option<String>,
+    auth: Option<RemoteHostAuth>,
+    telegram: State<'_, TelegramBotState>,
+) -> Result<HostInfo, String> {
+    let host = add_remote_host(&url, name, auth.unwrap_or_default()).await?;
+
+    let mut hosts = telegram.hosts.lock().await;
+    match hosts.iter_mut().find(|h| h.ip_address == host.ip_address) {
+        Some(existing) => *existing = host.clone(),
+        None => hosts.push(host.clone()),
+    }
+
+    Ok(host)
+}
+
+/// Removes a printer that was registered by URL
+/// 
+/// # Arguments
+/// * `host` - Host address, the hostname of its URL
+#[tauri::command]
+pub async fn remove_remote_host_command(
+    host: String,
+    telegram: State<'_, TelegramBotState>,
+) -> Result<(), String> {
+    remove_remote_host(&host)?;
+    telegram.hosts.lock().await.retain(|h| h.ip_address != host);
+    Ok(())
+}
+
+/// Gets the printer farm overview of all known hosts
+/// 
+/// Status, running jobs and job history of every host are fetched
+/// concurrently in the backend.
+/// 
+/// # Returns
+/// * Status counts, running jobs with progress and ETA, errors and history totals
+#[tauri::command]
+pub async fn get_fleet_overview_command(
+    telegram: State<'_, TelegramBotState>,
+) -> Result<FleetOverview, String> {
+    let hosts = telegram.hosts.lock().await.clone();
+    Ok(get_fleet_overview(&hosts).await)
+}
+
+/// Forecasts when each known printer is free for the next job
+/// 
+/// # Returns
+/// * Free printers first, then busy ones by the expected end of their job
+#[tauri::command]
+pub async fn get_availability_forecast_command(
+    telegram: State<'_, TelegramBotState>,
+) -> Result<AvailabilityForecast, String> {
+    let hosts = telegram.hosts.lock().await.clone();
+    Ok(get_availability_forecast(&hosts).await)
+}
+
+/// Lists the problems Moonraker reports per host with suggested fixes
+/// 
+/// Covers components that failed to load, Klipper sections Moonraker
+/// needs and server warnings.
+/// 
+/// # Arguments
+/// * `hosts` - IP addresses to check (None: every known host)
+#[tauri::command]
+pub async fn get_host_diagnostics_command(
+    hosts: Option<Vec<String>>,
+    telegram: State<'_, TelegramBotState>,
+) -> Result<Vec<HostDiagnostics>, String> {
+    let mut known = telegram.hosts.lock().await.clone();
+    if let Some(selected) = hosts {
+        known.retain(|h| selected.contains(&h.ip_address));
+    }
+    Ok(get_diagnostics(&known).await)
+}
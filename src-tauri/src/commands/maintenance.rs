@@ -0,0 +1,130 @@
+//! Maintenance schedule Tauri commands
+//! 
+//! This module contains Tauri commands for print-hour based maintenance
+//! tasks and the per-host maintenance log.
+
+use crate::api::history::get_print_hours;
+use crate::models::HostMaintenance;
+use crate::models::config::AppSettings;
+
+/// Gets the maintenance schedule and log of a host
+/// 
+/// Print hours are refreshed from the printer history when it is reachable.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Maintenance tasks, log and current print hours
+#[tauri::command]
+pub async fn get_host_maintenance_command(host: String) -> Result<HostMaintenance, String> {
+    let print_hours = get_print_hours(&host).await.ok();
+
+    AppSettings::update(|settings| {
+        let schedule = settings.maintenance_schedules.entry(host).or_default();
+        if let Some(print_hours) = print_hours {
+            schedule.print_hours = print_hours;
+        }
+        schedule.clone()
+    })
+}
+
+/// Adds a recurring maintenance task to a host
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `name` - Task description, e.g. "Lube Z rods"
+/// * `interval_hours` - Print hours between two services
+/// 
+/// # Returns
+/// * Updated maintenance schedule
+#[tauri::command]
+pub async fn add_maintenance_task_command(
+    host: String,
+    name: String,
+    interval_hours: f64,
+) -> Result<HostMaintenance, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Task name must not be empty".to_string());
+    }
+    if !interval_hours.is_finite() || interval_hours <= 0.0 {
+        return Err("Interval must be a positive number of hours".to_string());
+    }
+
+    let print_hours = get_print_hours(&host).await.ok();
+
+    AppSettings::update(|settings| {
+        let schedule = settings.maintenance_schedules.entry(host).or_default();
+        if let Some(print_hours) = print_hours {
+            schedule.print_hours = print_hours;
+        }
+        schedule.add_task(name, interval_hours);
+        schedule.clone()
+    })
+}
+
+/// Removes a maintenance task from a host
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `task_id` - Task identifier
+#[tauri::command]
+pub async fn remove_maintenance_task_command(host: String, task_id: String) -> Result<(), String> {
+    AppSettings::update(|settings| {
+        if let Some(schedule) = settings.maintenance_schedules.get_mut(&host) {
+            schedule.tasks.retain(|t| t.id != task_id);
+        }
+    })
+}
+
+/// Marks a maintenance task as done and records it in the log
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `task_id` - Task identifier
+/// * `note` - Optional note for the log
+/// 
+/// # Returns
+/// * Updated maintenance schedule
+#[tauri::command]
+pub async fn complete_maintenance_task_command(
+    host: String,
+    task_id: String,
+    note: Option<String>,
+) -> Result<HostMaintenance, String> {
+    let print_hours = get_print_hours(&host).await.ok();
+
+    AppSettings::try_update(|settings| {
+        let schedule = settings.maintenance_schedules.get_mut(&host)
+            .ok_or_else(|| "No maintenance schedule for this host".to_string())?;
+        if let Some(print_hours) = print_hours {
+            schedule.print_hours = print_hours;
+        }
+        schedule.complete_task(&task_id, note.filter(|n| !n.trim().is_empty()))?;
+        Ok(schedule.clone())
+    })
+}
+
+/// Adds a free-form entry to the maintenance log of a host
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `title` - Short description of the work done
+/// * `note` - Optional details
+#[tauri::command]
+pub async fn add_maintenance_log_entry_command(
+    host: String,
+    title: String,
+    note: Option<String>,
+) -> Result<(), String> {
+    let title = title.trim().to_string();
+    if title.is_empty() {
+        return Err("Title must not be empty".to_string());
+    }
+
+    AppSettings::update(|settings| {
+        settings.maintenance_schedules.entry(host).or_default()
+            .add_note(title, note.filter(|n| !n.trim().is_empty()));
+    })
+}
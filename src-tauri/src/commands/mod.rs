@@ -9,6 +9,18 @@ pub mod updater;
 pub mod print_info;
 pub mod background;
 pub mod telegram;
+pub mod server;
+pub mod hosts;
+pub mod maintenance;
+pub mod queue;
+pub mod files;
+pub mod calibration;
+pub mod config_files;
+pub mod schedule;
+pub mod telemetry;
+pub mod rules;
+pub mod quick_actions;
+pub mod notifications;
 
 pub use scan::*;
 pub use printer::*;
@@ -17,3 +29,15 @@ pub use updater::*;
 pub use print_info::*;
 pub use background::*;
 pub use telegram::*;
+pub use server::*;
+pub use hosts::*;
+pub use maintenance::*;
+pub use queue::*;
+pub use files::*;
+pub use calibration::*;
+pub use config_files::*;
+pub use schedule::*;
+pub use telemetry::*;
+pub use rules::*;
+pub use quick_actions::*;
+pub use notifications::*;
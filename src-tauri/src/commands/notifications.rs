@@ -0,0 +1,56 @@
+//! Notification center Tauri commands
+//! 
+//! This module contains Tauri commands for the in-app list of notifications
+//! behind the bell in the UI.
+
+use crate::notifications::center::{
+    clear_notifications, list_notifications, mark_all_notifications_read, mark_notification_read, unread_notification_count,
+    CenterNotification,
+};
+
+/// Lists the notifications of the notification center
+/// 
+/// # Arguments
+/// * `unread_only` - Only list unread notifications (default: false)
+/// 
+/// # Returns
+/// * Notifications, newest first
+#[tauri::command]
+pub fn list_notifications_command(unread_only: Option<bool>) -> Result<Vec<CenterNotification>, String> {
+    Ok(list_notifications(unread_only.unwrap_or(false)))
+}
+
+/// Gets the number of unread notifications, e.g. for the badge of the bell
+#[tauri::command]
+pub fn get_unread_notification_count_command() -> Result<usize, String> {
+    Ok(unread_notification_count())
+}
+
+/// Marks a notification as read
+/// 
+/// # Arguments
+/// * `id` - Notification ID
+#[tauri::command]
+pub fn mark_notification_read_command(id: u64) -> Result<(), String> {
+    if mark_notification_read(id) {
+        Ok(())
+    } else {
+        Err(format!("Notification {} not found", id))
+    }
+}
+
+/// Marks every notification as read
+/// 
+/// # Returns
+/// * Number of notifications that were unread
+#[tauri::command]
+pub fn mark_all_notifications_read_command() -> Result<usize, String> {
+    Ok(mark_all_notifications_read())
+}
+
+/// Removes every notification from the notification center
+#[tauri::command]
+pub fn clear_notifications_command() -> Result<(), String> {
+    clear_notifications();
+    Ok(())
+}
@@ -4,8 +4,12 @@
 //! and progress data from Moonraker printers.
 
 use crate::error::error_to_string;
+use crate::api::history::{get_current_job, get_history_stats};
 use crate::api::print_info::{get_print_info, get_print_progress, format_duration};
-use crate::models::print_info::PrintJobInfo;
+use crate::i18n::{format_finish_time, format_timestamp};
+use crate::models::config::{AppSettings, CostSettings};
+use crate::models::job_notes::{JobNote, JobNotes};
+use crate::models::print_info::{HistoryStats, PrintJobInfo};
 
 /// Gets comprehensive print information for a host
 /// 
@@ -33,14 +37,121 @@ pub async fn get_print_progress_command(host: String, port: Option<u16>) -> Resu
     get_print_progress(&host, port).await.map_err(error_to_string)
 }
 
-/// Formats duration in human readable format
+/// Formats duration in human readable format in the configured language
 /// 
 /// # Arguments
 /// * `seconds` - Duration in seconds
 /// 
 /// # Returns
-/// * Formatted duration string (e.g., "2h 15m 30s")
+/// * Formatted duration string (e.g., "2h 15m 30s" or "1d 3h 5m")
 #[tauri::command]
 pub fn format_duration_command(seconds: f64) -> Result<String, String> {
     Ok(format_duration(seconds))
 }
+
+/// Formats a Unix timestamp as local date and time
+/// 
+/// # Arguments
+/// * `timestamp` - Seconds since the Unix epoch
+/// 
+/// # Returns
+/// * Date and time in the configured language and clock format
+#[tauri::command]
+pub fn format_timestamp_command(timestamp: f64) -> Result<String, String> {
+    format_timestamp(timestamp).ok_or_else(|| format!("Invalid timestamp: {}", timestamp))
+}
+
+/// Formats when a job ends from its remaining time
+/// 
+/// # Arguments
+/// * `remaining_seconds` - Estimated remaining time in seconds
+/// 
+/// # Returns
+/// * Text such as "finishes at 14:30"
+#[tauri::command]
+pub fn format_finish_time_command(remaining_seconds: f64) -> Result<String, String> {
+    Ok(format_finish_time(remaining_seconds))
+}
+
+/// Gets lifetime job statistics of a host with the estimated total cost
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Job history totals and estimated cost
+#[tauri::command]
+pub async fn get_history_stats_command(host: String) -> Result<HistoryStats, String> {
+    get_history_stats(&host).await.map_err(error_to_string)
+}
+
+/// Gets cost estimation settings
+#[tauri::command]
+pub async fn get_cost_settings_command() -> Result<CostSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.costs)
+}
+
+/// Updates cost estimation settings
+/// 
+/// # Arguments
+/// * `costs` - New cost settings
+#[tauri::command]
+pub async fn update_cost_settings_command(costs: CostSettings) -> Result<(), String> {
+    if costs.filament_diameter <= 0.0 || costs.filament_density <= 0.0 {
+        return Err("Filament diameter and density must be positive".to_string());
+    }
+
+    AppSettings::update(|settings| {
+        settings.costs = costs;
+    })
+}
+
+/// Lists the notes attached to print jobs, most recently edited first
+/// 
+/// # Arguments
+/// * `host` - Only notes of this host (None: all hosts)
+#[tauri::command]
+pub fn get_job_notes_command(host: Option<String>) -> Result<Vec<JobNote>, String> {
+    let notes = JobNotes::load().map_err(|e| format!("Failed to load job notes: {}", e))?;
+    Ok(notes.list(host.as_deref()))
+}
+
+/// Attaches a note and labels to a print job
+/// 
+/// Without a job id the note goes to the job the printer is running.
+/// An empty note without labels removes the job's entry.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `job_id` - Job id of the Moonraker history (None: the running job)
+/// * `filename` - Printed file, stored for display
+/// * `note` - Free text
+/// * `labels` - Short labels, e.g. "new filament"
+/// 
+/// # Returns
+/// * The stored note, None when it was removed
+#[tauri::command]
+pub async fn set_job_note_command(
+    host: String,
+    job_id: Option<String>,
+    filename: Option<String>,
+    note: String,
+    labels: Vec<String>,
+) -> Result<Option<JobNote>, String> {
+    let (job_id, filename) = match job_id {
+        Some(job_id) => (job_id, filename),
+        None => {
+            let job = get_current_job(&host)
+                .await
+                .map_err(error_to_string)?
+                .ok_or_else(|| format!("No job is running on {}", host))?;
+            (job.job_id, Some(job.filename))
+        }
+    };
+
+    let mut notes = JobNotes::load().map_err(|e| format!("Failed to load job notes: {}", e))?;
+    let stored = notes.set(&host, &job_id, filename, &note, &labels);
+    notes.save().map_err(|e| format!("Failed to save job notes: {}", e))?;
+    Ok(stored)
+}
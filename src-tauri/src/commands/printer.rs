@@ -3,8 +3,19 @@
 //! This module contains Tauri commands for controlling 3D printers.
 
 use crate::error::error_to_string;
-use crate::api::printer::control_printer_with_string;
-use crate::api::moonraker::get_comprehensive_printer_status;
+use std::time::Duration;
+use crate::api::printer::{
+    adjust_z_offset, control_printer_with_string, firmware_restart_and_wait, get_mcu_stats, get_sensors, get_z_offset, home_axes, jog,
+    save_config_and_wait, set_fan_speed, set_led, FIRMWARE_RESTART_TIMEOUT_SECONDS,
+};
+use crate::api::capabilities::{get_printer_capabilities, invalidate_printer_objects};
+use crate::api::peripherals::{get_canbus_devices, DEFAULT_CAN_INTERFACE};
+use crate::api::moonraker::{get_comprehensive_printer_status, query_printer_objects};
+use crate::api::power::{get_power_devices, set_power_device};
+use crate::models::api::{AuxiliarySensor, CanbusDevices, KlippyState, McuStats, ObjectField, PowerDevice};
+use crate::models::config::{AppSettings, AutoPowerOffSettings, ObjectQueryPreset, PreflightSettings, SensorSettings};
+use crate::models::host::PrinterCapabilities;
+use crate::preflight::{run_preflight, start_print_checked, PreflightReport};
 
 /// Controls the printer with the specified action
 /// 
@@ -21,6 +32,140 @@ pub async fn control_printer_command(host: String, action: String) -> Result<ser
         .map_err(error_to_string)
 }
 
+/// Restarts the firmware and waits until Klippy reports a final state
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `timeout_seconds` - Maximum wait (60 seconds when omitted)
+/// 
+/// # Returns
+/// * Final Klippy state ("ready", "error" or "shutdown")
+#[tauri::command]
+pub async fn firmware_restart_command(host: String, timeout_seconds: Option<u64>) -> Result<KlippyState, String> {
+    let timeout = Duration::from_secs(timeout_seconds.unwrap_or(FIRMWARE_RESTART_TIMEOUT_SECONDS));
+    firmware_restart_and_wait(&host, timeout)
+        .await
+        .map_err(error_to_string)
+}
+
+/// Saves pending configuration changes (`SAVE_CONFIG`) and waits until
+/// Klippy has restarted
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `timeout_seconds` - Maximum wait (60 seconds when omitted)
+/// 
+/// # Returns
+/// * Final Klippy state ("ready", "error" or "shutdown")
+#[tauri::command]
+pub async fn save_config_command(host: String, timeout_seconds: Option<u64>) -> Result<KlippyState, String> {
+    let timeout = Duration::from_secs(timeout_seconds.unwrap_or(FIRMWARE_RESTART_TIMEOUT_SECONDS));
+    save_config_and_wait(&host, timeout)
+        .await
+        .map_err(error_to_string)
+}
+
+/// Sets the speed of the part cooling fan or a named fan
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `fan` - `[fan_generic]` name such as a chamber fan (part cooling fan when omitted)
+/// * `speed` - Speed in percent (0 - 100)
+/// 
+/// # Returns
+/// * API response as JSON
+#[tauri::command]
+pub async fn set_fan_speed_command(host: String, fan: Option<String>, speed: f64) -> Result<serde_json::Value, String> {
+    set_fan_speed(&host, fan.as_deref().filter(|f| !f.is_empty()), speed)
+        .await
+        .map_err(error_to_string)
+}
+
+/// Sets the color of a Klipper-configured LED
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `led` - LED config name
+/// * `red`, `green`, `blue` - Color channels (0.0 - 1.0)
+/// * `white` - White channel for RGBW LEDs
+/// 
+/// # Returns
+/// * API response as JSON
+#[tauri::command]
+pub async fn set_led_command(
+    host: String,
+    led: String,
+    red: f64,
+    green: f64,
+    blue: f64,
+    white: Option<f64>,
+) -> Result<serde_json::Value, String> {
+    set_led(&host, &led, red, green, blue, white)
+        .await
+        .map_err(error_to_string)
+}
+
+/// Homes printer axes (refused while printing)
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `axes` - Axes to home, e.g. "xy" (all axes when empty)
+/// 
+/// # Returns
+/// * API response as JSON
+#[tauri::command]
+pub async fn home_axes_command(host: String, axes: String) -> Result<serde_json::Value, String> {
+    home_axes(&host, &axes)
+        .await
+        .map_err(error_to_string)
+}
+
+/// Moves one axis by a relative distance (refused while printing)
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `axis` - "x", "y", "z" or "e"
+/// * `distance` - Distance in mm
+/// * `feedrate` - Speed in mm/min
+/// 
+/// # Returns
+/// * API response as JSON
+#[tauri::command]
+pub async fn jog_command(host: String, axis: String, distance: f64, feedrate: f64) -> Result<serde_json::Value, String> {
+    jog(&host, &axis, distance, feedrate)
+        .await
+        .map_err(error_to_string)
+}
+
+/// Gets the current Z offset (babystep)
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Z offset in mm
+#[tauri::command]
+pub async fn get_z_offset_command(host: String) -> Result<f64, String> {
+    get_z_offset(&host)
+        .await
+        .map_err(error_to_string)
+}
+
+/// Babysteps the Z offset, e.g. for first-layer tuning during a print
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `delta` - Change in mm (positive raises the nozzle)
+/// 
+/// # Returns
+/// * Z offset after the adjustment
+#[tauri::command]
+pub async fn adjust_z_offset_command(host: String, delta: f64) -> Result<f64, String> {
+    adjust_z_offset(&host, delta)
+        .await
+        .map_err(error_to_string)
+}
+
 /// Gets comprehensive printer status information
 /// 
 /// # Arguments
@@ -34,3 +179,308 @@ pub async fn get_printer_status_command(host: String) -> Result<serde_json::Valu
         .await
         .map_err(error_to_string)
 }
+
+/// Gets the features of a printer from its Klipper object list
+/// 
+/// The list is cached after the first request and fetched again after
+/// Klippy restarts.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `refresh` - Fetch the object list even if it is cached
+#[tauri::command]
+pub async fn get_printer_capabilities_command(host: String, refresh: Option<bool>) -> Result<PrinterCapabilities, String> {
+    if refresh.unwrap_or(false) {
+        invalidate_printer_objects(&host);
+    }
+    get_printer_capabilities(&host).await.map_err(error_to_string)
+}
+
+/// Gets the saved printer object query presets
+/// 
+/// # Returns
+/// * All presets in saved order
+#[tauri::command]
+pub fn get_object_query_presets_command() -> Result<Vec<ObjectQueryPreset>, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.object_query_presets)
+}
+
+/// Saves a printer object query preset, replacing one with the same name
+/// 
+/// # Arguments
+/// * `preset` - Preset name and object list
+#[tauri::command]
+pub fn save_object_query_preset_command(preset: ObjectQueryPreset) -> Result<(), String> {
+    let name = preset.name.trim().to_string();
+    let objects: Vec<String> = preset.objects
+        .iter()
+        .map(|o| o.trim().to_string())
+        .filter(|o| !o.is_empty())
+        .collect();
+    if name.is_empty() {
+        return Err("Preset name cannot be empty".to_string());
+    }
+    if objects.is_empty() {
+        return Err("Preset must contain at least one printer object".to_string());
+    }
+
+    AppSettings::update(|settings| {
+        let preset = ObjectQueryPreset { name, objects };
+        match settings.object_query_presets.iter_mut().find(|p| p.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => settings.object_query_presets.push(preset),
+        }
+    })?;
+    Ok(())
+}
+
+/// Deletes a printer object query preset
+/// 
+/// # Arguments
+/// * `name` - Preset name
+#[tauri::command]
+pub fn delete_object_query_preset_command(name: String) -> Result<(), String> {
+    AppSettings::update(|settings| {
+        settings.object_query_presets.retain(|p| p.name != name);
+    })?;
+    Ok(())
+}
+
+/// Queries printer objects for the raw-data inspector
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `preset` - Name of a saved preset to query
+/// * `objects` - Object names to query when no preset is given
+/// 
+/// # Returns
+/// * Typed key/value pairs of the queried objects
+#[tauri::command]
+pub async fn query_printer_objects_command(
+    host: String,
+    preset: Option<String>,
+    objects: Option<Vec<String>>,
+) -> Result<Vec<ObjectField>, String> {
+    let objects = match preset {
+        Some(name) => {
+            let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+            settings.object_query_presets
+                .into_iter()
+                .find(|p| p.name == name)
+                .map(|p| p.objects)
+                .ok_or_else(|| format!("Query preset '{}' not found", name))?
+        }
+        None => objects.unwrap_or_default(),
+    };
+
+    query_printer_objects(&host, &objects)
+        .await
+        .map_err(error_to_string)
+}
+
+/// Gets the power devices (smart plugs, relays) configured in Moonraker
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Power devices with their current state
+#[tauri::command]
+pub async fn get_power_devices_command(host: String) -> Result<Vec<PowerDevice>, String> {
+    get_power_devices(&host).await.map_err(error_to_string)
+}
+
+/// Switches a Moonraker power device on or off
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `device` - Power device name
+/// * `on` - true to switch on, false to switch off
+#[tauri::command]
+pub async fn set_power_device_command(host: String, device: String, on: bool) -> Result<serde_json::Value, String> {
+    set_power_device(&host, &device, on).await.map_err(error_to_string)
+}
+
+/// Gets the automatic power off settings of a host
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Settings, or None when the host is not switched off automatically
+#[tauri::command]
+pub fn get_auto_power_off_command(host: String) -> Result<Option<AutoPowerOffSettings>, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.auto_power_off.get(&host).cloned())
+}
+
+/// Enables or disables powering off a host after a finished print
+/// 
+/// The background monitor switches the power device off once the job is
+/// complete and the nozzle has cooled below the threshold.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `config` - Power off settings, or None to disable
+#[tauri::command]
+pub fn set_auto_power_off_command(host: String, config: Option<AutoPowerOffSettings>) -> Result<(), String> {
+    AppSettings::try_update(|settings| {
+        match config {
+            Some(config) => {
+                if config.device.as_deref().is_none_or(|d| d.trim().is_empty()) {
+                    return Err("Choose the power device to switch off".to_string());
+                }
+                if !(0.0..=300.0).contains(&config.temperature_threshold) {
+                    return Err("Temperature threshold must be between 0 and 300 °C".to_string());
+                }
+                settings.auto_power_off.insert(host, config);
+            }
+            None => {
+                settings.auto_power_off.remove(&host);
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Gets the readings of a printer's additional sensors, e.g. enclosure or dryer
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Sensors with the labels chosen by the user
+#[tauri::command]
+pub async fn get_sensors_command(host: String) -> Result<Vec<AuxiliarySensor>, String> {
+    let mut sensors = get_sensors(&host).await.map_err(error_to_string)?;
+    let settings = AppSettings::load().unwrap_or_default();
+    if let Some(configured) = settings.sensors.get(&host) {
+        for sensor in &mut sensors {
+            sensor.label = configured.get(&sensor.name).and_then(|c| c.label.clone());
+        }
+    }
+    Ok(sensors)
+}
+
+/// Gets the connection statistics of a printer's micro-controllers
+/// 
+/// Rising retransmit or invalid byte counts point to a failing USB or CAN link.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+#[tauri::command]
+pub async fn get_mcu_stats_command(host: String) -> Result<Vec<McuStats>, String> {
+    get_mcu_stats(&host).await.map_err(error_to_string)
+}
+
+/// Lists the unassigned CAN nodes a host sees, e.g. a new toolhead board
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `interface` - CAN interface (default "can0")
+/// * `ssh_user` - Run `canbus_query.py` over SSH as this user when Moonraker cannot list the nodes
+#[tauri::command]
+pub async fn list_canbus_devices_command(host: String, interface: Option<String>, ssh_user: Option<String>) -> Result<CanbusDevices, String> {
+    let interface = interface.unwrap_or_else(|| DEFAULT_CAN_INTERFACE.to_string());
+    get_canbus_devices(&host, &interface, ssh_user.as_deref())
+        .await
+        .map_err(error_to_string)
+}
+
+/// Runs the pre-flight checks of a host without starting a print
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `bed_clear_confirmed` - Whether the user confirmed that the bed is clear
+#[tauri::command]
+pub async fn run_preflight_command(host: String, bed_clear_confirmed: bool) -> Result<PreflightReport, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(run_preflight(&host, &settings.preflight, bed_clear_confirmed).await)
+}
+
+/// Starts printing a file on a host if the pre-flight checks pass
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `file_name` - File path relative to the `gcodes` root
+/// * `bed_clear_confirmed` - Whether the user confirmed that the bed is clear
+/// 
+/// # Returns
+/// * Report of the checks, with `started` set when the print was started
+#[tauri::command]
+pub async fn start_print_command(host: String, file_name: String, bed_clear_confirmed: bool) -> Result<PreflightReport, String> {
+    start_print_checked(&host, &file_name, bed_clear_confirmed).await
+}
+
+/// Gets the checks run before remote print starts
+#[tauri::command]
+pub fn get_preflight_settings_command() -> Result<PreflightSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.preflight)
+}
+
+/// Saves the checks run before remote print starts
+/// 
+/// # Arguments
+/// * `preflight` - New settings
+#[tauri::command]
+pub fn update_preflight_settings_command(preflight: PreflightSettings) -> Result<(), String> {
+    if let (Some(min), Some(max)) = (preflight.chamber_min_temperature, preflight.chamber_max_temperature) {
+        if min > max {
+            return Err("The lowest chamber temperature is above the highest".to_string());
+        }
+    }
+    AppSettings::update(|settings| {
+        settings.preflight = preflight;
+    })?;
+    Ok(())
+}
+
+/// Gets the names and alert thresholds of a printer's additional sensors
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Settings keyed by sensor name
+#[tauri::command]
+pub fn get_sensor_settings_command(host: String) -> Result<std::collections::HashMap<String, SensorSettings>, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.sensors.get(&host).cloned().unwrap_or_default())
+}
+
+/// Sets the name and alert thresholds of an additional sensor
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `sensor` - Sensor name from the Klipper configuration
+/// * `config` - Name and thresholds, or None to reset them
+#[tauri::command]
+pub fn set_sensor_settings_command(host: String, sensor: String, config: Option<SensorSettings>) -> Result<(), String> {
+    AppSettings::try_update(|settings| {
+        match config {
+            Some(mut config) => {
+                if let (Some(min), Some(max)) = (config.min_temperature, config.max_temperature) {
+                    if min >= max {
+                        return Err("Minimum temperature must be below the maximum".to_string());
+                    }
+                }
+                if config.max_humidity.is_some_and(|h| !(0.0..=100.0).contains(&h)) {
+                    return Err("Humidity threshold must be between 0 and 100 %".to_string());
+                }
+                config.label = config.label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty());
+                settings.sensors.entry(host).or_default().insert(sensor, config);
+            }
+            None => {
+                if let Some(sensors) = settings.sensors.get_mut(&host) {
+                    sensors.remove(&sensor);
+                    if sensors.is_empty() {
+                        settings.sensors.remove(&host);
+                    }
+                }
+            }
+        }
+        Ok(())
+    })
+}
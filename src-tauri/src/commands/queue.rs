@@ -0,0 +1,67 @@
+//! Print queue Tauri commands
+//! 
+//! This module contains Tauri commands for managing the MHS-level print queue.
+
+use tauri::State;
+use crate::models::{PrintQueue, QueueJob};
+use crate::print_queue::PrintQueueState;
+
+/// Gets the print queue
+/// 
+/// # Returns
+/// * All queued, running and finished jobs
+#[tauri::command]
+pub async fn get_print_queue_command(
+    state: State<'_, PrintQueueState>,
+) -> Result<PrintQueue, String> {
+    Ok(state.queue().lock().await.clone())
+}
+
+/// Adds a local g-code file to the print queue
+/// 
+/// # Arguments
+/// * `file_path` - Local path of the g-code file
+/// * `required_tags` - Tags a printer must carry to take the job
+/// 
+/// # Returns
+/// * The queued job
+#[tauri::command]
+pub async fn add_queue_job_command(
+    file_path: String,
+    required_tags: Vec<String>,
+    state: State<'_, PrintQueueState>,
+) -> Result<QueueJob, String> {
+    state.add(file_path, required_tags).await
+}
+
+/// Removes a job from the print queue
+/// 
+/// # Arguments
+/// * `job_id` - Job identifier
+#[tauri::command]
+pub async fn remove_queue_job_command(
+    job_id: String,
+    state: State<'_, PrintQueueState>,
+) -> Result<(), String> {
+    state.remove(&job_id).await
+}
+
+/// Removes all completed and failed jobs from the print queue
+#[tauri::command]
+pub async fn clear_finished_queue_jobs_command(
+    state: State<'_, PrintQueueState>,
+) -> Result<(), String> {
+    state.clear_finished().await
+}
+
+/// Confirms that the bed of a printer is empty so it can take the next job
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+#[tauri::command]
+pub async fn mark_bed_cleared_command(
+    host: String,
+    state: State<'_, PrintQueueState>,
+) -> Result<(), String> {
+    state.mark_bed_cleared(&host).await
+}
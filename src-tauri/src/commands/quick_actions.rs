@@ -0,0 +1,84 @@
+//! Custom quick action Tauri commands
+//! 
+//! This module contains Tauri commands for the buttons users add to a host,
+//! e.g. "run PURGE_NOZZLE", "toggle the enclosure lights" or "restart the
+//! webcam service". The same actions are offered in the Telegram host menu.
+
+use crate::models::config::AppSettings;
+use crate::models::quick_actions::QuickAction;
+use crate::quick_actions::{find_quick_action, run_quick_action};
+
+/// Gets the quick actions of a host
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Actions in button order
+#[tauri::command]
+pub fn get_quick_actions_command(host: String) -> Result<Vec<QuickAction>, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.quick_actions.get(&host).cloned().unwrap_or_default())
+}
+
+/// Adds a quick action to a host, or replaces the action with the same identifier
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `action` - Action to save; an empty identifier adds a new action
+/// 
+/// # Returns
+/// * The saved action
+#[tauri::command]
+pub fn save_quick_action_command(host: String, mut action: QuickAction) -> Result<QuickAction, String> {
+    action.validate()?;
+    AppSettings::update(|settings| {
+        let actions = settings.quick_actions.entry(host).or_default();
+        match actions.iter_mut().find(|a| !action.id.is_empty() && a.id == action.id) {
+            Some(existing) => *existing = action.clone(),
+            None => {
+                action.id = format!("{:016x}", rand::random::<u64>());
+                actions.push(action.clone());
+            }
+        }
+    })?;
+    Ok(action)
+}
+
+/// Removes a quick action from a host
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `id` - Action identifier
+#[tauri::command]
+pub fn remove_quick_action_command(host: String, id: String) -> Result<(), String> {
+    AppSettings::try_update(|settings| {
+        let actions = settings
+            .quick_actions
+            .get_mut(&host)
+            .filter(|actions| actions.iter().any(|a| a.id == id))
+            .ok_or_else(|| format!("Quick action not found: {}", id))?;
+        actions.retain(|a| a.id != id);
+        if actions.is_empty() {
+            settings.quick_actions.remove(&host);
+        }
+        Ok(())
+    })
+}
+
+/// Runs a quick action of a host
+/// 
+/// Confirmation is up to the caller; the action's `confirm` flag tells
+/// whether to ask first.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `id` - Action identifier
+/// 
+/// # Returns
+/// * Output of the action
+#[tauri::command]
+pub async fn run_quick_action_command(host: String, id: String) -> Result<String, String> {
+    let action = find_quick_action(&host, &id)?;
+    run_quick_action(&host, &action).await
+}
@@ -0,0 +1,75 @@
+//! Automation rule Tauri commands
+//! 
+//! This module contains Tauri commands for managing "if this then that"
+//! rules, e.g. "bed above 60 °C while idle: run TURN_OFF_HEATERS". The rules
+//! are evaluated by the background monitor.
+
+use crate::models::rules::{AutomationRule, AutomationRules};
+
+/// Gets the automation rules
+/// 
+/// # Returns
+/// * All rules, enabled or not
+#[tauri::command]
+pub fn get_rules_command() -> Result<Vec<AutomationRule>, String> {
+    let rules = AutomationRules::load().map_err(|e| format!("Failed to load automation rules: {}", e))?;
+    Ok(rules.rules)
+}
+
+/// Adds a rule, or replaces the rule with the same identifier
+/// 
+/// # Arguments
+/// * `rule` - Rule to save; an empty identifier adds a new rule
+/// 
+/// # Returns
+/// * The saved rule
+#[tauri::command]
+pub fn save_rule_command(mut rule: AutomationRule) -> Result<AutomationRule, String> {
+    rule.validate()?;
+    let mut rules = AutomationRules::load().map_err(|e| format!("Failed to load automation rules: {}", e))?;
+    match rules.rules.iter_mut().find(|r| !rule.id.is_empty() && r.id == rule.id) {
+        Some(existing) => {
+            rule.last_triggered = existing.last_triggered.take();
+            *existing = rule.clone();
+        }
+        None => {
+            rule.id = format!("{:016x}", rand::random::<u64>());
+            rule.last_triggered = None;
+            rules.rules.push(rule.clone());
+        }
+    }
+    rules.save().map_err(|e| format!("Failed to save automation rules: {}", e))?;
+    Ok(rule)
+}
+
+/// Enables or disables a rule
+/// 
+/// # Arguments
+/// * `id` - Rule identifier
+/// * `enabled` - Whether the monitor evaluates the rule
+#[tauri::command]
+pub fn set_rule_enabled_command(id: String, enabled: bool) -> Result<(), String> {
+    let mut rules = AutomationRules::load().map_err(|e| format!("Failed to load automation rules: {}", e))?;
+    let rule = rules
+        .rules
+        .iter_mut()
+        .find(|r| r.id == id)
+        .ok_or_else(|| format!("Rule not found: {}", id))?;
+    rule.enabled = enabled;
+    rules.save().map_err(|e| format!("Failed to save automation rules: {}", e))
+}
+
+/// Removes a rule
+/// 
+/// # Arguments
+/// * `id` - Rule identifier
+#[tauri::command]
+pub fn remove_rule_command(id: String) -> Result<(), String> {
+    let mut rules = AutomationRules::load().map_err(|e| format!("Failed to load automation rules: {}", e))?;
+    let count = rules.rules.len();
+    rules.rules.retain(|r| r.id != id);
+    if rules.rules.len() == count {
+        return Err(format!("Rule not found: {}", id));
+    }
+    rules.save().map_err(|e| format!("Failed to save automation rules: {}", e))
+}
@@ -2,22 +2,82 @@
 //! 
 //! This module contains Tauri commands for network scanning and host discovery.
 
+use std::collections::HashMap;
+use tauri::State;
+use crate::background_monitor::BackgroundMonitorState;
+use crate::commands::telegram::TelegramBotState;
 use crate::error::error_to_string;
-use crate::models::{SubnetConfig, HostInfo};
-use crate::network::scanner::{scan_network, scan_host, check_host_status};
+use crate::models::{SubnetConfig, HostInfo, LocalNetwork, NetworkChange};
+use crate::models::config::{AppSettings, NetworkChangeSettings, OverlayScanSettings, ScanLimitSettings, ScanMethod};
+use crate::api::host_limiter::set_host_request_concurrency;
+use crate::network::ip_utils::{is_valid_ip, split_host_port};
+use crate::network::port_checker::{clear_port_cache, port_cache_stats, PortCacheStats};
+use crate::api::moonraker::get_machine_identity;
+use crate::network::reservation::DhcpReservation;
+use crate::network::scanner::{relink_moved_hosts, scan_network, scan_host};
+use crate::notifications::system::send_host_moved_notification;
+use crate::network::status_cache::set_status_cache_ttl;
+use crate::network::syn_scan::syn_scan_available;
+use crate::network::watcher::local_networks;
 
 /// Scans the network for Moonraker-enabled printers
 /// 
+/// Known printers found under a new address have their settings moved to
+/// it, are listed in `relinked` and announced with a notification.
+/// 
 /// # Arguments
 /// * `subnets` - Vector of subnet configurations to scan
 /// 
 /// # Returns
 /// * ScanResult with discovered hosts
 #[tauri::command]
-pub async fn scan_network_command(subnets: Vec<SubnetConfig>) -> Result<crate::models::ScanResult, String> {
-    scan_network(subnets)
+pub async fn scan_network_command(
+    subnets: Vec<SubnetConfig>,
+    telegram: State<'_, TelegramBotState>,
+) -> Result<crate::models::ScanResult, String> {
+    let mut result = scan_network(subnets)
         .await
-        .map_err(error_to_string)
+        .map_err(error_to_string)?;
+    relink_moved_hosts(&telegram.hosts.lock().await, &mut result);
+    for relink in &result.relinked {
+        send_host_moved_notification(relink);
+    }
+    Ok(result)
+}
+
+/// Writes the DHCP reservation that pins a printer to its current address
+/// 
+/// The MAC address is read from the printer; the one stored at the last
+/// scan is used while it is unreachable.
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// 
+/// # Returns
+/// * Reservation in dnsmasq, ISC dhcpd, OpenWrt and MikroTik syntax
+#[tauri::command]
+pub async fn get_dhcp_reservation_command(
+    host: String,
+    telegram: State<'_, TelegramBotState>,
+) -> Result<DhcpReservation, String> {
+    let known = telegram.hosts.lock().await.iter().find(|h| h.ip_address == host).cloned();
+    let address = split_host_port(&host).0.to_string();
+    let interface_mac = match get_machine_identity(&host).await {
+        Ok(identity) => identity
+            .interfaces
+            .into_iter()
+            .find(|i| i.ipv4_addresses.contains(&address))
+            .map(|i| i.mac_address),
+        Err(e) => {
+            eprintln!("Failed to read network interfaces of {}: {}", host, e);
+            None
+        }
+    };
+    let mac_address = interface_mac
+        .or_else(|| known.as_ref().and_then(|h| h.mac_addresses.first().cloned()))
+        .ok_or_else(|| format!("MAC address of {} is unknown", host))?;
+    let hostname = known.map_or_else(|| host.clone(), |h| h.hostname);
+    Ok(DhcpReservation::new(&hostname, &mac_address, &address))
 }
 
 /// Gets detailed information about a specific host
@@ -36,12 +96,251 @@ pub async fn get_host_info_command(host: String) -> Result<HostInfo, String> {
 
 /// Checks the current status of a host
 /// 
+/// The result is also recorded in the host health metrics.
+/// 
 /// # Arguments
 /// * `ip` - Host IP address
 /// 
 /// # Returns
 /// * HostStatusResponse with current status
 #[tauri::command]
-pub async fn check_host_status_command(ip: String) -> Result<crate::models::HostStatusResponse, String> {
-    Ok(check_host_status(&ip).await)
+pub async fn check_host_status_command(
+    ip: String,
+    monitor: State<'_, BackgroundMonitorState>,
+) -> Result<crate::models::HostStatusResponse, String> {
+    Ok(BackgroundMonitorState::check_and_record(&monitor.health_registry(), &ip).await)
+}
+
+/// Checks the status of many hosts concurrently
+/// 
+/// Replaces one `check_host_status_command` call per host; the results are
+/// also recorded in the host health metrics.
+/// 
+/// # Arguments
+/// * `ips` - Host IP addresses (all stored hosts when omitted)
+/// 
+/// # Returns
+/// * HostStatusResponse keyed by IP address
+#[tauri::command]
+pub async fn refresh_all_hosts_command(
+    ips: Option<Vec<String>>,
+    monitor: State<'_, BackgroundMonitorState>,
+    telegram: State<'_, TelegramBotState>,
+) -> Result<HashMap<String, crate::models::HostStatusResponse>, String> {
+    let ips = match ips {
+        Some(ips) => ips,
+        None => telegram.hosts.lock().await.iter().map(|h| h.ip_address.clone()).collect(),
+    };
+    Ok(BackgroundMonitorState::check_all_and_record(&monitor.health_registry(), ips).await)
+}
+
+/// Gets how long a fetched host status is shared between callers
+/// 
+/// # Returns
+/// * Cache TTL in milliseconds
+#[tauri::command]
+pub async fn get_status_cache_ttl_command() -> Result<u64, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.status_cache_ttl_ms)
+}
+
+/// Sets how long a fetched host status is shared between callers
+/// 
+/// # Arguments
+/// * `ttl_ms` - Cache TTL in milliseconds (0 disables caching, concurrent requests are still shared)
+#[tauri::command]
+pub async fn set_status_cache_ttl_command(ttl_ms: u64) -> Result<(), String> {
+    AppSettings::update(|settings| {
+        settings.status_cache_ttl_ms = ttl_ms;
+    })?;
+    set_status_cache_ttl(ttl_ms);
+    Ok(())
+}
+
+/// Gets how many API requests may run at once per printer
+#[tauri::command]
+pub async fn get_host_request_concurrency_command() -> Result<u32, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.host_request_concurrency)
+}
+
+/// Sets how many API requests may run at once per printer
+/// 
+/// # Arguments
+/// * `limit` - Simultaneous requests per printer, further requests wait (0: unlimited)
+#[tauri::command]
+pub async fn set_host_request_concurrency_command(limit: u32) -> Result<(), String> {
+    AppSettings::update(|settings| {
+        settings.host_request_concurrency = limit;
+    })?;
+    set_host_request_concurrency(limit);
+    Ok(())
+}
+
+/// Gets the network scan time budget and early-exit settings
+#[tauri::command]
+pub async fn get_scan_limits_command() -> Result<ScanLimitSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.scan_limits)
+}
+
+/// Saves the network scan time budget and early-exit settings
+/// 
+/// # Arguments
+/// * `scan_limits` - New limits (0 disables a limit)
+#[tauri::command]
+pub async fn update_scan_limits_command(scan_limits: ScanLimitSettings) -> Result<(), String> {
+    AppSettings::update(|settings| {
+        settings.scan_limits = scan_limits;
+    })?;
+    Ok(())
+}
+
+/// Gets how network scans probe the Moonraker port
+#[tauri::command]
+pub async fn get_scan_method_command() -> Result<ScanMethod, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.scan_method)
+}
+
+/// Sets how network scans probe the Moonraker port
+/// 
+/// SYN scanning is saved even when it is not available right now; scans
+/// then fall back to connect scanning.
+/// 
+/// # Arguments
+/// * `scan_method` - Probing method
+#[tauri::command]
+pub async fn set_scan_method_command(scan_method: ScanMethod) -> Result<(), String> {
+    AppSettings::update(|settings| {
+        settings.scan_method = scan_method;
+    })?;
+    Ok(())
+}
+
+/// Gets the timeout and peer discovery of overlay network scans
+#[tauri::command]
+pub async fn get_overlay_scan_settings_command() -> Result<OverlayScanSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.overlay_scan)
+}
+
+/// Saves the timeout and peer discovery of overlay network scans
+/// 
+/// # Arguments
+/// * `overlay_scan` - New settings
+#[tauri::command]
+pub async fn update_overlay_scan_settings_command(overlay_scan: OverlayScanSettings) -> Result<(), String> {
+    if overlay_scan.timeout_ms == 0 {
+        return Err("The probe timeout must be at least 1 ms".to_string());
+    }
+    AppSettings::update(|settings| {
+        settings.overlay_scan = overlay_scan;
+    })?;
+    Ok(())
+}
+
+/// Gets the additional Moonraker ports probed per IP address
+/// 
+/// # Returns
+/// * Ports keyed by IP address
+#[tauri::command]
+pub async fn get_moonraker_ports_command() -> Result<HashMap<String, Vec<u16>>, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.moonraker_ports)
+}
+
+/// Sets the Moonraker ports probed on an IP address
+/// 
+/// Each Klipper instance found on one of them becomes a separate host
+/// identified as "ip:port".
+/// 
+/// # Arguments
+/// * `ip` - IP address of the machine
+/// * `ports` - Ports of its Moonraker instances (empty: only 7125)
+#[tauri::command]
+pub async fn set_moonraker_ports_command(ip: String, mut ports: Vec<u16>) -> Result<(), String> {
+    if !is_valid_ip(&ip) {
+        return Err(format!("Invalid IP address: {}", ip));
+    }
+    if ports.contains(&0) {
+        return Err("Port 0 is not a valid Moonraker port".to_string());
+    }
+    ports.sort_unstable();
+    ports.dedup();
+
+    AppSettings::update(|settings| {
+        if ports.is_empty() {
+            settings.moonraker_ports.remove(&ip);
+        } else {
+            settings.moonraker_ports.insert(ip, ports);
+        }
+    })?;
+    Ok(())
+}
+
+/// Checks whether SYN scanning works (Linux with root or CAP_NET_RAW)
+#[tauri::command]
+pub async fn is_syn_scan_available_command() -> Result<bool, String> {
+    Ok(syn_scan_available())
+}
+
+/// Gets the statistics of the cache of ports found closed by recent scans
+#[tauri::command]
+pub async fn get_port_cache_stats_command() -> Result<PortCacheStats, String> {
+    Ok(port_cache_stats())
+}
+
+/// Clears the closed port cache so the next scan probes every address
+#[tauri::command]
+pub async fn clear_port_cache_command() -> Result<(), String> {
+    clear_port_cache();
+    Ok(())
+}
+
+/// Gets the IPv4 networks the machine is connected to
+/// 
+/// # Returns
+/// * Networks with interface, own address and subnet
+#[tauri::command]
+pub async fn get_local_networks_command() -> Result<Vec<LocalNetwork>, String> {
+    Ok(local_networks())
+}
+
+/// Gets the last detected network change the user has not dismissed
+/// 
+/// Changes are detected by the background monitor.
+#[tauri::command]
+pub async fn get_network_change_command(
+    state: State<'_, BackgroundMonitorState>,
+) -> Result<Option<NetworkChange>, String> {
+    Ok(state.network_watcher().lock().await.last_change())
+}
+
+/// Dismisses the last detected network change
+#[tauri::command]
+pub async fn dismiss_network_change_command(
+    state: State<'_, BackgroundMonitorState>,
+) -> Result<(), String> {
+    state.network_watcher().lock().await.dismiss_change();
+    Ok(())
+}
+
+/// Gets the network change settings
+#[tauri::command]
+pub async fn get_network_change_settings_command() -> Result<NetworkChangeSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.network_change)
+}
+
+/// Saves the network change settings
+/// 
+/// # Arguments
+/// * `network_change` - New settings
+#[tauri::command]
+pub async fn update_network_change_settings_command(network_change: NetworkChangeSettings) -> Result<(), String> {
+    AppSettings::update(|settings| {
+        settings.network_change = network_change;
+    })?;
+    Ok(())
 }
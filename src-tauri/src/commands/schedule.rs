@@ -0,0 +1,106 @@
+//! Scheduled print action Tauri commands
+//! 
+//! This module contains Tauri commands for "pause at 19:00" or "cancel if
+//! not complete by 80% at midnight" rules on running prints. The rules are
+//! carried out by the background monitor.
+
+use crate::api::print_info::get_print_info;
+use crate::error::error_to_string;
+use crate::models::{parse_schedule_time, ScheduleStatus, ScheduledAction, ScheduledActionKind, ScheduledActions};
+
+/// Gets the scheduled actions
+/// 
+/// # Arguments
+/// * `host` - Host IP address (optional, all hosts when omitted)
+/// 
+/// # Returns
+/// * Pending and finished scheduled actions
+#[tauri::command]
+pub fn get_scheduled_actions_command(host: Option<String>) -> Result<Vec<ScheduledAction>, String> {
+    let schedules = ScheduledActions::load().map_err(|e| format!("Failed to load scheduled actions: {}", e))?;
+    Ok(schedules
+        .actions
+        .into_iter()
+        .filter(|a| host.as_ref().is_none_or(|h| a.host == *h))
+        .collect())
+}
+
+/// Schedules a pause or cancel of the running print of a host
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `action` - "pause" or "cancel"
+/// * `at_time` - Time to act, local "HH:MM" (next occurrence) or RFC 3339
+/// * `at_progress` - Progress percentage to act at
+/// * `below_progress` - Only act while the progress is below this percentage
+/// 
+/// # Returns
+/// * The scheduled action
+#[tauri::command]
+pub async fn add_scheduled_action_command(
+    host: String,
+    action: ScheduledActionKind,
+    at_time: Option<String>,
+    at_progress: Option<f64>,
+    below_progress: Option<f64>,
+) -> Result<ScheduledAction, String> {
+    let at_time = at_time
+        .filter(|t| !t.trim().is_empty())
+        .map(|t| parse_schedule_time(&t))
+        .transpose()?;
+    if at_time.is_none() && at_progress.is_none() {
+        return Err("A time or a progress threshold is required".to_string());
+    }
+    if [at_progress, below_progress].iter().flatten().any(|p| !(0.0..=100.0).contains(p)) {
+        return Err("Progress must be between 0 and 100".to_string());
+    }
+
+    let job = get_print_info(&host, None)
+        .await
+        .map_err(error_to_string)?
+        .filter(|j| j.status == "printing" || j.status == "paused")
+        .ok_or_else(|| format!("No print is running on {}", host))?;
+
+    let scheduled = ScheduledAction {
+        id: format!("{:016x}", rand::random::<u64>()),
+        host: host.clone(),
+        filename: job.filename.clone(),
+        action,
+        at_time: at_time.map(|t| t.to_rfc3339()),
+        at_progress,
+        below_progress,
+        status: ScheduleStatus::Pending,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        finished_at: None,
+        error: None,
+    };
+
+    ScheduledActions::update(|schedules| {
+        // Finished rules of earlier prints on this host are no longer interesting
+        schedules
+            .actions
+            .retain(|a| a.host != host || a.status == ScheduleStatus::Pending || a.filename == job.filename);
+        schedules.actions.push(scheduled.clone());
+    })
+    .map_err(|e| format!("Failed to save scheduled actions: {}", e))?;
+
+    Ok(scheduled)
+}
+
+/// Removes a scheduled action
+/// 
+/// # Arguments
+/// * `id` - Scheduled action identifier
+#[tauri::command]
+pub fn remove_scheduled_action_command(id: String) -> Result<(), String> {
+    let removed = ScheduledActions::update(|schedules| {
+        let count = schedules.actions.len();
+        schedules.actions.retain(|a| a.id != id);
+        schedules.actions.len() != count
+    })
+    .map_err(|e| format!("Failed to save scheduled actions: {}", e))?;
+    if !removed {
+        return Err(format!("Scheduled action not found: {}", id));
+    }
+    Ok(())
+}
@@ -0,0 +1,66 @@
+//! REST API server Tauri commands
+//! 
+//! This module contains Tauri commands for managing the embedded REST API server.
+
+use tauri::State;
+use crate::background_monitor::BackgroundMonitorState;
+use crate::commands::telegram::TelegramBotState;
+use crate::models::config::{AppSettings, RestApiSettings};
+use crate::server::RestApiState;
+
+/// Starts the REST API server using the saved settings
+/// 
+/// # Returns
+/// * Address the server listens on
+#[tauri::command]
+pub async fn start_rest_api_command(
+    state: State<'_, RestApiState>,
+    telegram: State<'_, TelegramBotState>,
+    monitor: State<'_, BackgroundMonitorState>,
+) -> Result<String, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    state.start(&settings.rest_api, telegram.hosts.clone(), monitor.health_registry()).await
+}
+
+/// Stops the REST API server
+#[tauri::command]
+pub async fn stop_rest_api_command(
+    state: State<'_, RestApiState>,
+) -> Result<(), String> {
+    state.stop().await
+}
+
+/// Gets the REST API server status
+/// 
+/// # Returns
+/// * Listening address, or None if the server is stopped
+#[tauri::command]
+pub async fn get_rest_api_status_command(
+    state: State<'_, RestApiState>,
+) -> Result<Option<String>, String> {
+    Ok(state.address().await)
+}
+
+/// Gets the REST API server settings
+/// 
+/// # Returns
+/// * Current RestApiSettings
+#[tauri::command]
+pub fn get_rest_api_settings_command() -> Result<RestApiSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.rest_api)
+}
+
+/// Saves the REST API server settings
+/// 
+/// Changes take effect the next time the server is started.
+/// 
+/// # Arguments
+/// * `rest_api` - New server settings
+#[tauri::command]
+pub fn update_rest_api_settings_command(rest_api: RestApiSettings) -> Result<(), String> {
+    AppSettings::update(|settings| {
+        settings.rest_api = rest_api;
+    })?;
+    Ok(())
+}
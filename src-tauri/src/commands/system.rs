@@ -3,30 +3,42 @@
 //! This module contains Tauri commands for system operations like
 //! opening URLs, sending notifications, and SSH connections.
 
+use tauri::State;
+use crate::api::client::{
+    build_web_ui_url, detect_web_port, render_user_agent, set_outbound_http_settings, validate_outbound_http_settings,
+};
+use crate::commands::telegram::TelegramBotState;
+use crate::i18n::{language, set_language, set_use_24_hour_clock, use_24_hour_clock, Language};
+use crate::models::config::{AlertKind, AppSettings, OutboundHttpSettings, DEFAULT_USER_AGENT};
+use crate::network::ip_utils::split_host_port;
+use crate::notifications::permission::{notification_permission, request_notification_permission, NotificationPermission};
+use crate::notifications::snooze::is_alert_silenced_now;
+use crate::notifications::system::{send_host_notification, send_notification};
+use crate::webcam::snapshot::build_stream_url;
 
-use crate::notifications::system::send_notification;
-
-#[cfg(target_os = "macos")]
-use crate::notifications::system::check_notification_permissions;
+/// Checks whether the system shows the app's notifications
+/// 
+/// # Returns
+/// * The permission; "not_required" on platforms that do not ask
+#[tauri::command]
+pub async fn check_notification_status_command() -> Result<NotificationPermission, String> {
+    tokio::task::spawn_blocking(notification_permission)
+        .await
+        .map_err(|e| format!("Failed to check notification permission: {}", e))?
+}
 
-/// Checks notification permissions and status
+/// Asks the user to allow the app's notifications
+/// 
+/// macOS shows its prompt only while the user has not decided yet; the
+/// command waits for the answer.
 /// 
 /// # Returns
-/// * Success or error message
+/// * The permission after the prompt
 #[tauri::command]
-pub fn check_notification_status_command() -> Result<String, String> {
-    #[cfg(target_os = "macos")]
-    {
-        match check_notification_permissions() {
-            Ok(_) => Ok("Notification permissions check successful".to_string()),
-            Err(e) => Err(format!("Notification permissions check failed: {}", e)),
-        }
-    }
-    
-    #[cfg(not(target_os = "macos"))]
-    {
-        Ok("Notification permissions check not required on this platform".to_string())
-    }
+pub async fn request_notification_permission_command() -> Result<NotificationPermission, String> {
+    tokio::task::spawn_blocking(request_notification_permission)
+        .await
+        .map_err(|e| format!("Failed to request notification permission: {}", e))?
 }
 
 /// Opens a webcam stream in the default browser
@@ -38,31 +50,14 @@ pub fn check_notification_status_command() -> Result<String, String> {
 /// * Success or error message
 #[tauri::command]
 pub fn open_webcam_command(host: String) -> Result<(), String> {
-    let webcam_url = format!("http://{}/webcam/?action=stream", host);
-    
-    // Use system browser to open URL
-    #[cfg(target_os = "macos")]
-    {
-        use std::process::Command;
-        Command::new("open").arg(&webcam_url).spawn()
-            .map_err(|e| e.to_string())?;
-    }
-    #[cfg(target_os = "windows")]
-    {
-        use std::process::Command;
-        Command::new("cmd").args(&["/C", "start", &webcam_url]).spawn()
-            .map_err(|e| e.to_string())?;
-    }
-    #[cfg(target_os = "linux")]
-    {
-        use std::process::Command;
-        Command::new("xdg-open").arg(&webcam_url).spawn()
-            .map_err(|e| e.to_string())?;
-    }
-    Ok(())
+    let webcam_url = build_stream_url(&host);
+    open_in_browser(&webcam_url)
 }
 
-/// Opens the host in the default browser
+/// Opens the Mainsail/Fluidd web interface of the host in the default browser
+/// 
+/// The web interface port found during the scan is used; hosts that are not
+/// in the host list are probed first.
 /// 
 /// # Arguments
 /// * `host` - Host IP address
@@ -70,48 +65,43 @@ pub fn open_webcam_command(host: String) -> Result<(), String> {
 /// # Returns
 /// * Success or error message
 #[tauri::command]
-pub fn open_host_in_browser_command(host: String) -> Result<(), String> {
-    // Try multiple URL formats
-    let urls = vec![
-        format!("http://{}", host),
-        format!("http://{}:7125", host), // Moonraker default port
-        format!("http://{}:8080", host), // Alternative port
-    ];
-    
-    // Use system browser to open URL
-    #[cfg(target_os = "macos")]
-    {
-        use std::process::Command;
-        for url in urls {
-            match Command::new("open").arg(&url).spawn() {
-                Ok(_) => return Ok(()),
-                Err(_) => continue,
-            }
-        }
-        Err("Failed to open any URL in browser".to_string())
-    }
-    #[cfg(target_os = "windows")]
-    {
-        use std::process::Command;
-        for url in urls {
-            match Command::new("cmd").args(&["/C", "start", &url]).spawn() {
-                Ok(_) => return Ok(()),
-                Err(_) => continue,
-            }
-        }
-        Err("Failed to open any URL in browser".to_string())
-    }
-    #[cfg(target_os = "linux")]
-    {
-        use std::process::Command;
-        for url in urls {
-            match Command::new("xdg-open").arg(&url).spawn() {
-                Ok(_) => return Ok(()),
-                Err(_) => continue,
-            }
-        }
-        Err("Failed to open any URL in browser".to_string())
+pub async fn open_host_in_browser_command(
+    host: String,
+    telegram: State<'_, TelegramBotState>,
+) -> Result<(), String> {
+    let known_port = telegram
+        .hosts
+        .lock()
+        .await
+        .iter()
+        .find(|h| h.ip_address == host)
+        .map(|h| h.web_port);
+    let web_port = match known_port {
+        Some(port) => port,
+        None => detect_web_port(&host).await,
+    };
+    let url = build_web_ui_url(&host, web_port);
+    open_in_browser(&url)
+}
+
+/// Opens an http(s) URL in the default browser
+/// 
+/// The URL is built from a host name the user entered, so it is checked to
+/// be a plain web address and handed to the system opener directly rather
+/// than through a shell.
+/// 
+/// # Arguments
+/// * `url` - URL to open
+/// 
+/// # Returns
+/// * Success or error message
+fn open_in_browser(url: &str) -> Result<(), String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid URL {}: {}", url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(format!("Refusing to open {}: not an http(s) URL", url));
     }
+    tauri_plugin_opener::open_url(parsed.as_str(), None::<&str>)
+        .map_err(|e| format!("Failed to open {} in browser: {}", url, e))
 }
 
 /// Opens an SSH connection to the host
@@ -124,6 +114,8 @@ pub fn open_host_in_browser_command(host: String) -> Result<(), String> {
 /// * Success or error message
 #[tauri::command]
 pub fn open_ssh_connection_command(host: String, user: String) -> Result<(), String> {
+    // Additional Klipper instances share the machine's SSH server
+    let host = split_host_port(&host).0.to_string();
     #[cfg(target_os = "macos")]
     {
         use std::process::Command;
@@ -187,12 +179,27 @@ pub fn open_ssh_connection_command(host: String, user: String) -> Result<(), Str
 /// # Arguments
 /// * `title` - Notification title
 /// * `body` - Notification body text
+/// * `host` - Host IP address of a notification about a host, which replaces the host's previous one
+/// * `alert` - Alert the notification is about; nothing is sent while the host's alert is snoozed or acknowledged
 /// 
 /// # Returns
 /// * Success or error message
 #[tauri::command]
-pub fn send_system_notification_command(title: String, body: String) -> Result<(), String> {
-    send_notification(&title, &body);
+pub fn send_system_notification_command(
+    title: String,
+    body: String,
+    host: Option<String>,
+    alert: Option<AlertKind>,
+) -> Result<(), String> {
+    if let (Some(host), Some(kind)) = (&host, alert) {
+        if is_alert_silenced_now(host, kind) {
+            return Ok(());
+        }
+    }
+    match host {
+        Some(host) => send_host_notification(&host, &title, &body),
+        None => send_notification(&title, &body),
+    }
     Ok(())
 }
 
@@ -202,26 +209,80 @@ pub fn send_system_notification_command(title: String, body: String) -> Result<(
 /// * `url` - URL to open
 #[tauri::command]
 pub fn open_url_in_browser_command(url: String) -> Result<(), String> {
-    #[cfg(target_os = "macos")] {
-        let _ = std::process::Command::new("open")
-            .arg(&url)
-            .output()
-            .map_err(|e| format!("Failed to open URL: {}", e))?;
-    }
-    
-    #[cfg(target_os = "windows")] {
-        let _ = std::process::Command::new("cmd")
-            .args(&["/C", "start", &url])
-            .output()
-            .map_err(|e| format!("Failed to open URL: {}", e))?;
-    }
-    
-    #[cfg(target_os = "linux")] {
-        let _ = std::process::Command::new("xdg-open")
-            .arg(&url)
-            .output()
-            .map_err(|e| format!("Failed to open URL: {}", e))?;
-    }
-    
+    open_in_browser(&url)
+}
+
+/// Gets the language used for notifications and the Telegram bot
+/// 
+/// # Returns
+/// * Language code ("en", "ru" or "de")
+#[tauri::command]
+pub fn get_language_command() -> Result<String, String> {
+    Ok(language().code().to_string())
+}
+
+/// Sets the language used for notifications and the Telegram bot
+/// 
+/// # Arguments
+/// * `language` - Language code; unsupported codes fall back to English
+#[tauri::command]
+pub fn set_language_command(language: String) -> Result<(), String> {
+    let language = Language::from_code(&language);
+    AppSettings::update(|settings| {
+        settings.language = language.code().to_string();
+    })?;
+    set_language(language);
     Ok(())
 }
+
+/// Checks whether times are shown with a 24-hour clock
+#[tauri::command]
+pub fn get_use_24_hour_clock_command() -> Result<bool, String> {
+    Ok(use_24_hour_clock())
+}
+
+/// Sets whether times are shown with a 24-hour or a 12-hour clock
+/// 
+/// # Arguments
+/// * `use_24_hour` - true for "14:30", false for "2:30 PM"
+#[tauri::command]
+pub fn set_use_24_hour_clock_command(use_24_hour: bool) -> Result<(), String> {
+    AppSettings::update(|settings| {
+        settings.use_24_hour_clock = use_24_hour;
+    })?;
+    set_use_24_hour_clock(use_24_hour);
+    Ok(())
+}
+
+/// Gets the proxy and CA bundle settings of outbound HTTP requests
+#[tauri::command]
+pub fn get_outbound_http_settings_command() -> Result<OutboundHttpSettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.outbound_http)
+}
+
+/// Saves the proxy and CA bundle settings of outbound HTTP requests
+/// 
+/// The CA bundle and proxy URL are checked before saving. A running
+/// Telegram bot uses the new settings after it is restarted.
+/// 
+/// # Arguments
+/// * `outbound_http` - New settings
+#[tauri::command]
+pub fn update_outbound_http_settings_command(outbound_http: OutboundHttpSettings) -> Result<(), String> {
+    validate_outbound_http_settings(&outbound_http)?;
+    AppSettings::update(|settings| {
+        settings.outbound_http = outbound_http.clone();
+    })?;
+    set_outbound_http_settings(outbound_http)
+}
+
+/// Previews the User-Agent a template produces
+/// 
+/// # Arguments
+/// * `template` - User-Agent template (None or empty: the default one)
+#[tauri::command]
+pub fn preview_user_agent_command(template: Option<String>) -> Result<String, String> {
+    let template = template.filter(|t| !t.trim().is_empty());
+    Ok(render_user_agent(template.as_deref().map(str::trim).unwrap_or(DEFAULT_USER_AGENT)))
+}
@@ -1,9 +1,11 @@
-use tauri::State;
+use tauri::{AppHandle, State};
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use crate::telegram::TelegramBot;
-use crate::models::TelegramUser;
-use crate::models::config::AppSettings;
+use crate::telegram::{TelegramBot, TelegramError, TelegramResult};
+use crate::models::{PendingRegistration, RegistrationCode, RegistrationLink, TelegramAuditEntry, TelegramAuditLog, TelegramRole, TelegramUser};
+use crate::models::config::{AlertKind, AppSettings};
+use crate::notifications::snooze::is_alert_silenced_now;
+use crate::network::ip_utils::validate_allowlist_entry;
 
 pub struct TelegramBotState {
     pub bot: Arc<Mutex<Option<TelegramBot>>>,
@@ -23,8 +25,9 @@ impl TelegramBotState {
 
 #[tauri::command]
 pub async fn start_telegram_bot(
+    app_handle: AppHandle,
     state: State<'_, TelegramBotState>,
-) -> Result<String, String> {
+) -> TelegramResult<String> {
     let mut bot_guard = state.bot.lock().await;
     
     // Stop existing bot if running
@@ -36,10 +39,11 @@ pub async fn start_telegram_bot(
     
     let token_guard = state.bot_token.lock().await;
     let bot_token = token_guard.as_ref()
-        .ok_or("Bot token not set. Please set the token first.")?;
+        .ok_or_else(|| TelegramError::Config("Bot token not set. Please set the token first.".to_string()))?;
     
     // Create and start new bot
-    let bot = TelegramBot::new(bot_token.clone(), state.hosts.clone()).await?;
+    let bot = TelegramBot::new(bot_token.clone(), state.hosts.clone()).await?
+        .with_app_handle(app_handle);
     bot.start().await?;
     
     *bot_guard = Some(bot);
@@ -50,7 +54,7 @@ pub async fn start_telegram_bot(
 #[tauri::command]
 pub async fn stop_telegram_bot(
     state: State<'_, TelegramBotState>,
-) -> Result<String, String> {
+) -> TelegramResult<String> {
     let mut bot_guard = state.bot.lock().await;
     
     if let Some(ref bot) = *bot_guard {
@@ -58,14 +62,14 @@ pub async fn stop_telegram_bot(
         *bot_guard = None;
         Ok("Telegram bot stopped successfully".to_string())
     } else {
-        Err("No bot is currently running".to_string())
+        Err(TelegramError::NotRunning)
     }
 }
 
 #[tauri::command]
 pub async fn get_telegram_bot_status(
     state: State<'_, TelegramBotState>,
-) -> Result<bool, String> {
+) -> TelegramResult<bool> {
     let bot_guard = state.bot.lock().await;
     
     if let Some(ref bot) = *bot_guard {
@@ -78,36 +82,134 @@ pub async fn get_telegram_bot_status(
 #[tauri::command]
 pub async fn start_telegram_registration(
     state: State<'_, TelegramBotState>,
-) -> Result<String, String> {
+) -> TelegramResult<String> {
     let bot_guard = state.bot.lock().await;
     
     if let Some(ref bot) = *bot_guard {
         bot.start_registration().await
     } else {
-        Err("Bot is not running".to_string())
+        Err(TelegramError::NotRunning)
     }
 }
 
 #[tauri::command]
 pub async fn stop_telegram_registration(
     state: State<'_, TelegramBotState>,
-) -> Result<String, String> {
+) -> TelegramResult<String> {
     let bot_guard = state.bot.lock().await;
     
     if let Some(ref bot) = *bot_guard {
         bot.stop_registration().await?;
         Ok("Registration stopped successfully".to_string())
     } else {
-        Err("Bot is not running".to_string())
+        Err(TelegramError::NotRunning)
     }
 }
 
+/// Gets the `t.me` deep link and QR code of the active registration
+#[tauri::command]
+pub async fn get_telegram_registration_link(
+    code: Option<String>,
+    state: State<'_, TelegramBotState>,
+) -> TelegramResult<RegistrationLink> {
+    let bot_guard = state.bot.lock().await;
+    let bot = bot_guard.as_ref().ok_or(TelegramError::NotRunning)?;
+    bot.get_registration_link(code.as_deref()).await
+}
+
+/// Creates an additional registration code bound to a role
+///
+/// # Arguments
+/// * `role` - Role suggested when users of the code are approved
+/// * `expires_minutes` - Minutes until the code expires
+/// * `label` - Description, e.g. "Workshop visitors"
+/// * `single_use` - Whether the code stops working after one registration (default true)
+#[tauri::command]
+pub async fn create_telegram_registration_code(
+    role: TelegramRole,
+    expires_minutes: u32,
+    label: Option<String>,
+    single_use: Option<bool>,
+    state: State<'_, TelegramBotState>,
+) -> TelegramResult<RegistrationCode> {
+    if expires_minutes == 0 {
+        return Err(TelegramError::Config("Expiry must be at least one minute".to_string()));
+    }
+    let bot_guard = state.bot.lock().await;
+    let bot = bot_guard.as_ref().ok_or(TelegramError::NotRunning)?;
+    let label = label.filter(|l| !l.trim().is_empty());
+    Ok(bot
+        .create_registration_code(role, chrono::Duration::minutes(expires_minutes as i64), label, single_use.unwrap_or(true))
+        .await)
+}
+
+/// Gets the registration codes that can still be used
+#[tauri::command]
+pub async fn get_telegram_registration_codes(
+    state: State<'_, TelegramBotState>,
+) -> TelegramResult<Vec<RegistrationCode>> {
+    let bot_guard = state.bot.lock().await;
+    let bot = bot_guard.as_ref().ok_or(TelegramError::NotRunning)?;
+    Ok(bot.get_registration_codes().await)
+}
+
+/// Revokes one registration code
+#[tauri::command]
+pub async fn revoke_telegram_registration_code(
+    code: String,
+    state: State<'_, TelegramBotState>,
+) -> TelegramResult<()> {
+    let bot_guard = state.bot.lock().await;
+    let bot = bot_guard.as_ref().ok_or(TelegramError::NotRunning)?;
+    bot.revoke_registration_code(&code).await
+}
+
+/// Gets the users who entered a valid registration code and wait for approval
+#[tauri::command]
+pub async fn get_telegram_pending_registrations(
+    state: State<'_, TelegramBotState>,
+) -> TelegramResult<Vec<PendingRegistration>> {
+    let bot_guard = state.bot.lock().await;
+    let bot = bot_guard.as_ref().ok_or(TelegramError::NotRunning)?;
+    Ok(bot.get_pending_registrations().await)
+}
+
+/// Approves a pending registration
+///
+/// # Arguments
+/// * `user_id` - Telegram user ID
+/// * `role` - Role of the new user (the role of the registration code when omitted)
+#[tauri::command]
+pub async fn approve_telegram_registration(
+    user_id: i64,
+    role: Option<TelegramRole>,
+    state: State<'_, TelegramBotState>,
+) -> TelegramResult<TelegramUser> {
+    let bot_guard = state.bot.lock().await;
+    let bot = bot_guard.as_ref().ok_or(TelegramError::NotRunning)?;
+    bot.approve_registration(user_id, role).await
+}
+
+/// Denies a pending registration
+///
+/// # Arguments
+/// * `user_id` - Telegram user ID
+#[tauri::command]
+pub async fn deny_telegram_registration(
+    user_id: i64,
+    state: State<'_, TelegramBotState>,
+) -> TelegramResult<()> {
+    let bot_guard = state.bot.lock().await;
+    let bot = bot_guard.as_ref().ok_or(TelegramError::NotRunning)?;
+    bot.deny_registration(user_id).await
+}
+
 #[tauri::command]
 pub async fn get_telegram_users(
     _state: State<'_, TelegramBotState>,
-) -> Result<Vec<TelegramUser>, String> {
+) -> TelegramResult<Vec<TelegramUser>> {
     // Load users from config file
-    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    let settings = AppSettings::load().map_err(|e| TelegramError::Config(format!("Failed to load settings: {}", e)))?;
     Ok(settings.telegram.registered_users)
 }
 
@@ -115,15 +217,11 @@ pub async fn get_telegram_users(
 pub async fn remove_telegram_user(
     user_id: i64,
     state: State<'_, TelegramBotState>,
-) -> Result<String, String> {
-    // Load current users from config
-    let mut settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
-    
-    // Remove user from the list
-    settings.telegram.registered_users.retain(|user| user.user_id != user_id);
-    
-    // Save updated users to config
-    settings.save().map_err(|e| format!("Failed to save settings: {}", e))?;
+) -> TelegramResult<String> {
+    // Remove user from the users in config
+    AppSettings::update(|settings| {
+        settings.telegram.registered_users.retain(|user| user.user_id != user_id);
+    }).map_err(TelegramError::Config)?;
     
     // Also remove from bot if it's running
     let bot_guard = state.bot.lock().await;
@@ -137,20 +235,20 @@ pub async fn remove_telegram_user(
 #[tauri::command]
 pub async fn is_telegram_registration_active(
     state: State<'_, TelegramBotState>,
-) -> Result<bool, String> {
+) -> TelegramResult<bool> {
     let bot_guard = state.bot.lock().await;
     
     if let Some(ref bot) = *bot_guard {
         Ok(bot.is_registration_active().await)
     } else {
-        Err("Bot is not running".to_string())
+        Err(TelegramError::NotRunning)
     }
 }
 
 #[tauri::command]
 pub async fn get_telegram_hosts(
     state: State<'_, TelegramBotState>,
-) -> Result<Vec<crate::models::HostInfo>, String> {
+) -> TelegramResult<Vec<crate::models::HostInfo>> {
     let hosts = state.hosts.lock().await;
     Ok(hosts.clone())
 }
@@ -159,26 +257,52 @@ pub async fn get_telegram_hosts(
 pub async fn update_telegram_hosts(
     hosts: Vec<crate::models::HostInfo>,
     state: State<'_, TelegramBotState>,
-) -> Result<(), String> {
+) -> TelegramResult<()> {
+    let settings = AppSettings::load().unwrap_or_default();
+    let mut hosts = hosts;
+    for host in &mut hosts {
+        host.apply_user_settings(&settings);
+    }
+
     let mut state_hosts = state.hosts.lock().await;
     *state_hosts = hosts;
+    drop(state_hosts);
+
+    if let Some(bot) = state.bot.lock().await.as_ref() {
+        bot.refresh_host_cache().await;
+    }
     Ok(())
 }
 
+/// Sends a notification to every registered user
+///
+/// An alert about a host is not sent while it is snoozed or acknowledged,
+/// and otherwise gets the buttons to snooze or acknowledge it.
+///
+/// # Arguments
+/// * `title` - Notification title
+/// * `body` - Notification body text
+/// * `host_ip` - Host IP address of a notification about a host
+/// * `alert` - Alert the notification is about
 #[tauri::command]
 pub async fn send_telegram_notification(
     title: String,
     body: String,
     host_ip: Option<String>,
+    alert: Option<AlertKind>,
     state: State<'_, TelegramBotState>,
-) -> Result<(), String> {
+) -> TelegramResult<()> {
     let bot_guard = state.bot.lock().await;
     
     if let Some(ref bot) = *bot_guard {
-        bot.send_notification_to_all_users(&title, &body, host_ip.as_deref()).await?;
+        match (host_ip.as_deref(), alert) {
+            (Some(host_ip), Some(kind)) if is_alert_silenced_now(host_ip, kind) => {}
+            (Some(host_ip), Some(kind)) => bot.send_alert_to_all_users(&title, &body, host_ip, kind).await?,
+            _ => bot.send_notification_to_all_users(&title, &body, host_ip.as_deref()).await?,
+        }
         Ok(())
     } else {
-        Err("Bot is not running".to_string())
+        Err(TelegramError::NotRunning)
     }
 }
 
@@ -187,17 +311,13 @@ pub async fn update_telegram_user_notifications(
     user_id: i64,
     notifications_enabled: bool,
     state: State<'_, TelegramBotState>,
-) -> Result<(), String> {
-    // Load current users from config
-    let mut settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
-    
-    // Update user notifications
-    if let Some(user) = settings.telegram.registered_users.iter_mut().find(|u| u.user_id == user_id) {
-        user.notifications_enabled = notifications_enabled;
-    }
-    
-    // Save updated users to config
-    settings.save().map_err(|e| format!("Failed to save settings: {}", e))?;
+) -> TelegramResult<()> {
+    // Update user notifications in config
+    AppSettings::update(|settings| {
+        if let Some(user) = settings.telegram.registered_users.iter_mut().find(|u| u.user_id == user_id) {
+            user.notifications_enabled = notifications_enabled;
+        }
+    }).map_err(TelegramError::Config)?;
     
     // Also update in bot if it's running
     let bot_guard = state.bot.lock().await;
@@ -212,14 +332,14 @@ pub async fn update_telegram_user_notifications(
 pub async fn save_telegram_bot_token(
     token: String,
     state: State<'_, TelegramBotState>,
-) -> Result<(), String> {
+) -> TelegramResult<()> {
     let mut token_guard = state.bot_token.lock().await;
     *token_guard = Some(token.clone());
     
     // Save to config file
-    let mut settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
-    settings.telegram.bot_token = Some(token);
-    settings.save().map_err(|e| format!("Failed to save settings: {}", e))?;
+    AppSettings::update(|settings| {
+        settings.telegram.bot_token = Some(token);
+    }).map_err(TelegramError::Config)?;
     
     Ok(())
 }
@@ -227,12 +347,12 @@ pub async fn save_telegram_bot_token(
 #[tauri::command]
 pub async fn get_telegram_bot_token(
     state: State<'_, TelegramBotState>,
-) -> Result<Option<String>, String> {
+) -> TelegramResult<Option<String>> {
     let token_guard = state.bot_token.lock().await;
     
     // If token is not in memory, try to load from config file
     if token_guard.is_none() {
-        let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+        let settings = AppSettings::load().map_err(|e| TelegramError::Config(format!("Failed to load settings: {}", e)))?;
         if let Some(token) = settings.telegram.bot_token {
             drop(token_guard); // Release the lock
             let mut token_guard = state.bot_token.lock().await;
@@ -247,14 +367,14 @@ pub async fn get_telegram_bot_token(
 #[tauri::command]
 pub async fn clear_telegram_bot_token(
     state: State<'_, TelegramBotState>,
-) -> Result<(), String> {
+) -> TelegramResult<()> {
     let mut token_guard = state.bot_token.lock().await;
     *token_guard = None;
     
     // Remove from config file
-    let mut settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
-    settings.telegram.bot_token = None;
-    settings.save().map_err(|e| format!("Failed to save settings: {}", e))?;
+    AppSettings::update(|settings| {
+        settings.telegram.bot_token = None;
+    }).map_err(TelegramError::Config)?;
     
     Ok(())
 }
@@ -262,9 +382,9 @@ pub async fn clear_telegram_bot_token(
 #[tauri::command]
 pub async fn load_telegram_settings(
     state: State<'_, TelegramBotState>,
-) -> Result<(), String> {
+) -> TelegramResult<()> {
     // Load settings from config file and populate memory
-    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    let settings = AppSettings::load().map_err(|e| TelegramError::Config(format!("Failed to load settings: {}", e)))?;
     
     if let Some(token) = settings.telegram.bot_token {
         let mut token_guard = state.bot_token.lock().await;
@@ -285,36 +405,31 @@ pub async fn load_telegram_settings(
 #[tauri::command]
 pub async fn get_telegram_registration_info(
     state: State<'_, TelegramBotState>,
-) -> Result<Option<String>, String> {
+) -> TelegramResult<Option<String>> {
     let bot_guard = state.bot.lock().await;
     
     if let Some(ref bot) = *bot_guard {
         let reg_state = bot.get_registration_state().await;
-        if reg_state.is_active && !reg_state.is_expired() {
-            let remaining_attempts = reg_state.max_attempts - reg_state.attempts;
-            let expires_in = if let Some(expires_at) = reg_state.expires_at {
-                let now = chrono::Utc::now();
-                if expires_at > now {
-                    let duration = expires_at - now;
-                    format!("{} minutes", duration.num_minutes())
-                } else {
-                    "expired".to_string()
-                }
-            } else {
-                "unknown".to_string()
-            };
+        if reg_state.is_active() {
+            let locked_out = reg_state.locked_out_count();
+            let now = chrono::Utc::now();
+            let codes = reg_state.codes
+                .iter()
+                .filter(|c| !c.is_expired())
+                .map(|c| format!("{} ({:?}, expires in {} minutes)", c.code, c.role, (c.expires_at - now).num_minutes()))
+                .collect::<Vec<_>>()
+                .join(", ");
             
             Ok(Some(format!(
-                "Registration active. Code: {}. Attempts remaining: {}. Expires in: {}",
-                reg_state.code.as_deref().unwrap_or("unknown"),
-                remaining_attempts,
-                expires_in
+                "Registration active. Codes: {}. Users locked out after wrong codes: {}",
+                codes,
+                locked_out
             )))
         } else {
             Ok(None)
         }
     } else {
-        Err("Bot is not running".to_string())
+        Err(TelegramError::NotRunning)
     }
 }
 
@@ -322,10 +437,52 @@ pub async fn get_telegram_registration_info(
 pub async fn save_telegram_users(
     users: Vec<TelegramUser>,
     _state: State<'_, TelegramBotState>,
-) -> Result<(), String> {
+) -> TelegramResult<()> {
     // Save to config file
-    let mut settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
-    settings.telegram.registered_users = users;
-    settings.save().map_err(|e| format!("Failed to save settings: {}", e))?;
+    AppSettings::update(|settings| {
+        settings.telegram.registered_users = users;
+    }).map_err(TelegramError::Config)?;
+    Ok(())
+}
+
+/// Gets the control actions executed through the bot, newest first
+///
+/// # Arguments
+/// * `limit` - Maximum number of entries (all when omitted)
+#[tauri::command]
+pub async fn get_telegram_audit_log(limit: Option<usize>) -> TelegramResult<Vec<TelegramAuditEntry>> {
+    let log = TelegramAuditLog::load()
+        .map_err(|e| TelegramError::Config(format!("Failed to load audit log: {}", e)))?;
+    Ok(log.entries.into_iter().rev().take(limit.unwrap_or(usize::MAX)).collect())
+}
+
+/// Gets the networks the bot may reach besides private and local addresses
+#[tauri::command]
+pub async fn get_telegram_allowed_networks() -> TelegramResult<Vec<String>> {
+    let settings = AppSettings::load().map_err(|e| TelegramError::Config(format!("Failed to load settings: {}", e)))?;
+    Ok(settings.telegram.allowed_networks)
+}
+
+/// Sets the networks the bot may reach besides private and local addresses
+///
+/// # Arguments
+/// * `networks` - Networks in CIDR notation, IP addresses or hostnames
+#[tauri::command]
+pub async fn set_telegram_allowed_networks(networks: Vec<String>) -> TelegramResult<()> {
+    for network in &networks {
+        validate_allowlist_entry(network).map_err(|e| TelegramError::Config(e.to_string()))?;
+    }
+    AppSettings::update(|settings| {
+        settings.telegram.allowed_networks = networks.iter().map(|n| n.trim().to_string()).collect();
+    }).map_err(TelegramError::Config)?;
+    Ok(())
+}
+
+/// Enables or disables the notice to other users when someone triggers an emergency stop
+#[tauri::command]
+pub async fn set_telegram_notify_emergency_stop(enabled: bool) -> TelegramResult<()> {
+    AppSettings::update(|settings| {
+        settings.telegram.notify_emergency_stop = enabled;
+    }).map_err(TelegramError::Config)?;
     Ok(())
 }
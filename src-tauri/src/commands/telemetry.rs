@@ -0,0 +1,78 @@
+//! Telemetry Tauri commands
+//! 
+//! This module contains Tauri commands for configuring the telemetry logger,
+//! reading the recorded samples and charting temperature history.
+
+use chrono::{DateTime, Utc};
+use crate::models::config::{AppSettings, TelemetrySettings};
+use crate::models::telemetry::{TelemetrySample, TemperatureSeries};
+use crate::telemetry::{query_samples, temperature_series, MAX_QUERY_SAMPLES};
+
+/// Gets the telemetry logger settings
+/// 
+/// # Returns
+/// * Current TelemetrySettings
+#[tauri::command]
+pub fn get_telemetry_settings_command() -> Result<TelemetrySettings, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    Ok(settings.telemetry)
+}
+
+/// Saves the telemetry logger settings
+/// 
+/// # Arguments
+/// * `telemetry` - New telemetry settings
+#[tauri::command]
+pub fn update_telemetry_settings_command(telemetry: TelemetrySettings) -> Result<(), String> {
+    if telemetry.sample_interval_seconds == 0 {
+        return Err("The sample interval must be at least one second".to_string());
+    }
+    AppSettings::update(|settings| {
+        settings.telemetry = telemetry;
+    })
+}
+
+/// Reads recorded telemetry samples, oldest first
+/// 
+/// # Arguments
+/// * `host` - Only samples of this host (None: all hosts)
+/// * `from` - Earliest sample time (None: no lower bound)
+/// * `to` - Latest sample time (None: now)
+/// * `limit` - Maximum number of samples, the newest are kept (10000 when omitted)
+#[tauri::command]
+pub fn query_telemetry_command(
+    host: Option<String>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    limit: Option<u32>,
+) -> Result<Vec<TelemetrySample>, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    query_samples(
+        &settings.telemetry.database_path(),
+        host.as_deref(),
+        from,
+        to,
+        limit.unwrap_or(MAX_QUERY_SAMPLES),
+    )
+}
+
+/// Gets the temperature history of a host, downsampled for a chart
+/// 
+/// # Arguments
+/// * `host` - Host IP address
+/// * `window` - Seconds of history up to now, at most the retention period
+/// * `resolution` - Number of points the window is split into (at most 2000)
+#[tauri::command]
+pub fn get_temperature_series_command(host: String, window: u64, resolution: u32) -> Result<TemperatureSeries, String> {
+    if window == 0 || resolution == 0 {
+        return Err("Window and resolution must be greater than 0".to_string());
+    }
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    // Nothing older than the retention period is stored
+    let window = i64::try_from(window)
+        .ok()
+        .and_then(chrono::Duration::try_seconds)
+        .unwrap_or(chrono::Duration::MAX);
+    let window = settings.telemetry.retention().map_or(window, |retention| window.min(retention));
+    temperature_series(&settings.telemetry.database_path(), &host, window, resolution)
+}
@@ -0,0 +1,271 @@
+//! Pushing config backups to a GitHub repository
+//!
+//! Users who already keep their printer configs in git get every backup
+//! committed to a repository, one directory per host. The commit is built
+//! with the Git Data API (tree, commit, branch update), so files removed
+//! from a printer are removed from its directory as well and unchanged
+//! backups do not create empty commits.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+use reqwest::{Client, Method};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+use crate::api::client::{configure_http_client, HttpTarget};
+use crate::config_backup::{read_backup_file, ConfigBackup};
+use crate::models::config::GitHubBackupSettings;
+
+const GITHUB_API_BASE: &str = "https://api.github.com";
+
+/// Changed files listed in the commit subject before it is shortened
+const MAX_SUBJECT_FILES: usize = 3;
+
+/// Held for a whole push. Every push moves the branch from the head it read
+/// first, so pushes of several hosts at once would fail as non-fast-forward.
+static PUSH_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Result of pushing a backup
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitHubPush {
+    /// Created commit, None when the repository already had these files
+    pub commit_sha: Option<String>,
+    /// Web URL of the commit
+    pub commit_url: Option<String>,
+    /// Host directory in the repository
+    pub directory: String,
+    /// Added or modified files
+    pub changed: Vec<String>,
+    /// Files removed because they are no longer on the printer
+    pub removed: Vec<String>,
+    /// Binary files, which are not pushed
+    pub skipped: Vec<String>,
+}
+
+/// Commits a backup to the configured repository and branch
+///
+/// # Arguments
+/// * `backup` - Local backup to push
+/// * `settings` - Repository, branch and token
+pub async fn push_backup_to_github(backup: &ConfigBackup, settings: &GitHubBackupSettings) -> Result<GitHubPush, String> {
+    let token = settings
+        .token
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .ok_or("No GitHub token configured")?;
+    let repository = settings.repository.trim();
+    if repository.split('/').filter(|part| !part.is_empty()).count() != 2 {
+        return Err(format!("Invalid repository \"{}\", expected owner/name", repository));
+    }
+    let branch = settings.branch.trim();
+    let github = GitHubApi::new(token, repository)?;
+    let _push = PUSH_LOCK.lock().await;
+
+    let head = github.request(Method::GET, &format!("git/ref/heads/{}", branch), None).await?;
+    let parent = string_at(&head, "/object/sha")?;
+    let parent_commit = github.request(Method::GET, &format!("git/commits/{}", parent), None).await?;
+    let base_tree = string_at(&parent_commit, "/tree/sha")?;
+    let directory = host_directory(&settings.path_prefix, &backup.hostname, &backup.host);
+    let before = github.blobs_under(&base_tree, &directory).await?;
+
+    // Text files are sent inline; GitHub creates the blobs
+    let mut entries = Vec::new();
+    let mut pushed = Vec::new();
+    let mut skipped = Vec::new();
+    for path in &backup.files {
+        match String::from_utf8(read_backup_file(Path::new(&backup.directory), path)?) {
+            Ok(content) => {
+                let full_path = format!("{}/{}", directory, path);
+                entries.push(json!({ "path": full_path, "mode": "100644", "type": "blob", "content": content }));
+                pushed.push(full_path);
+            }
+            Err(_) => skipped.push(path.clone()),
+        }
+    }
+    let removed: Vec<String> = before.keys().filter(|path| !pushed.contains(path)).cloned().collect();
+    for path in &removed {
+        entries.push(json!({ "path": path, "mode": "100644", "type": "blob", "sha": null }));
+    }
+
+    let tree = github
+        .request(Method::POST, "git/trees", Some(json!({ "base_tree": base_tree, "tree": entries })))
+        .await?;
+    let tree_sha = string_at(&tree, "/sha")?;
+    let strip = |path: &String| path.strip_prefix(&format!("{}/", directory)).unwrap_or(path).to_string();
+    let removed: Vec<String> = removed.iter().map(strip).collect();
+    let mut push = GitHubPush {
+        commit_sha: None,
+        commit_url: None,
+        directory: directory.clone(),
+        changed: Vec::new(),
+        removed,
+        skipped,
+    };
+    if tree_sha == base_tree {
+        return Ok(push);
+    }
+
+    let after = github.blobs_under(&tree_sha, &directory).await?;
+    push.changed = pushed
+        .iter()
+        .filter(|path| before.get(*path) != after.get(*path))
+        .map(strip)
+        .collect();
+
+    let message = commit_message(backup, &push.changed, &push.removed);
+    let commit = github
+        .request(Method::POST, "git/commits", Some(json!({ "message": message, "tree": tree_sha, "parents": [parent] })))
+        .await?;
+    let commit_sha = string_at(&commit, "/sha")?;
+    github
+        .request(Method::PATCH, &format!("git/refs/heads/{}", branch), Some(json!({ "sha": commit_sha })))
+        .await?;
+
+    push.commit_url = commit.get("html_url").and_then(|u| u.as_str()).map(str::to_string);
+    push.commit_sha = Some(commit_sha);
+    Ok(push)
+}
+
+/// Repository directory of a host: the prefix and a path-safe host name and address
+///
+/// The address keeps printers that share a host name, e.g. several
+/// "mainsailos" installs, in directories of their own.
+fn host_directory(prefix: &str, hostname: &str, address: &str) -> String {
+    let path_safe = |value: &str| -> String {
+        value
+            .trim()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+            .collect()
+    };
+    let name = path_safe(hostname);
+    let name = if name.trim_matches(['_', '.']).is_empty() { "printer".to_string() } else { name };
+    let name = match path_safe(address) {
+        address if address.trim_matches(['_', '.']).is_empty() || address == name => name,
+        address => format!("{}-{}", name, address),
+    };
+    match prefix.trim().trim_matches('/') {
+        "" => name,
+        prefix => format!("{}/{}", prefix, name),
+    }
+}
+
+/// Commit message naming the host and the changed files
+fn commit_message(backup: &ConfigBackup, changed: &[String], removed: &[String]) -> String {
+    let files: Vec<&str> = changed.iter().chain(removed).map(String::as_str).collect();
+    let listed = if files.len() > MAX_SUBJECT_FILES {
+        format!("{} and {} more", files[..MAX_SUBJECT_FILES].join(", "), files.len() - MAX_SUBJECT_FILES)
+    } else {
+        files.join(", ")
+    };
+    let mut message = format!(
+        "Update {} config: {}\n\nBacked up from {} at {}.",
+        backup.hostname,
+        listed,
+        backup.host,
+        backup.created_at.format("%Y-%m-%d %H:%M UTC")
+    );
+    if !removed.is_empty() {
+        message.push_str(&format!("\nRemoved from the printer: {}", removed.join(", ")));
+    }
+    message
+}
+
+/// Reads a string from a GitHub response
+fn string_at(value: &Value, pointer: &str) -> Result<String, String> {
+    value
+        .pointer(pointer)
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| format!("Unexpected GitHub response, {} missing", pointer))
+}
+
+/// Minimal client of the GitHub repository API
+struct GitHubApi {
+    client: Client,
+    token: String,
+    repository: String,
+}
+
+impl GitHubApi {
+    fn new(token: &str, repository: &str) -> Result<Self, String> {
+        let client = configure_http_client(Client::builder(), HttpTarget::Internet)
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+        Ok(Self { client, token: token.to_string(), repository: repository.to_string() })
+    }
+
+    /// Sends a request to `/repos/{repository}/{endpoint}`
+    async fn request(&self, method: Method, endpoint: &str, body: Option<Value>) -> Result<Value, String> {
+        let url = format!("{}/repos/{}/{}", GITHUB_API_BASE, self.repository, endpoint);
+        let mut request = self
+            .client
+            .request(method, &url)
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28");
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request.send().await.map_err(|e| format!("GitHub request failed: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(match status.as_u16() {
+                401 => "GitHub rejected the token".to_string(),
+                404 => format!("GitHub repository or branch not found ({})", endpoint),
+                409 => "The GitHub repository is empty, create a first commit on the branch".to_string(),
+                _ => format!("GitHub API error: {} - {}", status, text),
+            });
+        }
+        response.json().await.map_err(|e| format!("Invalid GitHub response: {}", e))
+    }
+
+    /// Blob SHAs of the files under a directory of a tree, keyed by path
+    async fn blobs_under(&self, tree_sha: &str, directory: &str) -> Result<HashMap<String, String>, String> {
+        let tree = self.request(Method::GET, &format!("git/trees/{}?recursive=1", tree_sha), None).await?;
+        let prefix = format!("{}/", directory);
+        Ok(tree
+            .get("tree")
+            .and_then(|t| t.as_array())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter(|e| e.get("type").and_then(|t| t.as_str()) == Some("blob"))
+                    .filter_map(|e| Some((e.get("path")?.as_str()?.to_string(), e.get("sha")?.as_str()?.to_string())))
+                    .filter(|(path, _)| path.starts_with(&prefix))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commit_names_host_and_files() {
+        let backup = ConfigBackup {
+            host: "192.168.1.50".to_string(),
+            hostname: "Voron 2.4".to_string(),
+            created_at: chrono::DateTime::parse_from_rfc3339("2026-03-01T12:30:00Z").unwrap().into(),
+            directory: String::new(),
+            files: Vec::new(),
+            size: 0,
+            skipped: Vec::new(),
+        };
+        let changed = vec!["printer.cfg".to_string(), "macros.cfg".to_string(), "a.cfg".to_string(), "b.cfg".to_string()];
+
+        let message = commit_message(&backup, &changed, &[]);
+        assert!(message.starts_with("Update Voron 2.4 config: printer.cfg, macros.cfg, a.cfg and 1 more\n\n"));
+        assert!(message.contains("192.168.1.50 at 2026-03-01 12:30 UTC"));
+        assert_eq!(host_directory("/printers/", "Voron 2.4", "192.168.1.50"), "printers/Voron_2.4-192.168.1.50");
+        assert_eq!(host_directory("", "..", "192.168.1.51:7126"), "printer-192.168.1.51_7126");
+        assert_eq!(host_directory("", "192.168.1.52", "192.168.1.52"), "192.168.1.52");
+    }
+}
@@ -0,0 +1,321 @@
+//! Backups of printer configurations
+//!
+//! Print-farm printers usually run from SD cards, which fail. This module
+//! pulls every file of a host's Moonraker `config` root into a dated local
+//! folder, keeps a limited number of backups per host and uploads a backup
+//! to a printer again to restore it. Backups can also be committed to a
+//! GitHub repository (see [`github`]).
+
+pub mod github;
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use serde::{Deserialize, Serialize};
+use crate::api::calibration::download_file;
+use crate::api::config_files::list_config_directory;
+use crate::api::files::upload_file;
+use crate::error::error_to_string;
+use crate::models::config::ConfigBackupSettings;
+use crate::webcam::archive::job_folder_name;
+
+/// File with the backup details inside a backup folder
+pub const BACKUP_INFO_FILE_NAME: &str = "backup.json";
+
+/// Folder inside a backup folder that holds the config files
+const BACKUP_FILES_DIR: &str = "config";
+
+/// Files larger than this are not backed up (e.g. resonance graphs)
+const MAX_BACKUP_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Creation time of each host's newest backup by backup directory and host
+///
+/// Read from disk once per host, so the monitor does not scan the backup
+/// directory every cycle. None: the host has no backup.
+type NewestBackups = HashMap<(PathBuf, String), Option<chrono::DateTime<chrono::Utc>>>;
+
+static NEWEST_BACKUPS: OnceLock<Mutex<NewestBackups>> = OnceLock::new();
+
+fn newest_backups() -> &'static Mutex<NewestBackups> {
+    NEWEST_BACKUPS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Files of a host's `config` root
+#[derive(Debug, Clone, Default)]
+pub struct FetchedConfig {
+    /// Paths relative to the root and their content
+    pub files: Vec<(String, Vec<u8>)>,
+    /// Files that were too large or could not be downloaded
+    pub skipped: Vec<String>,
+}
+
+/// Backup of a host's config directory
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfigBackup {
+    /// Host IP address
+    pub host: String,
+    pub hostname: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Backup folder
+    pub directory: String,
+    /// Backed up files, relative to the `config` root
+    pub files: Vec<String>,
+    /// Total size in bytes
+    pub size: u64,
+    /// Files that were too large or could not be downloaded
+    #[serde(default)]
+    pub skipped: Vec<String>,
+}
+
+/// Outcome of backing up several hosts
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ConfigBackupRun {
+    /// Backups that succeeded
+    pub backups: Vec<ConfigBackup>,
+    /// Errors of the hosts that failed, prefixed with the host name
+    pub errors: Vec<String>,
+}
+
+/// Downloads every file of a host's `config` root
+///
+/// # Arguments
+/// * `host` - Host IP address
+pub async fn fetch_config_files(host: &str) -> Result<FetchedConfig, String> {
+    let listing = list_config_directory(host).await.map_err(error_to_string)?;
+    let mut fetched = FetchedConfig::default();
+    for file in listing {
+        if file.size > MAX_BACKUP_FILE_BYTES || !is_safe_relative_path(&file.path) {
+            fetched.skipped.push(file.path);
+            continue;
+        }
+        match download_file(host, "config", &file.path).await {
+            Ok(content) => fetched.files.push((file.path, content)),
+            Err(e) => {
+                eprintln!("Failed to back up {} of {}: {}", file.path, host, e);
+                fetched.skipped.push(file.path);
+            }
+        }
+    }
+    Ok(fetched)
+}
+
+/// Backs up a host's config directory into a new dated folder
+///
+/// Older backups of the host beyond `keep_per_host` are deleted afterwards.
+///
+/// # Arguments
+/// * `host` - Host IP address
+/// * `hostname` - Display name of the host
+/// * `settings` - Storage and retention
+pub async fn backup_host_configs(host: &str, hostname: &str, settings: &ConfigBackupSettings) -> Result<ConfigBackup, String> {
+    let fetched = fetch_config_files(host).await?;
+    if fetched.files.is_empty() {
+        return Err(format!("No config files found on {}", host));
+    }
+
+    let base_dir = settings.backup_dir();
+    let directory = base_dir.join(job_folder_name(host, "config"));
+    let mut backup = ConfigBackup {
+        host: host.to_string(),
+        hostname: hostname.to_string(),
+        created_at: chrono::Utc::now(),
+        directory: directory.to_string_lossy().to_string(),
+        files: Vec::new(),
+        size: 0,
+        skipped: fetched.skipped,
+    };
+    if let Err(e) = write_backup(&directory, fetched.files, &mut backup) {
+        // A half written folder would be listed and restored like a complete backup
+        if let Err(cleanup) = fs::remove_dir_all(&directory) {
+            eprintln!("Failed to delete incomplete config backup {}: {}", directory.display(), cleanup);
+        }
+        return Err(e);
+    }
+
+    if settings.keep_per_host > 0 {
+        prune_backups(&base_dir, host, settings.keep_per_host as usize);
+    }
+    newest_backups().lock().unwrap().insert((base_dir, host.to_string()), Some(backup.created_at));
+    Ok(backup)
+}
+
+/// Writes the fetched files and the backup details into a backup folder
+fn write_backup(directory: &Path, files: Vec<(String, Vec<u8>)>, backup: &mut ConfigBackup) -> Result<(), String> {
+    let files_dir = directory.join(BACKUP_FILES_DIR);
+    for (path, content) in files {
+        let target = files_dir.join(&path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        fs::write(&target, &content).map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        backup.size += content.len() as u64;
+        backup.files.push(path);
+    }
+
+    let content = serde_json::to_string_pretty(backup).map_err(|e| e.to_string())?;
+    fs::write(directory.join(BACKUP_INFO_FILE_NAME), content)
+        .map_err(|e| format!("Failed to save backup details: {}", e))
+}
+
+/// Checks whether a host's newest backup is older than the backup interval
+///
+/// The backup directory is only read the first time a host is checked;
+/// backups made or deleted afterwards update the remembered time.
+///
+/// # Arguments
+/// * `settings` - Storage and interval
+/// * `host` - Host IP address
+pub async fn is_backup_due(settings: &ConfigBackupSettings, host: &str) -> bool {
+    let interval = chrono::Duration::hours(settings.interval_hours.max(1) as i64);
+    let key = (settings.backup_dir(), host.to_string());
+    let known = newest_backups().lock().unwrap().get(&key).copied();
+    let newest = match known {
+        Some(newest) => newest,
+        None => {
+            let (base_dir, host) = key.clone();
+            let newest = tokio::task::spawn_blocking(move || {
+                list_config_backups(&base_dir, Some(&host)).first().map(|backup| backup.created_at)
+            })
+            .await
+            .unwrap_or(None);
+            newest_backups().lock().unwrap().insert(key, newest);
+            newest
+        }
+    };
+    newest.is_none_or(|newest| chrono::Utc::now() - newest >= interval)
+}
+
+/// Lists saved backups, newest first
+///
+/// # Arguments
+/// * `base_dir` - Backup directory
+/// * `host` - Only backups of this host (None: all hosts)
+pub fn list_config_backups(base_dir: &Path, host: Option<&str>) -> Vec<ConfigBackup> {
+    let Ok(entries) = fs::read_dir(base_dir) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<ConfigBackup> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| fs::read_to_string(entry.path().join(BACKUP_INFO_FILE_NAME)).ok())
+        .filter_map(|content| serde_json::from_str::<ConfigBackup>(&content).ok())
+        .filter(|backup| host.is_none_or(|host| backup.host == host))
+        .collect();
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.created_at));
+    backups
+}
+
+/// Uploads the files of a backup to a printer
+///
+/// The target may be another printer than the one backed up, e.g. a
+/// replacement Pi. Klipper only reads the restored files after a restart.
+///
+/// # Arguments
+/// * `base_dir` - Backup directory
+/// * `directory` - Backup folder, as listed by `list_config_backups`
+/// * `host` - Host IP address to restore to
+/// * `files` - Files to restore (None: every file of the backup)
+///
+/// # Returns
+/// * Restored files
+pub async fn restore_config_backup(
+    base_dir: &Path,
+    directory: &str,
+    host: &str,
+    files: Option<Vec<String>>,
+) -> Result<Vec<String>, String> {
+    let folder = backup_folder(base_dir, directory)?;
+    let backup = load_config_backup(base_dir, directory)?;
+    let selected: Vec<String> = match files {
+        Some(files) => {
+            if let Some(unknown) = files.iter().find(|f| !backup.files.contains(f)) {
+                return Err(format!("{} is not part of the backup", unknown));
+            }
+            files
+        }
+        None => backup.files.clone(),
+    };
+
+    for path in &selected {
+        let content = read_backup_file(&folder, path)?;
+        upload_file(host, "config", path, content).await.map_err(error_to_string)?;
+    }
+    Ok(selected)
+}
+
+/// Reads the details of a backup
+///
+/// # Arguments
+/// * `base_dir` - Backup directory
+/// * `directory` - Backup folder, as listed by `list_config_backups`
+pub fn load_config_backup(base_dir: &Path, directory: &str) -> Result<ConfigBackup, String> {
+    let directory = backup_folder(base_dir, directory)?;
+    let content = fs::read_to_string(directory.join(BACKUP_INFO_FILE_NAME))
+        .map_err(|e| format!("Failed to read backup details: {}", e))?;
+    let mut backup: ConfigBackup = serde_json::from_str(&content).map_err(|e| format!("Invalid backup details: {}", e))?;
+    // The folder saved in the details is not trusted, the checked one is used
+    backup.directory = directory.to_string_lossy().to_string();
+    Ok(backup)
+}
+
+/// Reads one backed up file
+///
+/// # Arguments
+/// * `directory` - Backup folder, checked to be inside the backup directory
+/// * `path` - Path relative to the `config` root
+pub fn read_backup_file(directory: &Path, path: &str) -> Result<Vec<u8>, String> {
+    if !is_safe_relative_path(path) {
+        return Err(format!("{} is outside the backup", path));
+    }
+    let source = directory.join(BACKUP_FILES_DIR).join(path);
+    fs::read(&source).map_err(|e| format!("Failed to read {}: {}", source.display(), e))
+}
+
+/// Deletes a backup folder
+///
+/// Only folders inside the backup directory that contain backup details
+/// are deleted.
+pub fn delete_config_backup(base_dir: &Path, directory: &str) -> Result<(), String> {
+    let directory = backup_folder(base_dir, directory)?;
+    fs::remove_dir_all(&directory).map_err(|e| format!("Failed to delete {}: {}", directory.display(), e))?;
+    // The newest backup may be gone; read the directory again on the next check
+    newest_backups().lock().unwrap().retain(|(dir, _), _| dir != base_dir);
+    Ok(())
+}
+
+/// Checks that a folder is a backup inside the backup directory
+fn backup_folder(base_dir: &Path, directory: &str) -> Result<PathBuf, String> {
+    let directory = PathBuf::from(directory);
+    if directory.parent() != Some(base_dir) || !directory.join(BACKUP_INFO_FILE_NAME).is_file() {
+        return Err(format!("{} is not a config backup", directory.display()));
+    }
+    Ok(directory)
+}
+
+/// Deletes the oldest backups of a host beyond the given count
+fn prune_backups(base_dir: &Path, host: &str, keep: usize) {
+    for backup in list_config_backups(base_dir, Some(host)).into_iter().skip(keep) {
+        if let Err(e) = delete_config_backup(base_dir, &backup.directory) {
+            eprintln!("Failed to delete old config backup: {}", e);
+        }
+    }
+}
+
+/// Checks that a path from the printer stays inside the backup folder
+fn is_safe_relative_path(path: &str) -> bool {
+    !path.is_empty() && !path.starts_with('/') && !path.split(['/', '\\']).any(|segment| segment == ".." || segment.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_paths_inside_the_config_root_are_backed_up() {
+        assert!(is_safe_relative_path("printer.cfg"));
+        assert!(is_safe_relative_path("macros/purge.cfg"));
+        assert!(!is_safe_relative_path("../moonraker.conf"));
+        assert!(!is_safe_relative_path("/etc/passwd"));
+        assert!(!is_safe_relative_path("macros//purge.cfg"));
+    }
+}
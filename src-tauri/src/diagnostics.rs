@@ -0,0 +1,75 @@
+//! Moonraker diagnostics
+//!
+//! Collects what `server/info` reports as broken on each host, i.e.
+//! components that failed to load, Klipper sections Moonraker needs and
+//! server warnings, and suggests a fix for each.
+
+use futures::StreamExt;
+use crate::api::moonraker::check_moonraker_api;
+use crate::i18n::{t, tf};
+use crate::models::config::STATUS_REFRESH_CONCURRENCY;
+use crate::models::{DiagnosticIssue, HostDiagnostics, HostInfo, HostWarningKind, ServerInfoResult};
+
+/// Suggests how to fix a problem Moonraker reports
+///
+/// # Arguments
+/// * `kind` - Kind of the problem
+/// * `name` - Component, Klipper section or warning text
+pub fn remediation(kind: HostWarningKind, name: &str) -> String {
+    match kind {
+        HostWarningKind::FailedComponent => match name {
+            "update_manager" => t("diagnostics.component.update_manager").to_string(),
+            "timelapse" => t("diagnostics.component.timelapse").to_string(),
+            "power" => t("diagnostics.component.power").to_string(),
+            _ => tf("diagnostics.component.generic", &[("component", &name)]),
+        },
+        HostWarningKind::MissingKlippyRequirement => tf("diagnostics.requirement", &[("section", &name)]),
+        HostWarningKind::Moonraker => t("diagnostics.warning").to_string(),
+        HostWarningKind::Announcement => t("diagnostics.announcement").to_string(),
+    }
+}
+
+/// Lists the problems in a `server/info` result
+pub fn diagnostic_issues(server_info: &ServerInfoResult) -> Vec<DiagnosticIssue> {
+    let issue = |kind: HostWarningKind, name: &String| DiagnosticIssue {
+        kind,
+        name: name.clone(),
+        remediation: remediation(kind, name),
+    };
+    let missing = server_info.missing_klippy_requirements.iter().flatten();
+
+    server_info
+        .failed_components
+        .iter()
+        .map(|name| issue(HostWarningKind::FailedComponent, name))
+        .chain(missing.map(|name| issue(HostWarningKind::MissingKlippyRequirement, name)))
+        .chain(server_info.warnings.iter().map(|name| issue(HostWarningKind::Moonraker, name)))
+        .collect()
+}
+
+/// Gets the diagnostics of the given hosts
+///
+/// # Arguments
+/// * `hosts` - Hosts to check
+///
+/// # Returns
+/// * Diagnostics per host, in host order
+pub async fn get_diagnostics(hosts: &[HostInfo]) -> Vec<HostDiagnostics> {
+    futures::stream::iter(hosts)
+        .map(diagnose_host)
+        .buffered(STATUS_REFRESH_CONCURRENCY)
+        .collect()
+        .await
+}
+
+async fn diagnose_host(host: &HostInfo) -> HostDiagnostics {
+    let server_info = check_moonraker_api(&host.ip_address).await.ok();
+    HostDiagnostics {
+        host: host.ip_address.clone(),
+        hostname: host.hostname.clone(),
+        reachable: server_info.is_some(),
+        moonraker_version: server_info.as_ref().map(|info| info.result.moonraker_version.clone()),
+        klippy_state: server_info.as_ref().map(|info| info.result.klippy_state),
+        issues: server_info.map(|info| diagnostic_issues(&info.result)).unwrap_or_default(),
+    }
+}
@@ -25,9 +25,17 @@ pub enum MoonrakerError {
     #[error("Request timeout after {0:?}")]
     Timeout(std::time::Duration),
     
-    /// API-related errors (HTTP errors, parsing failures, etc.)
+    /// API-related errors (parsing failures, unexpected responses, etc.)
     #[error("API error: {0}")]
     Api(String),
+
+    /// Websocket connection failures
+    #[error("Websocket error: {0}")]
+    WebSocket(String),
+
+    /// Unsuccessful HTTP response
+    #[error("API error: HTTP {status}: {message}")]
+    Http { status: reqwest::StatusCode, message: String },
     
     /// Host not found or not responding
     #[error("Host not found or not responding: {0}")]
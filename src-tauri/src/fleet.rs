@@ -0,0 +1,139 @@
+//! Printer farm overview
+//!
+//! Builds the dashboard aggregate in one pass: every host's status, running
+//! job and job history are fetched concurrently instead of the frontend
+//! querying the printers one by one. The availability forecast uses the
+//! running jobs' estimates to tell when each printer frees up.
+
+use futures::StreamExt;
+use crate::api::history::get_job_totals;
+use crate::api::print_info::get_print_info;
+use crate::i18n::format_finish_time;
+use crate::models::config::STATUS_REFRESH_CONCURRENCY;
+use crate::models::{
+    AvailabilityForecast, FleetError, FleetJob, FleetOverview, HostAvailability, HostInfo, JobTotals, PrinterState,
+};
+use crate::network::status_cache::get_host_status;
+
+/// Everything fetched from one host for the overview
+struct HostSnapshot {
+    state: PrinterState,
+    job: Option<FleetJob>,
+    error: Option<FleetError>,
+    totals: Option<JobTotals>,
+}
+
+/// Builds the overview of the given hosts
+///
+/// # Arguments
+/// * `hosts` - Known hosts
+///
+/// # Returns
+/// * Status counts, running jobs, errors and combined history totals
+pub async fn get_fleet_overview(hosts: &[HostInfo]) -> FleetOverview {
+    let snapshots: Vec<HostSnapshot> = futures::stream::iter(hosts)
+        .map(snapshot_host)
+        .buffer_unordered(STATUS_REFRESH_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut overview = FleetOverview {
+        total_hosts: hosts.len(),
+        ..FleetOverview::default()
+    };
+
+    for snapshot in snapshots {
+        *overview.status_counts.entry(snapshot.state).or_insert(0) += 1;
+        overview.jobs.extend(snapshot.job);
+        overview.errors.extend(snapshot.error);
+        if let Some(totals) = snapshot.totals {
+            overview.totals.add(&totals);
+            overview.history_hosts += 1;
+        }
+    }
+
+    overview.jobs.sort_by(|a, b| a.hostname.cmp(&b.hostname));
+    overview.errors.sort_by(|a, b| a.hostname.cmp(&b.hostname));
+    overview
+}
+
+async fn snapshot_host(host: &HostInfo) -> HostSnapshot {
+    let status = get_host_status(&host.ip_address).await.status;
+    let state = status.state();
+
+    if status.status == "offline" {
+        return HostSnapshot { state, job: None, error: None, totals: None };
+    }
+
+    let job = if state.has_active_job() {
+        match get_print_info(&host.ip_address, None).await {
+            Ok(Some(info)) => {
+                let remaining_seconds = info.progress.remaining_seconds();
+                Some(FleetJob {
+                    host: host.ip_address.clone(),
+                    hostname: host.hostname.clone(),
+                    filename: info.filename,
+                    status: info.status,
+                    progress: info.progress.progress,
+                    print_duration: info.progress.print_duration,
+                    remaining_seconds,
+                    estimated_completion: info.estimated_completion,
+                    finish_time: remaining_seconds.map(format_finish_time),
+                })
+            }
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    let error = (state == PrinterState::Error).then(|| FleetError {
+        host: host.ip_address.clone(),
+        hostname: host.hostname.clone(),
+        klippy_state: status.klippy_state,
+        message: status.error_message.clone(),
+    });
+
+    // The history component is optional, hosts without it are left out of the totals
+    let totals = get_job_totals(&host.ip_address).await.ok();
+
+    HostSnapshot { state, job, error, totals }
+}
+
+/// Forecasts when each of the given hosts is free for the next job
+///
+/// # Arguments
+/// * `hosts` - Known hosts
+///
+/// # Returns
+/// * Free printers and the expected end of the running jobs, soonest first
+pub async fn get_availability_forecast(hosts: &[HostInfo]) -> AvailabilityForecast {
+    let hosts: Vec<HostAvailability> = futures::stream::iter(hosts)
+        .map(host_availability)
+        .buffer_unordered(STATUS_REFRESH_CONCURRENCY)
+        .collect()
+        .await;
+    AvailabilityForecast::new(hosts)
+}
+
+async fn host_availability(host: &HostInfo) -> HostAvailability {
+    let state = get_host_status(&host.ip_address).await.status.state();
+    let mut availability = HostAvailability {
+        host: host.ip_address.clone(),
+        hostname: host.hostname.clone(),
+        state,
+        available: state == PrinterState::Standby && !host.maintenance,
+        remaining_seconds: None,
+        available_at: None,
+        finish_time: None,
+    };
+
+    if state.has_active_job() {
+        if let Ok(Some(info)) = get_print_info(&host.ip_address, None).await {
+            availability.remaining_seconds = info.progress.remaining_seconds();
+            availability.available_at = info.estimated_completion;
+            availability.finish_time = availability.remaining_seconds.map(format_finish_time);
+        }
+    }
+    availability
+}
@@ -0,0 +1,229 @@
+//! G-code header parsing
+//!
+//! This module reads the comments PrusaSlicer, OrcaSlicer/BambuStudio and Cura
+//! write into g-code files: estimated time, filament usage, layer count and
+//! embedded thumbnails. It works on local files, so the information is
+//! available before a file is uploaded or when Moonraker has no metadata.
+//!
+//! Slicers put thumbnails and most Cura values at the start of the file,
+//! while PrusaSlicer writes its statistics and settings at the end, so only
+//! the head and the tail of large files are read.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use crate::models::{GcodeMetadata, GcodeThumbnail};
+
+/// Bytes read from the start of a file
+const HEAD_BYTES: u64 = 1024 * 1024;
+
+/// Bytes read from the end of a file
+const TAIL_BYTES: u64 = 256 * 1024;
+
+/// Reads slicer metadata from a local g-code file
+///
+/// # Arguments
+/// * `path` - Path of the g-code file
+///
+/// # Returns
+/// * Parsed metadata (fields are None when the slicer did not write them)
+pub fn parse_gcode_file(path: &Path) -> Result<GcodeMetadata, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let size = file.metadata().map_err(|e| format!("Failed to read {}: {}", path.display(), e))?.len();
+
+    if size <= HEAD_BYTES + TAIL_BYTES {
+        let mut content = Vec::with_capacity(size as usize);
+        file.read_to_end(&mut content).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        return Ok(parse_gcode_header(&String::from_utf8_lossy(&content)));
+    }
+
+    let mut head = vec![0u8; HEAD_BYTES as usize];
+    file.read_exact(&mut head).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut tail = Vec::with_capacity(TAIL_BYTES as usize);
+    file.seek(SeekFrom::End(-(TAIL_BYTES as i64)))
+        .and_then(|_| file.read_to_end(&mut tail))
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    // Drop the partial lines at the cut points
+    let head = String::from_utf8_lossy(&head);
+    let head = head.rsplit_once('\n').map(|(h, _)| h).unwrap_or(&head);
+    let tail = String::from_utf8_lossy(&tail);
+    let tail = tail.split_once('\n').map(|(_, t)| t).unwrap_or(&tail);
+
+    Ok(parse_gcode_header(&format!("{}\n{}", head, tail)))
+}
+
+/// Parses slicer comments from g-code text
+///
+/// # Arguments
+/// * `content` - G-code text (whole file or head and tail)
+pub fn parse_gcode_header(content: &str) -> GcodeMetadata {
+    let mut metadata = GcodeMetadata::default();
+    let mut thumbnail: Option<GcodeThumbnail> = None;
+
+    for line in content.lines() {
+        let comment = match line.trim().strip_prefix(';') {
+            Some(comment) => comment.trim(),
+            None => continue,
+        };
+
+        if let Some(mut current) = thumbnail.take() {
+            if comment.starts_with("thumbnail") && comment.ends_with(" end") {
+                if !current.data.is_empty() {
+                    metadata.thumbnails.push(current);
+                }
+            } else {
+                current.data.push_str(comment);
+                thumbnail = Some(current);
+            }
+            continue;
+        }
+
+        if let Some(started) = parse_thumbnail_begin(comment) {
+            thumbnail = Some(started);
+            continue;
+        }
+
+        parse_comment(comment, &mut metadata);
+    }
+
+    metadata.thumbnails.sort_by_key(|t| t.width * t.height);
+    metadata
+}
+
+/// Parses a "thumbnail begin 300x300 12345" line
+///
+/// PrusaSlicer also writes "thumbnail_JPG begin" and "thumbnail_QOI begin".
+fn parse_thumbnail_begin(comment: &str) -> Option<GcodeThumbnail> {
+    let mut parts = comment.split_whitespace();
+    let keyword = parts.next()?;
+    if parts.next()? != "begin" {
+        return None;
+    }
+    let format = match keyword {
+        "thumbnail" | "thumbnail_PNG" => "png",
+        "thumbnail_JPG" => "jpg",
+        "thumbnail_QOI" => "qoi",
+        _ => return None,
+    };
+    let (width, height) = parts.next()?.split_once('x')?;
+
+    Some(GcodeThumbnail {
+        width: width.parse().ok()?,
+        height: height.parse().ok()?,
+        format: format.to_string(),
+        data: String::new(),
+    })
+}
+
+/// Applies one "key = value" or "key: value" comment to the metadata
+fn parse_comment(comment: &str, metadata: &mut GcodeMetadata) {
+    let lower = comment.to_ascii_lowercase();
+
+    // Slicer identification lines have no separator
+    if let Some(rest) = lower.strip_prefix("generated by ") {
+        let end = rest.find(" on ").unwrap_or(rest.len());
+        metadata.slicer = Some(comment["generated by ".len().."generated by ".len() + end].trim().to_string());
+        return;
+    }
+    if lower.starts_with("generated with ") {
+        metadata.slicer = Some(comment["generated with ".len()..].trim().to_string());
+        return;
+    }
+
+    // OrcaSlicer: "model printing time: 1h 2m; total estimated time: 1h 5m"
+    if let Some(index) = lower.find("total estimated time:") {
+        if let Some(seconds) = parse_duration(&comment[index + "total estimated time:".len()..]) {
+            metadata.estimated_time = Some(seconds);
+        }
+        return;
+    }
+
+    let (key, value) = match comment.split_once('=').or_else(|| comment.split_once(':')) {
+        Some((key, value)) => (key.trim().to_lowercase(), value.trim()),
+        None => return,
+    };
+
+    match key.as_str() {
+        "estimated printing time (normal mode)" => {
+            if let Some(seconds) = parse_duration(value) {
+                metadata.estimated_time = Some(seconds);
+            }
+        }
+        // Cura writes whole seconds
+        "time" => {
+            if let Ok(seconds) = value.parse::<f64>() {
+                metadata.estimated_time = Some(seconds);
+            }
+        }
+        "filament used [mm]" | "total filament length [mm]" => {
+            if let Some(length) = parse_sum(value) {
+                metadata.filament_length = Some(length);
+            }
+        }
+        "filament used [g]" | "total filament weight [g]" | "total filament used [g]" => {
+            if let Some(weight) = parse_sum(value) {
+                metadata.filament_weight = Some(weight);
+            }
+        }
+        // Cura: "Filament used: 1.23456m, 0m"
+        "filament used" => {
+            let meters = value.split(',').filter_map(|v| v.trim().trim_end_matches('m').parse::<f64>().ok());
+            let total: f64 = meters.sum();
+            if total > 0.0 {
+                metadata.filament_length = Some(total * 1000.0);
+            }
+        }
+        "filament_type" => {
+            metadata.filament_type = value.split(';').next().map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+        }
+        "layer_count" | "total layers count" | "total layer number" => {
+            if let Ok(layers) = value.parse::<u32>() {
+                metadata.layer_count = Some(layers);
+            }
+        }
+        "layer_height" | "layer height" => {
+            if let Ok(height) = value.parse::<f64>() {
+                metadata.layer_height = Some(height);
+            }
+        }
+        "nozzle_diameter" => {
+            if let Some(diameter) = value.split(',').next().and_then(|v| v.trim().parse::<f64>().ok()) {
+                metadata.nozzle_diameter = Some(diameter);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Sums a comma separated list of numbers (one value per extruder)
+fn parse_sum(value: &str) -> Option<f64> {
+    let values: Vec<f64> = value.split(',').filter_map(|v| v.trim().parse::<f64>().ok()).collect();
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum())
+    }
+}
+
+/// Parses a slicer duration like "1d 2h 3m 4s" into seconds
+fn parse_duration(value: &str) -> Option<f64> {
+    let mut total = 0.0;
+    let mut found = false;
+
+    for token in value.split_whitespace() {
+        let (number, unit) = token.split_at(token.find(|c: char| c.is_alphabetic())?);
+        let number: f64 = number.parse().ok()?;
+        let factor = match unit {
+            "d" => 86400.0,
+            "h" => 3600.0,
+            "m" => 60.0,
+            "s" => 1.0,
+            _ => return None,
+        };
+        total += number * factor;
+        found = true;
+    }
+
+    found.then_some(total)
+}
@@ -0,0 +1,8 @@
+//! G-code file helpers
+//!
+//! This module contains local g-code file handling, such as reading slicer
+//! metadata without asking Moonraker.
+
+pub mod metadata;
+
+pub use metadata::*;
@@ -0,0 +1,161 @@
+//! Headless (daemon) mode
+//!
+//! Runs the backend without a webview: periodic network scans, background
+//! monitoring with status notifications, the Telegram bot and the optional
+//! REST API server. Intended for a Raspberry Pi or home server next to a farm.
+
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::time::{interval, Duration, MissedTickBehavior};
+
+use crate::api::client::create_client;
+use crate::background_monitor::{BackgroundMonitorState, MonitorContext};
+use crate::cli::EXIT_FAILURE;
+use crate::i18n::t;
+use crate::models::config::{AppSettings, ScanProfile};
+use crate::models::{HostInfo, SubnetConfig};
+use crate::network::scanner::scan_and_merge;
+use crate::notifications::system::host_moved_body;
+use crate::print_queue::{PrintQueueState, QUEUE_INTERVAL_SECONDS};
+use crate::server::RestApiState;
+use crate::telegram::TelegramBot;
+
+/// Runs headless mode on a new Tokio runtime until Ctrl+C
+///
+/// # Arguments
+/// * `subnets` - Subnets from the command line (override saved settings when not empty)
+///
+/// # Returns
+/// * Process exit code
+pub fn run_blocking(subnets: Vec<String>) -> i32 {
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to create Tokio runtime: {}", e);
+            return EXIT_FAILURE;
+        }
+    };
+
+    match runtime.block_on(run(subnets)) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Headless mode failed: {}", e);
+            EXIT_FAILURE
+        }
+    }
+}
+
+/// Runs headless mode until Ctrl+C
+pub async fn run(subnets: Vec<String>) -> Result<(), String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    let headless = settings.headless.clone();
+
+    let subnets: Vec<SubnetConfig> = if subnets.is_empty() {
+        headless.subnets.clone()
+    } else {
+        subnets
+            .into_iter()
+            .map(|range| SubnetConfig {
+                name: range.clone(),
+                range,
+                enabled: true,
+                profile: ScanProfile::Lan,
+            })
+            .collect()
+    };
+
+    if subnets.iter().all(|s| !s.enabled) && settings.remote_hosts.is_empty() {
+        return Err("No subnets configured. Use --subnet or add subnets to the headless settings.".to_string());
+    }
+
+    println!("Starting headless mode for {} subnet(s)", subnets.len());
+
+    let hosts: Arc<Mutex<Vec<HostInfo>>> = Arc::new(Mutex::new(Vec::new()));
+    scan_and_merge(&subnets, &hosts).await;
+
+    // Telegram bot
+    let telegram_bot = Arc::new(Mutex::new(None));
+    if headless.start_telegram {
+        if let Some(token) = settings.telegram.bot_token.clone() {
+            let bot = TelegramBot::new(token, hosts.clone()).await.map_err(|e| e.to_string())?;
+            bot.start().await.map_err(|e| e.to_string())?;
+            *telegram_bot.lock().await = Some(bot);
+            println!("Telegram bot started");
+        }
+    }
+
+    // Background monitoring
+    let monitor = BackgroundMonitorState::new();
+    let context = MonitorContext {
+        hosts: hosts.clone(),
+        health: monitor.health_registry(),
+        detector: monitor.failure_detector(),
+        maintenance_checks: monitor.maintenance_checks(),
+        printing_hosts: monitor.printing_hosts(),
+        power_off_pending: monitor.power_off_pending(),
+        cooldown_pending: monitor.cooldown_pending(),
+        sensor_alerts: monitor.sensor_alerts(),
+        config_backups: monitor.config_backups(),
+        telemetry_samples: monitor.telemetry_samples(),
+        rule_engine: monitor.rule_engine(),
+        heater_watchdog: monitor.heater_watchdog(),
+        heater_idle: monitor.heater_idle(),
+        mcu_watch: monitor.mcu_watch(),
+        archiver: monitor.archiver(),
+        offline_escalations: monitor.offline_escalations(),
+        unacknowledged_alerts: monitor.unacknowledged_alerts(),
+        network_watcher: monitor.network_watcher(),
+        known_warnings: monitor.known_warnings(),
+        job_comparator: monitor.job_comparator(),
+        telegram_bot: telegram_bot.clone(),
+        client: create_client().await.map_err(|e| e.to_string())?,
+        app_handle: None,
+        standalone: true,
+    };
+    monitor.start_with_context(context, headless.monitor_interval_seconds.max(1)).await?;
+
+    // REST API server
+    let rest_api = RestApiState::new();
+    if settings.rest_api.enabled {
+        let address = rest_api.start(&settings.rest_api, hosts.clone(), monitor.health_registry()).await?;
+        println!("REST API server listening on {}", address);
+    }
+
+    // Print queue scheduler
+    let print_queue = PrintQueueState::new();
+    print_queue.start(hosts.clone(), QUEUE_INTERVAL_SECONDS).await;
+
+    // Periodic rescans until Ctrl+C
+    let rescan_enabled = headless.rescan_interval_minutes > 0;
+    let mut rescan_timer = interval(Duration::from_secs(headless.rescan_interval_minutes.max(1) * 60));
+    rescan_timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    rescan_timer.tick().await; // First tick completes immediately
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = rescan_timer.tick(), if rescan_enabled => {
+                let relinked = scan_and_merge(&subnets, &hosts).await;
+                if let Some(bot) = telegram_bot.lock().await.as_ref() {
+                    bot.refresh_host_cache().await;
+                    for relink in &relinked {
+                        let title = t("notify.host_moved.title");
+                        if let Err(e) = bot.send_notification_to_all_users(title, &host_moved_body(relink), Some(&relink.address)).await {
+                            eprintln!("Failed to send address change to Telegram: {}", e);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    println!("Shutting down headless mode...");
+    monitor.stop();
+    print_queue.stop();
+    let _ = rest_api.stop().await;
+    if let Some(bot) = telegram_bot.lock().await.take() {
+        let _ = bot.stop().await;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,229 @@
+//! Heater power cross-check and idle timeout
+//!
+//! Klipper shuts a heater down itself when it fails to heat
+//! (`verify_heater`), but that protection can be misconfigured or turned
+//! off. The background monitor checks independently: a heater driven at
+//! full power whose temperature does not rise over a window points to a
+//! thermistor that fell out of its block or a loose heater cartridge.
+//!
+//! Klipper's `idle_timeout` turns heaters off only after motors were
+//! enabled; a preheat started from a UI and then forgotten keeps the bed
+//! and nozzle hot. Heaters with a target on a printer without a running
+//! print are reported, or turned off, after a configurable time.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tokio::time::Instant;
+use crate::models::api::HeaterReading;
+use crate::models::config::{HeaterIdleSettings, HeaterWatchSettings};
+
+/// Degrees below the target under which a heater at full power must still be heating up
+const TARGET_MARGIN: f64 = 5.0;
+
+/// Heater that stayed at full power without heating up
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeaterAlarm {
+    /// Klipper heater object, e.g. "extruder"
+    pub heater: String,
+    /// Heater power (0.0 - 1.0)
+    pub power: f64,
+    /// Temperature in °C when full power was first seen
+    pub start_temperature: f64,
+    /// Temperature in °C now
+    pub temperature: f64,
+    /// Seconds the heater was at full power
+    pub seconds: u64,
+}
+
+/// Stretch of full heater power, with the temperature it started at
+#[derive(Debug)]
+struct HeatingRun {
+    since: Instant,
+    start_temperature: f64,
+}
+
+/// Heater state carried between monitoring cycles
+#[derive(Debug, Default)]
+pub struct HeaterWatchdog {
+    /// Heaters at full power, keyed by (host, heater)
+    runs: HashMap<(String, String), HeatingRun>,
+    /// Heaters an alarm was raised for, until they heat or stop being driven
+    alarmed: HashSet<(String, String)>,
+}
+
+impl HeaterWatchdog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a heater reading and tells whether the heater just failed the check
+    ///
+    /// # Arguments
+    /// * `host` - Host IP address
+    /// * `reading` - Current heater reading
+    /// * `settings` - Power threshold, window and expected rise
+    /// * `now` - Time of the reading
+    ///
+    /// # Returns
+    /// * An alarm the first time a window passes at full power without the expected rise
+    pub fn check(&mut self, host: &str, reading: &HeaterReading, settings: &HeaterWatchSettings, now: Instant) -> Option<HeaterAlarm> {
+        let key = (host.to_string(), reading.name.clone());
+        // Chamber and other generic heaters run at full power for long
+        // stretches while the temperature barely moves
+        let heating = !reading.name.starts_with("heater_generic ")
+            && reading.power >= settings.min_power
+            && reading.target > 0.0
+            && reading.temperature < reading.target - TARGET_MARGIN;
+        if !heating {
+            self.runs.remove(&key);
+            self.alarmed.remove(&key);
+            return None;
+        }
+
+        let run = self.runs.entry(key.clone()).or_insert(HeatingRun { since: now, start_temperature: reading.temperature });
+        let elapsed = now.saturating_duration_since(run.since);
+        if elapsed < Duration::from_secs(settings.window_seconds) {
+            return None;
+        }
+        if reading.temperature - run.start_temperature >= settings.min_rise {
+            // Heating as expected: measure the next window from here
+            *run = HeatingRun { since: now, start_temperature: reading.temperature };
+            self.alarmed.remove(&key);
+            return None;
+        }
+
+        let alarm = HeaterAlarm {
+            heater: reading.name.clone(),
+            power: reading.power,
+            start_temperature: run.start_temperature,
+            temperature: reading.temperature,
+            seconds: elapsed.as_secs(),
+        };
+        self.alarmed.insert(key).then_some(alarm)
+    }
+
+    /// Forgets the heaters of a host, e.g. when it went offline
+    pub fn forget_host(&mut self, host: &str) {
+        self.runs.retain(|(h, _), _| h != host);
+        self.alarmed.retain(|(h, _)| h != host);
+    }
+
+    /// Whether a heater of a host raised an alarm and still does not heat
+    pub fn is_alarmed(&self, host: &str) -> bool {
+        self.alarmed.iter().any(|(h, _)| h == host)
+    }
+}
+
+/// Heaters that stayed on while the printer was idle
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdleHeaters {
+    /// Klipper heater objects with a target, e.g. "extruder" and "heater_bed"
+    pub heaters: Vec<String>,
+    /// Minutes the printer was idle with heaters on
+    pub minutes: u64,
+}
+
+/// Idle printers with heaters on, carried between monitoring cycles
+#[derive(Debug, Default)]
+pub struct HeaterIdleWatch {
+    /// When heaters were first seen on while idle, per host
+    since: HashMap<String, Instant>,
+    /// Hosts reported until their heaters go off or a print starts
+    reported: HashSet<String>,
+}
+
+impl HeaterIdleWatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the heaters of a host and tells whether they were on too long
+    ///
+    /// # Arguments
+    /// * `host` - Host IP address
+    /// * `idle` - Whether Klippy is ready and no print is running
+    /// * `readings` - Current heater readings
+    /// * `settings` - Allowed idle time
+    /// * `now` - Time of the reading
+    ///
+    /// # Returns
+    /// * The heaters still on, once per idle stretch, when the allowed time passed
+    pub fn check(&mut self, host: &str, idle: bool, readings: &[HeaterReading], settings: &HeaterIdleSettings, now: Instant) -> Option<IdleHeaters> {
+        let heaters: Vec<String> = readings.iter().filter(|r| r.target > 0.0).map(|r| r.name.clone()).collect();
+        if !idle || heaters.is_empty() {
+            self.forget_host(host);
+            return None;
+        }
+
+        let since = *self.since.entry(host.to_string()).or_insert(now);
+        let elapsed = now.saturating_duration_since(since);
+        if elapsed < Duration::from_secs(settings.idle_minutes * 60) || !self.reported.insert(host.to_string()) {
+            return None;
+        }
+        Some(IdleHeaters { heaters, minutes: elapsed.as_secs() / 60 })
+    }
+
+    /// Forgets a host, e.g. when it went offline or started printing
+    pub fn forget_host(&mut self, host: &str) {
+        self.since.remove(host);
+        self.reported.remove(host);
+    }
+
+    /// Whether heaters of a host were reported and are still on
+    pub fn is_reported(&self, host: &str) -> bool {
+        self.reported.contains(host)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reading(temperature: f64, power: f64) -> HeaterReading {
+        HeaterReading { name: "extruder".to_string(), temperature, target: 220.0, power }
+    }
+
+    #[test]
+    fn full_power_without_rise_raises_one_alarm() {
+        let settings = HeaterWatchSettings::default();
+        let mut watchdog = HeaterWatchdog::new();
+        let start = Instant::now();
+        let at = |seconds: u64| start + Duration::from_secs(seconds);
+
+        assert_eq!(watchdog.check("192.168.1.50", &reading(25.0, 1.0), &settings, at(0)), None);
+        assert_eq!(watchdog.check("192.168.1.50", &reading(25.5, 1.0), &settings, at(30)), None);
+        let alarm = watchdog.check("192.168.1.50", &reading(26.0, 1.0), &settings, at(61)).expect("alarm");
+        assert_eq!((alarm.start_temperature, alarm.temperature, alarm.seconds), (25.0, 26.0, 61));
+        assert_eq!(watchdog.check("192.168.1.50", &reading(26.0, 1.0), &settings, at(90)), None);
+
+        // A heater that heats normally never alarms
+        assert_eq!(watchdog.check("192.168.1.51", &reading(25.0, 1.0), &settings, at(0)), None);
+        assert_eq!(watchdog.check("192.168.1.51", &reading(80.0, 1.0), &settings, at(61)), None);
+        assert_eq!(watchdog.check("192.168.1.51", &reading(140.0, 1.0), &settings, at(122)), None);
+        assert_eq!(watchdog.check("192.168.1.51", &reading(140.0, 0.4), &settings, at(200)), None);
+
+        // Chamber heaters are not checked
+        let chamber = |temperature| HeaterReading { name: "heater_generic chamber".to_string(), temperature, target: 60.0, power: 1.0 };
+        assert_eq!(watchdog.check("192.168.1.52", &chamber(30.0), &settings, at(0)), None);
+        assert_eq!(watchdog.check("192.168.1.52", &chamber(30.5), &settings, at(600)), None);
+    }
+
+    #[test]
+    fn heaters_left_on_while_idle_are_reported_once() {
+        let settings = HeaterIdleSettings::default();
+        let mut watch = HeaterIdleWatch::new();
+        let start = Instant::now();
+        let at = |minutes: u64| start + Duration::from_secs(minutes * 60);
+        let bed = [HeaterReading { name: "heater_bed".to_string(), temperature: 60.0, target: 60.0, power: 0.3 }];
+
+        assert_eq!(watch.check("192.168.1.50", true, &bed, &settings, at(0)), None);
+        assert_eq!(watch.check("192.168.1.50", true, &bed, &settings, at(29)), None);
+        let idle = watch.check("192.168.1.50", true, &bed, &settings, at(31)).expect("idle heaters");
+        assert_eq!((idle.heaters, idle.minutes), (vec!["heater_bed".to_string()], 31));
+        assert_eq!(watch.check("192.168.1.50", true, &bed, &settings, at(40)), None);
+
+        // A print in between starts the idle time over
+        assert_eq!(watch.check("192.168.1.50", false, &bed, &settings, at(41)), None);
+        assert_eq!(watch.check("192.168.1.50", true, &bed, &settings, at(60)), None);
+    }
+}
@@ -0,0 +1,234 @@
+//! German backend strings
+
+pub(super) const STRINGS: &[(&str, &str)] = &[
+    // Printer states
+    ("status.printing", "druckt"),
+    ("status.paused", "pausiert"),
+    ("status.error", "Fehler"),
+    ("status.cancelling", "bricht ab"),
+    ("status.standby", "bereit"),
+    ("status.offline", "offline"),
+    ("status.complete", "abgeschlossen"),
+
+    // Klippy states
+    ("klippy.ready", "Klippy bereit"),
+    ("klippy.startup", "Klippy startet"),
+    ("klippy.shutdown", "Klippy heruntergefahren"),
+    ("klippy.error", "Klippy-Fehler"),
+    ("klippy.disconnected", "Klippy getrennt"),
+    ("klippy.unknown", "Klippy-Zustand unbekannt"),
+
+    // Durations
+    ("duration.dhm", "{d} T {h} h {m} min"),
+    ("duration.hms", "{h} h {m} min {s} s"),
+    ("duration.ms", "{m} min {s} s"),
+    ("duration.s", "{s} s"),
+    ("duration.hm", "{h} h {m} min"),
+    ("duration.m", "{m} min"),
+
+    // Dates and times (chrono format strings)
+    ("format.date", "%d.%m.%Y"),
+    ("format.finishes_today", "fertig um {time}"),
+    ("format.finishes_tomorrow", "fertig morgen um {time}"),
+    ("format.finishes_on", "fertig am {date} um {time}"),
+    ("format.finishes_unknown", "Ende unbekannt"),
+
+    // Notifications
+    ("notify.status_changed.title", "Druckerstatus geändert"),
+    ("notify.status_changed.body", "{host}: {from} → {to}"),
+    ("notify.action.show", "Anzeigen"),
+    ("notify.action.open_web_ui", "Weboberfläche öffnen"),
+    ("notify.deferred.title", "{count} Benachrichtigungen während „Nicht stören“"),
+    ("notify.deferred.more", "…und {count} weitere"),
+    ("notify.printer_discovered.title", "Neuer Drucker gefunden"),
+    ("notify.printer_offline.title", "Drucker offline"),
+    ("notify.printer_offline.body", "{host} ({ip}) antwortet nicht mehr"),
+    ("notify.host_moved.title", "Druckeradresse geändert"),
+    ("notify.host_moved.body", "{host} ist von {previous} nach {ip} umgezogen. Reserviere die Adresse im Router, damit sie gleich bleibt."),
+    ("notify.offline_escalation.title", "Drucker weiterhin offline"),
+    ("notify.offline_escalation.critical_title", "Drucker seit Langem offline"),
+    ("notify.offline_escalation.body", "{host} ({ip}) ist seit {duration} offline"),
+    ("notify.host_warning.title", "Druckerwarnung"),
+    ("notify.host_warning.body", "{host}:\n{warnings}"),
+    ("notify.host_warning.failed_component", "Moonraker-Komponente konnte nicht geladen werden: {component}"),
+    ("notify.host_warning.missing_requirement", "In Klipper fehlt der von Moonraker benötigte Abschnitt [{section}]"),
+    ("notify.telegram_action.title", "Aktion über Telegram"),
+    ("notify.telegram_action.emergency_stop", "{user} hat einen Notstopp an {hostname} gesendet"),
+    ("notify.telegram_action.cancel_print", "{user} hat den Druck auf {hostname} abgebrochen"),
+    ("notify.telegram_action.firmware_restart", "{user} hat die Firmware von {hostname} neu gestartet"),
+    ("notify.telegram_action.failed", "Fehlgeschlagen: {error}"),
+    ("diagnostics.component.generic", "Prüfe moonraker.log auf die Ursache und den Abschnitt [{component}] in moonraker.conf, dann Moonraker neu starten."),
+    ("diagnostics.component.update_manager", "Prüfe die [update_manager]-Einträge in moonraker.conf; ein Repository-Pfad oder Origin ist eventuell falsch."),
+    ("diagnostics.component.timelapse", "Installiere moonraker-timelapse neu oder entferne den Abschnitt [timelapse] aus moonraker.conf."),
+    ("diagnostics.component.power", "Prüfe die [power]-Geräte in moonraker.conf; ein Gerät ist eventuell nicht erreichbar oder falsch konfiguriert."),
+    ("diagnostics.requirement", "Füge printer.cfg einen Abschnitt [{section}] hinzu und starte Klipper neu."),
+    ("diagnostics.warning", "Korrigiere die gemeldete Einstellung in moonraker.conf und starte Moonraker neu."),
+    ("diagnostics.announcement", "Öffne die Ankündigung in Mainsail oder Fluidd für Details."),
+    ("notify.print_failure.title", "Druck möglicherweise fehlgeschlagen"),
+    ("notify.print_failure.frozen", "Das Webcam-Bild hat sich seit {snapshots} Aufnahmen nicht verändert ({difference}% Unterschied)"),
+    ("notify.print_failure.chaotic", "Das Webcam-Bild hat sich ungewöhnlich verändert ({difference}% Unterschied, Entropieänderung {entropy})"),
+    ("notify.print_slowdown.title", "Druck langsamer als sonst"),
+    ("notify.print_slowdown.body", "{host}: {file} braucht {percent}% länger als bei früheren Drucken ({elapsed} statt {expected} bei {progress}%, {runs} Drucke verglichen). Ursache kann eine teilweise verstopfte Düse oder ein geänderter Geschwindigkeitsfaktor sein."),
+    ("notify.print_finished.title", "Druck abgeschlossen"),
+    ("notify.fleet_idle.title", "Alle Drucker frei"),
+    ("notify.fleet_idle.body", "Der letzte laufende Druck ist beendet ({hosts}). Kein Drucker ist mehr beschäftigt."),
+    ("notify.cooldown.title", "Druck abgekühlt"),
+    ("notify.cooldown.body", "{host}: das Teil kann entnommen werden (Düse {hotend} °C, Bett {bed} °C)"),
+    ("notify.cooldown.body_hotend", "{host}: das Teil kann entnommen werden (Düse {hotend} °C)"),
+    ("notify.sensor_alert.title", "Sensorwarnung"),
+    ("notify.heater_watch.title", "Heizung heizt nicht"),
+    ("notify.heater_watch.body", "{host}: {heater} läuft seit {seconds} s mit {power}% Leistung, die Temperatur stieg aber nur von {from}°C auf {to}°C. Thermistor und Heizpatrone prüfen."),
+    ("notify.heater_idle.title", "Heizungen nicht ausgeschaltet"),
+    ("notify.heater_idle.body", "{host}: {heaters} seit {minutes} Minuten ohne Druck eingeschaltet."),
+    ("notify.heater_idle.turned_off", "{host}: {heaters} waren {minutes} Minuten ohne Druck eingeschaltet und wurden ausgeschaltet."),
+    ("notify.heater_idle.turn_off_failed", "{host}: {heaters} seit {minutes} Minuten ohne Druck eingeschaltet. Ausschalten fehlgeschlagen: {error}"),
+    ("notify.unacknowledged.title", "Meldung nicht bestätigt"),
+    ("notify.unacknowledged.body", "Niemand hat diese Meldung seit {minutes} Minuten bestätigt:\n{title}\n{body}"),
+    ("alert.kind.offline", "Offline"),
+    ("alert.kind.sensor", "Sensor"),
+    ("alert.kind.heater_idle", "Heizung im Leerlauf"),
+    ("alert.kind.heater_watch", "Heizungsfehler"),
+    ("alert.kind.mcu_link", "MCU-Verbindung"),
+    ("notify.mcu_watch.title", "MCU-Verbindung instabil"),
+    ("notify.mcu_watch.body", "{host}: {mcu} überträgt {rate} Bytes pro Minute erneut ({invalid} ungültige Bytes empfangen). USB-Kabel oder CAN-Bus prüfen, bevor Klipper die Verbindung verliert."),
+    ("notify.sensor_alert.temperature_high", "{host}: {sensor} hat {value} °C, über {limit} °C"),
+    ("notify.sensor_alert.temperature_low", "{host}: {sensor} hat {value} °C, unter {limit} °C"),
+    ("notify.sensor_alert.humidity_high", "{host}: Luftfeuchtigkeit von {sensor} ist {value} %, über {limit} %"),
+    ("notify.sensor_alert.recovered", "{host}: {sensor} ist wieder im zulässigen Bereich ({value})"),
+    ("notify.cost", "Kosten: {cost}"),
+    ("notify.maintenance_due.title", "Wartung fällig"),
+    ("notify.maintenance_due.body", "{host}: {task} ({hours} Druckstunden)"),
+    ("notify.calibration_finished.title", "Kalibrierung abgeschlossen"),
+    ("notify.calibration_finished.files", "{command} auf {host} abgeschlossen ({count} Ergebnisdateien)"),
+    ("notify.calibration_finished.body", "{command} auf {host} abgeschlossen\n{recommendations}"),
+    ("notify.calibration_failed.title", "Kalibrierung fehlgeschlagen"),
+    ("notify.calibration_failed.body", "{command} auf {host} fehlgeschlagen: {error}"),
+    ("notify.pid_calibration_finished.title", "PID-Kalibrierung abgeschlossen"),
+    ("notify.pid_calibration_finished.body", "PID_CALIBRATE {heater} auf {host} abgeschlossen: Kp={kp} Ki={ki} Kd={kd}\nSAVE_CONFIG ausführen, um die Werte zu übernehmen"),
+    ("notify.rule.title", "Regel ausgelöst"),
+    ("notify.rule.body", "{host}: {rule}"),
+    ("notify.scheduled_action.title", "Geplante Aktion"),
+    ("notify.scheduled_action.pause", "{host}: {file} bei {progress} % pausiert"),
+    ("notify.scheduled_action.cancel", "{host}: {file} bei {progress} % abgebrochen"),
+    ("notify.scheduled_action.failed", "{host}: geplante Aktion ({action}) für {file} fehlgeschlagen: {error}"),
+    ("notify.power_off.title", "Drucker ausgeschaltet"),
+    ("notify.power_off.body", "{host}: {device} nach dem Druck ausgeschaltet (Düse {temperature} °C)"),
+    ("notify.power_off.failed", "{host}: automatisches Ausschalten fehlgeschlagen: {error}"),
+    ("notify.unknown_error", "unbekannter Fehler"),
+
+    // Telegram buttons
+    ("bot.button.hosts_list", "📋 Hosts"),
+    ("bot.button.settings", "⚙️ Einstellungen"),
+    ("bot.button.help", "❓ Hilfe"),
+    ("bot.button.refresh", "🔄 Aktualisieren"),
+    ("bot.button.force_refresh", "📡 Drucker prüfen"),
+    ("bot.button.main_menu", "🏠 Hauptmenü"),
+    ("bot.button.image", "📷 Bild"),
+    ("bot.button.light", "💡 Licht"),
+    ("bot.button.part_fan", "🌀 Bauteillüfter"),
+    ("bot.button.z_offset", "↕️ Z-Offset"),
+    ("bot.button.stop_print", "⏹️ Druck stoppen"),
+    ("bot.button.firmware_restart", "🔄 Firmware-Neustart"),
+    ("bot.button.emergency_stop", "🛑 Notstopp"),
+    ("bot.button.open_browser", "🌐 Im Browser öffnen"),
+    ("bot.button.back_to_list", "🔙 Zurück zur Liste"),
+    ("bot.button.back_to_host", "🔙 Zurück zum Host"),
+    ("bot.button.cancel", "❌ Abbrechen"),
+    ("bot.button.confirm_emergency", "✅ STOPP BESTÄTIGEN"),
+    ("bot.button.confirm_stop_print", "✅ Ja, Druck stoppen"),
+    ("bot.button.confirm_firmware_restart", "✅ Ja, Firmware neu starten"),
+    ("bot.button.notifications", "{state} Benachrichtigungen"),
+
+    // Telegram messages (entries sent as MarkdownV2 keep their escapes)
+    ("bot.welcome", "🤖 *Willkommen beim MHS Bot\\!*\n\nAktion wählen:"),
+    ("bot.main_menu", "🤖 *Hauptmenü*\n\nAktion wählen:"),
+    ("bot.unknown_command", "❓ Unbekannter Befehl\\. Mit /start öffnest du das Hauptmenü\\."),
+    ("bot.unknown_action", "❌ Unbekannte Aktion"),
+    ("bot.registration.success", "✅ Registrierung erfolgreich! Willkommen, {name}! Aktion wählen:"),
+    ("bot.registration.too_many_attempts", "❌ Zu viele Fehlversuche\\. Weitere Codes von dir werden ignoriert\\."),
+    ("bot.registration.wrong_code", "❌ Falscher Code. Verbleibende Versuche: {remaining}"),
+    ("bot.registration.pending", "⏳ Code akzeptiert. Deine Registrierung wartet auf Freigabe in der Desktop-App."),
+    ("bot.registration.denied", "❌ Deine Registrierung wurde abgelehnt."),
+    ("bot.no_permission", "🔒 Deine Rolle erlaubt keine Steuerung von Druckern."),
+    ("bot.host_not_allowed", "🔒 Dieser Drucker liegt außerhalb der Netzwerke, die der Bot erreichen darf."),
+    ("bot.command.loading", "⏳ Wird geladen..."),
+    ("bot.command.usage", "ℹ️ Verwendung: /{command} <Druckername>"),
+    ("bot.command.host_not_found", "❌ Kein Drucker passt zu \"{name}\""),
+    ("bot.command.host_ambiguous", "🤔 Mehrere Drucker passen zu \"{name}\": {hosts}. Bitte genauer angeben."),
+    ("bot.command.status_title", "📊 Druckerstatus"),
+    ("bot.command.status_empty", "❌ Keine Hosts gefunden. Stellen Sie sicher, dass die App läuft und das Netzwerk gescannt hat."),
+    ("bot.command.status_free_in", "frei in ~{duration}"),
+    ("bot.command.status_free_unknown", "Ende unbekannt"),
+    ("bot.pause.done", "⏸️ Druck auf {hostname} pausiert"),
+    ("bot.pause.failed", "❌ Druck auf {hostname} konnte nicht pausiert werden: {error}"),
+    ("bot.hosts.empty", "📋 *Hosts*\n\n❌ Keine Hosts gefunden\\. Stelle sicher, dass die App läuft und das Netzwerk gescannt hat\\."),
+    ("bot.hosts.choose", "📋 *Hosts*\n\nHost zur Steuerung wählen:"),
+    ("bot.hosts.refreshing", "📡 Alle Drucker werden geprüft\\.\\.\\."),
+    ("bot.host_not_found", "❌ Host nicht gefunden"),
+    ("bot.host.details", "🖥️ {hostname}\n\n{emoji} IP: {ip}\n📊 Status: {status}{extra}\n\nAktion wählen:"),
+    ("bot.host.maintenance", "🔧 In Wartung"),
+    ("bot.print_info_unavailable", "🖨️ Druckinformationen nicht verfügbar"),
+    ("bot.unknown", "Unbekannt"),
+    ("bot.emergency.confirm", "⚠️ *NOTSTOPP*\n\n🖥️ Host: {hostname}\n📍 IP: `{ip}`\n\n🚨 **ACHTUNG:** Der Drucker wird sofort angehalten\\!\n\nMöchtest du fortfahren\\?"),
+    ("bot.emergency.sending", "🛑 Sende Notstopp an {hostname}..."),
+    ("bot.emergency.sent", "✅ Notstopp an {hostname} gesendet!"),
+    ("bot.emergency.failed", "❌ Notstopp konnte nicht gesendet werden: {error}"),
+    ("bot.emergency.notice", "🚨 {user} hat einen Notstopp auf {hostname} ({ip}) ausgelöst"),
+    ("bot.stop_print.confirm", "⚠️ Druck auf {hostname} wirklich stoppen?\n\nDas kann nicht rückgängig gemacht werden."),
+    ("bot.stop_print.sending", "⏹️ Stoppe Druck auf {hostname}..."),
+    ("bot.stop_print.done", "✅ Druck auf {hostname} gestoppt"),
+    ("bot.stop_print.failed", "❌ Druck auf {hostname} konnte nicht gestoppt werden: {error}"),
+    ("bot.firmware_restart.confirm", "⚠️ Firmware auf {hostname} wirklich neu starten?\n\nDer Drucker startet neu und ist einige Sekunden nicht erreichbar."),
+    ("bot.firmware_restart.sending", "🔄 Starte Firmware auf {hostname} neu..."),
+    ("bot.firmware_restart.waiting", "⏳ Warte auf Klipper auf {hostname}..."),
+    ("bot.firmware_restart.ready", "✅ Firmware neu gestartet, Klipper auf {hostname} ist bereit"),
+    ("bot.firmware_restart.state", "⚠️ Firmware neu gestartet, aber Klipper auf {hostname} ist im Zustand: {state}"),
+    ("bot.firmware_restart.timeout", "⚠️ Klipper auf {hostname} ist {seconds} s nach dem Neustart nicht bereit"),
+    ("bot.firmware_restart.failed", "❌ Firmware-Neustart auf {hostname} fehlgeschlagen: {error}"),
+    ("bot.toggle.light_on", "💡 Licht an"),
+    ("bot.toggle.light_off", "🌑 Licht aus"),
+    ("bot.toggle.part_fan_on", "🌀 Bauteillüfter an"),
+    ("bot.toggle.part_fan_off", "⭕ Bauteillüfter aus"),
+    ("bot.toggle.result", "{state} auf {hostname}"),
+    ("bot.toggle.failed", "❌ Fehler auf {hostname}: {error}"),
+    ("bot.button.confirm_quick_action", "✅ Ja, ausführen"),
+    ("bot.quick_action.confirm", "⚠️ „{action}“ auf {hostname} ausführen?"),
+    ("bot.quick_action.running", "⏳ „{action}“ wird auf {hostname} ausgeführt..."),
+    ("bot.quick_action.done", "✅ „{action}“ auf {hostname}:\n{output}"),
+    ("bot.quick_action.failed", "❌ „{action}“ auf {hostname} fehlgeschlagen: {error}"),
+    ("bot.quick_action.not_found", "❌ Diese Aktion existiert nicht mehr"),
+    ("preflight.printer_ready", "Drucker ist bereit"),
+    ("preflight.printer_busy", "Es läuft bereits ein Druck"),
+    ("preflight.printer_not_ready", "Klippy ist nicht bereit ({state})"),
+    ("preflight.bed_clear_confirmed", "Druckbett als frei bestätigt"),
+    ("preflight.bed_clear_required", "Bestätigen, dass das Druckbett frei ist"),
+    ("preflight.filament_detected", "{sensor}: Filament erkannt"),
+    ("preflight.filament_missing", "{sensor}: kein Filament"),
+    ("preflight.filament_unknown", "Filamentsensoren konnten nicht gelesen werden: {error}"),
+    ("preflight.chamber_ok", "Bauraum {temperature}°C ({range})"),
+    ("preflight.chamber_out_of_range", "Bauraum {temperature}°C, erlaubt {range}"),
+    ("preflight.chamber_missing", "Bauraumsensor {sensor} liefert keinen Wert"),
+    ("bot.button.confirm_bed_clear", "✅ Druckbett ist frei, starten"),
+    ("bot.button.snooze_alert", "😴 {hours} Std."),
+    ("bot.button.acknowledge_alert", "✅ Bestätigen"),
+    ("bot.reprint.no_file", "❌ {hostname} hat keinen vorherigen Druck zum Wiederholen"),
+    ("bot.reprint.checks_failed", "⚠️ Startprüfung für {file} auf {hostname}:\n{checks}"),
+    ("bot.reprint.started", "▶️ {file} auf {hostname} gestartet\n{checks}"),
+    ("bot.reprint.failed", "❌ Druck auf {hostname} konnte nicht gestartet werden: {error}"),
+    ("bot.alert.snoozed", "😴 {alert}-Meldungen von {hostname} pausiert bis {until}"),
+    ("bot.alert.acknowledged", "✅ {alert}-Meldung von {hostname} bestätigt. Sie bleibt still, bis das Problem behoben ist."),
+    ("bot.alert.failed", "❌ Meldung von {hostname} konnte nicht stummgeschaltet werden: {error}"),
+    ("bot.z_offset.value", "↕️ Z-Offset auf {hostname}: {offset} mm\n\n➕ hebt die Düse, ➖ senkt sie"),
+    ("bot.z_offset.failed", "❌ Z-Offset-Fehler auf {hostname}: {error}"),
+    ("bot.image.fetching", "📷 Lade Bild von {hostname}..."),
+    ("bot.image.caption", "📷 Bild von {hostname}"),
+    ("bot.image.received", "✅ Bild empfangen!"),
+    ("bot.image.failed", "❌ Bild konnte nicht geladen werden: {error}"),
+    ("bot.notifications.on", "🔔 Ein"),
+    ("bot.notifications.off", "🔕 Aus"),
+    ("bot.notifications.enabled", "✅ Benachrichtigungen eingeschaltet!"),
+    ("bot.notifications.disabled", "✅ Benachrichtigungen ausgeschaltet!"),
+    ("bot.settings", "⚙️ *Einstellungen*\n\n🔔 Benachrichtigungen: {state}"),
+    ("bot.user_not_found", "❌ Benutzer nicht gefunden"),
+    ("bot.help", "❓ Hilfe\n\n🤖 MHS Bot - ein Bot zur Überwachung von 3D-Druckern\n\n📋 Funktionen:\n• Hostliste\n• Überwachung des Druckerstatus\n• Kamerabilder\n• Notstopp\n• Weboberfläche öffnen\n\n⚙️ Einstellungen:\n• Benachrichtigungen verwalten\n\n🔧 Support:\nWende dich an deinen Administrator"),
+];
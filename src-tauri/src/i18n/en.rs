@@ -0,0 +1,234 @@
+//! English backend strings (reference language)
+
+pub(super) const STRINGS: &[(&str, &str)] = &[
+    // Printer states
+    ("status.printing", "printing"),
+    ("status.paused", "paused"),
+    ("status.error", "error"),
+    ("status.cancelling", "cancelling"),
+    ("status.standby", "standby"),
+    ("status.offline", "offline"),
+    ("status.complete", "complete"),
+
+    // Klippy states
+    ("klippy.ready", "Klippy ready"),
+    ("klippy.startup", "Klippy starting"),
+    ("klippy.shutdown", "Klippy shutdown"),
+    ("klippy.error", "Klippy error"),
+    ("klippy.disconnected", "Klippy disconnected"),
+    ("klippy.unknown", "Klippy state unknown"),
+
+    // Durations
+    ("duration.dhm", "{d}d {h}h {m}m"),
+    ("duration.hms", "{h}h {m}m {s}s"),
+    ("duration.ms", "{m}m {s}s"),
+    ("duration.s", "{s}s"),
+    ("duration.hm", "{h}h {m}m"),
+    ("duration.m", "{m}m"),
+
+    // Dates and times (chrono format strings)
+    ("format.date", "%Y-%m-%d"),
+    ("format.finishes_today", "finishes at {time}"),
+    ("format.finishes_tomorrow", "finishes tomorrow at {time}"),
+    ("format.finishes_on", "finishes on {date} at {time}"),
+    ("format.finishes_unknown", "finish time unknown"),
+
+    // Notifications
+    ("notify.status_changed.title", "Printer Status Changed"),
+    ("notify.status_changed.body", "{host}: {from} → {to}"),
+    ("notify.action.show", "Show"),
+    ("notify.action.open_web_ui", "Open web UI"),
+    ("notify.deferred.title", "{count} notifications during Do Not Disturb"),
+    ("notify.deferred.more", "…and {count} more"),
+    ("notify.printer_discovered.title", "New Printer Discovered"),
+    ("notify.printer_offline.title", "Printer Offline"),
+    ("notify.printer_offline.body", "{host} ({ip}) is no longer responding"),
+    ("notify.host_moved.title", "Printer Address Changed"),
+    ("notify.host_moved.body", "{host} moved from {previous} to {ip}. Reserve the address in your router to keep it fixed."),
+    ("notify.offline_escalation.title", "Printer still offline"),
+    ("notify.offline_escalation.critical_title", "Printer offline for a long time"),
+    ("notify.offline_escalation.body", "{host} ({ip}) has been offline for {duration}"),
+    ("notify.host_warning.title", "Printer warning"),
+    ("notify.host_warning.body", "{host}:\n{warnings}"),
+    ("notify.host_warning.failed_component", "Moonraker component failed to load: {component}"),
+    ("notify.host_warning.missing_requirement", "Klipper lacks the [{section}] section Moonraker needs"),
+    ("notify.telegram_action.title", "Action from Telegram"),
+    ("notify.telegram_action.emergency_stop", "{user} sent an emergency stop to {hostname}"),
+    ("notify.telegram_action.cancel_print", "{user} cancelled the print on {hostname}"),
+    ("notify.telegram_action.firmware_restart", "{user} restarted the firmware of {hostname}"),
+    ("notify.telegram_action.failed", "Failed: {error}"),
+    ("diagnostics.component.generic", "Check moonraker.log for the reason and the [{component}] section of moonraker.conf, then restart Moonraker."),
+    ("diagnostics.component.update_manager", "Check the [update_manager] entries in moonraker.conf; a repository path or origin may be wrong."),
+    ("diagnostics.component.timelapse", "Reinstall moonraker-timelapse or remove the [timelapse] section from moonraker.conf."),
+    ("diagnostics.component.power", "Check the [power] devices in moonraker.conf; a device may be unreachable or misconfigured."),
+    ("diagnostics.requirement", "Add a [{section}] section to printer.cfg and restart Klipper."),
+    ("diagnostics.warning", "Fix the reported setting in moonraker.conf and restart Moonraker."),
+    ("diagnostics.announcement", "Open the announcement in Mainsail or Fluidd for details."),
+    ("notify.print_failure.title", "Print may have failed"),
+    ("notify.print_failure.frozen", "Webcam image has not changed for {snapshots} snapshots ({difference}% difference)"),
+    ("notify.print_failure.chaotic", "Webcam image changed unusually ({difference}% difference, entropy change {entropy})"),
+    ("notify.print_slowdown.title", "Print slower than usual"),
+    ("notify.print_slowdown.body", "{host}: {file} is taking {percent}% longer than its previous runs ({elapsed} instead of {expected} at {progress}%, {runs} runs compared). A partial clog or a changed speed factor may be the cause."),
+    ("notify.print_finished.title", "Print finished"),
+    ("notify.fleet_idle.title", "All printers idle"),
+    ("notify.fleet_idle.body", "The last running print finished ({hosts}). No printer is busy now."),
+    ("notify.cooldown.title", "Print cooled down"),
+    ("notify.cooldown.body", "{host}: the part can be removed (nozzle {hotend} °C, bed {bed} °C)"),
+    ("notify.cooldown.body_hotend", "{host}: the part can be removed (nozzle {hotend} °C)"),
+    ("notify.sensor_alert.title", "Sensor alert"),
+    ("notify.heater_watch.title", "Heater not heating"),
+    ("notify.heater_watch.body", "{host}: {heater} has been at {power}% power for {seconds} s but only went from {from}°C to {to}°C. Check the thermistor and heater cartridge."),
+    ("notify.heater_idle.title", "Heaters left on"),
+    ("notify.heater_idle.body", "{host}: {heaters} still on after {minutes} minutes without a print."),
+    ("notify.heater_idle.turned_off", "{host}: {heaters} were on for {minutes} minutes without a print and have been turned off."),
+    ("notify.heater_idle.turn_off_failed", "{host}: {heaters} on for {minutes} minutes without a print. Turning them off failed: {error}"),
+    ("notify.unacknowledged.title", "Alert not acknowledged"),
+    ("notify.unacknowledged.body", "Nobody acknowledged this alert for {minutes} minutes:\n{title}\n{body}"),
+    ("alert.kind.offline", "Offline"),
+    ("alert.kind.sensor", "Sensor"),
+    ("alert.kind.heater_idle", "Idle heater"),
+    ("alert.kind.heater_watch", "Heater fault"),
+    ("alert.kind.mcu_link", "MCU link"),
+    ("notify.mcu_watch.title", "MCU connection unstable"),
+    ("notify.mcu_watch.body", "{host}: {mcu} is retransmitting {rate} bytes per minute ({invalid} invalid bytes received). Check the USB cable or CAN bus before Klipper loses communication."),
+    ("notify.sensor_alert.temperature_high", "{host}: {sensor} is at {value} °C, above {limit} °C"),
+    ("notify.sensor_alert.temperature_low", "{host}: {sensor} is at {value} °C, below {limit} °C"),
+    ("notify.sensor_alert.humidity_high", "{host}: {sensor} humidity is {value} %, above {limit} %"),
+    ("notify.sensor_alert.recovered", "{host}: {sensor} is back within its limits ({value})"),
+    ("notify.cost", "Cost: {cost}"),
+    ("notify.maintenance_due.title", "Maintenance due"),
+    ("notify.maintenance_due.body", "{host}: {task} ({hours} print hours)"),
+    ("notify.calibration_finished.title", "Calibration finished"),
+    ("notify.calibration_finished.files", "{command} finished on {host} ({count} result files)"),
+    ("notify.calibration_finished.body", "{command} finished on {host}\n{recommendations}"),
+    ("notify.calibration_failed.title", "Calibration failed"),
+    ("notify.calibration_failed.body", "{command} failed on {host}: {error}"),
+    ("notify.pid_calibration_finished.title", "PID calibration finished"),
+    ("notify.pid_calibration_finished.body", "PID_CALIBRATE {heater} finished on {host}: Kp={kp} Ki={ki} Kd={kd}\nRun SAVE_CONFIG to keep the values"),
+    ("notify.rule.title", "Rule triggered"),
+    ("notify.rule.body", "{host}: {rule}"),
+    ("notify.scheduled_action.title", "Scheduled action"),
+    ("notify.scheduled_action.pause", "{host}: paused {file} at {progress}%"),
+    ("notify.scheduled_action.cancel", "{host}: cancelled {file} at {progress}%"),
+    ("notify.scheduled_action.failed", "{host}: scheduled {action} of {file} failed: {error}"),
+    ("notify.power_off.title", "Printer powered off"),
+    ("notify.power_off.body", "{host}: switched off {device} after the print (nozzle {temperature} °C)"),
+    ("notify.power_off.failed", "{host}: automatic power off failed: {error}"),
+    ("notify.unknown_error", "unknown error"),
+
+    // Telegram buttons
+    ("bot.button.hosts_list", "📋 Hosts"),
+    ("bot.button.settings", "⚙️ Settings"),
+    ("bot.button.help", "❓ Help"),
+    ("bot.button.refresh", "🔄 Refresh"),
+    ("bot.button.force_refresh", "📡 Check printers"),
+    ("bot.button.main_menu", "🏠 Main menu"),
+    ("bot.button.image", "📷 Image"),
+    ("bot.button.light", "💡 Light"),
+    ("bot.button.part_fan", "🌀 Part fan"),
+    ("bot.button.z_offset", "↕️ Z-offset"),
+    ("bot.button.stop_print", "⏹️ Stop print"),
+    ("bot.button.firmware_restart", "🔄 Firmware restart"),
+    ("bot.button.emergency_stop", "🛑 Emergency stop"),
+    ("bot.button.open_browser", "🌐 Open in browser"),
+    ("bot.button.back_to_list", "🔙 Back to list"),
+    ("bot.button.back_to_host", "🔙 Back to host"),
+    ("bot.button.cancel", "❌ Cancel"),
+    ("bot.button.confirm_emergency", "✅ CONFIRM STOP"),
+    ("bot.button.confirm_stop_print", "✅ Yes, stop the print"),
+    ("bot.button.confirm_firmware_restart", "✅ Yes, restart firmware"),
+    ("bot.button.notifications", "{state} Notifications"),
+
+    // Telegram messages (entries sent as MarkdownV2 keep their escapes)
+    ("bot.welcome", "🤖 *Welcome to MHS Bot\\!*\n\nChoose an action:"),
+    ("bot.main_menu", "🤖 *Main menu*\n\nChoose an action:"),
+    ("bot.unknown_command", "❓ Unknown command\\. Use /start to open the main menu\\."),
+    ("bot.unknown_action", "❌ Unknown action"),
+    ("bot.registration.success", "✅ Registration successful! Welcome, {name}! Choose an action:"),
+    ("bot.registration.too_many_attempts", "❌ Too many failed attempts\\. Further codes from you are ignored\\."),
+    ("bot.registration.wrong_code", "❌ Wrong code. Attempts left: {remaining}"),
+    ("bot.registration.pending", "⏳ Code accepted. Your registration is waiting for approval in the desktop app."),
+    ("bot.registration.denied", "❌ Your registration was declined."),
+    ("bot.no_permission", "🔒 Your role does not allow controlling printers."),
+    ("bot.host_not_allowed", "🔒 This printer is outside the networks the bot may reach."),
+    ("bot.command.loading", "⏳ Loading..."),
+    ("bot.command.usage", "ℹ️ Usage: /{command} <printer name>"),
+    ("bot.command.host_not_found", "❌ No printer matches \"{name}\""),
+    ("bot.command.host_ambiguous", "🤔 Several printers match \"{name}\": {hosts}. Please be more specific."),
+    ("bot.command.status_title", "📊 Printer status"),
+    ("bot.command.status_empty", "❌ No hosts found. Make sure the app is running and has scanned the network."),
+    ("bot.command.status_free_in", "free in ~{duration}"),
+    ("bot.command.status_free_unknown", "end unknown"),
+    ("bot.pause.done", "⏸️ Print paused on {hostname}"),
+    ("bot.pause.failed", "❌ Failed to pause the print on {hostname}: {error}"),
+    ("bot.hosts.empty", "📋 *Hosts*\n\n❌ No hosts found\\. Make sure the app is running and has scanned the network\\."),
+    ("bot.hosts.choose", "📋 *Hosts*\n\nChoose a host to manage:"),
+    ("bot.hosts.refreshing", "📡 Checking all printers\\.\\.\\."),
+    ("bot.host_not_found", "❌ Host not found"),
+    ("bot.host.details", "🖥️ {hostname}\n\n{emoji} IP: {ip}\n📊 Status: {status}{extra}\n\nChoose an action:"),
+    ("bot.host.maintenance", "🔧 In maintenance"),
+    ("bot.print_info_unavailable", "🖨️ Print information unavailable"),
+    ("bot.unknown", "Unknown"),
+    ("bot.emergency.confirm", "⚠️ *EMERGENCY STOP*\n\n🖥️ Host: {hostname}\n📍 IP: `{ip}`\n\n🚨 **WARNING:** This immediately stops the printer\\!\n\nDo you want to continue\\?"),
+    ("bot.emergency.sending", "🛑 Sending emergency stop to {hostname}..."),
+    ("bot.emergency.sent", "✅ Emergency stop sent to {hostname}!"),
+    ("bot.emergency.failed", "❌ Failed to send emergency stop: {error}"),
+    ("bot.emergency.notice", "🚨 {user} triggered an emergency stop on {hostname} ({ip})"),
+    ("bot.stop_print.confirm", "⚠️ Do you really want to stop the print on {hostname}?\n\nThis cannot be undone."),
+    ("bot.stop_print.sending", "⏹️ Stopping the print on {hostname}..."),
+    ("bot.stop_print.done", "✅ Print stopped on {hostname}"),
+    ("bot.stop_print.failed", "❌ Failed to stop the print on {hostname}: {error}"),
+    ("bot.firmware_restart.confirm", "⚠️ Do you really want to restart the firmware on {hostname}?\n\nThe printer restarts and may be unavailable for a few seconds."),
+    ("bot.firmware_restart.sending", "🔄 Restarting firmware on {hostname}..."),
+    ("bot.firmware_restart.waiting", "⏳ Waiting for Klipper on {hostname}..."),
+    ("bot.firmware_restart.ready", "✅ Firmware restarted, Klipper is ready on {hostname}"),
+    ("bot.firmware_restart.state", "⚠️ Firmware restarted, but Klipper on {hostname} is in state: {state}"),
+    ("bot.firmware_restart.timeout", "⚠️ Klipper on {hostname} is not ready {seconds} s after the restart"),
+    ("bot.firmware_restart.failed", "❌ Failed to restart firmware on {hostname}: {error}"),
+    ("bot.toggle.light_on", "💡 Light on"),
+    ("bot.toggle.light_off", "🌑 Light off"),
+    ("bot.toggle.part_fan_on", "🌀 Part fan on"),
+    ("bot.toggle.part_fan_off", "⭕ Part fan off"),
+    ("bot.toggle.result", "{state} on {hostname}"),
+    ("bot.toggle.failed", "❌ Error on {hostname}: {error}"),
+    ("bot.button.confirm_quick_action", "✅ Yes, run it"),
+    ("bot.quick_action.confirm", "⚠️ Run \"{action}\" on {hostname}?"),
+    ("bot.quick_action.running", "⏳ Running \"{action}\" on {hostname}..."),
+    ("bot.quick_action.done", "✅ \"{action}\" on {hostname}:\n{output}"),
+    ("bot.quick_action.failed", "❌ \"{action}\" failed on {hostname}: {error}"),
+    ("bot.quick_action.not_found", "❌ This action no longer exists"),
+    ("preflight.printer_ready", "Printer is ready"),
+    ("preflight.printer_busy", "A print is already running"),
+    ("preflight.printer_not_ready", "Klippy is not ready ({state})"),
+    ("preflight.bed_clear_confirmed", "Bed confirmed clear"),
+    ("preflight.bed_clear_required", "Confirm that the bed is clear"),
+    ("preflight.filament_detected", "{sensor}: filament detected"),
+    ("preflight.filament_missing", "{sensor}: no filament"),
+    ("preflight.filament_unknown", "Filament sensors could not be read: {error}"),
+    ("preflight.chamber_ok", "Chamber {temperature}°C ({range})"),
+    ("preflight.chamber_out_of_range", "Chamber {temperature}°C, allowed {range}"),
+    ("preflight.chamber_missing", "Chamber sensor {sensor} has no reading"),
+    ("bot.button.confirm_bed_clear", "✅ Bed is clear, start"),
+    ("bot.button.snooze_alert", "😴 {hours} h"),
+    ("bot.button.acknowledge_alert", "✅ Acknowledge"),
+    ("bot.reprint.no_file", "❌ {hostname} has no previous print to start again"),
+    ("bot.reprint.checks_failed", "⚠️ Pre-flight check for {file} on {hostname}:\n{checks}"),
+    ("bot.reprint.started", "▶️ {file} started on {hostname}\n{checks}"),
+    ("bot.reprint.failed", "❌ Failed to start the print on {hostname}: {error}"),
+    ("bot.alert.snoozed", "😴 {alert} alerts of {hostname} snoozed until {until}"),
+    ("bot.alert.acknowledged", "✅ {alert} alert of {hostname} acknowledged. It stays quiet until it clears."),
+    ("bot.alert.failed", "❌ Failed to silence the alert of {hostname}: {error}"),
+    ("bot.z_offset.value", "↕️ Z-offset on {hostname}: {offset} mm\n\n➕ raises the nozzle, ➖ lowers it"),
+    ("bot.z_offset.failed", "❌ Z-offset error on {hostname}: {error}"),
+    ("bot.image.fetching", "📷 Fetching image from {hostname}..."),
+    ("bot.image.caption", "📷 Image from {hostname}"),
+    ("bot.image.received", "✅ Image received!"),
+    ("bot.image.failed", "❌ Failed to get image: {error}"),
+    ("bot.notifications.on", "🔔 On"),
+    ("bot.notifications.off", "🔕 Off"),
+    ("bot.notifications.enabled", "✅ Notifications enabled!"),
+    ("bot.notifications.disabled", "✅ Notifications disabled!"),
+    ("bot.settings", "⚙️ *Settings*\n\n🔔 Notifications: {state}"),
+    ("bot.user_not_found", "❌ User not found"),
+    ("bot.help", "❓ Help\n\n🤖 MHS Bot - a bot for monitoring 3D printers\n\n📋 Features:\n• Host list\n• Printer status monitoring\n• Camera images\n• Emergency stop\n• Opening the web interface\n\n⚙️ Settings:\n• Notification management\n\n🔧 Support:\nContact your administrator"),
+];
@@ -0,0 +1,169 @@
+//! Localization of backend-generated text
+//!
+//! Notifications, the Telegram bot and formatted values are produced in the
+//! backend, so they cannot use the frontend translations. This module keeps
+//! simple key tables per language, selected by `AppSettings.language`.
+//! Missing keys fall back to English, unknown keys to the key itself.
+//!
+//! Placeholders are written as `{name}` and filled by [`tf`]. Times and
+//! dates are formatted by the helpers in [`time`].
+
+mod de;
+mod en;
+mod ru;
+pub mod time;
+
+pub use time::*;
+
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+use crate::models::config::AppSettings;
+
+/// Marker for "language not loaded from settings yet"
+const LANGUAGE_UNSET: u8 = u8::MAX;
+
+/// Current language, loaded from settings on first use
+static LANGUAGE: AtomicU8 = AtomicU8::new(LANGUAGE_UNSET);
+
+/// Languages with backend translations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Language {
+    En = 0,
+    Ru = 1,
+    De = 2,
+}
+
+impl Language {
+    /// Gets the language for a settings code, English for unknown codes
+    pub fn from_code(code: &str) -> Self {
+        match code.trim().to_lowercase().as_str() {
+            "ru" => Language::Ru,
+            "de" => Language::De,
+            _ => Language::En,
+        }
+    }
+
+    /// Gets the settings code of the language
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::Ru => "ru",
+            Language::De => "de",
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Language::Ru,
+            2 => Language::De,
+            _ => Language::En,
+        }
+    }
+
+    fn strings(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::En => en::STRINGS,
+            Language::Ru => ru::STRINGS,
+            Language::De => de::STRINGS,
+        }
+    }
+}
+
+/// Gets the current backend language
+pub fn language() -> Language {
+    let value = LANGUAGE.load(Ordering::Relaxed);
+    if value != LANGUAGE_UNSET {
+        return Language::from_u8(value);
+    }
+
+    let language = AppSettings::load()
+        .map(|s| Language::from_code(&s.language))
+        .unwrap_or(Language::En);
+    set_language(language);
+    language
+}
+
+/// Replaces the current language, e.g. after settings were saved
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+/// Gets the translation of a key in the current language
+///
+/// # Arguments
+/// * `key` - Translation key, e.g. "notify.print_finished"
+///
+/// # Returns
+/// * Translated text, the English text when missing, or the key itself
+pub fn t(key: &str) -> &str {
+    translate(language(), key)
+}
+
+/// Gets the translation of a key and fills its `{name}` placeholders
+///
+/// # Arguments
+/// * `key` - Translation key
+/// * `args` - Placeholder names and values
+///
+/// # Returns
+/// * Translated text with the placeholders replaced
+pub fn tf(key: &str, args: &[(&str, &(dyn Display + Sync))]) -> String {
+    let mut text = t(key).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    text
+}
+
+/// Gets the translation of a key in the given language
+pub fn translate(language: Language, key: &str) -> &str {
+    lookup(language, key)
+        .or_else(|| lookup(Language::En, key))
+        .unwrap_or(key)
+}
+
+fn lookup(language: Language, key: &str) -> Option<&'static str> {
+    language
+        .strings()
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, text)| *text)
+}
+
+/// Gets the translated name of a printer status ("printing", "offline", ...)
+pub fn status_name(status: &str) -> &str {
+    let key = format!("status.{}", status);
+    match lookup(language(), &key).or_else(|| lookup(Language::En, &key)) {
+        Some(text) => text,
+        None => status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_language_has_the_english_keys() {
+        for language in [Language::Ru, Language::De] {
+            for (key, _) in en::STRINGS {
+                assert!(lookup(language, key).is_some(), "{} is missing {}", language.code(), key);
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_keys_fall_back_to_the_key() {
+        assert_eq!(translate(Language::Ru, "no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn placeholders_are_filled() {
+        set_language(Language::En);
+        assert_eq!(
+            tf("notify.status_changed.body", &[("host", &"voron"), ("from", &"a"), ("to", &"b")]),
+            "voron: a → b"
+        );
+    }
+}
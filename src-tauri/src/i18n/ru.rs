@@ -0,0 +1,234 @@
+//! Russian backend strings
+
+pub(super) const STRINGS: &[(&str, &str)] = &[
+    // Printer states
+    ("status.printing", "печать"),
+    ("status.paused", "пауза"),
+    ("status.error", "ошибка"),
+    ("status.cancelling", "отмена"),
+    ("status.standby", "ожидание"),
+    ("status.offline", "не в сети"),
+    ("status.complete", "завершено"),
+
+    // Klippy states
+    ("klippy.ready", "Klippy готов"),
+    ("klippy.startup", "Klippy запускается"),
+    ("klippy.shutdown", "Klippy остановлен"),
+    ("klippy.error", "Ошибка Klippy"),
+    ("klippy.disconnected", "Klippy отключен"),
+    ("klippy.unknown", "Состояние Klippy неизвестно"),
+
+    // Durations
+    ("duration.dhm", "{d} д {h} ч {m} мин"),
+    ("duration.hms", "{h} ч {m} мин {s} с"),
+    ("duration.ms", "{m} мин {s} с"),
+    ("duration.s", "{s} с"),
+    ("duration.hm", "{h} ч {m} мин"),
+    ("duration.m", "{m} мин"),
+
+    // Dates and times (chrono format strings)
+    ("format.date", "%d.%m.%Y"),
+    ("format.finishes_today", "завершится в {time}"),
+    ("format.finishes_tomorrow", "завершится завтра в {time}"),
+    ("format.finishes_on", "завершится {date} в {time}"),
+    ("format.finishes_unknown", "время завершения неизвестно"),
+
+    // Notifications
+    ("notify.status_changed.title", "Статус принтера изменился"),
+    ("notify.status_changed.body", "{host}: {from} → {to}"),
+    ("notify.action.show", "Показать"),
+    ("notify.action.open_web_ui", "Открыть веб-интерфейс"),
+    ("notify.deferred.title", "Уведомлений в режиме «Не беспокоить»: {count}"),
+    ("notify.deferred.more", "…и ещё {count}"),
+    ("notify.printer_discovered.title", "Найден новый принтер"),
+    ("notify.printer_offline.title", "Принтер не в сети"),
+    ("notify.printer_offline.body", "{host} ({ip}) больше не отвечает"),
+    ("notify.host_moved.title", "Адрес принтера изменился"),
+    ("notify.host_moved.body", "{host} переехал с {previous} на {ip}. Закрепите адрес в настройках роутера, чтобы он больше не менялся."),
+    ("notify.offline_escalation.title", "Принтер всё ещё не в сети"),
+    ("notify.offline_escalation.critical_title", "Принтер давно не в сети"),
+    ("notify.offline_escalation.body", "{host} ({ip}) не в сети уже {duration}"),
+    ("notify.host_warning.title", "Предупреждение принтера"),
+    ("notify.host_warning.body", "{host}:\n{warnings}"),
+    ("notify.host_warning.failed_component", "Не удалось загрузить компонент Moonraker: {component}"),
+    ("notify.host_warning.missing_requirement", "В Klipper нет секции [{section}], нужной Moonraker"),
+    ("notify.telegram_action.title", "Действие из Telegram"),
+    ("notify.telegram_action.emergency_stop", "{user} отправил(а) аварийную остановку на {hostname}"),
+    ("notify.telegram_action.cancel_print", "{user} отменил(а) печать на {hostname}"),
+    ("notify.telegram_action.firmware_restart", "{user} перезапустил(а) прошивку {hostname}"),
+    ("notify.telegram_action.failed", "Ошибка: {error}"),
+    ("diagnostics.component.generic", "Найдите причину в moonraker.log и проверьте секцию [{component}] в moonraker.conf, затем перезапустите Moonraker."),
+    ("diagnostics.component.update_manager", "Проверьте записи [update_manager] в moonraker.conf: возможно, неверен путь или origin репозитория."),
+    ("diagnostics.component.timelapse", "Переустановите moonraker-timelapse или удалите секцию [timelapse] из moonraker.conf."),
+    ("diagnostics.component.power", "Проверьте устройства [power] в moonraker.conf: устройство может быть недоступно или неверно настроено."),
+    ("diagnostics.requirement", "Добавьте секцию [{section}] в printer.cfg и перезапустите Klipper."),
+    ("diagnostics.warning", "Исправьте указанную настройку в moonraker.conf и перезапустите Moonraker."),
+    ("diagnostics.announcement", "Подробности — в объявлении в Mainsail или Fluidd."),
+    ("notify.print_failure.title", "Возможно, печать не удалась"),
+    ("notify.print_failure.frozen", "Изображение с камеры не меняется уже {snapshots} снимков (разница {difference}%)"),
+    ("notify.print_failure.chaotic", "Изображение с камеры резко изменилось (разница {difference}%, изменение энтропии {entropy})"),
+    ("notify.print_slowdown.title", "Печать идёт медленнее обычного"),
+    ("notify.print_slowdown.body", "{host}: {file} печатается на {percent}% дольше, чем раньше ({elapsed} вместо {expected} на {progress}%, сравнено печатей: {runs}). Возможная причина — частичный засор сопла или изменённый множитель скорости."),
+    ("notify.print_finished.title", "Печать завершена"),
+    ("notify.fleet_idle.title", "Все принтеры свободны"),
+    ("notify.fleet_idle.body", "Последняя печать завершена ({hosts}). Сейчас ни один принтер не занят."),
+    ("notify.cooldown.title", "Печать остыла"),
+    ("notify.cooldown.body", "{host}: деталь можно снимать (сопло {hotend} °C, стол {bed} °C)"),
+    ("notify.cooldown.body_hotend", "{host}: деталь можно снимать (сопло {hotend} °C)"),
+    ("notify.sensor_alert.title", "Датчик вне пределов"),
+    ("notify.heater_watch.title", "Нагреватель не греет"),
+    ("notify.heater_watch.body", "{host}: {heater} работает на {power}% мощности уже {seconds} с, но температура изменилась лишь с {from}°C до {to}°C. Проверьте термистор и нагревательный картридж."),
+    ("notify.heater_idle.title", "Нагреватели не выключены"),
+    ("notify.heater_idle.body", "{host}: {heaters} включены уже {minutes} мин без печати."),
+    ("notify.heater_idle.turned_off", "{host}: {heaters} были включены {minutes} мин без печати и выключены."),
+    ("notify.heater_idle.turn_off_failed", "{host}: {heaters} включены {minutes} мин без печати. Выключить не удалось: {error}"),
+    ("notify.unacknowledged.title", "Оповещение не подтверждено"),
+    ("notify.unacknowledged.body", "Никто не подтвердил это оповещение за {minutes} мин.:\n{title}\n{body}"),
+    ("alert.kind.offline", "Офлайн"),
+    ("alert.kind.sensor", "Датчик"),
+    ("alert.kind.heater_idle", "Нагреватель в простое"),
+    ("alert.kind.heater_watch", "Неисправность нагревателя"),
+    ("alert.kind.mcu_link", "Связь с MCU"),
+    ("notify.mcu_watch.title", "Нестабильная связь с MCU"),
+    ("notify.mcu_watch.body", "{host}: {mcu} повторно передаёт {rate} байт в минуту (получено {invalid} ошибочных байт). Проверьте USB-кабель или шину CAN, пока Klipper не потерял связь."),
+    ("notify.sensor_alert.temperature_high", "{host}: {sensor} — {value} °C, выше {limit} °C"),
+    ("notify.sensor_alert.temperature_low", "{host}: {sensor} — {value} °C, ниже {limit} °C"),
+    ("notify.sensor_alert.humidity_high", "{host}: влажность {sensor} — {value} %, выше {limit} %"),
+    ("notify.sensor_alert.recovered", "{host}: {sensor} снова в пределах нормы ({value})"),
+    ("notify.cost", "Стоимость: {cost}"),
+    ("notify.maintenance_due.title", "Требуется обслуживание"),
+    ("notify.maintenance_due.body", "{host}: {task} ({hours} ч печати)"),
+    ("notify.calibration_finished.title", "Калибровка завершена"),
+    ("notify.calibration_finished.files", "{command} завершена на {host} (файлов результатов: {count})"),
+    ("notify.calibration_finished.body", "{command} завершена на {host}\n{recommendations}"),
+    ("notify.calibration_failed.title", "Калибровка не удалась"),
+    ("notify.calibration_failed.body", "{command} не удалась на {host}: {error}"),
+    ("notify.pid_calibration_finished.title", "Калибровка PID завершена"),
+    ("notify.pid_calibration_finished.body", "PID_CALIBRATE {heater} на {host} завершена: Kp={kp} Ki={ki} Kd={kd}\nВыполните SAVE_CONFIG, чтобы сохранить значения"),
+    ("notify.rule.title", "Сработало правило"),
+    ("notify.rule.body", "{host}: {rule}"),
+    ("notify.scheduled_action.title", "Запланированное действие"),
+    ("notify.scheduled_action.pause", "{host}: печать {file} приостановлена на {progress}%"),
+    ("notify.scheduled_action.cancel", "{host}: печать {file} отменена на {progress}%"),
+    ("notify.scheduled_action.failed", "{host}: запланированное действие ({action}) для {file} не выполнено: {error}"),
+    ("notify.power_off.title", "Принтер выключен"),
+    ("notify.power_off.body", "{host}: {device} выключено после печати (сопло {temperature} °C)"),
+    ("notify.power_off.failed", "{host}: автоматическое выключение не удалось: {error}"),
+    ("notify.unknown_error", "неизвестная ошибка"),
+
+    // Telegram buttons
+    ("bot.button.hosts_list", "📋 Список хостов"),
+    ("bot.button.settings", "⚙️ Настройки"),
+    ("bot.button.help", "❓ Помощь"),
+    ("bot.button.refresh", "🔄 Обновить"),
+    ("bot.button.force_refresh", "📡 Опросить принтеры"),
+    ("bot.button.main_menu", "🏠 Главное меню"),
+    ("bot.button.image", "📷 Изображение"),
+    ("bot.button.light", "💡 Свет"),
+    ("bot.button.part_fan", "🌀 Обдув"),
+    ("bot.button.z_offset", "↕️ Z-offset"),
+    ("bot.button.stop_print", "⏹️ Остановить печать"),
+    ("bot.button.firmware_restart", "🔄 Перезагрузка firmware"),
+    ("bot.button.emergency_stop", "🛑 Экстренная остановка"),
+    ("bot.button.open_browser", "🌐 Открыть в браузере"),
+    ("bot.button.back_to_list", "🔙 Назад к списку"),
+    ("bot.button.back_to_host", "🔙 Назад к хосту"),
+    ("bot.button.cancel", "❌ Отмена"),
+    ("bot.button.confirm_emergency", "✅ ПОДТВЕРДИТЬ ОСТАНОВКУ"),
+    ("bot.button.confirm_stop_print", "✅ Да, остановить печать"),
+    ("bot.button.confirm_firmware_restart", "✅ Да, перезагрузить firmware"),
+    ("bot.button.notifications", "{state} Уведомления"),
+
+    // Telegram messages (entries sent as MarkdownV2 keep their escapes)
+    ("bot.welcome", "🤖 *Добро пожаловать в MHS Bot\\!*\n\nВыберите действие:"),
+    ("bot.main_menu", "🤖 *Главное меню*\n\nВыберите действие:"),
+    ("bot.unknown_command", "❓ Неизвестная команда\\. Используйте /start для открытия главного меню\\."),
+    ("bot.unknown_action", "❌ Неизвестное действие"),
+    ("bot.registration.success", "✅ Регистрация успешна! Добро пожаловать, {name}! Выберите действие:"),
+    ("bot.registration.too_many_attempts", "❌ Слишком много неудачных попыток\\. Дальнейшие коды от вас игнорируются\\."),
+    ("bot.registration.wrong_code", "❌ Неверный код. Осталось попыток: {remaining}"),
+    ("bot.registration.pending", "⏳ Код принят. Регистрация ожидает подтверждения в приложении."),
+    ("bot.registration.denied", "❌ Ваша регистрация отклонена."),
+    ("bot.no_permission", "🔒 Ваша роль не позволяет управлять принтерами."),
+    ("bot.host_not_allowed", "🔒 Этот принтер находится вне сетей, доступных боту."),
+    ("bot.command.loading", "⏳ Загрузка..."),
+    ("bot.command.usage", "ℹ️ Использование: /{command} <имя принтера>"),
+    ("bot.command.host_not_found", "❌ Нет принтера с именем \"{name}\""),
+    ("bot.command.host_ambiguous", "🤔 Имени \"{name}\" соответствуют несколько принтеров: {hosts}. Уточните имя."),
+    ("bot.command.status_title", "📊 Состояние принтеров"),
+    ("bot.command.status_empty", "❌ Хосты не найдены. Убедитесь, что приложение запущено и просканировало сеть."),
+    ("bot.command.status_free_in", "освободится через ~{duration}"),
+    ("bot.command.status_free_unknown", "время окончания неизвестно"),
+    ("bot.pause.done", "⏸️ Печать на {hostname} приостановлена"),
+    ("bot.pause.failed", "❌ Не удалось приостановить печать на {hostname}: {error}"),
+    ("bot.hosts.empty", "📋 *Список хостов*\n\n❌ Хосты не найдены\\. Убедитесь, что приложение запущено и выполнило сканирование\\."),
+    ("bot.hosts.choose", "📋 *Список хостов*\n\nВыберите хост для управления:"),
+    ("bot.hosts.refreshing", "📡 Опрашиваю все принтеры\\.\\.\\."),
+    ("bot.host_not_found", "❌ Хост не найден"),
+    ("bot.host.details", "🖥️ {hostname}\n\n{emoji} IP: {ip}\n📊 Статус: {status}{extra}\n\nВыберите действие:"),
+    ("bot.host.maintenance", "🔧 На обслуживании"),
+    ("bot.print_info_unavailable", "🖨️ Информация о печати недоступна"),
+    ("bot.unknown", "Неизвестно"),
+    ("bot.emergency.confirm", "⚠️ *ЭКСТРЕННАЯ ОСТАНОВКА*\n\n🖥️ Хост: {hostname}\n📍 IP: `{ip}`\n\n🚨 **ВНИМАНИЕ:** Это действие немедленно остановит принтер\\!\n\nВы уверены, что хотите продолжить\\?"),
+    ("bot.emergency.sending", "🛑 Отправка экстренной остановки на {hostname}..."),
+    ("bot.emergency.sent", "✅ Экстренная остановка успешно отправлена на {hostname}!"),
+    ("bot.emergency.failed", "❌ Ошибка отправки экстренной остановки: {error}"),
+    ("bot.emergency.notice", "🚨 {user} выполнил экстренную остановку на {hostname} ({ip})"),
+    ("bot.stop_print.confirm", "⚠️ Вы уверены, что хотите остановить печать на {hostname}?\n\nЭто действие нельзя отменить."),
+    ("bot.stop_print.sending", "⏹️ Остановка печати на {hostname}..."),
+    ("bot.stop_print.done", "✅ Печать остановлена на {hostname}"),
+    ("bot.stop_print.failed", "❌ Ошибка остановки печати на {hostname}: {error}"),
+    ("bot.firmware_restart.confirm", "⚠️ Вы уверены, что хотите перезагрузить firmware на {hostname}?\n\nПринтер будет перезагружен и может быть недоступен несколько секунд."),
+    ("bot.firmware_restart.sending", "🔄 Перезагрузка firmware на {hostname}..."),
+    ("bot.firmware_restart.waiting", "⏳ Ожидание готовности Klipper на {hostname}..."),
+    ("bot.firmware_restart.ready", "✅ Firmware перезагружен, Klipper готов на {hostname}"),
+    ("bot.firmware_restart.state", "⚠️ Firmware перезагружен, но Klipper на {hostname} в состоянии: {state}"),
+    ("bot.firmware_restart.timeout", "⚠️ Klipper на {hostname} не готов через {seconds} с после перезагрузки"),
+    ("bot.firmware_restart.failed", "❌ Ошибка перезагрузки firmware на {hostname}: {error}"),
+    ("bot.toggle.light_on", "💡 Свет включен"),
+    ("bot.toggle.light_off", "🌑 Свет выключен"),
+    ("bot.toggle.part_fan_on", "🌀 Обдув включен"),
+    ("bot.toggle.part_fan_off", "⭕ Обдув выключен"),
+    ("bot.toggle.result", "{state} на {hostname}"),
+    ("bot.toggle.failed", "❌ Ошибка на {hostname}: {error}"),
+    ("bot.button.confirm_quick_action", "✅ Да, выполнить"),
+    ("bot.quick_action.confirm", "⚠️ Выполнить «{action}» на {hostname}?"),
+    ("bot.quick_action.running", "⏳ Выполняется «{action}» на {hostname}..."),
+    ("bot.quick_action.done", "✅ «{action}» на {hostname}:\n{output}"),
+    ("bot.quick_action.failed", "❌ Ошибка «{action}» на {hostname}: {error}"),
+    ("bot.quick_action.not_found", "❌ Это действие больше не существует"),
+    ("preflight.printer_ready", "Принтер готов"),
+    ("preflight.printer_busy", "Уже идёт печать"),
+    ("preflight.printer_not_ready", "Klippy не готов ({state})"),
+    ("preflight.bed_clear_confirmed", "Стол подтверждён свободным"),
+    ("preflight.bed_clear_required", "Подтвердите, что стол свободен"),
+    ("preflight.filament_detected", "{sensor}: филамент есть"),
+    ("preflight.filament_missing", "{sensor}: нет филамента"),
+    ("preflight.filament_unknown", "Не удалось прочитать датчики филамента: {error}"),
+    ("preflight.chamber_ok", "Камера {temperature}°C ({range})"),
+    ("preflight.chamber_out_of_range", "Камера {temperature}°C, допустимо {range}"),
+    ("preflight.chamber_missing", "Нет показаний датчика камеры {sensor}"),
+    ("bot.button.confirm_bed_clear", "✅ Стол свободен, запустить"),
+    ("bot.button.snooze_alert", "😴 {hours} ч"),
+    ("bot.button.acknowledge_alert", "✅ Принято"),
+    ("bot.reprint.no_file", "❌ На {hostname} нет предыдущей печати для повтора"),
+    ("bot.reprint.checks_failed", "⚠️ Предполётная проверка {file} на {hostname}:\n{checks}"),
+    ("bot.reprint.started", "▶️ {file} запущен на {hostname}\n{checks}"),
+    ("bot.reprint.failed", "❌ Не удалось запустить печать на {hostname}: {error}"),
+    ("bot.alert.snoozed", "😴 Оповещения «{alert}» для {hostname} отложены до {until}"),
+    ("bot.alert.acknowledged", "✅ Оповещение «{alert}» для {hostname} принято. Повторов не будет, пока проблема не устранится."),
+    ("bot.alert.failed", "❌ Не удалось отключить оповещение для {hostname}: {error}"),
+    ("bot.z_offset.value", "↕️ Z-offset на {hostname}: {offset} мм\n\n➕ поднимает сопло, ➖ опускает"),
+    ("bot.z_offset.failed", "❌ Ошибка Z-offset на {hostname}: {error}"),
+    ("bot.image.fetching", "📷 Получение изображения с {hostname}..."),
+    ("bot.image.caption", "📷 Изображение с {hostname}"),
+    ("bot.image.received", "✅ Изображение получено!"),
+    ("bot.image.failed", "❌ Ошибка получения изображения: {error}"),
+    ("bot.notifications.on", "🔔 Включены"),
+    ("bot.notifications.off", "🔕 Выключены"),
+    ("bot.notifications.enabled", "✅ Уведомления включены!"),
+    ("bot.notifications.disabled", "✅ Уведомления выключены!"),
+    ("bot.settings", "⚙️ *Настройки*\n\n🔔 Уведомления: {state}"),
+    ("bot.user_not_found", "❌ Пользователь не найден"),
+    ("bot.help", "❓ Помощь\n\n🤖 MHS Bot - бот для мониторинга 3D принтеров\n\n📋 Основные функции:\n• Просмотр списка хостов\n• Мониторинг статуса принтеров\n• Получение изображений с камер\n• Экстренная остановка печати\n• Открытие веб-интерфейса\n\n⚙️ Настройки:\n• Управление уведомлениями\n\n🔧 Поддержка:\nОбратитесь к администратору"),
+];
@@ -0,0 +1,105 @@
+//! Localized times and dates
+//!
+//! Times follow the 12/24-hour preference from the settings, dates and the
+//! "finishes at" wording follow the configured language.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use chrono::{DateTime, Duration, Local, TimeZone};
+use crate::models::config::AppSettings;
+use super::{t, tf};
+
+/// Marker for "clock preference not loaded from settings yet"
+const CLOCK_UNSET: u8 = u8::MAX;
+
+/// 1 for a 24-hour clock, 0 for a 12-hour clock
+static CLOCK_24H: AtomicU8 = AtomicU8::new(CLOCK_UNSET);
+
+/// Checks whether times are shown with a 24-hour clock
+pub fn use_24_hour_clock() -> bool {
+    let value = CLOCK_24H.load(Ordering::Relaxed);
+    if value != CLOCK_UNSET {
+        return value == 1;
+    }
+
+    let enabled = AppSettings::load().map(|s| s.use_24_hour_clock).unwrap_or(true);
+    set_use_24_hour_clock(enabled);
+    enabled
+}
+
+/// Replaces the clock preference, e.g. after settings were saved
+pub fn set_use_24_hour_clock(enabled: bool) {
+    CLOCK_24H.store(enabled as u8, Ordering::Relaxed);
+}
+
+/// Formats the time of day, e.g. "14:30" or "2:30 PM"
+pub fn format_time<Tz: TimeZone>(time: &DateTime<Tz>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let pattern = if use_24_hour_clock() { "%H:%M" } else { "%-I:%M %p" };
+    time.format(pattern).to_string()
+}
+
+/// Formats a date and time, e.g. "2024-05-01 14:30" or "01.05.2024 14:30"
+pub fn format_date_time<Tz: TimeZone>(time: &DateTime<Tz>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    format!("{} {}", time.format(t("format.date")), format_time(time))
+}
+
+/// Formats a Unix timestamp in local time
+///
+/// # Arguments
+/// * `timestamp` - Seconds since the Unix epoch
+///
+/// # Returns
+/// * Local date and time, None for timestamps out of range
+pub fn format_timestamp(timestamp: f64) -> Option<String> {
+    let time = Local.timestamp_opt(timestamp.trunc() as i64, 0).single()?;
+    Some(format_date_time(&time))
+}
+
+/// Formats when a job ends, e.g. "finishes at 14:30" or "finishes tomorrow at 09:15"
+///
+/// # Arguments
+/// * `remaining_seconds` - Estimated remaining time
+///
+/// # Returns
+/// * "finish time unknown" when the end is out of the representable range
+pub fn format_finish_time(remaining_seconds: f64) -> String {
+    let now = Local::now();
+    let finish = Duration::try_seconds(remaining_seconds.max(0.0) as i64).and_then(|remaining| now.checked_add_signed(remaining));
+    let Some(finish) = finish else {
+        return t("format.finishes_unknown").to_string();
+    };
+    let time = format_time(&finish);
+
+    match (finish.date_naive() - now.date_naive()).num_days() {
+        0 => tf("format.finishes_today", &[("time", &time)]),
+        1 => tf("format.finishes_tomorrow", &[("time", &time)]),
+        _ => tf("format.finishes_on", &[("date", &finish.format(t("format.date"))), ("time", &time)]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_follows_the_clock_preference() {
+        let time = Local.with_ymd_and_hms(2024, 5, 1, 14, 5, 0).unwrap();
+        set_use_24_hour_clock(true);
+        assert_eq!(format_time(&time), "14:05");
+        set_use_24_hour_clock(false);
+        assert_eq!(format_time(&time), "2:05 PM");
+        set_use_24_hour_clock(true);
+    }
+
+    #[test]
+    fn finish_time_out_of_range_is_unknown() {
+        assert_eq!(format_finish_time(1e13), t("format.finishes_unknown"));
+        assert_eq!(format_finish_time(f64::INFINITY), t("format.finishes_unknown"));
+        assert_ne!(format_finish_time(3600.0), t("format.finishes_unknown"));
+    }
+}
@@ -0,0 +1,175 @@
+//! Comparison of running prints with their previous runs
+//!
+//! When a job starts, the completed runs of the same file are looked up in
+//! the Moonraker history. A print that needs much more time to reach the
+//! same progress than those runs hints at a slowdown, e.g. a partial clog
+//! or a reduced speed factor. Progress is the file position, so the first
+//! part of a print (slow first layers, heat soak macros) is not compared.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::models::api::HistoryJob;
+use crate::models::config::JobComparisonSettings;
+use crate::models::print_info::PrintProgress;
+
+/// Print that runs noticeably slower than its previous runs
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrintSlowdown {
+    /// Host IP address
+    pub host: String,
+    pub filename: String,
+    /// Current progress percentage
+    pub progress: f64,
+    /// Time printed so far in seconds
+    pub print_duration: f64,
+    /// Time the previous runs needed to reach the same progress, in seconds
+    pub expected_duration: f64,
+    /// How much longer this print takes, in percent
+    pub slowdown_percent: f64,
+    /// Number of completed runs compared against
+    pub previous_runs: usize,
+}
+
+/// Previous runs of the job a host is printing
+#[derive(Debug)]
+struct JobBaseline {
+    filename: String,
+    /// Median print time of the completed runs (None: the file was never completed)
+    typical_duration: Option<f64>,
+    previous_runs: usize,
+    /// Whether this job was already reported
+    warned: bool,
+}
+
+/// Per-host baselines of running jobs
+#[derive(Debug, Default)]
+pub struct JobComparator {
+    jobs: HashMap<String, JobBaseline>,
+}
+
+impl JobComparator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks whether the previous runs of a host's current job still have to be looked up
+    pub fn needs_baseline(&self, host: &str, filename: &str) -> bool {
+        self.jobs.get(host).is_none_or(|job| job.filename != filename)
+    }
+
+    /// Records the previous runs of a host's current job
+    ///
+    /// # Arguments
+    /// * `host` - Host IP address
+    /// * `filename` - File being printed
+    /// * `history` - History jobs of that file; only completed ones count
+    pub fn set_baseline(&mut self, host: &str, filename: &str, history: &[HistoryJob]) {
+        let mut durations: Vec<f64> = history
+            .iter()
+            .filter(|job| job.filename == filename && job.status == "completed" && job.print_duration > 0.0)
+            .map(|job| job.print_duration)
+            .collect();
+        durations.sort_by(|a, b| a.total_cmp(b));
+
+        self.jobs.insert(host.to_string(), JobBaseline {
+            filename: filename.to_string(),
+            typical_duration: durations.get(durations.len() / 2).copied(),
+            previous_runs: durations.len(),
+            warned: false,
+        });
+    }
+
+    /// Compares the progress of a running job with its previous runs
+    ///
+    /// # Returns
+    /// * The slowdown the first time the job falls too far behind, None otherwise
+    pub fn check(&mut self, host: &str, progress: &PrintProgress, settings: &JobComparisonSettings) -> Option<PrintSlowdown> {
+        let job = self.jobs.get_mut(host)?;
+        let typical_duration = job.typical_duration?;
+        if job.warned || progress.progress < settings.min_progress || progress.progress >= 100.0 {
+            return None;
+        }
+
+        let expected_duration = typical_duration * progress.progress / 100.0;
+        let slowdown_percent = (progress.print_duration / expected_duration - 1.0) * 100.0;
+        if slowdown_percent < settings.slowdown_percent {
+            return None;
+        }
+
+        job.warned = true;
+        Some(PrintSlowdown {
+            host: host.to_string(),
+            filename: job.filename.clone(),
+            progress: progress.progress,
+            print_duration: progress.print_duration,
+            expected_duration,
+            slowdown_percent,
+            previous_runs: job.previous_runs,
+        })
+    }
+
+    /// Forgets the job of a host, e.g. when it stopped printing
+    pub fn reset(&mut self, host: &str) {
+        self.jobs.remove(host);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(filename: &str, status: &str, print_duration: f64) -> HistoryJob {
+        HistoryJob {
+            job_id: "000001".to_string(),
+            filename: filename.to_string(),
+            status: status.to_string(),
+            start_time: 0.0,
+            print_duration,
+        }
+    }
+
+    fn progress(progress: f64, print_duration: f64) -> PrintProgress {
+        PrintProgress {
+            progress,
+            print_duration,
+            total_duration: print_duration,
+            current_layer: None,
+            total_layers: None,
+            height: None,
+            total_height: None,
+        }
+    }
+
+    #[test]
+    fn slow_print_is_reported_once_against_the_median_of_completed_runs() {
+        let settings = JobComparisonSettings::default();
+        let mut comparator = JobComparator::new();
+        comparator.set_baseline("10.0.0.5", "benchy.gcode", &[
+            run("benchy.gcode", "completed", 3600.0),
+            run("benchy.gcode", "completed", 3700.0),
+            run("benchy.gcode", "completed", 9000.0),
+            run("benchy.gcode", "cancelled", 100.0),
+            run("cube.gcode", "completed", 600.0),
+        ]);
+        assert!(!comparator.needs_baseline("10.0.0.5", "benchy.gcode"));
+
+        // Below the minimum progress and within the tolerance nothing is reported
+        assert!(comparator.check("10.0.0.5", &progress(5.0, 600.0), &settings).is_none());
+        assert!(comparator.check("10.0.0.5", &progress(50.0, 2000.0), &settings).is_none());
+
+        let slowdown = comparator.check("10.0.0.5", &progress(50.0, 2775.0), &settings).unwrap();
+        assert_eq!(slowdown.previous_runs, 3);
+        assert_eq!(slowdown.expected_duration, 1850.0);
+        assert!((slowdown.slowdown_percent - 50.0).abs() < 1e-9);
+        assert!(comparator.check("10.0.0.5", &progress(60.0, 4000.0), &settings).is_none());
+    }
+
+    #[test]
+    fn files_without_completed_runs_are_not_compared() {
+        let mut comparator = JobComparator::new();
+        comparator.set_baseline("10.0.0.5", "new.gcode", &[run("new.gcode", "error", 1200.0)]);
+
+        assert!(comparator.check("10.0.0.5", &progress(50.0, 99999.0), &JobComparisonSettings::default()).is_none());
+        assert!(comparator.needs_baseline("10.0.0.5", "other.gcode"));
+    }
+}
@@ -13,6 +13,16 @@
 //! - `network/` - Network scanning and utilities
 //! - `commands/` - Tauri command handlers
 //! - `notifications/` - System notification functions
+//! - `webcam/` - Webcam snapshots and print failure heuristics
+//! - `server/` - Optional embedded REST API server
+//! - `print_queue.rs` - Print queue scheduler for printer farms
+//! - `telemetry.rs` - SQLite telemetry log of temperatures, progress and status
+//! - `rules.rs` - Evaluation of user-defined automation rules
+//! - `heater_watch.rs` - Cross-check of heater power against temperature rise, idle heater timeout
+//! - `mcu_watch.rs` - Retransmit tracking of MCU links
+//! - `quick_actions.rs` - Execution of user-defined host buttons
+//! - `preflight.rs` - Checks run before remote print starts
+//! - `cli.rs` / `headless.rs` - Command line arguments and daemon mode
 //! - `error.rs` - Error handling and types
 //! 
 //! # Features
@@ -33,6 +43,25 @@ pub mod notifications;
 pub mod updater;
 pub mod background_monitor;
 pub mod telegram;
+pub mod webcam;
+pub mod server;
+pub mod cli;
+pub mod headless;
+pub mod print_queue;
+pub mod gcode;
+pub mod inventory;
+pub mod fleet;
+pub mod diagnostics;
+pub mod job_comparison;
+pub mod config_backup;
+pub mod telemetry;
+pub mod rules;
+pub mod heater_watch;
+pub mod mcu_watch;
+pub mod quick_actions;
+pub mod preflight;
+pub mod retry;
+pub mod i18n;
 
 // Re-export commonly used types
 pub use error::{MoonrakerError, MoonrakerResult};
@@ -71,6 +100,9 @@ pub fn run() {
         })
         .manage(background_monitor::BackgroundMonitorState::new())
         .manage(commands::telegram::TelegramBotState::new())
+        .manage(server::RestApiState::new())
+        .manage(print_queue::PrintQueueState::new())
+        .manage(commands::calibration::CalibrationState::new())
         .setup(|app| {
             // Create system tray with menu
             use tauri::{
@@ -161,23 +193,103 @@ pub fn run() {
                 })
                 .build(app)?;
 
+            // Let clicks on notifications bring the window to the front
+            notifications::set_notification_app_handle(app.handle().clone());
+
+            // Start the REST API server if it is enabled in settings
+            let settings = models::config::AppSettings::load().unwrap_or_default();
+            if settings.rest_api.enabled {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let rest_api = app_handle.state::<server::RestApiState>();
+                    let telegram = app_handle.state::<commands::telegram::TelegramBotState>();
+                    let monitor = app_handle.state::<background_monitor::BackgroundMonitorState>();
+                    if let Err(e) = rest_api.start(&settings.rest_api, telegram.hosts.clone(), monitor.health_registry()).await {
+                        eprintln!("Failed to start REST API server: {}", e);
+                    }
+                });
+            }
+
+            // Start the print queue scheduler
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let queue = app_handle.state::<print_queue::PrintQueueState>();
+                let telegram = app_handle.state::<commands::telegram::TelegramBotState>();
+                queue.start(telegram.hosts.clone(), print_queue::QUEUE_INTERVAL_SECONDS).await;
+            });
+
             println!("Application initialized successfully with system tray");
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             // Scan commands
             commands::scan::scan_network_command,
+            commands::scan::get_dhcp_reservation_command,
             commands::scan::get_host_info_command,
             commands::scan::check_host_status_command,
+            commands::scan::refresh_all_hosts_command,
+            commands::scan::get_status_cache_ttl_command,
+            commands::scan::set_status_cache_ttl_command,
+            commands::scan::get_host_request_concurrency_command,
+            commands::scan::set_host_request_concurrency_command,
+            commands::scan::get_scan_limits_command,
+            commands::scan::update_scan_limits_command,
+            commands::scan::get_scan_method_command,
+            commands::scan::set_scan_method_command,
+            commands::scan::get_overlay_scan_settings_command,
+            commands::scan::update_overlay_scan_settings_command,
+            commands::scan::get_moonraker_ports_command,
+            commands::scan::set_moonraker_ports_command,
+            commands::scan::is_syn_scan_available_command,
+            commands::scan::get_port_cache_stats_command,
+            commands::scan::clear_port_cache_command,
+            commands::scan::get_local_networks_command,
+            commands::scan::get_network_change_command,
+            commands::scan::dismiss_network_change_command,
+            commands::scan::get_network_change_settings_command,
+            commands::scan::update_network_change_settings_command,
             
             // Printer commands
             commands::printer::control_printer_command,
             commands::printer::get_printer_status_command,
+            commands::printer::get_printer_capabilities_command,
+            commands::printer::firmware_restart_command,
+            commands::printer::save_config_command,
+            commands::printer::set_fan_speed_command,
+            commands::printer::set_led_command,
+            commands::printer::home_axes_command,
+            commands::printer::jog_command,
+            commands::printer::get_z_offset_command,
+            commands::printer::adjust_z_offset_command,
+            commands::printer::get_object_query_presets_command,
+            commands::printer::save_object_query_preset_command,
+            commands::printer::delete_object_query_preset_command,
+            commands::printer::query_printer_objects_command,
+            commands::printer::get_power_devices_command,
+            commands::printer::set_power_device_command,
+            commands::printer::get_auto_power_off_command,
+            commands::printer::set_auto_power_off_command,
+            commands::printer::get_sensors_command,
+            commands::printer::get_mcu_stats_command,
+            commands::printer::list_canbus_devices_command,
+            commands::printer::run_preflight_command,
+            commands::printer::start_print_command,
+            commands::printer::get_preflight_settings_command,
+            commands::printer::update_preflight_settings_command,
+            commands::printer::get_sensor_settings_command,
+            commands::printer::set_sensor_settings_command,
             
             // Print info commands
             commands::print_info::get_print_info_command,
             commands::print_info::get_print_progress_command,
             commands::print_info::format_duration_command,
+            commands::print_info::format_timestamp_command,
+            commands::print_info::format_finish_time_command,
+            commands::print_info::get_history_stats_command,
+            commands::print_info::get_cost_settings_command,
+            commands::print_info::update_cost_settings_command,
+            commands::print_info::get_job_notes_command,
+            commands::print_info::set_job_note_command,
             
             // System commands
             commands::system::open_webcam_command,
@@ -186,6 +298,21 @@ pub fn run() {
             commands::system::send_system_notification_command,
             commands::system::open_url_in_browser_command,
             commands::system::check_notification_status_command,
+            commands::system::request_notification_permission_command,
+            commands::system::get_language_command,
+            commands::system::set_language_command,
+            commands::system::get_use_24_hour_clock_command,
+            commands::system::set_use_24_hour_clock_command,
+            commands::system::get_outbound_http_settings_command,
+            commands::system::update_outbound_http_settings_command,
+            commands::system::preview_user_agent_command,
+            
+            // Notification center commands
+            commands::notifications::list_notifications_command,
+            commands::notifications::get_unread_notification_count_command,
+            commands::notifications::mark_notification_read_command,
+            commands::notifications::mark_all_notifications_read_command,
+            commands::notifications::clear_notifications_command,
             
             // Updater commands
             commands::updater::check_for_updates_command,
@@ -196,6 +323,46 @@ pub fn run() {
             commands::background::start_background_monitoring_command,
             commands::background::stop_background_monitoring_command,
             commands::background::get_background_monitoring_status_command,
+            commands::background::get_host_health_command,
+            commands::background::get_failure_detection_settings_command,
+            commands::background::update_failure_detection_settings_command,
+            commands::background::get_job_comparison_settings_command,
+            commands::background::update_job_comparison_settings_command,
+            commands::background::get_snapshot_archive_settings_command,
+            commands::background::update_snapshot_archive_settings_command,
+            commands::background::get_error_capture_settings_command,
+            commands::background::update_error_capture_settings_command,
+            commands::background::get_error_captures_command,
+            commands::background::delete_error_capture_command,
+            commands::background::get_completion_gif_command,
+            commands::background::set_completion_gif_command,
+            commands::background::get_completion_webhooks_command,
+            commands::background::update_completion_webhooks_command,
+            commands::background::get_cooldown_settings_command,
+            commands::background::update_cooldown_settings_command,
+            commands::background::get_heater_watch_settings_command,
+            commands::background::update_heater_watch_settings_command,
+            commands::background::get_heater_idle_settings_command,
+            commands::background::update_heater_idle_settings_command,
+            commands::background::snooze_alert_command,
+            commands::background::acknowledge_alert_command,
+            commands::background::unsilence_alert_command,
+            commands::background::get_alert_silences_command,
+            commands::background::get_ack_escalation_settings_command,
+            commands::background::update_ack_escalation_settings_command,
+            commands::background::get_mcu_watch_settings_command,
+            commands::background::update_mcu_watch_settings_command,
+            commands::background::get_offline_escalation_settings_command,
+            commands::background::update_offline_escalation_settings_command,
+            commands::background::get_fleet_idle_notification_settings_command,
+            commands::background::update_fleet_idle_notification_settings_command,
+            commands::background::get_notification_templates_command,
+            commands::background::update_notification_templates_command,
+            commands::background::get_do_not_disturb_state_command,
+            commands::background::set_respect_do_not_disturb_command,
+            commands::background::validate_notification_template_command,
+            commands::background::preview_notification_template_command,
+            commands::background::render_status_change_notification_command,
             
             // Telegram bot commands
             commands::telegram::start_telegram_bot,
@@ -204,6 +371,13 @@ pub fn run() {
             commands::telegram::start_telegram_registration,
             commands::telegram::stop_telegram_registration,
             commands::telegram::is_telegram_registration_active,
+            commands::telegram::get_telegram_registration_link,
+            commands::telegram::create_telegram_registration_code,
+            commands::telegram::get_telegram_registration_codes,
+            commands::telegram::revoke_telegram_registration_code,
+            commands::telegram::get_telegram_pending_registrations,
+            commands::telegram::approve_telegram_registration,
+            commands::telegram::deny_telegram_registration,
             commands::telegram::get_telegram_users,
             commands::telegram::remove_telegram_user,
             commands::telegram::get_telegram_hosts,
@@ -216,6 +390,89 @@ pub fn run() {
             commands::telegram::load_telegram_settings,
             commands::telegram::get_telegram_registration_info,
             commands::telegram::save_telegram_users,
+            commands::telegram::get_telegram_audit_log,
+            commands::telegram::set_telegram_notify_emergency_stop,
+            commands::telegram::get_telegram_allowed_networks,
+            commands::telegram::set_telegram_allowed_networks,
+            
+            // REST API server commands
+            commands::server::start_rest_api_command,
+            commands::server::stop_rest_api_command,
+            commands::server::get_rest_api_status_command,
+            commands::server::get_rest_api_settings_command,
+            commands::server::update_rest_api_settings_command,
+            
+            // Host configuration commands
+            commands::hosts::get_host_connections_command,
+            commands::hosts::update_host_connection_command,
+            commands::hosts::get_host_names_command,
+            commands::hosts::rename_host_command,
+            commands::hosts::set_host_maintenance_command,
+            commands::hosts::set_host_tags_command,
+            commands::hosts::export_hosts_command,
+            commands::hosts::import_hosts_command,
+            commands::hosts::add_remote_host_command,
+            commands::hosts::remove_remote_host_command,
+            commands::hosts::get_fleet_overview_command,
+            commands::hosts::get_availability_forecast_command,
+            commands::hosts::get_host_diagnostics_command,
+            
+            // Maintenance schedule commands
+            commands::maintenance::get_host_maintenance_command,
+            commands::maintenance::add_maintenance_task_command,
+            commands::maintenance::remove_maintenance_task_command,
+            commands::maintenance::complete_maintenance_task_command,
+            commands::maintenance::add_maintenance_log_entry_command,
+            
+            // Print queue commands
+            commands::queue::get_print_queue_command,
+            commands::queue::add_queue_job_command,
+            commands::queue::remove_queue_job_command,
+            commands::queue::clear_finished_queue_jobs_command,
+            commands::queue::mark_bed_cleared_command,
+            commands::files::get_gcode_metadata_command,
+            
+            // Scheduled action commands
+            commands::schedule::get_scheduled_actions_command,
+            commands::schedule::add_scheduled_action_command,
+            commands::schedule::remove_scheduled_action_command,
+            
+            // Automation rule commands
+            commands::rules::get_rules_command,
+            commands::rules::save_rule_command,
+            commands::rules::set_rule_enabled_command,
+            commands::rules::remove_rule_command,
+            
+            // Quick action commands
+            commands::quick_actions::get_quick_actions_command,
+            commands::quick_actions::save_quick_action_command,
+            commands::quick_actions::remove_quick_action_command,
+            commands::quick_actions::run_quick_action_command,
+            
+            // Calibration commands
+            commands::calibration::start_calibration_command,
+            commands::calibration::get_calibration_run_command,
+            commands::calibration::start_pid_calibration_command,
+            commands::calibration::get_pid_calibration_run_command,
+            commands::calibration::download_calibration_file_command,
+            // Printer configuration commands
+            commands::config_files::list_config_files_command,
+            commands::config_files::read_config_file_command,
+            commands::config_files::diff_config_file_command,
+            commands::config_files::save_config_file_command,
+            commands::config_files::restart_config_service_command,
+            commands::config_files::get_config_backup_settings_command,
+            commands::config_files::update_config_backup_settings_command,
+            commands::config_files::backup_configs_command,
+            commands::config_files::push_config_backup_command,
+            commands::config_files::get_config_backups_command,
+            commands::config_files::restore_config_backup_command,
+            commands::config_files::delete_config_backup_command,
+            // Telemetry commands
+            commands::telemetry::get_telemetry_settings_command,
+            commands::telemetry::update_telemetry_settings_command,
+            commands::telemetry::query_telemetry_command,
+            commands::telemetry::get_temperature_series_command,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -2,5 +2,13 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
-    moonrakerhostscanner_lib::run()
+    let cli = moonrakerhostscanner_lib::cli::parse_args();
+
+    if let Some(command) = cli.command {
+        std::process::exit(moonrakerhostscanner_lib::cli::run_blocking(command));
+    } else if cli.headless {
+        std::process::exit(moonrakerhostscanner_lib::headless::run_blocking(cli.subnets));
+    } else {
+        moonrakerhostscanner_lib::run()
+    }
 }
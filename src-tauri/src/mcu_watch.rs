@@ -0,0 +1,122 @@
+//! MCU link quality tracking
+//!
+//! Klipper counts the bytes it has to send again to each micro-controller.
+//! A few retransmits after a restart are normal, but a steadily rising count
+//! is an early sign of a failing USB cable or CAN bus, long before Klipper
+//! shuts down with "Lost communication with MCU".
+
+use std::collections::{HashMap, HashSet};
+use tokio::time::Instant;
+use crate::models::api::McuStats;
+use crate::models::config::McuWatchSettings;
+
+/// MCU link retransmitting more than the configured rate
+#[derive(Debug, Clone, PartialEq)]
+pub struct McuLinkAlarm {
+    /// Klipper object, e.g. "mcu" or "mcu EBBCan"
+    pub mcu: String,
+    /// Bytes retransmitted since the previous check
+    pub retransmits: u64,
+    /// Retransmitted bytes per minute since the previous check
+    pub per_minute: f64,
+    /// Total invalid bytes received since Klippy started
+    pub bytes_invalid: u64,
+}
+
+/// MCU counters carried between monitoring cycles
+#[derive(Debug, Default)]
+pub struct McuLinkWatch {
+    /// Last retransmit count and when it was read, keyed by (host, mcu)
+    counters: HashMap<(String, String), (u64, Instant)>,
+    /// Links an alarm was raised for, until their rate drops again
+    alarmed: HashSet<(String, String)>,
+}
+
+impl McuLinkWatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the counters of an MCU and tells whether its link just became bad
+    ///
+    /// # Arguments
+    /// * `host` - Host IP address
+    /// * `stats` - Current MCU statistics
+    /// * `settings` - Retransmit threshold
+    /// * `now` - Time of the reading
+    ///
+    /// # Returns
+    /// * An alarm when the retransmit rate exceeds the threshold for the first time
+    pub fn check(&mut self, host: &str, stats: &McuStats, settings: &McuWatchSettings, now: Instant) -> Option<McuLinkAlarm> {
+        let key = (host.to_string(), stats.name.clone());
+        let previous = self.counters.insert(key.clone(), (stats.bytes_retransmit, now));
+        // Counters start over when Klippy restarts
+        let (count, since) = previous.filter(|(count, _)| *count <= stats.bytes_retransmit)?;
+        let minutes = now.saturating_duration_since(since).as_secs_f64() / 60.0;
+        if minutes <= 0.0 {
+            return None;
+        }
+
+        let retransmits = stats.bytes_retransmit - count;
+        let per_minute = retransmits as f64 / minutes;
+        if per_minute < settings.retransmits_per_minute {
+            self.alarmed.remove(&key);
+            return None;
+        }
+        self.alarmed.insert(key).then(|| McuLinkAlarm {
+            mcu: stats.name.clone(),
+            retransmits,
+            per_minute,
+            bytes_invalid: stats.bytes_invalid,
+        })
+    }
+
+    /// Whether a link of a host raised an alarm and still retransmits too much
+    pub fn is_alarmed(&self, host: &str) -> bool {
+        self.alarmed.iter().any(|(h, _)| h == host)
+    }
+
+    /// Forgets the MCUs of a host, e.g. when it went offline or Klippy restarted
+    pub fn forget_host(&mut self, host: &str) {
+        self.counters.retain(|(h, _), _| h != host);
+        self.alarmed.retain(|(h, _)| h != host);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn stats(bytes_retransmit: u64) -> McuStats {
+        McuStats {
+            name: "mcu EBBCan".to_string(),
+            version: None,
+            clock_frequency: None,
+            frequency: None,
+            bytes_write: 0,
+            bytes_read: 0,
+            bytes_retransmit,
+            bytes_invalid: 0,
+            srtt: None,
+        }
+    }
+
+    #[test]
+    fn rising_retransmits_raise_one_alarm() {
+        let settings = McuWatchSettings::default();
+        let mut watch = McuLinkWatch::new();
+        let start = Instant::now();
+        let at = |seconds: u64| start + Duration::from_secs(seconds);
+
+        assert_eq!(watch.check("192.168.1.50", &stats(40), &settings, at(0)), None);
+        assert_eq!(watch.check("192.168.1.50", &stats(60), &settings, at(60)), None);
+        let alarm = watch.check("192.168.1.50", &stats(360), &settings, at(120)).expect("alarm");
+        assert_eq!((alarm.retransmits, alarm.per_minute), (300, 300.0));
+        assert_eq!(watch.check("192.168.1.50", &stats(700), &settings, at(180)), None);
+
+        // A Klippy restart resets the counters without an alarm
+        assert_eq!(watch.check("192.168.1.50", &stats(0), &settings, at(240)), None);
+        assert_eq!(watch.check("192.168.1.50", &stats(10), &settings, at(300)), None);
+    }
+}
@@ -16,7 +16,7 @@ pub struct MoonrakerServerInfo {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ServerInfoResult {
     pub klippy_connected: bool,
-    pub klippy_state: String,
+    pub klippy_state: KlippyState,
     pub components: Vec<String>,
     pub failed_components: Vec<String>,
     pub registered_directories: Vec<String>,
@@ -30,6 +30,139 @@ pub struct ServerInfoResult {
     pub missing_klippy_requirements: Option<Vec<String>>,
 }
 
+/// State of the Klippy host software as reported by Moonraker
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum KlippyState {
+    /// Klippy is running and the printer is usable
+    Ready,
+    /// Klippy is starting up or restarting
+    Startup,
+    /// Klippy stopped itself, e.g. after an MCU error or emergency stop
+    Shutdown,
+    /// Klippy failed to start, usually because of a config error
+    Error,
+    /// Moonraker has no connection to Klippy
+    Disconnected,
+    /// State not known to this version of the app
+    #[serde(other)]
+    Unknown,
+}
+
+impl KlippyState {
+    /// Gets the state as sent by Moonraker
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KlippyState::Ready => "ready",
+            KlippyState::Startup => "startup",
+            KlippyState::Shutdown => "shutdown",
+            KlippyState::Error => "error",
+            KlippyState::Disconnected => "disconnected",
+            KlippyState::Unknown => "unknown",
+        }
+    }
+
+    /// Checks whether the state needs attention (shutdown or error)
+    pub fn is_fault(&self) -> bool {
+        matches!(self, KlippyState::Shutdown | KlippyState::Error)
+    }
+
+    /// Gets a short human readable label in the configured language
+    pub fn label(&self) -> &'static str {
+        crate::i18n::t(match self {
+            KlippyState::Ready => "klippy.ready",
+            KlippyState::Startup => "klippy.startup",
+            KlippyState::Shutdown => "klippy.shutdown",
+            KlippyState::Error => "klippy.error",
+            KlippyState::Disconnected => "klippy.disconnected",
+            KlippyState::Unknown => "klippy.unknown",
+        })
+    }
+}
+
+impl std::fmt::Display for KlippyState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// State of a printer as shown in the app
+///
+/// Derived from the Moonraker state flags, plus `Offline` for hosts that
+/// cannot be reached or whose Moonraker has lost Klippy. Serialized in
+/// lowercase ("printing", "standby", ...) like the status strings before it.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum PrinterState {
+    Printing,
+    Paused,
+    /// Print is being cancelled
+    Cancelling,
+    /// Klipper reports an error, e.g. after a shutdown
+    Error,
+    /// Idle and ready for the next print
+    Standby,
+    /// Host or Klippy unreachable
+    Offline,
+}
+
+impl PrinterState {
+    /// Determines the state from Moonraker flags
+    ///
+    /// Priority order: cancelling > error > paused > printing > standby
+    pub fn from_flags(flags: &PrinterFlags) -> Self {
+        if flags.cancelling {
+            PrinterState::Cancelling
+        } else if flags.error {
+            PrinterState::Error
+        } else if flags.paused {
+            PrinterState::Paused
+        } else if flags.printing {
+            PrinterState::Printing
+        } else {
+            PrinterState::Standby
+        }
+    }
+
+    /// Gets the state as sent to the frontend
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrinterState::Printing => "printing",
+            PrinterState::Paused => "paused",
+            PrinterState::Cancelling => "cancelling",
+            PrinterState::Error => "error",
+            PrinterState::Standby => "standby",
+            PrinterState::Offline => "offline",
+        }
+    }
+
+    /// Gets the localized state name
+    pub fn label(&self) -> &'static str {
+        crate::i18n::status_name(self.as_str())
+    }
+
+    /// Checks whether a job is loaded (printing or paused)
+    pub fn has_active_job(&self) -> bool {
+        matches!(self, PrinterState::Printing | PrinterState::Paused)
+    }
+
+    /// Checks whether the printer cannot be used (error or offline)
+    pub fn is_fault(&self) -> bool {
+        matches!(self, PrinterState::Error | PrinterState::Offline)
+    }
+
+    /// Checks whether changing from `previous` to this state ended a job
+    pub fn ends_job(&self, previous: Option<PrinterState>) -> bool {
+        previous.is_some_and(|p| p.has_active_job()) && !self.has_active_job()
+    }
+}
+
+impl std::fmt::Display for PrinterState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Printer information response from Moonraker API
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MoonrakerPrinterInfo {
@@ -100,22 +233,679 @@ pub struct PrinterFlags {
 }
 
 impl PrinterFlags {
-    /// Determines the printer status based on flags priority
+    /// Determines the printer state based on flags priority
     /// 
-    /// Priority order: cancelling > error > paused > printing > ready > standby
-    pub fn get_status(&self) -> &'static str {
-        if self.cancelling {
-            "cancelling"
-        } else if self.error {
-            "error"
-        } else if self.paused {
-            "paused"
-        } else if self.printing {
-            "printing"
-        } else if self.ready {
-            "standby"
-        } else {
-            "standby"
+    /// Priority order: cancelling > error > paused > printing > standby
+    pub fn get_status(&self) -> PrinterState {
+        PrinterState::from_flags(self)
+    }
+}
+
+/// Job history totals response from Moonraker API
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoonrakerJobTotals {
+    pub result: JobTotalsResult,
+}
+
+/// Job history totals result
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobTotalsResult {
+    pub job_totals: JobTotals,
+}
+
+/// Cumulative statistics over all jobs in the printer history
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct JobTotals {
+    #[serde(default)]
+    pub total_jobs: u64,
+    /// Total job time in seconds, including pauses
+    #[serde(default)]
+    pub total_time: f64,
+    /// Total time spent printing in seconds
+    #[serde(default)]
+    pub total_print_time: f64,
+    /// Total filament used in millimeters
+    #[serde(default)]
+    pub total_filament_used: f64,
+    #[serde(default)]
+    pub longest_job: f64,
+    #[serde(default)]
+    pub longest_print: f64,
+}
+
+impl JobTotals {
+    /// Adds the history of another printer (longest jobs take the maximum)
+    pub fn add(&mut self, other: &JobTotals) {
+        self.total_jobs += other.total_jobs;
+        self.total_time += other.total_time;
+        self.total_print_time += other.total_print_time;
+        self.total_filament_used += other.total_filament_used;
+        self.longest_job = self.longest_job.max(other.longest_job);
+        self.longest_print = self.longest_print.max(other.longest_print);
+    }
+}
+
+/// Job history list response from Moonraker API
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoonrakerJobList {
+    pub result: JobListResult,
+}
+
+/// Job history list result
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobListResult {
+    #[serde(default)]
+    pub jobs: Vec<HistoryJob>,
+}
+
+/// One job of the printer history
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HistoryJob {
+    pub job_id: String,
+    pub filename: String,
+    /// "completed", "cancelled", "error", "klippy_shutdown", "in_progress", ...
+    pub status: String,
+    /// Start time (Unix timestamp)
+    #[serde(default)]
+    pub start_time: f64,
+    /// Time spent printing in seconds, without pauses
+    #[serde(default)]
+    pub print_duration: f64,
+}
+
+/// Type of a queried printer object value
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ObjectValueKind {
+    Number,
+    Text,
+    Boolean,
+    List,
+    Null,
+}
+
+/// One value of a queried printer object, flattened for a raw-data view
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ObjectField {
+    /// Klipper object name, e.g. "heater_bed"
+    pub object: String,
+    /// Field path inside the object, nested fields joined with "."
+    pub key: String,
+    /// Value type
+    pub kind: ObjectValueKind,
+    /// Raw value
+    pub value: serde_json::Value,
+}
+
+/// Current hotend and bed temperatures
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HeaterTemperatures {
+    /// Primary extruder temperature in °C
+    pub extruder: f64,
+    /// Bed temperature in °C (None without a heated bed)
+    pub bed: Option<f64>,
+    /// Temperatures of all tools and the active one
+    #[serde(default)]
+    pub tools: ToolsStatus,
+}
+
+impl HeaterTemperatures {
+    /// Temperature of the hottest hotend in °C, e.g. to wait until all have cooled down
+    pub fn hottest_hotend(&self) -> f64 {
+        self.tools.tools.iter().map(|tool| tool.temperature).fold(self.extruder, f64::max)
+    }
+}
+
+/// Gets the tool number of an extruder object
+///
+/// # Returns
+/// * 0 for "extruder", n for "extruder<n>", None for other objects
+pub fn extruder_index(object: &str) -> Option<u32> {
+    match object.strip_prefix("extruder")? {
+        "" => Some(0),
+        index if index.chars().all(|c| c.is_ascii_digit()) => index.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Checks whether a Klipper object is a heater: an extruder, the bed or a
+/// `heater_generic`
+pub fn is_heater_object(object: &str) -> bool {
+    extruder_index(object).is_some() || object == "heater_bed" || object.starts_with("heater_generic ")
+}
+
+/// Temperature and power of one heater
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HeaterReading {
+    /// Klipper heater object, e.g. "extruder", "heater_bed" or "heater_generic chamber"
+    pub name: String,
+    /// Temperature in °C
+    pub temperature: f64,
+    /// Target temperature in °C (0 when the heater is off)
+    pub target: f64,
+    /// Heater PWM power (0.0 - 1.0)
+    pub power: f64,
+}
+
+impl HeaterReading {
+    /// Reads the heaters from a `printer/objects/query` status
+    ///
+    /// # Arguments
+    /// * `status` - `result.status` of the query
+    pub fn from_status(status: &serde_json::Value) -> Vec<Self> {
+        let Some(objects) = status.as_object() else {
+            return Vec::new();
+        };
+        let mut readings: Vec<Self> = objects
+            .iter()
+            .filter(|(object, _)| is_heater_object(object))
+            .filter_map(|(object, values)| {
+                let value = |key: &str| values.get(key).and_then(|v| v.as_f64());
+                Some(Self {
+                    name: object.clone(),
+                    temperature: value("temperature")?,
+                    target: value("target").unwrap_or(0.0),
+                    power: value("power")?,
+                })
+            })
+            .collect();
+        readings.sort_by(|a, b| a.name.cmp(&b.name));
+        readings
+    }
+}
+
+/// Checks whether a Klipper object is a micro-controller: `mcu` or `mcu <name>`
+pub fn is_mcu_object(object: &str) -> bool {
+    object == "mcu" || object.starts_with("mcu ")
+}
+
+/// Connection statistics of one micro-controller
+///
+/// The byte counters come from Klipper's `last_stats` and start over when
+/// Klippy restarts.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct McuStats {
+    /// Klipper object, e.g. "mcu" or "mcu EBBCan"
+    pub name: String,
+    /// Firmware version
+    pub version: Option<String>,
+    /// Nominal clock frequency in Hz (`CLOCK_FREQ` of the firmware)
+    pub clock_frequency: Option<f64>,
+    /// Clock frequency in Hz measured by the host
+    pub frequency: Option<f64>,
+    pub bytes_write: u64,
+    pub bytes_read: u64,
+    /// Bytes sent again because the MCU did not acknowledge them
+    pub bytes_retransmit: u64,
+    /// Bytes received with a bad checksum or framing
+    pub bytes_invalid: u64,
+    /// Smoothed round-trip time in seconds
+    pub srtt: Option<f64>,
+}
+
+impl McuStats {
+    /// Reads the micro-controllers from a `printer/objects/query` status
+    ///
+    /// # Arguments
+    /// * `status` - `result.status` of the query
+    pub fn from_status(status: &serde_json::Value) -> Vec<Self> {
+        let Some(objects) = status.as_object() else {
+            return Vec::new();
+        };
+        let mut mcus: Vec<Self> = objects
+            .iter()
+            .filter(|(object, _)| is_mcu_object(object))
+            .map(|(object, values)| {
+                let stat = |key: &str| values.pointer(&format!("/last_stats/{}", key));
+                let counter = |key: &str| stat(key).and_then(|v| v.as_u64()).unwrap_or(0);
+                Self {
+                    name: object.clone(),
+                    version: values.get("mcu_version").and_then(|v| v.as_str()).map(str::to_string),
+                    clock_frequency: values.pointer("/mcu_constants/CLOCK_FREQ").and_then(|v| v.as_f64()),
+                    frequency: stat("freq").and_then(|v| v.as_f64()),
+                    bytes_write: counter("bytes_write"),
+                    bytes_read: counter("bytes_read"),
+                    bytes_retransmit: counter("bytes_retransmit"),
+                    bytes_invalid: counter("bytes_invalid"),
+                    srtt: stat("srtt").and_then(|v| v.as_f64()),
+                }
+            })
+            .collect();
+        mcus.sort_by(|a, b| a.name.cmp(&b.name));
+        mcus
+    }
+
+    /// Deviation of the measured clock from the nominal one in parts per million
+    pub fn clock_skew_ppm(&self) -> Option<f64> {
+        let nominal = self.clock_frequency.filter(|f| *f > 0.0)?;
+        Some((self.frequency? - nominal) / nominal * 1_000_000.0)
+    }
+}
+
+/// Hotend temperature of one tool
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ToolTemperature {
+    /// Klipper extruder object, e.g. "extruder" or "extruder1"
+    pub name: String,
+    /// Temperature in °C
+    pub temperature: f64,
+    /// Target temperature in °C (0 when the heater is off)
+    pub target: f64,
+    /// Heater power (0.0 - 1.0)
+    pub power: Option<f64>,
+}
+
+/// State of a klipper-toolchanger setup (`toolchanger` object)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ToolchangerState {
+    /// e.g. "ready", "changing", "error" or "uninitialized"
+    pub status: String,
+    /// Object of the mounted tool, e.g. "tool T0" (None when no tool is mounted)
+    pub tool: Option<String>,
+    /// Number of the mounted tool (None when no tool is mounted)
+    pub tool_number: Option<i64>,
+    /// Names of all tools
+    pub tool_names: Vec<String>,
+}
+
+/// Tools of a printer: hotend temperatures and which tool is active
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct ToolsStatus {
+    /// Extruders ordered by tool number
+    pub tools: Vec<ToolTemperature>,
+    /// Extruder the toolhead currently uses, e.g. "extruder1"
+    pub active_extruder: Option<String>,
+    /// Toolchanger state (None without a toolchanger)
+    pub toolchanger: Option<ToolchangerState>,
+}
+
+impl ToolsStatus {
+    /// Reads the tools from a `printer/objects/query` status of the
+    /// extruders, `toolhead` and `toolchanger`
+    ///
+    /// # Arguments
+    /// * `status` - `result.status` of the query
+    pub fn from_status(status: &serde_json::Value) -> Self {
+        let mut tools: Vec<(u32, ToolTemperature)> = status
+            .as_object()
+            .map(|objects| {
+                objects
+                    .iter()
+                    .filter_map(|(object, values)| {
+                        let index = extruder_index(object)?;
+                        let value = |key: &str| values.get(key).and_then(|v| v.as_f64());
+                        Some((index, ToolTemperature {
+                            name: object.clone(),
+                            temperature: value("temperature")?,
+                            target: value("target").unwrap_or(0.0),
+                            power: value("power"),
+                        }))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        tools.sort_by_key(|(index, _)| *index);
+
+        let toolchanger = status.get("toolchanger").map(|changer| {
+            let text = |key: &str| changer.get(key).and_then(|v| v.as_str()).filter(|v| !v.is_empty()).map(str::to_string);
+            ToolchangerState {
+                status: text("status").unwrap_or_else(|| "unknown".to_string()),
+                tool: text("tool"),
+                tool_number: changer.get("tool_number").and_then(|v| v.as_i64()).filter(|n| *n >= 0),
+                tool_names: changer
+                    .get("tool_names")
+                    .and_then(|v| v.as_array())
+                    .map(|names| names.iter().filter_map(|n| n.as_str()).map(str::to_string).collect())
+                    .unwrap_or_default(),
+            }
+        });
+
+        Self {
+            tools: tools.into_iter().map(|(_, tool)| tool).collect(),
+            active_extruder: status
+                .pointer("/toolhead/extruder")
+                .and_then(|e| e.as_str())
+                .filter(|e| !e.is_empty())
+                .map(str::to_string),
+            toolchanger,
+        }
+    }
+
+    /// Gets the tool the toolhead currently uses
+    pub fn active(&self) -> Option<&ToolTemperature> {
+        let name = self.active_extruder.as_deref()?;
+        self.tools.iter().find(|tool| tool.name == name)
+    }
+
+    /// Short summary of the tool temperatures, the active tool marked,
+    /// e.g. "T0 150/150°C · ▶T1 251/250°C"
+    pub fn summary(&self) -> String {
+        self.tools
+            .iter()
+            .map(|tool| {
+                let marker = if self.active_extruder.as_deref() == Some(tool.name.as_str()) { "▶" } else { "" };
+                let number = extruder_index(&tool.name).unwrap_or(0);
+                format!("{}T{} {:.0}/{:.0}°C", marker, number, tool.temperature, tool.target)
+            })
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+/// Klipper object prefixes of configurable LEDs
+pub const LED_OBJECT_PREFIXES: [&str; 5] = ["led ", "neopixel ", "dotstar ", "pca9533 ", "pca9632 "];
+
+/// Klipper object prefixes of temperature and humidity sensors other than heaters
+pub const SENSOR_OBJECT_PREFIXES: [&str; 6] =
+    ["temperature_sensor ", "temperature_fan ", "bme280 ", "htu21d ", "aht10 ", "sht3x "];
+
+/// Additional sensor of a printer, e.g. enclosure or filament dryer
+///
+/// Combined sensors such as the BME280 appear as a `temperature_sensor` and
+/// as a `bme280` object of the same name; both are merged into one sensor.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuxiliarySensor {
+    /// Sensor name from the Klipper configuration, e.g. "chamber"
+    pub name: String,
+    /// Name chosen by the user, if any
+    pub label: Option<String>,
+    /// Temperature in °C
+    pub temperature: Option<f64>,
+    /// Relative humidity in %
+    pub humidity: Option<f64>,
+    /// Air pressure in hPa
+    pub pressure: Option<f64>,
+}
+
+impl AuxiliarySensor {
+    /// Reads the sensors from a `printer/objects/query` status
+    ///
+    /// # Arguments
+    /// * `status` - `result.status` of the query
+    pub fn from_status(status: &serde_json::Value) -> Vec<Self> {
+        let Some(objects) = status.as_object() else {
+            return Vec::new();
+        };
+        let mut sensors: Vec<Self> = Vec::new();
+        for (object, values) in objects {
+            let Some(name) = SENSOR_OBJECT_PREFIXES.iter().find_map(|prefix| object.strip_prefix(prefix)) else {
+                continue;
+            };
+            let value = |key: &str| values.get(key).and_then(|v| v.as_f64());
+            let index = match sensors.iter().position(|s| s.name == name) {
+                Some(index) => index,
+                None => {
+                    sensors.push(Self { name: name.to_string(), label: None, temperature: None, humidity: None, pressure: None });
+                    sensors.len() - 1
+                }
+            };
+            let sensor = &mut sensors[index];
+            sensor.temperature = sensor.temperature.or(value("temperature"));
+            sensor.humidity = sensor.humidity.or(value("humidity"));
+            sensor.pressure = sensor.pressure.or(value("pressure"));
+        }
+        sensors.sort_by(|a, b| a.name.cmp(&b.name));
+        sensors
+    }
+
+    /// Name to show, the user's label or the configured name
+    pub fn display_name(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Klipper objects of filament runout sensors
+pub const FILAMENT_SENSOR_PREFIXES: [&str; 2] = ["filament_switch_sensor ", "filament_motion_sensor "];
+
+/// Filament runout sensor of a printer
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FilamentSensor {
+    /// Sensor name from the Klipper configuration, e.g. "runout"
+    pub name: String,
+    /// Whether the sensor is enabled (SET_FILAMENT_SENSOR)
+    pub enabled: bool,
+    pub filament_detected: bool,
+}
+
+impl FilamentSensor {
+    /// Reads the filament sensors from a `printer/objects/query` status
+    ///
+    /// # Arguments
+    /// * `status` - `result.status` of the query
+    pub fn from_status(status: &serde_json::Value) -> Vec<Self> {
+        let Some(objects) = status.as_object() else {
+            return Vec::new();
+        };
+        let mut sensors: Vec<Self> = objects
+            .iter()
+            .filter_map(|(object, values)| {
+                let name = FILAMENT_SENSOR_PREFIXES.iter().find_map(|prefix| object.strip_prefix(prefix))?;
+                let flag = |key: &str| values.get(key).and_then(|v| v.as_bool());
+                Some(Self {
+                    name: name.to_string(),
+                    enabled: flag("enabled").unwrap_or(true),
+                    filament_detected: flag("filament_detected").unwrap_or(false),
+                })
+            })
+            .collect();
+        sensors.sort_by(|a, b| a.name.cmp(&b.name));
+        sensors
+    }
+}
+
+/// Power device list response from Moonraker API
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoonrakerPowerDevices {
+    pub result: PowerDevicesResult,
+}
+
+/// Power device list result
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PowerDevicesResult {
+    pub devices: Vec<PowerDevice>,
+}
+
+/// Unassigned CAN node answering a UUID query
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CanDevice {
+    /// canbus_uuid to put in the Klipper `[mcu]` section
+    pub uuid: String,
+    /// Firmware running on the node, "Klipper" or "CanBoot"/"Katapult"
+    pub application: Option<String>,
+}
+
+/// How the CAN nodes were listed
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CanQuerySource {
+    /// `machine/peripherals/canbus` of Moonraker
+    Moonraker,
+    /// `canbus_query.py` run over SSH
+    Ssh,
+}
+
+/// CAN nodes found on one interface of a host
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CanbusDevices {
+    /// CAN interface, e.g. "can0"
+    pub interface: String,
+    pub source: CanQuerySource,
+    pub devices: Vec<CanDevice>,
+}
+
+/// Switchable power device configured in Moonraker (`[power ...]` section)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PowerDevice {
+    /// Device name
+    pub device: String,
+    /// "on", "off", "init" or "error"
+    pub status: String,
+    /// Whether Moonraker refuses to switch it while printing
+    #[serde(default)]
+    pub locked_while_printing: bool,
+    /// Device type, e.g. "gpio" or "tplink_smartplug"
+    #[serde(rename = "type", default)]
+    pub device_type: String,
+}
+
+/// Announcement list response from Moonraker API (`server/announcements/list`)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoonrakerAnnouncements {
+    pub result: AnnouncementsResult,
+}
+
+/// Announcement list result
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnnouncementsResult {
+    pub entries: Vec<Announcement>,
+}
+
+/// Announcement of the Moonraker or Klipper project, or of a configured feed
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Announcement {
+    pub entry_id: String,
+    pub url: String,
+    pub title: String,
+    pub description: String,
+    /// "normal" or "high"
+    pub priority: String,
+    /// Whether the user dismissed it in Mainsail/Fluidd
+    #[serde(default)]
+    pub dismissed: bool,
+}
+
+impl Announcement {
+    /// Checks whether the announcement should be shown as a host warning
+    pub fn is_important(&self) -> bool {
+        self.priority == "high" && !self.dismissed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuxiliarySensor, KlippyState, McuStats, PrinterFlags, PrinterState, ToolsStatus};
+
+    fn flags(set: &[&str]) -> PrinterFlags {
+        let mut value = serde_json::json!({
+            "operational": true,
+            "paused": false,
+            "printing": false,
+            "cancelling": false,
+            "pausing": false,
+            "error": false,
+            "ready": false,
+            "closedOrError": false
+        });
+        for name in set {
+            value[*name] = serde_json::json!(true);
         }
+        serde_json::from_value(value).expect("valid flags")
+    }
+
+    #[test]
+    fn get_status_follows_priority_order() {
+        assert_eq!(flags(&["cancelling", "error", "paused", "printing"]).get_status(), PrinterState::Cancelling);
+        assert_eq!(flags(&["error", "paused", "printing", "closedOrError"]).get_status(), PrinterState::Error);
+        assert_eq!(flags(&["paused", "printing"]).get_status(), PrinterState::Paused);
+        assert_eq!(flags(&["printing", "ready"]).get_status(), PrinterState::Printing);
+        assert_eq!(flags(&["ready"]).get_status(), PrinterState::Standby);
+        assert_eq!(flags(&[]).get_status(), PrinterState::Standby);
+    }
+
+    #[test]
+    fn pausing_is_still_printing() {
+        assert_eq!(flags(&["printing", "pausing"]).get_status(), PrinterState::Printing);
+    }
+
+    #[test]
+    fn printer_state_keeps_the_status_strings() {
+        assert_eq!(serde_json::to_value(PrinterState::Cancelling).unwrap(), "cancelling");
+        assert_eq!(serde_json::from_value::<PrinterState>(serde_json::json!("offline")).unwrap(), PrinterState::Offline);
+        assert!(PrinterState::Standby.ends_job(Some(PrinterState::Paused)));
+        assert!(!PrinterState::Paused.ends_job(Some(PrinterState::Printing)));
+        assert!(!PrinterState::Standby.ends_job(None));
+    }
+
+    #[test]
+    fn optional_flags_default_to_false() {
+        let parsed = flags(&[]);
+        assert!(!parsed.resuming);
+        assert!(!parsed.sd_ready);
+    }
+
+    #[test]
+    fn camel_case_flags_are_read() {
+        let parsed = flags(&["sdReady", "closedOrError"]);
+        assert!(parsed.sd_ready);
+        assert!(parsed.closed_or_error);
+    }
+
+    #[test]
+    fn klippy_state_parses_known_and_unknown_values() {
+        let parse = |s: &str| serde_json::from_value::<KlippyState>(serde_json::json!(s)).expect("valid state");
+        assert_eq!(parse("ready"), KlippyState::Ready);
+        assert_eq!(parse("shutdown"), KlippyState::Shutdown);
+        assert_eq!(parse("initializing"), KlippyState::Unknown);
+        assert_eq!(serde_json::to_value(KlippyState::Disconnected).unwrap(), "disconnected");
+    }
+
+    #[test]
+    fn tools_are_ordered_by_number_with_the_active_one() {
+        let status = serde_json::json!({
+            "extruder10": { "temperature": 24.0, "target": 0.0 },
+            "extruder1": { "temperature": 251.2, "target": 250.0, "power": 0.41 },
+            "extruder": { "temperature": 150.0, "target": 150.0 },
+            "extruder_stepper belt": { "pressure_advance": 0.0 },
+            "toolhead": { "extruder": "extruder1" },
+            "toolchanger": { "status": "ready", "tool": "tool T1", "tool_number": 1, "tool_names": ["tool T0", "tool T1"] }
+        });
+
+        let tools = ToolsStatus::from_status(&status);
+        let names: Vec<&str> = tools.tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["extruder", "extruder1", "extruder10"]);
+        assert_eq!(tools.active().map(|t| t.target), Some(250.0));
+        assert_eq!(tools.summary(), "T0 150/150°C · ▶T1 251/250°C · T10 24/0°C");
+        let changer = tools.toolchanger.expect("toolchanger");
+        assert_eq!((changer.tool.as_deref(), changer.tool_number), (Some("tool T1"), Some(1)));
+
+        let single = ToolsStatus::from_status(&serde_json::json!({ "extruder": { "temperature": 21.0, "target": 0.0 } }));
+        assert_eq!(single.tools.len(), 1);
+        assert!(single.active().is_none() && single.toolchanger.is_none());
+    }
+
+    #[test]
+    fn mcu_stats_are_read_with_clock_skew() {
+        let status = serde_json::json!({
+            "mcu EBBCan": {
+                "mcu_version": "v0.12.0-200",
+                "mcu_constants": { "CLOCK_FREQ": 64000000 },
+                "last_stats": { "freq": 64000640, "bytes_retransmit": 27, "bytes_invalid": 0, "srtt": 0.002 }
+            },
+            "mcu": { "mcu_version": "v0.12.0-200", "last_stats": { "bytes_write": 1200 } },
+            "mcu_temperature": { "temperature": 40.0 }
+        });
+
+        let mcus = McuStats::from_status(&status);
+        assert_eq!(mcus.len(), 2);
+        assert_eq!((mcus[0].name.as_str(), mcus[0].bytes_write, mcus[0].clock_skew_ppm()), ("mcu", 1200, None));
+        assert_eq!(mcus[1].bytes_retransmit, 27);
+        assert!(mcus[1].clock_skew_ppm().is_some_and(|ppm| (ppm - 10.0).abs() < 1e-6));
+    }
+
+    #[test]
+    fn bme280_sensor_is_merged_with_its_temperature_sensor() {
+        let status = serde_json::json!({
+            "temperature_sensor chamber": { "temperature": 41.5, "measured_min_temp": 20.0 },
+            "bme280 chamber": { "temperature": 41.4, "humidity": 18.2, "pressure": 1003.1 },
+            "temperature_sensor raspberry_pi": { "temperature": 52.0 },
+            "heater_bed": { "temperature": 60.0 }
+        });
+
+        let sensors = AuxiliarySensor::from_status(&status);
+        assert_eq!(sensors.len(), 2);
+        assert_eq!(sensors[0].name, "chamber");
+        assert_eq!(sensors[0].humidity, Some(18.2));
+        assert!(sensors[0].temperature.is_some());
+        assert_eq!(sensors[1].display_name(), "raspberry_pi");
     }
 }
@@ -0,0 +1,179 @@
+//! Telegram audit log data structures
+//!
+//! This module contains the record of control actions (emergency stop,
+//! cancel, firmware restart, toggles, Z offset) executed through the
+//! Telegram bot. The log is persisted in `telegram_audit.jsonl` next to the
+//! configuration file, one entry per line, and keeps the most recent entries
+//! only. Entries are appended under a lock, so concurrent actions do not
+//! overwrite each other's entries.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use crate::models::config::AppSettings;
+use crate::models::host::HostInfo;
+use crate::models::telegram::TelegramUser;
+
+/// Number of entries kept in the audit log
+pub const TELEGRAM_AUDIT_LOG_MAX_ENTRIES: usize = 1000;
+
+/// Number of lines in the audit log file; None until counted
+///
+/// Also serializes writes: the file is only appended to or rewritten while
+/// this is locked.
+static AUDIT_LOG_LINES: Mutex<Option<usize>> = Mutex::new(None);
+
+/// Control action executed by a Telegram user
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TelegramAuditEntry {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Telegram user ID
+    pub user_id: i64,
+    /// Display name of the user at the time of the action
+    pub user_name: String,
+    /// Host IP address
+    pub host: String,
+    /// Display name of the host
+    pub hostname: String,
+    /// Action name, e.g. "emergency_stop" or "z_offset -0.01"
+    pub action: String,
+    pub success: bool,
+    /// Failure reason
+    pub error: Option<String>,
+}
+
+impl TelegramAuditEntry {
+    /// Creates an entry for an action with its outcome
+    pub fn new<T, E: std::fmt::Display>(user: &TelegramUser, host: &HostInfo, action: &str, result: &Result<T, E>) -> Self {
+        Self {
+            timestamp: chrono::Utc::now(),
+            user_id: user.user_id,
+            user_name: user.display_name(),
+            host: host.ip_address.clone(),
+            hostname: host.hostname.clone(),
+            action: action.to_string(),
+            success: result.is_ok(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        }
+    }
+}
+
+/// Persisted audit log, oldest entry first
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TelegramAuditLog {
+    #[serde(default)]
+    pub entries: Vec<TelegramAuditEntry>,
+}
+
+impl TelegramAuditLog {
+    /// Get the audit log file path
+    pub fn audit_log_path() -> PathBuf {
+        AppSettings::config_path().with_file_name("telegram_audit.jsonl")
+    }
+
+    /// Load the audit log from file
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let _lines = AUDIT_LOG_LINES.lock().unwrap_or_else(|e| e.into_inner());
+        Self::load_from(&Self::audit_log_path())
+    }
+
+    /// Appends an entry to the persisted log, dropping the oldest ones past the limit
+    pub fn record(entry: TelegramAuditEntry) -> Result<(), Box<dyn std::error::Error>> {
+        let mut lines = AUDIT_LOG_LINES.lock().unwrap_or_else(|e| e.into_inner());
+        Self::append_to(&Self::audit_log_path(), &mut lines, &entry)
+    }
+
+    /// Reads the entries of a log file, skipping lines that cannot be parsed
+    fn load_from(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        let mut entries: Vec<TelegramAuditEntry> =
+            content.lines().filter_map(|line| serde_json::from_str(line).ok()).collect();
+        let excess = entries.len().saturating_sub(TELEGRAM_AUDIT_LOG_MAX_ENTRIES);
+        entries.drain(..excess);
+        Ok(Self { entries })
+    }
+
+    /// Appends an entry as one line
+    ///
+    /// Once the file holds twice the kept entries, it is rewritten with the
+    /// most recent ones, so the log is not rewritten on every action.
+    ///
+    /// # Arguments
+    /// * `path` - Log file
+    /// * `lines` - Number of lines in the file, counted on first use
+    /// * `entry` - Entry to append
+    fn append_to(path: &Path, lines: &mut Option<usize>, entry: &TelegramAuditEntry) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let count = match *lines {
+            Some(count) => count,
+            None => fs::read_to_string(path).map(|content| content.lines().count()).unwrap_or(0),
+        };
+
+        let mut file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        *lines = Some(count + 1);
+
+        if count + 1 > TELEGRAM_AUDIT_LOG_MAX_ENTRIES * 2 {
+            let log = Self::load_from(path)?;
+            let mut content = String::new();
+            for entry in &log.entries {
+                content.push_str(&serde_json::to_string(entry)?);
+                content.push('\n');
+            }
+            let temporary = path.with_extension("jsonl.tmp");
+            fs::write(&temporary, content)?;
+            fs::rename(&temporary, path)?;
+            *lines = Some(log.entries.len());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(action: &str) -> TelegramAuditEntry {
+        TelegramAuditEntry {
+            timestamp: chrono::Utc::now(),
+            user_id: 42,
+            user_name: "@operator".to_string(),
+            host: "192.168.1.50".to_string(),
+            hostname: "voron".to_string(),
+            action: action.to_string(),
+            success: true,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn entries_are_appended_and_trimmed_to_the_limit() {
+        let path = std::env::temp_dir().join(format!("mhs-telegram-audit-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut lines = None;
+        TelegramAuditLog::append_to(&path, &mut lines, &entry("emergency_stop")).unwrap();
+        fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(b"{\"torn\n").unwrap();
+        assert_eq!(TelegramAuditLog::load_from(&path).unwrap().entries.len(), 1);
+
+        let mut lines = None;
+        for i in 0..TELEGRAM_AUDIT_LOG_MAX_ENTRIES * 2 {
+            TelegramAuditLog::append_to(&path, &mut lines, &entry(&format!("z_offset {}", i))).unwrap();
+        }
+        let written = fs::read_to_string(&path).unwrap().lines().count();
+        assert_eq!(lines, Some(written));
+        assert!(written <= TELEGRAM_AUDIT_LOG_MAX_ENTRIES * 2);
+        let log = TelegramAuditLog::load_from(&path).unwrap();
+        assert_eq!(log.entries.len(), TELEGRAM_AUDIT_LOG_MAX_ENTRIES);
+        assert_eq!(log.entries.last().unwrap().action, format!("z_offset {}", TELEGRAM_AUDIT_LOG_MAX_ENTRIES * 2 - 1));
+
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,224 @@
+//! Resonance calibration models
+//!
+//! This module contains the state and results of remotely started input
+//! shaper calibrations, resonance tests and heater PID calibrations.
+
+use serde::{Deserialize, Serialize};
+
+/// Calibration command to run
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CalibrationKind {
+    /// `SHAPER_CALIBRATE`: measures resonances and recommends input shapers
+    ShaperCalibrate,
+    /// `TEST_RESONANCES`: only records raw resonance data
+    TestResonances,
+}
+
+impl CalibrationKind {
+    /// Klipper command name
+    pub fn command(&self) -> &'static str {
+        match self {
+            CalibrationKind::ShaperCalibrate => "SHAPER_CALIBRATE",
+            CalibrationKind::TestResonances => "TEST_RESONANCES",
+        }
+    }
+}
+
+/// State of a calibration run
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CalibrationStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Result file of a calibration, available through the Moonraker file API
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CalibrationFile {
+    /// Moonraker file root (e.g. "config")
+    pub root: String,
+    /// Path relative to the root
+    pub path: String,
+    /// File size in bytes
+    pub size: u64,
+    /// Modification time (Unix timestamp)
+    pub modified: f64,
+}
+
+/// Calibration run of a host
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CalibrationRun {
+    /// Host IP address
+    pub host: String,
+    /// Calibration command
+    pub kind: CalibrationKind,
+    /// Axis, if only one axis was tested
+    pub axis: Option<String>,
+    /// Current state
+    pub status: CalibrationStatus,
+    /// Start time (RFC 3339)
+    pub started_at: String,
+    /// End time (RFC 3339)
+    pub finished_at: Option<String>,
+    /// Console lines with the recommended shapers
+    #[serde(default)]
+    pub recommendations: Vec<String>,
+    /// Result files in the config root written since the run started (e.g. by Shake&Tune)
+    #[serde(default)]
+    pub files: Vec<CalibrationFile>,
+    /// CSV files Klipper reported writing on the printer; Klipper writes them
+    /// to /tmp, which Moonraker does not serve
+    #[serde(default)]
+    pub data_files: Vec<String>,
+    /// Failure reason
+    pub error: Option<String>,
+}
+
+impl CalibrationRun {
+    /// Takes the results from the console output of the calibration
+    ///
+    /// # Arguments
+    /// * `lines` - Console lines written while the calibration ran
+    pub fn read_console(&mut self, lines: &[String]) {
+        self.recommendations = lines
+            .iter()
+            .filter(|l| l.starts_with("Recommended shaper") || l.starts_with("Fitted shaper"))
+            .cloned()
+            .collect();
+        // e.g. "Shaper calibration data written to /tmp/calibration_data_x_20240101_120000.csv file"
+        self.data_files = lines
+            .iter()
+            .filter_map(|l| l.split_once(" written to ")?.1.strip_suffix(" file"))
+            .map(str::to_string)
+            .collect();
+    }
+}
+
+/// Times a heater reaches the target during `PID_CALIBRATE`: the first
+/// heat-up plus the oscillations Klipper measures (12 temperature peaks)
+pub const PID_CALIBRATION_CYCLES: u32 = 7;
+
+/// PID values found by `PID_CALIBRATE`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct PidValues {
+    pub kp: f64,
+    pub ki: f64,
+    pub kd: f64,
+}
+
+impl PidValues {
+    /// Reads the values from the console line Klipper writes at the end of the
+    /// calibration, e.g. "PID parameters: pid_Kp=22.865 pid_Ki=1.593 pid_Kd=82.038"
+    pub fn parse(line: &str) -> Option<Self> {
+        let value = |key: &str| {
+            line.split_whitespace()
+                .find_map(|word| word.strip_prefix(key))
+                .and_then(|v| v.parse().ok())
+        };
+        Some(Self {
+            kp: value("pid_Kp=")?,
+            ki: value("pid_Ki=")?,
+            kd: value("pid_Kd=")?,
+        })
+    }
+}
+
+/// PID calibration run of a heater
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PidCalibrationRun {
+    /// Host IP address
+    pub host: String,
+    /// Heater name as used by `PID_CALIBRATE`, e.g. "extruder" or "heater_bed"
+    pub heater: String,
+    /// Target temperature in °C
+    pub target: f64,
+    /// Current state
+    pub status: CalibrationStatus,
+    /// Start time (RFC 3339)
+    pub started_at: String,
+    /// End time (RFC 3339)
+    pub finished_at: Option<String>,
+    /// Latest heater temperature in °C
+    pub temperature: Option<f64>,
+    /// Times the heater reached the target so far
+    pub cycles: u32,
+    /// Estimated progress (0.0 - 1.0)
+    pub progress: f64,
+    /// Calibrated values, to be stored with `SAVE_CONFIG`
+    pub result: Option<PidValues>,
+    /// Failure reason
+    pub error: Option<String>,
+}
+
+impl PidCalibrationRun {
+    /// Records a temperature reading and updates the progress estimate
+    ///
+    /// The progress stays below 100 % until Klipper has finished, since the
+    /// number of oscillations is only approximate.
+    pub fn record_temperature(&mut self, temperature: f64) {
+        if self.temperature.is_some_and(|previous| previous < self.target) && temperature >= self.target {
+            self.cycles += 1;
+        }
+        self.temperature = Some(temperature);
+        self.progress = (self.cycles as f64 / PID_CALIBRATION_CYCLES as f64).min(0.95);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shaper_results_are_read_from_the_console() {
+        let mut run = CalibrationRun {
+            host: "192.168.1.50".to_string(),
+            kind: CalibrationKind::ShaperCalibrate,
+            axis: Some("X".to_string()),
+            status: CalibrationStatus::Completed,
+            started_at: String::new(),
+            finished_at: None,
+            recommendations: Vec::new(),
+            files: Vec::new(),
+            data_files: Vec::new(),
+            error: None,
+        };
+        let lines = [
+            "Wait for calibrations",
+            "Fitted shaper 'zv' frequency = 35.8 Hz (vibrations = 5.2%, smoothing ~= 0.058)",
+            "Recommended shaper is mzv @ 40.0 Hz",
+            "Shaper calibration data written to /tmp/calibration_data_x_20240101_120000.csv file",
+        ]
+        .map(str::to_string);
+        run.read_console(&lines);
+        assert_eq!(run.recommendations, lines[1..3]);
+        assert_eq!(run.data_files, ["/tmp/calibration_data_x_20240101_120000.csv"]);
+    }
+
+    #[test]
+    fn pid_values_are_parsed_and_cycles_counted() {
+        let values = PidValues::parse("PID parameters: pid_Kp=22.865 pid_Ki=1.593 pid_Kd=82.038").unwrap();
+        assert_eq!(values, PidValues { kp: 22.865, ki: 1.593, kd: 82.038 });
+        assert!(PidValues::parse("Recommended shaper is mzv @ 50.2 Hz").is_none());
+
+        let mut run = PidCalibrationRun {
+            host: "192.168.1.50".to_string(),
+            heater: "extruder".to_string(),
+            target: 210.0,
+            status: CalibrationStatus::Running,
+            started_at: String::new(),
+            finished_at: None,
+            temperature: None,
+            cycles: 0,
+            progress: 0.0,
+            result: None,
+            error: None,
+        };
+        for temperature in [25.0, 150.0, 211.0, 212.0, 204.0, 210.5] {
+            run.record_temperature(temperature);
+        }
+        assert_eq!(run.cycles, 2);
+        assert!(run.progress > 0.0 && run.progress < 1.0);
+    }
+}
@@ -0,0 +1,188 @@
+//! Printer configuration file models
+//!
+//! This module contains the configuration files of a printer as listed by
+//! Moonraker, line diffs between two versions of a file and the result of
+//! saving an edited file.
+
+use serde::{Deserialize, Serialize};
+
+/// Lines of unchanged context kept around each change
+pub const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Largest number of line pairs compared exactly; bigger rewrites are shown
+/// as removed and added blocks
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Configuration file in the `config` root
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfigFile {
+    /// Path relative to the root, e.g. "printer.cfg" or "macros/purge.cfg"
+    pub path: String,
+    /// File size in bytes
+    pub size: u64,
+    /// Modification time (Unix timestamp)
+    pub modified: f64,
+}
+
+/// Service that reads a configuration file and must restart to apply it
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigService {
+    Klipper,
+    Moonraker,
+}
+
+impl ConfigService {
+    /// Service reading a file: `moonraker.conf` belongs to Moonraker, every
+    /// other config to Klipper
+    pub fn for_path(path: &str) -> Self {
+        if path.rsplit('/').next() == Some("moonraker.conf") {
+            ConfigService::Moonraker
+        } else {
+            ConfigService::Klipper
+        }
+    }
+
+    /// Moonraker endpoint restarting the service
+    pub fn restart_endpoint(&self) -> &'static str {
+        match self {
+            ConfigService::Klipper => "printer/restart",
+            ConfigService::Moonraker => "server/restart",
+        }
+    }
+}
+
+/// Kind of a diff line
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffKind {
+    Context,
+    Removed,
+    Added,
+}
+
+/// Line of a diff between two versions of a file
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    /// Line number in the old version (1-based), None for added lines
+    pub old_line: Option<usize>,
+    /// Line number in the new version (1-based), None for removed lines
+    pub new_line: Option<usize>,
+    pub text: String,
+}
+
+/// Result of saving an edited configuration file
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfigSaveResult {
+    pub path: String,
+    /// Changes against the version that was on the printer
+    pub changes: Vec<DiffLine>,
+    /// Service to restart for the changes to apply, None when nothing changed
+    pub restart: Option<ConfigService>,
+}
+
+/// Compares two versions of a text file line by line
+///
+/// # Arguments
+/// * `old` - Previous content
+/// * `new` - New content
+/// * `context` - Unchanged lines kept around each change
+///
+/// # Returns
+/// * Changed lines with their context, empty when the contents are equal
+pub fn diff_lines(old: &str, new: &str, context: usize) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    // (kind, index in old, index in new)
+    let mut ops: Vec<(DiffKind, usize, usize)> = (0..prefix).map(|i| (DiffKind::Context, i, i)).collect();
+    if a.len() * b.len() <= MAX_DIFF_CELLS {
+        // Longest common subsequence of the changed middle part
+        let width = b.len() + 1;
+        let mut lcs = vec![0u32; (a.len() + 1) * width];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i * width + j] = if a[i] == b[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i] == b[j] {
+                ops.push((DiffKind::Context, prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if j == b.len() || (i < a.len() && lcs[(i + 1) * width + j] >= lcs[i * width + j + 1]) {
+                ops.push((DiffKind::Removed, prefix + i, prefix + j));
+                i += 1;
+            } else {
+                ops.push((DiffKind::Added, prefix + i, prefix + j));
+                j += 1;
+            }
+        }
+    } else {
+        ops.extend((0..a.len()).map(|i| (DiffKind::Removed, prefix + i, prefix)));
+        ops.extend((0..b.len()).map(|j| (DiffKind::Added, prefix + a.len(), prefix + j)));
+    }
+    ops.extend((0..suffix).map(|k| (DiffKind::Context, old.len() - suffix + k, new.len() - suffix + k)));
+
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (kind, _, _))| *kind != DiffKind::Context)
+        .map(|(index, _)| index)
+        .collect();
+    ops.iter()
+        .enumerate()
+        .filter(|(index, (kind, _, _))| {
+            *kind != DiffKind::Context || changed.iter().any(|c| c.abs_diff(*index) <= context)
+        })
+        .map(|(_, &(kind, i, j))| DiffLine {
+            kind,
+            old_line: (kind != DiffKind::Added).then_some(i + 1),
+            new_line: (kind != DiffKind::Removed).then_some(j + 1),
+            text: match kind {
+                DiffKind::Added => new[j].to_string(),
+                _ => old[i].to_string(),
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_shows_changed_lines_with_context() {
+        let old = "[extruder]\nrotation_distance: 22.6\nnozzle_diameter: 0.4\n\n[heater_bed]\nmax_temp: 110\n";
+        let new = "[extruder]\nrotation_distance: 22.9\nnozzle_diameter: 0.4\n\n[heater_bed]\nmax_temp: 110\n";
+
+        let diff = diff_lines(old, new, 1);
+        let summary: Vec<(DiffKind, Option<usize>, Option<usize>, &str)> =
+            diff.iter().map(|l| (l.kind, l.old_line, l.new_line, l.text.as_str())).collect();
+        assert_eq!(summary, vec![
+            (DiffKind::Context, Some(1), Some(1), "[extruder]"),
+            (DiffKind::Removed, Some(2), None, "rotation_distance: 22.6"),
+            (DiffKind::Added, None, Some(2), "rotation_distance: 22.9"),
+            (DiffKind::Context, Some(3), Some(3), "nozzle_diameter: 0.4"),
+        ]);
+        assert!(diff_lines(old, old, DIFF_CONTEXT_LINES).is_empty());
+        assert_eq!(ConfigService::for_path("moonraker.conf"), ConfigService::Moonraker);
+        assert_eq!(ConfigService::for_path("macros/moonraker.cfg"), ConfigService::Klipper);
+    }
+}
@@ -0,0 +1,33 @@
+//! Host diagnostics structures
+//!
+//! This module contains the consolidated list of problems Moonraker reports
+//! for a host (failed components, Klipper sections Moonraker needs, server
+//! warnings), each with a suggestion how to fix it.
+
+use serde::{Deserialize, Serialize};
+use crate::models::api::KlippyState;
+use crate::models::host::HostWarningKind;
+
+/// One problem reported by a host's Moonraker
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiagnosticIssue {
+    pub kind: HostWarningKind,
+    /// Component, Klipper section or warning text
+    pub name: String,
+    /// Localized suggestion how to fix the problem
+    pub remediation: String,
+}
+
+/// Problems of one host
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HostDiagnostics {
+    /// Host IP address
+    pub host: String,
+    /// Display name of the host
+    pub hostname: String,
+    /// Whether Moonraker answered; unreachable hosts have no issues listed
+    pub reachable: bool,
+    pub moonraker_version: Option<String>,
+    pub klippy_state: Option<KlippyState>,
+    pub issues: Vec<DiagnosticIssue>,
+}
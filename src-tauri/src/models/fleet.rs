@@ -0,0 +1,143 @@
+//! Printer farm overview structures
+//!
+//! This module contains the aggregate returned to the dashboard: status
+//! counts, running jobs, printers in error state and combined job history,
+//! and the forecast of when each printer is free for the next job.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::models::api::{JobTotals, KlippyState, PrinterState};
+
+/// Running or paused job of one printer
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FleetJob {
+    /// Host IP address
+    pub host: String,
+    /// Display name of the host
+    pub hostname: String,
+    /// G-code file being printed
+    pub filename: String,
+    /// Job state ("printing", "paused", ...)
+    pub status: String,
+    /// Progress percentage (0.0 - 100.0)
+    pub progress: f64,
+    /// Time spent printing in seconds
+    pub print_duration: f64,
+    /// Estimated remaining time in seconds
+    pub remaining_seconds: Option<f64>,
+    /// Estimated end as Unix timestamp
+    pub estimated_completion: Option<f64>,
+    /// Localized end time, e.g. "finishes at 14:30"
+    pub finish_time: Option<String>,
+}
+
+/// Printer in error state
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FleetError {
+    /// Host IP address
+    pub host: String,
+    /// Display name of the host
+    pub hostname: String,
+    pub klippy_state: Option<KlippyState>,
+    /// Error text reported by Klipper
+    pub message: Option<String>,
+}
+
+/// Aggregate state of all known printers
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FleetOverview {
+    /// Number of hosts included
+    pub total_hosts: usize,
+    /// Number of hosts per printer state
+    pub status_counts: BTreeMap<PrinterState, usize>,
+    /// Running and paused jobs, sorted by host name
+    pub jobs: Vec<FleetJob>,
+    /// Printers in error state, sorted by host name
+    pub errors: Vec<FleetError>,
+    /// Job history of all hosts combined
+    pub totals: JobTotals,
+    /// Number of hosts whose history could be read
+    pub history_hosts: usize,
+}
+
+/// When a printer is expected to be free for the next job
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HostAvailability {
+    /// Host IP address
+    pub host: String,
+    /// Display name of the host
+    pub hostname: String,
+    pub state: PrinterState,
+    /// Whether the printer can take a job now
+    pub available: bool,
+    /// Estimated seconds until the running job ends (None: free now, or no estimate)
+    pub remaining_seconds: Option<f64>,
+    /// Estimated end as Unix timestamp
+    pub available_at: Option<f64>,
+    /// Localized end time, e.g. "finishes at 14:30"
+    pub finish_time: Option<String>,
+}
+
+/// When the printers of the farm free up
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AvailabilityForecast {
+    /// Free printers first, then busy ones by their expected end; printers
+    /// without an estimate (errors, offline, maintenance) last
+    pub hosts: Vec<HostAvailability>,
+    /// Number of printers free now
+    pub available_now: usize,
+    /// Seconds until the next busy printer frees up
+    pub next_available_seconds: Option<f64>,
+}
+
+impl AvailabilityForecast {
+    /// Builds the forecast from the availability of each printer
+    pub fn new(mut hosts: Vec<HostAvailability>) -> Self {
+        let rank = |h: &HostAvailability| match (h.available, h.remaining_seconds) {
+            (true, _) => (0, 0.0),
+            (false, Some(remaining)) => (1, remaining),
+            (false, None) => (2, 0.0),
+        };
+        hosts.sort_by(|a, b| {
+            let (rank_a, rank_b) = (rank(a), rank(b));
+            rank_a.0.cmp(&rank_b.0).then(rank_a.1.total_cmp(&rank_b.1)).then_with(|| a.hostname.cmp(&b.hostname))
+        });
+        Self {
+            available_now: hosts.iter().filter(|h| h.available).count(),
+            next_available_seconds: hosts.iter().filter(|h| !h.available).find_map(|h| h.remaining_seconds),
+            hosts,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host(hostname: &str, state: PrinterState, remaining_seconds: Option<f64>) -> HostAvailability {
+        HostAvailability {
+            host: format!("192.168.1.{}", hostname.len()),
+            hostname: hostname.to_string(),
+            state,
+            available: state == PrinterState::Standby,
+            remaining_seconds,
+            available_at: None,
+            finish_time: None,
+        }
+    }
+
+    #[test]
+    fn forecast_lists_free_printers_then_soonest_finish() {
+        let forecast = AvailabilityForecast::new(vec![
+            host("Prusa", PrinterState::Error, None),
+            host("Voron", PrinterState::Printing, Some(7800.0)),
+            host("Ender", PrinterState::Printing, Some(600.0)),
+            host("Bambu", PrinterState::Standby, None),
+        ]);
+
+        let order: Vec<&str> = forecast.hosts.iter().map(|h| h.hostname.as_str()).collect();
+        assert_eq!(order, vec!["Bambu", "Ender", "Voron", "Prusa"]);
+        assert_eq!(forecast.available_now, 1);
+        assert_eq!(forecast.next_available_seconds, Some(600.0));
+    }
+}
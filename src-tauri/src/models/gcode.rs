@@ -0,0 +1,60 @@
+//! G-code file metadata models
+//!
+//! This module contains the metadata read from slicer comments in g-code
+//! files, used when Moonraker metadata is not available.
+
+use serde::{Deserialize, Serialize};
+
+/// Thumbnail image embedded in a g-code file
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GcodeThumbnail {
+    /// Image width in pixels
+    pub width: u32,
+    /// Image height in pixels
+    pub height: u32,
+    /// Image format ("png", "jpg" or "qoi")
+    pub format: String,
+    /// Base64 encoded image data
+    pub data: String,
+}
+
+/// Metadata parsed from slicer comments in a g-code file
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GcodeMetadata {
+    /// Slicer name and version
+    pub slicer: Option<String>,
+    /// Estimated print time in seconds
+    pub estimated_time: Option<f64>,
+    /// Filament length in mm
+    pub filament_length: Option<f64>,
+    /// Filament weight in grams
+    pub filament_weight: Option<f64>,
+    /// Filament type (PLA, PETG, ...)
+    pub filament_type: Option<String>,
+    /// Number of layers
+    pub layer_count: Option<u32>,
+    /// Layer height in mm
+    pub layer_height: Option<f64>,
+    /// Nozzle diameter in mm
+    pub nozzle_diameter: Option<f64>,
+    /// Embedded thumbnails, smallest first
+    #[serde(default)]
+    pub thumbnails: Vec<GcodeThumbnail>,
+}
+
+impl GcodeMetadata {
+    /// Checks whether no metadata was found
+    pub fn is_empty(&self) -> bool {
+        self.slicer.is_none()
+            && self.estimated_time.is_none()
+            && self.filament_length.is_none()
+            && self.filament_weight.is_none()
+            && self.layer_count.is_none()
+            && self.thumbnails.is_empty()
+    }
+
+    /// Returns the largest thumbnail
+    pub fn largest_thumbnail(&self) -> Option<&GcodeThumbnail> {
+        self.thumbnails.iter().max_by_key(|t| t.width * t.height)
+    }
+}
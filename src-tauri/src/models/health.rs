@@ -0,0 +1,154 @@
+//! Host health tracking structures
+//!
+//! This module contains data structures for tracking rolling reachability
+//! metrics per host, used to spot flaky printers (e.g. weak Wi-Fi).
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Number of most recent checks kept per host
+pub const HEALTH_WINDOW_SIZE: usize = 100;
+
+/// Latency at or below which a host gets the full latency score
+const HEALTH_GOOD_LATENCY_MS: f64 = 200.0;
+
+/// Latency at or above which a host gets no latency score
+const HEALTH_BAD_LATENCY_MS: f64 = 2000.0;
+
+/// Single status check sample
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HealthSample {
+    /// Check time
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Whether the host answered the API check
+    pub success: bool,
+    /// API round trip in milliseconds (successful checks only)
+    pub latency_ms: Option<u64>,
+}
+
+/// Rolling window of status checks for one host
+#[derive(Debug, Clone, Default)]
+pub struct HostHealthStats {
+    samples: VecDeque<HealthSample>,
+    consecutive_failures: u32,
+}
+
+/// Health summary returned to the frontend
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HostHealth {
+    /// Host IP address
+    pub host: String,
+    /// Number of checks in the window
+    pub total_checks: u32,
+    /// Number of successful checks in the window
+    pub successful_checks: u32,
+    /// Successful checks percentage (0.0 - 100.0)
+    pub success_rate: f64,
+    /// Average API latency of successful checks in milliseconds
+    pub average_latency_ms: Option<f64>,
+    /// Time-weighted online percentage over the window (0.0 - 100.0)
+    pub uptime_percentage: f64,
+    /// Failed checks in a row since the last success
+    pub consecutive_failures: u32,
+    /// Combined score (0 - 100), higher is healthier
+    pub health_score: u8,
+    /// Last check time (RFC 3339)
+    pub last_check: Option<String>,
+    /// Last successful check time (RFC 3339)
+    pub last_success: Option<String>,
+}
+
+impl HostHealthStats {
+    /// Creates an empty stats window
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a check result, dropping the oldest sample when the window is full
+    pub fn record(&mut self, success: bool, latency_ms: Option<u64>) {
+        if self.samples.len() >= HEALTH_WINDOW_SIZE {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(HealthSample {
+            timestamp: chrono::Utc::now(),
+            success,
+            latency_ms: if success { latency_ms } else { None },
+        });
+
+        if success {
+            self.consecutive_failures = 0;
+        } else {
+            self.consecutive_failures += 1;
+        }
+    }
+
+    /// Builds the health summary for the given host
+    pub fn summary(&self, host: &str) -> HostHealth {
+        let total_checks = self.samples.len() as u32;
+        let successful_checks = self.samples.iter().filter(|s| s.success).count() as u32;
+
+        let success_rate = if total_checks > 0 {
+            successful_checks as f64 / total_checks as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        let latencies: Vec<u64> = self.samples.iter().filter_map(|s| s.latency_ms).collect();
+        let average_latency_ms = if latencies.is_empty() {
+            None
+        } else {
+            Some(latencies.iter().sum::<u64>() as f64 / latencies.len() as f64)
+        };
+
+        let uptime_percentage = self.uptime_percentage(success_rate);
+
+        HostHealth {
+            host: host.to_string(),
+            total_checks,
+            successful_checks,
+            success_rate,
+            average_latency_ms,
+            uptime_percentage,
+            consecutive_failures: self.consecutive_failures,
+            health_score: Self::score(total_checks, success_rate, uptime_percentage, average_latency_ms),
+            last_check: self.samples.back().map(|s| s.timestamp.to_rfc3339()),
+            last_success: self.samples.iter().rev().find(|s| s.success).map(|s| s.timestamp.to_rfc3339()),
+        }
+    }
+
+    /// Time-weighted uptime: each interval counts as online if it started with a successful check
+    fn uptime_percentage(&self, fallback: f64) -> f64 {
+        let mut online_ms = 0i64;
+        let mut total_ms = 0i64;
+
+        for (prev, next) in self.samples.iter().zip(self.samples.iter().skip(1)) {
+            let span = (next.timestamp - prev.timestamp).num_milliseconds().max(0);
+            total_ms += span;
+            if prev.success {
+                online_ms += span;
+            }
+        }
+
+        if total_ms > 0 {
+            online_ms as f64 / total_ms as f64 * 100.0
+        } else {
+            fallback
+        }
+    }
+
+    /// Combines success rate, uptime and latency into a 0-100 score
+    fn score(total_checks: u32, success_rate: f64, uptime: f64, latency: Option<f64>) -> u8 {
+        if total_checks == 0 {
+            return 0;
+        }
+
+        let latency_score = match latency {
+            Some(ms) if ms <= HEALTH_GOOD_LATENCY_MS => 100.0,
+            Some(ms) if ms >= HEALTH_BAD_LATENCY_MS => 0.0,
+            Some(ms) => 100.0 * (HEALTH_BAD_LATENCY_MS - ms) / (HEALTH_BAD_LATENCY_MS - HEALTH_GOOD_LATENCY_MS),
+            None => 0.0,
+        };
+
+        (success_rate * 0.5 + uptime * 0.3 + latency_score * 0.2).round().clamp(0.0, 100.0) as u8
+    }
+}
@@ -1,11 +1,15 @@
 //! Host-related data structures
 
 use serde::{Deserialize, Serialize};
-use crate::models::api::PrinterFlags;
+use crate::models::api::{extruder_index, KlippyState, PrinterFlags, PrinterState, LED_OBJECT_PREFIXES, SENSOR_OBJECT_PREFIXES};
+use crate::models::config::{AppSettings, ScanProfile, MOONRAKER_PORT};
+use crate::network::ip_utils::split_host_port;
 
 /// Network host information
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct HostInfo {
+    /// Stable identity of the printer: Moonraker's instance UUID, else the
+    /// first MAC address, else the IP address
     pub id: String,
     pub hostname: String,
     pub original_hostname: String,
@@ -14,11 +18,397 @@ pub struct HostInfo {
     pub status: String,
     pub device_status: String,
     pub moonraker_version: Option<String>,
-    pub klippy_state: Option<String>,
-    pub printer_state: Option<String>,
+    pub klippy_state: Option<KlippyState>,
+    pub printer_state: Option<PrinterState>,
     pub printer_flags: Option<PrinterFlags>,
+    /// Error text reported by Klipper while the printer is in error state
+    #[serde(default)]
+    pub error_message: Option<String>,
     pub last_seen: Option<String>,
     pub failed_attempts: Option<u32>,
+    /// Planned downtime: offline/error notifications are suppressed
+    #[serde(default)]
+    pub maintenance: bool,
+    /// User-defined tags/groups, e.g. "petg" or "voron"
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Port of the Mainsail/Fluidd web interface found during the scan
+    #[serde(default)]
+    pub web_port: Option<u16>,
+    /// Printer model read from the Klipper configuration
+    #[serde(default)]
+    pub printer_model: Option<PrinterModel>,
+    /// Features found in the Klipper object list (None while Klippy was not ready)
+    #[serde(default)]
+    pub capabilities: Option<PrinterCapabilities>,
+    /// MAC addresses of the printer's network interfaces, used to recognize
+    /// one printer reachable under several addresses
+    #[serde(default)]
+    pub mac_addresses: Vec<String>,
+    /// Interface the printer is reached through, e.g. "eth0" or "wlan0"
+    #[serde(default)]
+    pub network_interface: Option<String>,
+    /// Other addresses the same printer answered on during the scan
+    #[serde(default)]
+    pub alternate_addresses: Vec<String>,
+    /// Moonraker warnings, failed components and important announcements
+    #[serde(default)]
+    pub warnings: Vec<HostWarning>,
+    /// Klipper has configuration changes (e.g. calibration results) not yet saved with SAVE_CONFIG
+    #[serde(default)]
+    pub save_config_pending: bool,
+}
+
+/// Where a host warning comes from
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum HostWarningKind {
+    /// Warning listed in `server/info`, e.g. a deprecated configuration option
+    Moonraker,
+    /// Moonraker component that failed to load
+    FailedComponent,
+    /// Klipper section Moonraker needs, e.g. `virtual_sdcard`
+    MissingKlippyRequirement,
+    /// High priority announcement, e.g. a required update
+    Announcement,
+}
+
+/// Warning reported by a host's Moonraker
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct HostWarning {
+    pub kind: HostWarningKind,
+    pub message: String,
+    /// Link with details (announcements only)
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// Network interface of a printer, from Moonraker's system information
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct NetworkInterface {
+    pub name: String,
+    pub mac_address: String,
+    pub ipv4_addresses: Vec<String>,
+}
+
+impl NetworkInterface {
+    /// Reads the interfaces from `system_info.network` of `/machine/system_info`
+    ///
+    /// Interfaces without a usable MAC address (loopback, tunnels) are left out.
+    pub fn from_system_info(network: &serde_json::Value) -> Vec<Self> {
+        let Some(interfaces) = network.as_object() else {
+            return Vec::new();
+        };
+        let mut found: Vec<Self> = interfaces
+            .iter()
+            .filter_map(|(name, interface)| {
+                let mac_address = interface.get("mac_address")?.as_str()?.to_lowercase();
+                if mac_address.is_empty() || mac_address == "00:00:00:00:00:00" {
+                    return None;
+                }
+                let ipv4_addresses = interface
+                    .get("ip_addresses")
+                    .and_then(|a| a.as_array())
+                    .map(|addresses| {
+                        addresses
+                            .iter()
+                            .filter(|a| a.get("family").and_then(|f| f.as_str()) == Some("ipv4"))
+                            .filter_map(|a| a.get("address").and_then(|a| a.as_str()).map(str::to_string))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Some(Self { name: name.clone(), mac_address, ipv4_addresses })
+            })
+            .collect();
+        found.sort_by(|a, b| a.name.cmp(&b.name));
+        found
+    }
+
+    /// Checks whether the interface is wireless, judging by its name (wlan0, wlp2s0)
+    pub fn is_wireless(name: &str) -> bool {
+        name.starts_with("wl")
+    }
+}
+
+/// What identifies a printer independently of its address
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MachineIdentity {
+    /// UUID Moonraker generates on its first start (Moonraker 0.8+)
+    pub instance_id: Option<String>,
+    pub interfaces: Vec<NetworkInterface>,
+}
+
+impl MachineIdentity {
+    /// Reads the identity from `system_info` of `/machine/system_info`
+    pub fn from_system_info(system_info: &serde_json::Value) -> Self {
+        Self {
+            instance_id: system_info
+                .pointer("/instance_ids/moonraker")
+                .and_then(|id| id.as_str())
+                .map(str::to_lowercase)
+                .filter(|id| !id.is_empty()),
+            interfaces: system_info
+                .get("network")
+                .map(NetworkInterface::from_system_info)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Id of the printer that survives DHCP lease changes
+    ///
+    /// The instance UUID is preferred; printers on older Moonraker versions
+    /// are identified by their first MAC address, and the IP address is
+    /// only used when neither is known. The MAC address is shared by the
+    /// Klipper instances of one machine, so it gets the Moonraker port of
+    /// additional instances appended.
+    pub fn host_id(&self, ip: &str) -> String {
+        let port = split_host_port(ip).1;
+        self.instance_id
+            .clone()
+            .or_else(|| {
+                self.interfaces.first().map(|i| match port {
+                    MOONRAKER_PORT => i.mac_address.clone(),
+                    port => format!("{}@{}", i.mac_address, port),
+                })
+            })
+            .unwrap_or_else(|| ip.to_string())
+    }
+}
+
+/// Features of a printer, derived from its Klipper object list
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct PrinterCapabilities {
+    /// Objects of a running print can be excluded (`[exclude_object]`)
+    pub exclude_object: bool,
+    /// The moonraker-timelapse macros are installed
+    pub timelapse: bool,
+    /// Number of extruders (`extruder`, `extruder1`, ...)
+    pub extruders: u32,
+    /// Tools are changed by klipper-toolchanger (`toolchanger`)
+    #[serde(default)]
+    pub toolchanger: bool,
+    pub heater_bed: bool,
+    /// Part cooling fan (`[fan]`)
+    pub part_fan: bool,
+    /// Object of the first configured LED, e.g. "neopixel caselight"
+    pub led: Option<String>,
+    /// Additional temperature or humidity sensors are configured
+    pub sensors: bool,
+}
+
+impl PrinterCapabilities {
+    /// Derives the capabilities from the names of `printer/objects/list`
+    pub fn from_objects(objects: &[String]) -> Self {
+        let has = |name: &str| objects.iter().any(|o| o == name);
+        Self {
+            exclude_object: has("exclude_object"),
+            timelapse: objects.iter().any(|o| o.eq_ignore_ascii_case("gcode_macro TIMELAPSE_TAKE_FRAME")),
+            extruders: objects.iter().filter(|o| extruder_index(o).is_some()).count() as u32,
+            toolchanger: has("toolchanger"),
+            heater_bed: has("heater_bed"),
+            part_fan: has("fan"),
+            led: objects
+                .iter()
+                .find(|o| LED_OBJECT_PREFIXES.iter().any(|prefix| o.starts_with(prefix)))
+                .cloned(),
+            sensors: objects.iter().any(|o| SENSOR_OBJECT_PREFIXES.iter().any(|prefix| o.starts_with(prefix))),
+        }
+    }
+}
+
+/// Printer model information read from the Klipper configuration
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct PrinterModel {
+    /// Model name from a `# model: ...` comment in printer.cfg
+    pub name: Option<String>,
+    /// Kinematics of the `[printer]` section, e.g. "corexy"
+    pub kinematics: Option<String>,
+    /// Build volume (X, Y, Z) in mm from the steppers' position_max
+    pub build_volume: Option<[f64; 3]>,
+}
+
+impl PrinterModel {
+    /// Reads the kinematics and build volume from `configfile.settings`
+    pub fn from_config_settings(settings: &serde_json::Value) -> Self {
+        let position_max = |stepper: &str| {
+            settings.get(stepper).and_then(|s| s.get("position_max")).and_then(|v| v.as_f64())
+        };
+        let build_volume = match (position_max("stepper_x"), position_max("stepper_y"), position_max("stepper_z")) {
+            (Some(x), Some(y), Some(z)) => Some([x, y, z]),
+            _ => None,
+        };
+
+        Self {
+            name: None,
+            kinematics: settings
+                .pointer("/printer/kinematics")
+                .and_then(|k| k.as_str())
+                .map(str::to_string),
+            build_volume,
+        }
+    }
+
+    /// Finds a model name comment such as `# model: Voron 2.4 350` in a config file
+    pub fn parse_name(config: &str) -> Option<String> {
+        config.lines().find_map(|line| {
+            let comment = line.trim().strip_prefix('#')?.trim();
+            let (key, value) = comment.split_once(':')?;
+            let key = key.trim().to_lowercase();
+            let value = value.trim();
+            (matches!(key.as_str(), "model" | "printer_model" | "printer model") && !value.is_empty())
+                .then(|| value.to_string())
+        })
+    }
+
+    /// Gets a short label, e.g. "Voron 2.4 350" or "CoreXY 350×350×340"
+    pub fn display_name(&self) -> Option<String> {
+        if let Some(name) = &self.name {
+            return Some(name.clone());
+        }
+
+        let kinematics = match self.kinematics.as_deref()? {
+            "corexy" => "CoreXY".to_string(),
+            "corexz" => "CoreXZ".to_string(),
+            "hybrid_corexy" => "Hybrid CoreXY".to_string(),
+            "hybrid_corexz" => "Hybrid CoreXZ".to_string(),
+            "cartesian" => "Cartesian".to_string(),
+            "delta" => "Delta".to_string(),
+            other => other.to_string(),
+        };
+        Some(match self.build_volume {
+            Some([x, y, z]) => format!("{} {:.0}×{:.0}×{:.0}", kinematics, x, y, z),
+            None => kinematics,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PrinterModel;
+
+    #[test]
+    fn model_name_comes_from_config_comment() {
+        let config = "# This file contains common pin mappings\n#  Model:  Voron 2.4 350 \n[printer]\nkinematics: corexy\n";
+        assert_eq!(PrinterModel::parse_name(config).as_deref(), Some("Voron 2.4 350"));
+        assert_eq!(PrinterModel::parse_name("[printer]\nkinematics: corexy"), None);
+    }
+
+    #[test]
+    fn network_interfaces_are_read_from_system_info() {
+        let network = serde_json::json!({
+            "wlan0": {
+                "mac_address": "B8:27:EB:00:00:02",
+                "ip_addresses": [
+                    { "family": "ipv4", "address": "192.168.1.51", "is_link_local": false },
+                    { "family": "ipv6", "address": "fe80::1", "is_link_local": true }
+                ]
+            },
+            "lo": { "mac_address": "00:00:00:00:00:00", "ip_addresses": [] }
+        });
+
+        let interfaces = super::NetworkInterface::from_system_info(&network);
+        assert_eq!(interfaces.len(), 1);
+        assert_eq!(interfaces[0].mac_address, "b8:27:eb:00:00:02");
+        assert_eq!(interfaces[0].ipv4_addresses, vec!["192.168.1.51"]);
+    }
+
+    #[test]
+    fn host_id_prefers_the_instance_uuid_over_mac_addresses() {
+        let system_info = serde_json::json!({
+            "instance_ids": { "moonraker": "9E0E6C0B-1B4C-4F4C-A2C3-5A4F1E2D3C4B", "klipper": "" },
+            "network": { "eth0": { "mac_address": "dc:a6:32:00:00:01", "ip_addresses": [] } }
+        });
+        let identity = super::MachineIdentity::from_system_info(&system_info);
+        assert_eq!(identity.host_id("192.168.1.50"), "9e0e6c0b-1b4c-4f4c-a2c3-5a4f1e2d3c4b");
+
+        let older = super::MachineIdentity { instance_id: None, ..identity };
+        assert_eq!(older.host_id("192.168.1.50"), "dc:a6:32:00:00:01");
+        assert_eq!(older.host_id("192.168.1.50:7126"), "dc:a6:32:00:00:01@7126");
+        assert_eq!(super::MachineIdentity::default().host_id("192.168.1.50"), "192.168.1.50");
+    }
+
+    #[test]
+    fn capabilities_follow_the_object_list() {
+        let objects: Vec<String> = [
+            "webhooks", "extruder", "extruder1", "extruder_stepper belt", "heater_bed", "fan",
+            "neopixel caselight", "exclude_object", "gcode_macro TIMELAPSE_TAKE_FRAME", "temperature_sensor chamber",
+        ]
+        .iter()
+        .map(|o| o.to_string())
+        .collect();
+        let capabilities = super::PrinterCapabilities::from_objects(&objects);
+        assert_eq!(capabilities.extruders, 2);
+        assert!(capabilities.exclude_object && capabilities.timelapse && capabilities.part_fan && capabilities.sensors);
+        assert_eq!(capabilities.led.as_deref(), Some("neopixel caselight"));
+
+        let bare = super::PrinterCapabilities::from_objects(&["extruder".to_string()]);
+        assert!(!bare.exclude_object && !bare.timelapse && !bare.heater_bed && bare.led.is_none());
+    }
+
+    #[test]
+    fn display_name_falls_back_to_kinematics_and_volume() {
+        let settings = serde_json::json!({
+            "printer": { "kinematics": "corexy" },
+            "stepper_x": { "position_max": 350.0 },
+            "stepper_y": { "position_max": 350.0 },
+            "stepper_z": { "position_max": 340.0 }
+        });
+        let model = PrinterModel::from_config_settings(&settings);
+        assert_eq!(model.display_name().as_deref(), Some("CoreXY 350×350×340"));
+    }
+}
+
+impl HostInfo {
+    /// Applies the per-host settings saved by the user (custom name, maintenance mode, tags)
+    pub fn apply_user_settings(&mut self, settings: &AppSettings) {
+        if let Some(name) = settings.host_names.get(&self.ip_address) {
+            self.hostname = name.clone();
+        }
+        self.maintenance = settings.maintenance_hosts.contains(&self.ip_address);
+        self.tags = settings.host_tags.get(&self.ip_address).cloned().unwrap_or_default();
+    }
+
+    /// Copies a fresh status check into the host
+    pub fn apply_status(&mut self, status: &HostStatusResponse) {
+        self.status = status.status.clone();
+        self.device_status = status.device_status.clone().unwrap_or_else(|| status.status.clone());
+        if status.moonraker_version.is_some() {
+            self.moonraker_version = status.moonraker_version.clone();
+        }
+        self.klippy_state = status.klippy_state;
+        self.printer_state = status.printer_state;
+        self.printer_flags = status.printer_flags.clone();
+        self.error_message = status.error_message.clone();
+        if status.success {
+            self.warnings = status.warnings.clone();
+            self.save_config_pending = status.save_config_pending;
+            self.last_seen = Some(chrono::Utc::now().to_rfc3339());
+            self.failed_attempts = Some(0);
+        } else {
+            self.failed_attempts = Some(self.failed_attempts.unwrap_or(0) + 1);
+        }
+    }
+
+    /// Determines the state to show for the host from everything known about it
+    /// 
+    /// Priority order: offline > cancelling > error > paused > printing > standby.
+    /// Without state flags, a Klippy error still shows as error.
+    pub fn current_state(&self) -> PrinterState {
+        if self.status == "offline" || self.klippy_state == Some(KlippyState::Disconnected) {
+            return PrinterState::Offline;
+        }
+        match &self.printer_flags {
+            Some(flags) => PrinterState::from_flags(flags),
+            None if self.device_status == "offline" || self.device_status == "klippy_disconnected" => PrinterState::Offline,
+            None if self.klippy_state == Some(KlippyState::Error) => PrinterState::Error,
+            None => PrinterState::Standby,
+        }
+    }
+
+    /// Checks whether the host carries every given tag (case-insensitive)
+    pub fn has_tags(&self, required: &[String]) -> bool {
+        required.iter().all(|tag| self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+    }
 }
 
 /// Host status response
@@ -28,9 +418,25 @@ pub struct HostStatusResponse {
     pub status: String,
     pub device_status: Option<String>,
     pub moonraker_version: Option<String>,
-    pub klippy_state: Option<String>,
-    pub printer_state: Option<String>,
+    pub klippy_state: Option<KlippyState>,
+    pub printer_state: Option<PrinterState>,
     pub printer_flags: Option<PrinterFlags>,
+    /// Error text reported by Klipper, e.g. "Heater extruder not heating at expected rate"
+    #[serde(default)]
+    pub error_message: Option<String>,
+    /// Moonraker warnings, failed components and important announcements
+    #[serde(default)]
+    pub warnings: Vec<HostWarning>,
+    /// Klipper has configuration changes not yet saved with SAVE_CONFIG
+    #[serde(default)]
+    pub save_config_pending: bool,
+}
+
+impl HostStatusResponse {
+    /// Gets the printer state, offline when the check failed without one
+    pub fn state(&self) -> PrinterState {
+        self.printer_state.unwrap_or(if self.success { PrinterState::Standby } else { PrinterState::Offline })
+    }
 }
 
 /// Subnet configuration for scanning
@@ -39,6 +445,9 @@ pub struct SubnetConfig {
     pub name: String,
     pub range: String,
     pub enabled: bool,
+    /// LAN or overlay network (Tailscale, WireGuard)
+    #[serde(default)]
+    pub profile: ScanProfile,
 }
 
 /// Network scan result
@@ -48,4 +457,23 @@ pub struct ScanResult {
     pub total_scanned: u32,
     pub hosts_found: u32,
     pub scan_duration_ms: u64,
+    /// Subnets whose scan stopped before every address was probed
+    #[serde(default)]
+    pub stopped_early: Vec<String>,
+    /// Whether the scan ran out of its time budget
+    #[serde(default)]
+    pub time_budget_exceeded: bool,
+    /// Known printers found under a new address
+    #[serde(default)]
+    pub relinked: Vec<HostRelink>,
+}
+
+/// Known printer that came back under another address, e.g. after its DHCP
+/// lease changed
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HostRelink {
+    pub id: String,
+    pub hostname: String,
+    pub previous_address: String,
+    pub address: String,
 }
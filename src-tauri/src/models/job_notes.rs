@@ -0,0 +1,131 @@
+//! Job note data structures
+//!
+//! This module contains free-text notes and labels users attach to print
+//! jobs, e.g. "test print with new filament". Jobs are identified by the
+//! host and the job id of the Moonraker history. The notes are persisted in
+//! `job_notes.json` next to the configuration file.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use crate::models::config::AppSettings;
+
+/// Note and labels of one print job
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct JobNote {
+    /// Host IP address
+    pub host: String,
+    /// Job id of the Moonraker history
+    pub job_id: String,
+    /// Printed file, for display when the history entry is gone
+    pub filename: Option<String>,
+    #[serde(default)]
+    pub note: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Persisted job notes
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct JobNotes {
+    #[serde(default)]
+    pub notes: Vec<JobNote>,
+}
+
+impl JobNotes {
+    /// Get the job notes file path
+    pub fn notes_path() -> PathBuf {
+        AppSettings::config_path().with_file_name("job_notes.json")
+    }
+
+    /// Load the notes from file
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Self::notes_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Save the notes to file
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::notes_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Gets the note of a job
+    pub fn get(&self, host: &str, job_id: &str) -> Option<&JobNote> {
+        self.notes.iter().find(|n| n.host == host && n.job_id == job_id)
+    }
+
+    /// Lists the notes of a host, or of every host, most recently edited first
+    pub fn list(&self, host: Option<&str>) -> Vec<JobNote> {
+        let mut notes: Vec<JobNote> = self
+            .notes
+            .iter()
+            .filter(|n| host.is_none_or(|host| n.host == host))
+            .cloned()
+            .collect();
+        notes.sort_by_key(|n| std::cmp::Reverse(n.updated_at));
+        notes
+    }
+
+    /// Sets the note and labels of a job
+    ///
+    /// Labels are trimmed and deduplicated. A job left without note and
+    /// labels has its entry removed.
+    ///
+    /// # Returns
+    /// * The stored note, None when it was removed
+    pub fn set(&mut self, host: &str, job_id: &str, filename: Option<String>, note: &str, labels: &[String]) -> Option<JobNote> {
+        self.notes.retain(|n| !(n.host == host && n.job_id == job_id));
+
+        let mut unique: Vec<String> = Vec::new();
+        for label in labels.iter().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+            if !unique.iter().any(|u| u.eq_ignore_ascii_case(label)) {
+                unique.push(label.to_string());
+            }
+        }
+        let note = note.trim();
+        if note.is_empty() && unique.is_empty() {
+            return None;
+        }
+
+        let entry = JobNote {
+            host: host.to_string(),
+            job_id: job_id.to_string(),
+            filename,
+            note: note.to_string(),
+            labels: unique,
+            updated_at: chrono::Utc::now(),
+        };
+        self.notes.push(entry.clone());
+        Some(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_replace_each_other_and_empty_ones_are_removed() {
+        let mut notes = JobNotes::default();
+        let labels = vec!["PETG ".to_string(), "petg".to_string(), "new spool".to_string()];
+        notes.set("192.168.1.50", "00002A", None, "first try", &[]);
+        let stored = notes.set("192.168.1.50", "00002A", None, " test print ", &labels).unwrap();
+
+        assert_eq!(stored.note, "test print");
+        assert_eq!(stored.labels, vec!["PETG", "new spool"]);
+        assert_eq!(notes.list(None).len(), 1);
+
+        assert!(notes.set("192.168.1.50", "00002A", None, "", &[]).is_none());
+        assert!(notes.get("192.168.1.50", "00002A").is_none());
+    }
+}
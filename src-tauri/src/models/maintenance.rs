@@ -0,0 +1,154 @@
+//! Maintenance schedule data structures
+//!
+//! This module contains data structures for print-hour based maintenance
+//! reminders (e.g. "lube Z every 200 h") and the per-host maintenance log.
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of log entries kept per host
+pub const MAINTENANCE_LOG_LIMIT: usize = 200;
+
+/// Recurring maintenance task
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MaintenanceTask {
+    /// Unique task identifier
+    pub id: String,
+    /// Task description, e.g. "Lube Z rods"
+    pub name: String,
+    /// Print hours between two services
+    pub interval_hours: f64,
+    /// Print hours at which the task was last done
+    pub last_done_hours: f64,
+    /// Whether a reminder was already sent for the current interval
+    #[serde(default)]
+    pub reminded: bool,
+}
+
+/// Entry of the maintenance log
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MaintenanceLogEntry {
+    /// Completed task (None for free-form entries)
+    pub task_id: Option<String>,
+    /// Task name at the time of the entry
+    pub task_name: String,
+    /// Print hours at the time of the entry
+    pub print_hours: f64,
+    /// Entry time (RFC 3339)
+    pub timestamp: String,
+    /// Optional user note
+    pub note: Option<String>,
+}
+
+/// Maintenance schedule and log of one host
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HostMaintenance {
+    /// Last known cumulative print hours
+    #[serde(default)]
+    pub print_hours: f64,
+    /// Recurring tasks
+    #[serde(default)]
+    pub tasks: Vec<MaintenanceTask>,
+    /// Completed maintenance, newest last
+    #[serde(default)]
+    pub log: Vec<MaintenanceLogEntry>,
+}
+
+/// Reminder for a task that is due
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MaintenanceReminder {
+    /// Host IP address
+    pub host: String,
+    /// Task identifier
+    pub task_id: String,
+    /// Task name
+    pub task_name: String,
+    /// Current print hours
+    pub print_hours: f64,
+    /// Print hours past the due point
+    pub overdue_hours: f64,
+}
+
+impl MaintenanceTask {
+    /// Print hours left until the task is due (negative when overdue)
+    pub fn hours_until_due(&self, print_hours: f64) -> f64 {
+        self.last_done_hours + self.interval_hours - print_hours
+    }
+}
+
+impl HostMaintenance {
+    /// Adds a task that becomes due one interval from the current print hours
+    pub fn add_task(&mut self, name: String, interval_hours: f64) -> MaintenanceTask {
+        let task = MaintenanceTask {
+            id: format!("{:016x}", rand::random::<u64>()),
+            name,
+            interval_hours,
+            last_done_hours: self.print_hours,
+            reminded: false,
+        };
+        self.tasks.push(task.clone());
+        task
+    }
+
+    /// Updates the print hours and collects reminders for newly due tasks
+    ///
+    /// Each task is reminded once per interval; completing it re-arms the reminder.
+    pub fn update_print_hours(&mut self, host: &str, print_hours: f64) -> Vec<MaintenanceReminder> {
+        self.print_hours = print_hours;
+
+        let mut reminders = Vec::new();
+        for task in self.tasks.iter_mut() {
+            let remaining = task.hours_until_due(print_hours);
+            if remaining <= 0.0 && !task.reminded {
+                task.reminded = true;
+                reminders.push(MaintenanceReminder {
+                    host: host.to_string(),
+                    task_id: task.id.clone(),
+                    task_name: task.name.clone(),
+                    print_hours,
+                    overdue_hours: -remaining,
+                });
+            }
+        }
+        reminders
+    }
+
+    /// Marks a task as done at the current print hours and logs it
+    pub fn complete_task(&mut self, task_id: &str, note: Option<String>) -> Result<(), String> {
+        let print_hours = self.print_hours;
+        let task = self.tasks.iter_mut()
+            .find(|t| t.id == task_id)
+            .ok_or_else(|| "Maintenance task not found".to_string())?;
+
+        task.last_done_hours = print_hours;
+        task.reminded = false;
+        let entry = MaintenanceLogEntry {
+            task_id: Some(task.id.clone()),
+            task_name: task.name.clone(),
+            print_hours,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            note,
+        };
+        self.push_log(entry);
+        Ok(())
+    }
+
+    /// Adds a free-form log entry
+    pub fn add_note(&mut self, title: String, note: Option<String>) {
+        let entry = MaintenanceLogEntry {
+            task_id: None,
+            task_name: title,
+            print_hours: self.print_hours,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            note,
+        };
+        self.push_log(entry);
+    }
+
+    fn push_log(&mut self, entry: MaintenanceLogEntry) {
+        self.log.push(entry);
+        if self.log.len() > MAINTENANCE_LOG_LIMIT {
+            let excess = self.log.len() - MAINTENANCE_LOG_LIMIT;
+            self.log.drain(..excess);
+        }
+    }
+}
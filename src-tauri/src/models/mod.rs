@@ -9,6 +9,21 @@ pub mod config;
 pub mod scan_progress;
 pub mod print_info;
 pub mod telegram;
+pub mod health;
+pub mod maintenance;
+pub mod queue;
+pub mod gcode;
+pub mod calibration;
+pub mod fleet;
+pub mod schedule;
+pub mod audit;
+pub mod network;
+pub mod diagnostics;
+pub mod job_notes;
+pub mod config_file;
+pub mod telemetry;
+pub mod rules;
+pub mod quick_actions;
 
 pub use api::*;
 pub use host::*;
@@ -16,3 +31,18 @@ pub use config::*;
 pub use scan_progress::*;
 pub use print_info::*;
 pub use telegram::*;
+pub use health::*;
+pub use maintenance::*;
+pub use queue::*;
+pub use gcode::*;
+pub use calibration::*;
+pub use fleet::*;
+pub use schedule::*;
+pub use audit::*;
+pub use network::*;
+pub use diagnostics::*;
+pub use job_notes::*;
+pub use config_file::*;
+pub use telemetry::*;
+pub use rules::*;
+pub use quick_actions::*;
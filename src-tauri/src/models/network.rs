@@ -0,0 +1,28 @@
+//! Local network data structures
+//!
+//! This module contains the networks the machine is connected to and the
+//! changes between them, used to notice when the machine moves to another
+//! network (e.g. from the workshop Wi-Fi to the office LAN).
+
+use serde::{Deserialize, Serialize};
+
+/// IPv4 network the machine has an address in
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct LocalNetwork {
+    /// Interface name, e.g. "wlan0" or "Ethernet"
+    pub interface: String,
+    /// Address of the machine in the network
+    pub address: String,
+    /// Network in CIDR notation, e.g. "192.168.1.0/24"
+    pub subnet: String,
+}
+
+/// Networks joined and left since the previous check
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NetworkChange {
+    pub detected_at: chrono::DateTime<chrono::Utc>,
+    /// Networks the machine is newly connected to
+    pub joined: Vec<LocalNetwork>,
+    /// Networks the machine is no longer connected to
+    pub left: Vec<LocalNetwork>,
+}
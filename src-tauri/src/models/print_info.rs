@@ -4,6 +4,7 @@
 //! and detailed information about current printing tasks.
 
 use serde::{Deserialize, Serialize};
+use crate::models::api::ToolsStatus;
 
 /// Print job progress information
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -24,6 +25,52 @@ pub struct PrintProgress {
     pub total_height: Option<f64>,
 }
 
+impl PrintProgress {
+    /// Estimates the remaining print time from the file progress so far
+    pub fn remaining_seconds(&self) -> Option<f64> {
+        if self.progress <= 0.0 || self.progress >= 100.0 || self.print_duration <= 0.0 {
+            return None;
+        }
+        Some(self.print_duration * (100.0 - self.progress) / self.progress)
+    }
+}
+
+/// Estimated cost of a print job
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JobCost {
+    /// Filament weight in grams
+    pub filament_grams: f64,
+    /// Filament cost
+    pub filament_cost: f64,
+    /// Energy used in kWh
+    pub energy_kwh: f64,
+    /// Electricity cost
+    pub energy_cost: f64,
+    /// Filament plus electricity cost
+    pub total_cost: f64,
+    /// Currency label
+    pub currency: String,
+}
+
+impl JobCost {
+    /// Short human readable summary, e.g. "1.23 EUR (45 g, 0.30 kWh)"
+    pub fn summary(&self) -> String {
+        format!(
+            "{:.2} {} ({:.0} g, {:.2} kWh)",
+            self.total_cost, self.currency, self.filament_grams, self.energy_kwh
+        )
+    }
+}
+
+/// Lifetime statistics of a printer from its job history
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HistoryStats {
+    /// Job history totals
+    pub totals: crate::models::api::JobTotals,
+    /// Estimated cost of all jobs (None if no prices are configured)
+    pub estimated_cost: Option<JobCost>,
+}
+
 /// Print job information
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PrintJobInfo {
@@ -39,6 +86,15 @@ pub struct PrintJobInfo {
     pub estimated_completion: Option<f64>,
     /// Print status (printing, paused, completed, etc.)
     pub status: String,
+    /// Extruded filament length in mm
+    #[serde(default)]
+    pub filament_used: Option<f64>,
+    /// Estimated job cost so far (None if no prices are configured)
+    #[serde(default)]
+    pub estimated_cost: Option<JobCost>,
+    /// Hotend temperatures of all tools and the active tool
+    #[serde(default)]
+    pub tools: ToolsStatus,
 }
 
 /// Moonraker printer objects query response
@@ -102,8 +158,8 @@ pub struct PrintInfo {
 /// Virtual SD card information
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct VirtualSDCard {
-    /// File path
-    pub file_path: String,
+    /// File path (null when no file is loaded)
+    pub file_path: Option<String>,
     /// Progress percentage (0.0 - 1.0)
     pub progress: f64,
     /// Is SD card ready
@@ -0,0 +1,141 @@
+//! Print queue data structures
+//!
+//! This module contains the MHS-level print queue: g-code files waiting to be
+//! sent to the next available compatible printer. The queue is persisted in
+//! `queue.json` next to the configuration file.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use crate::models::config::AppSettings;
+use crate::models::gcode::GcodeMetadata;
+
+/// State of a queued job
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum QueueJobStatus {
+    /// Waiting for a compatible idle printer
+    Pending,
+    /// File is being uploaded
+    Uploading,
+    /// Print was started on the assigned host
+    Printing,
+    /// Print finished successfully
+    Completed,
+    /// Upload, start or print failed
+    Failed,
+}
+
+/// Job in the print queue
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QueueJob {
+    /// Unique job identifier
+    pub id: String,
+    /// Local path of the g-code file
+    pub file_path: String,
+    /// File name used on the printer
+    pub file_name: String,
+    /// Tags a printer must carry to take the job (empty: any printer)
+    #[serde(default)]
+    pub required_tags: Vec<String>,
+    /// Current state
+    pub status: QueueJobStatus,
+    /// Host the job was sent to
+    pub assigned_host: Option<String>,
+    /// Time the job was queued (RFC 3339)
+    pub created_at: String,
+    /// Time the print was started (RFC 3339)
+    pub started_at: Option<String>,
+    /// Time the job finished or failed (RFC 3339)
+    pub finished_at: Option<String>,
+    /// Failure reason
+    pub error: Option<String>,
+    /// Slicer metadata read from the file when it was queued
+    #[serde(default)]
+    pub metadata: Option<GcodeMetadata>,
+}
+
+/// Persisted print queue
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PrintQueue {
+    /// Jobs in queue order
+    #[serde(default)]
+    pub jobs: Vec<QueueJob>,
+    /// Hosts whose bed the user confirmed as empty since their last job
+    #[serde(default)]
+    pub cleared_hosts: HashSet<String>,
+}
+
+impl QueueJob {
+    /// Checks whether the job no longer needs the scheduler
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status, QueueJobStatus::Completed | QueueJobStatus::Failed)
+    }
+
+    /// Marks the job as finished with the given state
+    pub fn finish(&mut self, status: QueueJobStatus, error: Option<String>) {
+        self.status = status;
+        self.error = error;
+        self.finished_at = Some(chrono::Utc::now().to_rfc3339());
+    }
+}
+
+impl PrintQueue {
+    /// Get the queue file path
+    pub fn queue_path() -> PathBuf {
+        AppSettings::config_path().with_file_name("queue.json")
+    }
+
+    /// Load the queue from file
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Self::queue_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Save the queue to file
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::queue_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Appends a pending job
+    pub fn enqueue(
+        &mut self,
+        file_path: String,
+        file_name: String,
+        required_tags: Vec<String>,
+        metadata: Option<GcodeMetadata>,
+    ) -> QueueJob {
+        let job = QueueJob {
+            id: format!("{:016x}", rand::random::<u64>()),
+            file_path,
+            file_name,
+            required_tags,
+            status: QueueJobStatus::Pending,
+            assigned_host: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            started_at: None,
+            finished_at: None,
+            error: None,
+            metadata,
+        };
+        self.jobs.push(job.clone());
+        job
+    }
+
+    /// Checks whether a host is busy with a queued job
+    pub fn is_host_busy(&self, host: &str) -> bool {
+        self.jobs.iter().any(|j| {
+            !j.is_finished() && j.status != QueueJobStatus::Pending && j.assigned_host.as_deref() == Some(host)
+        })
+    }
+}
@@ -0,0 +1,105 @@
+//! Custom quick action data structures
+//!
+//! Users can add their own buttons to a host: a G-code script or macro, an
+//! HTTP request (e.g. to a smart plug or Home Assistant) or a shell command
+//! run over SSH. The actions are stored per host in the settings and offered
+//! in the desktop app and the Telegram host menu.
+
+use serde::{Deserialize, Serialize};
+use crate::network::ssh::is_valid_ssh_user;
+
+/// What a quick action does
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum QuickActionKind {
+    /// Runs a G-code script or macro on the printer
+    Gcode { script: String },
+    /// Sends an HTTP request
+    Http {
+        #[serde(default = "default_http_method")]
+        method: String,
+        url: String,
+        /// Request body, sent as JSON when it parses as JSON
+        #[serde(default)]
+        body: Option<String>,
+    },
+    /// Runs a shell command on the printer's machine over SSH (key-based login)
+    Ssh { user: String, command: String },
+}
+
+fn default_http_method() -> String {
+    "POST".to_string()
+}
+
+/// User-defined button of a host
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct QuickAction {
+    /// Unique action identifier, assigned when the action is added
+    #[serde(default)]
+    pub id: String,
+    /// Button text
+    pub label: String,
+    pub action: QuickActionKind,
+    /// Whether the action has to be confirmed before it runs
+    #[serde(default)]
+    pub confirm: bool,
+    /// Whether the button is also offered in the Telegram host menu
+    #[serde(default = "default_true")]
+    pub telegram: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl QuickAction {
+    /// Checks that the action can be run
+    pub fn validate(&self) -> Result<(), String> {
+        if self.label.trim().is_empty() {
+            return Err("The action needs a label".to_string());
+        }
+        match &self.action {
+            QuickActionKind::Gcode { script } if script.trim().is_empty() => {
+                Err("The G-code to run is missing".to_string())
+            }
+            QuickActionKind::Http { url, .. } if !url.starts_with("http://") && !url.starts_with("https://") => {
+                Err(format!("Invalid URL: {}", url))
+            }
+            QuickActionKind::Http { method, .. } if reqwest::Method::from_bytes(method.to_uppercase().as_bytes()).is_err() => {
+                Err(format!("Invalid HTTP method: {}", method))
+            }
+            QuickActionKind::Ssh { user, command } if user.trim().is_empty() || command.trim().is_empty() => {
+                Err("SSH actions need a user and a command".to_string())
+            }
+            QuickActionKind::Ssh { user, .. } if !is_valid_ssh_user(user) => Err(format!("Invalid SSH user: {}", user)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn quick_action_defaults_and_validation() {
+        let action: QuickAction = serde_json::from_value(json!({
+            "label": "Chamber lights",
+            "action": { "type": "http", "url": "http://192.168.1.20/relay/0?turn=toggle" },
+        }))
+        .unwrap();
+        assert!(action.telegram && !action.confirm);
+        assert_eq!(action.action, QuickActionKind::Http {
+            method: "POST".to_string(),
+            url: "http://192.168.1.20/relay/0?turn=toggle".to_string(),
+            body: None,
+        });
+        assert!(action.validate().is_ok());
+
+        let ssh = QuickAction { action: QuickActionKind::Ssh { user: "pi".to_string(), command: " ".to_string() }, ..action };
+        assert!(ssh.validate().is_err());
+        let ssh = QuickAction { action: QuickActionKind::Ssh { user: "-oProxyCommand=id".to_string(), command: "uptime".to_string() }, ..ssh };
+        assert!(ssh.validate().is_err());
+    }
+}
@@ -0,0 +1,283 @@
+//! Automation rule data structures
+//!
+//! This module contains user-defined "if this then that" rules: conditions
+//! on monitored metrics such as the printer state, heater temperatures or
+//! print progress, and the actions run when they start to hold. The
+//! background monitor evaluates them; they are persisted in `rules.json`
+//! next to the configuration file.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use crate::models::api::PrinterState;
+use crate::models::config::AppSettings;
+
+/// Comparison of a metric with a rule value
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Comparison {
+    Above,
+    Below,
+    AtLeast,
+    AtMost,
+}
+
+impl Comparison {
+    /// Compares a reading with the rule value
+    pub fn holds(&self, reading: f64, value: f64) -> bool {
+        match self {
+            Comparison::Above => reading > value,
+            Comparison::Below => reading < value,
+            Comparison::AtLeast => reading >= value,
+            Comparison::AtMost => reading <= value,
+        }
+    }
+}
+
+/// Condition on a monitored metric
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "metric", rename_all = "snake_case")]
+pub enum RuleCondition {
+    /// Printer state equals the given state, e.g. error or offline
+    State { equals: PrinterState },
+    /// Primary extruder temperature in °C
+    ExtruderTemperature { comparison: Comparison, value: f64 },
+    /// Bed temperature in °C
+    BedTemperature { comparison: Comparison, value: f64 },
+    /// Progress of the active job in percent
+    Progress { comparison: Comparison, value: f64 },
+    /// A printing job has not advanced for the given number of minutes
+    ProgressStalled { minutes: f64 },
+    /// Temperature of an additional sensor in °C, e.g. "chamber"
+    SensorTemperature { sensor: String, comparison: Comparison, value: f64 },
+}
+
+impl RuleCondition {
+    /// Checks the condition against the current metrics of a host
+    ///
+    /// Conditions on readings the host does not report never hold.
+    pub fn holds(&self, metrics: &RuleMetrics) -> bool {
+        let compare = |reading: Option<f64>, comparison: &Comparison, value: f64| {
+            reading.is_some_and(|reading| comparison.holds(reading, value))
+        };
+        match self {
+            RuleCondition::State { equals } => metrics.state == *equals,
+            RuleCondition::ExtruderTemperature { comparison, value } => compare(metrics.extruder_temperature, comparison, *value),
+            RuleCondition::BedTemperature { comparison, value } => compare(metrics.bed_temperature, comparison, *value),
+            RuleCondition::Progress { comparison, value } => compare(metrics.progress, comparison, *value),
+            RuleCondition::ProgressStalled { minutes } => metrics.stalled_minutes.is_some_and(|stalled| stalled >= *minutes),
+            RuleCondition::SensorTemperature { sensor, comparison, value } => {
+                compare(metrics.sensors.get(sensor).copied(), comparison, *value)
+            }
+        }
+    }
+
+    /// Checks whether evaluating the condition needs the additional sensors
+    pub fn needs_sensors(&self) -> bool {
+        matches!(self, RuleCondition::SensorTemperature { .. })
+    }
+}
+
+/// Where a rule notification is sent
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifyChannel {
+    /// Desktop notification and Telegram
+    #[default]
+    All,
+    Desktop,
+    Telegram,
+}
+
+/// Action run when a rule triggers
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleAction {
+    /// Sends a notification (None: a message naming the rule and host)
+    Notify {
+        #[serde(default)]
+        channel: NotifyChannel,
+        message: Option<String>,
+    },
+    /// Posts the rule, host and metrics as JSON to a URL
+    Webhook {
+        url: String,
+        /// Attach the current webcam snapshot (multipart post)
+        #[serde(default)]
+        include_snapshot: bool,
+    },
+    /// Runs a G-code script or macro on the printer
+    RunMacro { gcode: String },
+    /// Switches a Moonraker power device
+    PowerDevice { device: String, on: bool },
+}
+
+/// User-defined automation rule
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AutomationRule {
+    /// Unique rule identifier
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    /// Host IP addresses the rule applies to (empty: all hosts)
+    #[serde(default)]
+    pub hosts: Vec<String>,
+    /// Conditions that must all hold
+    pub conditions: Vec<RuleCondition>,
+    /// Actions run, in order, when the conditions start to hold
+    pub actions: Vec<RuleAction>,
+    /// Last time the rule triggered (RFC 3339)
+    #[serde(default)]
+    pub last_triggered: Option<String>,
+}
+
+impl AutomationRule {
+    /// Checks whether the rule is evaluated for a host
+    pub fn applies_to(&self, host: &str) -> bool {
+        self.enabled && (self.hosts.is_empty() || self.hosts.iter().any(|h| h == host))
+    }
+
+    /// Checks that the rule can be evaluated and its actions run
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("The rule needs a name".to_string());
+        }
+        if self.conditions.is_empty() || self.actions.is_empty() {
+            return Err("A rule needs at least one condition and one action".to_string());
+        }
+        for condition in &self.conditions {
+            match condition {
+                RuleCondition::ProgressStalled { minutes } if *minutes <= 0.0 => {
+                    return Err("The stall duration must be greater than 0 minutes".to_string());
+                }
+                RuleCondition::Progress { value, .. } if !(0.0..=100.0).contains(value) => {
+                    return Err("Progress must be between 0 and 100".to_string());
+                }
+                RuleCondition::SensorTemperature { sensor, .. } if sensor.trim().is_empty() => {
+                    return Err("The sensor name is missing".to_string());
+                }
+                _ => {}
+            }
+        }
+        for action in &self.actions {
+            match action {
+                RuleAction::Webhook { url, .. } if !url.starts_with("http://") && !url.starts_with("https://") => {
+                    return Err(format!("Invalid webhook URL: {}", url));
+                }
+                RuleAction::RunMacro { gcode } if gcode.trim().is_empty() => {
+                    return Err("The G-code to run is missing".to_string());
+                }
+                RuleAction::PowerDevice { device, .. } if device.trim().is_empty() => {
+                    return Err("The power device is missing".to_string());
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks whether all conditions hold
+    pub fn matches(&self, metrics: &RuleMetrics) -> bool {
+        !self.conditions.is_empty() && self.conditions.iter().all(|condition| condition.holds(metrics))
+    }
+}
+
+/// Metrics of a host the rule conditions are checked against
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RuleMetrics {
+    pub state: PrinterState,
+    /// Primary extruder temperature in °C
+    pub extruder_temperature: Option<f64>,
+    /// Bed temperature in °C
+    pub bed_temperature: Option<f64>,
+    /// Progress of the active job in percent
+    pub progress: Option<f64>,
+    /// Minutes a printing job has not advanced
+    pub stalled_minutes: Option<f64>,
+    /// Additional sensor temperatures by sensor name
+    pub sensors: HashMap<String, f64>,
+}
+
+impl RuleMetrics {
+    /// Creates metrics without readings, e.g. for an offline host
+    pub fn new(state: PrinterState) -> Self {
+        Self {
+            state,
+            extruder_temperature: None,
+            bed_temperature: None,
+            progress: None,
+            stalled_minutes: None,
+            sensors: HashMap::new(),
+        }
+    }
+}
+
+/// Persisted automation rules
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AutomationRules {
+    #[serde(default)]
+    pub rules: Vec<AutomationRule>,
+}
+
+impl AutomationRules {
+    /// Get the rules file path
+    pub fn rules_path() -> PathBuf {
+        AppSettings::config_path().with_file_name("rules.json")
+    }
+
+    /// Load the rules from file
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Self::rules_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Save the rules to file
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::rules_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn rule_matches_when_all_conditions_hold() {
+        let rule: AutomationRule = serde_json::from_value(json!({
+            "id": "1",
+            "name": "Hot bed while idle",
+            "enabled": true,
+            "conditions": [
+                { "metric": "state", "equals": "standby" },
+                { "metric": "bed_temperature", "comparison": "above", "value": 50.0 },
+            ],
+            "actions": [{ "type": "run_macro", "gcode": "TURN_OFF_HEATERS" }],
+        }))
+        .unwrap();
+
+        let mut metrics = RuleMetrics::new(PrinterState::Standby);
+        assert!(!rule.matches(&metrics), "missing readings never match");
+        metrics.bed_temperature = Some(60.0);
+        assert!(rule.matches(&metrics));
+        metrics.state = PrinterState::Printing;
+        assert!(!rule.matches(&metrics));
+        assert!(rule.applies_to("192.168.1.50"));
+    }
+
+    #[test]
+    fn webhook_snapshot_is_opt_in() {
+        let action: RuleAction = serde_json::from_value(json!({ "type": "webhook", "url": "https://example.com/hook" })).unwrap();
+        assert_eq!(action, RuleAction::Webhook { url: "https://example.com/hook".to_string(), include_snapshot: false });
+    }
+}
@@ -0,0 +1,269 @@
+//! Scheduled print action data structures
+//!
+//! This module contains rules such as "pause at 19:00" or "cancel if not 80%
+//! done by midnight" that are bound to the print running when they were
+//! created. The background monitor evaluates them; they are persisted in
+//! `schedules.json` next to the configuration file.
+
+use chrono::{DateTime, Local, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use crate::models::config::AppSettings;
+
+/// Held while `schedules.json` is read, changed and written, so the commands
+/// and the background monitor do not overwrite each other's changes
+static SCHEDULES_LOCK: Mutex<()> = Mutex::new(());
+
+/// Action taken when a schedule fires
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduledActionKind {
+    Pause,
+    Cancel,
+}
+
+impl ScheduledActionKind {
+    /// Gets the printer action name ("pause" or "cancel")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScheduledActionKind::Pause => "pause",
+            ScheduledActionKind::Cancel => "cancel",
+        }
+    }
+}
+
+/// State of a scheduled action
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduleStatus {
+    /// Waiting for its time or progress
+    Pending,
+    /// Action was sent to the printer
+    Executed,
+    /// Fired, but the print was already past the progress limit
+    Skipped,
+    /// The print it belonged to ended before it fired
+    Expired,
+    /// Sending the action failed
+    Failed,
+}
+
+/// Outcome of evaluating a pending action
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScheduleDecision {
+    /// Not due yet
+    Wait,
+    /// Due, send the action
+    Run,
+    /// Due, but the print is already far enough
+    Skip,
+}
+
+/// Pause or cancel rule for the running print of a host
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScheduledAction {
+    /// Unique action identifier
+    pub id: String,
+    /// Host IP address
+    pub host: String,
+    /// Print the rule belongs to
+    pub filename: String,
+    pub action: ScheduledActionKind,
+    /// Fire at this time (RFC 3339)
+    pub at_time: Option<String>,
+    /// Fire once the progress reaches this percentage
+    pub at_progress: Option<f64>,
+    /// Only act while the progress is below this percentage,
+    /// e.g. "cancel if not complete by 80%"
+    pub below_progress: Option<f64>,
+    pub status: ScheduleStatus,
+    /// Creation time (RFC 3339)
+    pub created_at: String,
+    /// Time the rule fired or expired (RFC 3339)
+    pub finished_at: Option<String>,
+    /// Failure reason
+    pub error: Option<String>,
+}
+
+impl ScheduledAction {
+    /// Decides what to do with a pending action
+    ///
+    /// # Arguments
+    /// * `now` - Current time
+    /// * `progress` - Current print progress percentage
+    pub fn evaluate(&self, now: DateTime<Utc>, progress: f64) -> ScheduleDecision {
+        let time_reached = self
+            .at_time
+            .as_deref()
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .is_some_and(|t| now >= t);
+        let progress_reached = self.at_progress.is_some_and(|p| progress >= p);
+
+        if !time_reached && !progress_reached {
+            ScheduleDecision::Wait
+        } else if self.below_progress.is_some_and(|limit| progress >= limit) {
+            ScheduleDecision::Skip
+        } else {
+            ScheduleDecision::Run
+        }
+    }
+
+    /// Marks the action as finished with the given state
+    pub fn finish(&mut self, status: ScheduleStatus, error: Option<String>) {
+        self.status = status;
+        self.error = error;
+        self.finished_at = Some(Utc::now().to_rfc3339());
+    }
+}
+
+/// Parses a schedule time given as RFC 3339 or as local "HH:MM"
+///
+/// A bare time of day means its next occurrence, so "00:00" is the coming midnight.
+pub fn parse_schedule_time(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+
+    let time = NaiveTime::parse_from_str(value, "%H:%M")
+        .map_err(|_| format!("Invalid time: {} (expected HH:MM or RFC 3339)", value))?;
+    let now = Local::now();
+    let mut date = now.date_naive();
+    if time <= now.time() {
+        date = date.succ_opt().ok_or("Invalid date")?;
+    }
+    Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+        .ok_or_else(|| format!("Time does not exist in the local timezone: {}", value))
+}
+
+/// Persisted scheduled actions
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ScheduledActions {
+    #[serde(default)]
+    pub actions: Vec<ScheduledAction>,
+}
+
+impl ScheduledActions {
+    /// Get the schedules file path
+    pub fn schedules_path() -> PathBuf {
+        AppSettings::config_path().with_file_name("schedules.json")
+    }
+
+    /// Load the scheduled actions from file
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Self::schedules_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Save the scheduled actions to file
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::schedules_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Loads, changes and saves the scheduled actions as one step
+    ///
+    /// # Arguments
+    /// * `change` - Change applied to the actions on file
+    ///
+    /// # Returns
+    /// * What `change` returned
+    pub fn update<T>(change: impl FnOnce(&mut Self) -> T) -> Result<T, Box<dyn std::error::Error>> {
+        let _guard = SCHEDULES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut schedules = Self::load()?;
+        let result = change(&mut schedules);
+        schedules.save()?;
+        Ok(result)
+    }
+
+    /// Takes over actions that fired or expired, matched by id
+    ///
+    /// Actions removed or finished in the meantime are left alone.
+    pub fn merge_finished(&mut self, finished: &[ScheduledAction]) {
+        for action in finished {
+            if let Some(stored) = self
+                .actions
+                .iter_mut()
+                .find(|a| a.id == action.id && a.status == ScheduleStatus::Pending)
+            {
+                *stored = action.clone();
+            }
+        }
+    }
+
+    /// Checks whether a host has actions waiting to fire
+    pub fn has_pending(&self, host: &str) -> bool {
+        self.actions.iter().any(|a| a.host == host && a.status == ScheduleStatus::Pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(at_time: Option<&str>, at_progress: Option<f64>, below_progress: Option<f64>) -> ScheduledAction {
+        ScheduledAction {
+            id: "1".to_string(),
+            host: "192.168.1.10".to_string(),
+            filename: "benchy.gcode".to_string(),
+            action: ScheduledActionKind::Cancel,
+            at_time: at_time.map(str::to_string),
+            at_progress,
+            below_progress,
+            status: ScheduleStatus::Pending,
+            created_at: Utc::now().to_rfc3339(),
+            finished_at: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn cancel_at_midnight_unless_far_enough() {
+        let rule = action(Some("2024-05-02T00:00:00Z"), None, Some(80.0));
+        let before = "2024-05-01T23:59:00Z".parse().unwrap();
+        let after = "2024-05-02T00:00:30Z".parse().unwrap();
+
+        assert_eq!(rule.evaluate(before, 50.0), ScheduleDecision::Wait);
+        assert_eq!(rule.evaluate(after, 50.0), ScheduleDecision::Run);
+        assert_eq!(rule.evaluate(after, 85.0), ScheduleDecision::Skip);
+    }
+
+    #[test]
+    fn pause_at_progress() {
+        let rule = action(None, Some(50.0), None);
+        assert_eq!(rule.evaluate(Utc::now(), 49.9), ScheduleDecision::Wait);
+        assert_eq!(rule.evaluate(Utc::now(), 50.0), ScheduleDecision::Run);
+    }
+
+    #[test]
+    fn merging_keeps_actions_added_and_removed_meanwhile() {
+        let mut fired = action(None, Some(50.0), None);
+        fired.finish(ScheduleStatus::Executed, None);
+        let mut removed = action(None, Some(20.0), None);
+        removed.id = "2".to_string();
+        removed.finish(ScheduleStatus::Skipped, None);
+        let mut added = action(None, Some(90.0), None);
+        added.id = "3".to_string();
+
+        let mut schedules = ScheduledActions { actions: vec![action(None, Some(50.0), None), added.clone()] };
+        schedules.merge_finished(&[fired, removed]);
+
+        assert_eq!(schedules.actions.len(), 2);
+        assert_eq!(schedules.actions[0].status, ScheduleStatus::Executed);
+        assert_eq!(schedules.actions[1].id, added.id);
+        assert_eq!(schedules.actions[1].status, ScheduleStatus::Pending);
+    }
+}
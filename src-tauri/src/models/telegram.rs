@@ -1,6 +1,27 @@
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use teloxide::types::UserId;
 
+/// What a Telegram user may do through the bot
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TelegramRole {
+    /// Control actions, and notices about other users' emergency stops
+    Admin,
+    /// Control actions (stop, restart, toggles, Z offset)
+    #[default]
+    Operator,
+    /// Status, snapshots and notifications only
+    Viewer,
+}
+
+impl TelegramRole {
+    /// Checks whether the role may send commands to printers
+    pub fn can_control(&self) -> bool {
+        *self != TelegramRole::Viewer
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TelegramUser {
     pub user_id: i64, // Serialized as i64 for frontend compatibility
@@ -9,6 +30,14 @@ pub struct TelegramUser {
     pub last_name: Option<String>,
     pub registered_at: chrono::DateTime<chrono::Utc>,
     pub notifications_enabled: bool,
+    #[serde(default)]
+    pub role: TelegramRole,
+    /// Why messages can no longer be delivered ("bot was blocked by the user", ...)
+    #[serde(default)]
+    pub blocked_reason: Option<String>,
+    /// When delivery first failed permanently
+    #[serde(default)]
+    pub blocked_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 impl TelegramUser {
@@ -20,11 +49,19 @@ impl TelegramUser {
             last_name,
             registered_at: chrono::Utc::now(),
             notifications_enabled: true, // Default to enabled
+            role: TelegramRole::default(),
+            blocked_reason: None,
+            blocked_at: None,
         }
     }
 }
 
 impl TelegramUser {
+    /// Checks whether the user blocked the bot or the chat is gone
+    pub fn is_blocked(&self) -> bool {
+        self.blocked_reason.is_some()
+    }
+
     pub fn display_name(&self) -> String {
         if let Some(username) = &self.username {
             format!("@{}", username)
@@ -40,13 +77,79 @@ impl TelegramUser {
     }
 }
 
+/// User who entered a valid code and waits for approval in the desktop app
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingRegistration {
+    pub user_id: i64,
+    pub username: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub requested_at: chrono::DateTime<chrono::Utc>,
+    /// Role of the code the user registered with
+    #[serde(default)]
+    pub role: TelegramRole,
+}
+
+impl PendingRegistration {
+    /// Creates the registered user, with the given role, once approved
+    pub fn approve(&self, role: TelegramRole) -> TelegramUser {
+        TelegramUser {
+            user_id: self.user_id,
+            username: self.username.clone(),
+            first_name: self.first_name.clone(),
+            last_name: self.last_name.clone(),
+            registered_at: chrono::Utc::now(),
+            notifications_enabled: true,
+            role,
+            blocked_reason: None,
+            blocked_at: None,
+        }
+    }
+}
+
+/// Registration link with its QR code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrationLink {
+    /// `https://t.me/<bot>?start=<token>`
+    pub url: String,
+    /// QR code of the URL as SVG document
+    pub qr_svg: String,
+}
+
+/// Registration code with the role its users are suggested for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrationCode {
+    /// 6-digit code typed into the chat
+    pub code: String,
+    /// Token of the `t.me/<bot>?start=<token>` registration link
+    pub link_token: String,
+    /// Role preselected when the registration is approved
+    pub role: TelegramRole,
+    /// Free text shown in the desktop app, e.g. "Workshop visitors"
+    pub label: Option<String>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    /// Whether the code stops working after the first registration
+    pub single_use: bool,
+}
+
+impl RegistrationCode {
+    pub fn is_expired(&self) -> bool {
+        chrono::Utc::now() > self.expires_at
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistrationState {
-    pub is_active: bool,
-    pub code: Option<String>,
-    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub attempts: u32,
+    /// Active registration codes
+    #[serde(default)]
+    pub codes: Vec<RegistrationCode>,
+    /// Wrong codes entered per Telegram user since the last code was created
+    #[serde(default)]
+    pub attempts: HashMap<i64, u32>,
     pub max_attempts: u32,
+    /// Users waiting for approval
+    #[serde(default)]
+    pub pending: Vec<PendingRegistration>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -90,63 +193,155 @@ pub struct HostCache {
 impl RegistrationState {
     pub fn new() -> Self {
         Self {
-            is_active: false,
-            code: None,
-            expires_at: None,
-            attempts: 0,
+            codes: Vec::new(),
+            attempts: HashMap::new(),
             max_attempts: 3,
+            pending: Vec::new(),
         }
     }
 
+    /// Creates a single-use operator code valid for 5 minutes
     pub fn start_registration(&mut self) -> String {
+        self.add_code(TelegramRole::Operator, chrono::Duration::seconds(300), None, true).code
+    }
+
+    /// Creates an additional registration code
+    ///
+    /// # Arguments
+    /// * `role` - Role suggested for users of the code
+    /// * `valid_for` - Time until the code expires
+    /// * `label` - Description shown in the desktop app
+    /// * `single_use` - Whether the code stops working after one registration
+    pub fn add_code(
+        &mut self,
+        role: TelegramRole,
+        valid_for: chrono::Duration,
+        label: Option<String>,
+        single_use: bool,
+    ) -> RegistrationCode {
         use rand::Rng;
-        
-        // Generate a secure 6-digit code
+
+        self.prune_expired();
         let mut rng = rand::thread_rng();
-        let code = format!("{:06}", rng.gen_range(100000..=999999));
-        
-        self.is_active = true;
-        self.code = Some(code.clone());
-        self.expires_at = Some(chrono::Utc::now() + chrono::Duration::seconds(300)); // 5 minutes
-        self.attempts = 0; // Reset attempts counter
-        
-        code
+        // Generate a secure 6-digit code that is not in use yet
+        let code = loop {
+            let code = format!("{:06}", rng.gen_range(100000..=999999));
+            if !self.codes.iter().any(|c| c.code == code) {
+                break code;
+            }
+        };
+        let registration_code = RegistrationCode {
+            code,
+            // Deep-link parameters allow up to 64 characters of [A-Za-z0-9_-]
+            link_token: format!("{:016x}{:016x}", rng.gen::<u64>(), rng.gen::<u64>()),
+            role,
+            label,
+            expires_at: chrono::Utc::now() + valid_for,
+            single_use,
+        };
+
+        self.codes.push(registration_code.clone());
+        self.attempts.clear(); // Reset attempts counters
+        registration_code
     }
 
-    pub fn is_expired(&self) -> bool {
-        if let Some(expires_at) = self.expires_at {
-            chrono::Utc::now() > expires_at
-        } else {
-            false
-        }
+    /// Checks whether any code can still be used
+    pub fn is_active(&self) -> bool {
+        self.codes.iter().any(|c| !c.is_expired())
     }
 
-    pub fn verify_code(&mut self, input_code: &str) -> bool {
-        if !self.is_active || self.is_expired() {
-            return false;
-        }
-        
-        if self.attempts >= self.max_attempts {
-            return false;
+    /// Drops codes past their expiry
+    pub fn prune_expired(&mut self) {
+        self.codes.retain(|c| !c.is_expired());
+    }
+
+    /// Checks a code typed by a user, counting wrong ones against `max_attempts`
+    ///
+    /// Only the user who typed the wrong codes is locked out; codes stay
+    /// usable for everyone else.
+    ///
+    /// # Arguments
+    /// * `user_id` - Telegram user ID of the sender
+    /// * `input_code` - Typed code
+    ///
+    /// # Returns
+    /// * The matching code; single-use codes are used up by this
+    pub fn verify_code(&mut self, user_id: i64, input_code: &str) -> Option<RegistrationCode> {
+        self.prune_expired();
+        if self.codes.is_empty() || self.is_locked_out(user_id) {
+            return None;
         }
-        
-        if let Some(code) = &self.code {
-            if code == input_code {
-                return true;
-            } else {
-                self.attempts += 1;
-                return false;
+
+        match self.codes.iter().position(|c| c.code == input_code) {
+            Some(index) => Some(self.use_code(index)),
+            None => {
+                *self.attempts.entry(user_id).or_insert(0) += 1;
+                None
             }
+        }
+    }
+
+    /// Number of wrong codes a user may still type
+    pub fn remaining_attempts(&self, user_id: i64) -> u32 {
+        self.max_attempts.saturating_sub(self.attempts.get(&user_id).copied().unwrap_or(0))
+    }
+
+    /// Checks whether a user typed too many wrong codes
+    pub fn is_locked_out(&self, user_id: i64) -> bool {
+        self.remaining_attempts(user_id) == 0
+    }
+
+    /// Number of users who typed too many wrong codes
+    pub fn locked_out_count(&self) -> usize {
+        self.attempts.values().filter(|&&attempts| attempts >= self.max_attempts).count()
+    }
+
+    /// Checks the token of a registration link
+    ///
+    /// # Returns
+    /// * The code the link belongs to; single-use codes are used up by this
+    pub fn verify_link_token(&mut self, token: &str) -> Option<RegistrationCode> {
+        self.prune_expired();
+        if token.is_empty() {
+            return None;
+        }
+        let index = self.codes.iter().position(|c| c.link_token == token)?;
+        Some(self.use_code(index))
+    }
+
+    fn use_code(&mut self, index: usize) -> RegistrationCode {
+        if self.codes[index].single_use {
+            self.codes.remove(index)
         } else {
-            false
+            self.codes[index].clone()
         }
     }
 
+    /// Removes one code
+    ///
+    /// # Returns
+    /// * Whether the code existed
+    pub fn revoke_code(&mut self, code: &str) -> bool {
+        let count = self.codes.len();
+        self.codes.retain(|c| c.code != code);
+        self.codes.len() != count
+    }
+
+    /// Removes all codes
     pub fn finish_registration(&mut self) {
-        self.is_active = false;
-        self.code = None;
-        self.expires_at = None;
-        self.attempts = 0;
+        self.codes.clear();
+        self.attempts.clear();
+    }
+
+    /// Checks whether a user is waiting for approval
+    pub fn is_pending(&self, user_id: i64) -> bool {
+        self.pending.iter().any(|p| p.user_id == user_id)
+    }
+
+    /// Removes and returns the pending registration of a user
+    pub fn take_pending(&mut self, user_id: i64) -> Option<PendingRegistration> {
+        let index = self.pending.iter().position(|p| p.user_id == user_id)?;
+        Some(self.pending.remove(index))
     }
 }
 
@@ -247,3 +442,36 @@ impl HostCache {
         chrono::Utc::now() - self.last_updated > chrono::Duration::seconds(30)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_carry_their_role_and_single_use_codes_are_used_up() {
+        let mut state = RegistrationState::new();
+        let operator = state.start_registration();
+        let viewer = state.add_code(TelegramRole::Viewer, chrono::Duration::hours(4), Some("Workshop".to_string()), false);
+
+        assert_eq!(state.verify_code(1, &viewer.code).map(|c| c.role), Some(TelegramRole::Viewer));
+        assert_eq!(state.verify_link_token(&viewer.link_token).map(|c| c.role), Some(TelegramRole::Viewer));
+        assert_eq!(state.verify_code(2, &operator).map(|c| c.role), Some(TelegramRole::Operator));
+        assert!(state.verify_code(3, &operator).is_none());
+        assert_eq!(state.codes.len(), 1);
+    }
+
+    #[test]
+    fn wrong_codes_lock_out_only_the_sender() {
+        let mut state = RegistrationState::new();
+        let code = state.start_registration();
+        for _ in 0..state.max_attempts {
+            assert!(state.verify_code(1, "000000").is_none());
+        }
+        assert!(state.is_locked_out(1));
+        assert!(state.verify_code(1, &code).is_none());
+        assert_eq!(state.locked_out_count(), 1);
+
+        assert_eq!(state.remaining_attempts(2), state.max_attempts);
+        assert_eq!(state.verify_code(2, &code).map(|c| c.role), Some(TelegramRole::Operator));
+    }
+}
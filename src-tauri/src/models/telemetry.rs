@@ -0,0 +1,142 @@
+//! Telemetry models
+//!
+//! This module contains the samples the telemetry logger records per host:
+//! heater temperatures and targets, job progress and the printer state, and
+//! the downsampled temperature series built from them for charts.
+
+use serde::{Deserialize, Serialize};
+use crate::models::PrinterState;
+
+/// Temperature, progress and status of a host at one point in time
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TelemetrySample {
+    /// Host IP address
+    pub host: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Printer state as sent to the frontend, e.g. "printing" or "offline"
+    pub state: String,
+    /// Job progress in percent (None without an active job)
+    pub progress: Option<f64>,
+    /// File of the active job
+    pub filename: Option<String>,
+    /// Temperature of the extruder in use in °C (the primary one unless a
+    /// multi-extruder printer or toolchanger switched tools)
+    pub extruder_temperature: Option<f64>,
+    pub extruder_target: Option<f64>,
+    /// Bed temperature in °C (None without a heated bed)
+    pub bed_temperature: Option<f64>,
+    pub bed_target: Option<f64>,
+}
+
+impl TelemetrySample {
+    /// Creates a sample without readings, e.g. for an offline host
+    ///
+    /// # Arguments
+    /// * `host` - Host IP address
+    /// * `state` - Printer state
+    pub fn state_only(host: &str, state: PrinterState) -> Self {
+        Self {
+            host: host.to_string(),
+            timestamp: chrono::Utc::now(),
+            state: state.as_str().to_string(),
+            progress: None,
+            filename: None,
+            extruder_temperature: None,
+            extruder_target: None,
+            bed_temperature: None,
+            bed_target: None,
+        }
+    }
+
+    /// Reads a sample from a `printer/objects/query` status of the
+    /// extruders, `toolhead`, `heater_bed`, `print_stats` and `virtual_sdcard`
+    ///
+    /// # Arguments
+    /// * `host` - Host IP address
+    /// * `state` - Printer state
+    /// * `status` - `result.status` of the query
+    pub fn from_status(host: &str, state: PrinterState, status: &serde_json::Value) -> Self {
+        let value = |pointer: &str| status.pointer(pointer).and_then(|v| v.as_f64());
+        let active = state.has_active_job();
+        let extruder = status.pointer("/toolhead/extruder").and_then(|e| e.as_str()).filter(|e| !e.is_empty()).unwrap_or("extruder");
+        let heater = |key: &str| status.get(extruder).and_then(|e| e.get(key)).and_then(|v| v.as_f64());
+        Self {
+            progress: value("/virtual_sdcard/progress").filter(|_| active).map(|p| p * 100.0),
+            filename: status
+                .pointer("/print_stats/filename")
+                .and_then(|f| f.as_str())
+                .filter(|f| active && !f.is_empty())
+                .map(str::to_string),
+            extruder_temperature: heater("temperature"),
+            extruder_target: heater("target"),
+            bed_temperature: value("/heater_bed/temperature"),
+            bed_target: value("/heater_bed/target"),
+            ..Self::state_only(host, state)
+        }
+    }
+}
+
+/// Largest number of points of a temperature series
+pub const MAX_SERIES_POINTS: u32 = 2000;
+
+/// Aggregated temperatures of one time bucket
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TemperaturePoint {
+    /// Start of the bucket
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Mean extruder temperature in °C
+    pub extruder: Option<f64>,
+    pub extruder_min: Option<f64>,
+    pub extruder_max: Option<f64>,
+    /// Last extruder target in the bucket
+    pub extruder_target: Option<f64>,
+    /// Mean bed temperature in °C
+    pub bed: Option<f64>,
+    pub bed_min: Option<f64>,
+    pub bed_max: Option<f64>,
+    /// Last bed target in the bucket
+    pub bed_target: Option<f64>,
+    /// Samples aggregated into the point
+    pub samples: u32,
+}
+
+/// Temperature history of a host, one point per bucket with samples
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TemperatureSeries {
+    /// Host IP address
+    pub host: String,
+    pub from: chrono::DateTime<chrono::Utc>,
+    pub to: chrono::DateTime<chrono::Utc>,
+    /// Length of a bucket in seconds
+    pub bucket_seconds: f64,
+    /// Points in time order; buckets without samples are left out so charts show gaps
+    pub points: Vec<TemperaturePoint>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sample_keeps_progress_of_active_jobs_only() {
+        let status = json!({
+            "extruder": { "temperature": 214.6, "target": 215.0 },
+            "heater_bed": { "temperature": 59.8, "target": 60.0 },
+            "print_stats": { "state": "printing", "filename": "benchy.gcode" },
+            "virtual_sdcard": { "progress": 0.425 },
+        });
+
+        let printing = TelemetrySample::from_status("192.168.1.50", PrinterState::Printing, &status);
+        assert_eq!(printing.state, "printing");
+        assert_eq!(printing.progress, Some(42.5));
+        assert_eq!(printing.filename.as_deref(), Some("benchy.gcode"));
+        assert_eq!(printing.extruder_target, Some(215.0));
+        assert_eq!(printing.bed_temperature, Some(59.8));
+
+        let idle = TelemetrySample::from_status("192.168.1.50", PrinterState::Standby, &status);
+        assert_eq!(idle.progress, None);
+        assert_eq!(idle.filename, None);
+        assert_eq!(idle.extruder_temperature, Some(214.6));
+    }
+}
@@ -5,6 +5,7 @@
 
 use std::str::FromStr;
 use crate::error::{MoonrakerError, MoonrakerResult};
+use crate::models::config::MOONRAKER_PORT;
 
 /// Generates a list of IP addresses from a subnet range
 /// 
@@ -27,6 +28,48 @@ pub fn generate_ip_range(subnet: &str) -> MoonrakerResult<Vec<String>> {
     Ok(ips)
 }
 
+/// Largest overlay range probed address by address
+pub const MAX_OVERLAY_SCAN_ADDRESSES: u64 = 65536;
+
+/// Generates the addresses of an overlay network range (Tailscale, WireGuard)
+/// 
+/// Unlike [`generate_ip_range`] the first and last addresses are kept:
+/// overlay networks have no broadcast address and hand out any address of
+/// their range, e.g. Tailscale's 100.64.0.0/10.
+/// 
+/// # Arguments
+/// * `subnet` - Range in CIDR notation (e.g., "100.100.0.0/16")
+/// 
+/// # Returns
+/// * Every address of the range, or an error if it is too large to probe
+pub fn generate_overlay_ip_range(subnet: &str) -> MoonrakerResult<Vec<String>> {
+    let network = ipnetwork::IpNetwork::from_str(subnet)
+        .map_err(|e| MoonrakerError::InvalidSubnet(e.to_string()))?;
+    let too_large = match network {
+        ipnetwork::IpNetwork::V4(net) => u64::from(net.size()) > MAX_OVERLAY_SCAN_ADDRESSES,
+        ipnetwork::IpNetwork::V6(net) => net.prefix() < 112,
+    };
+    if too_large {
+        return Err(MoonrakerError::InvalidSubnet(format!(
+            "{} has more than {} addresses; use Tailscale peer discovery or a smaller range",
+            subnet, MAX_OVERLAY_SCAN_ADDRESSES
+        )));
+    }
+    Ok(network.iter().map(|ip| ip.to_string()).collect())
+}
+
+/// Checks whether an IP address belongs to a subnet
+/// 
+/// # Arguments
+/// * `ip` - IP address
+/// * `subnet` - Subnet in CIDR notation
+pub fn subnet_contains(subnet: &str, ip: &str) -> bool {
+    match (ipnetwork::IpNetwork::from_str(subnet), std::net::IpAddr::from_str(ip)) {
+        (Ok(network), Ok(ip)) => network.contains(ip),
+        _ => false,
+    }
+}
+
 /// Validates if a string is a valid IP address
 /// 
 /// # Arguments
@@ -38,6 +81,43 @@ pub fn is_valid_ip(ip: &str) -> bool {
     std::net::IpAddr::from_str(ip).is_ok()
 }
 
+/// Splits a host identity into the address and the Moonraker port
+/// 
+/// Additional Klipper instances on one machine are separate hosts
+/// identified as "ip:port"; a bare address is the instance on the default
+/// port. IPv6 addresses are never split.
+/// 
+/// # Arguments
+/// * `host` - Host identity, e.g. "192.168.1.50" or "192.168.1.50:7126"
+/// 
+/// # Returns
+/// * Address and Moonraker port
+pub fn split_host_port(host: &str) -> (&str, u16) {
+    match host.split_once(':') {
+        Some((address, port)) if !port.contains(':') => match port.parse::<u16>() {
+            Ok(port) => (address, port),
+            Err(_) => (host, MOONRAKER_PORT),
+        },
+        _ => (host, MOONRAKER_PORT),
+    }
+}
+
+/// Builds the identity of the Moonraker instance on a port of an address
+/// 
+/// # Arguments
+/// * `address` - IP address or hostname
+/// * `port` - Moonraker port
+/// 
+/// # Returns
+/// * The bare address for the default port, "address:port" otherwise
+pub fn host_identity(address: &str, port: u16) -> String {
+    if port == MOONRAKER_PORT {
+        address.to_string()
+    } else {
+        format!("{}:{}", address, port)
+    }
+}
+
 /// Validates if a string is a valid subnet in CIDR notation
 /// 
 /// # Arguments
@@ -87,3 +167,68 @@ pub fn get_subnet_host_count(subnet: &str) -> MoonrakerResult<usize> {
         }
     }
 }
+
+/// Checks whether an address is covered by an allowlist
+/// 
+/// # Arguments
+/// * `address` - IP address or hostname, without port
+/// * `allowlist` - Networks in CIDR notation, single IP addresses or hostnames
+/// 
+/// # Returns
+/// * True if a network contains the address or a hostname equals it
+pub fn is_address_allowed(address: &str, allowlist: &[String]) -> bool {
+    let ip = std::net::IpAddr::from_str(address).ok();
+    allowlist.iter().map(|entry| entry.trim()).any(|entry| match (ipnetwork::IpNetwork::from_str(entry), ip) {
+        (Ok(network), Some(ip)) => network.contains(ip),
+        (Ok(_), None) => false,
+        (Err(_), _) => entry.eq_ignore_ascii_case(address),
+    })
+}
+
+/// Checks that an allowlist entry is a network, an IP address or a hostname
+/// 
+/// Networks covering every address (prefix length 0) are refused.
+pub fn validate_allowlist_entry(entry: &str) -> MoonrakerResult<()> {
+    let entry = entry.trim();
+    match ipnetwork::IpNetwork::from_str(entry) {
+        Ok(network) if network.prefix() == 0 => Err(MoonrakerError::InvalidSubnet(format!("{} allows every address", entry))),
+        Ok(_) => Ok(()),
+        Err(_) if !entry.is_empty() && entry.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.') => Ok(()),
+        Err(_) => Err(MoonrakerError::InvalidSubnet(entry.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_identity_carries_non_default_ports() {
+        assert_eq!(split_host_port("192.168.1.50"), ("192.168.1.50", 7125));
+        assert_eq!(split_host_port("192.168.1.50:7126"), ("192.168.1.50", 7126));
+        assert_eq!(split_host_port("fe80::1"), ("fe80::1", 7125));
+        assert_eq!(host_identity("192.168.1.50", 7125), "192.168.1.50");
+        assert_eq!(host_identity("192.168.1.50", 7127), "192.168.1.50:7127");
+    }
+
+    #[test]
+    fn overlay_ranges_keep_every_address() {
+        let ips = generate_overlay_ip_range("100.101.102.0/30").unwrap();
+        assert_eq!(ips, vec!["100.101.102.0", "100.101.102.1", "100.101.102.2", "100.101.102.3"]);
+        assert!(generate_overlay_ip_range("100.64.0.0/10").is_err());
+        assert!(subnet_contains("100.64.0.0/10", "100.101.102.103"));
+        assert!(!subnet_contains("100.64.0.0/10", "192.168.1.50"));
+    }
+
+    #[test]
+    fn allowlist_matches_networks_addresses_and_hostnames() {
+        let allowlist = vec!["100.64.0.0/10".to_string(), "203.0.113.7".to_string(), "Voron.tailnet.ts.net".to_string()];
+        assert!(is_address_allowed("100.101.102.103", &allowlist));
+        assert!(is_address_allowed("203.0.113.7", &allowlist));
+        assert!(is_address_allowed("voron.tailnet.ts.net", &allowlist));
+        assert!(!is_address_allowed("203.0.113.8", &allowlist));
+        assert!(!is_address_allowed("8.8.8.8", &[]));
+        assert!(validate_allowlist_entry("0.0.0.0/0").is_err());
+        assert!(validate_allowlist_entry("100.64.0.0/10").is_ok());
+    }
+}
@@ -6,7 +6,19 @@
 pub mod scanner;
 pub mod port_checker;
 pub mod ip_utils;
+pub mod status_cache;
+pub mod watcher;
+pub mod syn_scan;
+pub mod reservation;
+pub mod ssh;
+pub mod tailscale;
 
 pub use scanner::*;
 pub use port_checker::*;
 pub use ip_utils::*;
+pub use status_cache::*;
+pub use watcher::*;
+pub use syn_scan::*;
+pub use reservation::*;
+pub use ssh::*;
+pub use tailscale::*;
@@ -4,18 +4,78 @@
 //! on network hosts with optimized scanning and retry logic.
 
 use std::str::FromStr;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::time::timeout;
 use tokio::net::TcpStream;
 use std::net::SocketAddr;
 use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use serde::Serialize;
 
 use crate::models::config::{
     DEFAULT_PORT_SCAN_TIMEOUT_MS, 
     PORT_SCAN_CONCURRENCY, 
-    PORT_SCAN_RETRY_COUNT,
+    PORT_CLOSED_CACHE_TTL_MS,
+    PORT_SCAN_RETRY_POLICY,
     SLOW_NETWORK_TIMEOUT_MS
 };
+use crate::retry::retry;
+use crate::network::ip_utils::split_host_port;
+use crate::network::syn_scan::SynScanner;
+
+/// Addresses whose Moonraker port was found closed by a scan, with the time of the probe
+///
+/// Only scans use this cache. Status checks of known hosts always probe,
+/// so a printer that was switched on is noticed by the next monitor cycle.
+static CLOSED_PORT_CACHE: StdMutex<Option<HashMap<String, Instant>>> = StdMutex::new(None);
+
+/// Scan probes answered from the cache
+static CLOSED_PORT_CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+
+/// Scan probes that had to connect
+static CLOSED_PORT_CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// Closed port cache statistics
+#[derive(Debug, Clone, Serialize)]
+pub struct PortCacheStats {
+    /// Addresses currently cached as closed
+    pub entries: usize,
+    /// Probes skipped because the address was cached
+    pub hits: u64,
+    /// Probes sent since the cache was last cleared
+    pub misses: u64,
+    /// How long a closed port stays cached, in milliseconds
+    pub ttl_ms: u64,
+}
+
+/// Checks whether a closed port probed at this moment is still cached
+fn is_fresh(probed_at: &Instant) -> bool {
+    probed_at.elapsed() < Duration::from_millis(PORT_CLOSED_CACHE_TTL_MS)
+}
+
+/// Gets the closed port cache statistics
+pub fn port_cache_stats() -> PortCacheStats {
+    let mut guard = CLOSED_PORT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let cache = guard.get_or_insert_with(HashMap::new);
+    cache.retain(|_, probed_at| is_fresh(probed_at));
+    PortCacheStats {
+        entries: cache.len(),
+        hits: CLOSED_PORT_CACHE_HITS.load(Ordering::Relaxed),
+        misses: CLOSED_PORT_CACHE_MISSES.load(Ordering::Relaxed),
+        ttl_ms: PORT_CLOSED_CACHE_TTL_MS,
+    }
+}
+
+/// Forgets every cached closed port and resets the statistics
+///
+/// The next scan probes every address again.
+pub fn clear_port_cache() {
+    let mut guard = CLOSED_PORT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    *guard = None;
+    CLOSED_PORT_CACHE_HITS.store(0, Ordering::Relaxed);
+    CLOSED_PORT_CACHE_MISSES.store(0, Ordering::Relaxed);
+}
 
 /// Checks if a port is open on the specified host with retry logic
 /// 
@@ -34,28 +94,15 @@ pub async fn check_port_with_retry(ip: &str, port: u16, timeout_ms: u64) -> bool
     };
 
     let timeout_duration = Duration::from_millis(timeout_ms);
-    
-    for attempt in 0..PORT_SCAN_RETRY_COUNT {
+
+    retry(&PORT_SCAN_RETRY_POLICY, || async {
         match timeout(timeout_duration, TcpStream::connect(socket_addr)).await {
-            Ok(Ok(_)) => return true,
-            Ok(Err(_)) => {
-                // Connection failed, try again if we have attempts left
-                if attempt < PORT_SCAN_RETRY_COUNT - 1 {
-                    tokio::time::sleep(Duration::from_millis(50)).await;
-                    continue;
-                }
-            }
-            Err(_) => {
-                // Timeout, try again if we have attempts left
-                if attempt < PORT_SCAN_RETRY_COUNT - 1 {
-                    tokio::time::sleep(Duration::from_millis(100)).await;
-                    continue;
-                }
-            }
+            Ok(result) => result.map(|_| ()),
+            Err(_) => Err(std::io::Error::from(std::io::ErrorKind::TimedOut)),
         }
-    }
-    
-    false
+    })
+    .await
+    .is_ok()
 }
 
 /// Checks if a port is open on the specified host
@@ -85,20 +132,21 @@ pub async fn check_moonraker_port(ip: &str) -> bool {
 /// Uses longer timeout for potentially slow networks
 /// 
 /// # Arguments
-/// * `ip` - IP address to check
+/// * `ip` - IP address to check, with ":port" for additional instances
 /// 
 /// # Returns
 /// * True if Moonraker port is open, false otherwise
 pub async fn check_moonraker_port_adaptive(ip: &str) -> bool {
-    
+    let (ip, port) = split_host_port(ip);
+
     // First try with normal timeout
-    if check_port_with_retry(ip, 7125, DEFAULT_PORT_SCAN_TIMEOUT_MS).await {
+    if check_port_with_retry(ip, port, DEFAULT_PORT_SCAN_TIMEOUT_MS).await {
         return true;
     }
     
     
     // If failed, try with longer timeout for slow networks
-    let result = check_port_with_retry(ip, 7125, SLOW_NETWORK_TIMEOUT_MS).await;
+    let result = check_port_with_retry(ip, port, SLOW_NETWORK_TIMEOUT_MS).await;
     if result {
     } else {
     }
@@ -124,6 +172,33 @@ pub async fn check_multiple_ports(ip: &str, ports: Vec<u16>) -> Vec<(u16, bool)>
     results
 }
 
+/// Why a limited port scan stopped before probing every address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortScanStop {
+    /// The scan time budget ran out
+    Deadline,
+    /// Too many consecutive chunks had no open port
+    EmptyChunks,
+}
+
+/// How a limited port scan probes the addresses of a chunk
+pub enum PortProbe {
+    /// Connect with the adaptive LAN timeouts
+    Adaptive,
+    /// Connect with a fixed timeout in milliseconds
+    Timeout(u64),
+    /// Send SYN packets from a raw socket
+    Syn(SynScanner),
+}
+
+/// Result of a limited port scan
+pub struct PortScanOutcome {
+    /// Port status of every probed address
+    pub results: HashMap<String, bool>,
+    /// Why the scan stopped early, None when every address was probed
+    pub stopped: Option<PortScanStop>,
+}
+
 /// Efficiently scans multiple IP addresses for open Moonraker ports
 /// Uses controlled concurrency to avoid overwhelming the network
 /// 
@@ -133,27 +208,131 @@ pub async fn check_multiple_ports(ip: &str, ports: Vec<u16>) -> Vec<(u16, bool)>
 /// # Returns
 /// * HashMap mapping IP addresses to port status
 pub async fn scan_multiple_ips_for_moonraker(ips: Vec<String>) -> HashMap<String, bool> {
+    scan_ips_for_moonraker_limited(ips, None, 0, &PortProbe::Adaptive).await.results
+}
+
+/// Scans IP addresses for open Moonraker ports until a deadline or an empty stretch
+/// 
+/// # Arguments
+/// * `ips` - Vector of IP addresses to scan
+/// * `deadline` - Stop starting new chunks after this moment
+/// * `max_empty_chunks` - Stop after this many consecutive chunks without an open port (0: never)
+/// * `probe` - How addresses are probed
+/// 
+/// # Returns
+/// * Port status of the probed addresses and the reason for stopping early
+pub async fn scan_ips_for_moonraker_limited(
+    ips: Vec<String>,
+    deadline: Option<Instant>,
+    max_empty_chunks: u32,
+    probe: &PortProbe,
+) -> PortScanOutcome {
     let mut results = HashMap::new();
+    let mut empty_chunks = 0;
     
     // Process IPs in chunks to control concurrency
     for chunk in ips.chunks(PORT_SCAN_CONCURRENCY) {
-        let futures: Vec<_> = chunk.iter().map(|ip| {
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return PortScanOutcome { results, stopped: Some(PortScanStop::Deadline) };
+        }
+        if max_empty_chunks > 0 && empty_chunks >= max_empty_chunks {
+            return PortScanOutcome { results, stopped: Some(PortScanStop::EmptyChunks) };
+        }
+
+        // Addresses found closed by a recent scan are not probed again
+        let (cached, to_probe): (Vec<&String>, Vec<&String>) = {
+            let guard = CLOSED_PORT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+            chunk.iter().partition(|ip| {
+                guard.as_ref().and_then(|cache| cache.get(ip.as_str())).is_some_and(is_fresh)
+            })
+        };
+        CLOSED_PORT_CACHE_HITS.fetch_add(cached.len() as u64, Ordering::Relaxed);
+        CLOSED_PORT_CACHE_MISSES.fetch_add(to_probe.len() as u64, Ordering::Relaxed);
+        for ip in cached {
+            results.insert(ip.clone(), false);
+        }
+
+        // Addresses the SYN scanner could not answer for are connect probed
+        let (mut chunk_results, to_connect): (Vec<(String, bool)>, Vec<&String>) = match probe {
+            PortProbe::Syn(scanner) if !to_probe.is_empty() => {
+                let ips: Vec<String> = to_probe.iter().map(|ip| ip.to_string()).collect();
+                match scanner.probe(&ips).await {
+                    Some(answered) => {
+                        let rest = to_probe.into_iter().filter(|ip| !answered.iter().any(|(a, _)| a == *ip)).collect();
+                        (answered, rest)
+                    }
+                    None => (Vec::new(), to_probe),
+                }
+            }
+            _ => (Vec::new(), to_probe),
+        };
+        let futures: Vec<_> = to_connect.into_iter().map(|ip| {
             let ip_clone = ip.clone();
             async move {
-                let is_open = check_moonraker_port_adaptive(&ip_clone).await;
+                let is_open = match probe {
+                    PortProbe::Timeout(timeout_ms) => {
+                        let (ip, port) = split_host_port(&ip_clone);
+                        check_port_with_retry(ip, port, *timeout_ms).await
+                    }
+                    _ => check_moonraker_port_adaptive(&ip_clone).await,
+                };
                 (ip_clone, is_open)
             }
         }).collect();
-        
+
         // Execute chunk concurrently
-        let chunk_results = futures::future::join_all(futures).await;
+        chunk_results.extend(futures::future::join_all(futures).await);
+        if chunk_results.iter().any(|(_, is_open)| *is_open) {
+            empty_chunks = 0;
+        } else {
+            empty_chunks += 1;
+        }
+        {
+            let mut guard = CLOSED_PORT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+            let cache = guard.get_or_insert_with(HashMap::new);
+            for (ip, is_open) in &chunk_results {
+                if *is_open {
+                    cache.remove(ip);
+                } else {
+                    cache.insert(ip.clone(), Instant::now());
+                }
+            }
+        }
+        let probed = !chunk_results.is_empty();
         for (ip, is_open) in chunk_results {
             results.insert(ip, is_open);
         }
         
         // Small delay between chunks to be network-friendly
-        tokio::time::sleep(Duration::from_millis(10)).await;
+        if probed {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
     }
     
-    results
+    PortScanOutcome { results, stopped: None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn closed_ports_are_not_probed_again_until_cleared() {
+        clear_port_cache();
+        // Seed the cache as if a scan had just found the port closed
+        let ip = "192.0.2.1".to_string();
+        CLOSED_PORT_CACHE
+            .lock()
+            .unwrap()
+            .get_or_insert_with(HashMap::new)
+            .insert(ip.clone(), Instant::now());
+
+        let outcome = scan_ips_for_moonraker_limited(vec![ip.clone()], None, 0, &PortProbe::Adaptive).await;
+        assert_eq!(outcome.results.get(&ip), Some(&false));
+        let stats = port_cache_stats();
+        assert_eq!((stats.entries, stats.hits, stats.misses), (1, 1, 0));
+
+        clear_port_cache();
+        assert_eq!(port_cache_stats().entries, 0);
+    }
 }
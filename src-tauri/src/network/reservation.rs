@@ -0,0 +1,89 @@
+//! DHCP reservation lines
+//!
+//! A printer whose address changes breaks bookmarks, slicer upload targets
+//! and per-host settings. Reserving the address in the DHCP server pins
+//! it; this module writes the reservation for common servers from the
+//! printer's MAC address and current IP.
+
+use serde::Serialize;
+
+/// Reservation of a printer's address, in the syntax of several DHCP servers
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DhcpReservation {
+    /// Host name used in the reservation (lowercase DNS label)
+    pub hostname: String,
+    pub mac_address: String,
+    /// Address to reserve, the one the printer has now
+    pub address: String,
+    /// Line for `/etc/dnsmasq.conf` (also Pi-hole, many routers)
+    pub dnsmasq: String,
+    /// Block for ISC `dhcpd.conf`
+    pub isc_dhcp: String,
+    /// Commands for OpenWrt
+    pub openwrt: String,
+    /// Command for MikroTik RouterOS
+    pub mikrotik: String,
+}
+
+impl DhcpReservation {
+    /// Writes the reservation of an address
+    ///
+    /// # Arguments
+    /// * `hostname` - Printer name, turned into a DNS label
+    /// * `mac_address` - MAC address of the interface the address belongs to
+    /// * `address` - Address to reserve
+    pub fn new(hostname: &str, mac_address: &str, address: &str) -> Self {
+        let hostname = dns_label(hostname);
+        let mac_address = mac_address.to_lowercase();
+        Self {
+            dnsmasq: format!("dhcp-host={},{},{}", mac_address, address, hostname),
+            isc_dhcp: format!(
+                "host {} {{\n  hardware ethernet {};\n  fixed-address {};\n}}",
+                hostname, mac_address, address
+            ),
+            openwrt: format!(
+                "uci add dhcp host\nuci set dhcp.@host[-1].name='{}'\nuci set dhcp.@host[-1].mac='{}'\nuci set dhcp.@host[-1].ip='{}'\nuci commit dhcp",
+                hostname, mac_address, address
+            ),
+            mikrotik: format!(
+                "/ip dhcp-server lease add mac-address={} address={} comment={}",
+                mac_address.to_uppercase(),
+                address,
+                hostname
+            ),
+            hostname,
+            mac_address,
+            address: address.to_string(),
+        }
+    }
+}
+
+/// Turns a printer name into a host name DHCP servers accept
+///
+/// Letters and digits are kept, anything else becomes a single dash.
+fn dns_label(name: &str) -> String {
+    let mut label = String::new();
+    for c in name.trim().to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            label.push(c);
+        } else if !label.is_empty() && !label.ends_with('-') {
+            label.push('-');
+        }
+    }
+    let label: String = label.trim_end_matches('-').chars().take(63).collect();
+    if label.is_empty() { "printer".to_string() } else { label }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reservation_uses_a_clean_host_name() {
+        let reservation = DhcpReservation::new("Voron 2.4 (350)", "DC:A6:32:00:00:01", "192.168.1.50");
+        assert_eq!(reservation.hostname, "voron-2-4-350");
+        assert_eq!(reservation.dnsmasq, "dhcp-host=dc:a6:32:00:00:01,192.168.1.50,voron-2-4-350");
+        assert!(reservation.mikrotik.contains("mac-address=DC:A6:32:00:00:01"));
+        assert_eq!(DhcpReservation::new("  ", "dc:a6:32:00:00:01", "10.0.0.2").hostname, "printer");
+    }
+}
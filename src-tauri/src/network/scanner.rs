@@ -4,82 +4,172 @@
 //! Moonraker-enabled 3D printers with optimized scanning algorithms.
 
 use std::collections::HashMap;
+use tokio::sync::Mutex;
+use std::time::{Duration, Instant};
 use crate::error::MoonrakerResult;
 use crate::models::{
     HostInfo,
+    HostRelink,
+    HostWarning,
+    HostWarningKind,
+    NetworkInterface,
+    ServerInfoResult,
     SubnetConfig,
     ScanResult,
     HostStatusResponse,
+    KlippyState,
+    PrinterState,
 };
 
-use crate::api::moonraker::{check_moonraker_api, get_printer_flags, get_printer_info};
-use crate::network::port_checker::{check_moonraker_port_adaptive, scan_multiple_ips_for_moonraker};
-use crate::network::ip_utils::generate_ip_range;
-use crate::models::config::{API_SCAN_CONCURRENCY, API_SCAN_RETRY_COUNT};
+use crate::api::capabilities::{get_printer_capabilities, invalidate_printer_objects};
+use crate::api::client::{detect_web_port, has_custom_base_url};
+use crate::api::moonraker::{
+    check_moonraker_api, get_announcements, get_machine_identity, get_printer_error_message, get_printer_flags, get_printer_info,
+    get_printer_model, get_save_config_pending,
+};
+use crate::network::port_checker::{check_moonraker_port_adaptive, check_multiple_ports, scan_ips_for_moonraker_limited, PortProbe, PortScanStop};
+use crate::network::syn_scan::SynScanner;
+use crate::network::ip_utils::{generate_ip_range, generate_overlay_ip_range, host_identity, split_host_port, subnet_contains};
+use crate::network::tailscale::tailscale_peers;
+use crate::models::config::{AppSettings, ScanMethod, ScanProfile, API_SCAN_CONCURRENCY, API_SCAN_RETRY_POLICY, MOONRAKER_PORT, REMOTE_SUBNET};
+use crate::retry::retry;
 
 /// Scans a single host for Moonraker API availability with retry logic
 /// 
 /// # Arguments
-/// * `ip` - IP address to scan
+/// * `ip` - IP address to scan, with ":port" for an additional Klipper instance
 /// 
 /// # Returns
 /// * HostInfo if Moonraker is found, None otherwise
 pub async fn scan_host(ip: &str) -> Option<HostInfo> {
     // First check if port 7125 is open with adaptive timeout
-    if !check_moonraker_port_adaptive(ip).await {
+    // (hosts behind a reverse proxy are checked through the API only)
+    if !has_custom_base_url(ip) && !check_moonraker_port_adaptive(ip).await {
         return None;
     }
 
     // Then check Moonraker API with retry logic
-    for attempt in 0..API_SCAN_RETRY_COUNT {
-        match check_moonraker_api(ip).await {
-            Ok(server_info) => {
-                // Get printer hostname
-                let hostname = match get_printer_info(ip).await {
-                    Ok(printer_info) => printer_info.result.hostname.unwrap_or_else(|| ip.to_string()),
-                    Err(_) => ip.to_string(),
-                };
-
-                // Get printer flags
-                let printer_flags = match get_printer_flags(ip).await {
-                    Ok(flags) => Some(flags),
-                    Err(_) => None
-                };
-
-                // Determine printer status based on flags
-                let printer_state = if let Some(flags) = &printer_flags {
-                    flags.get_status()
-                } else {
-                    "standby"
-                };
-
-                return Some(HostInfo {
-                    id: ip.to_string(),
-                    hostname: hostname.clone(),
-                    original_hostname: hostname,
-                    ip_address: ip.to_string(),
-                    subnet: "".to_string(), // Will be filled later
-                    status: "online".to_string(),
-                    device_status: printer_state.to_string(),
-                    moonraker_version: Some(server_info.result.moonraker_version),
-                    klippy_state: Some(server_info.result.klippy_state),
-                    printer_state: Some(printer_state.to_string()),
-                    printer_flags,
-                    last_seen: Some(chrono::Utc::now().to_rfc3339()),
-                    failed_attempts: Some(0),
-                });
-            }
-            Err(_) => {
-                // If this is not the last attempt, wait a bit and try again
-                if attempt < API_SCAN_RETRY_COUNT - 1 {
-                    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
-                    continue;
-                }
-            }
+    let server_info = retry(&API_SCAN_RETRY_POLICY, || check_moonraker_api(ip)).await.ok()?;
+
+    // Get printer hostname; instances on one machine share it, so additional
+    // ones are told apart by their port
+    let (address, port) = split_host_port(ip);
+    let hostname = match get_printer_info(ip).await {
+        Ok(printer_info) => match printer_info.result.hostname {
+            Some(hostname) if port != MOONRAKER_PORT => format!("{}:{}", hostname, port),
+            Some(hostname) => hostname,
+            None => ip.to_string(),
+        },
+        Err(_) => ip.to_string(),
+    };
+
+    // Get printer flags
+    let printer_flags = match get_printer_flags(ip).await {
+        Ok(flags) => Some(flags),
+        Err(_) => None
+    };
+
+    // Determine printer status based on flags
+    let printer_state = printer_flags.as_ref().map_or(PrinterState::Standby, PrinterState::from_flags);
+
+    let error_message = if printer_state == PrinterState::Error || server_info.result.klippy_state.is_fault() {
+        get_printer_error_message(ip).await.ok().flatten()
+    } else {
+        None
+    };
+
+    // Mainsail/Fluidd may not be on port 80 (e.g. 81 or 4408 next to another UI)
+    let web_port = if has_custom_base_url(ip) { None } else { detect_web_port(ip).await };
+
+    // configfile is unavailable while Klippy is not ready
+    let printer_model = get_printer_model(ip)
+        .await
+        .ok()
+        .filter(|m| m.kinematics.is_some() || m.name.is_some());
+
+    let warnings = host_warnings(ip, &server_info.result).await;
+    let save_config_pending = get_save_config_pending(ip).await.unwrap_or(false);
+    let capabilities = get_printer_capabilities(ip).await.ok();
+
+    // Identifies the printer when its address changes or it is reachable
+    // through several interfaces
+    let identity = get_machine_identity(ip).await.unwrap_or_default();
+    let network_interface = identity
+        .interfaces
+        .iter()
+        .find(|i| i.ipv4_addresses.iter().any(|a| a == address))
+        .map(|i| i.name.clone());
+
+    Some(HostInfo {
+        id: identity.host_id(ip),
+        hostname: hostname.clone(),
+        original_hostname: hostname,
+        ip_address: ip.to_string(),
+        subnet: "".to_string(), // Will be filled later
+        status: "online".to_string(),
+        device_status: printer_state.to_string(),
+        moonraker_version: Some(server_info.result.moonraker_version),
+        klippy_state: Some(server_info.result.klippy_state),
+        printer_state: Some(printer_state),
+        printer_flags,
+        error_message,
+        last_seen: Some(chrono::Utc::now().to_rfc3339()),
+        failed_attempts: Some(0),
+        maintenance: false,
+        tags: Vec::new(),
+        web_port,
+        printer_model,
+        capabilities,
+        mac_addresses: identity.interfaces.into_iter().map(|i| i.mac_address).collect(),
+        network_interface,
+        alternate_addresses: Vec::new(),
+        warnings,
+        save_config_pending,
+    })
+}
+
+/// Collects the warnings a host's Moonraker reports
+/// 
+/// Server warnings, failed components and missing Klipper sections come
+/// with `server/info`;
+/// announcements are only included when they are of high priority and
+/// were not dismissed. Hosts whose Moonraker has no announcements
+/// component simply have none.
+/// 
+/// # Arguments
+/// * `ip` - IP address of the host
+/// * `server_info` - Its `server/info` result
+pub async fn host_warnings(ip: &str, server_info: &ServerInfoResult) -> Vec<HostWarning> {
+    let mut warnings: Vec<HostWarning> = server_info
+        .warnings
+        .iter()
+        .map(|message| HostWarning {
+            kind: HostWarningKind::Moonraker,
+            message: message.clone(),
+            url: None,
+        })
+        .chain(server_info.failed_components.iter().map(|component| HostWarning {
+            kind: HostWarningKind::FailedComponent,
+            message: component.clone(),
+            url: None,
+        }))
+        .chain(server_info.missing_klippy_requirements.iter().flatten().map(|section| HostWarning {
+            kind: HostWarningKind::MissingKlippyRequirement,
+            message: section.clone(),
+            url: None,
+        }))
+        .collect();
+
+    if server_info.components.iter().any(|c| c == "announcements") {
+        if let Ok(announcements) = get_announcements(ip).await {
+            warnings.extend(announcements.into_iter().filter(|a| a.is_important()).map(|a| HostWarning {
+                kind: HostWarningKind::Announcement,
+                message: a.title,
+                url: Some(a.url).filter(|url| !url.is_empty()),
+            }));
         }
     }
-    
-    None
+    warnings
 }
 
 /// Checks the status of a single host with improved error handling
@@ -92,151 +182,263 @@ pub async fn scan_host(ip: &str) -> Option<HostInfo> {
 pub async fn check_host_status(ip: &str) -> HostStatusResponse {
     
     // First check if port 7125 is open with adaptive timeout
-    if !check_moonraker_port_adaptive(ip).await {
+    // (hosts behind a reverse proxy are checked through the API only)
+    if !has_custom_base_url(ip) && !check_moonraker_port_adaptive(ip).await {
+        invalidate_printer_objects(ip);
         return HostStatusResponse {
             success: false,
             status: "offline".to_string(),
             device_status: Some("offline".to_string()),
             moonraker_version: None,
-            klippy_state: Some("disconnected".to_string()),
-            printer_state: Some("offline".to_string()),
+            klippy_state: Some(KlippyState::Disconnected),
+            printer_state: Some(PrinterState::Offline),
             printer_flags: None,
+            error_message: None,
+            warnings: Vec::new(),
+            save_config_pending: false,
         };
     }
     
 
     // Check Moonraker API with retry logic
-    for attempt in 0..API_SCAN_RETRY_COUNT {
-        match check_moonraker_api(ip).await {
-            Ok(server_info) => {
-                // Check if Klippy is completely disconnected (not just in error state)
-                let klippy_disconnected = server_info.result.klippy_state == "disconnected";
-                
-                if klippy_disconnected {
-                    return HostStatusResponse {
-                        success: false,
-                        status: "offline".to_string(),
-                        device_status: Some("klippy_disconnected".to_string()),
-                        moonraker_version: Some(server_info.result.moonraker_version),
-                        klippy_state: Some(server_info.result.klippy_state),
-                        printer_state: Some("offline".to_string()),
-                        printer_flags: None,
-                    };
-                }
-                
-                // Get printer flags
-                let printer_flags = match get_printer_flags(ip).await {
-                    Ok(flags) => Some(flags),
-                    Err(_e) => {
-                        None
-                    }
-                };
-
-                // Determine printer status based on flags
-                let printer_state = if let Some(flags) = &printer_flags {
-                    flags.get_status()
-                } else {
-                    "standby"
-                };
-            
-                return HostStatusResponse {
-                    success: true,
-                    status: "online".to_string(),
-                    device_status: Some(printer_state.to_string()),
-                    moonraker_version: Some(server_info.result.moonraker_version),
-                    klippy_state: Some(server_info.result.klippy_state),
-                    printer_state: Some(printer_state.to_string()),
-                    printer_flags,
-                };
-            }
-            Err(_) => {
-                // If this is not the last attempt, wait a bit and try again
-                if attempt < API_SCAN_RETRY_COUNT - 1 {
-                    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
-                    continue;
-                }
-            }
+    if let Ok(server_info) = retry(&API_SCAN_RETRY_POLICY, || check_moonraker_api(ip)).await {
+        // A restarting Klippy may come back with a different configuration
+        if server_info.result.klippy_state != KlippyState::Ready {
+            invalidate_printer_objects(ip);
+        }
+
+        // Check if Klippy is completely disconnected (not just in error state)
+        let klippy_disconnected = server_info.result.klippy_state == KlippyState::Disconnected;
+        
+        if klippy_disconnected {
+            return HostStatusResponse {
+                success: false,
+                status: "offline".to_string(),
+                device_status: Some("klippy_disconnected".to_string()),
+                moonraker_version: Some(server_info.result.moonraker_version),
+                klippy_state: Some(server_info.result.klippy_state),
+                printer_state: Some(PrinterState::Offline),
+                printer_flags: None,
+                error_message: None,
+                warnings: Vec::new(),
+                save_config_pending: false,
+            };
         }
+        
+        // Get printer flags
+        let printer_flags = match get_printer_flags(ip).await {
+            Ok(flags) => Some(flags),
+            Err(_e) => {
+                None
+            }
+        };
+
+        // Determine printer status based on flags
+        let printer_state = printer_flags.as_ref().map_or(PrinterState::Standby, PrinterState::from_flags);
+
+        let error_message = if printer_state == PrinterState::Error || server_info.result.klippy_state.is_fault() {
+            get_printer_error_message(ip).await.ok().flatten()
+        } else {
+            None
+        };
+        let warnings = host_warnings(ip, &server_info.result).await;
+        let save_config_pending = get_save_config_pending(ip).await.unwrap_or(false);
+            
+        return HostStatusResponse {
+            success: true,
+            status: "online".to_string(),
+            device_status: Some(printer_state.to_string()),
+            moonraker_version: Some(server_info.result.moonraker_version),
+            klippy_state: Some(server_info.result.klippy_state),
+            printer_state: Some(printer_state),
+            printer_flags,
+            error_message,
+            warnings,
+            save_config_pending,
+        };
     }
     
+    invalidate_printer_objects(ip);
     HostStatusResponse {
         success: false,
         status: "offline".to_string(),
         device_status: Some("offline".to_string()),
         moonraker_version: None,
-        klippy_state: Some("disconnected".to_string()),
-        printer_state: Some("offline".to_string()),
+        klippy_state: Some(KlippyState::Disconnected),
+        printer_state: Some(PrinterState::Offline),
         printer_flags: None,
+        error_message: None,
+        warnings: Vec::new(),
+        save_config_pending: false,
     }
 }
 
 /// Scans multiple subnets for Moonraker hosts with optimized parallel scanning
 /// 
+/// The scan respects the configured time budget and stops probing a subnet
+/// after a long stretch of addresses without an open Moonraker port.
+/// Hosts registered by URL are probed as well.
+/// 
 /// # Arguments
 /// * `subnets` - Vector of subnet configurations to scan
 /// 
 /// # Returns
 /// * ScanResult with discovered hosts
 pub async fn scan_network(subnets: Vec<SubnetConfig>) -> MoonrakerResult<ScanResult> {
+    let started = Instant::now();
     let mut all_hosts = Vec::new();
     let enabled_subnets: Vec<_> = subnets.into_iter().filter(|s| s.enabled).collect();
+    let settings = AppSettings::load().unwrap_or_default();
     
-    if enabled_subnets.is_empty() {
+    if enabled_subnets.is_empty() && settings.remote_hosts.is_empty() {
         return Ok(ScanResult {
             hosts: vec![],
             total_scanned: 0,
             hosts_found: 0,
             scan_duration_ms: 0,
+            stopped_early: vec![],
+            time_budget_exceeded: false,
+            relinked: vec![],
         });
     }
 
-    let mut total_ips = 0;
-    let mut ip_subnet_map = HashMap::new();
-    
-    // Count total IP addresses and build IP list
-    let mut all_ips = Vec::new();
+    let limits = &settings.scan_limits;
+    let deadline = (limits.time_budget_seconds > 0)
+        .then(|| started + Duration::from_secs(limits.time_budget_seconds));
+
+    // Online tailnet peers replace brute-force probing of overlay ranges
+    let overlay = &settings.overlay_scan;
+    let peers = if overlay.tailscale_peers && enabled_subnets.iter().any(|s| s.profile == ScanProfile::Overlay) {
+        tailscale_peers()
+            .await
+            .map_err(|e| eprintln!("Tailscale peers unavailable, probing overlay ranges: {}", e))
+            .ok()
+    } else {
+        None
+    };
+
+    // Build IP lists first so an invalid range fails before anything is probed
+    let mut subnet_ips = Vec::new();
     for subnet in &enabled_subnets {
-        match generate_ip_range(&subnet.range) {
-            Ok(ips) => {
-                total_ips += ips.len();
-                for ip in ips {
-                    ip_subnet_map.insert(ip.clone(), subnet.range.clone());
-                    all_ips.push(ip);
+        let ips = match (subnet.profile, &peers) {
+            (ScanProfile::Lan, _) => generate_ip_range(&subnet.range)?,
+            (ScanProfile::Overlay, Some(peers)) => peers
+                .iter()
+                .filter(|peer| peer.online)
+                .flat_map(|peer| peer.addresses.iter())
+                .filter(|ip| subnet_contains(&subnet.range, ip))
+                .cloned()
+                .collect(),
+            (ScanProfile::Overlay, None) => generate_overlay_ip_range(&subnet.range)?,
+        };
+        subnet_ips.push((subnet.range.clone(), subnet.profile, ips));
+    }
+
+    // Phase 1: Parallel port scanning with controlled concurrency, one subnet at a time
+    // so an empty range can be abandoned without affecting the others
+    let mut total_scanned = 0;
+    let mut ip_subnet_map = HashMap::new();
+    let mut hosts_with_open_port = Vec::new();
+    let mut stopped_early = Vec::new();
+    let mut time_budget_exceeded = false;
+    let mut use_syn_scan = settings.scan_method == ScanMethod::Syn;
+
+    for (range, profile, ips) in subnet_ips {
+        if time_budget_exceeded {
+            stopped_early.push(range);
+            continue;
+        }
+
+        // Overlay peers are sparse and slow to answer: no SYN scan, fixed
+        // longer timeout and no giving up on empty stretches
+        let overlay_scan = profile == ScanProfile::Overlay;
+        let outcome = if overlay_scan {
+            scan_ips_for_moonraker_limited(ips, deadline, 0, &PortProbe::Timeout(overlay.timeout_ms)).await
+        } else {
+            let mut probe = PortProbe::Adaptive;
+            if let (true, Some(first)) = (use_syn_scan, ips.first()) {
+                match SynScanner::open(first) {
+                    Ok(scanner) => probe = PortProbe::Syn(scanner),
+                    Err(e) => {
+                        eprintln!("SYN scan unavailable, falling back to connect scan: {}", e);
+                        use_syn_scan = false;
+                    }
                 }
             }
-            Err(e) => return Err(e),
+            scan_ips_for_moonraker_limited(ips, deadline, limits.max_empty_chunks, &probe).await
+        };
+        total_scanned += outcome.results.len();
+
+        // Machines running several Klipper instances have Moonraker on further ports
+        let instances = outcome.results.keys().filter_map(|ip| {
+            let ports: Vec<u16> = settings
+                .moonraker_ports
+                .get(ip)?
+                .iter()
+                .copied()
+                .filter(|port| *port != MOONRAKER_PORT)
+                .collect();
+            (!ports.is_empty()).then_some(async move {
+                check_multiple_ports(ip, ports)
+                    .await
+                    .into_iter()
+                    .map(|(port, is_open)| (host_identity(ip, port), is_open))
+                    .collect::<Vec<_>>()
+            })
+        });
+        let instance_results: Vec<_> = futures::future::join_all(instances).await.into_iter().flatten().collect();
+
+        for (ip, is_open) in outcome.results.into_iter().chain(instance_results) {
+            if is_open {
+                ip_subnet_map.insert(ip.clone(), range.clone());
+                hosts_with_open_port.push(ip);
+            }
+        }
+
+        match outcome.stopped {
+            Some(PortScanStop::Deadline) => {
+                time_budget_exceeded = true;
+                stopped_early.push(range);
+            }
+            Some(PortScanStop::EmptyChunks) => stopped_early.push(range),
+            None => {}
         }
     }
 
-    // Phase 1: Parallel port scanning with controlled concurrency
-    let port_scan_results = scan_multiple_ips_for_moonraker(all_ips).await;
-    
-    let hosts_with_open_port: Vec<String> = port_scan_results
-        .into_iter()
-        .filter(|(_, is_open)| *is_open)
-        .map(|(ip, _)| ip)
-        .collect();
+    // Hosts registered by URL are reached through their base URL, not found by probing
+    for host in &settings.remote_hosts {
+        if !ip_subnet_map.contains_key(host) {
+            ip_subnet_map.insert(host.clone(), REMOTE_SUBNET.to_string());
+            hosts_with_open_port.push(host.clone());
+            total_scanned += 1;
+        }
+    }
 
     // Phase 2: API scanning with controlled concurrency
-    let mut online_hosts = 0;
+    let mut found_hosts = Vec::new();
     
     // Process API checks in chunks to control concurrency
     for chunk in hosts_with_open_port.chunks(API_SCAN_CONCURRENCY) {
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            time_budget_exceeded = true;
+            break;
+        }
+
         let futures: Vec<_> = chunk.iter().map(|ip| {
             let ip_clone = ip.clone();
             async move {
+                let started = Instant::now();
                 let host_info = scan_host(&ip_clone).await;
-                (ip_clone, host_info)
+                (ip_clone, host_info, started.elapsed())
             }
         }).collect();
         
         // Execute chunk concurrently
         let chunk_results = futures::future::join_all(futures).await;
-        for (ip, host_info) in chunk_results {
+        for (ip, host_info, elapsed) in chunk_results {
             if let Some(mut host_info) = host_info {
                 host_info.subnet = ip_subnet_map.get(&ip).unwrap_or(&"".to_string()).clone();
-                all_hosts.push(host_info);
-                online_hosts += 1;
+                found_hosts.push((host_info, elapsed));
             }
         }
         
@@ -244,10 +446,271 @@ pub async fn scan_network(subnets: Vec<SubnetConfig>) -> MoonrakerResult<ScanRes
         tokio::time::sleep(tokio::time::Duration::from_millis(5)).await;
     }
 
+    // A printer with both Ethernet and Wi-Fi answers on two addresses
+    all_hosts.extend(merge_duplicate_hosts(found_hosts));
+    let online_hosts = all_hosts.len() as u32;
+
+    // Show the names and flags the user chose instead of the printer defaults
+    for host in &mut all_hosts {
+        host.apply_user_settings(&settings);
+    }
+
     Ok(ScanResult {
         hosts: all_hosts,
-        total_scanned: total_ips as u32,
+        total_scanned: total_scanned as u32,
         hosts_found: online_hosts,
-        scan_duration_ms: 0, // TODO: Calculate actual scan duration
+        scan_duration_ms: started.elapsed().as_millis() as u64,
+        stopped_early,
+        time_budget_exceeded,
+        relinked: Vec::new(),
     })
 }
+
+/// Merges hosts that share a MAC address into one record per printer
+///
+/// The address kept is the one of a wired interface if there is one, else
+/// the one that answered the scan fastest; the others are listed as
+/// alternate addresses. Hosts without known MAC addresses are kept as is,
+/// and Klipper instances on different ports of one machine stay separate.
+///
+/// # Arguments
+/// * `hosts` - Found hosts with the time their scan took
+pub fn merge_duplicate_hosts(hosts: Vec<(HostInfo, Duration)>) -> Vec<HostInfo> {
+    let mut groups: Vec<Vec<(HostInfo, Duration)>> = Vec::new();
+    for (host, elapsed) in hosts {
+        let same_printer = groups.iter_mut().find(|group| {
+            group.iter().any(|(other, _)| same_machine_instance(other, &host))
+        });
+        match same_printer {
+            Some(group) => group.push((host, elapsed)),
+            None => groups.push(vec![(host, elapsed)]),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|mut group| {
+            group.sort_by_key(|(host, elapsed)| {
+                let wireless = host.network_interface.as_deref().is_some_and(NetworkInterface::is_wireless);
+                (wireless, *elapsed)
+            });
+            let mut addresses = group.into_iter().map(|(host, _)| host);
+            let mut primary = addresses.next().expect("groups are never empty");
+            primary.alternate_addresses = addresses.map(|host| host.ip_address).collect();
+            primary
+        })
+        .collect()
+}
+
+/// Finds known printers that a scan found under a new address
+///
+/// A printer is recognized by its id, or by a shared MAC address for hosts
+/// stored before ids were stable (their id is the IP address). Addresses
+/// the printer still answers on do not count as a move. Nothing is moved
+/// while something still answers on the old address, or when several
+/// scanned hosts match, e.g. cloned SD card images sharing one id.
+///
+/// # Arguments
+/// * `known` - Hosts known before the scan
+/// * `found` - Hosts found by the scan
+pub fn find_relinks(known: &[HostInfo], found: &[HostInfo]) -> Vec<HostRelink> {
+    known
+        .iter()
+        .filter_map(|host| {
+            let still_answers = found
+                .iter()
+                .any(|f| f.ip_address == host.ip_address || f.alternate_addresses.contains(&host.ip_address));
+            if still_answers {
+                return None;
+            }
+            let mut matches = found
+                .iter()
+                .filter(|f| (f.id == host.id && f.id != host.ip_address) || same_machine_instance(f, host));
+            let printer = matches.next()?;
+            if matches.next().is_some() {
+                return None;
+            }
+            let moved = printer.ip_address != host.ip_address && !printer.alternate_addresses.contains(&host.ip_address);
+            moved.then(|| HostRelink {
+                id: printer.id.clone(),
+                hostname: host.hostname.clone(),
+                previous_address: host.ip_address.clone(),
+                address: printer.ip_address.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Checks whether two hosts are the same Klipper instance of one machine
+///
+/// The machine is recognized by a shared MAC address, the instance by its
+/// Moonraker port.
+fn same_machine_instance(a: &HostInfo, b: &HostInfo) -> bool {
+    split_host_port(&a.ip_address).1 == split_host_port(&b.ip_address).1
+        && a.mac_addresses.iter().any(|mac| b.mac_addresses.contains(mac))
+}
+
+/// Re-links known printers that came back under a new address
+///
+/// Settings stored for the old address (name, tags, maintenance, ...)
+/// move to the new one and are applied to the scanned hosts again. The
+/// moves are listed in the scan result.
+///
+/// # Arguments
+/// * `known` - Hosts known before the scan
+/// * `result` - Scan result to reconcile
+pub fn relink_moved_hosts(known: &[HostInfo], result: &mut ScanResult) {
+    let relinked = find_relinks(known, &result.hosts);
+    if relinked.is_empty() {
+        return;
+    }
+
+    // Settings that cannot be read are left alone instead of being replaced by defaults
+    let updated = AppSettings::update(|settings| {
+        for relink in &relinked {
+            settings.move_host_settings(&relink.previous_address, &relink.address);
+        }
+        settings.clone()
+    });
+    let settings = match updated {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("Moved hosts were not relinked: {}", e);
+            return;
+        }
+    };
+    for relink in &relinked {
+        println!("{} moved from {} to {}", relink.hostname, relink.previous_address, relink.address);
+    }
+    for host in &mut result.hosts {
+        host.apply_user_settings(&settings);
+    }
+    result.relinked = relinked;
+}
+
+/// Scans the subnets and merges discovered hosts into the shared list
+///
+/// Known hosts keep their display name; hosts that were not found stay in
+/// the list so the monitor can report them as offline. A printer found
+/// under a new address or under several addresses replaces every entry
+/// of the same printer.
+///
+/// # Returns
+/// * Known printers found under a new address
+pub async fn scan_and_merge(subnets: &[SubnetConfig], hosts: &Mutex<Vec<HostInfo>>) -> Vec<HostRelink> {
+    match scan_network(subnets.to_vec()).await {
+        Ok(mut result) => {
+            let mut known = hosts.lock().await;
+            relink_moved_hosts(&known, &mut result);
+            for found in result.hosts {
+                // The printer may be known under one of its other addresses
+                let addresses: Vec<String> =
+                    std::iter::once(found.ip_address.clone()).chain(found.alternate_addresses.iter().cloned()).collect();
+                let previous: Vec<&str> = result
+                    .relinked
+                    .iter()
+                    .filter(|r| r.address == found.ip_address)
+                    .map(|r| r.previous_address.as_str())
+                    .collect();
+                let same_printer = |h: &HostInfo| addresses.contains(&h.ip_address) || previous.contains(&h.ip_address.as_str());
+                match known.iter().position(same_printer) {
+                    Some(index) => {
+                        let hostname = known[index].hostname.clone();
+                        known[index] = found;
+                        known[index].hostname = hostname;
+                        let mut position = 0;
+                        known.retain(|h| {
+                            let keep = position == index || !same_printer(h);
+                            position += 1;
+                            keep
+                        });
+                    }
+                    None => {
+                        println!("Discovered {} ({})", found.hostname, found.ip_address);
+                        known.push(found);
+                    }
+                }
+            }
+            println!("Scan completed: {} known host(s)", known.len());
+            result.relinked
+        }
+        Err(e) => {
+            eprintln!("Scan failed: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host(ip: &str, interface: &str, macs: &[&str]) -> HostInfo {
+        HostInfo {
+            ip_address: ip.to_string(),
+            network_interface: Some(interface.to_string()),
+            mac_addresses: macs.iter().map(|m| m.to_string()).collect(),
+            ..HostInfo::default()
+        }
+    }
+
+    #[test]
+    fn printer_with_a_new_lease_is_relinked_by_id_or_mac() {
+        let mut moved = host("192.168.1.77", "eth0", &["dc:a6:32:00:00:01"]);
+        moved.id = "9e0e6c0b-1b4c-4f4c-a2c3-5a4f1e2d3c4b".to_string();
+        let mut known_by_id = host("192.168.1.50", "eth0", &[]);
+        known_by_id.id = moved.id.clone();
+        let mut legacy = host("192.168.1.51", "eth0", &["dc:a6:32:00:00:01"]);
+        legacy.id = legacy.ip_address.clone();
+        let unchanged = HostInfo { id: "192.168.1.60".to_string(), ..host("192.168.1.60", "eth0", &[]) };
+
+        let relinks = find_relinks(&[known_by_id, legacy, unchanged.clone()], &[moved, unchanged]);
+        let moves: Vec<(&str, &str)> =
+            relinks.iter().map(|r| (r.previous_address.as_str(), r.address.as_str())).collect();
+        assert_eq!(moves, vec![("192.168.1.50", "192.168.1.77"), ("192.168.1.51", "192.168.1.77")]);
+    }
+
+    #[test]
+    fn ambiguous_or_still_answering_printers_are_not_relinked() {
+        let id = "9e0e6c0b-1b4c-4f4c-a2c3-5a4f1e2d3c4b".to_string();
+        let known = [HostInfo { id: id.clone(), ..host("192.168.1.50", "eth0", &[]) }];
+        let clone_a = HostInfo { id: id.clone(), ..host("192.168.1.77", "eth0", &[]) };
+        let clone_b = HostInfo { id: id.clone(), ..host("192.168.1.78", "eth0", &[]) };
+        let other_on_old_address = host("192.168.1.50", "eth0", &[]);
+
+        assert!(find_relinks(&known, &[clone_a.clone(), clone_b]).is_empty());
+        assert!(find_relinks(&known, &[clone_a.clone(), other_on_old_address]).is_empty());
+        assert_eq!(find_relinks(&known, &[clone_a]).len(), 1);
+    }
+
+    #[test]
+    fn printer_on_ethernet_and_wifi_becomes_one_host() {
+        let macs = ["b8:27:eb:00:00:01", "b8:27:eb:00:00:02"];
+        let hosts = vec![
+            (host("192.168.1.51", "wlan0", &macs), Duration::from_millis(80)),
+            (host("192.168.1.60", "eth0", &["dc:a6:32:00:00:01"]), Duration::from_millis(40)),
+            (host("192.168.1.50", "eth0", &macs), Duration::from_millis(120)),
+        ];
+
+        let merged = merge_duplicate_hosts(hosts);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].ip_address, "192.168.1.50");
+        assert_eq!(merged[0].alternate_addresses, vec!["192.168.1.51"]);
+        assert!(merged[1].alternate_addresses.is_empty());
+    }
+
+    #[test]
+    fn klipper_instances_on_one_machine_stay_separate_hosts() {
+        let macs = ["dc:a6:32:00:00:01"];
+        let hosts = vec![
+            (host("192.168.1.50", "eth0", &macs), Duration::from_millis(40)),
+            (host("192.168.1.50:7126", "eth0", &macs), Duration::from_millis(40)),
+            (host("192.168.1.51:7126", "wlan0", &macs), Duration::from_millis(60)),
+        ];
+
+        let merged = merge_duplicate_hosts(hosts);
+        let addresses: Vec<&str> = merged.iter().map(|h| h.ip_address.as_str()).collect();
+        assert_eq!(addresses, vec!["192.168.1.50", "192.168.1.50:7126"]);
+        assert_eq!(merged[1].alternate_addresses, vec!["192.168.1.51:7126"]);
+    }
+}
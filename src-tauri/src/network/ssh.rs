@@ -0,0 +1,73 @@
+//! Remote commands over SSH
+//!
+//! Runs single commands on a printer's machine with the system `ssh` client.
+//! Only key-based login works: password prompts are disabled so a missing
+//! key fails instead of hanging.
+
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+use crate::error::{MoonrakerError, MoonrakerResult};
+use crate::network::ip_utils::split_host_port;
+
+/// Runs a command on a host over SSH
+///
+/// # Arguments
+/// * `host` - Host IP address, optionally with a Moonraker port
+/// * `user` - SSH user
+/// * `command` - Shell command to run
+/// * `timeout` - Time the command may take, including connecting
+///
+/// # Returns
+/// * Standard output of the command
+pub async fn run_ssh_command(host: &str, user: &str, command: &str, timeout: Duration) -> MoonrakerResult<String> {
+    if !is_valid_ssh_user(user) {
+        return Err(MoonrakerError::SystemCommand(format!("Invalid SSH user: {}", user)));
+    }
+    // Additional Klipper instances share the machine's SSH server
+    let address = split_host_port(host).0;
+    let output = Command::new("ssh")
+        .args(["-o", "BatchMode=yes", "-o", "ConnectTimeout=10", "--"])
+        .arg(format!("{}@{}", user, address))
+        .arg(command)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(timeout, output)
+        .await
+        .map_err(|_| MoonrakerError::Timeout(timeout))?
+        .map_err(|e| MoonrakerError::SystemCommand(format!("Failed to run ssh: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(MoonrakerError::SystemCommand(format!("{} failed: {}", command, stderr.trim())));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Checks that a user name is a plain POSIX login name
+///
+/// Names like `-oProxyCommand=...` would otherwise be read by `ssh` as an
+/// option, and `@` or spaces would change the destination.
+///
+/// # Arguments
+/// * `user` - SSH user
+pub fn is_valid_ssh_user(user: &str) -> bool {
+    let mut chars = user.chars();
+    chars.next().is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '.' | '-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_plain_login_names_are_valid_users() {
+        for user in ["pi", "biqu", "_klipper", "mks-2.user"] {
+            assert!(is_valid_ssh_user(user), "{}", user);
+        }
+        for user in ["", "-oProxyCommand=touch /tmp/x", "Pi", "pi@evil", "pi user", "1pi"] {
+            assert!(!is_valid_ssh_user(user), "{}", user);
+        }
+    }
+}
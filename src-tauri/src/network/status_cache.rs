@@ -0,0 +1,120 @@
+//! Shared host status cache
+//!
+//! The desktop UI, the Telegram bot, the print queue and the background
+//! monitor all ask for host status. This cache keeps the last response per
+//! host for a short, configurable time and makes concurrent callers for the
+//! same host wait for one request instead of each sending their own. With a
+//! TTL of 0 nothing is kept, but callers arriving while a request runs still
+//! share its result.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use crate::models::config::{AppSettings, DEFAULT_STATUS_CACHE_TTL_MS};
+use crate::models::HostStatusResponse;
+use crate::network::scanner::check_host_status;
+
+/// Marker for "TTL not loaded from settings yet"
+const TTL_UNSET: u64 = u64::MAX;
+
+/// Cached status time-to-live in milliseconds, loaded from settings on first use
+static STATUS_CACHE_TTL_MS: AtomicU64 = AtomicU64::new(TTL_UNSET);
+
+/// Cache slot of one host; its lock is held while the status is being fetched
+type StatusSlot = Arc<Mutex<Option<CachedStatus>>>;
+
+/// Per-host slots keyed by IP address
+static STATUS_CACHE: StdMutex<Option<HashMap<String, StatusSlot>>> = StdMutex::new(None);
+
+/// Status response with the time it was fetched
+struct CachedStatus {
+    fetched_at: Instant,
+    status: HostStatusResponse,
+}
+
+/// Result of a cached status lookup
+pub struct StatusLookup {
+    /// Host status
+    pub status: HostStatusResponse,
+    /// Request latency in milliseconds, None when the status came from the cache
+    pub latency_ms: Option<u64>,
+}
+
+/// Gets the status of a host, fetching it only when the cached one is stale
+///
+/// Callers asking for the same host while a request is running wait for
+/// that request and share its result, whatever the TTL.
+///
+/// # Arguments
+/// * `ip` - Host IP address
+///
+/// # Returns
+/// * Host status and, for fresh fetches, the request latency
+pub async fn get_host_status(ip: &str) -> StatusLookup {
+    let ttl = status_cache_ttl();
+    let requested_at = Instant::now();
+    let slot = host_slot(ip);
+    let mut cached = slot.lock().await;
+
+    if let Some(entry) = cached.as_ref() {
+        // A status fetched while this caller waited for the slot is as fresh as its own would be
+        if entry.fetched_at >= requested_at || entry.fetched_at.elapsed() < ttl {
+            return StatusLookup {
+                status: entry.status.clone(),
+                latency_ms: None,
+            };
+        }
+    }
+
+    let started = Instant::now();
+    let status = check_host_status(ip).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    *cached = Some(CachedStatus {
+        fetched_at: Instant::now(),
+        status: status.clone(),
+    });
+
+    StatusLookup {
+        status,
+        latency_ms: Some(latency_ms),
+    }
+}
+
+/// Drops the cached status of a host, e.g. after a command changed its state
+pub fn invalidate_host_status(ip: &str) {
+    if let Ok(mut cache) = STATUS_CACHE.lock() {
+        if let Some(slots) = cache.as_mut() {
+            slots.remove(ip);
+        }
+    }
+}
+
+/// Replaces the cached TTL, e.g. after settings were saved
+pub fn set_status_cache_ttl(ttl_ms: u64) {
+    STATUS_CACHE_TTL_MS.store(ttl_ms, Ordering::Relaxed);
+}
+
+/// Gets the current cache TTL
+fn status_cache_ttl() -> Duration {
+    let mut ttl_ms = STATUS_CACHE_TTL_MS.load(Ordering::Relaxed);
+    if ttl_ms == TTL_UNSET {
+        ttl_ms = AppSettings::load()
+            .map(|s| s.status_cache_ttl_ms)
+            .unwrap_or(DEFAULT_STATUS_CACHE_TTL_MS);
+        set_status_cache_ttl(ttl_ms);
+    }
+    Duration::from_millis(ttl_ms)
+}
+
+/// Gets (or creates) the cache slot of a host
+fn host_slot(ip: &str) -> StatusSlot {
+    let mut cache = STATUS_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache
+        .get_or_insert_with(HashMap::new)
+        .entry(ip.to_string())
+        .or_insert_with(|| Arc::new(Mutex::new(None)))
+        .clone()
+}
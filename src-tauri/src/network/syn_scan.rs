@@ -0,0 +1,214 @@
+//! SYN port scanning
+//!
+//! Sends bare TCP SYN packets from a raw socket and collects the SYN-ACK
+//! replies instead of completing a connection to every address, which
+//! probes large ranges an order of magnitude faster than connect scanning.
+//! Raw sockets need root or CAP_NET_RAW, and only Linux hands TCP replies
+//! to them, so callers fall back to connect scanning when a scanner cannot
+//! be opened.
+//!
+//! A scanner probes one chunk of addresses at a time for the port scan in
+//! [`crate::network::port_checker`], so closed port caching, the deadline
+//! and giving up on empty ranges work as for connect scanning. Addresses no
+//! packet could be sent to, and chunks probed after the socket stopped
+//! receiving, are connect scanned instead of being reported closed.
+
+use crate::models::config::MOONRAKER_PORT;
+
+/// Checks whether SYN scanning can be used (Linux with raw socket permissions)
+pub fn syn_scan_available() -> bool {
+    platform::open_channel().is_ok()
+}
+
+/// Raw socket probing the Moonraker port of the addresses in one subnet
+pub struct SynScanner {
+    session: platform::Session,
+}
+
+impl SynScanner {
+    /// Opens a raw socket for scanning the subnet of an address
+    ///
+    /// Packets are sent from the local address the kernel routes to
+    /// `target` through, so each subnet is scanned from its own interface.
+    ///
+    /// # Arguments
+    /// * `target` - IP address in the subnet to scan
+    pub fn open(target: &str) -> Result<Self, String> {
+        platform::Session::open(target, MOONRAKER_PORT).map(|session| Self { session })
+    }
+
+    /// Sends SYN packets to addresses and waits for their replies
+    ///
+    /// # Arguments
+    /// * `ips` - IP addresses to probe
+    ///
+    /// # Returns
+    /// * Port status of the addresses a packet was sent to, None when the
+    ///   socket stopped receiving and the replies cannot be trusted
+    pub async fn probe(&self, ips: &[String]) -> Option<Vec<(String, bool)>> {
+        self.session.probe(ips).await
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::collections::HashSet;
+    use std::io::ErrorKind;
+    use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use pnet_packet::ip::IpNextHeaderProtocols;
+    use pnet_packet::tcp::{ipv4_checksum, MutableTcpPacket, TcpFlags, TcpPacket};
+    use pnet_transport::TransportChannelType::Layer4;
+    use pnet_transport::TransportProtocol::Ipv4;
+    use pnet_transport::{tcp_packet_iter, transport_channel, TransportReceiver, TransportSender};
+    use crate::models::config::{SYN_SCAN_BATCH_DELAY_MS, SYN_SCAN_BATCH_SIZE, SYN_SCAN_REPLY_WAIT_MS};
+
+    pub fn open_channel() -> Result<(TransportSender, TransportReceiver), String> {
+        transport_channel(4096, Layer4(Ipv4(IpNextHeaderProtocols::Tcp))).map_err(|e| match e.kind() {
+            ErrorKind::PermissionDenied => "SYN scan needs root or the CAP_NET_RAW capability".to_string(),
+            _ => format!("Failed to open raw socket: {}", e),
+        })
+    }
+
+    /// Raw socket with a thread collecting the addresses that answered
+    pub struct Session {
+        sender: Arc<Mutex<TransportSender>>,
+        source: Ipv4Addr,
+        source_port: u16,
+        port: u16,
+        open: Arc<Mutex<HashSet<Ipv4Addr>>>,
+        stop: Arc<AtomicBool>,
+        alive: Arc<AtomicBool>,
+    }
+
+    impl Session {
+        pub fn open(target: &str, port: u16) -> Result<Self, String> {
+            let target: Ipv4Addr = target.parse().map_err(|_| "SYN scan supports IPv4 only".to_string())?;
+            let source = source_address(target, port)?;
+            let (sender, mut receiver) = open_channel()?;
+            let source_port = 40000 + (std::process::id() % 20000) as u16;
+
+            let open = Arc::new(Mutex::new(HashSet::new()));
+            let stop = Arc::new(AtomicBool::new(false));
+            let alive = Arc::new(AtomicBool::new(true));
+            let (receiver_open, receiver_stop, receiver_alive) = (open.clone(), stop.clone(), alive.clone());
+            std::thread::spawn(move || {
+                let mut packets = tcp_packet_iter(&mut receiver);
+                while !receiver_stop.load(Ordering::Relaxed) {
+                    match packets.next_with_timeout(Duration::from_millis(100)) {
+                        Ok(Some((packet, IpAddr::V4(address)))) => {
+                            let flags = packet.get_flags();
+                            if packet.get_source() == port
+                                && packet.get_destination() == source_port
+                                && flags & TcpFlags::SYN != 0
+                                && flags & TcpFlags::ACK != 0
+                            {
+                                receiver_open.lock().unwrap().insert(address);
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            eprintln!("SYN scan receive failed: {}", e);
+                            break;
+                        }
+                    }
+                }
+                receiver_alive.store(false, Ordering::Relaxed);
+            });
+
+            Ok(Self { sender: Arc::new(Mutex::new(sender)), source, source_port, port, open, stop, alive })
+        }
+
+        pub async fn probe(&self, ips: &[String]) -> Option<Vec<(String, bool)>> {
+            if !self.alive.load(Ordering::Relaxed) {
+                return None;
+            }
+            let targets: Vec<Ipv4Addr> = ips.iter().filter_map(|ip| ip.parse().ok()).collect();
+            let (sender, source, source_port, port) = (self.sender.clone(), self.source, self.source_port, self.port);
+            let sent = tokio::task::spawn_blocking(move || {
+                let mut sender = sender.lock().unwrap();
+                let mut sent = Vec::with_capacity(targets.len());
+                for (index, target) in targets.into_iter().enumerate() {
+                    let mut buffer = syn_packet(source, source_port, target, port);
+                    let packet = MutableTcpPacket::new(&mut buffer).expect("buffer holds a TCP header");
+                    match sender.send_to(packet, IpAddr::V4(target)) {
+                        Ok(_) => sent.push(target),
+                        Err(e) => eprintln!("Failed to send SYN to {}: {}", target, e),
+                    }
+                    // Pace the packets so switches and Wi-Fi access points keep up
+                    if (index + 1) % SYN_SCAN_BATCH_SIZE == 0 {
+                        std::thread::sleep(Duration::from_millis(SYN_SCAN_BATCH_DELAY_MS));
+                    }
+                }
+                sent
+            })
+            .await
+            .unwrap_or_default();
+
+            tokio::time::sleep(Duration::from_millis(SYN_SCAN_REPLY_WAIT_MS)).await;
+            // Replies may have been missed if the receiver stopped while waiting
+            if !self.alive.load(Ordering::Relaxed) {
+                return None;
+            }
+            let open = self.open.lock().unwrap();
+            Some(sent.into_iter().map(|ip| (ip.to_string(), open.contains(&ip))).collect())
+        }
+    }
+
+    impl Drop for Session {
+        fn drop(&mut self) {
+            // The receiver thread notices within its receive timeout
+            self.stop.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Finds the local address the kernel routes to a target through
+    fn source_address(target: Ipv4Addr, port: u16) -> Result<Ipv4Addr, String> {
+        let socket = UdpSocket::bind("0.0.0.0:0").map_err(|e| format!("Failed to find source address: {}", e))?;
+        socket
+            .connect((target, port))
+            .map_err(|e| format!("Failed to find source address: {}", e))?;
+        match socket.local_addr().map_err(|e| format!("Failed to find source address: {}", e))?.ip() {
+            IpAddr::V4(address) => Ok(address),
+            IpAddr::V6(_) => Err("SYN scan supports IPv4 only".to_string()),
+        }
+    }
+
+    fn syn_packet(source: Ipv4Addr, source_port: u16, destination: Ipv4Addr, port: u16) -> Vec<u8> {
+        let mut buffer = vec![0u8; TcpPacket::minimum_packet_size()];
+        let mut packet = MutableTcpPacket::new(&mut buffer).expect("buffer holds a TCP header");
+        packet.set_source(source_port);
+        packet.set_destination(port);
+        packet.set_sequence(u32::from(destination));
+        packet.set_data_offset(5);
+        packet.set_flags(TcpFlags::SYN);
+        packet.set_window(64240);
+        let checksum = ipv4_checksum(&packet.to_immutable(), &source, &destination);
+        packet.set_checksum(checksum);
+        buffer
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod platform {
+    const UNSUPPORTED: &str = "SYN scan is only supported on Linux";
+
+    pub fn open_channel() -> Result<(), String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    /// Never opened on this platform
+    pub enum Session {}
+
+    impl Session {
+        pub fn open(_target: &str, _port: u16) -> Result<Self, String> {
+            Err(UNSUPPORTED.to_string())
+        }
+
+        pub async fn probe(&self, _ips: &[String]) -> Option<Vec<(String, bool)>> {
+            match *self {}
+        }
+    }
+}
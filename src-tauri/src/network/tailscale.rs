@@ -0,0 +1,96 @@
+//! Tailscale peer discovery
+//!
+//! Overlay networks are too large and too sparse to probe address by
+//! address. When Tailscale runs on this machine, its local API already knows
+//! every peer of the tailnet; `tailscale status --json` reads it.
+
+use std::process::Stdio;
+use std::time::Duration;
+use serde::Deserialize;
+use tokio::process::Command;
+use crate::error::{MoonrakerError, MoonrakerResult};
+
+/// Time the local Tailscale client may take to answer
+const TAILSCALE_STATUS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Peer of the tailnet
+#[derive(Debug, Clone, PartialEq)]
+pub struct TailscalePeer {
+    pub hostname: String,
+    /// Tailscale addresses (IPv4 and IPv6)
+    pub addresses: Vec<String>,
+    pub online: bool,
+}
+
+#[derive(Deserialize)]
+struct TailscaleStatus {
+    #[serde(rename = "Peer", default)]
+    peers: std::collections::HashMap<String, TailscalePeerStatus>,
+}
+
+#[derive(Deserialize)]
+struct TailscalePeerStatus {
+    #[serde(rename = "HostName", default)]
+    hostname: String,
+    #[serde(rename = "TailscaleIPs", default)]
+    addresses: Vec<String>,
+    #[serde(rename = "Online", default)]
+    online: bool,
+}
+
+/// Parses the output of `tailscale status --json`
+///
+/// # Returns
+/// * Peers of the tailnet, ordered by hostname
+pub fn parse_tailscale_status(json: &str) -> MoonrakerResult<Vec<TailscalePeer>> {
+    let status: TailscaleStatus = serde_json::from_str(json)?;
+    let mut peers: Vec<TailscalePeer> = status
+        .peers
+        .into_values()
+        .map(|peer| TailscalePeer { hostname: peer.hostname, addresses: peer.addresses, online: peer.online })
+        .collect();
+    peers.sort_by(|a, b| a.hostname.cmp(&b.hostname));
+    Ok(peers)
+}
+
+/// Lists the peers of the tailnet this machine is logged in to
+///
+/// # Returns
+/// * Peers known to the local Tailscale client, or an error if Tailscale is not installed or not running
+pub async fn tailscale_peers() -> MoonrakerResult<Vec<TailscalePeer>> {
+    let output = Command::new("tailscale")
+        .args(["status", "--json"])
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(TAILSCALE_STATUS_TIMEOUT, output)
+        .await
+        .map_err(|_| MoonrakerError::Timeout(TAILSCALE_STATUS_TIMEOUT))?
+        .map_err(|e| MoonrakerError::SystemCommand(format!("Failed to run tailscale: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(MoonrakerError::SystemCommand(format!("tailscale status failed: {}", stderr.trim())));
+    }
+    parse_tailscale_status(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tailnet_peers() {
+        let json = r#"{
+            "Self": { "HostName": "desktop", "TailscaleIPs": ["100.64.0.1"] },
+            "Peer": {
+                "nodekey:b": { "HostName": "voron", "TailscaleIPs": ["100.101.102.103", "fd7a:115c:a1e0::1"], "Online": true },
+                "nodekey:a": { "HostName": "ender", "TailscaleIPs": ["100.90.1.2"], "Online": false }
+            }
+        }"#;
+        let peers = parse_tailscale_status(json).unwrap();
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers[0], TailscalePeer { hostname: "ender".to_string(), addresses: vec!["100.90.1.2".to_string()], online: false });
+        assert!(peers[1].online && peers[1].addresses[0] == "100.101.102.103");
+        assert!(parse_tailscale_status(r#"{"BackendState": "Stopped"}"#).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+//! Network change detection
+//!
+//! Compares the machine's IPv4 networks between monitoring cycles. A change
+//! of subnet means the machine joined another network (new Wi-Fi, cable
+//! plugged in, VPN); moving between interfaces of the same subnet does not
+//! count. Hosts in networks the machine has left are not reachable and are
+//! not monitored until the network is back.
+
+use std::collections::HashSet;
+use std::net::{IpAddr, Ipv4Addr};
+use ipnetwork::Ipv4Network;
+use crate::models::{LocalNetwork, NetworkChange};
+use crate::network::ip_utils::split_host_port;
+
+/// Interface name prefixes of container and VM bridges, which are not real networks
+const VIRTUAL_INTERFACE_PREFIXES: [&str; 6] = ["docker", "br-", "veth", "virbr", "vmnet", "vboxnet"];
+
+/// Lists the IPv4 networks the machine is connected to
+///
+/// Loopback, link-local, point-to-point (/32) and virtual bridge
+/// interfaces are left out, as are networks larger than /16.
+pub fn local_networks() -> Vec<LocalNetwork> {
+    let interfaces = match if_addrs::get_if_addrs() {
+        Ok(interfaces) => interfaces,
+        Err(e) => {
+            eprintln!("Failed to list network interfaces: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut networks: Vec<LocalNetwork> = interfaces
+        .into_iter()
+        .filter(|interface| !interface.is_loopback() && !interface.is_link_local())
+        .filter(|interface| !VIRTUAL_INTERFACE_PREFIXES.iter().any(|p| interface.name.starts_with(p)))
+        .filter_map(|interface| match interface.addr {
+            if_addrs::IfAddr::V4(addr) => {
+                let network = Ipv4Network::with_netmask(addr.ip, addr.netmask).ok()?;
+                (16..32).contains(&network.prefix()).then(|| LocalNetwork {
+                    interface: interface.name,
+                    address: addr.ip.to_string(),
+                    subnet: format!("{}/{}", network.network(), network.prefix()),
+                })
+            }
+            if_addrs::IfAddr::V6(_) => None,
+        })
+        .collect();
+    networks.sort_by(|a, b| a.subnet.cmp(&b.subnet));
+    networks
+}
+
+/// Remembers the networks of the previous check
+#[derive(Debug, Default)]
+pub struct NetworkWatcher {
+    /// Networks seen in the last check (None before the first one)
+    current: Option<Vec<LocalNetwork>>,
+    /// Subnets the machine has left and not rejoined
+    left_subnets: HashSet<String>,
+    /// Last change, until the user dismisses it
+    last_change: Option<NetworkChange>,
+}
+
+impl NetworkWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the current networks and reports what changed since the previous call
+    ///
+    /// The first call only records the networks.
+    pub fn update(&mut self, networks: Vec<LocalNetwork>) -> Option<NetworkChange> {
+        let previous = self.current.replace(networks.clone())?;
+
+        let subnets = |list: &[LocalNetwork]| list.iter().map(|n| n.subnet.clone()).collect::<HashSet<_>>();
+        let (before, after) = (subnets(&previous), subnets(&networks));
+        let joined: Vec<LocalNetwork> = networks.into_iter().filter(|n| !before.contains(&n.subnet)).collect();
+        let left: Vec<LocalNetwork> = previous.into_iter().filter(|n| !after.contains(&n.subnet)).collect();
+        if joined.is_empty() && left.is_empty() {
+            return None;
+        }
+
+        for network in &joined {
+            self.left_subnets.remove(&network.subnet);
+        }
+        self.left_subnets.extend(left.iter().map(|n| n.subnet.clone()));
+
+        let change = NetworkChange {
+            detected_at: chrono::Utc::now(),
+            joined,
+            left,
+        };
+        self.last_change = Some(change.clone());
+        Some(change)
+    }
+
+    /// Checks whether a host belongs to a network the machine has left
+    pub fn is_on_left_network(&self, ip: &str) -> bool {
+        let Ok(IpAddr::V4(ip)) = split_host_port(ip).0.parse::<IpAddr>() else {
+            return false;
+        };
+        self.left_subnets.iter().any(|subnet| contains(subnet, ip))
+    }
+
+    /// Gets the last change that was not dismissed
+    pub fn last_change(&self) -> Option<NetworkChange> {
+        self.last_change.clone()
+    }
+
+    /// Forgets the last change, e.g. after the user acted on it
+    pub fn dismiss_change(&mut self) {
+        self.last_change = None;
+    }
+}
+
+fn contains(subnet: &str, ip: Ipv4Addr) -> bool {
+    subnet.parse::<Ipv4Network>().map(|network| network.contains(ip)).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network(interface: &str, subnet: &str) -> LocalNetwork {
+        LocalNetwork {
+            interface: interface.to_string(),
+            address: subnet.replace(".0/24", ".10"),
+            subnet: subnet.to_string(),
+        }
+    }
+
+    #[test]
+    fn joining_another_subnet_pauses_hosts_of_the_old_one() {
+        let mut watcher = NetworkWatcher::new();
+        assert!(watcher.update(vec![network("wlan0", "192.168.1.0/24")]).is_none());
+
+        // Same subnet on another interface is not a change
+        assert!(watcher.update(vec![network("eth0", "192.168.1.0/24")]).is_none());
+
+        let change = watcher.update(vec![network("wlan0", "10.0.5.0/24")]).unwrap();
+        assert_eq!(change.joined[0].subnet, "10.0.5.0/24");
+        assert_eq!(change.left[0].subnet, "192.168.1.0/24");
+        assert!(watcher.is_on_left_network("192.168.1.50"));
+        assert!(!watcher.is_on_left_network("10.0.5.20"));
+
+        watcher.update(vec![network("wlan0", "192.168.1.0/24")]);
+        assert!(!watcher.is_on_left_network("192.168.1.50"));
+        assert!(watcher.is_on_left_network("10.0.5.20"));
+    }
+}
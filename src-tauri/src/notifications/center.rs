@@ -0,0 +1,166 @@
+//! In-app notification center
+//!
+//! Every system notification is also kept in a list the frontend shows behind
+//! a bell, with read and unread state, so a toast that was missed, dismissed
+//! or held back during Do Not Disturb is not lost. The list lives as long as
+//! the app runs and keeps the latest entries.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Notifications kept in the list; older ones are dropped
+const MAX_CENTER_NOTIFICATIONS: usize = 200;
+
+static CENTER: Mutex<NotificationCenter> = Mutex::new(NotificationCenter::new());
+
+/// Notification as listed in the notification center
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CenterNotification {
+    pub id: u64,
+    pub title: String,
+    pub body: String,
+    /// Host IP address, when the notification is about a host
+    pub host: Option<String>,
+    pub critical: bool,
+    pub created_at: DateTime<Utc>,
+    pub read: bool,
+}
+
+/// Latest notifications, oldest first
+#[derive(Debug, Default)]
+pub struct NotificationCenter {
+    entries: VecDeque<CenterNotification>,
+    next_id: u64,
+}
+
+impl NotificationCenter {
+    pub const fn new() -> Self {
+        Self { entries: VecDeque::new(), next_id: 1 }
+    }
+
+    /// Adds an unread notification
+    pub fn add(&mut self, title: &str, body: &str, host: Option<&str>, critical: bool) -> CenterNotification {
+        let notification = CenterNotification {
+            id: self.next_id,
+            title: title.to_string(),
+            body: body.to_string(),
+            host: host.map(str::to_string),
+            critical,
+            created_at: Utc::now(),
+            read: false,
+        };
+        self.next_id += 1;
+        if self.entries.len() >= MAX_CENTER_NOTIFICATIONS {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(notification.clone());
+        notification
+    }
+
+    /// Lists the notifications, newest first
+    pub fn list(&self, unread_only: bool) -> Vec<CenterNotification> {
+        self.entries.iter().rev().filter(|n| !unread_only || !n.read).cloned().collect()
+    }
+
+    pub fn unread_count(&self) -> usize {
+        self.entries.iter().filter(|n| !n.read).count()
+    }
+
+    /// Marks a notification as read
+    ///
+    /// # Returns
+    /// * Whether the notification is in the list
+    pub fn mark_read(&mut self, id: u64) -> bool {
+        match self.entries.iter_mut().find(|n| n.id == id) {
+            Some(notification) => {
+                notification.read = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Marks every notification as read
+    ///
+    /// # Returns
+    /// * Number of notifications that were unread
+    pub fn mark_all_read(&mut self) -> usize {
+        let unread = self.unread_count();
+        self.entries.iter_mut().for_each(|n| n.read = true);
+        unread
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Keeps a notification in the notification center
+///
+/// # Arguments
+/// * `title` - Notification title
+/// * `body` - Notification body text
+/// * `host` - Host IP address, when the notification is about a host
+/// * `critical` - Whether it was sent as critical
+pub fn record_notification(title: &str, body: &str, host: Option<&str>, critical: bool) -> CenterNotification {
+    CENTER.lock().unwrap().add(title, body, host, critical)
+}
+
+/// Lists the notifications of the notification center, newest first
+pub fn list_notifications(unread_only: bool) -> Vec<CenterNotification> {
+    CENTER.lock().unwrap().list(unread_only)
+}
+
+/// Number of unread notifications
+pub fn unread_notification_count() -> usize {
+    CENTER.lock().unwrap().unread_count()
+}
+
+/// Marks a notification as read
+///
+/// # Returns
+/// * Whether the notification is in the list
+pub fn mark_notification_read(id: u64) -> bool {
+    CENTER.lock().unwrap().mark_read(id)
+}
+
+/// Marks every notification as read
+///
+/// # Returns
+/// * Number of notifications that were unread
+pub fn mark_all_notifications_read() -> usize {
+    CENTER.lock().unwrap().mark_all_read()
+}
+
+/// Removes every notification from the notification center
+pub fn clear_notifications() {
+    CENTER.lock().unwrap().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notifications_are_listed_newest_first_with_read_state() {
+        let mut center = NotificationCenter::new();
+        let first = center.add("Status changed", "voron: printing → paused", Some("192.168.1.50"), false);
+        let second = center.add("Heater fault", "ender: extruder", Some("192.168.1.51"), true);
+        assert_eq!(center.list(false).iter().map(|n| n.id).collect::<Vec<_>>(), vec![second.id, first.id]);
+
+        assert!(center.mark_read(first.id));
+        assert!(!center.mark_read(999));
+        assert_eq!(center.list(true), vec![second.clone()]);
+        assert_eq!(center.mark_all_read(), 1);
+        assert_eq!(center.unread_count(), 0);
+
+        for i in 0..MAX_CENTER_NOTIFICATIONS {
+            center.add("Print finished", &format!("job {}", i), None, false);
+        }
+        let listed = center.list(false);
+        assert_eq!(listed.len(), MAX_CENTER_NOTIFICATIONS);
+        assert!(listed.iter().all(|n| n.id != first.id && n.id != second.id));
+    }
+}
@@ -0,0 +1,311 @@
+//! Do Not Disturb awareness
+//!
+//! While the system is in a Do Not Disturb or focus mode, notifications that
+//! are not critical are held back and shown as one summary once the mode
+//! ends. Critical notifications are still sent right away; whether they get
+//! through depends on the platform: Linux notification servers usually show
+//! critical urgency anyway, and Windows shows them as reminders.
+//!
+//! Detection, where the platform allows it:
+//! * Linux - the `Inhibited` property of the notification server (KDE and
+//!   others), or GNOME's "show banners" setting
+//! * macOS - focus modes turned on by hand (scheduled ones are not detected).
+//!   The focus state is kept in a folder protected by macOS privacy
+//!   controls; it can only be read once the app was granted Full Disk Access
+//!   in System Settings → Privacy & Security, and is unknown otherwise
+//! * Windows - full-screen games and videos, presentations and quiet hours
+//!   (Focus Assist); other full-screen windows do not count
+//!
+//! Detecting runs external commands on Linux, so the result is kept for a
+//! few seconds and refreshed off the async worker threads.
+
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use crate::i18n::tf;
+use crate::models::config::AppSettings;
+
+/// Notifications kept while Do Not Disturb is on; older ones are dropped
+const MAX_DEFERRED_NOTIFICATIONS: usize = 50;
+
+/// Notifications listed in the summary; the rest are counted
+const SUMMARY_LINES: usize = 5;
+
+/// Marker for "setting not loaded yet"
+const RESPECT_UNSET: u8 = u8::MAX;
+
+/// Whether notifications wait for Do Not Disturb to end, loaded from settings on first use
+static RESPECT_DO_NOT_DISTURB: AtomicU8 = AtomicU8::new(RESPECT_UNSET);
+
+static DEFERRED: Mutex<DeferredNotifications> = Mutex::new(DeferredNotifications::new());
+
+/// How long a detected Do Not Disturb state is used before it is detected again
+const DETECTION_TTL: Duration = Duration::from_secs(5);
+
+/// Latest detected Do Not Disturb state and when it was detected
+static DETECTED: Mutex<Option<(Instant, Option<bool>)>> = Mutex::new(None);
+
+/// Whether a detection runs in the background
+static DETECTING: AtomicBool = AtomicBool::new(false);
+
+/// Do Not Disturb as seen by the app
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DoNotDisturbState {
+    /// Whether Do Not Disturb is on; None when it cannot be detected
+    pub active: Option<bool>,
+    /// Whether notifications wait for Do Not Disturb to end
+    pub respected: bool,
+    /// Notifications waiting for Do Not Disturb to end
+    pub deferred: usize,
+}
+
+/// Notification held back while Do Not Disturb is on
+#[derive(Debug, Clone, PartialEq)]
+struct DeferredNotification {
+    title: String,
+    body: String,
+    /// Host IP address of a notification that replaces the host's previous one
+    host: Option<String>,
+}
+
+/// Notifications held back while Do Not Disturb is on
+#[derive(Debug, Default)]
+struct DeferredNotifications {
+    pending: Vec<DeferredNotification>,
+    /// Notifications dropped because too many were waiting
+    dropped: usize,
+}
+
+impl DeferredNotifications {
+    const fn new() -> Self {
+        Self { pending: Vec::new(), dropped: 0 }
+    }
+
+    fn len(&self) -> usize {
+        self.pending.len() + self.dropped
+    }
+
+    /// Keeps a notification; a host's notification replaces its earlier one
+    fn push(&mut self, title: &str, body: &str, host: Option<&str>) {
+        if let Some(host) = host {
+            self.pending.retain(|n| n.host.as_deref() != Some(host));
+        }
+        if self.pending.len() >= MAX_DEFERRED_NOTIFICATIONS {
+            self.pending.remove(0);
+            self.dropped += 1;
+        }
+        self.pending.push(DeferredNotification { title: title.to_string(), body: body.to_string(), host: host.map(str::to_string) });
+    }
+
+    /// Takes the waiting notifications as one summary
+    ///
+    /// # Returns
+    /// * Title and body of the summary; None when nothing is waiting
+    fn take_summary(&mut self) -> Option<(String, String)> {
+        if self.len() == 0 {
+            return None;
+        }
+        let title = tf("notify.deferred.title", &[("count", &self.len())]);
+        let mut lines: Vec<String> = self
+            .pending
+            .iter()
+            .rev()
+            .take(SUMMARY_LINES)
+            .map(|n| format!("• {}: {}", n.title, n.body.lines().next().unwrap_or_default()))
+            .collect();
+        let more = self.len() - lines.len();
+        if more > 0 {
+            lines.push(tf("notify.deferred.more", &[("count", &more)]));
+        }
+        *self = Self::new();
+        Some((title, lines.join("\n")))
+    }
+}
+
+/// Checks whether notifications wait for Do Not Disturb to end
+pub fn respects_do_not_disturb() -> bool {
+    match RESPECT_DO_NOT_DISTURB.load(Ordering::Relaxed) {
+        RESPECT_UNSET => {
+            let respect = AppSettings::load().map(|s| s.notifications.respect_do_not_disturb).unwrap_or(true);
+            set_respect_do_not_disturb(respect);
+            respect
+        }
+        value => value != 0,
+    }
+}
+
+/// Replaces whether notifications wait for Do Not Disturb to end, e.g. after settings were saved
+pub fn set_respect_do_not_disturb(respect: bool) {
+    RESPECT_DO_NOT_DISTURB.store(respect as u8, Ordering::Relaxed);
+}
+
+/// Keeps a notification until Do Not Disturb ends
+///
+/// # Arguments
+/// * `title` - Notification title
+/// * `body` - Notification body text
+/// * `host` - Host IP address of a notification replacing the host's previous one
+pub fn defer_notification(title: &str, body: &str, host: Option<&str>) {
+    DEFERRED.lock().unwrap().push(title, body, host);
+}
+
+/// Number of notifications waiting for Do Not Disturb to end
+pub fn deferred_count() -> usize {
+    DEFERRED.lock().unwrap().len()
+}
+
+/// Takes the waiting notifications as one summary notification
+///
+/// # Returns
+/// * Title and body of the summary; None when nothing is waiting
+pub fn take_deferred_summary() -> Option<(String, String)> {
+    DEFERRED.lock().unwrap().take_summary()
+}
+
+/// Reads the state of Do Not Disturb and of the waiting notifications
+pub async fn do_not_disturb_state() -> DoNotDisturbState {
+    let active = refresh_do_not_disturb().await;
+    DoNotDisturbState { active, respected: respects_do_not_disturb(), deferred: deferred_count() }
+}
+
+/// Checks whether the system is in a Do Not Disturb or focus mode
+///
+/// A state detected within the last few seconds is reused. On a Tokio
+/// runtime an older state is refreshed in the background and the last known
+/// one is returned meanwhile, so async tasks do not wait for the detection.
+///
+/// # Returns
+/// * None when the platform or desktop does not tell
+pub fn do_not_disturb_active() -> Option<bool> {
+    let detected = *DETECTED.lock().unwrap();
+    match detected {
+        Some((at, active)) if at.elapsed() < DETECTION_TTL => active,
+        _ => match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                if !DETECTING.swap(true, Ordering::AcqRel) {
+                    runtime.spawn_blocking(|| {
+                        detect_do_not_disturb();
+                        DETECTING.store(false, Ordering::Release);
+                    });
+                }
+                detected.and_then(|(_, active)| active)
+            }
+            Err(_) => detect_do_not_disturb(),
+        },
+    }
+}
+
+/// Detects Do Not Disturb now, off the async worker threads
+///
+/// Called once per monitoring cycle, so notifications sent during the cycle
+/// see a recent state.
+pub async fn refresh_do_not_disturb() -> Option<bool> {
+    tokio::task::spawn_blocking(detect_do_not_disturb).await.unwrap_or(None)
+}
+
+fn detect_do_not_disturb() -> Option<bool> {
+    let active = platform::do_not_disturb_active();
+    *DETECTED.lock().unwrap() = Some((Instant::now(), active));
+    active
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+mod platform {
+    use std::process::Command;
+
+    /// Runs a command and returns its output when it succeeds
+    fn command_output(program: &str, args: &[&str]) -> Option<String> {
+        let output = Command::new(program).args(args).output().ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    pub fn do_not_disturb_active() -> Option<bool> {
+        // Notification servers following version 1.2 of the specification
+        let inhibited = command_output("busctl", &[
+            "--user",
+            "get-property",
+            "org.freedesktop.Notifications",
+            "/org/freedesktop/Notifications",
+            "org.freedesktop.Notifications",
+            "Inhibited",
+        ]);
+        if let Some(inhibited) = inhibited {
+            return Some(inhibited == "b true");
+        }
+        // GNOME Shell does not implement the property
+        command_output("gsettings", &["get", "org.gnome.desktop.notifications", "show-banners"]).map(|banners| banners == "false")
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    /// Focus modes turned on from Control Center are recorded as assertions
+    ///
+    /// Reading them needs Full Disk Access; without it the read fails and
+    /// the state is unknown.
+    pub fn do_not_disturb_active() -> Option<bool> {
+        let path = dirs::home_dir()?.join("Library/DoNotDisturb/DB/Assertions.json");
+        let assertions: serde_json::Value = serde_json::from_slice(&std::fs::read(path).ok()?).ok()?;
+        let records = assertions.get("data")?.get(0).and_then(|data| data.get("storeAssertionRecords"));
+        Some(records.and_then(|records| records.as_array()).is_some_and(|records| !records.is_empty()))
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    /// `QUNS_RUNNING_D3D_FULL_SCREEN`: a full-screen game or video
+    const QUNS_RUNNING_D3D_FULL_SCREEN: i32 = 3;
+    /// `QUNS_PRESENTATION_MODE`: presentation settings are on
+    const QUNS_PRESENTATION_MODE: i32 = 4;
+    /// `QUNS_QUIET_TIME`: quiet hours
+    const QUNS_QUIET_TIME: i32 = 6;
+
+    #[link(name = "shell32")]
+    extern "system" {
+        fn SHQueryUserNotificationState(state: *mut i32) -> i32;
+    }
+
+    pub fn do_not_disturb_active() -> Option<bool> {
+        let mut state = 0;
+        if unsafe { SHQueryUserNotificationState(&mut state) } != 0 {
+            return None;
+        }
+        // QUNS_BUSY (any full-screen window, e.g. a maximized browser) is not Do Not Disturb
+        Some(matches!(state, QUNS_RUNNING_D3D_FULL_SCREEN | QUNS_PRESENTATION_MODE | QUNS_QUIET_TIME))
+    }
+}
+
+#[cfg(not(any(unix, target_os = "windows")))]
+mod platform {
+    pub fn do_not_disturb_active() -> Option<bool> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deferred_notifications_are_summarized_once() {
+        let mut deferred = DeferredNotifications::new();
+        assert!(deferred.take_summary().is_none());
+
+        deferred.push("Status changed", "voron: printing → paused", Some("192.168.1.50"));
+        deferred.push("Status changed", "voron: paused → printing\nresumed", Some("192.168.1.50"));
+        deferred.push("Print finished", "ender: benchy.gcode", None);
+        assert_eq!(deferred.len(), 2);
+
+        let (_, body) = deferred.take_summary().unwrap();
+        assert!(body.contains("paused → printing") && !body.contains("printing → paused") && !body.contains("resumed"));
+        assert!(deferred.take_summary().is_none());
+
+        for i in 0..MAX_DEFERRED_NOTIFICATIONS + 3 {
+            deferred.push("Print finished", &format!("job {}", i), None);
+        }
+        assert_eq!(deferred.len(), MAX_DEFERRED_NOTIFICATIONS + 3);
+        let (_, body) = deferred.take_summary().unwrap();
+        assert_eq!(body.lines().count(), SUMMARY_LINES + 1);
+    }
+}
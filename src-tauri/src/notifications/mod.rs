@@ -1,8 +1,18 @@
 //! Notification system modules
 //! 
 //! This module contains functionality for sending system notifications
-//! to users about printer status changes.
+//! to users about printer status changes, the in-app notification center,
+//! the notification permission on macOS, holding notifications back
+//! during Do Not Disturb, the alerts users snoozed or acknowledged, the
+//! escalation of critical alerts nobody acknowledged and webhook delivery.
 
+pub mod center;
+pub mod do_not_disturb;
+pub mod permission;
+pub mod snooze;
 pub mod system;
+pub mod template;
+pub mod unacknowledged;
+pub mod webhook;
 
 pub use system::*;
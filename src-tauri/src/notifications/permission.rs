@@ -0,0 +1,165 @@
+//! Notification permission
+//!
+//! macOS only shows the notifications of an app the user allowed. The state
+//! is read from, and the permission prompt shown by, `UNUserNotificationCenter`
+//! of the UserNotifications framework. Linux and Windows have no such prompt;
+//! notifications are allowed unless turned off in the system settings.
+
+use serde::{Deserialize, Serialize};
+
+/// Whether the system shows the app's notifications
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationPermission {
+    /// The user has not been asked yet
+    NotDetermined,
+    /// The user turned the notifications off
+    Denied,
+    /// The user allowed the notifications
+    Authorized,
+    /// Notifications are delivered quietly to the notification center until the user decides
+    Provisional,
+    /// Allowed for a limited time (App Clips)
+    Ephemeral,
+    /// The platform shows notifications without asking
+    NotRequired,
+    /// The permission cannot be read, e.g. when not running from an app bundle
+    Unavailable,
+}
+
+impl NotificationPermission {
+    /// Maps an `UNAuthorizationStatus` value
+    #[cfg(any(target_os = "macos", test))]
+    fn from_authorization_status(status: isize) -> Self {
+        match status {
+            0 => Self::NotDetermined,
+            1 => Self::Denied,
+            2 => Self::Authorized,
+            3 => Self::Provisional,
+            4 => Self::Ephemeral,
+            _ => Self::Unavailable,
+        }
+    }
+}
+
+/// Reads whether the system shows the app's notifications
+///
+/// Blocks while macOS answers; call it off the main thread.
+pub fn notification_permission() -> Result<NotificationPermission, String> {
+    #[cfg(target_os = "macos")]
+    {
+        match user_notifications::authorization_status() {
+            Ok(status) => Ok(NotificationPermission::from_authorization_status(status)),
+            Err(e) => {
+                eprintln!("Failed to read notification permission: {}", e);
+                Ok(NotificationPermission::Unavailable)
+            }
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        Ok(NotificationPermission::NotRequired)
+    }
+}
+
+/// Asks the user to allow the app's notifications
+///
+/// macOS shows the prompt only while the permission is not determined;
+/// afterwards the answer can only be changed in the system settings.
+/// Blocks until the user answers; call it off the main thread.
+///
+/// # Returns
+/// * The permission after the prompt
+pub fn request_notification_permission() -> Result<NotificationPermission, String> {
+    #[cfg(target_os = "macos")]
+    {
+        user_notifications::request_authorization()?;
+        notification_permission()
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        Ok(NotificationPermission::NotRequired)
+    }
+}
+
+/// `UNUserNotificationCenter` through the objc2 bindings
+///
+/// The framework answers through completion handlers; each handler sends its
+/// answer over a channel the caller waits on with a timeout.
+#[cfg(target_os = "macos")]
+mod user_notifications {
+    use std::ptr::NonNull;
+    use std::sync::mpsc::channel;
+    use std::time::Duration;
+    use block2::RcBlock;
+    use objc2::rc::{autoreleasepool, Retained};
+    use objc2::runtime::Bool;
+    use objc2_foundation::{NSBundle, NSError};
+    use objc2_user_notifications::{UNAuthorizationOptions, UNNotificationSettings, UNUserNotificationCenter};
+
+    /// Seconds to wait for the notification settings
+    const SETTINGS_TIMEOUT_SECONDS: u64 = 5;
+
+    /// Seconds the user has to answer the permission prompt
+    const PROMPT_TIMEOUT_SECONDS: u64 = 300;
+
+    /// The notification center, or an error when not running from an app bundle
+    ///
+    /// `currentNotificationCenter` aborts the process outside a bundle, e.g.
+    /// in development builds started with `cargo run`.
+    fn notification_center() -> Result<Retained<UNUserNotificationCenter>, String> {
+        if !NSBundle::mainBundle().bundlePath().to_string().ends_with(".app") {
+            return Err("Notification permission is only available when running from the app bundle".to_string());
+        }
+        Ok(UNUserNotificationCenter::currentNotificationCenter())
+    }
+
+    /// Reads the `UNAuthorizationStatus` of the app
+    pub fn authorization_status() -> Result<isize, String> {
+        let (sender, receiver) = channel();
+        autoreleasepool(|_| {
+            let handler = RcBlock::new(move |settings: NonNull<UNNotificationSettings>| {
+                // The framework passes valid settings for the duration of the call
+                let status = unsafe { settings.as_ref() }.authorizationStatus();
+                let _ = sender.send(status.0);
+            });
+            notification_center()?.getNotificationSettingsWithCompletionHandler(&handler);
+            Ok::<_, String>(())
+        })?;
+        receiver
+            .recv_timeout(Duration::from_secs(SETTINGS_TIMEOUT_SECONDS))
+            .map_err(|_| "Notification settings did not arrive".to_string())
+    }
+
+    /// Shows the permission prompt and waits for the answer
+    ///
+    /// # Returns
+    /// * Whether the user allowed the notifications
+    pub fn request_authorization() -> Result<bool, String> {
+        let (sender, receiver) = channel();
+        autoreleasepool(|_| {
+            let handler = RcBlock::new(move |granted: Bool, _error: *mut NSError| {
+                let _ = sender.send(granted.as_bool());
+            });
+            let options = UNAuthorizationOptions::Badge | UNAuthorizationOptions::Sound | UNAuthorizationOptions::Alert;
+            notification_center()?.requestAuthorizationWithOptions_completionHandler(options, &handler);
+            Ok::<_, String>(())
+        })?;
+        receiver
+            .recv_timeout(Duration::from_secs(PROMPT_TIMEOUT_SECONDS))
+            .map_err(|_| "No answer to the notification permission prompt".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn authorization_status_maps_to_permission() {
+        assert_eq!(NotificationPermission::from_authorization_status(0), NotificationPermission::NotDetermined);
+        assert_eq!(NotificationPermission::from_authorization_status(2), NotificationPermission::Authorized);
+        assert_eq!(NotificationPermission::from_authorization_status(9), NotificationPermission::Unavailable);
+        assert_eq!(serde_json::to_string(&NotificationPermission::NotRequired).unwrap(), "\"not_required\"");
+    }
+}
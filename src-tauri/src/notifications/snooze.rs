@@ -0,0 +1,121 @@
+//! Snoozed and acknowledged alerts
+//!
+//! A printer switched off on purpose or a sensor hovering around its limit
+//! should not keep paging everyone. An alert of a host can be snoozed for a
+//! while, or acknowledged, which keeps it quiet until it clears. The state is
+//! stored in the settings, so the desktop app, the Telegram bot and the
+//! background monitor see the same silences and they survive a restart.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use crate::models::config::{AlertKind, AlertSilence, AppSettings};
+
+/// Longest snooze, in minutes (one week)
+pub const MAX_ALERT_SNOOZE_MINUTES: u64 = 7 * 24 * 60;
+
+fn load_settings() -> Result<AppSettings, String> {
+    AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))
+}
+
+/// Snoozes an alert of a host
+///
+/// # Arguments
+/// * `host` - Host IP address
+/// * `kind` - Alert to snooze
+/// * `minutes` - Length of the snooze
+///
+/// # Returns
+/// * When the snooze ends
+pub fn snooze_alert(host: &str, kind: AlertKind, minutes: u64) -> Result<DateTime<Utc>, String> {
+    if minutes == 0 || minutes > MAX_ALERT_SNOOZE_MINUTES {
+        return Err(format!("Snooze must be between 1 and {} minutes", MAX_ALERT_SNOOZE_MINUTES));
+    }
+    let until = Utc::now() + chrono::Duration::minutes(minutes as i64);
+    AppSettings::update(|settings| settings.silence_alert(host, kind, Some(until)))?;
+    Ok(until)
+}
+
+/// Acknowledges an alert of a host, keeping it quiet until it clears
+///
+/// # Arguments
+/// * `host` - Host IP address
+/// * `kind` - Alert to acknowledge
+pub fn acknowledge_alert(host: &str, kind: AlertKind) -> Result<(), String> {
+    AppSettings::update(|settings| settings.silence_alert(host, kind, None))
+}
+
+/// Ends the snooze or acknowledgement of an alert of a host
+///
+/// # Returns
+/// * Whether the alert was silenced
+pub fn unsilence_alert(host: &str, kind: AlertKind) -> Result<bool, String> {
+    AppSettings::update(|settings| settings.clear_alert_silence(host, kind))
+}
+
+/// Ends the acknowledgement of an alert that has cleared, so it notifies again next time
+///
+/// Snoozes are kept: they are meant to last for their time, even when the
+/// alert clears and comes back in between.
+///
+/// # Returns
+/// * Whether an acknowledgement was ended
+pub fn release_acknowledgement(host: &str, kind: AlertKind) -> Result<bool, String> {
+    AppSettings::update(|settings| {
+        let acknowledged = settings
+            .alert_silences
+            .get(host)
+            .is_some_and(|silences| silences.iter().any(|s| s.kind == kind && s.until.is_none()));
+        if acknowledged {
+            settings.clear_alert_silence(host, kind);
+        }
+        acknowledged
+    })
+}
+
+/// Checks whether an alert of a host is snoozed or acknowledged right now
+///
+/// Settings that cannot be loaded count as not silenced, so the alert is
+/// still sent.
+pub fn is_alert_silenced_now(host: &str, kind: AlertKind) -> bool {
+    load_settings().is_ok_and(|settings| settings.is_alert_silenced(host, kind, Utc::now()))
+}
+
+/// Lists the alerts currently snoozed or acknowledged, keyed by host IP address
+pub fn active_alert_silences() -> Result<HashMap<String, Vec<AlertSilence>>, String> {
+    let settings = load_settings()?;
+    let now = Utc::now();
+    Ok(settings
+        .alert_silences
+        .into_iter()
+        .map(|(host, silences)| (host, silences.into_iter().filter(|s| s.is_active(now)).collect::<Vec<_>>()))
+        .filter(|(_, silences)| !silences.is_empty())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snoozes_end_and_acknowledgements_wait_for_clearing() {
+        let now = Utc::now();
+        let mut settings = AppSettings::default();
+        settings.silence_alert("192.168.1.50", AlertKind::Offline, Some(now + chrono::Duration::hours(2)));
+        settings.silence_alert("192.168.1.50", AlertKind::Sensor, None);
+
+        assert!(settings.is_alert_silenced("192.168.1.50", AlertKind::Offline, now));
+        assert!(!settings.is_alert_silenced("192.168.1.50", AlertKind::Offline, now + chrono::Duration::hours(3)));
+        assert!(settings.is_alert_silenced("192.168.1.50", AlertKind::Sensor, now + chrono::Duration::days(30)));
+        assert!(!settings.is_alert_silenced("192.168.1.50", AlertKind::HeaterIdle, now));
+        assert!(!settings.is_alert_silenced("192.168.1.51", AlertKind::Offline, now));
+
+        // Snoozing again replaces the earlier snooze
+        settings.silence_alert("192.168.1.50", AlertKind::Offline, Some(now + chrono::Duration::minutes(10)));
+        assert_eq!(settings.alert_silences["192.168.1.50"].len(), 2);
+        assert!(!settings.is_alert_silenced("192.168.1.50", AlertKind::Offline, now + chrono::Duration::hours(1)));
+
+        assert!(settings.clear_alert_silence("192.168.1.50", AlertKind::Sensor));
+        assert!(!settings.clear_alert_silence("192.168.1.50", AlertKind::Sensor));
+        assert_eq!(AlertKind::parse("heater_idle"), Some(AlertKind::HeaterIdle));
+    }
+}
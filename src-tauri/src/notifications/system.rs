@@ -2,25 +2,52 @@
 //! 
 //! This module provides functions for sending native system notifications
 //! across different platforms.
+//!
+//! On Windows the toasts are sent under the app's own AppUserModelID, which is
+//! registered for the current user on first use, so they carry the app name
+//! and icon instead of PowerShell's. A click on a toast brings the main window
+//! to the front, and a webcam snapshot can be shown in the toast.
+//!
+//! On Linux the notifications carry an urgency hint, the latest notification
+//! of a host is replaced instead of stacking a new one per change, and
+//! notification servers with action support get buttons to show the window or
+//! open the host's web interface.
+//!
+//! While the OS is in Do Not Disturb, notifications that are not critical
+//! wait and are shown as one summary afterwards (see [`super::do_not_disturb`]).
+//! Every notification is also kept in the in-app notification center (see
+//! [`super::center`]).
 
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+#[cfg(not(all(unix, not(target_os = "macos"))))]
 use notify_rust::Notification;
+use tauri::Emitter;
+use crate::i18n::{status_name, t, tf};
+use crate::models::HostRelink;
+use crate::notifications::center::record_notification;
+use crate::notifications::do_not_disturb::{
+    defer_notification, deferred_count, do_not_disturb_active, respects_do_not_disturb, take_deferred_summary,
+};
 
-/// Checks notification permissions on macOS
-#[cfg(target_os = "macos")]
-pub fn check_notification_permissions() -> Result<(), String> {
-    use std::process::Command;
-    
-    // Check if notifications are enabled in System Preferences
-    let output = Command::new("osascript")
-        .args(&["-e", "tell application \"System Events\" to get properties of process \"SystemUIServer\""])
-        .output()
-        .map_err(|e| format!("Failed to check system permissions: {}", e))?;
-    
-    if output.status.success() {
-        Ok(())
-    } else {
-        Err("System permissions check failed".to_string())
-    }
+/// Bundle identifier from tauri.conf.json, also the AppUserModelID of the Windows toasts
+pub const APP_ID: &str = "com.tormyhseviv.moonrakerhostscanner";
+
+/// Snapshot files kept for notification images; older ones are overwritten
+const NOTIFICATION_IMAGE_SLOTS: usize = 8;
+
+static NEXT_IMAGE_SLOT: AtomicUsize = AtomicUsize::new(0);
+
+/// App handle used to update the notification center and show the main window from a notification
+static APP_HANDLE: OnceLock<tauri::AppHandle> = OnceLock::new();
+
+/// How insistently a notification is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Priority {
+    Low,
+    Normal,
+    Critical,
 }
 
 /// Sends a system notification using the platform's native notification system
@@ -29,6 +56,149 @@ pub fn check_notification_permissions() -> Result<(), String> {
 /// * `title` - Notification title
 /// * `body` - Notification body text
 pub fn send_notification(title: &str, body: &str) {
+    show_notification(title, body, Priority::Normal, None, None);
+}
+
+/// Sends a system notification showing a webcam snapshot
+///
+/// Windows shows the image in the toast, Linux passes it to the notification
+/// daemon and macOS next to the text. Without an image, or when it cannot be
+/// saved, the notification is sent without it.
+///
+/// # Arguments
+/// * `title` - Notification title
+/// * `body` - Notification body text
+/// * `image` - Snapshot (usually JPEG)
+pub fn send_notification_with_image(title: &str, body: &str, image: Option<&[u8]>) {
+    let path = image.and_then(|image| match save_notification_image(image) {
+        Ok(path) => Some(path),
+        Err(e) => {
+            eprintln!("Failed to save notification image: {}", e);
+            None
+        }
+    });
+    show_notification(title, body, Priority::Normal, path.as_deref(), None);
+}
+
+/// Sends a notification about a host, replacing the host's previous one
+///
+/// On Linux the notification takes the place of the host's latest one, so a
+/// printer changing state often does not stack up notifications. Other
+/// platforms show a new notification.
+///
+/// # Arguments
+/// * `host` - Host IP address
+/// * `title` - Notification title
+/// * `body` - Notification body text
+pub fn send_host_notification(host: &str, title: &str, body: &str) {
+    show_notification(title, body, Priority::Normal, None, Some(host));
+}
+
+/// Sends a critical notification about a host, replacing the host's previous one
+///
+/// # Arguments
+/// * `host` - Host IP address
+/// * `title` - Notification title
+/// * `body` - Notification body text
+pub fn send_critical_host_notification(host: &str, title: &str, body: &str) {
+    show_notification(title, body, Priority::Critical, None, Some(host));
+}
+
+/// Lets notifications reach the notification center and bring the main window to the front
+///
+/// Called once when the desktop app starts. New notifications are then
+/// emitted as "notification-added" events, and Windows toasts and Linux
+/// notifications with action support show the window when clicked; macOS
+/// does this by itself.
+pub fn set_notification_app_handle(app_handle: tauri::AppHandle) {
+    let _ = APP_HANDLE.set(app_handle);
+}
+
+/// Sends a notification marked as critical
+/// 
+/// On Linux critical notifications stay on screen until dismissed; other
+/// platforms show them like normal notifications.
+/// 
+/// # Arguments
+/// * `title` - Notification title
+/// * `body` - Notification body text
+pub fn send_critical_notification(title: &str, body: &str) {
+    show_notification(title, body, Priority::Critical, None, None);
+}
+
+/// Saves an image for a notification, which only takes a file path
+///
+/// The files rotate through a few slots in the temporary directory, so a
+/// notification still on screen keeps its image while new ones are sent.
+fn save_notification_image(image: &[u8]) -> Result<PathBuf, String> {
+    let mut path = std::env::temp_dir();
+    path.push("moonraker-host-scanner");
+    std::fs::create_dir_all(&path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let slot = NEXT_IMAGE_SLOT.fetch_add(1, Ordering::Relaxed) % NOTIFICATION_IMAGE_SLOTS;
+    path.push(format!("notification-{}.jpg", slot));
+    std::fs::write(&path, image).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Shows the notifications held back during Do Not Disturb once it has ended
+///
+/// Called every monitoring cycle, so the summary also arrives when no new
+/// notification follows.
+pub fn flush_deferred_notifications() {
+    if deferred_count() == 0 || (respects_do_not_disturb() && do_not_disturb_active() == Some(true)) {
+        return;
+    }
+    show_deferred_summary();
+}
+
+fn show_deferred_summary() {
+    if let Some((title, body)) = take_deferred_summary() {
+        deliver_notification(&title, &body, Priority::Normal, None, None);
+    }
+}
+
+/// Shows the main window of the desktop app
+#[cfg(not(target_os = "macos"))]
+fn focus_main_window() {
+    use tauri::Manager;
+
+    if let Some(window) = APP_HANDLE.get().and_then(|app_handle| app_handle.get_webview_window("main")) {
+        let _ = window.set_skip_taskbar(false);
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Shows a notification, or holds it back while Do Not Disturb is on
+///
+/// Critical notifications are always shown, with whatever priority the
+/// platform gives them.
+fn show_notification(title: &str, body: &str, priority: Priority, image: Option<&Path>, host: Option<&str>) {
+    let entry = record_notification(title, body, host, priority == Priority::Critical);
+    if let Some(app_handle) = APP_HANDLE.get() {
+        let _ = app_handle.emit("notification-added", entry);
+    }
+
+    if priority != Priority::Critical && respects_do_not_disturb() {
+        if do_not_disturb_active() == Some(true) {
+            defer_notification(title, body, host);
+            return;
+        }
+        show_deferred_summary();
+    }
+    deliver_notification(title, body, priority, image, host);
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn deliver_notification(title: &str, body: &str, priority: Priority, image: Option<&Path>, host: Option<&str>) {
+    if let Err(e) = freedesktop::show(title, body, priority, image, host) {
+        eprintln!("Failed to show notification: {}", e);
+    }
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn deliver_notification(title: &str, body: &str, priority: Priority, image: Option<&Path>, host: Option<&str>) {
     // On macOS, we need to set the app name only once
     #[cfg(target_os = "macos")]
     {
@@ -36,25 +206,269 @@ pub fn send_notification(title: &str, body: &str) {
         static INIT: Once = Once::new();
         
         INIT.call_once(|| {
-            // Check permissions first
-            if let Err(_e) = check_notification_permissions() {
-            }
-            
             // Use the correct bundle identifier from tauri.conf.json
-            match notify_rust::set_application("com.tormyhseviv.moonrakerhostscanner") {
+            match notify_rust::set_application(APP_ID) {
                 Ok(_) => {},
                 Err(_) => {},
             }
         });
     }
     
-    match Notification::new()
+    let mut notification = Notification::new();
+    notification
         .summary(title)
         .body(body)
-        .icon("printer") // Printer icon
-        .show() {
-        Ok(_) => {},
-        Err(_) => {},
+        .icon("printer"); // Printer icon
+    #[cfg(target_os = "windows")]
+    {
+        windows_toast::register_app_id();
+        notification.app_id(APP_ID);
+        if priority == Priority::Critical {
+            notification.urgency(notify_rust::Urgency::Critical);
+        }
+    }
+    let _ = host;
+    #[cfg(target_os = "macos")]
+    let _ = priority;
+    if let Some(image) = image.and_then(Path::to_str) {
+        notification.image_path(image);
+    }
+
+    // The wait for a click ends when the toast is dismissed or expires into
+    // the action center, so every toast holds a short-lived thread
+    #[cfg(target_os = "windows")]
+    if let (Ok(handle), Some(on_click)) = (notification.show(), windows_toast::click_handler()) {
+        std::thread::spawn(move || {
+            let _ = handle.wait_for_response(on_click);
+        });
+    }
+    #[cfg(target_os = "macos")]
+    let _ = notification.show();
+}
+
+/// Notifications on freedesktop notification servers, sent over D-Bus
+///
+/// The latest notification of a host is replaced, and servers with action
+/// support get buttons to show the window or open the host's web interface.
+/// All notifications go over one session bus connection whose signals one
+/// thread receives: some servers send a notification's actions only to the
+/// connection that showed it, and GNOME keeps notifications in its list long
+/// after they were shown, so waiting for each one on its own would hold a
+/// thread and a connection per notification.
+#[cfg(all(unix, not(target_os = "macos")))]
+mod freedesktop {
+    use std::collections::{BTreeMap, HashMap};
+    use std::path::Path;
+    use std::sync::{Mutex, OnceLock};
+    use zbus::blocking::{Connection, MessageIterator};
+    use zbus::zvariant::Value;
+    use zbus::MatchRule;
+    use crate::api::client::{build_web_ui_url, detect_web_port};
+    use crate::i18n::t;
+    use super::{focus_main_window, Priority, APP_HANDLE};
+
+    const NOTIFICATIONS_NAME: &str = "org.freedesktop.Notifications";
+    const NOTIFICATIONS_PATH: &str = "/org/freedesktop/Notifications";
+
+    /// Application name shown by the notification server
+    const APP_NAME: &str = "Moonraker Host Scanner";
+
+    /// Action opening the host's web interface
+    const OPEN_WEB_UI_ACTION: &str = "open_web_ui";
+
+    /// Notifications remembered; the oldest are forgotten
+    const MAX_SHOWN_NOTIFICATIONS: usize = 200;
+
+    /// Notifications shown by the app with the host they are about, by
+    /// notification ID; signals of other apps' notifications are ignored
+    static SHOWN: Mutex<BTreeMap<u32, Option<String>>> = Mutex::new(BTreeMap::new());
+
+    /// Session bus connection, opened on first use
+    static CONNECTION: Mutex<Option<Connection>> = Mutex::new(None);
+
+    /// Whether action buttons can be used: the desktop app runs and the
+    /// notification server supports actions (asked once)
+    fn actions_available() -> bool {
+        static SUPPORTED: OnceLock<bool> = OnceLock::new();
+        APP_HANDLE.get().is_some()
+            && *SUPPORTED.get_or_init(|| {
+                notify_rust::get_capabilities().is_ok_and(|capabilities| capabilities.iter().any(|c| c == "actions"))
+            })
+    }
+
+    /// Connects to the session bus on first use and starts receiving the notification signals
+    fn connection() -> zbus::Result<Connection> {
+        let mut shared = CONNECTION.lock().unwrap();
+        if let Some(connection) = shared.as_ref() {
+            return Ok(connection.clone());
+        }
+        let connection = Connection::session()?;
+        let rule = MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .interface(NOTIFICATIONS_NAME)?
+            .path(NOTIFICATIONS_PATH)?
+            .build();
+        let signals = MessageIterator::for_match_rule(rule, &connection, None)?;
+        std::thread::spawn(move || receive_signals(signals));
+        Ok(shared.insert(connection).clone())
+    }
+
+    /// Shows a notification, replacing the host's latest one
+    pub fn show(title: &str, body: &str, priority: Priority, image: Option<&Path>, host: Option<&str>) -> zbus::Result<()> {
+        let replaces_id = host
+            .and_then(|host| SHOWN.lock().unwrap().iter().find(|(_, h)| h.as_deref() == Some(host)).map(|(id, _)| *id))
+            .unwrap_or(0);
+
+        let mut actions = Vec::new();
+        if actions_available() {
+            // "default" is invoked by clicking the notification itself
+            actions.extend(["default", t("notify.action.show")]);
+            if host.is_some() {
+                actions.extend([OPEN_WEB_UI_ACTION, t("notify.action.open_web_ui")]);
+            }
+        }
+        let mut hints: HashMap<&str, Value> = HashMap::new();
+        let urgency: u8 = match priority {
+            Priority::Low => 0,
+            Priority::Normal => 1,
+            Priority::Critical => 2,
+        };
+        hints.insert("urgency", urgency.into());
+        if let Some(image) = image.and_then(Path::to_str) {
+            hints.insert("image-path", image.into());
+        }
+
+        // The server picks how long the notification stays
+        let expire_timeout = -1i32;
+        let reply = connection()?.call_method(
+            Some(NOTIFICATIONS_NAME),
+            NOTIFICATIONS_PATH,
+            Some(NOTIFICATIONS_NAME),
+            "Notify",
+            &(APP_NAME, replaces_id, "printer", title, body, actions, hints, expire_timeout),
+        )?;
+        let id: u32 = reply.body().deserialize()?;
+        let mut shown = SHOWN.lock().unwrap();
+        if host.is_some() {
+            shown.retain(|_, h| h.as_deref() != host);
+        }
+        shown.insert(id, host.map(str::to_string));
+        if shown.len() > MAX_SHOWN_NOTIFICATIONS {
+            shown.pop_first();
+        }
+        Ok(())
+    }
+
+    /// Handles the actions of the notifications and forgets closed ones
+    ///
+    /// A closed notification of a host is not replaced; the host's next
+    /// notification is a new one.
+    fn receive_signals(signals: MessageIterator) {
+        for message in signals.flatten() {
+            let header = message.header();
+            match header.member().map(|member| member.as_str()) {
+                Some("ActionInvoked") => {
+                    let Ok((id, action)) = message.body().deserialize::<(u32, String)>() else {
+                        continue;
+                    };
+                    let Some(host) = SHOWN.lock().unwrap().get(&id).cloned() else {
+                        continue;
+                    };
+                    match (action.as_str(), host) {
+                        ("default", _) => focus_main_window(),
+                        (OPEN_WEB_UI_ACTION, Some(host)) => open_web_ui(&host),
+                        _ => {}
+                    }
+                }
+                Some("NotificationClosed") => {
+                    if let Ok((id, _reason)) = message.body().deserialize::<(u32, u32)>() {
+                        SHOWN.lock().unwrap().remove(&id);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Opens the host's web interface in the default browser
+    fn open_web_ui(host: &str) {
+        let web_port = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .ok()
+            .and_then(|runtime| runtime.block_on(detect_web_port(host)));
+        let url = build_web_ui_url(host, web_port);
+        if let Err(e) = std::process::Command::new("xdg-open").arg(&url).spawn() {
+            eprintln!("Failed to open {} in browser: {}", url, e);
+        }
+    }
+}
+
+/// AppUserModelID registration and toast clicks on Windows
+#[cfg(target_os = "windows")]
+mod windows_toast {
+    use std::os::windows::process::CommandExt;
+    use std::process::Command;
+    use std::sync::Once;
+    use notify_rust::NotificationResponse;
+    use crate::models::config::AppSettings;
+    use super::{focus_main_window, APP_HANDLE, APP_ID};
+
+    /// Keeps `reg.exe` from opening a console window
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    /// Name shown on the toasts and in the notification settings
+    const DISPLAY_NAME: &str = "Moonraker Host Scanner";
+
+    /// Registers the AppUserModelID for the current user, once per run
+    ///
+    /// Windows only shows toasts of an ID it knows. The installer registers a
+    /// start menu shortcut with the ID; the registry entry also covers
+    /// portable and development builds, and gives the toasts a name and icon.
+    pub fn register_app_id() {
+        static REGISTER: Once = Once::new();
+        REGISTER.call_once(|| {
+            if let Err(e) = write_registration() {
+                eprintln!("Failed to register notification app ID: {}", e);
+            }
+        });
+    }
+
+    fn write_registration() -> Result<(), String> {
+        let mut icon = AppSettings::config_path();
+        icon.set_file_name("notification-icon.png");
+        if let Some(dir) = icon.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        std::fs::write(&icon, include_bytes!("../../icons/128x128.png"))
+            .map_err(|e| format!("Failed to write {}: {}", icon.display(), e))?;
+
+        let key = format!("HKCU\\Software\\Classes\\AppUserModelId\\{}", APP_ID);
+        let icon = icon.to_string_lossy();
+        for (name, value) in [("DisplayName", DISPLAY_NAME), ("IconUri", &*icon)] {
+            let status = Command::new("reg")
+                .args(["add", key.as_str(), "/v", name, "/t", "REG_SZ", "/d", value, "/f"])
+                .creation_flags(CREATE_NO_WINDOW)
+                .status()
+                .map_err(|e| format!("Failed to run reg: {}", e))?;
+            if !status.success() {
+                return Err(format!("reg add {} failed with {}", name, status));
+            }
+        }
+        Ok(())
+    }
+
+    /// Handler bringing the main window to the front when a toast is clicked
+    ///
+    /// # Returns
+    /// * None without a desktop app, e.g. in headless mode
+    pub fn click_handler() -> Option<impl FnOnce(&NotificationResponse) + Send + 'static> {
+        APP_HANDLE.get()?;
+        Some(|response: &NotificationResponse| {
+            if response.is_default_action() {
+                focus_main_window();
+            }
+        })
     }
 }
 
@@ -64,10 +478,29 @@ pub fn send_notification(title: &str, body: &str) {
 /// * `hostname` - Printer hostname
 /// * `old_status` - Previous status
 /// * `new_status` - New status
-pub fn send_status_change_notification(hostname: &str, old_status: &str, new_status: &str) {
-    let title = "Printer Status Changed";
-    let body = format!("{}: {} → {}", hostname, old_status, new_status);
-    send_notification(title, &body);
+/// * `detail` - Optional error text shown below the status change
+pub fn send_status_change_notification(hostname: &str, old_status: &str, new_status: &str, detail: Option<&str>) {
+    let body = status_change_body(hostname, old_status, new_status, detail);
+    send_notification(t("notify.status_changed.title"), &body);
+}
+
+/// Builds the localized body of a status change notification
+/// 
+/// # Arguments
+/// * `hostname` - Printer hostname
+/// * `old_status` - Previous status
+/// * `new_status` - New status
+/// * `detail` - Optional error text shown below the status change
+pub fn status_change_body(hostname: &str, old_status: &str, new_status: &str, detail: Option<&str>) -> String {
+    let mut body = tf("notify.status_changed.body", &[
+        ("host", &hostname),
+        ("from", &status_name(old_status)),
+        ("to", &status_name(new_status)),
+    ]);
+    if let Some(detail) = detail {
+        body.push_str(&format!("\n{}", detail));
+    }
+    body
 }
 
 /// Sends a notification about printer discovery
@@ -76,9 +509,8 @@ pub fn send_status_change_notification(hostname: &str, old_status: &str, new_sta
 /// * `hostname` - Printer hostname
 /// * `ip_address` - Printer IP address
 pub fn send_printer_discovered_notification(hostname: &str, ip_address: &str) {
-    let title = "New Printer Discovered";
     let body = format!("{} ({})", hostname, ip_address);
-    send_notification(title, &body);
+    show_notification(t("notify.printer_discovered.title"), &body, Priority::Low, None, None);
 }
 
 /// Sends a notification about printer going offline
@@ -87,7 +519,22 @@ pub fn send_printer_discovered_notification(hostname: &str, ip_address: &str) {
 /// * `hostname` - Printer hostname
 /// * `ip_address` - Printer IP address
 pub fn send_printer_offline_notification(hostname: &str, ip_address: &str) {
-    let title = "Printer Offline";
-    let body = format!("{} ({}) is no longer responding", hostname, ip_address);
-    send_notification(title, &body);
+    let body = tf("notify.printer_offline.body", &[("host", &hostname), ("ip", &ip_address)]);
+    send_notification(t("notify.printer_offline.title"), &body);
+}
+
+/// Builds the localized body of a notification about a printer that got a new address
+pub fn host_moved_body(relink: &HostRelink) -> String {
+    tf("notify.host_moved.body", &[
+        ("host", &relink.hostname),
+        ("previous", &relink.previous_address),
+        ("ip", &relink.address),
+    ])
+}
+
+/// Sends a notification about a known printer found under a new address
+/// 
+/// The text suggests reserving the address in the DHCP server.
+pub fn send_host_moved_notification(relink: &HostRelink) {
+    send_notification(t("notify.host_moved.title"), &host_moved_body(relink));
 }
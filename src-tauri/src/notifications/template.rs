@@ -0,0 +1,193 @@
+//! Notification text templates
+//!
+//! Users can replace the built-in notification texts with their own.
+//! A template contains placeholders in braces, e.g. `{hostname} is {status}`,
+//! which are filled in for each event; `{{` stands for a literal brace. The
+//! rendered text is the same for every channel (system notification,
+//! Telegram, webhooks), in the background monitor as well as in the desktop
+//! app, which renders through a command.
+
+use crate::api::print_info::{format_duration, get_print_info};
+use crate::i18n::status_name;
+
+/// Placeholders a template may use
+pub const TEMPLATE_PLACEHOLDERS: [&str; 7] = ["hostname", "ip", "status", "previous_status", "progress", "eta", "filename"];
+
+/// Values filled into a template
+///
+/// Job values are empty when the event has no running or finished job.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateValues {
+    pub hostname: String,
+    pub ip: String,
+    /// Localized status name
+    pub status: String,
+    /// Localized status name before the change
+    pub previous_status: String,
+    /// Job progress in percent
+    pub progress: Option<f64>,
+    /// Remaining print time, e.g. "1h 20m"
+    pub eta: Option<String>,
+    pub filename: Option<String>,
+}
+
+impl TemplateValues {
+    /// Example values used for template previews
+    pub fn sample() -> Self {
+        Self {
+            hostname: "Voron".to_string(),
+            ip: "192.168.1.50".to_string(),
+            status: crate::i18n::status_name("printing").to_string(),
+            previous_status: crate::i18n::status_name("standby").to_string(),
+            progress: Some(42.0),
+            eta: Some("1h 20m".to_string()),
+            filename: Some("benchy.gcode".to_string()),
+        }
+    }
+
+    /// Collects the values of a status change, including the host's job
+    ///
+    /// # Arguments
+    /// * `ip` - Host IP address
+    /// * `hostname` - Host display name
+    /// * `previous_status` - Status before the change, e.g. "standby"
+    /// * `status` - Status after the change, e.g. "printing"
+    pub async fn for_status_change(ip: &str, hostname: &str, previous_status: &str, status: &str) -> Self {
+        let job = get_print_info(ip, None).await.ok().flatten();
+        Self {
+            hostname: hostname.to_string(),
+            ip: ip.to_string(),
+            status: status_name(status).to_string(),
+            previous_status: status_name(previous_status).to_string(),
+            progress: job.as_ref().map(|job| job.progress.progress),
+            eta: job.as_ref().and_then(|job| job.progress.remaining_seconds()).map(format_duration),
+            filename: job.map(|job| job.filename),
+        }
+    }
+
+    fn value(&self, placeholder: &str) -> String {
+        match placeholder {
+            "hostname" => self.hostname.clone(),
+            "ip" => self.ip.clone(),
+            "status" => self.status.clone(),
+            "previous_status" => self.previous_status.clone(),
+            "progress" => self.progress.map(|p| format!("{:.0}%", p)).unwrap_or_default(),
+            "eta" => self.eta.clone().unwrap_or_default(),
+            "filename" => self.filename.clone().unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Part of a parsed template
+enum Segment<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+fn parse(template: &str) -> Result<Vec<Segment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        segments.push(Segment::Text(&rest[..start]));
+        let after = &rest[start + 1..];
+        if let Some(escaped) = after.strip_prefix('{') {
+            segments.push(Segment::Text("{"));
+            rest = escaped;
+            continue;
+        }
+        let end = after
+            .find('}')
+            .ok_or_else(|| format!("Unclosed placeholder: {}", &rest[start..]))?;
+        let name = after[..end].trim();
+        if !TEMPLATE_PLACEHOLDERS.contains(&name) {
+            return Err(format!(
+                "Unknown placeholder {{{}}}, available: {}",
+                name,
+                TEMPLATE_PLACEHOLDERS.map(|p| format!("{{{}}}", p)).join(", ")
+            ));
+        }
+        segments.push(Segment::Placeholder(name));
+        rest = &after[end + 1..];
+    }
+    segments.push(Segment::Text(rest));
+    Ok(segments)
+}
+
+/// Checks that a template is not empty and only uses known placeholders
+pub fn validate_template(template: &str) -> Result<(), String> {
+    parse_non_empty(template).map(|_| ())
+}
+
+fn parse_non_empty(template: &str) -> Result<Vec<Segment<'_>>, String> {
+    if template.trim().is_empty() {
+        return Err("Template is empty".to_string());
+    }
+    parse(template)
+}
+
+/// Fills the placeholders of a template
+///
+/// # Returns
+/// * Rendered text, or the validation error of an invalid template
+pub fn render_template(template: &str, values: &TemplateValues) -> Result<String, String> {
+    let text = parse_non_empty(template)?
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => text.to_string(),
+            Segment::Placeholder(name) => values.value(name),
+        })
+        .collect();
+    Ok(text)
+}
+
+/// Renders the user's template, or builds the built-in text when none is set
+///
+/// A template that fails to render (e.g. edited by hand in the config file)
+/// falls back to the built-in text so the notification is still sent.
+pub fn render_or_default(template: Option<&str>, values: &TemplateValues, default: impl FnOnce() -> String) -> String {
+    let Some(template) = template.filter(|t| !t.trim().is_empty()) else {
+        return default();
+    };
+    render_template(template, values).unwrap_or_else(|e| {
+        eprintln!("Invalid notification template: {}", e);
+        default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_are_filled_and_missing_job_values_are_empty() {
+        let values = TemplateValues {
+            hostname: "Ender".to_string(),
+            status: "Printing".to_string(),
+            progress: Some(12.4),
+            ..TemplateValues::default()
+        };
+
+        let text = render_template("{hostname}: {status} {progress} ({ eta })", &values).unwrap();
+        assert_eq!(text, "Ender: Printing 12% ()");
+    }
+
+    #[test]
+    fn doubled_braces_are_literal() {
+        let values = TemplateValues { hostname: "Ender".to_string(), ..TemplateValues::default() };
+        assert_eq!(render_template("{{hostname}: {hostname}", &values).unwrap(), "{hostname}: Ender");
+        assert_eq!(render_template("{{{hostname}}", &values).unwrap(), "{Ender}");
+    }
+
+    #[test]
+    fn unknown_and_unclosed_placeholders_are_rejected() {
+        assert!(validate_template("{hostname} is {state}").unwrap_err().contains("{state}"));
+        assert!(validate_template("{hostname").is_err());
+        assert!(validate_template("  ").is_err());
+        assert_eq!(
+            render_or_default(Some("{nope}"), &TemplateValues::default(), || "built-in".to_string()),
+            "built-in"
+        );
+    }
+}
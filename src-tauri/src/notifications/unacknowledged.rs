@@ -0,0 +1,121 @@
+//! Escalation of critical alerts nobody acknowledged
+//!
+//! In a shared workshop the operator who gets the alerts may be away. A
+//! critical alert that is neither acknowledged nor snoozed within the
+//! configured time is sent once more to secondary contacts: chosen Telegram
+//! users and a webhook, e.g. an email or paging gateway.
+
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::time::Instant;
+use crate::models::config::AlertKind;
+use crate::notifications::webhook::post_webhook;
+
+/// Critical alert waiting to be acknowledged
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingAlert {
+    /// Host IP address
+    pub host: String,
+    pub kind: AlertKind,
+    pub title: String,
+    pub body: String,
+    /// When the alert was first sent
+    pub raised: Instant,
+}
+
+/// Critical alerts sent and not acknowledged yet, carried between monitoring cycles
+#[derive(Debug, Default)]
+pub struct UnacknowledgedAlerts {
+    /// Pending alerts and whether they were escalated, keyed by (host, alert)
+    pending: HashMap<(String, AlertKind), (PendingAlert, bool)>,
+}
+
+impl UnacknowledgedAlerts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a critical alert; an alert already pending keeps its first time
+    pub fn raise(&mut self, host: &str, kind: AlertKind, title: &str, body: &str, now: Instant) {
+        self.pending.entry((host.to_string(), kind)).or_insert_with(|| {
+            let alert = PendingAlert { host: host.to_string(), kind, title: title.to_string(), body: body.to_string(), raised: now };
+            (alert, false)
+        });
+    }
+
+    /// Forgets an alert that was acknowledged or has cleared
+    pub fn resolve(&mut self, host: &str, kind: AlertKind) {
+        self.pending.remove(&(host.to_string(), kind));
+    }
+
+    /// Alerts of a host still waiting to be acknowledged
+    pub fn pending_kinds(&self, host: &str) -> Vec<AlertKind> {
+        self.pending.keys().filter(|(h, _)| h == host).map(|(_, kind)| *kind).collect()
+    }
+
+    /// Takes the alerts of a host that waited longer than allowed, once per alert
+    ///
+    /// # Arguments
+    /// * `host` - Host IP address
+    /// * `wait` - Time an alert may stay unacknowledged
+    /// * `now` - Current time
+    pub fn take_overdue(&mut self, host: &str, wait: Duration, now: Instant) -> Vec<PendingAlert> {
+        self.pending
+            .values_mut()
+            .filter(|(alert, escalated)| !*escalated && alert.host == host && now.saturating_duration_since(alert.raised) >= wait)
+            .map(|(alert, escalated)| {
+                *escalated = true;
+                alert.clone()
+            })
+            .collect()
+    }
+}
+
+/// Posts an unacknowledged alert to the escalation webhook
+///
+/// The payload is JSON with the host, the alert and how long it waited.
+///
+/// # Arguments
+/// * `url` - Webhook URL
+/// * `alert` - Unacknowledged alert
+/// * `hostname` - Display name of the host
+pub async fn send_escalation_webhook(url: &str, alert: &PendingAlert, hostname: &str) -> Result<(), String> {
+    let payload = serde_json::json!({
+        "host": alert.host,
+        "hostname": hostname,
+        "alert": alert.kind,
+        "title": alert.title,
+        "message": alert.body,
+        "unacknowledged_seconds": alert.raised.elapsed().as_secs(),
+        "escalated_at": chrono::Utc::now().to_rfc3339(),
+    });
+    post_webhook(url, &payload, None).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overdue_alerts_escalate_once() {
+        let mut alerts = UnacknowledgedAlerts::new();
+        let start = Instant::now();
+        let at = |minutes: u64| start + Duration::from_secs(minutes * 60);
+        let wait = Duration::from_secs(15 * 60);
+
+        alerts.raise("192.168.1.50", AlertKind::Offline, "Printer offline", "voron offline for 1 h", at(0));
+        alerts.raise("192.168.1.50", AlertKind::Offline, "Printer offline", "voron offline for 1 h", at(10));
+        alerts.raise("192.168.1.51", AlertKind::HeaterWatch, "Heater fault", "ender: extruder", at(5));
+
+        assert!(alerts.take_overdue("192.168.1.50", wait, at(14)).is_empty());
+        let overdue = alerts.take_overdue("192.168.1.50", wait, at(15));
+        assert_eq!(overdue.len(), 1);
+        assert_eq!(overdue[0].raised, at(0));
+        assert!(alerts.take_overdue("192.168.1.50", wait, at(30)).is_empty());
+
+        alerts.resolve("192.168.1.51", AlertKind::HeaterWatch);
+        assert!(alerts.pending_kinds("192.168.1.51").is_empty());
+        assert!(alerts.take_overdue("192.168.1.51", wait, at(30)).is_empty());
+        assert_eq!(alerts.pending_kinds("192.168.1.50"), vec![AlertKind::Offline]);
+    }
+}
@@ -0,0 +1,49 @@
+//! Webhook delivery
+//!
+//! Rules, print completion and unacknowledged alerts can post to a URL. The
+//! payload is sent as JSON; when a webcam snapshot is attached, the request
+//! is `multipart/form-data` instead, with the JSON in the `payload_json` part
+//! and the image in the `file` part. Discord webhooks accept this layout
+//! directly, other services read the two parts by name.
+
+use std::time::Duration;
+use reqwest::multipart::{Form, Part};
+use reqwest::Client;
+use crate::api::client::{configure_http_client, HttpTarget};
+
+/// Seconds a webhook receiver has to answer
+const WEBHOOK_TIMEOUT_SECONDS: u64 = 10;
+
+/// Seconds a webhook receiver has to answer a post with an image
+const WEBHOOK_UPLOAD_TIMEOUT_SECONDS: u64 = 30;
+
+/// Posts a payload to a webhook
+///
+/// # Arguments
+/// * `url` - Webhook URL
+/// * `payload` - JSON payload
+/// * `snapshot` - Webcam image (usually JPEG) sent along as multipart file
+pub async fn post_webhook(url: &str, payload: &serde_json::Value, snapshot: Option<Vec<u8>>) -> Result<(), String> {
+    let timeout = if snapshot.is_some() { WEBHOOK_UPLOAD_TIMEOUT_SECONDS } else { WEBHOOK_TIMEOUT_SECONDS };
+    let client = configure_http_client(Client::builder(), HttpTarget::Internet)
+        .timeout(Duration::from_secs(timeout))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let request = match snapshot {
+        Some(image) => {
+            let file = Part::bytes(image)
+                .file_name("snapshot.jpg")
+                .mime_str("image/jpeg")
+                .map_err(|e| format!("Failed to attach snapshot: {}", e))?;
+            let form = Form::new().text("payload_json", payload.to_string()).part("file", file);
+            client.post(url).multipart(form)
+        }
+        None => client.post(url).json(payload),
+    };
+    let response = request.send().await.map_err(|e| format!("Webhook request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Webhook answered {}", response.status()));
+    }
+    Ok(())
+}
@@ -0,0 +1,215 @@
+//! Pre-flight checks before remote print starts
+//!
+//! A print started from the app or Telegram has nobody standing next to the
+//! printer. Before the start is sent, the printer has to be ready, the user
+//! has to confirm that the bed is clear, the filament sensors have to see
+//! filament and the chamber has to be within its temperature range. Every
+//! check ends up in a report, so the user sees all problems at once.
+
+use serde::{Deserialize, Serialize};
+use crate::api::files::start_print_file;
+use crate::api::printer::{get_filament_sensors, get_sensors};
+use crate::i18n::{t, tf};
+use crate::models::api::{AuxiliarySensor, FilamentSensor, KlippyState, PrinterState};
+use crate::models::config::{AppSettings, PreflightSettings};
+use crate::network::status_cache::{get_host_status, invalidate_host_status};
+
+/// What a pre-flight check looked at
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PreflightCheckKind {
+    /// Klippy is ready and no print is running
+    PrinterReady,
+    /// The user confirmed that the bed is clear
+    BedClear,
+    /// A filament sensor detects filament
+    Filament,
+    /// The chamber temperature is within the configured range
+    ChamberTemperature,
+}
+
+/// Outcome of one pre-flight check
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PreflightCheck {
+    pub kind: PreflightCheckKind,
+    pub passed: bool,
+    /// Description in the user's language
+    pub message: String,
+}
+
+/// Outcome of all pre-flight checks of a host
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PreflightReport {
+    /// Host IP address
+    pub host: String,
+    /// Whether every check passed
+    pub passed: bool,
+    pub checks: Vec<PreflightCheck>,
+    /// Whether the print was started
+    pub started: bool,
+}
+
+impl PreflightReport {
+    fn new(host: &str, checks: Vec<PreflightCheck>) -> Self {
+        Self { host: host.to_string(), passed: checks.iter().all(|c| c.passed), checks, started: false }
+    }
+
+    /// Whether the confirmation that the bed is clear is all that is missing
+    pub fn needs_only_bed_clear(&self) -> bool {
+        !self.passed && self.checks.iter().filter(|c| !c.passed).all(|c| c.kind == PreflightCheckKind::BedClear)
+    }
+
+    /// One line per check, marked as passed or failed
+    pub fn summary(&self) -> String {
+        self.checks
+            .iter()
+            .map(|check| format!("{} {}", if check.passed { "✅" } else { "❌" }, check.message))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn check(kind: PreflightCheckKind, passed: bool, message: String) -> PreflightCheck {
+    PreflightCheck { kind, passed, message }
+}
+
+/// Checks that Klippy is ready and the printer is idle
+fn check_printer_ready(klippy_state: Option<KlippyState>, printer_state: Option<PrinterState>) -> PreflightCheck {
+    match (klippy_state, printer_state) {
+        (Some(KlippyState::Ready), Some(PrinterState::Printing | PrinterState::Paused)) => {
+            check(PreflightCheckKind::PrinterReady, false, t("preflight.printer_busy").to_string())
+        }
+        (Some(KlippyState::Ready), _) => check(PreflightCheckKind::PrinterReady, true, t("preflight.printer_ready").to_string()),
+        (state, _) => {
+            let state = state.map_or("offline", |s| s.label());
+            check(PreflightCheckKind::PrinterReady, false, tf("preflight.printer_not_ready", &[("state", &state)]))
+        }
+    }
+}
+
+/// Checks that every enabled filament sensor detects filament
+fn check_filament(sensors: &[FilamentSensor]) -> Vec<PreflightCheck> {
+    sensors
+        .iter()
+        .filter(|sensor| sensor.enabled)
+        .map(|sensor| {
+            let key = if sensor.filament_detected { "preflight.filament_detected" } else { "preflight.filament_missing" };
+            check(PreflightCheckKind::Filament, sensor.filament_detected, tf(key, &[("sensor", &sensor.name)]))
+        })
+        .collect()
+}
+
+/// Checks that the chamber sensor reads a temperature within the configured range
+fn check_chamber(settings: &PreflightSettings, sensor: &str, sensors: &[AuxiliarySensor]) -> PreflightCheck {
+    let Some(temperature) = sensors.iter().find(|s| s.name == sensor).and_then(|s| s.temperature) else {
+        return check(PreflightCheckKind::ChamberTemperature, false, tf("preflight.chamber_missing", &[("sensor", &sensor)]));
+    };
+    let min = settings.chamber_min_temperature.unwrap_or(f64::MIN);
+    let max = settings.chamber_max_temperature.unwrap_or(f64::MAX);
+    let range = match (settings.chamber_min_temperature, settings.chamber_max_temperature) {
+        (Some(min), Some(max)) => format!("{:.0} - {:.0}°C", min, max),
+        (Some(min), None) => format!("≥ {:.0}°C", min),
+        (None, Some(max)) => format!("≤ {:.0}°C", max),
+        (None, None) => "-".to_string(),
+    };
+    let passed = (min..=max).contains(&temperature);
+    let key = if passed { "preflight.chamber_ok" } else { "preflight.chamber_out_of_range" };
+    let message = tf(key, &[("temperature", &format!("{:.1}", temperature)), ("range", &range)]);
+    check(PreflightCheckKind::ChamberTemperature, passed, message)
+}
+
+/// Runs the configured pre-flight checks of a host
+///
+/// # Arguments
+/// * `host` - Host IP address
+/// * `settings` - Checks to run
+/// * `bed_clear_confirmed` - Whether the user confirmed that the bed is clear
+///
+/// # Returns
+/// * Report of every check; no checks when pre-flight is turned off
+pub async fn run_preflight(host: &str, settings: &PreflightSettings, bed_clear_confirmed: bool) -> PreflightReport {
+    if !settings.enabled {
+        return PreflightReport::new(host, Vec::new());
+    }
+
+    invalidate_host_status(host);
+    let status = get_host_status(host).await.status;
+    let mut checks = vec![check_printer_ready(status.success.then_some(status.klippy_state).flatten(), status.printer_state)];
+
+    if settings.require_bed_clear {
+        let key = if bed_clear_confirmed { "preflight.bed_clear_confirmed" } else { "preflight.bed_clear_required" };
+        checks.push(check(PreflightCheckKind::BedClear, bed_clear_confirmed, t(key).to_string()));
+    }
+
+    if settings.check_filament {
+        match get_filament_sensors(host).await {
+            Ok(sensors) => checks.extend(check_filament(&sensors)),
+            Err(e) => checks.push(check(PreflightCheckKind::Filament, false, tf("preflight.filament_unknown", &[("error", &e)]))),
+        }
+    }
+
+    if let Some(sensor) = settings.chamber_sensor.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        let sensors = get_sensors(host).await.unwrap_or_default();
+        checks.push(check_chamber(settings, sensor, &sensors));
+    }
+
+    PreflightReport::new(host, checks)
+}
+
+/// Runs the pre-flight checks and starts the print if they all pass
+///
+/// # Arguments
+/// * `host` - Host IP address
+/// * `file_name` - File path relative to the `gcodes` root
+/// * `bed_clear_confirmed` - Whether the user confirmed that the bed is clear
+///
+/// # Returns
+/// * The report, with `started` set when the print was started
+pub async fn start_print_checked(host: &str, file_name: &str, bed_clear_confirmed: bool) -> Result<PreflightReport, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    let mut report = run_preflight(host, &settings.preflight, bed_clear_confirmed).await;
+    if report.passed {
+        start_print_file(host, file_name).await.map_err(|e| format!("Failed to start print: {}", e))?;
+        invalidate_host_status(host);
+        report.started = true;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_report_every_problem() {
+        assert!(check_printer_ready(Some(KlippyState::Ready), Some(PrinterState::Standby)).passed);
+        assert!(!check_printer_ready(Some(KlippyState::Ready), Some(PrinterState::Printing)).passed);
+        assert!(!check_printer_ready(None, None).passed);
+
+        let sensors = vec![
+            FilamentSensor { name: "runout".to_string(), enabled: true, filament_detected: false },
+            FilamentSensor { name: "spare".to_string(), enabled: false, filament_detected: false },
+        ];
+        let filament = check_filament(&sensors);
+        assert_eq!(filament.len(), 1);
+        assert!(!filament[0].passed);
+
+        let settings = PreflightSettings { chamber_min_temperature: Some(35.0), ..PreflightSettings::default() };
+        let chamber = |temperature| AuxiliarySensor {
+            name: "chamber".to_string(),
+            label: None,
+            temperature: Some(temperature),
+            humidity: None,
+            pressure: None,
+        };
+        assert!(!check_chamber(&settings, "chamber", &[chamber(24.0)]).passed);
+        assert!(check_chamber(&settings, "chamber", &[chamber(41.5)]).passed);
+        assert!(!check_chamber(&settings, "enclosure", &[chamber(41.5)]).passed);
+
+        let report = PreflightReport::new("192.168.1.50", vec![
+            check_printer_ready(Some(KlippyState::Ready), Some(PrinterState::Standby)),
+            check(PreflightCheckKind::BedClear, false, String::new()),
+        ]);
+        assert!(!report.passed && report.needs_only_bed_clear());
+    }
+}
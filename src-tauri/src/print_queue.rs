@@ -0,0 +1,292 @@
+//! Print queue scheduler
+//!
+//! This module turns the app into a basic farm scheduler: queued g-code files
+//! are uploaded to the next idle printer whose tags match the job and the
+//! print is started automatically.
+//!
+//! A printer only takes a job when its bed is known to be empty: either
+//! Klipper reports no finished job (`print_stats.state == "standby"`) or the
+//! user confirmed that the bed was cleared.
+
+use std::path::Path;
+use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Duration};
+
+use crate::api::files::{start_print_file, upload_gcode};
+use crate::api::print_info::get_print_info;
+use crate::gcode::parse_gcode_file;
+use crate::models::{HostInfo, KlippyState, PrintQueue, PrinterState, QueueJob, QueueJobStatus};
+use crate::network::status_cache::{get_host_status, invalidate_host_status};
+
+/// Seconds between scheduler passes
+pub const QUEUE_INTERVAL_SECONDS: u64 = 30;
+
+/// Seconds a started print may take to show up as printing
+const PRINT_START_GRACE_SECONDS: i64 = 120;
+
+/// Print queue state
+pub struct PrintQueueState {
+    queue: Arc<Mutex<PrintQueue>>,
+    is_running: Arc<AtomicBool>,
+    task_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
+}
+
+impl Default for PrintQueueState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrintQueueState {
+    /// Creates the queue state from the saved queue
+    pub fn new() -> Self {
+        let mut queue = PrintQueue::load().unwrap_or_default();
+        // An upload interrupted by a restart has to be retried
+        for job in queue.jobs.iter_mut().filter(|j| j.status == QueueJobStatus::Uploading) {
+            job.status = QueueJobStatus::Pending;
+            job.assigned_host = None;
+        }
+
+        Self {
+            queue: Arc::new(Mutex::new(queue)),
+            is_running: Arc::new(AtomicBool::new(false)),
+            task_handle: Mutex::new(None),
+        }
+    }
+
+    /// Returns the shared queue
+    pub fn queue(&self) -> Arc<Mutex<PrintQueue>> {
+        self.queue.clone()
+    }
+
+    /// Starts the scheduler loop
+    ///
+    /// # Arguments
+    /// * `hosts` - Known hosts to dispatch jobs to
+    /// * `interval_seconds` - Seconds between scheduler passes
+    pub async fn start(&self, hosts: Arc<Mutex<Vec<HostInfo>>>, interval_seconds: u64) {
+        if self.is_running.swap(true, Ordering::Relaxed) {
+            return;
+        }
+
+        let queue = self.queue.clone();
+        let is_running = self.is_running.clone();
+        let handle = tokio::spawn(async move {
+            while is_running.load(Ordering::Relaxed) {
+                Self::process(&queue, &hosts).await;
+                sleep(Duration::from_secs(interval_seconds)).await;
+            }
+        });
+
+        *self.task_handle.lock().await = Some(handle);
+    }
+
+    /// Stops the scheduler loop
+    pub fn stop(&self) {
+        self.is_running.store(false, Ordering::Relaxed);
+    }
+
+    /// Adds a local g-code file to the queue
+    ///
+    /// # Arguments
+    /// * `file_path` - Local path of the g-code file
+    /// * `required_tags` - Tags a printer must carry to take the job
+    ///
+    /// # Returns
+    /// * The queued job
+    pub async fn add(&self, file_path: String, required_tags: Vec<String>) -> Result<QueueJob, String> {
+        let path = Path::new(&file_path);
+        if !path.is_file() {
+            return Err(format!("File not found: {}", file_path));
+        }
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.to_string())
+            .ok_or_else(|| "Invalid file name".to_string())?;
+        if !file_name.to_lowercase().ends_with(".gcode") {
+            return Err("Only .gcode files can be queued".to_string());
+        }
+
+        let required_tags = required_tags
+            .into_iter()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        let metadata = parse_gcode_file(path).ok().filter(|m| !m.is_empty());
+
+        let mut queue = self.queue.lock().await;
+        let job = queue.enqueue(file_path, file_name, required_tags, metadata);
+        queue.save().map_err(|e| format!("Failed to save queue: {}", e))?;
+        Ok(job)
+    }
+
+    /// Removes a job that is not currently being uploaded or printed
+    pub async fn remove(&self, job_id: &str) -> Result<(), String> {
+        let mut queue = self.queue.lock().await;
+        let job = queue.jobs.iter()
+            .find(|j| j.id == job_id)
+            .ok_or_else(|| "Queue job not found".to_string())?;
+        if matches!(job.status, QueueJobStatus::Uploading | QueueJobStatus::Printing) {
+            return Err("Job is in progress; cancel the print on the printer first".to_string());
+        }
+
+        queue.jobs.retain(|j| j.id != job_id);
+        queue.save().map_err(|e| format!("Failed to save queue: {}", e))
+    }
+
+    /// Removes all completed and failed jobs
+    pub async fn clear_finished(&self) -> Result<(), String> {
+        let mut queue = self.queue.lock().await;
+        queue.jobs.retain(|j| !j.is_finished());
+        queue.save().map_err(|e| format!("Failed to save queue: {}", e))
+    }
+
+    /// Records that the bed of a host was cleared and it may take the next job
+    pub async fn mark_bed_cleared(&self, host: &str) -> Result<(), String> {
+        let mut queue = self.queue.lock().await;
+        queue.cleared_hosts.insert(host.to_string());
+        queue.save().map_err(|e| format!("Failed to save queue: {}", e))
+    }
+
+    /// Runs one scheduler pass: tracks running jobs, then dispatches pending ones
+    pub async fn process(queue: &Arc<Mutex<PrintQueue>>, hosts: &Arc<Mutex<Vec<HostInfo>>>) {
+        let jobs = queue.lock().await.jobs.clone();
+        if jobs.iter().all(|j| j.is_finished()) {
+            return;
+        }
+
+        for job in jobs.iter().filter(|j| j.status == QueueJobStatus::Printing) {
+            Self::track_job(queue, job).await;
+        }
+
+        let hosts = hosts.lock().await.clone();
+        for job in jobs.iter().filter(|j| j.status == QueueJobStatus::Pending) {
+            let host = match Self::find_idle_host(queue, &hosts, job).await {
+                Some(host) => host,
+                None => continue,
+            };
+            Self::dispatch(queue, job, &host).await;
+        }
+    }
+
+    /// Finds an idle, compatible printer with an empty bed for the job
+    async fn find_idle_host(queue: &Arc<Mutex<PrintQueue>>, hosts: &[HostInfo], job: &QueueJob) -> Option<HostInfo> {
+        for host in hosts {
+            if host.maintenance || !host.has_tags(&job.required_tags) {
+                continue;
+            }
+            let (busy, cleared) = {
+                let queue = queue.lock().await;
+                (queue.is_host_busy(&host.ip_address), queue.cleared_hosts.contains(&host.ip_address))
+            };
+            if busy {
+                continue;
+            }
+
+            let status = get_host_status(&host.ip_address).await.status;
+            let idle = status.success
+                && status.printer_state == Some(PrinterState::Standby)
+                && status.klippy_state == Some(KlippyState::Ready);
+            if !idle {
+                continue;
+            }
+
+            // A finished or aborted job may still be on the bed
+            let bed_empty = cleared || matches!(
+                get_print_info(&host.ip_address, None).await,
+                Ok(Some(ref info)) if info.status == "standby"
+            );
+            if bed_empty {
+                return Some(host.clone());
+            }
+        }
+        None
+    }
+
+    /// Uploads the job file to the host and starts the print
+    async fn dispatch(queue: &Arc<Mutex<PrintQueue>>, job: &QueueJob, host: &HostInfo) {
+        Self::update_job(queue, &job.id, |j| {
+            j.status = QueueJobStatus::Uploading;
+            j.assigned_host = Some(host.ip_address.clone());
+        }).await;
+        queue.lock().await.cleared_hosts.remove(&host.ip_address);
+
+        println!("Print queue: sending {} to {} ({})", job.file_name, host.hostname, host.ip_address);
+
+        let result = async {
+            let content = tokio::fs::read(&job.file_path)
+                .await
+                .map_err(|e| format!("Failed to read {}: {}", job.file_path, e))?;
+            upload_gcode(&host.ip_address, &job.file_name, content)
+                .await
+                .map_err(|e| format!("Upload failed: {}", e))?;
+            start_print_file(&host.ip_address, &job.file_name)
+                .await
+                .map_err(|e| format!("Failed to start print: {}", e))?;
+            Ok::<(), String>(())
+        }.await;
+
+        invalidate_host_status(&host.ip_address);
+
+        match result {
+            Ok(()) => {
+                Self::update_job(queue, &job.id, |j| {
+                    j.status = QueueJobStatus::Printing;
+                    j.started_at = Some(chrono::Utc::now().to_rfc3339());
+                }).await;
+            }
+            Err(e) => {
+                eprintln!("Print queue: {}", e);
+                Self::update_job(queue, &job.id, |j| j.finish(QueueJobStatus::Failed, Some(e))).await;
+            }
+        }
+    }
+
+    /// Checks a running job and marks it completed or failed when it ends
+    async fn track_job(queue: &Arc<Mutex<PrintQueue>>, job: &QueueJob) {
+        let host = match &job.assigned_host {
+            Some(host) => host.clone(),
+            None => return,
+        };
+
+        // Offline printers keep their job until they come back
+        let info = match get_print_info(&host, None).await {
+            Ok(Some(info)) => info,
+            _ => return,
+        };
+
+        let started_recently = job.started_at.as_deref()
+            .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| (chrono::Utc::now() - t.with_timezone(&chrono::Utc)).num_seconds() < PRINT_START_GRACE_SECONDS)
+            .unwrap_or(false);
+        let same_file = info.filename == job.file_name;
+
+        let outcome = match info.status.as_str() {
+            "printing" | "paused" if same_file => None,
+            "complete" if same_file => Some((QueueJobStatus::Completed, None)),
+            "cancelled" if same_file => Some((QueueJobStatus::Failed, Some("Print was cancelled".to_string()))),
+            "error" if same_file => Some((QueueJobStatus::Failed, Some("Printer reported an error".to_string()))),
+            _ if started_recently => None,
+            _ => Some((QueueJobStatus::Failed, Some("Print is no longer running on the printer".to_string()))),
+        };
+
+        if let Some((status, error)) = outcome {
+            println!("Print queue: {} on {} finished as {:?}", job.file_name, host, status);
+            Self::update_job(queue, &job.id, |j| j.finish(status, error)).await;
+        }
+    }
+
+    /// Applies a change to a job and saves the queue
+    async fn update_job(queue: &Arc<Mutex<PrintQueue>>, job_id: &str, change: impl FnOnce(&mut QueueJob)) {
+        let mut queue = queue.lock().await;
+        if let Some(job) = queue.jobs.iter_mut().find(|j| j.id == job_id) {
+            change(job);
+        }
+        if let Err(e) = queue.save() {
+            eprintln!("Print queue: failed to save queue: {}", e);
+        }
+    }
+}
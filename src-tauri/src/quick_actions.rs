@@ -0,0 +1,84 @@
+//! Custom quick action execution
+//!
+//! Runs the buttons users added to a host (see
+//! [`crate::models::quick_actions`]) for the desktop app and the Telegram
+//! bot, so both report the same result.
+
+use std::time::Duration;
+use reqwest::Client;
+use crate::api::client::{configure_http_client, HttpTarget};
+use crate::api::printer::run_gcode;
+use crate::models::config::AppSettings;
+use crate::models::quick_actions::{QuickAction, QuickActionKind};
+use crate::network::ssh::run_ssh_command;
+
+/// Seconds an HTTP or SSH action may take
+const QUICK_ACTION_TIMEOUT_SECONDS: u64 = 30;
+
+/// Longest action output returned, in characters
+const MAX_OUTPUT_CHARS: usize = 500;
+
+/// Finds a quick action of a host in the settings
+///
+/// # Arguments
+/// * `host` - Host IP address
+/// * `id` - Action identifier
+pub fn find_quick_action(host: &str, id: &str) -> Result<QuickAction, String> {
+    let settings = AppSettings::load().map_err(|e| format!("Failed to load settings: {}", e))?;
+    settings
+        .quick_actions
+        .get(host)
+        .and_then(|actions| actions.iter().find(|a| a.id == id))
+        .cloned()
+        .ok_or_else(|| format!("Quick action not found: {}", id))
+}
+
+/// Runs a quick action on a host
+///
+/// # Arguments
+/// * `host` - Host IP address
+/// * `action` - Action to run
+///
+/// # Returns
+/// * Output of the action, shortened to a few hundred characters
+pub async fn run_quick_action(host: &str, action: &QuickAction) -> Result<String, String> {
+    let timeout = Duration::from_secs(QUICK_ACTION_TIMEOUT_SECONDS);
+    let output = match &action.action {
+        QuickActionKind::Gcode { script } => {
+            run_gcode(host, script).await.map_err(|e| e.to_string())?;
+            "ok".to_string()
+        }
+        QuickActionKind::Http { method, url, body } => send_http_action(method, url, body.as_deref(), timeout).await?,
+        QuickActionKind::Ssh { user, command } => run_ssh_command(host, user, command, timeout).await.map_err(|e| e.to_string())?,
+    };
+
+    let output = output.trim();
+    Ok(match output.char_indices().nth(MAX_OUTPUT_CHARS) {
+        Some((end, _)) => format!("{}…", &output[..end]),
+        None => output.to_string(),
+    })
+}
+
+/// Sends the HTTP request of a quick action
+async fn send_http_action(method: &str, url: &str, body: Option<&str>, timeout: Duration) -> Result<String, String> {
+    let method = reqwest::Method::from_bytes(method.to_uppercase().as_bytes()).map_err(|e| format!("Invalid HTTP method: {}", e))?;
+    let client = configure_http_client(Client::builder(), HttpTarget::Internet)
+        .timeout(timeout)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let mut request = client.request(method, url);
+    if let Some(body) = body {
+        request = match serde_json::from_str::<serde_json::Value>(body) {
+            Ok(json) => request.json(&json),
+            Err(_) => request.body(body.to_string()),
+        };
+    }
+
+    let response = request.send().await.map_err(|e| format!("Request failed: {}", e))?;
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        return Err(format!("Server answered {}: {}", status, text.trim()));
+    }
+    Ok(if text.trim().is_empty() { status.to_string() } else { text })
+}
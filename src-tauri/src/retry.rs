@@ -0,0 +1,177 @@
+//! Retry with exponential backoff
+//!
+//! This module provides a small retry helper used for Moonraker API checks,
+//! port probes, Telegram sends and update checks. Delays grow exponentially
+//! and are jittered so many hosts failing at once do not retry in lockstep.
+//! Only errors that can go away on their own (timeouts, dropped connections,
+//! 5xx responses, Telegram flood control) are retried.
+
+use std::future::Future;
+use std::time::Duration;
+use crate::error::MoonrakerError;
+
+/// How often and how fast an operation is retried
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_delay: Duration,
+    /// Upper bound for a single delay
+    pub max_delay: Duration,
+    /// Factor the delay grows by after each retry
+    pub multiplier: f64,
+    /// Fraction of the delay that is randomized (0.0 - 1.0)
+    pub jitter: f64,
+}
+
+impl RetryPolicy {
+    /// Creates a policy doubling the delay after each retry, with 50% jitter
+    ///
+    /// # Arguments
+    /// * `max_attempts` - Total number of attempts (1 disables retries)
+    /// * `initial_delay` - Delay before the first retry
+    /// * `max_delay` - Upper bound for a single delay
+    pub const fn new(max_attempts: u32, initial_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            initial_delay,
+            max_delay,
+            multiplier: 2.0,
+            jitter: 0.5,
+        }
+    }
+
+    /// Gets the delay before the given retry (0 = first retry)
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let base = self.initial_delay.as_secs_f64() * self.multiplier.powi(retry as i32);
+        let capped = base.min(self.max_delay.as_secs_f64());
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let factor = 1.0 - jitter + jitter * rand::random::<f64>();
+        Duration::from_secs_f64(capped * factor)
+    }
+}
+
+/// Errors that know whether retrying can help
+pub trait Retryable {
+    /// Checks whether the failed operation may succeed when repeated
+    fn is_retryable(&self) -> bool;
+
+    /// Delay requested by the server, used instead of the backoff delay
+    fn retry_after(&self) -> Option<Duration> {
+        None
+    }
+}
+
+impl Retryable for reqwest::Error {
+    fn is_retryable(&self) -> bool {
+        if let Some(status) = self.status() {
+            return status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+        }
+        self.is_timeout() || self.is_connect() || self.is_request()
+    }
+}
+
+impl Retryable for MoonrakerError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            MoonrakerError::Network(e) => e.is_retryable(),
+            MoonrakerError::Timeout(_) => true,
+            // Gateway errors of a proxy in front of a restarting Moonraker
+            MoonrakerError::Http { status, .. } => matches!(
+                *status,
+                reqwest::StatusCode::BAD_GATEWAY | reqwest::StatusCode::SERVICE_UNAVAILABLE | reqwest::StatusCode::GATEWAY_TIMEOUT
+            ),
+            _ => false,
+        }
+    }
+}
+
+impl Retryable for std::io::Error {
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self.kind(),
+            std::io::ErrorKind::TimedOut
+                | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::Interrupted
+        )
+    }
+}
+
+impl Retryable for teloxide::RequestError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            teloxide::RequestError::RetryAfter(_) => true,
+            teloxide::RequestError::Network(e) => e.is_retryable(),
+            teloxide::RequestError::Io(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            teloxide::RequestError::RetryAfter(delay) => Some(*delay),
+            _ => None,
+        }
+    }
+}
+
+/// Runs an operation, retrying retryable failures according to a policy
+///
+/// # Arguments
+/// * `policy` - Number of attempts and delays
+/// * `operation` - Creates the future for one attempt
+///
+/// # Returns
+/// * The first success, or the last error
+pub async fn retry<T, E, F, Fut>(policy: &RetryPolicy, mut operation: F) -> Result<T, E>
+where
+    E: Retryable,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < policy.max_attempts && e.is_retryable() => {
+                let delay = e.retry_after().unwrap_or_else(|| policy.delay_for(attempt - 1));
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delays_grow_exponentially_up_to_the_cap() {
+        let policy = RetryPolicy { jitter: 0.0, ..RetryPolicy::new(5, Duration::from_millis(100), Duration::from_millis(350)) };
+        let delays: Vec<Duration> = (0..4).map(|retry| policy.delay_for(retry)).collect();
+        assert_eq!(delays, [100, 200, 350, 350].map(Duration::from_millis));
+
+        let jittered = RetryPolicy::new(5, Duration::from_millis(100), Duration::from_secs(10));
+        for _ in 0..100 {
+            let delay = jittered.delay_for(1);
+            assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(200), "{:?}", delay);
+        }
+    }
+
+    #[test]
+    fn only_transient_http_errors_are_retried() {
+        let http = |status| MoonrakerError::Http { status, message: String::new() };
+        assert!(http(reqwest::StatusCode::SERVICE_UNAVAILABLE).is_retryable());
+        assert!(http(reqwest::StatusCode::BAD_GATEWAY).is_retryable());
+        assert!(!http(reqwest::StatusCode::NOT_FOUND).is_retryable());
+        assert!(!MoonrakerError::Api("HTTP 503 in a message".to_string()).is_retryable());
+        assert_eq!(
+            http(reqwest::StatusCode::SERVICE_UNAVAILABLE).to_string(),
+            "API error: HTTP 503 Service Unavailable: "
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! Automation rule evaluation
+//!
+//! The background monitor checks the user's rules (see
+//! [`crate::models::rules`]) against the metrics of every host. A rule
+//! triggers once when its conditions start to hold and is re-armed when they
+//! stop holding, so a printer staying in the error state is not reported on
+//! every cycle.
+
+use std::collections::{HashMap, HashSet};
+use tokio::time::Instant;
+use crate::api::printer::{get_sensors, get_telemetry_sample};
+use crate::models::api::PrinterState;
+use crate::models::host::{HostInfo, HostStatusResponse};
+use crate::models::rules::{AutomationRule, RuleMetrics};
+use crate::notifications::webhook::post_webhook;
+
+/// Progress changes smaller than this (in percent) do not count as advancing
+const STALL_PROGRESS_EPSILON: f64 = 0.01;
+
+/// Rule state carried between monitoring cycles
+#[derive(Debug, Default)]
+pub struct RuleEngine {
+    /// Last progress change of printing hosts: progress and when it was reached
+    progress: HashMap<String, (f64, Instant)>,
+    /// Rules whose conditions held in the previous cycle, as (rule id, host)
+    active: HashSet<(String, String)>,
+}
+
+impl RuleEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the progress of a host and returns for how many minutes a
+    /// printing job has not advanced
+    ///
+    /// # Arguments
+    /// * `host` - Host IP address
+    /// * `state` - Printer state
+    /// * `progress` - Job progress in percent
+    pub fn record_progress(&mut self, host: &str, state: PrinterState, progress: Option<f64>) -> Option<f64> {
+        let (Some(progress), PrinterState::Printing) = (progress, state) else {
+            // Paused or finished jobs are not stalled
+            self.progress.remove(host);
+            return None;
+        };
+        let entry = self.progress.entry(host.to_string()).or_insert((progress, Instant::now()));
+        if (progress - entry.0).abs() >= STALL_PROGRESS_EPSILON {
+            *entry = (progress, Instant::now());
+        }
+        Some(entry.1.elapsed().as_secs_f64() / 60.0)
+    }
+
+    /// Records whether a rule matches a host and tells whether it just started to
+    ///
+    /// # Returns
+    /// * true when the rule matches now but did not in the previous cycle
+    pub fn update(&mut self, rule_id: &str, host: &str, matches: bool) -> bool {
+        let key = (rule_id.to_string(), host.to_string());
+        if matches {
+            self.active.insert(key)
+        } else {
+            self.active.remove(&key);
+            false
+        }
+    }
+
+    /// Forgets the state of rules that no longer exist
+    pub fn retain_rules(&mut self, rules: &[AutomationRule]) {
+        self.active.retain(|(id, _)| rules.iter().any(|rule| &rule.id == id));
+    }
+}
+
+/// Reads the metrics of a host needed by its rules
+///
+/// Heater temperatures and progress come from one query; the additional
+/// sensors are only read when a rule has a condition on them.
+///
+/// # Arguments
+/// * `host` - Host IP address
+/// * `status` - Status from the current monitoring cycle
+/// * `rules` - Rules applying to the host
+pub async fn collect_metrics(host: &str, status: &HostStatusResponse, rules: &[&AutomationRule]) -> RuleMetrics {
+    let state = status.state();
+    let mut metrics = RuleMetrics::new(state);
+    if !status.success {
+        return metrics;
+    }
+
+    if let Ok(sample) = get_telemetry_sample(host, state).await {
+        metrics.extruder_temperature = sample.extruder_temperature;
+        metrics.bed_temperature = sample.bed_temperature;
+        metrics.progress = sample.progress;
+    }
+    if rules.iter().flat_map(|rule| &rule.conditions).any(|condition| condition.needs_sensors()) {
+        if let Ok(sensors) = get_sensors(host).await {
+            metrics.sensors = sensors
+                .into_iter()
+                .filter_map(|sensor| Some((sensor.name, sensor.temperature?)))
+                .collect();
+        }
+    }
+    metrics
+}
+
+/// Posts a triggered rule to a webhook
+///
+/// The payload is JSON with the rule, the host and the metrics that matched.
+///
+/// # Arguments
+/// * `url` - Webhook URL
+/// * `rule` - Triggered rule
+/// * `host` - Host the rule triggered for
+/// * `metrics` - Metrics of the host
+/// * `snapshot` - Webcam image posted along with the payload
+pub async fn send_rule_webhook(
+    url: &str,
+    rule: &AutomationRule,
+    host: &HostInfo,
+    metrics: &RuleMetrics,
+    snapshot: Option<Vec<u8>>,
+) -> Result<(), String> {
+    let payload = serde_json::json!({
+        "rule_id": rule.id,
+        "rule": rule.name,
+        "host": host.ip_address,
+        "hostname": host.hostname,
+        "triggered_at": chrono::Utc::now().to_rfc3339(),
+        "metrics": metrics,
+    });
+    post_webhook(url, &payload, snapshot).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rule_triggers_once_until_it_stops_matching() {
+        let mut engine = RuleEngine::new();
+        assert!(engine.update("rule", "192.168.1.50", true));
+        assert!(!engine.update("rule", "192.168.1.50", true));
+        assert!(engine.update("rule", "192.168.1.51", true));
+        assert!(!engine.update("rule", "192.168.1.50", false));
+        assert!(engine.update("rule", "192.168.1.50", true));
+
+        assert!(engine.record_progress("192.168.1.50", PrinterState::Printing, Some(10.0)).is_some_and(|minutes| minutes < 1.0));
+        assert_eq!(engine.record_progress("192.168.1.50", PrinterState::Paused, Some(10.0)), None);
+    }
+}
@@ -0,0 +1,114 @@
+//! REST API request handlers
+//!
+//! Handlers mirror the Tauri commands and return JSON. Errors are reported
+//! as `{"error": "..."}` with an appropriate HTTP status code.
+
+use axum::extract::{Path, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+
+use crate::api::print_info::get_print_info;
+use crate::api::printer::control_printer_with_string;
+use crate::background_monitor::BackgroundMonitorState;
+use crate::error::MoonrakerError;
+use crate::models::{HostHealth, HostInfo, HostStatusResponse, PrintJobInfo};
+use crate::server::ApiContext;
+
+/// JSON error response
+pub struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+impl From<MoonrakerError> for ApiError {
+    fn from(err: MoonrakerError) -> Self {
+        let status = match err {
+            MoonrakerError::InvalidIp(_) | MoonrakerError::InvalidSubnet(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::BAD_GATEWAY,
+        };
+        ApiError(status, err.to_string())
+    }
+}
+
+/// Rejects requests without a valid `Authorization: Bearer <token>` header
+pub async fn require_token(State(context): State<ApiContext>, request: Request, next: Next) -> Response {
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match provided {
+        Some(token) if constant_time_eq(token.as_bytes(), context.token.as_bytes()) => next.run(request).await,
+        _ => ApiError(StatusCode::UNAUTHORIZED, "Invalid or missing API token".to_string()).into_response(),
+    }
+}
+
+/// Compares two byte strings without short-circuiting on the first mismatch
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Ensures the requested IP belongs to a known host
+async fn ensure_known_host(context: &ApiContext, ip: &str) -> Result<(), ApiError> {
+    let hosts = context.hosts.lock().await;
+    if hosts.iter().any(|h| h.ip_address == ip) {
+        Ok(())
+    } else {
+        Err(ApiError(StatusCode::NOT_FOUND, format!("Unknown host: {}", ip)))
+    }
+}
+
+/// GET /api/hosts
+pub async fn list_hosts(State(context): State<ApiContext>) -> Json<Vec<HostInfo>> {
+    let hosts = context.hosts.lock().await;
+    Json(hosts.clone())
+}
+
+/// GET /api/hosts/:ip/status
+pub async fn host_status(
+    State(context): State<ApiContext>,
+    Path(ip): Path<String>,
+) -> Result<Json<HostStatusResponse>, ApiError> {
+    ensure_known_host(&context, &ip).await?;
+    Ok(Json(BackgroundMonitorState::check_and_record(&context.health, &ip).await))
+}
+
+/// GET /api/hosts/:ip/print
+pub async fn print_info(
+    State(context): State<ApiContext>,
+    Path(ip): Path<String>,
+) -> Result<Json<Option<PrintJobInfo>>, ApiError> {
+    ensure_known_host(&context, &ip).await?;
+    Ok(Json(get_print_info(&ip, None).await?))
+}
+
+/// GET /api/hosts/:ip/health
+pub async fn host_health(
+    State(context): State<ApiContext>,
+    Path(ip): Path<String>,
+) -> Result<Json<HostHealth>, ApiError> {
+    ensure_known_host(&context, &ip).await?;
+    let registry = context.health.lock().await;
+    registry
+        .get(&ip)
+        .map(|stats| Json(stats.summary(&ip)))
+        .ok_or_else(|| ApiError(StatusCode::NOT_FOUND, format!("No health data for {}", ip)))
+}
+
+/// POST /api/hosts/:ip/control/:action
+pub async fn control_printer(
+    State(context): State<ApiContext>,
+    Path((ip, action)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    ensure_known_host(&context, &ip).await?;
+    Ok(Json(control_printer_with_string(&ip, &action).await?))
+}
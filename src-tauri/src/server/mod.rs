@@ -0,0 +1,145 @@
+//! Embedded REST API server
+//!
+//! This module provides an optional HTTP server (axum) exposing the same
+//! functionality as the Tauri commands, so scripts or other clients can talk
+//! to MHS running on a home server. Every request requires a bearer token.
+
+pub mod handlers;
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use axum::routing::{get, post};
+use axum::{middleware, Router};
+use tokio::sync::{oneshot, Mutex};
+
+use crate::background_monitor::HealthRegistry;
+use crate::models::config::RestApiSettings;
+use crate::models::HostInfo;
+
+/// Shared data available to every request handler
+#[derive(Clone)]
+pub struct ApiContext {
+    /// Hosts known to the application
+    pub hosts: Arc<Mutex<Vec<HostInfo>>>,
+    /// Rolling health metrics
+    pub health: HealthRegistry,
+    /// Required bearer token
+    pub token: Arc<String>,
+}
+
+/// Running server handle
+struct RunningServer {
+    address: SocketAddr,
+    shutdown: oneshot::Sender<()>,
+}
+
+/// REST API server state managed by Tauri
+pub struct RestApiState {
+    server: Mutex<Option<RunningServer>>,
+}
+
+impl RestApiState {
+    /// Creates a stopped server state
+    pub fn new() -> Self {
+        Self {
+            server: Mutex::new(None),
+        }
+    }
+
+    /// Starts the server with the given settings
+    ///
+    /// # Arguments
+    /// * `settings` - Bind address, port and token
+    /// * `hosts` - Shared host list
+    /// * `health` - Shared health registry
+    ///
+    /// # Returns
+    /// * Address the server listens on
+    pub async fn start(
+        &self,
+        settings: &RestApiSettings,
+        hosts: Arc<Mutex<Vec<HostInfo>>>,
+        health: HealthRegistry,
+    ) -> Result<String, String> {
+        let mut server = self.server.lock().await;
+        if server.is_some() {
+            return Err("REST API server is already running".to_string());
+        }
+
+        let token = settings.token.clone()
+            .filter(|t| !t.trim().is_empty())
+            .ok_or("REST API token is not set. Please set a token first.")?;
+
+        let address: SocketAddr = format!("{}:{}", settings.bind_address, settings.port)
+            .parse()
+            .map_err(|e| format!("Invalid bind address: {}", e))?;
+
+        let listener = tokio::net::TcpListener::bind(address)
+            .await
+            .map_err(|e| format!("Failed to bind {}: {}", address, e))?;
+
+        let context = ApiContext {
+            hosts,
+            health,
+            token: Arc::new(token),
+        };
+        let app = build_router(context);
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        tokio::spawn(async move {
+            let result = axum::serve(listener, app)
+                .with_graceful_shutdown(async {
+                    let _ = shutdown_rx.await;
+                })
+                .await;
+            if let Err(e) = result {
+                eprintln!("REST API server error: {}", e);
+            }
+            println!("REST API server stopped.");
+        });
+
+        println!("REST API server listening on {}", address);
+        *server = Some(RunningServer {
+            address,
+            shutdown: shutdown_tx,
+        });
+
+        Ok(address.to_string())
+    }
+
+    /// Stops the server if it is running
+    pub async fn stop(&self) -> Result<(), String> {
+        let mut server = self.server.lock().await;
+        match server.take() {
+            Some(running) => {
+                let _ = running.shutdown.send(());
+                Ok(())
+            }
+            None => Err("REST API server is not running".to_string()),
+        }
+    }
+
+    /// Gets the listening address if the server is running
+    pub async fn address(&self) -> Option<String> {
+        let server = self.server.lock().await;
+        server.as_ref().map(|s| s.address.to_string())
+    }
+}
+
+impl Default for RestApiState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builds the API router
+fn build_router(context: ApiContext) -> Router {
+    Router::new()
+        .route("/api/hosts", get(handlers::list_hosts))
+        .route("/api/hosts/:ip/status", get(handlers::host_status))
+        .route("/api/hosts/:ip/print", get(handlers::print_info))
+        .route("/api/hosts/:ip/health", get(handlers::host_health))
+        .route("/api/hosts/:ip/control/:action", post(handlers::control_printer))
+        .layer(middleware::from_fn_with_state(context.clone(), handlers::require_token))
+        .with_state(context)
+}
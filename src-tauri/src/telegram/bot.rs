@@ -2,66 +2,63 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::Mutex;
 use teloxide::{prelude::*, utils::command::BotCommands, types::{InlineKeyboardButton, InlineKeyboardMarkup, ParseMode, InputFile, MessageId}};
-use crate::models::{TelegramUser, RegistrationState, VideoRequestState, EmergencyStopRequestState, UserSessionState, MenuState, HostCache};
+use crate::models::{AvailabilityForecast, KlippyState, PrinterState, TelegramUser, TelegramRole, PendingRegistration, RegistrationCode, RegistrationLink, TelegramAuditEntry, TelegramAuditLog, RegistrationState, VideoRequestState, EmergencyStopRequestState, UserSessionState, MenuState, HostCache};
 use crate::models::host::HostInfo;
-use crate::api::print_info::{get_print_info, format_duration};
+use crate::api::print_info::{get_print_info, format_duration, format_duration_approx};
+use crate::api::capabilities::get_printer_capabilities;
+use crate::api::client::{build_web_ui_url, build_web_url, configure_http_client, with_host_headers, HttpTarget};
+use crate::api::host_limiter::acquire_host_permit;
+use crate::api::printer::{
+    adjust_z_offset, control_printer, get_last_print_filename, get_z_offset, toggle_led, toggle_part_fan, wait_for_klippy_ready,
+    PrinterAction, FIRMWARE_RESTART_TIMEOUT_SECONDS,
+};
+use crate::webcam::get_cached_snapshot;
+use crate::network::status_cache::{get_host_status, invalidate_host_status};
+use crate::network::ip_utils::{is_address_allowed, split_host_port};
+use crate::models::config::STATUS_REFRESH_CONCURRENCY;
+use futures::StreamExt;
+use crate::fleet::get_availability_forecast;
+use crate::preflight::start_print_checked;
+use crate::quick_actions::{find_quick_action, run_quick_action};
+use crate::telegram::callback_data::{host_callback, resolve_host_reference};
+use crate::telegram::host_match::{match_host, HostMatch};
+use crate::telegram::send_queue::{OutgoingMessage, SendQueue};
+use crate::i18n::{format_date_time, format_finish_time, t, tf};
+use crate::notifications::snooze::{acknowledge_alert, snooze_alert};
+use crate::notifications::system::send_notification;
+use crate::models::config::AlertKind;
+use crate::telegram::error::{TelegramError, TelegramResult};
 use std::time::Duration;
-
-/// Determines printer status based on Moonraker API flags
-/// Priority order: offline > cancelling > error > paused > printing > ready > standby
-fn get_printer_status(host: &HostInfo) -> String {
-    // First check if host is marked as offline
-    if host.status == "offline" {
-        return "offline".to_string();
-    }
-    
-    // Check if Klippy is completely disconnected (not just in error state)
-    if let Some(klippy_state) = &host.klippy_state {
-        if klippy_state == "disconnected" {
-            return "offline".to_string();
-        }
-    }
-    
-    // If no printer flags, check if we have any device status
-    if let Some(flags) = &host.printer_flags {
-        // Priority order: cancelling > error > paused > printing > ready > standby
-        if flags.cancelling {
-            return "cancelling".to_string();
-        }
-        if flags.error {
-            return "error".to_string();
-        }
-        if flags.paused {
-            return "paused".to_string();
-        }
-        if flags.printing {
-            return "printing".to_string();
-        }
-        if flags.ready {
-            return "standby".to_string();
-        }
-    } else {
-        // If no printer flags, check device status
-        if host.device_status == "offline" || host.device_status == "klippy_disconnected" {
-            return "offline".to_string();
-        }
-        // If Klippy is in error state but host responds, show error status
-        if let Some(klippy_state) = &host.klippy_state {
-            if klippy_state == "error" {
-                return "error".to_string();
-            }
-        }
-        return "standby".to_string();
-    }
-    
-    "standby".to_string()
-}
+use tauri::{AppHandle, Emitter};
+
+/// Z offset steps offered in Telegram, in mm
+const Z_OFFSET_STEPS: [f64; 4] = [-0.05, -0.01, 0.01, 0.05];
+/// Snooze lengths offered below alerts, in hours
+const ALERT_SNOOZE_HOURS: [u64; 2] = [2, 8];
+/// Callback data prefixes of actions that send commands to a printer
+const CONTROL_ACTION_PREFIXES: [&str; 12] = [
+    "host_led_", "host_fan_", "zadj_", "host_emergency_", "host_stop_print_", "host_firmware_restart_",
+    "emergency_confirm_", "stop_print_confirm_", "firmware_restart_confirm_", "qact_", "qactok_", "reprint_",
+];
 
 #[derive(BotCommands, Clone)]
 #[command(rename_rule = "lowercase", description = "MHS Bot commands:")]
 enum Command {
+    /// Carries the token of a registration link as `/start <token>`
     #[command(description = "Start the bot and show main menu")]
-    Start,
+    Start(String),
+    #[command(description = "Show the host list")]
+    Hosts,
+    #[command(description = "Show the status of all printers")]
+    Status,
+    #[command(description = "Send a webcam image: /snapshot <printer>")]
+    Snapshot(String),
+    #[command(description = "Pause the print: /pause <printer>")]
+    Pause(String),
+    #[command(description = "Stop the print after confirmation: /stop <printer>")]
+    Stop(String),
+    #[command(description = "Print the last file again after the pre-flight check: /reprint <printer>")]
+    Reprint(String),
 }
 
 #[derive(Clone)]
@@ -77,6 +74,9 @@ pub struct TelegramBot {
     user_sessions: Arc<Mutex<std::collections::HashMap<i64, UserSessionState>>>,
     host_cache: Arc<Mutex<HostCache>>,
     http_client: reqwest::Client,
+    send_queue: SendQueue,
+    /// Desktop app notified about registrations waiting for approval
+    app_handle: Option<AppHandle>,
 }
 
 impl TelegramBot {
@@ -88,19 +88,26 @@ impl TelegramBot {
     /// 
     /// # Returns
     /// * `Ok(TelegramBot)` - Successfully created bot instance
-    /// * `Err(String)` - Error message if creation failed
-    pub async fn new(bot_token: String, hosts: Arc<Mutex<Vec<crate::models::HostInfo>>>) -> Result<Self, String> {
+    /// * `Err(TelegramError)` - Error if creation failed
+    pub async fn new(bot_token: String, hosts: Arc<Mutex<Vec<crate::models::HostInfo>>>) -> TelegramResult<Self> {
         // Create HTTP client with timeout configuration
-        let http_client = reqwest::Client::builder()
+        let http_client = configure_http_client(reqwest::Client::builder(), HttpTarget::Printer)
             .timeout(Duration::from_secs(10))
             .build()
-            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+            .map_err(|e| TelegramError::Config(format!("Failed to create HTTP client: {}", e)))?;
+        // Bot API client, sent through the proxy unlike printer requests
+        let api_client = configure_http_client(teloxide::net::default_reqwest_settings(), HttpTarget::Internet)
+            .build()
+            .map_err(|e| TelegramError::Config(format!("Failed to create HTTP client: {}", e)))?;
         
+        let telegram_bot = Bot::with_client(bot_token, api_client);
+        let registered_users = Arc::new(Mutex::new(Vec::new()));
         let bot = Self {
-            bot: Bot::new(bot_token),
+            send_queue: SendQueue::new(telegram_bot.clone(), registered_users.clone()),
+            bot: telegram_bot,
             is_running: Arc::new(AtomicBool::new(false)),
             task_handle: Arc::new(Mutex::new(None)),
-            registered_users: Arc::new(Mutex::new(Vec::new())),
+            registered_users,
             _registration_state: Arc::new(Mutex::new(RegistrationState::new())),
             video_request_state: Arc::new(Mutex::new(VideoRequestState::new())),
             emergency_stop_request_state: Arc::new(Mutex::new(EmergencyStopRequestState::new())),
@@ -108,6 +115,7 @@ impl TelegramBot {
             user_sessions: Arc::new(Mutex::new(std::collections::HashMap::new())),
             host_cache: Arc::new(Mutex::new(HostCache::new())),
             http_client,
+            app_handle: None,
         };
         
         // Load users from file
@@ -116,14 +124,30 @@ impl TelegramBot {
         Ok(bot)
     }
 
+    /// Sets the app that is told about registrations waiting for approval
+    pub fn with_app_handle(mut self, app_handle: AppHandle) -> Self {
+        self.app_handle = Some(app_handle);
+        self
+    }
+
     /// Starts the Telegram bot
     /// 
+    /// The token is checked before the dispatcher is started, so an invalid
+    /// token is reported as `TelegramError::Unauthorized`.
+    /// 
     /// # Returns
     /// * `Ok(())` - Bot started successfully
-    /// * `Err(String)` - Error message if start failed
-    pub async fn start(&self) -> Result<(), String> {
+    /// * `Err(TelegramError)` - Error if start failed
+    pub async fn start(&self) -> TelegramResult<()> {
         if self.is_running.load(Ordering::Relaxed) {
-            return Err("Bot is already running".to_string());
+            return Err(TelegramError::AlreadyRunning);
+        }
+
+        let bot_info = self.bot.get_me().await?;
+        println!("Bot started successfully: @{}", bot_info.username());
+
+        if let Err(e) = self.bot.set_my_commands(Command::bot_commands()).await {
+            println!("Failed to set bot commands: {}", e);
         }
 
         let bot = self.bot.clone();
@@ -138,27 +162,11 @@ impl TelegramBot {
         let user_sessions = self.user_sessions.clone();
         let host_cache = self.host_cache.clone();
         let http_client = self.http_client.clone();
+        let send_queue = self.send_queue.clone();
+        let app_handle = self.app_handle.clone();
         
+        is_running.store(true, Ordering::Relaxed);
         let handle = tokio::spawn(async move {
-            is_running.store(true, Ordering::Relaxed);
-            
-            // Test bot token by getting bot info first
-            match bot.get_me().await {
-                Ok(bot_info) => {
-                    println!("Bot started successfully: @{}", bot_info.username());
-                    
-                    // Set bot commands menu only if bot is valid
-                    if let Err(e) = bot.set_my_commands(Command::bot_commands()).await {
-                        println!("Failed to set bot commands: {}", e);
-                    }
-                }
-                Err(e) => {
-                    println!("Failed to start bot - invalid token: {}", e);
-                    is_running.store(false, Ordering::Relaxed);
-                    return;
-                }
-            }
-            
             let handler = dptree::entry()
                 .branch(Update::filter_message().endpoint({
                     let users = registered_users.clone();
@@ -169,8 +177,9 @@ impl TelegramBot {
                     let sessions = user_sessions.clone();
                     let cache = host_cache.clone();
                     let client = http_client.clone();
+                    let app = app_handle.clone();
                     move |bot, msg| {
-                        message_handler(bot, msg, users.clone(), reg_state.clone(), video_state.clone(), emergency_state.clone(), hosts.clone(), sessions.clone(), cache.clone(), client.clone())
+                        message_handler(bot, msg, users.clone(), reg_state.clone(), video_state.clone(), emergency_state.clone(), hosts.clone(), sessions.clone(), cache.clone(), client.clone(), app.clone())
                     }
                 }))
                 .branch(Update::filter_callback_query().endpoint({
@@ -179,8 +188,10 @@ impl TelegramBot {
                     let cache = host_cache.clone();
                     let hosts = hosts.clone();
                     let client = http_client.clone();
+                    let queue = send_queue.clone();
+                    let app = app_handle.clone();
                     move |bot, q| {
-                        callback_handler(bot, q, users.clone(), sessions.clone(), cache.clone(), hosts.clone(), client.clone())
+                        callback_handler(bot, q, users.clone(), sessions.clone(), cache.clone(), hosts.clone(), client.clone(), queue.clone(), app.clone())
                     }
                 }));
 
@@ -203,9 +214,9 @@ impl TelegramBot {
         Ok(())
     }
 
-    pub async fn stop(&self) -> Result<(), String> {
+    pub async fn stop(&self) -> TelegramResult<()> {
         if !self.is_running.load(Ordering::Relaxed) {
-            return Err("Bot is not running".to_string());
+            return Err(TelegramError::NotRunning);
         }
 
         self.is_running.store(false, Ordering::Relaxed);
@@ -222,17 +233,17 @@ impl TelegramBot {
         self.is_running.load(Ordering::Relaxed)
     }
 
-    pub async fn start_registration(&self) -> Result<String, String> {
+    pub async fn start_registration(&self) -> TelegramResult<String> {
         let mut reg_state = self._registration_state.lock().await;
-        if reg_state.is_active {
-            return Err("Registration is already active".to_string());
+        if reg_state.is_active() {
+            return Err(TelegramError::RegistrationActive);
         }
         
         let code = reg_state.start_registration();
         Ok(code)
     }
 
-    pub async fn stop_registration(&self) -> Result<(), String> {
+    pub async fn stop_registration(&self) -> TelegramResult<()> {
         let mut reg_state = self._registration_state.lock().await;
         reg_state.finish_registration();
         Ok(())
@@ -240,7 +251,41 @@ impl TelegramBot {
 
     pub async fn is_registration_active(&self) -> bool {
         let reg_state = self._registration_state.lock().await;
-        reg_state.is_active && !reg_state.is_expired()
+        reg_state.is_active()
+    }
+
+    /// Creates an additional registration code, next to the ones already active
+    /// 
+    /// # Arguments
+    /// * `role` - Role suggested for users of the code
+    /// * `valid_for` - Time until the code expires
+    /// * `label` - Description shown in the desktop app
+    /// * `single_use` - Whether the code stops working after one registration
+    pub async fn create_registration_code(
+        &self,
+        role: TelegramRole,
+        valid_for: chrono::Duration,
+        label: Option<String>,
+        single_use: bool,
+    ) -> RegistrationCode {
+        let mut reg_state = self._registration_state.lock().await;
+        reg_state.add_code(role, valid_for, label, single_use)
+    }
+
+    /// Gets the registration codes that can still be used
+    pub async fn get_registration_codes(&self) -> Vec<RegistrationCode> {
+        let mut reg_state = self._registration_state.lock().await;
+        reg_state.prune_expired();
+        reg_state.codes.clone()
+    }
+
+    /// Revokes one registration code
+    pub async fn revoke_registration_code(&self, code: &str) -> TelegramResult<()> {
+        let mut reg_state = self._registration_state.lock().await;
+        if !reg_state.revoke_code(code) {
+            return Err(TelegramError::Config(format!("Registration code not found: {}", code)));
+        }
+        Ok(())
     }
 
     pub async fn get_registration_state(&self) -> crate::models::RegistrationState {
@@ -248,23 +293,103 @@ impl TelegramBot {
         reg_state.clone()
     }
 
+    /// Gets the deep link and QR code of a registration code
+    /// 
+    /// Opening the link sends `/start <token>` to the bot, which registers
+    /// the user like the correct code would.
+    /// 
+    /// # Arguments
+    /// * `code` - Registration code (the most recent one when omitted)
+    /// 
+    /// # Returns
+    /// * `Ok(RegistrationLink)` - Link and QR code
+    /// * `Err(TelegramError)` - No such active code, or the bot name could not be read
+    pub async fn get_registration_link(&self, code: Option<&str>) -> TelegramResult<RegistrationLink> {
+        let token = {
+            let mut reg_state = self._registration_state.lock().await;
+            reg_state.prune_expired();
+            reg_state.codes.iter()
+                .rev()
+                .find(|c| code.is_none_or(|code| c.code == code))
+                .map(|c| c.link_token.clone())
+                .ok_or_else(|| TelegramError::Config("Registration is not active".to_string()))?
+        };
 
-    pub async fn save_users_to_file(&self) -> Result<(), String> {
-        let users = self.registered_users.lock().await;
-        
-        // Save to config file instead of separate file
-        let mut settings = crate::models::config::AppSettings::load()
-            .map_err(|e| format!("Failed to load settings: {}", e))?;
-        settings.telegram.registered_users = (*users).clone();
-        settings.save().map_err(|e| format!("Failed to save settings: {}", e))?;
-        
+        let me = self.bot.get_me().await?;
+        let url = format!("https://t.me/{}?start={}", me.username(), token);
+        let qr_svg = qrcode::QrCode::new(url.as_bytes())
+            .map_err(|e| TelegramError::Config(format!("Failed to create QR code: {}", e)))?
+            .render::<qrcode::render::svg::Color>()
+            .min_dimensions(240, 240)
+            .build();
+
+        Ok(RegistrationLink { url, qr_svg })
+    }
+
+    /// Replaces the bot's host cache with the current shared host list
+    ///
+    /// Called whenever the host list changes (scan, monitor cycle, frontend
+    /// update) so the bot never shows state older than the app's own.
+    pub async fn refresh_host_cache(&self) {
+        let hosts = self.hosts.lock().await.clone();
+        self.host_cache.lock().await.update_hosts(hosts);
+    }
+
+    /// Gets the users waiting for approval
+    pub async fn get_pending_registrations(&self) -> Vec<PendingRegistration> {
+        self._registration_state.lock().await.pending.clone()
+    }
+
+    /// Approves a pending registration and welcomes the user
+    /// 
+    /// # Arguments
+    /// * `user_id` - Telegram user ID
+    /// * `role` - Role given to the user, the one of the registration code when omitted
+    /// 
+    /// # Returns
+    /// * The registered user
+    pub async fn approve_registration(&self, user_id: i64, role: Option<TelegramRole>) -> TelegramResult<TelegramUser> {
+        let pending = self._registration_state.lock().await
+            .take_pending(user_id)
+            .ok_or(TelegramError::UserNotFound(user_id))?;
+        let user = pending.approve(role.unwrap_or(pending.role));
+        self.add_user(user.clone()).await?;
+
+        let keyboard = InlineKeyboardMarkup::new(vec![
+            vec![InlineKeyboardButton::callback(t("bot.button.hosts_list"), "hosts_list")],
+            vec![InlineKeyboardButton::callback(t("bot.button.settings"), "settings")],
+            vec![InlineKeyboardButton::callback(t("bot.button.help"), "help")],
+        ]);
+        let welcome_message = tf("bot.registration.success", &[("name", &user.display_name())]);
+        self.bot.send_message(ChatId(user_id), welcome_message)
+            .reply_markup(keyboard)
+            .await?;
+
+        Ok(user)
+    }
+
+    /// Denies a pending registration and tells the user
+    /// 
+    /// # Arguments
+    /// * `user_id` - Telegram user ID
+    pub async fn deny_registration(&self, user_id: i64) -> TelegramResult<()> {
+        self._registration_state.lock().await
+            .take_pending(user_id)
+            .ok_or(TelegramError::UserNotFound(user_id))?;
+        self.bot.send_message(ChatId(user_id), t("bot.registration.denied")).await?;
         Ok(())
     }
 
-    pub async fn load_users_from_file(&self) -> Result<(), String> {
+
+    pub async fn save_users_to_file(&self) -> TelegramResult<()> {
+        let users = self.registered_users.lock().await;
+        save_users_to_file(&users).await
+    }
+
+    pub async fn load_users_from_file(&self) -> TelegramResult<()> {
         // Load from config file instead of separate file
         let settings = crate::models::config::AppSettings::load()
-            .map_err(|e| format!("Failed to load settings: {}", e))?;
+            .map_err(|e| TelegramError::Config(format!("Failed to load settings: {}", e)))?;
         
         let mut registered_users = self.registered_users.lock().await;
         *registered_users = settings.telegram.registered_users;
@@ -277,12 +402,12 @@ impl TelegramBot {
         users.clone()
     }
 
-    pub async fn add_user(&self, user: TelegramUser) -> Result<(), String> {
+    pub async fn add_user(&self, user: TelegramUser) -> TelegramResult<()> {
         let mut users = self.registered_users.lock().await;
         
         // Check if user already exists
         if users.iter().any(|u| u.user_id == user.user_id) {
-            return Err("User already exists".to_string());
+            return Err(TelegramError::UserExists(user.user_id));
         }
         
         users.push(user);
@@ -293,7 +418,7 @@ impl TelegramBot {
         Ok(())
     }
 
-    pub async fn remove_user(&self, user_id: i64) -> Result<(), String> {
+    pub async fn remove_user(&self, user_id: i64) -> TelegramResult<()> {
         let mut users = self.registered_users.lock().await;
         users.retain(|user| user.user_id != user_id);
         drop(users); // Release the lock before calling save
@@ -311,12 +436,44 @@ impl TelegramBot {
         users.iter().any(|user| user.user_id == user_id.0 as i64)
     }
 
-    pub async fn get_hosts(&self) -> Result<Vec<crate::models::HostInfo>, String> {
+    pub async fn get_hosts(&self) -> TelegramResult<Vec<crate::models::HostInfo>> {
         let hosts = self.hosts.lock().await;
         Ok(hosts.clone())
     }
 
-    pub async fn send_notification_to_all_users(&self, title: &str, body: &str, host_ip: Option<&str>) -> Result<(), String> {
+    pub async fn send_notification_to_all_users(&self, title: &str, body: &str, host_ip: Option<&str>) -> TelegramResult<()> {
+        self.broadcast_notification(title, body, host_ip, None, None, None).await
+    }
+
+    /// Sends a notification with an animated GIF (e.g. a print timelapse) instead of the webcam photo
+    pub async fn send_animation_to_all_users(&self, title: &str, body: &str, animation: Vec<u8>) -> TelegramResult<()> {
+        self.broadcast_notification(title, body, None, Some(animation), None, None).await
+    }
+
+    /// Sends an alert with buttons to snooze or acknowledge it for the host
+    ///
+    /// Offline alerts come without webcam photo, the host cannot send one.
+    pub async fn send_alert_to_all_users(&self, title: &str, body: &str, host_ip: &str, kind: AlertKind) -> TelegramResult<()> {
+        let photo_host = (kind != AlertKind::Offline).then_some(host_ip);
+        self.broadcast_notification(title, body, photo_host, None, Some(alert_keyboard(host_ip, kind)), None).await
+    }
+
+    /// Sends an alert with snooze and acknowledge buttons to chosen registered users,
+    /// also when they turned their notifications off (e.g. secondary contacts)
+    pub async fn send_alert_to_users(&self, user_ids: &[i64], title: &str, body: &str, host_ip: &str, kind: AlertKind) -> TelegramResult<()> {
+        let photo_host = (kind != AlertKind::Offline).then_some(host_ip);
+        self.broadcast_notification(title, body, photo_host, None, Some(alert_keyboard(host_ip, kind)), Some(user_ids)).await
+    }
+
+    async fn broadcast_notification(
+        &self,
+        title: &str,
+        body: &str,
+        host_ip: Option<&str>,
+        animation: Option<Vec<u8>>,
+        reply_markup: Option<InlineKeyboardMarkup>,
+        recipients: Option<&[i64]>,
+    ) -> TelegramResult<()> {
         let users = self.registered_users.lock().await;
         
         if users.is_empty() {
@@ -331,39 +488,33 @@ impl TelegramBot {
         
         // Try to get webcam image if host_ip is provided
         let webcam_image = if let Some(ip) = host_ip {
-            get_webcam_image(ip, &self.http_client).await.ok()
+            get_webcam_image(ip, &self.http_client, &AddressAllowlist::load()).await.ok()
         } else {
             None
         };
         
         for user in users.iter() {
-            // Only send notification if user has notifications enabled
-            if !user.notifications_enabled {
+            // Only send notification if user has notifications enabled (or was chosen) and can be reached
+            let wanted = recipients.map_or(user.notifications_enabled, |ids| ids.contains(&user.user_id));
+            if !wanted || user.is_blocked() {
                 continue;
             }
             
-            let result = if let Some(image_data) = &webcam_image {
-                // Send message with photo
-                self.bot.send_photo(teloxide::types::ChatId(user.user_id), teloxide::types::InputFile::memory(image_data.clone()))
-                    .caption(&message)
-                    .parse_mode(teloxide::types::ParseMode::MarkdownV2)
-                    .await
-            } else {
-                // Send text message only
-                self.bot.send_message(teloxide::types::ChatId(user.user_id), &message)
-                    .parse_mode(teloxide::types::ParseMode::MarkdownV2)
-                    .await
-            };
-            
-            if let Err(e) = result {
-                eprintln!("Failed to send notification to user {}: {}", user.user_id, e);
-            }
+            // Sent by the queue worker, which handles rate limits and retries
+            self.send_queue.enqueue(OutgoingMessage {
+                chat_id: teloxide::types::ChatId(user.user_id),
+                text: message.clone(),
+                parse_mode: Some(ParseMode::MarkdownV2),
+                photo: webcam_image.clone(),
+                animation: animation.clone(),
+                reply_markup: reply_markup.clone(),
+            })?;
         }
         
         Ok(())
     }
 
-    pub async fn update_user_notifications(&self, user_id: i64, notifications_enabled: bool) -> Result<(), String> {
+    pub async fn update_user_notifications(&self, user_id: i64, notifications_enabled: bool) -> TelegramResult<()> {
         let mut users = self.registered_users.lock().await;
         
         if let Some(user) = users.iter_mut().find(|u| u.user_id == user_id) {
@@ -374,20 +525,17 @@ impl TelegramBot {
             self.save_users_to_file().await?;
             Ok(())
         } else {
-            Err(format!("User {} not found", user_id))
+            Err(TelegramError::UserNotFound(user_id))
         }
     }
 }
 
 
 // Standalone function to save users to file
-async fn save_users_to_file(users: &[TelegramUser]) -> Result<(), String> {
+pub(crate) async fn save_users_to_file(users: &[TelegramUser]) -> TelegramResult<()> {
     // Save to config file instead of separate file
-    let mut settings = crate::models::config::AppSettings::load()
-        .map_err(|e| format!("Failed to load settings: {}", e))?;
-    settings.telegram.registered_users = users.to_vec();
-    settings.save().map_err(|e| format!("Failed to save settings: {}", e))?;
-    Ok(())
+    crate::models::config::AppSettings::update(|settings| settings.telegram.registered_users = users.to_vec())
+        .map_err(TelegramError::Config)
 }
 
 async fn message_handler(
@@ -397,48 +545,103 @@ async fn message_handler(
     _registration_state: Arc<Mutex<RegistrationState>>,
     _video_request_state: Arc<Mutex<VideoRequestState>>,
     _emergency_stop_request_state: Arc<Mutex<EmergencyStopRequestState>>,
-    _hosts: Arc<Mutex<Vec<crate::models::HostInfo>>>,
-    _user_sessions: Arc<Mutex<std::collections::HashMap<i64, UserSessionState>>>,
-    _host_cache: Arc<Mutex<HostCache>>,
-    _http_client: reqwest::Client
+    hosts: Arc<Mutex<Vec<crate::models::HostInfo>>>,
+    user_sessions: Arc<Mutex<std::collections::HashMap<i64, UserSessionState>>>,
+    host_cache: Arc<Mutex<HostCache>>,
+    http_client: reqwest::Client,
+    app_handle: Option<AppHandle>,
 ) -> ResponseResult<()> {
     let user_id = match msg.from() {
         Some(user) => user.id,
         None => return Ok(()), // Ignore messages without sender
     };
-    let is_registered = {
-        let users = registered_users.lock().await;
-        users.iter().any(|user| user.user_id == user_id.0 as i64)
+    let registered_user = {
+        let mut users = registered_users.lock().await;
+        let user = users.iter_mut().find(|user| user.user_id == user_id.0 as i64);
+        let registered_user = user.as_deref().cloned();
+        // A user who writes again has unblocked the bot
+        if let Some(user) = user.filter(|u| u.is_blocked()) {
+            user.blocked_reason = None;
+            user.blocked_at = None;
+            if let Err(e) = save_users_to_file(&users).await {
+                eprintln!("Failed to save Telegram users: {}", e);
+            }
+        }
+        registered_user
     };
+    let is_registered = registered_user.is_some();
 
     if let Some(text) = msg.text() {
         // Handle commands
         if text.starts_with('/') {
             if let Ok(command) = Command::parse(text, "") {
                 match command {
-                    Command::Start => {
+                    Command::Start(token) => {
                         if is_registered {
                             // Show main menu for registered users
                             let keyboard = InlineKeyboardMarkup::new(vec![
-                                vec![InlineKeyboardButton::callback("📋 Список хостов", "hosts_list")],
-                                vec![InlineKeyboardButton::callback("⚙️ Настройки", "settings")],
-                                vec![InlineKeyboardButton::callback("❓ Помощь", "help")],
+                                vec![InlineKeyboardButton::callback(t("bot.button.hosts_list"), "hosts_list")],
+                                vec![InlineKeyboardButton::callback(t("bot.button.settings"), "settings")],
+                                vec![InlineKeyboardButton::callback(t("bot.button.help"), "help")],
                             ]);
 
-                            bot.send_message(msg.chat.id, "🤖 *Добро пожаловать в MHS Bot\\!*\n\nВыберите действие:")
+                            bot.send_message(msg.chat.id, t("bot.welcome"))
                                 .parse_mode(ParseMode::MarkdownV2)
                                 .reply_markup(keyboard)
                                 .await?;
                         } else {
-                            // Ignore unregistered users - don't send any response
+                            let mut reg_state = _registration_state.lock().await;
+                            if reg_state.is_pending(user_id.0 as i64) {
+                                bot.send_message(msg.chat.id, t("bot.registration.pending")).await?;
+                            } else if let Some(code) = reg_state.verify_link_token(token.trim()) {
+                                request_approval(&bot, &msg, &mut reg_state, code.role, &app_handle).await?;
+                            }
+                            // Anyone else is ignored - don't send any response
                             // This prevents unauthorized access and code generation
                             return Ok(());
                         }
                     }
+                    // Shortcuts are for registered users only
+                    _ if registered_user.is_none() => return Ok(()),
+                    Command::Hosts => {
+                        let placeholder = bot.send_message(msg.chat.id, t("bot.command.loading")).await?;
+                        show_hosts_list(&bot, msg.chat.id, placeholder.id, user_sessions.clone(), host_cache.clone(), hosts.clone(), user_id.0 as i64).await?;
+                    }
+                    Command::Status => {
+                        let hosts_data = cached_hosts(&host_cache, &hosts).await;
+                        let forecast = get_availability_forecast(&hosts_data).await;
+                        bot.send_message(msg.chat.id, format_status_summary(&hosts_data, &forecast)).await?;
+                    }
+                    Command::Snapshot(name) => {
+                        if let Some(host) = resolve_host(&bot, msg.chat.id, &host_cache, &hosts, "snapshot", &name).await? {
+                            send_snapshot(&bot, msg.chat.id, &host, &http_client).await?;
+                        }
+                    }
+                    // Same rule as the control buttons
+                    Command::Pause(_) | Command::Stop(_) | Command::Reprint(_) if !registered_user.as_ref().is_some_and(|u| u.role.can_control()) => {
+                        bot.send_message(msg.chat.id, t("bot.no_permission")).await?;
+                    }
+                    Command::Pause(name) => {
+                        if let (Some(user), Some(host)) = (registered_user.as_ref(), resolve_host(&bot, msg.chat.id, &host_cache, &hosts, "pause", &name).await?) {
+                            pause_print(&bot, msg.chat.id, &host, user).await?;
+                        }
+                    }
+                    Command::Stop(name) => {
+                        if let Some(host) = resolve_host(&bot, msg.chat.id, &host_cache, &hosts, "stop", &name).await? {
+                            let placeholder = bot.send_message(msg.chat.id, t("bot.command.loading")).await?;
+                            show_stop_print_confirm(&bot, msg.chat.id, placeholder.id, user_sessions.clone(), host_cache.clone(), &host.ip_address, user_id.0 as i64).await?;
+                        }
+                    }
+                    Command::Reprint(name) => {
+                        if let (Some(user), Some(host)) = (registered_user.as_ref(), resolve_host(&bot, msg.chat.id, &host_cache, &hosts, "reprint", &name).await?) {
+                            let placeholder = bot.send_message(msg.chat.id, t("bot.command.loading")).await?;
+                            execute_reprint(&bot, msg.chat.id, placeholder.id, host_cache.clone(), &host.ip_address, false, user).await?;
+                        }
+                    }
                 }
             } else {
                 if is_registered {
-                    bot.send_message(msg.chat.id, "❓ Неизвестная команда\\. Используйте /start для открытия главного меню\\.")
+                    bot.send_message(msg.chat.id, t("bot.unknown_command"))
                         .parse_mode(ParseMode::MarkdownV2)
                         .await?;
                 } else {
@@ -451,58 +654,21 @@ async fn message_handler(
             if !is_registered {
                 // Check if registration is active and user is trying to register
                 let mut reg_state = _registration_state.lock().await;
-                if reg_state.is_active && !reg_state.is_expired() {
-                    if reg_state.verify_code(text) {
-                        // Registration successful
-                        reg_state.finish_registration();
-                        
-                        // Add user to registered users
-                        let from_user = match msg.from() {
-                            Some(user) => user,
-                            None => return Ok(()), // Ignore messages without sender
-                        };
-                        let user = crate::models::TelegramUser::from_teloxide_user(
-                            user_id,
-                            from_user.username.clone(),
-                            from_user.first_name.clone(),
-                            from_user.last_name.clone(),
-                        );
-                        
-                        // Add user to registered users
-                        let mut users = registered_users.lock().await;
-                        users.push(user.clone());
-                        drop(users); // Release the lock
-                        
-                        // Show main menu after successful registration
-                        let keyboard = InlineKeyboardMarkup::new(vec![
-                            vec![InlineKeyboardButton::callback("📋 Список хостов", "hosts_list")],
-                            vec![InlineKeyboardButton::callback("⚙️ Настройки", "settings")],
-                            vec![InlineKeyboardButton::callback("❓ Помощь", "help")],
-                        ]);
-
-                        let welcome_message = format!("✅ Регистрация успешна! Добро пожаловать, {}! Выберите действие:", user.display_name());
-                        bot.send_message(msg.chat.id, welcome_message)
-                            .reply_markup(keyboard)
-                            .await?;
-                        
-                        // Save users to file
-                        let users_to_save = registered_users.lock().await.clone();
-                        if let Err(e) = save_users_to_file(&users_to_save).await {
-                            println!("Failed to save users to file: {}", e);
-                        }
-                        
-                        // Notify frontend that registration is complete
-                        println!("Registration completed for user: {}", user_id.0);
+                if reg_state.is_pending(user_id.0 as i64) {
+                    bot.send_message(msg.chat.id, t("bot.registration.pending")).await?;
+                } else if reg_state.is_active() {
+                    if let Some(code) = reg_state.verify_code(user_id.0 as i64, text.trim()) {
+                        // Code accepted, the desktop app decides whether the user gets in
+                        request_approval(&bot, &msg, &mut reg_state, code.role, &app_handle).await?;
                     } else {
-                        // Check if max attempts reached
-                        if reg_state.attempts >= reg_state.max_attempts {
-                            reg_state.finish_registration();
-                            bot.send_message(msg.chat.id, "❌ Слишком много неудачных попыток\\. Регистрация отменена\\.")
+                        // Only this user is locked out, the codes stay valid for everyone else
+                        if reg_state.is_locked_out(user_id.0 as i64) {
+                            bot.send_message(msg.chat.id, t("bot.registration.too_many_attempts"))
                                 .parse_mode(ParseMode::MarkdownV2)
                                 .await?;
                         } else {
-                            let remaining = reg_state.max_attempts - reg_state.attempts;
-                            bot.send_message(msg.chat.id, format!("❌ Неверный код\\. Осталось попыток: {}", remaining))
+                            let remaining = reg_state.remaining_attempts(user_id.0 as i64);
+                            bot.send_message(msg.chat.id, tf("bot.registration.wrong_code", &[("remaining", &remaining)]))
                                 .await?;
                         }
                     }
@@ -513,12 +679,12 @@ async fn message_handler(
             } else {
                 // Registered user sent text message, show main menu
                 let keyboard = InlineKeyboardMarkup::new(vec![
-                    vec![InlineKeyboardButton::callback("📋 Список хостов", "hosts_list")],
-                    vec![InlineKeyboardButton::callback("⚙️ Настройки", "settings")],
-                    vec![InlineKeyboardButton::callback("❓ Помощь", "help")],
+                    vec![InlineKeyboardButton::callback(t("bot.button.hosts_list"), "hosts_list")],
+                    vec![InlineKeyboardButton::callback(t("bot.button.settings"), "settings")],
+                    vec![InlineKeyboardButton::callback(t("bot.button.help"), "help")],
                 ]);
 
-                bot.send_message(msg.chat.id, "🤖 *Главное меню*\n\nВыберите действие:")
+                bot.send_message(msg.chat.id, t("bot.main_menu"))
                     .parse_mode(ParseMode::MarkdownV2)
                     .reply_markup(keyboard)
                     .await?;
@@ -528,12 +694,66 @@ async fn message_handler(
     Ok(())
 }
 
+/// Puts the sender of a valid registration code or link on the approval list
+async fn request_approval(
+    bot: &Bot,
+    msg: &Message,
+    reg_state: &mut RegistrationState,
+    role: TelegramRole,
+    app_handle: &Option<AppHandle>,
+) -> ResponseResult<()> {
+    let from_user = match msg.from() {
+        Some(user) => user,
+        None => return Ok(()), // Ignore messages without sender
+    };
+    let pending = PendingRegistration {
+        user_id: from_user.id.0 as i64,
+        username: from_user.username.clone(),
+        first_name: Some(from_user.first_name.clone()),
+        last_name: from_user.last_name.clone(),
+        requested_at: chrono::Utc::now(),
+        role,
+    };
+    reg_state.pending.push(pending.clone());
+
+    bot.send_message(msg.chat.id, t("bot.registration.pending")).await?;
+
+    // Notify frontend that a registration waits for approval
+    if let Some(app_handle) = app_handle {
+        let _ = app_handle.emit("telegram-registration-pending", &pending);
+    }
+    println!("Registration pending approval for user: {}", pending.user_id);
+    Ok(())
+}
+
+/// Addresses the bot may contact besides private and local ones
+struct AddressAllowlist {
+    /// Hosts the user registered by URL
+    remote_hosts: std::collections::HashSet<String>,
+    /// Networks the user allowed for Telegram
+    networks: Vec<String>,
+}
+
+impl AddressAllowlist {
+    /// Reads the allowlist from the saved settings
+    fn load() -> Self {
+        let settings = crate::models::config::AppSettings::load().unwrap_or_default();
+        Self { remote_hosts: settings.remote_hosts, networks: settings.telegram.allowed_networks }
+    }
+}
+
 /// Validates IP address to prevent SSRF attacks
-/// Only allows private network ranges and localhost
-fn is_valid_ip_address(ip: &str) -> bool {
+/// Only allows private network ranges, localhost, the networks the user allowed
+/// and the hosts the user registered by URL
+fn is_valid_ip_address(ip: &str, allowlist: &AddressAllowlist) -> bool {
+    is_local_ip_address(ip) || allowlist.remote_hosts.contains(ip) || is_address_allowed(split_host_port(ip).0, &allowlist.networks)
+}
+
+/// Checks whether an address is private, link-local or localhost
+fn is_local_ip_address(ip: &str) -> bool {
     use std::net::IpAddr;
     
-    let ip_addr = match ip.parse::<IpAddr>() {
+    let ip_addr = match split_host_port(ip).0.parse::<IpAddr>() {
         Ok(addr) => addr,
         Err(_) => return false,
     };
@@ -578,40 +798,24 @@ fn is_valid_ip_address(ip: &str) -> bool {
     }
 }
 
-async fn get_webcam_image(ip_address: &str, client: &reqwest::Client) -> Result<Vec<u8>, String> {
+async fn get_webcam_image(ip_address: &str, client: &reqwest::Client, allowlist: &AddressAllowlist) -> Result<Vec<u8>, String> {
     // Validate IP address to prevent SSRF attacks
-    if !is_valid_ip_address(ip_address) {
+    if !is_valid_ip_address(ip_address, allowlist) {
         return Err("Invalid IP address".to_string());
     }
     
-    let url = format!("http://{}/webcam/?action=snapshot", ip_address);
-    
-    let response = client.get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to request image: {}", e))?;
-    
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
-    }
-    
-    let image_data = response.bytes()
-        .await
-        .map_err(|e| format!("Failed to read image data: {}", e))?
-        .to_vec();
-    
-    Ok(image_data)
+    get_cached_snapshot(ip_address, client).await
 }
 
-async fn send_emergency_stop(ip_address: &str, client: &reqwest::Client) -> Result<(), String> {
+async fn send_emergency_stop(ip_address: &str, client: &reqwest::Client, allowlist: &AddressAllowlist) -> Result<(), String> {
     // Validate IP address to prevent SSRF attacks
-    if !is_valid_ip_address(ip_address) {
+    if !is_valid_ip_address(ip_address, allowlist) {
         return Err("Invalid IP address".to_string());
     }
     
-    let url = format!("http://{}/printer/emergency_stop", ip_address);
+    let url = build_web_url(ip_address, "printer/emergency_stop");
     
-    let response = client.post(&url)
+    let response = with_host_headers(client.post(&url), ip_address)
         .send()
         .await
         .map_err(|e| format!("Failed to send emergency stop request: {}", e))?;
@@ -623,16 +827,16 @@ async fn send_emergency_stop(ip_address: &str, client: &reqwest::Client) -> Resu
     Ok(())
 }
 
-async fn send_stop_print(ip_address: &str, client: &reqwest::Client) -> Result<(), String> {
+async fn send_stop_print(ip_address: &str, client: &reqwest::Client, allowlist: &AddressAllowlist) -> Result<(), String> {
     // Validate IP address to prevent SSRF attacks
-    if !is_valid_ip_address(ip_address) {
+    if !is_valid_ip_address(ip_address, allowlist) {
         return Err("Invalid IP address".to_string());
     }
     
-    let url = format!("http://{}/printer/print/cancel", ip_address);
+    let url = build_web_url(ip_address, "printer/print/cancel");
     
-    let response = client
-        .post(&url)
+    let _permit = acquire_host_permit(ip_address).await;
+    let response = with_host_headers(client.post(&url), ip_address)
         .timeout(Duration::from_secs(10))
         .send()
         .await
@@ -645,16 +849,16 @@ async fn send_stop_print(ip_address: &str, client: &reqwest::Client) -> Result<(
     Ok(())
 }
 
-async fn send_firmware_restart(ip_address: &str, client: &reqwest::Client) -> Result<(), String> {
+async fn send_firmware_restart(ip_address: &str, client: &reqwest::Client, allowlist: &AddressAllowlist) -> Result<(), String> {
     // Validate IP address to prevent SSRF attacks
-    if !is_valid_ip_address(ip_address) {
+    if !is_valid_ip_address(ip_address, allowlist) {
         return Err("Invalid IP address".to_string());
     }
     
-    let url = format!("http://{}/printer/firmware_restart", ip_address);
+    let url = build_web_url(ip_address, "printer/firmware_restart");
     
-    let response = client
-        .post(&url)
+    let _permit = acquire_host_permit(ip_address).await;
+    let response = with_host_headers(client.post(&url), ip_address)
         .timeout(Duration::from_secs(10))
         .send()
         .await
@@ -675,6 +879,8 @@ async fn callback_handler(
     host_cache: Arc<Mutex<HostCache>>,
     hosts: Arc<Mutex<Vec<crate::models::HostInfo>>>,
     http_client: reqwest::Client,
+    send_queue: SendQueue,
+    app_handle: Option<AppHandle>,
 ) -> ResponseResult<()> {
     let user_id = match q.from.id.0 {
         id if id > 0 => id as i64,
@@ -682,20 +888,49 @@ async fn callback_handler(
     };
 
     // Check if user is registered
-    let is_registered = {
+    let user = {
         let users = registered_users.lock().await;
-        users.iter().any(|user| user.user_id == user_id)
+        users.iter().find(|user| user.user_id == user_id).cloned()
     };
 
-    if !is_registered {
+    let Some(user) = user else {
         // Ignore callback queries from unregistered users
         return Ok(());
-    }
+    };
 
     if let Some(data) = q.data {
         bot.answer_callback_query(q.id).await?;
+        // Buttons of hosts with long names carry a reference instead of the host
+        let (data, target) = {
+            let hosts = hosts.lock().await;
+            let data = resolve_host_reference(&data, hosts.iter().map(|h| h.ip_address.as_str()));
+            // Every host button ends with the address of its host
+            let target = hosts.iter().map(|h| h.ip_address.clone()).find(|ip| data.ends_with(&format!("_{}", ip)));
+            (data, target)
+        };
         
         if let Some(msg) = q.message {
+            if target.is_some_and(|ip| !is_valid_ip_address(&ip, &AddressAllowlist::load())) {
+                let keyboard = InlineKeyboardMarkup::new(vec![
+                    vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
+                ]);
+                bot.edit_message_text(msg.chat.id, msg.id, t("bot.host_not_allowed"))
+                    .reply_markup(keyboard)
+                    .await?;
+                return Ok(());
+            }
+
+            if !user.role.can_control() && CONTROL_ACTION_PREFIXES.iter().any(|p| data.starts_with(p)) {
+                let keyboard = InlineKeyboardMarkup::new(vec![
+                    vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
+                ]);
+                bot.edit_message_text(msg.chat.id, msg.id, t("bot.no_permission"))
+                    .reply_markup(keyboard)
+                    .await?;
+                return Ok(());
+            }
+
+            let reporting = ActionReporting { user: &user, registered_users: registered_users.clone(), send_queue: &send_queue, app_handle: &app_handle };
             match data.as_str() {
                 "main_menu" => {
                     show_main_menu(&bot, msg.chat.id, msg.id, user_sessions.clone(), user_id).await?;
@@ -703,6 +938,13 @@ async fn callback_handler(
                 "hosts_list" => {
                     show_hosts_list(&bot, msg.chat.id, msg.id, user_sessions.clone(), host_cache.clone(), hosts.clone(), user_id).await?;
                 }
+                "hosts_force_refresh" => {
+                    bot.edit_message_text(msg.chat.id, msg.id, t("bot.hosts.refreshing"))
+                        .parse_mode(ParseMode::MarkdownV2)
+                        .await?;
+                    refresh_hosts_from_printers(&hosts, &host_cache).await;
+                    show_hosts_list(&bot, msg.chat.id, msg.id, user_sessions.clone(), host_cache.clone(), hosts.clone(), user_id).await?;
+                }
                 "settings" => {
                     show_settings(&bot, msg.chat.id, msg.id, user_sessions.clone(), registered_users.clone(), user_id).await?;
                 }
@@ -713,6 +955,49 @@ async fn callback_handler(
                     let host_id = data.strip_prefix("host_image_").unwrap_or("");
                     get_host_image(&bot, msg.chat.id, msg.id, host_cache.clone(), http_client.clone(), host_id, user_id).await?;
                 }
+                _ if data.starts_with("host_led_") => {
+                    let host_id = data.strip_prefix("host_led_").unwrap_or("");
+                    execute_quick_toggle(&bot, msg.chat.id, msg.id, host_cache.clone(), host_id, QuickToggle::Light, &user).await?;
+                }
+                _ if data.starts_with("host_fan_") => {
+                    let host_id = data.strip_prefix("host_fan_").unwrap_or("");
+                    execute_quick_toggle(&bot, msg.chat.id, msg.id, host_cache.clone(), host_id, QuickToggle::PartFan, &user).await?;
+                }
+                _ if data.starts_with("host_zoffset_") => {
+                    let host_id = data.strip_prefix("host_zoffset_").unwrap_or("");
+                    show_z_offset(&bot, msg.chat.id, msg.id, host_cache.clone(), host_id, None, &user).await?;
+                }
+                _ if data.starts_with("zadj_") => {
+                    // Format: zadj_<delta>_<host>
+                    if let Some((delta, host_id)) = data.strip_prefix("zadj_").and_then(|d| d.split_once('_')) {
+                        let delta = delta.parse::<f64>().ok();
+                        show_z_offset(&bot, msg.chat.id, msg.id, host_cache.clone(), host_id, delta, &user).await?;
+                    }
+                }
+                _ if data.starts_with("qact_") || data.starts_with("qactok_") => {
+                    // Format: qact_<action id>_<host>, qactok_ once confirmed
+                    let confirmed = data.starts_with("qactok_");
+                    let target = data.strip_prefix("qactok_").or_else(|| data.strip_prefix("qact_")).unwrap_or("");
+                    execute_quick_action(&bot, msg.chat.id, msg.id, host_cache.clone(), target, confirmed, &user).await?;
+                }
+                _ if data.starts_with("asnooze_") => {
+                    // Format: asnooze_<minutes>_<alert>_<host>
+                    if let Some((minutes, target)) = data.strip_prefix("asnooze_").and_then(|d| d.split_once('_')) {
+                        if let Ok(minutes) = minutes.parse::<u64>() {
+                            silence_alert_from_button(&bot, msg.chat.id, msg.id, hosts.clone(), target, Some(minutes), &user).await?;
+                        }
+                    }
+                }
+                _ if data.starts_with("aack_") => {
+                    // Format: aack_<alert>_<host>
+                    let target = data.strip_prefix("aack_").unwrap_or("");
+                    silence_alert_from_button(&bot, msg.chat.id, msg.id, hosts.clone(), target, None, &user).await?;
+                }
+                _ if data.starts_with("reprint_") => {
+                    // Sent by the button confirming that the bed is clear
+                    let host_id = data.strip_prefix("reprint_").unwrap_or("");
+                    execute_reprint(&bot, msg.chat.id, msg.id, host_cache.clone(), host_id, true, &user).await?;
+                }
                 _ if data.starts_with("host_emergency_") => {
                     let host_id = data.strip_prefix("host_emergency_").unwrap_or("");
                     show_emergency_confirm(&bot, msg.chat.id, msg.id, user_sessions.clone(), host_cache.clone(), host_id, user_id).await?;
@@ -731,22 +1016,22 @@ async fn callback_handler(
                 }
                 _ if data.starts_with("emergency_confirm_") => {
                     let host_id = data.strip_prefix("emergency_confirm_").unwrap_or("");
-                    execute_emergency_stop(&bot, msg.chat.id, msg.id, host_cache.clone(), http_client.clone(), host_id, user_id).await?;
+                    execute_emergency_stop(&bot, msg.chat.id, msg.id, host_cache.clone(), http_client.clone(), host_id, &reporting).await?;
                 }
                 _ if data.starts_with("stop_print_confirm_") => {
                     let host_id = data.strip_prefix("stop_print_confirm_").unwrap_or("");
-                    execute_stop_print(&bot, msg.chat.id, msg.id, host_cache.clone(), http_client.clone(), host_id, user_id).await?;
+                    execute_stop_print(&bot, msg.chat.id, msg.id, host_cache.clone(), http_client.clone(), host_id, &reporting).await?;
                 }
                 _ if data.starts_with("firmware_restart_confirm_") => {
                     let host_id = data.strip_prefix("firmware_restart_confirm_").unwrap_or("");
-                    execute_firmware_restart(&bot, msg.chat.id, msg.id, host_cache.clone(), http_client.clone(), host_id, user_id).await?;
+                    execute_firmware_restart(&bot, msg.chat.id, msg.id, host_cache.clone(), http_client.clone(), host_id, &reporting).await?;
                 }
                 _ if data.starts_with("toggle_notifications_") => {
                     let action = data.strip_prefix("toggle_notifications_").unwrap_or("");
                     toggle_notifications(&bot, msg.chat.id, msg.id, registered_users.clone(), action, user_id).await?;
                 }
                 _ => {
-                    bot.edit_message_text(msg.chat.id, msg.id, "❌ Unknown action")
+                    bot.edit_message_text(msg.chat.id, msg.id, t("bot.unknown_action"))
                         .await?;
                 }
             }
@@ -769,12 +1054,12 @@ async fn show_main_menu(
     drop(sessions);
 
     let keyboard = InlineKeyboardMarkup::new(vec![
-        vec![InlineKeyboardButton::callback("📋 Список хостов", "hosts_list")],
-        vec![InlineKeyboardButton::callback("⚙️ Настройки", "settings")],
-        vec![InlineKeyboardButton::callback("❓ Помощь", "help")],
+        vec![InlineKeyboardButton::callback(t("bot.button.hosts_list"), "hosts_list")],
+        vec![InlineKeyboardButton::callback(t("bot.button.settings"), "settings")],
+        vec![InlineKeyboardButton::callback(t("bot.button.help"), "help")],
     ]);
 
-    bot.edit_message_text(chat_id, message_id, "🤖 *Главное меню*\n\nВыберите действие:")
+    bot.edit_message_text(chat_id, message_id, t("bot.main_menu"))
         .parse_mode(ParseMode::MarkdownV2)
         .reply_markup(keyboard)
         .await?;
@@ -782,6 +1067,161 @@ async fn show_main_menu(
     Ok(())
 }
 
+/// Checks every host now, bypassing the status cache, and stores the results
+///
+/// Updates both the shared host list and the bot's host cache.
+async fn refresh_hosts_from_printers(
+    hosts: &Arc<Mutex<Vec<crate::models::HostInfo>>>,
+    host_cache: &Arc<Mutex<HostCache>>,
+) {
+    let ips: Vec<String> = hosts.lock().await.iter().map(|h| h.ip_address.clone()).collect();
+    let statuses: Vec<(String, crate::models::host::HostStatusResponse)> = futures::stream::iter(ips)
+        .map(|ip| async move {
+            invalidate_host_status(&ip);
+            let status = get_host_status(&ip).await.status;
+            (ip, status)
+        })
+        .buffer_unordered(STATUS_REFRESH_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut hosts = hosts.lock().await;
+    for (ip, status) in &statuses {
+        if let Some(host) = hosts.iter_mut().find(|h| &h.ip_address == ip) {
+            host.apply_status(status);
+        }
+    }
+    host_cache.lock().await.update_hosts(hosts.clone());
+}
+
+/// Gets the hosts from the bot's cache, refreshing it from the app when stale
+async fn cached_hosts(
+    host_cache: &Arc<Mutex<HostCache>>,
+    hosts: &Arc<Mutex<Vec<crate::models::HostInfo>>>,
+) -> Vec<crate::models::HostInfo> {
+    let mut cache = host_cache.lock().await;
+    if cache.is_stale() || cache.hosts.is_empty() {
+        // Get hosts from the main application
+        let hosts_data = hosts.lock().await.clone();
+        cache.update_hosts(hosts_data.clone());
+        hosts_data
+    } else {
+        cache.hosts.clone()
+    }
+}
+
+/// Emoji shown in front of a host name for its current state
+fn status_emoji(host: &HostInfo) -> &'static str {
+    if host.maintenance {
+        return "🔧";
+    }
+    state_emoji(host.current_state())
+}
+
+/// Emoji of a printer state
+fn state_emoji(state: PrinterState) -> &'static str {
+    match state {
+        PrinterState::Printing => "🟡",
+        PrinterState::Paused => "⏸️",
+        PrinterState::Error => "❌",
+        PrinterState::Cancelling => "⏹️",
+        PrinterState::Standby => "🟢",
+        PrinterState::Offline => "🔴",
+    }
+}
+
+/// Builds the `/status` answer, one line per host, with the time busy
+/// printers are expected to be free
+fn format_status_summary(hosts: &[HostInfo], forecast: &AvailabilityForecast) -> String {
+    if hosts.is_empty() {
+        return t("bot.command.status_empty").to_string();
+    }
+    let lines: Vec<String> = hosts
+        .iter()
+        .map(|host| {
+            let line = format!("{} {}: {}", status_emoji(host), host.hostname, host.current_state().label());
+            let availability = forecast.hosts.iter().find(|a| a.host == host.ip_address);
+            match availability.filter(|a| a.state.has_active_job()) {
+                Some(availability) => {
+                    let free = match availability.remaining_seconds {
+                        Some(remaining) => tf("bot.command.status_free_in", &[("duration", &format_duration_approx(remaining))]),
+                        None => t("bot.command.status_free_unknown").to_string(),
+                    };
+                    format!("{} · {}", line, free)
+                }
+                None => line,
+            }
+        })
+        .collect();
+    format!("{}\n\n{}", t("bot.command.status_title"), lines.join("\n"))
+}
+
+/// Finds the host named in a command, telling the user when there is no single match
+/// or the host is outside the networks the bot may reach
+///
+/// # Arguments
+/// * `command` - Command name, used in the usage hint when no name was given
+/// * `name` - Printer name typed by the user
+async fn resolve_host(
+    bot: &Bot,
+    chat_id: ChatId,
+    host_cache: &Arc<Mutex<HostCache>>,
+    hosts: &Arc<Mutex<Vec<crate::models::HostInfo>>>,
+    command: &str,
+    name: &str,
+) -> ResponseResult<Option<HostInfo>> {
+    if name.trim().is_empty() {
+        bot.send_message(chat_id, tf("bot.command.usage", &[("command", &command)])).await?;
+        return Ok(None);
+    }
+
+    let hosts_data = cached_hosts(host_cache, hosts).await;
+    match match_host(&hosts_data, name) {
+        HostMatch::Found(host) if !is_valid_ip_address(&host.ip_address, &AddressAllowlist::load()) => {
+            bot.send_message(chat_id, t("bot.host_not_allowed")).await?;
+            Ok(None)
+        }
+        HostMatch::Found(host) => Ok(Some(*host)),
+        HostMatch::Ambiguous(candidates) => {
+            let names: Vec<&str> = candidates.iter().map(|h| h.hostname.as_str()).collect();
+            bot.send_message(chat_id, tf("bot.command.host_ambiguous", &[("name", &name.trim()), ("hosts", &names.join(", "))]))
+                .await?;
+            Ok(None)
+        }
+        HostMatch::NotFound => {
+            bot.send_message(chat_id, tf("bot.command.host_not_found", &[("name", &name.trim())])).await?;
+            Ok(None)
+        }
+    }
+}
+
+/// Sends a webcam image of a host as a new message
+async fn send_snapshot(bot: &Bot, chat_id: ChatId, host: &HostInfo, http_client: &reqwest::Client) -> ResponseResult<()> {
+    match get_webcam_image(&host.ip_address, http_client, &AddressAllowlist::load()).await {
+        Ok(image_data) => {
+            bot.send_photo(chat_id, InputFile::memory(image_data))
+                .caption(tf("bot.image.caption", &[("hostname", &host.hostname)]))
+                .await?;
+        }
+        Err(e) => {
+            bot.send_message(chat_id, tf("bot.image.failed", &[("error", &e)])).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Pauses the running print of a host and reports the outcome
+async fn pause_print(bot: &Bot, chat_id: ChatId, host: &HostInfo, user: &TelegramUser) -> ResponseResult<()> {
+    let result = control_printer(&host.ip_address, PrinterAction::Pause).await;
+    audit_action(user, host, "pause", &result);
+    let message = match result {
+        Ok(_) => tf("bot.pause.done", &[("hostname", &host.hostname)]),
+        Err(e) => tf("bot.pause.failed", &[("hostname", &host.hostname), ("error", &e)]),
+    };
+    bot.send_message(chat_id, message).await?;
+    Ok(())
+}
+
 async fn show_hosts_list(
     bot: &Bot,
     chat_id: ChatId,
@@ -797,28 +1237,15 @@ async fn show_hosts_list(
     session.set_message_id(message_id);
     drop(sessions);
 
-    // Get hosts from cache or update if stale
-    let hosts_data = {
-        let mut cache = host_cache.lock().await;
-        if cache.is_stale() || cache.hosts.is_empty() {
-            // Get hosts from the main application
-            let hosts_guard = hosts.lock().await;
-            let hosts_data = hosts_guard.clone();
-            drop(hosts_guard);
-            cache.update_hosts(hosts_data.clone());
-            hosts_data
-        } else {
-            cache.hosts.clone()
-        }
-    };
+    let hosts_data = cached_hosts(&host_cache, &hosts).await;
 
     if hosts_data.is_empty() {
         let keyboard = InlineKeyboardMarkup::new(vec![
-            vec![InlineKeyboardButton::callback("🔄 Обновить", "hosts_list")],
-            vec![InlineKeyboardButton::callback("🏠 Главное меню", "main_menu")],
+            vec![InlineKeyboardButton::callback(t("bot.button.refresh"), "hosts_list")],
+            vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
         ]);
 
-        bot.edit_message_text(chat_id, message_id, "📋 *Список хостов*\n\n❌ Хосты не найдены\\. Убедитесь, что приложение запущено и выполнило сканирование\\.")
+        bot.edit_message_text(chat_id, message_id, t("bot.hosts.empty"))
             .parse_mode(ParseMode::MarkdownV2)
             .reply_markup(keyboard)
             .await?;
@@ -826,27 +1253,19 @@ async fn show_hosts_list(
         let mut keyboard_buttons = Vec::new();
         
         for host in &hosts_data {
-            let printer_status = get_printer_status(host);
-            let status_emoji = match printer_status.as_str() {
-                "printing" => "🟡",
-                "paused" => "⏸️",
-                "error" => "❌",
-                "cancelling" => "⏹️",
-                "standby" => "🟢",
-                "offline" => "🔴",
-                _ => "⚪"
-            };
-            
-            let button_text = format!("{} {} ({})", status_emoji, host.hostname, host.ip_address);
-            keyboard_buttons.push(vec![InlineKeyboardButton::callback(button_text, format!("host_{}", host.ip_address))]);
+            let button_text = format!("{} {} ({})", status_emoji(host), host.hostname, host.ip_address);
+            keyboard_buttons.push(vec![InlineKeyboardButton::callback(button_text, host_callback("host_", &host.ip_address))]);
         }
         
-        keyboard_buttons.push(vec![InlineKeyboardButton::callback("🔄 Обновить", "hosts_list")]);
-        keyboard_buttons.push(vec![InlineKeyboardButton::callback("🏠 Главное меню", "main_menu")]);
+        keyboard_buttons.push(vec![
+            InlineKeyboardButton::callback(t("bot.button.refresh"), "hosts_list"),
+            InlineKeyboardButton::callback(t("bot.button.force_refresh"), "hosts_force_refresh"),
+        ]);
+        keyboard_buttons.push(vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")]);
         
         let keyboard = InlineKeyboardMarkup::new(keyboard_buttons);
 
-        bot.edit_message_text(chat_id, message_id, "📋 *Список хостов*\n\nВыберите хост для управления:")
+        bot.edit_message_text(chat_id, message_id, t("bot.hosts.choose"))
             .parse_mode(ParseMode::MarkdownV2)
             .reply_markup(keyboard)
             .await?;
@@ -877,31 +1296,49 @@ async fn show_host_details(
         cache.hosts.iter().find(|h| h.ip_address == host_id).cloned()
     };
 
-    if let Some(host) = host {
-        let printer_status = get_printer_status(&host);
-        let status_emoji = match printer_status.as_str() {
-            "printing" => "🟡",
-            "paused" => "⏸️",
-            "error" => "❌",
-            "cancelling" => "⏹️",
-            "standby" => "🟢",
-            "offline" => "🔴",
-            _ => "⚪"
-        };
-
-        let keyboard = InlineKeyboardMarkup::new(vec![
-            vec![InlineKeyboardButton::callback("📷 Изображение", format!("host_image_{}", host_id))],
-            vec![InlineKeyboardButton::callback("⏹️ Остановить печать", format!("host_stop_print_{}", host_id))],
-            vec![InlineKeyboardButton::callback("🔄 Firmware Restart", format!("host_firmware_restart_{}", host_id))],
-            vec![InlineKeyboardButton::callback("🛑 Экстренная остановка", format!("host_emergency_{}", host_id))],
-            vec![InlineKeyboardButton::url("🌐 Открыть в браузере", format!("http://{}", host.ip_address).parse().unwrap())],
-            vec![InlineKeyboardButton::callback("🔙 Назад к списку", "hosts_list")],
-            vec![InlineKeyboardButton::callback("🏠 Главное меню", "main_menu")],
+    if let Some(mut host) = host {
+        // Show the current state rather than the one from the last host list refresh
+        host.apply_status(&get_host_status(&host.ip_address).await.status);
+        let printer_status = host.current_state();
+        let status_emoji = state_emoji(printer_status);
+
+        // Only offer the controls the printer has (all of them if its object list is unavailable)
+        let capabilities = get_printer_capabilities(&host.ip_address).await.ok();
+        let mut controls = Vec::new();
+        if capabilities.as_ref().is_none_or(|c| c.led.is_some()) {
+            controls.push(InlineKeyboardButton::callback(t("bot.button.light"), host_callback("host_led_", host_id)));
+        }
+        if capabilities.as_ref().is_none_or(|c| c.part_fan) {
+            controls.push(InlineKeyboardButton::callback(t("bot.button.part_fan"), host_callback("host_fan_", host_id)));
+        }
+        let mut rows = vec![vec![InlineKeyboardButton::callback(t("bot.button.image"), host_callback("host_image_", host_id))]];
+        if !controls.is_empty() {
+            rows.push(controls);
+        }
+        let quick_actions = crate::models::config::AppSettings::load()
+            .ok()
+            .and_then(|mut s| s.quick_actions.remove(&host.ip_address))
+            .unwrap_or_default();
+        let quick_buttons: Vec<InlineKeyboardButton> = quick_actions
+            .iter()
+            .filter(|action| action.telegram)
+            .map(|action| InlineKeyboardButton::callback(format!("⚡ {}", action.label), host_callback(&format!("qact_{}_", action.id), host_id)))
+            .collect();
+        rows.extend(quick_buttons.chunks(2).map(|chunk| chunk.to_vec()));
+        rows.extend([
+            vec![InlineKeyboardButton::callback(t("bot.button.z_offset"), host_callback("host_zoffset_", host_id))],
+            vec![InlineKeyboardButton::callback(t("bot.button.stop_print"), host_callback("host_stop_print_", host_id))],
+            vec![InlineKeyboardButton::callback(t("bot.button.firmware_restart"), host_callback("host_firmware_restart_", host_id))],
+            vec![InlineKeyboardButton::callback(t("bot.button.emergency_stop"), host_callback("host_emergency_", host_id))],
+            vec![InlineKeyboardButton::url(t("bot.button.open_browser"), build_web_ui_url(&host.ip_address, host.web_port).parse().unwrap())],
+            vec![InlineKeyboardButton::callback(t("bot.button.back_to_list"), "hosts_list")],
+            vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
         ]);
+        let keyboard = InlineKeyboardMarkup::new(rows);
 
         // Get print information if printer is printing or paused
         let mut print_info_text = String::new();
-        if printer_status == "printing" || printer_status == "paused" {
+        if printer_status.has_active_job() {
             // Try to get print info with timeout
             let print_info_result = tokio::time::timeout(
                 Duration::from_secs(3),
@@ -912,10 +1349,9 @@ async fn show_host_details(
                 Ok(Ok(Some(print_job))) => {
                     let progress = print_job.progress.progress;
                     let print_duration = format_duration(print_job.progress.print_duration);
-                    let remaining_time = if print_job.progress.total_duration > print_job.progress.print_duration {
-                        format_duration(print_job.progress.total_duration - print_job.progress.print_duration)
-                    } else {
-                        "Неизвестно".to_string()
+                    let remaining_time = match print_job.progress.remaining_seconds() {
+                        Some(remaining) => format!("{} ({})", format_duration(remaining), format_finish_time(remaining)),
+                        None => t("bot.unknown").to_string(),
                     };
                     
                     // Use filename as-is without escaping
@@ -923,27 +1359,44 @@ async fn show_host_details(
                         "\n🖨️ {}\n📈 {:.1}% | ⏱️ {} | ⏳ {}",
                         print_job.filename, progress, print_duration, remaining_time
                     );
+                    if let Some(cost) = &print_job.estimated_cost {
+                        print_info_text.push_str(&format!("\n💰 {}", cost.summary()));
+                    }
+                    if print_job.tools.tools.len() > 1 {
+                        print_info_text.push_str(&format!("\n🌡️ {}", print_job.tools.summary()));
+                    }
                 }
                 _ => {
-                    print_info_text = "\n🖨️ Информация о печати недоступна".to_string();
+                    print_info_text = format!("\n{}", t("bot.print_info_unavailable"));
                 }
             }
         }
 
-        let message = format!(
-            "🖥️ {}\n\n{} IP: {}\n📊 Статус: {}{}\n\nВыберите действие:",
-            host.hostname,
-            status_emoji,
-            host.ip_address,
-            printer_status,
-            print_info_text
-        );
+        let mut extra = String::new();
+        if let Some(model) = host.printer_model.as_ref().and_then(|m| m.display_name()) {
+            extra.push_str(&format!("\n🖨️ {}", model));
+        }
+        if let Some(message) = host.error_message.as_ref().filter(|_| printer_status == PrinterState::Error) {
+            extra.push_str(&format!("\n⚠️ {}", message));
+        }
+        if host.maintenance {
+            extra.push_str(&format!("\n{}", t("bot.host.maintenance")));
+        }
+        extra.push_str(&print_info_text);
+
+        let message = tf("bot.host.details", &[
+            ("hostname", &host.hostname),
+            ("emoji", &status_emoji),
+            ("ip", &host.ip_address),
+            ("status", &printer_status.label()),
+            ("extra", &extra),
+        ]);
 
         bot.edit_message_text(chat_id, message_id, message)
             .reply_markup(keyboard)
             .await?;
     } else {
-        bot.edit_message_text(chat_id, message_id, "❌ Хост не найден")
+        bot.edit_message_text(chat_id, message_id, t("bot.host_not_found"))
             .await?;
     }
     Ok(())
@@ -973,23 +1426,19 @@ async fn show_emergency_confirm(
 
     if let Some(host) = host {
         let keyboard = InlineKeyboardMarkup::new(vec![
-            vec![InlineKeyboardButton::callback("✅ ПОДТВЕРДИТЬ ОСТАНОВКУ", format!("emergency_confirm_{}", host_id))],
-            vec![InlineKeyboardButton::callback("❌ Отмена", format!("host_{}", host_id))],
-            vec![InlineKeyboardButton::callback("🏠 Главное меню", "main_menu")],
+            vec![InlineKeyboardButton::callback(t("bot.button.confirm_emergency"), host_callback("emergency_confirm_", host_id))],
+            vec![InlineKeyboardButton::callback(t("bot.button.cancel"), host_callback("host_", host_id))],
+            vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
         ]);
 
-        let message = format!(
-            "⚠️ *ЭКСТРЕННАЯ ОСТАНОВКА*\n\n🖥️ Хост: {}\n📍 IP: `{}`\n\n🚨 **ВНИМАНИЕ:** Это действие немедленно остановит принтер\\!\n\nВы уверены, что хотите продолжить\\?",
-            host.hostname,
-            host.ip_address
-        );
+        let message = tf("bot.emergency.confirm", &[("hostname", &host.hostname), ("ip", &host.ip_address)]);
 
         bot.edit_message_text(chat_id, message_id, message)
             .parse_mode(ParseMode::MarkdownV2)
             .reply_markup(keyboard)
             .await?;
     } else {
-        bot.edit_message_text(chat_id, message_id, "❌ Хост не найден")
+        bot.edit_message_text(chat_id, message_id, t("bot.host_not_found"))
             .await?;
     }
 
@@ -1019,15 +1468,15 @@ async fn show_stop_print_confirm(
 
     if let Some(host) = host {
         let keyboard = InlineKeyboardMarkup::new(vec![
-            vec![InlineKeyboardButton::callback("✅ Да, остановить печать", format!("stop_print_confirm_{}", host_id))],
-            vec![InlineKeyboardButton::callback("❌ Отмена", format!("host_{}", host_id))],
+            vec![InlineKeyboardButton::callback(t("bot.button.confirm_stop_print"), host_callback("stop_print_confirm_", host_id))],
+            vec![InlineKeyboardButton::callback(t("bot.button.cancel"), host_callback("host_", host_id))],
         ]);
 
-        bot.edit_message_text(chat_id, message_id, format!("⚠️ Вы уверены, что хотите остановить печать на {}?\n\nЭто действие нельзя отменить.", host.hostname))
+        bot.edit_message_text(chat_id, message_id, tf("bot.stop_print.confirm", &[("hostname", &host.hostname)]))
             .reply_markup(keyboard)
             .await?;
     } else {
-        bot.edit_message_text(chat_id, message_id, "❌ Хост не найден")
+        bot.edit_message_text(chat_id, message_id, t("bot.host_not_found"))
             .await?;
     }
 
@@ -1057,21 +1506,32 @@ async fn show_firmware_restart_confirm(
 
     if let Some(host) = host {
         let keyboard = InlineKeyboardMarkup::new(vec![
-            vec![InlineKeyboardButton::callback("✅ Да, перезагрузить firmware", format!("firmware_restart_confirm_{}", host_id))],
-            vec![InlineKeyboardButton::callback("❌ Отмена", format!("host_{}", host_id))],
+            vec![InlineKeyboardButton::callback(t("bot.button.confirm_firmware_restart"), host_callback("firmware_restart_confirm_", host_id))],
+            vec![InlineKeyboardButton::callback(t("bot.button.cancel"), host_callback("host_", host_id))],
         ]);
 
-        bot.edit_message_text(chat_id, message_id, format!("⚠️ Вы уверены, что хотите перезагрузить firmware на {}?\n\nПринтер будет перезагружен и может быть недоступен несколько секунд.", host.hostname))
+        bot.edit_message_text(chat_id, message_id, tf("bot.firmware_restart.confirm", &[("hostname", &host.hostname)]))
             .reply_markup(keyboard)
             .await?;
     } else {
-        bot.edit_message_text(chat_id, message_id, "❌ Хост не найден")
+        bot.edit_message_text(chat_id, message_id, t("bot.host_not_found"))
             .await?;
     }
 
     Ok(())
 }
 
+/// Who sent a control action and where it is reported besides the chat
+struct ActionReporting<'a> {
+    /// User who sent the action
+    user: &'a TelegramUser,
+    /// Users told about emergency stops
+    registered_users: Arc<Mutex<Vec<TelegramUser>>>,
+    send_queue: &'a SendQueue,
+    /// App the action is mirrored to
+    app_handle: &'a Option<AppHandle>,
+}
+
 async fn execute_emergency_stop(
     bot: &Bot,
     chat_id: ChatId,
@@ -1079,7 +1539,7 @@ async fn execute_emergency_stop(
     host_cache: Arc<Mutex<HostCache>>,
     http_client: reqwest::Client,
     host_id: &str,
-    _user_id: i64,
+    reporting: &ActionReporting<'_>,
 ) -> ResponseResult<()> {
     // Find host in cache
     let host = {
@@ -1088,34 +1548,40 @@ async fn execute_emergency_stop(
     };
 
     if let Some(host) = host {
-        bot.edit_message_text(chat_id, message_id, format!("🛑 Отправка экстренной остановки на {}...", host.hostname))
+        bot.edit_message_text(chat_id, message_id, tf("bot.emergency.sending", &[("hostname", &host.hostname)]))
             .await?;
 
         // Send emergency stop command
-        match send_emergency_stop(&host.ip_address, &http_client).await {
+        let result = send_emergency_stop(&host.ip_address, &http_client, &AddressAllowlist::load()).await;
+        let entry = audit_action(reporting.user, &host, "emergency_stop", &result);
+        mirror_action(reporting.app_handle, &entry, None);
+        if result.is_ok() {
+            notify_emergency_stop(reporting.user, &host, reporting.registered_users.clone(), reporting.send_queue).await;
+        }
+        match result {
             Ok(_) => {
                 let keyboard = InlineKeyboardMarkup::new(vec![
-                    vec![InlineKeyboardButton::callback("🔙 Назад к хосту", format!("host_{}", host_id))],
-                    vec![InlineKeyboardButton::callback("🏠 Главное меню", "main_menu")],
+                    vec![InlineKeyboardButton::callback(t("bot.button.back_to_host"), host_callback("host_", host_id))],
+                    vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
                 ]);
 
-                bot.edit_message_text(chat_id, message_id, format!("✅ Экстренная остановка успешно отправлена на {}!", host.hostname))
+                bot.edit_message_text(chat_id, message_id, tf("bot.emergency.sent", &[("hostname", &host.hostname)]))
                     .reply_markup(keyboard)
                     .await?;
             }
             Err(e) => {
                 let keyboard = InlineKeyboardMarkup::new(vec![
-                    vec![InlineKeyboardButton::callback("🔙 Назад к хосту", format!("host_{}", host_id))],
-                    vec![InlineKeyboardButton::callback("🏠 Главное меню", "main_menu")],
+                    vec![InlineKeyboardButton::callback(t("bot.button.back_to_host"), host_callback("host_", host_id))],
+                    vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
                 ]);
 
-                bot.edit_message_text(chat_id, message_id, format!("❌ Ошибка отправки экстренной остановки: {}", e))
+                bot.edit_message_text(chat_id, message_id, tf("bot.emergency.failed", &[("error", &e)]))
                     .reply_markup(keyboard)
                     .await?;
             }
         }
     } else {
-        bot.edit_message_text(chat_id, message_id, "❌ Хост не найден")
+        bot.edit_message_text(chat_id, message_id, t("bot.host_not_found"))
             .await?;
     }
 
@@ -1129,7 +1595,7 @@ async fn execute_stop_print(
     host_cache: Arc<Mutex<HostCache>>,
     http_client: reqwest::Client,
     host_id: &str,
-    _user_id: i64,
+    reporting: &ActionReporting<'_>,
 ) -> ResponseResult<()> {
     // Find host in cache
     let host = {
@@ -1138,22 +1604,25 @@ async fn execute_stop_print(
     };
 
     if let Some(host) = host {
-        bot.edit_message_text(chat_id, message_id, format!("⏹️ Остановка печати на {}...", host.hostname))
+        bot.edit_message_text(chat_id, message_id, tf("bot.stop_print.sending", &[("hostname", &host.hostname)]))
             .await?;
 
         // Send stop print request
-        match send_stop_print(&host.ip_address, &http_client).await {
+        let result = send_stop_print(&host.ip_address, &http_client, &AddressAllowlist::load()).await;
+        let entry = audit_action(reporting.user, &host, "cancel_print", &result);
+        mirror_action(reporting.app_handle, &entry, None);
+        match result {
             Ok(_) => {
-                bot.edit_message_text(chat_id, message_id, format!("✅ Печать остановлена на {}", host.hostname))
+                bot.edit_message_text(chat_id, message_id, tf("bot.stop_print.done", &[("hostname", &host.hostname)]))
                     .await?;
             }
             Err(e) => {
-                bot.edit_message_text(chat_id, message_id, format!("❌ Ошибка остановки печати на {}: {}", host.hostname, e))
+                bot.edit_message_text(chat_id, message_id, tf("bot.stop_print.failed", &[("hostname", &host.hostname), ("error", &e)]))
                     .await?;
             }
         }
     } else {
-        bot.edit_message_text(chat_id, message_id, "❌ Хост не найден")
+        bot.edit_message_text(chat_id, message_id, t("bot.host_not_found"))
             .await?;
     }
 
@@ -1167,7 +1636,7 @@ async fn execute_firmware_restart(
     host_cache: Arc<Mutex<HostCache>>,
     http_client: reqwest::Client,
     host_id: &str,
-    _user_id: i64,
+    reporting: &ActionReporting<'_>,
 ) -> ResponseResult<()> {
     // Find host in cache
     let host = {
@@ -1176,28 +1645,451 @@ async fn execute_firmware_restart(
     };
 
     if let Some(host) = host {
-        bot.edit_message_text(chat_id, message_id, format!("🔄 Перезагрузка firmware на {}...", host.hostname))
+        bot.edit_message_text(chat_id, message_id, tf("bot.firmware_restart.sending", &[("hostname", &host.hostname)]))
             .await?;
 
         // Send firmware restart request
-        match send_firmware_restart(&host.ip_address, &http_client).await {
+        let result = send_firmware_restart(&host.ip_address, &http_client, &AddressAllowlist::load()).await;
+        let entry = audit_action(reporting.user, &host, "firmware_restart", &result);
+        match result {
             Ok(_) => {
-                bot.edit_message_text(chat_id, message_id, format!("✅ Firmware перезагружен на {}", host.hostname))
+                bot.edit_message_text(chat_id, message_id, tf("bot.firmware_restart.waiting", &[("hostname", &host.hostname)]))
+                    .await?;
+
+                // Report the state Klipper ends up in, so nobody sends commands too early
+                let timeout = Duration::from_secs(FIRMWARE_RESTART_TIMEOUT_SECONDS);
+                let message = match wait_for_klippy_ready(&host.ip_address, timeout).await {
+                    Ok(KlippyState::Ready) => tf("bot.firmware_restart.ready", &[("hostname", &host.hostname)]),
+                    Ok(state) => tf("bot.firmware_restart.state", &[("hostname", &host.hostname), ("state", &state)]),
+                    Err(_) => tf("bot.firmware_restart.timeout", &[("hostname", &host.hostname), ("seconds", &FIRMWARE_RESTART_TIMEOUT_SECONDS)]),
+                };
+                mirror_action(reporting.app_handle, &entry, Some(&message));
+                let keyboard = InlineKeyboardMarkup::new(vec![
+                    vec![InlineKeyboardButton::callback(t("bot.button.back_to_host"), host_callback("host_", host_id))],
+                    vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
+                ]);
+                bot.edit_message_text(chat_id, message_id, message)
+                    .reply_markup(keyboard)
                     .await?;
             }
             Err(e) => {
-                bot.edit_message_text(chat_id, message_id, format!("❌ Ошибка перезагрузки firmware на {}: {}", host.hostname, e))
+                mirror_action(reporting.app_handle, &entry, None);
+                bot.edit_message_text(chat_id, message_id, tf("bot.firmware_restart.failed", &[("hostname", &host.hostname), ("error", &e)]))
                     .await?;
             }
         }
     } else {
-        bot.edit_message_text(chat_id, message_id, "❌ Хост не найден")
+        bot.edit_message_text(chat_id, message_id, t("bot.host_not_found"))
             .await?;
     }
 
     Ok(())
 }
 
+/// Records a control action in the audit log
+fn audit_action<T, E: std::fmt::Display>(user: &TelegramUser, host: &HostInfo, action: &str, result: &Result<T, E>) -> TelegramAuditEntry {
+    let entry = TelegramAuditEntry::new(user, host, action, result);
+    if let Err(e) = TelegramAuditLog::record(entry.clone()) {
+        eprintln!("Failed to write Telegram audit log: {}", e);
+    }
+    entry
+}
+
+/// Shows a printer stop or restart done through the bot on the desktop
+/// 
+/// The audit entry is emitted as `telegram-action` event and, unless
+/// disabled in the settings, announced with a system notification.
+/// Nothing is shown in headless mode.
+/// 
+/// # Arguments
+/// * `entry` - Audit entry of the action
+/// * `outcome` - What happened afterwards, e.g. the Klipper state after a restart
+fn mirror_action(app_handle: &Option<AppHandle>, entry: &TelegramAuditEntry, outcome: Option<&str>) {
+    let Some(app_handle) = app_handle else {
+        return;
+    };
+    let _ = app_handle.emit("telegram-action", entry);
+
+    let enabled = crate::models::config::AppSettings::load()
+        .map(|s| s.telegram.mirror_actions)
+        .unwrap_or(true);
+    if !enabled {
+        return;
+    }
+
+    let mut body = tf(&format!("notify.telegram_action.{}", entry.action), &[
+        ("user", &entry.user_name),
+        ("hostname", &entry.hostname),
+    ]);
+    if let Some(error) = &entry.error {
+        body.push_str(&format!("\n{}", tf("notify.telegram_action.failed", &[("error", error)])));
+    }
+    if let Some(outcome) = outcome {
+        body.push_str(&format!("\n{}", outcome));
+    }
+    send_notification(t("notify.telegram_action.title"), &body);
+}
+
+/// Tells the admins that someone stopped a printer, unless disabled in the settings
+/// 
+/// While nobody has the admin role, all other users are told.
+async fn notify_emergency_stop(
+    user: &TelegramUser,
+    host: &HostInfo,
+    registered_users: Arc<Mutex<Vec<TelegramUser>>>,
+    send_queue: &SendQueue,
+) {
+    let enabled = crate::models::config::AppSettings::load()
+        .map(|s| s.telegram.notify_emergency_stop)
+        .unwrap_or(true);
+    if !enabled {
+        return;
+    }
+
+    let text = tf("bot.emergency.notice", &[
+        ("user", &user.display_name()),
+        ("hostname", &host.hostname),
+        ("ip", &host.ip_address),
+    ]);
+    let users = registered_users.lock().await;
+    let has_admins = users.iter().any(|u| u.role == TelegramRole::Admin);
+    let recipients = users.iter().filter(|u| {
+        u.user_id != user.user_id
+            && u.notifications_enabled
+            && !u.is_blocked()
+            && (!has_admins || u.role == TelegramRole::Admin)
+    });
+    for other in recipients {
+        let message = OutgoingMessage {
+            chat_id: ChatId(other.user_id),
+            text: text.clone(),
+            parse_mode: None,
+            photo: None,
+            animation: None,
+            reply_markup: None,
+        };
+        if let Err(e) = send_queue.enqueue(message) {
+            eprintln!("Failed to queue emergency stop notice: {}", e);
+        }
+    }
+}
+
+/// Quick toggles on the host details screen
+#[derive(Debug, Clone, Copy)]
+enum QuickToggle {
+    /// First configured LED, e.g. to light a dark enclosure before a snapshot
+    Light,
+    /// Part cooling fan
+    PartFan,
+}
+
+async fn execute_quick_toggle(
+    bot: &Bot,
+    chat_id: ChatId,
+    message_id: MessageId,
+    host_cache: Arc<Mutex<HostCache>>,
+    host_id: &str,
+    toggle: QuickToggle,
+    user: &TelegramUser,
+) -> ResponseResult<()> {
+    // Find host in cache
+    let host = {
+        let cache = host_cache.lock().await;
+        cache.hosts.iter().find(|h| h.ip_address == host_id).cloned()
+    };
+
+    let host = match host {
+        Some(host) => host,
+        None => {
+            bot.edit_message_text(chat_id, message_id, t("bot.host_not_found"))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let (result, on_text, off_text) = match toggle {
+        QuickToggle::Light => (toggle_led(&host.ip_address).await, t("bot.toggle.light_on"), t("bot.toggle.light_off")),
+        QuickToggle::PartFan => (toggle_part_fan(&host.ip_address).await, t("bot.toggle.part_fan_on"), t("bot.toggle.part_fan_off")),
+    };
+    let action = match (toggle, &result) {
+        (QuickToggle::Light, Ok(true)) => "light_on",
+        (QuickToggle::Light, _) => "light_off",
+        (QuickToggle::PartFan, Ok(true)) => "part_fan_on",
+        (QuickToggle::PartFan, _) => "part_fan_off",
+    };
+    audit_action(user, &host, action, &result);
+    let message = match result {
+        Ok(true) => tf("bot.toggle.result", &[("state", &on_text), ("hostname", &host.hostname)]),
+        Ok(false) => tf("bot.toggle.result", &[("state", &off_text), ("hostname", &host.hostname)]),
+        Err(e) => tf("bot.toggle.failed", &[("hostname", &host.hostname), ("error", &e)]),
+    };
+
+    let keyboard = InlineKeyboardMarkup::new(vec![
+        vec![InlineKeyboardButton::callback(t("bot.button.image"), host_callback("host_image_", host_id))],
+        vec![InlineKeyboardButton::callback(t("bot.button.back_to_host"), host_callback("host_", host_id))],
+        vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
+    ]);
+
+    bot.edit_message_text(chat_id, message_id, message)
+        .reply_markup(keyboard)
+        .await?;
+
+    Ok(())
+}
+
+/// Runs a custom quick action, asking first if the action wants confirmation
+/// 
+/// # Arguments
+/// * `target` - "<action id>_<host>" from the callback data
+async fn execute_quick_action(
+    bot: &Bot,
+    chat_id: ChatId,
+    message_id: MessageId,
+    host_cache: Arc<Mutex<HostCache>>,
+    target: &str,
+    confirmed: bool,
+    user: &TelegramUser,
+) -> ResponseResult<()> {
+    let Some((action_id, host_id)) = target.split_once('_') else {
+        return Ok(());
+    };
+    // Find host in cache
+    let host = {
+        let cache = host_cache.lock().await;
+        cache.hosts.iter().find(|h| h.ip_address == host_id).cloned()
+    };
+    let (host, action) = match host.map(|host| (find_quick_action(&host.ip_address, action_id), host)) {
+        Some((Ok(action), host)) => (host, action),
+        Some((Err(_), _)) => {
+            bot.edit_message_text(chat_id, message_id, t("bot.quick_action.not_found"))
+                .await?;
+            return Ok(());
+        }
+        None => {
+            bot.edit_message_text(chat_id, message_id, t("bot.host_not_found"))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    if action.confirm && !confirmed {
+        let keyboard = InlineKeyboardMarkup::new(vec![
+            vec![InlineKeyboardButton::callback(t("bot.button.confirm_quick_action"), host_callback(&format!("qactok_{}_", action.id), host_id))],
+            vec![InlineKeyboardButton::callback(t("bot.button.cancel"), host_callback("host_", host_id))],
+        ]);
+        bot.edit_message_text(chat_id, message_id, tf("bot.quick_action.confirm", &[("action", &action.label), ("hostname", &host.hostname)]))
+            .reply_markup(keyboard)
+            .await?;
+        return Ok(());
+    }
+
+    bot.edit_message_text(chat_id, message_id, tf("bot.quick_action.running", &[("action", &action.label), ("hostname", &host.hostname)]))
+        .await?;
+    let result = run_quick_action(&host.ip_address, &action).await;
+    audit_action(user, &host, "quick_action", &result);
+    let message = match result {
+        Ok(output) => tf("bot.quick_action.done", &[("action", &action.label), ("hostname", &host.hostname), ("output", &output)]),
+        Err(e) => tf("bot.quick_action.failed", &[("action", &action.label), ("hostname", &host.hostname), ("error", &e)]),
+    };
+
+    let keyboard = InlineKeyboardMarkup::new(vec![
+        vec![InlineKeyboardButton::callback(t("bot.button.back_to_host"), host_callback("host_", host_id))],
+        vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
+    ]);
+    bot.edit_message_text(chat_id, message_id, message)
+        .reply_markup(keyboard)
+        .await?;
+
+    Ok(())
+}
+
+/// Starts the last print of a host again once the pre-flight checks pass
+///
+/// When only the confirmation that the bed is clear is missing, a button
+/// to give it is offered.
+async fn execute_reprint(
+    bot: &Bot,
+    chat_id: ChatId,
+    message_id: MessageId,
+    host_cache: Arc<Mutex<HostCache>>,
+    host_id: &str,
+    bed_clear_confirmed: bool,
+    user: &TelegramUser,
+) -> ResponseResult<()> {
+    // Find host in cache
+    let host = {
+        let cache = host_cache.lock().await;
+        cache.hosts.iter().find(|h| h.ip_address == host_id).cloned()
+    };
+    let Some(host) = host else {
+        bot.edit_message_text(chat_id, message_id, t("bot.host_not_found"))
+            .await?;
+        return Ok(());
+    };
+
+    let mut keyboard_buttons = Vec::new();
+    // Only the file is needed, not the job details get_print_info collects
+    let message = match get_last_print_filename(&host.ip_address).await {
+        Ok(Some(filename)) => {
+            let result = start_print_checked(&host.ip_address, &filename, bed_clear_confirmed).await;
+            // Only attempted starts are audited, not failed checks
+            if !matches!(&result, Ok(report) if !report.started) {
+                audit_action(user, &host, &format!("reprint {}", filename), &result);
+            }
+            match result {
+                Ok(report) if report.started => {
+                    tf("bot.reprint.started", &[("file", &filename), ("hostname", &host.hostname), ("checks", &report.summary())])
+                }
+                Ok(report) => {
+                    if report.needs_only_bed_clear() {
+                        keyboard_buttons.push(vec![InlineKeyboardButton::callback(t("bot.button.confirm_bed_clear"), host_callback("reprint_", host_id))]);
+                    }
+                    tf("bot.reprint.checks_failed", &[("file", &filename), ("hostname", &host.hostname), ("checks", &report.summary())])
+                }
+                Err(e) => tf("bot.reprint.failed", &[("hostname", &host.hostname), ("error", &e)]),
+            }
+        }
+        _ => tf("bot.reprint.no_file", &[("hostname", &host.hostname)]),
+    };
+
+    keyboard_buttons.push(vec![InlineKeyboardButton::callback(t("bot.button.back_to_host"), host_callback("host_", host_id))]);
+    keyboard_buttons.push(vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")]);
+    bot.edit_message_text(chat_id, message_id, message)
+        .reply_markup(InlineKeyboardMarkup::new(keyboard_buttons))
+        .await?;
+
+    Ok(())
+}
+
+/// Buttons below an alert to snooze or acknowledge it
+fn alert_keyboard(host_ip: &str, kind: AlertKind) -> InlineKeyboardMarkup {
+    let mut row: Vec<InlineKeyboardButton> = ALERT_SNOOZE_HOURS
+        .iter()
+        .map(|hours| {
+            InlineKeyboardButton::callback(
+                tf("bot.button.snooze_alert", &[("hours", hours)]),
+                host_callback(&format!("asnooze_{}_{}_", hours * 60, kind.as_str()), host_ip),
+            )
+        })
+        .collect();
+    row.push(InlineKeyboardButton::callback(t("bot.button.acknowledge_alert"), host_callback(&format!("aack_{}_", kind.as_str()), host_ip)));
+    InlineKeyboardMarkup::new(vec![row])
+}
+
+/// Snoozes or acknowledges an alert from the buttons below it
+///
+/// Alerts often carry a webcam photo, whose caption cannot be replaced by
+/// text, so the result is sent as a new message and the buttons are removed
+/// from the alert.
+///
+/// # Arguments
+/// * `target` - `<alert>_<host>` from the callback data
+/// * `snooze_minutes` - Length of the snooze, `None` to acknowledge
+async fn silence_alert_from_button(
+    bot: &Bot,
+    chat_id: ChatId,
+    message_id: MessageId,
+    hosts: Arc<Mutex<Vec<crate::models::HostInfo>>>,
+    target: &str,
+    snooze_minutes: Option<u64>,
+    user: &TelegramUser,
+) -> ResponseResult<()> {
+    if !user.role.can_control() {
+        bot.send_message(chat_id, t("bot.no_permission")).await?;
+        return Ok(());
+    }
+    let Some((kind, host_ip)) = AlertKind::ALL
+        .into_iter()
+        .find_map(|kind| target.strip_prefix(kind.as_str()).and_then(|rest| rest.strip_prefix('_')).map(|host| (kind, host)))
+    else {
+        return Ok(());
+    };
+    let host = hosts.lock().await.iter().find(|h| h.ip_address == host_ip).cloned();
+    let Some(host) = host else {
+        bot.send_message(chat_id, t("bot.host_not_found")).await?;
+        return Ok(());
+    };
+
+    let alert = t(&format!("alert.kind.{}", kind.as_str())).to_string();
+    let message = match snooze_minutes {
+        Some(minutes) => {
+            let result = snooze_alert(&host.ip_address, kind, minutes);
+            audit_action(user, &host, &format!("snooze {} alerts for {} min", kind.as_str(), minutes), &result);
+            result.map(|until| {
+                let until = format_date_time(&until.with_timezone(&chrono::Local));
+                tf("bot.alert.snoozed", &[("alert", &alert), ("hostname", &host.hostname), ("until", &until)])
+            })
+        }
+        None => {
+            let result = acknowledge_alert(&host.ip_address, kind);
+            audit_action(user, &host, &format!("acknowledge {} alert", kind.as_str()), &result);
+            result.map(|_| tf("bot.alert.acknowledged", &[("alert", &alert), ("hostname", &host.hostname)]))
+        }
+    };
+    let message = message.unwrap_or_else(|e| tf("bot.alert.failed", &[("hostname", &host.hostname), ("error", &e)]));
+
+    // Fails when the buttons were already removed, which is fine
+    let _ = bot.edit_message_reply_markup(chat_id, message_id).await;
+    bot.send_message(chat_id, message).await?;
+    Ok(())
+}
+
+/// Shows the Z offset screen, applying an adjustment first if given
+async fn show_z_offset(
+    bot: &Bot,
+    chat_id: ChatId,
+    message_id: MessageId,
+    host_cache: Arc<Mutex<HostCache>>,
+    host_id: &str,
+    delta: Option<f64>,
+    user: &TelegramUser,
+) -> ResponseResult<()> {
+    // Find host in cache
+    let host = {
+        let cache = host_cache.lock().await;
+        cache.hosts.iter().find(|h| h.ip_address == host_id).cloned()
+    };
+
+    let host = match host {
+        Some(host) => host,
+        None => {
+            bot.edit_message_text(chat_id, message_id, t("bot.host_not_found"))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let result = match delta {
+        Some(delta) => {
+            let result = adjust_z_offset(&host.ip_address, delta).await;
+            audit_action(user, &host, &format!("z_offset {:+.3}", delta), &result);
+            result
+        }
+        None => get_z_offset(&host.ip_address).await,
+    };
+    let message = match result {
+        Ok(offset) => tf("bot.z_offset.value", &[("hostname", &host.hostname), ("offset", &format!("{:+.3}", offset))]),
+        Err(e) => tf("bot.z_offset.failed", &[("hostname", &host.hostname), ("error", &e)]),
+    };
+
+    let steps = Z_OFFSET_STEPS
+        .iter()
+        .map(|step| InlineKeyboardButton::callback(format!("{:+.2}", step), host_callback(&format!("zadj_{}_", step), host_id)))
+        .collect();
+    let keyboard = InlineKeyboardMarkup::new(vec![
+        steps,
+        vec![InlineKeyboardButton::callback(t("bot.button.image"), host_callback("host_image_", host_id))],
+        vec![InlineKeyboardButton::callback(t("bot.button.back_to_host"), host_callback("host_", host_id))],
+        vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
+    ]);
+
+    bot.edit_message_text(chat_id, message_id, message)
+        .reply_markup(keyboard)
+        .await?;
+
+    Ok(())
+}
+
 async fn get_host_image(
     bot: &Bot,
     chat_id: ChatId,
@@ -1214,40 +2106,40 @@ async fn get_host_image(
     };
 
     if let Some(host) = host {
-        bot.edit_message_text(chat_id, message_id, format!("📷 Получение изображения с {}...", host.hostname))
+        bot.edit_message_text(chat_id, message_id, tf("bot.image.fetching", &[("hostname", &host.hostname)]))
             .await?;
 
         // Get image from webcam
-        match get_webcam_image(&host.ip_address, &http_client).await {
+        match get_webcam_image(&host.ip_address, &http_client, &AddressAllowlist::load()).await {
             Ok(image_data) => {
                 // Send image to user
                 bot.send_photo(chat_id, InputFile::memory(image_data))
-                    .caption(format!("📷 Изображение с {}", host.hostname))
+                    .caption(tf("bot.image.caption", &[("hostname", &host.hostname)]))
                     .await?;
 
                 // Update the message with navigation buttons
                 let keyboard = InlineKeyboardMarkup::new(vec![
-                    vec![InlineKeyboardButton::callback("🔙 Назад к хосту", format!("host_{}", host_id))],
-                    vec![InlineKeyboardButton::callback("🏠 Главное меню", "main_menu")],
+                    vec![InlineKeyboardButton::callback(t("bot.button.back_to_host"), host_callback("host_", host_id))],
+                    vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
                 ]);
 
-                bot.edit_message_text(chat_id, message_id, "✅ Изображение получено!")
+                bot.edit_message_text(chat_id, message_id, t("bot.image.received"))
                     .reply_markup(keyboard)
                     .await?;
             }
             Err(e) => {
                 let keyboard = InlineKeyboardMarkup::new(vec![
-                    vec![InlineKeyboardButton::callback("🔙 Назад к хосту", format!("host_{}", host_id))],
-                    vec![InlineKeyboardButton::callback("🏠 Главное меню", "main_menu")],
+                    vec![InlineKeyboardButton::callback(t("bot.button.back_to_host"), host_callback("host_", host_id))],
+                    vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
                 ]);
 
-                bot.edit_message_text(chat_id, message_id, format!("❌ Ошибка получения изображения: {}", e))
+                bot.edit_message_text(chat_id, message_id, tf("bot.image.failed", &[("error", &e)]))
                     .reply_markup(keyboard)
                     .await?;
             }
         }
     } else {
-        bot.edit_message_text(chat_id, message_id, "❌ Хост не найден")
+        bot.edit_message_text(chat_id, message_id, t("bot.host_not_found"))
             .await?;
     }
 
@@ -1277,20 +2169,20 @@ async fn show_settings(
     };
 
     let notification_text = if notifications_enabled {
-        "🔔 Включены"
+        t("bot.notifications.on")
     } else {
-        "🔕 Выключены"
+        t("bot.notifications.off")
     };
 
     let keyboard = InlineKeyboardMarkup::new(vec![
         vec![InlineKeyboardButton::callback(
-            format!("{} Уведомления", notification_text),
+            tf("bot.button.notifications", &[("state", &notification_text)]),
             if notifications_enabled { "toggle_notifications_off" } else { "toggle_notifications_on" }
         )],
-        vec![InlineKeyboardButton::callback("🏠 Главное меню", "main_menu")],
+        vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
     ]);
 
-    bot.edit_message_text(chat_id, message_id, format!("⚙️ *Настройки*\n\n🔔 Уведомления: {}", notification_text))
+    bot.edit_message_text(chat_id, message_id, tf("bot.settings", &[("state", &notification_text)]))
         .parse_mode(ParseMode::MarkdownV2)
         .reply_markup(keyboard)
         .await?;
@@ -1311,21 +2203,10 @@ async fn show_help(
     session.set_message_id(message_id);
     drop(sessions);
 
-    let help_text = "❓ Помощь\n\n\
-🤖 MHS Bot - бот для мониторинга 3D принтеров\n\n\
-📋 Основные функции:\n\
-• Просмотр списка хостов\n\
-• Мониторинг статуса принтеров\n\
-• Получение изображений с камер\n\
-• Экстренная остановка печати\n\
-• Открытие веб-интерфейса\n\n\
-⚙️ Настройки:\n\
-• Управление уведомлениями\n\n\
-🔧 Поддержка:\n\
-Обратитесь к администратору";
+    let help_text = t("bot.help");
 
     let keyboard = InlineKeyboardMarkup::new(vec![
-        vec![InlineKeyboardButton::callback("🏠 Главное меню", "main_menu")],
+        vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
     ]);
 
     bot.edit_message_text(chat_id, message_id, help_text)
@@ -1356,20 +2237,19 @@ async fn toggle_notifications(
             println!("Failed to save users to file: {}", e);
         }
         
-        let status_text = if enable { "включены" } else { "выключены" };
         let keyboard = InlineKeyboardMarkup::new(vec![
             vec![InlineKeyboardButton::callback(
-                format!("{} Уведомления", if enable { "🔔 Включены" } else { "🔕 Выключены" }),
+                tf("bot.button.notifications", &[("state", &t(if enable { "bot.notifications.on" } else { "bot.notifications.off" }))]),
                 if enable { "toggle_notifications_off" } else { "toggle_notifications_on" }
             )],
-            vec![InlineKeyboardButton::callback("🏠 Главное меню", "main_menu")],
+            vec![InlineKeyboardButton::callback(t("bot.button.main_menu"), "main_menu")],
         ]);
 
-        bot.edit_message_text(chat_id, message_id, format!("✅ Уведомления {}!", status_text))
+        bot.edit_message_text(chat_id, message_id, t(if enable { "bot.notifications.enabled" } else { "bot.notifications.disabled" }))
             .reply_markup(keyboard)
             .await?;
     } else {
-        bot.edit_message_text(chat_id, message_id, "❌ Пользователь не найден")
+        bot.edit_message_text(chat_id, message_id, t("bot.user_not_found"))
             .await?;
     }
 
@@ -0,0 +1,74 @@
+//! Callback data of inline buttons
+//!
+//! Telegram accepts at most 64 bytes of callback data per button. Buttons
+//! name their host at the end of the data, which does not fit for long host
+//! names of printers registered by URL, e.g.
+//! `firmware_restart_confirm_printer.workshop.example.com`. Such hosts are
+//! referred to by a short hash of their address instead, which is resolved
+//! against the known hosts when the button is pressed.
+
+/// Maximum length of the callback data of a button, in bytes
+pub const MAX_CALLBACK_DATA_BYTES: usize = 64;
+
+/// Marks a host reference; host addresses never contain it
+const HOST_REFERENCE_MARKER: char = '#';
+
+/// Builds the callback data of a button acting on a host
+///
+/// # Arguments
+/// * `prefix` - Action and arguments, e.g. "qact_<action id>_"
+/// * `host` - Host IP address
+///
+/// # Returns
+/// * `<prefix><host>`, or `<prefix>#<hash>` when that would be too long
+pub fn host_callback(prefix: &str, host: &str) -> String {
+    let data = format!("{}{}", prefix, host);
+    if data.len() <= MAX_CALLBACK_DATA_BYTES {
+        return data;
+    }
+    format!("{}{}{}", prefix, HOST_REFERENCE_MARKER, host_reference(host))
+}
+
+/// Replaces a host reference at the end of callback data by the host
+///
+/// # Arguments
+/// * `data` - Callback data of a pressed button
+/// * `hosts` - Addresses of the known hosts
+///
+/// # Returns
+/// * The data as built before shortening; unchanged when there is no
+///   reference or the host is no longer known
+pub fn resolve_host_reference<'a>(data: &str, hosts: impl IntoIterator<Item = &'a str>) -> String {
+    let Some((prefix, reference)) = data.rsplit_once(HOST_REFERENCE_MARKER) else {
+        return data.to_string();
+    };
+    match hosts.into_iter().find(|host| host_reference(host) == reference) {
+        Some(host) => format!("{}{}", prefix, host),
+        None => data.to_string(),
+    }
+}
+
+/// Short, stable reference of a host: FNV-1a hash of its address
+fn host_reference(host: &str) -> String {
+    let hash = host
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3));
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_hosts_are_referred_to_by_hash() {
+        assert_eq!(host_callback("host_", "192.168.1.50"), "host_192.168.1.50");
+
+        let host = "voron-trident-300.workshop.printers.example.com";
+        let data = host_callback("firmware_restart_confirm_", host);
+        assert!(data.len() <= MAX_CALLBACK_DATA_BYTES);
+        assert_eq!(resolve_host_reference(&data, ["192.168.1.50", host]), format!("firmware_restart_confirm_{}", host));
+        assert_eq!(resolve_host_reference(&data, ["192.168.1.50"]), data);
+        assert_eq!(resolve_host_reference("host_192.168.1.50", [host]), "host_192.168.1.50");
+    }
+}
@@ -0,0 +1,100 @@
+//! Telegram error types
+//!
+//! This module defines the errors returned by the Telegram bot and its Tauri
+//! commands. They serialize to `{ kind, message }` so the UI can tell an
+//! invalid token from a missing user or a network problem.
+
+use thiserror::Error;
+use teloxide::{ApiError, RequestError};
+use crate::error::MoonrakerError;
+
+/// Errors of the Telegram bot
+#[derive(Debug, Error)]
+pub enum TelegramError {
+    /// Telegram could not be reached
+    #[error("Telegram network error: {0}")]
+    Network(String),
+
+    /// Telegram rejected a request
+    #[error("Telegram API error: {0}")]
+    Api(String),
+
+    /// The bot token is invalid or was revoked
+    #[error("Bot token is invalid or was revoked")]
+    Unauthorized,
+
+    /// Missing or unreadable configuration (token, settings file)
+    #[error("Configuration error: {0}")]
+    Config(String),
+
+    /// The bot is not running
+    #[error("Bot is not running")]
+    NotRunning,
+
+    /// The bot is already running
+    #[error("Bot is already running")]
+    AlreadyRunning,
+
+    /// A registration code is already active
+    #[error("Registration is already active")]
+    RegistrationActive,
+
+    /// The user is already registered
+    #[error("User {0} already exists")]
+    UserExists(i64),
+
+    /// The user is not registered
+    #[error("User {0} not found")]
+    UserNotFound(i64),
+
+    /// A printer request made on behalf of a user failed
+    #[error(transparent)]
+    Printer(#[from] MoonrakerError),
+}
+
+impl TelegramError {
+    /// Short machine readable category for the UI
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TelegramError::Network(_) => "network",
+            TelegramError::Api(_) => "api",
+            TelegramError::Unauthorized => "unauthorized",
+            TelegramError::Config(_) => "config",
+            TelegramError::NotRunning => "not_running",
+            TelegramError::AlreadyRunning => "already_running",
+            TelegramError::RegistrationActive => "registration_active",
+            TelegramError::UserExists(_) => "user_exists",
+            TelegramError::UserNotFound(_) => "user_not_found",
+            TelegramError::Printer(_) => "printer",
+        }
+    }
+}
+
+impl serde::Serialize for TelegramError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("TelegramError", 2)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+impl From<RequestError> for TelegramError {
+    fn from(err: RequestError) -> Self {
+        match err {
+            // teloxide reports "Unauthorized" (invalid token) as `NotFound`
+            RequestError::Api(ApiError::NotFound) => TelegramError::Unauthorized,
+            RequestError::Api(e) => TelegramError::Api(e.to_string()),
+            RequestError::Network(e) => TelegramError::Network(e.to_string()),
+            RequestError::Io(e) => TelegramError::Network(e.to_string()),
+            other => TelegramError::Api(other.to_string()),
+        }
+    }
+}
+
+/// Result type alias for Telegram operations
+pub type TelegramResult<T> = Result<T, TelegramError>;
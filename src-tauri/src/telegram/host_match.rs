@@ -0,0 +1,122 @@
+//! Host lookup by name for bot commands
+//!
+//! Slash commands such as `/snapshot voron` name the printer in free text.
+//! The name is matched against host names and IP addresses, tolerating
+//! different case, partial names and small typos.
+
+use crate::models::host::HostInfo;
+
+/// Maximum edit distance accepted for a typo match
+const MAX_TYPO_DISTANCE: usize = 2;
+
+/// Result of looking up a host by name
+#[derive(Debug, Clone)]
+pub enum HostMatch {
+    /// Exactly one host matches
+    Found(Box<HostInfo>),
+    /// Several hosts match equally well
+    Ambiguous(Vec<HostInfo>),
+    NotFound,
+}
+
+/// Finds the host a user meant
+///
+/// Tries, in order: exact name or IP address, names starting with the
+/// query, names containing it, and finally names within a small edit
+/// distance. The first step with any match decides the result.
+///
+/// # Arguments
+/// * `hosts` - Known hosts
+/// * `query` - Name typed by the user
+pub fn match_host(hosts: &[HostInfo], query: &str) -> HostMatch {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return HostMatch::NotFound;
+    }
+
+    let steps: [&dyn Fn(&str) -> bool; 3] = [
+        &|name| name == query,
+        &|name| name.starts_with(&query),
+        &|name| name.contains(&query),
+    ];
+    for step in steps {
+        let found: Vec<&HostInfo> = hosts
+            .iter()
+            .filter(|h| h.ip_address == query || step(&h.hostname.to_lowercase()))
+            .collect();
+        if !found.is_empty() {
+            return select(found);
+        }
+    }
+
+    let distances: Vec<(usize, &HostInfo)> = hosts
+        .iter()
+        .map(|h| (edit_distance(&h.hostname.to_lowercase(), &query), h))
+        .filter(|(distance, _)| *distance <= MAX_TYPO_DISTANCE)
+        .collect();
+    match distances.iter().map(|(distance, _)| *distance).min() {
+        Some(best) => select(distances.into_iter().filter(|(d, _)| *d == best).map(|(_, h)| h).collect()),
+        None => HostMatch::NotFound,
+    }
+}
+
+fn select(found: Vec<&HostInfo>) -> HostMatch {
+    match found.as_slice() {
+        [host] => HostMatch::Found(Box::new((*host).clone())),
+        _ => HostMatch::Ambiguous(found.into_iter().cloned().collect()),
+    }
+}
+
+/// Levenshtein distance between two strings, counted in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host(name: &str, ip: &str) -> HostInfo {
+        HostInfo {
+            hostname: name.to_string(),
+            ip_address: ip.to_string(),
+            ..HostInfo::default()
+        }
+    }
+
+    fn names(result: HostMatch) -> Vec<String> {
+        match result {
+            HostMatch::Found(host) => vec![host.hostname],
+            HostMatch::Ambiguous(hosts) => hosts.into_iter().map(|h| h.hostname).collect(),
+            HostMatch::NotFound => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn exact_and_prefix_matches_win_over_substrings() {
+        let hosts = vec![host("Voron", "192.168.1.10"), host("Voron-Trident", "192.168.1.11"), host("Ender", "192.168.1.12")];
+
+        assert_eq!(names(match_host(&hosts, "voron")), vec!["Voron"]);
+        assert_eq!(names(match_host(&hosts, "trid")), vec!["Voron-Trident"]);
+        assert_eq!(names(match_host(&hosts, "192.168.1.12")), vec!["Ender"]);
+        assert_eq!(names(match_host(&hosts, "vor")), vec!["Voron", "Voron-Trident"]);
+    }
+
+    #[test]
+    fn small_typos_are_tolerated() {
+        let hosts = vec![host("Prusa", "192.168.1.20"), host("Ender", "192.168.1.21")];
+
+        assert_eq!(names(match_host(&hosts, "pursa")), vec!["Prusa"]);
+        assert!(matches!(match_host(&hosts, "bambu"), HostMatch::NotFound));
+    }
+}
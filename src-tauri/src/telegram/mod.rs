@@ -1,3 +1,8 @@
 pub mod bot;
+pub mod callback_data;
+pub mod error;
+pub mod host_match;
+pub mod send_queue;
 
 pub use bot::TelegramBot;
+pub use error::{TelegramError, TelegramResult};
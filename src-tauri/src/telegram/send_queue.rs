@@ -0,0 +1,174 @@
+//! Rate limited Telegram send queue
+//!
+//! Broadcasts with photos to many users run into Telegram flood control
+//! (429 Too Many Requests). Messages are therefore queued and sent by one
+//! worker that keeps to the global and per-chat limits, waits out
+//! `RetryAfter` answers and retries transient failures, so a subscriber
+//! does not miss an alert because it was sent in a burst.
+//!
+//! Chats that can never be reached again (the user blocked the bot, the
+//! chat was deleted) are flagged on the user record instead of retried.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use teloxide::prelude::*;
+use teloxide::types::{ChatId, InlineKeyboardMarkup, InputFile, ParseMode};
+use teloxide::{ApiError, RequestError};
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::Instant;
+use crate::models::TelegramUser;
+use crate::models::config::{TELEGRAM_CHAT_SEND_INTERVAL_MS, TELEGRAM_GLOBAL_SEND_INTERVAL_MS, TELEGRAM_QUEUE_RETRY_POLICY};
+use crate::retry::retry;
+use crate::telegram::bot::save_users_to_file;
+use crate::telegram::error::{TelegramError, TelegramResult};
+
+/// Message waiting to be sent
+#[derive(Debug, Clone)]
+pub struct OutgoingMessage {
+    pub chat_id: ChatId,
+    /// Message text, or the caption when a photo or animation is attached
+    pub text: String,
+    pub parse_mode: Option<ParseMode>,
+    /// Photo sent with the text as caption
+    pub photo: Option<Vec<u8>>,
+    /// Animated GIF sent with the text as caption, instead of the photo
+    pub animation: Option<Vec<u8>>,
+    /// Buttons shown below the message
+    pub reply_markup: Option<InlineKeyboardMarkup>,
+}
+
+/// Handle of the send queue of one bot
+#[derive(Debug, Clone)]
+pub struct SendQueue {
+    sender: mpsc::UnboundedSender<OutgoingMessage>,
+}
+
+impl SendQueue {
+    /// Creates the queue and starts its worker
+    ///
+    /// The worker stops once every handle of the queue is dropped.
+    ///
+    /// # Arguments
+    /// * `bot` - Bot the messages are sent with
+    /// * `users` - Registered users, flagged when their chat is unreachable
+    pub fn new(bot: Bot, users: Arc<Mutex<Vec<TelegramUser>>>) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run_send_queue(bot, users, receiver));
+        Self { sender }
+    }
+
+    /// Queues a message for sending
+    pub fn enqueue(&self, message: OutgoingMessage) -> TelegramResult<()> {
+        self.sender.send(message).map_err(|_| TelegramError::NotRunning)
+    }
+}
+
+/// Sends queued messages one after another, keeping to the rate limits
+async fn run_send_queue(
+    bot: Bot,
+    users: Arc<Mutex<Vec<TelegramUser>>>,
+    mut receiver: mpsc::UnboundedReceiver<OutgoingMessage>,
+) {
+    let global_interval = Duration::from_millis(TELEGRAM_GLOBAL_SEND_INTERVAL_MS);
+    let chat_interval = Duration::from_millis(TELEGRAM_CHAT_SEND_INTERVAL_MS);
+    let mut last_send: Option<Instant> = None;
+    let mut last_chat_send: HashMap<ChatId, Instant> = HashMap::new();
+
+    while let Some(message) = receiver.recv().await {
+        // Messages queued before the chat was found to be unreachable
+        if is_blocked(&users, message.chat_id).await {
+            continue;
+        }
+
+        let mut ready_at = last_send.map(|t| t + global_interval);
+        if let Some(chat_sent) = last_chat_send.get(&message.chat_id) {
+            ready_at = ready_at.max(Some(*chat_sent + chat_interval));
+        }
+        if let Some(ready_at) = ready_at {
+            tokio::time::sleep_until(ready_at).await;
+        }
+
+        // RetryAfter is honoured by the retry helper; as the worker is
+        // sequential, the whole queue waits for flood control to end
+        let result = retry(&TELEGRAM_QUEUE_RETRY_POLICY, || send_message(&bot, &message)).await;
+        if let Err(e) = result {
+            match blocked_reason(&e) {
+                Some(reason) => flag_blocked(&users, message.chat_id, reason).await,
+                None => eprintln!("Failed to send Telegram message to {}: {}", message.chat_id, e),
+            }
+        }
+
+        let now = Instant::now();
+        last_send = Some(now);
+        last_chat_send.insert(message.chat_id, now);
+        last_chat_send.retain(|_, sent| now.duration_since(*sent) < chat_interval);
+    }
+}
+
+async fn send_message(bot: &Bot, message: &OutgoingMessage) -> Result<(), teloxide::RequestError> {
+    match (&message.animation, &message.photo) {
+        (Some(animation), _) => {
+            let file = InputFile::memory(animation.clone()).file_name("print.gif");
+            let mut request = bot.send_animation(message.chat_id, file).caption(&message.text);
+            if let Some(parse_mode) = message.parse_mode {
+                request = request.parse_mode(parse_mode);
+            }
+            if let Some(markup) = &message.reply_markup {
+                request = request.reply_markup(markup.clone());
+            }
+            request.await?;
+        }
+        (None, Some(photo)) => {
+            let mut request = bot.send_photo(message.chat_id, InputFile::memory(photo.clone())).caption(&message.text);
+            if let Some(parse_mode) = message.parse_mode {
+                request = request.parse_mode(parse_mode);
+            }
+            if let Some(markup) = &message.reply_markup {
+                request = request.reply_markup(markup.clone());
+            }
+            request.await?;
+        }
+        (None, None) => {
+            let mut request = bot.send_message(message.chat_id, &message.text);
+            if let Some(parse_mode) = message.parse_mode {
+                request = request.parse_mode(parse_mode);
+            }
+            if let Some(markup) = &message.reply_markup {
+                request = request.reply_markup(markup.clone());
+            }
+            request.await?;
+        }
+    }
+    Ok(())
+}
+
+/// Gets the reason when an error means the chat can never be reached again
+pub fn blocked_reason(error: &RequestError) -> Option<&'static str> {
+    match error {
+        RequestError::Api(ApiError::BotBlocked) => Some("bot was blocked by the user"),
+        RequestError::Api(ApiError::ChatNotFound) => Some("chat not found"),
+        RequestError::Api(ApiError::UserDeactivated) => Some("user is deactivated"),
+        RequestError::Api(ApiError::BotKicked) => Some("bot was kicked from the chat"),
+        _ => None,
+    }
+}
+
+async fn is_blocked(users: &Mutex<Vec<TelegramUser>>, chat_id: ChatId) -> bool {
+    users.lock().await.iter().any(|u| u.user_id == chat_id.0 && u.is_blocked())
+}
+
+/// Marks the user of a chat as unreachable and saves the user list
+async fn flag_blocked(users: &Mutex<Vec<TelegramUser>>, chat_id: ChatId, reason: &str) {
+    let mut users = users.lock().await;
+    let Some(user) = users.iter_mut().find(|u| u.user_id == chat_id.0) else {
+        return;
+    };
+    println!("Telegram user {} is unreachable: {}", user.display_name(), reason);
+    user.blocked_reason = Some(reason.to_string());
+    user.blocked_at = Some(chrono::Utc::now());
+
+    if let Err(e) = save_users_to_file(&users).await {
+        eprintln!("Failed to save Telegram users: {}", e);
+    }
+}
@@ -0,0 +1,411 @@
+//! Telemetry logging
+//!
+//! The background monitor records temperature, progress and status samples
+//! of every host into a local SQLite database, so thermal behavior and
+//! failure patterns can be analysed after the fact. Samples older than the
+//! retention period are deleted while recording, at most once an hour.
+//! Temperature charts get series downsampled here, so the webview never
+//! receives raw samples.
+//!
+//! One connection is kept open and shared behind a mutex; the functions
+//! here block and are called off the async worker threads.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, TimeZone, Utc};
+use rusqlite::{params, Connection};
+use crate::models::telemetry::{TelemetrySample, TemperaturePoint, TemperatureSeries, MAX_SERIES_POINTS};
+
+/// Largest number of samples a query returns
+pub const MAX_QUERY_SAMPLES: u32 = 10_000;
+
+/// Time between two deletions of samples older than the retention period
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+static DATABASE: Mutex<Option<Database>> = Mutex::new(None);
+
+/// Open telemetry database
+struct Database {
+    path: PathBuf,
+    connection: Connection,
+    /// When old samples were last deleted
+    pruned_at: Option<Instant>,
+}
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS samples (
+        id INTEGER PRIMARY KEY,
+        host TEXT NOT NULL,
+        timestamp INTEGER NOT NULL,
+        state TEXT NOT NULL,
+        progress REAL,
+        filename TEXT,
+        extruder_temperature REAL,
+        extruder_target REAL,
+        bed_temperature REAL,
+        bed_target REAL
+    );
+    CREATE INDEX IF NOT EXISTS samples_host_timestamp ON samples (host, timestamp);
+    CREATE INDEX IF NOT EXISTS samples_timestamp ON samples (timestamp);
+";
+
+/// Opens the telemetry database, creating it if needed
+///
+/// # Arguments
+/// * `path` - Database file
+pub fn open_database(path: &Path) -> Result<Connection, String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let connection = Connection::open(path).map_err(|e| format!("Failed to open telemetry database: {}", e))?;
+    connection
+        .execute_batch(SCHEMA)
+        .map_err(|e| format!("Failed to prepare telemetry database: {}", e))?;
+    Ok(connection)
+}
+
+/// Runs a function on the shared connection to a database
+///
+/// The connection is opened on first use and reopened when the database
+/// file changes, e.g. after the settings were edited.
+fn with_database<T>(path: &Path, f: impl FnOnce(&mut Database) -> Result<T, String>) -> Result<T, String> {
+    let mut database = DATABASE.lock().unwrap_or_else(|e| e.into_inner());
+    if database.as_ref().is_none_or(|database| database.path != path) {
+        *database = None;
+        *database = Some(Database { path: path.to_path_buf(), connection: open_database(path)?, pruned_at: None });
+    }
+    f(database.as_mut().expect("database was opened above"))
+}
+
+/// Gets the time before which samples are deleted
+///
+/// # Returns
+/// * None when samples are kept forever or the retention reaches beyond the calendar
+fn retention_cutoff(now: DateTime<Utc>, retention: Option<chrono::Duration>) -> Option<DateTime<Utc>> {
+    now.checked_sub_signed(retention?)
+}
+
+/// Stores a sample and, once in a while, deletes the samples older than the retention period
+///
+/// # Arguments
+/// * `path` - Database file
+/// * `sample` - Sample to store
+/// * `retention` - How long samples are kept (None: keep all), see `TelemetrySettings::retention`
+pub fn record_sample(path: &Path, sample: &TelemetrySample, retention: Option<chrono::Duration>) -> Result<(), String> {
+    with_database(path, |database| record_into(database, sample, retention))
+}
+
+fn record_into(database: &mut Database, sample: &TelemetrySample, retention: Option<chrono::Duration>) -> Result<(), String> {
+    database
+        .connection
+        .execute(
+            "INSERT INTO samples (host, timestamp, state, progress, filename, extruder_temperature, extruder_target, bed_temperature, bed_target)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                sample.host,
+                sample.timestamp.timestamp_millis(),
+                sample.state,
+                sample.progress,
+                sample.filename,
+                sample.extruder_temperature,
+                sample.extruder_target,
+                sample.bed_temperature,
+                sample.bed_target,
+            ],
+        )
+        .map_err(|e| format!("Failed to store telemetry sample: {}", e))?;
+
+    let Some(cutoff) = retention_cutoff(Utc::now(), retention) else {
+        return Ok(());
+    };
+    if database.pruned_at.is_some_and(|at| at.elapsed() < PRUNE_INTERVAL) {
+        return Ok(());
+    }
+    database.pruned_at = Some(Instant::now());
+    database
+        .connection
+        .execute("DELETE FROM samples WHERE timestamp < ?1", params![cutoff.timestamp_millis()])
+        .map_err(|e| format!("Failed to delete old telemetry samples: {}", e))?;
+    Ok(())
+}
+
+/// Reads stored samples, oldest first
+///
+/// When more samples than `limit` match, the newest ones are returned.
+///
+/// # Arguments
+/// * `path` - Database file
+/// * `host` - Only samples of this host (None: all hosts)
+/// * `from` - Earliest sample time (None: no lower bound)
+/// * `to` - Latest sample time (None: now)
+/// * `limit` - Maximum number of samples, capped at `MAX_QUERY_SAMPLES`
+pub fn query_samples(
+    path: &Path,
+    host: Option<&str>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    limit: u32,
+) -> Result<Vec<TelemetrySample>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    with_database(path, |database| query_from(&database.connection, host, from, to, limit))
+}
+
+fn query_from(
+    connection: &Connection,
+    host: Option<&str>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    limit: u32,
+) -> Result<Vec<TelemetrySample>, String> {
+    let mut statement = connection
+        .prepare(
+            "SELECT host, timestamp, state, progress, filename, extruder_temperature, extruder_target, bed_temperature, bed_target
+             FROM samples
+             WHERE (?1 IS NULL OR host = ?1) AND timestamp >= ?2 AND timestamp <= ?3
+             ORDER BY timestamp DESC
+             LIMIT ?4",
+        )
+        .map_err(|e| format!("Failed to query telemetry: {}", e))?;
+    let rows = statement
+        .query_map(
+            params![
+                host,
+                from.map_or(i64::MIN, |from| from.timestamp_millis()),
+                to.map_or(i64::MAX, |to| to.timestamp_millis()),
+                limit.min(MAX_QUERY_SAMPLES),
+            ],
+            |row| {
+                Ok(TelemetrySample {
+                    host: row.get(0)?,
+                    timestamp: Utc.timestamp_millis_opt(row.get(1)?).single().unwrap_or_default(),
+                    state: row.get(2)?,
+                    progress: row.get(3)?,
+                    filename: row.get(4)?,
+                    extruder_temperature: row.get(5)?,
+                    extruder_target: row.get(6)?,
+                    bed_temperature: row.get(7)?,
+                    bed_target: row.get(8)?,
+                })
+            },
+        )
+        .map_err(|e| format!("Failed to query telemetry: {}", e))?;
+
+    let mut samples = rows
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read telemetry: {}", e))?;
+    samples.reverse();
+    Ok(samples)
+}
+
+/// Builds a downsampled temperature series of a host
+///
+/// The window is split into `resolution` equal buckets; each bucket with
+/// samples becomes one point with the mean, minimum and maximum readings.
+///
+/// # Arguments
+/// * `path` - Database file
+/// * `host` - Host IP address
+/// * `window` - Time span ending now
+/// * `resolution` - Number of buckets, capped at `MAX_SERIES_POINTS`
+pub fn temperature_series(path: &Path, host: &str, window: chrono::Duration, resolution: u32) -> Result<TemperatureSeries, String> {
+    let to = Utc::now();
+    let from = to.checked_sub_signed(window).ok_or("Window is too long")?;
+    let buckets = resolution.clamp(1, MAX_SERIES_POINTS);
+    let bucket_ms = (window.num_milliseconds() as f64 / buckets as f64).max(1.0);
+    let mut series = TemperatureSeries {
+        host: host.to_string(),
+        from,
+        to,
+        bucket_seconds: bucket_ms / 1000.0,
+        points: Vec::new(),
+    };
+    if !path.exists() {
+        return Ok(series);
+    }
+    series.points = with_database(path, |database| {
+        downsample_from(&database.connection, host, from, to, Downsampler::new(from.timestamp_millis(), bucket_ms, buckets))
+    })?;
+    Ok(series)
+}
+
+fn downsample_from(
+    connection: &Connection,
+    host: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    mut downsampler: Downsampler,
+) -> Result<Vec<TemperaturePoint>, String> {
+    let mut statement = connection
+        .prepare(
+            "SELECT timestamp, extruder_temperature, extruder_target, bed_temperature, bed_target
+             FROM samples
+             WHERE host = ?1 AND timestamp >= ?2 AND timestamp <= ?3
+             ORDER BY timestamp",
+        )
+        .map_err(|e| format!("Failed to query telemetry: {}", e))?;
+    let rows = statement
+        .query_map(params![host, from.timestamp_millis(), to.timestamp_millis()], |row| {
+            Ok((row.get(0)?, [row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?]))
+        })
+        .map_err(|e| format!("Failed to query telemetry: {}", e))?;
+
+    for row in rows {
+        let (timestamp, readings) = row.map_err(|e| format!("Failed to read telemetry: {}", e))?;
+        downsampler.add(timestamp, readings);
+    }
+    Ok(downsampler.finish())
+}
+
+/// Running statistics of one heater within a bucket
+#[derive(Default)]
+struct HeaterStats {
+    sum: f64,
+    count: u32,
+    min: Option<f64>,
+    max: Option<f64>,
+    target: Option<f64>,
+}
+
+impl HeaterStats {
+    fn add(&mut self, temperature: Option<f64>, target: Option<f64>) {
+        if let Some(temperature) = temperature {
+            self.sum += temperature;
+            self.count += 1;
+            self.min = Some(self.min.map_or(temperature, |min| min.min(temperature)));
+            self.max = Some(self.max.map_or(temperature, |max| max.max(temperature)));
+        }
+        self.target = target.or(self.target);
+    }
+
+    fn mean(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum / self.count as f64)
+    }
+}
+
+/// Aggregates time-ordered samples into fixed-size buckets
+struct Downsampler {
+    start_ms: i64,
+    bucket_ms: f64,
+    buckets: u32,
+    current: Option<(u32, HeaterStats, HeaterStats, u32)>,
+    points: Vec<TemperaturePoint>,
+}
+
+impl Downsampler {
+    fn new(start_ms: i64, bucket_ms: f64, buckets: u32) -> Self {
+        Self { start_ms, bucket_ms, buckets, current: None, points: Vec::new() }
+    }
+
+    /// Adds a sample: extruder temperature and target, bed temperature and target
+    fn add(&mut self, timestamp_ms: i64, [extruder, extruder_target, bed, bed_target]: [Option<f64>; 4]) {
+        let index = (((timestamp_ms - self.start_ms) as f64 / self.bucket_ms) as u32).min(self.buckets - 1);
+        if self.current.as_ref().is_some_and(|(current, ..)| *current != index) {
+            self.flush();
+        }
+        let (_, extruder_stats, bed_stats, samples) =
+            self.current.get_or_insert_with(|| (index, HeaterStats::default(), HeaterStats::default(), 0));
+        extruder_stats.add(extruder, extruder_target);
+        bed_stats.add(bed, bed_target);
+        *samples += 1;
+    }
+
+    fn flush(&mut self) {
+        let Some((index, extruder, bed, samples)) = self.current.take() else {
+            return;
+        };
+        let start_ms = self.start_ms + (index as f64 * self.bucket_ms) as i64;
+        self.points.push(TemperaturePoint {
+            timestamp: Utc.timestamp_millis_opt(start_ms).single().unwrap_or_default(),
+            extruder: extruder.mean(),
+            extruder_min: extruder.min,
+            extruder_max: extruder.max,
+            extruder_target: extruder.target,
+            bed: bed.mean(),
+            bed_min: bed.min,
+            bed_max: bed.max,
+            bed_target: bed.target,
+            samples,
+        });
+    }
+
+    fn finish(mut self) -> Vec<TemperaturePoint> {
+        self.flush();
+        self.points
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PrinterState;
+
+    #[test]
+    fn samples_are_aggregated_per_bucket() {
+        // Two buckets of 10 seconds, nothing in the third
+        let mut downsampler = Downsampler::new(0, 10_000.0, 3);
+        downsampler.add(1_000, [Some(200.0), Some(215.0), Some(58.0), Some(60.0)]);
+        downsampler.add(6_000, [Some(210.0), Some(215.0), None, Some(60.0)]);
+        downsampler.add(12_000, [Some(214.0), Some(220.0), Some(60.0), Some(60.0)]);
+
+        let points = downsampler.finish();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].samples, 2);
+        assert_eq!(points[0].extruder, Some(205.0));
+        assert_eq!((points[0].extruder_min, points[0].extruder_max), (Some(200.0), Some(210.0)));
+        assert_eq!(points[0].bed, Some(58.0));
+        assert_eq!(points[1].timestamp.timestamp_millis(), 10_000);
+        assert_eq!(points[1].extruder_target, Some(220.0));
+    }
+
+    #[test]
+    fn series_window_beyond_the_calendar_is_rejected() {
+        let path = std::env::temp_dir().join("mhs-telemetry-missing.db");
+        assert!(temperature_series(&path, "192.168.1.50", chrono::Duration::MAX, 10).is_err());
+        let series = temperature_series(&path, "192.168.1.50", chrono::Duration::days(30), 10).unwrap();
+        assert!(series.points.is_empty());
+    }
+
+    #[test]
+    fn samples_are_stored_filtered_and_pruned() {
+        let path = std::env::temp_dir().join(format!("mhs-telemetry-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut old = TelemetrySample::state_only("192.168.1.50", PrinterState::Standby);
+        old.timestamp = Utc::now() - chrono::Duration::days(10);
+        let mut printing = TelemetrySample::state_only("192.168.1.50", PrinterState::Printing);
+        printing.extruder_temperature = Some(215.2);
+        printing.progress = Some(12.5);
+        let other = TelemetrySample::state_only("192.168.1.51", PrinterState::Offline);
+
+        record_sample(&path, &old, None).unwrap();
+        record_sample(&path, &other, None).unwrap();
+        assert_eq!(query_samples(&path, Some("192.168.1.50"), None, None, MAX_QUERY_SAMPLES).unwrap().len(), 1);
+
+        record_sample(&path, &printing, Some(chrono::Duration::days(7))).unwrap();
+        let samples = query_samples(&path, Some("192.168.1.50"), None, None, MAX_QUERY_SAMPLES).unwrap();
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].state, "printing");
+        assert_eq!(samples[0].extruder_temperature, Some(215.2));
+        assert_eq!(samples[0].progress, Some(12.5));
+        assert_eq!(query_samples(&path, None, None, None, MAX_QUERY_SAMPLES).unwrap().len(), 2);
+
+        // Pruned at most once per interval
+        record_sample(&path, &old, Some(chrono::Duration::days(7))).unwrap();
+        assert_eq!(query_samples(&path, None, None, None, MAX_QUERY_SAMPLES).unwrap().len(), 3);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn retention_beyond_the_calendar_keeps_everything() {
+        let settings = crate::models::config::TelemetrySettings { retention_days: u32::MAX, ..Default::default() };
+        assert_eq!(retention_cutoff(Utc::now(), settings.retention()), None);
+        assert_eq!(retention_cutoff(Utc::now(), None), None);
+
+        let now = Utc::now();
+        assert_eq!(retention_cutoff(now, Some(chrono::Duration::days(7))), Some(now - chrono::Duration::days(7)));
+    }
+}
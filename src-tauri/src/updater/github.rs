@@ -3,7 +3,10 @@
 //! This module provides functionality to check for updates
 //! by querying the GitHub repository API.
 
+use crate::api::client::{configure_http_client, HttpTarget};
 use crate::error::MoonrakerResult;
+use crate::models::config::UPDATE_CHECK_RETRY_POLICY;
+use crate::retry::retry;
 use crate::updater::models::{GitHubRelease, UpdateCheckResult};
 use reqwest::Client;
 use std::time::Duration;
@@ -11,7 +14,6 @@ use std::time::Duration;
 const GITHUB_API_BASE: &str = "https://api.github.com";
 const REPO_OWNER: &str = "konk22";
 const REPO_NAME: &str = "MHS";
-const USER_AGENT: &str = "MoonrakerHostScanner/0.0.9";
 
 /// GitHub API client for checking updates
 pub struct GitHubUpdater {
@@ -21,9 +23,8 @@ pub struct GitHubUpdater {
 impl GitHubUpdater {
     /// Creates a new GitHub updater instance
     pub fn new() -> Self {
-        let client = Client::builder()
+        let client = configure_http_client(Client::builder(), HttpTarget::Internet)
             .timeout(Duration::from_secs(10))
-            .user_agent(USER_AGENT)
             .build()
             .unwrap_or_default();
 
@@ -68,11 +69,18 @@ impl GitHubUpdater {
             GITHUB_API_BASE, REPO_OWNER, REPO_NAME
         );
 
-        let response = self.client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to fetch latest release: {}", e))?;
+        // Server errors and rate limits are retried; other error statuses are reported below
+        let response = retry(&UPDATE_CHECK_RETRY_POLICY, || async {
+            let response = self.client.get(&url).send().await?;
+            let status = response.status();
+            if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                response.error_for_status()
+            } else {
+                Ok(response)
+            }
+        })
+        .await
+        .map_err(|e| format!("Failed to fetch latest release: {}", e))?;
 
         if !response.status().is_success() {
             return Err(format!(
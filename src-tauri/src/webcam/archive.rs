@@ -0,0 +1,278 @@
+//! Snapshot archival and timelapse creation
+//!
+//! This module saves webcam snapshots of running prints into one folder per
+//! job and stitches them into an animated GIF when the print ends. It is meant
+//! for printers without the Moonraker timelapse component. A smaller GIF of
+//! the same snapshots can be attached to the Telegram completion message.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::imageops::FilterType;
+use image::{Delay, DynamicImage, Frame};
+use serde::{Deserialize, Serialize};
+use crate::models::config::CompletionGifSettings;
+
+/// File name of the generated timelapse inside a job folder
+pub const TIMELAPSE_FILE_NAME: &str = "timelapse.gif";
+
+/// Archive of one print job
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchivedJob {
+    /// Host IP address
+    pub host: String,
+    /// Job folder
+    pub directory: String,
+    /// Number of saved snapshots
+    pub frame_count: u32,
+    /// Timelapse file, if one was created
+    pub timelapse: Option<String>,
+}
+
+/// Per-host archival state of the running print
+#[derive(Debug)]
+struct ActiveArchive {
+    job_name: String,
+    directory: PathBuf,
+    last_capture: Instant,
+    frame_count: u32,
+}
+
+/// Tracks snapshot folders of running prints for all hosts
+#[derive(Debug, Default)]
+pub struct SnapshotArchiver {
+    active: HashMap<String, ActiveArchive>,
+    /// Job name and folder of the last finished archive per host
+    finished: HashMap<String, (String, PathBuf)>,
+}
+
+impl SnapshotArchiver {
+    /// Creates an empty archiver
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks whether a new snapshot is due for the host
+    pub fn should_capture(&self, host: &str, interval_minutes: u64) -> bool {
+        match self.active.get(host) {
+            Some(archive) => archive.last_capture.elapsed() >= Duration::from_secs(interval_minutes * 60),
+            None => true,
+        }
+    }
+
+    /// Saves a snapshot of the running print, creating the job folder on first use
+    ///
+    /// # Arguments
+    /// * `base_dir` - Archive root directory
+    /// * `host` - Host IP address
+    /// * `job_name` - Printed file name, used for the folder name
+    /// * `image_data` - Snapshot bytes (usually JPEG)
+    pub fn save_frame(&mut self, base_dir: &Path, host: &str, job_name: &str, image_data: &[u8]) -> Result<(), String> {
+        if !self.active.contains_key(host) {
+            let directory = base_dir.join(job_folder_name(host, job_name));
+            fs::create_dir_all(&directory)
+                .map_err(|e| format!("Failed to create {}: {}", directory.display(), e))?;
+            self.finished.remove(host);
+            self.active.insert(host.to_string(), ActiveArchive {
+                job_name: job_name.to_string(),
+                directory,
+                last_capture: Instant::now(),
+                frame_count: 0,
+            });
+        }
+
+        let archive = self.active.get_mut(host).ok_or_else(|| "Archive not found".to_string())?;
+        let path = archive.directory.join(format!("frame_{:05}.jpg", archive.frame_count));
+        fs::write(&path, image_data).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        archive.frame_count += 1;
+        archive.last_capture = Instant::now();
+        Ok(())
+    }
+
+    /// Ends archival of the host's print
+    ///
+    /// # Returns
+    /// * Job folder and snapshot count, if the print was archived
+    pub fn finish(&mut self, host: &str) -> Option<ArchivedJob> {
+        let archive = self.active.remove(host)?;
+        let job = ArchivedJob {
+            host: host.to_string(),
+            directory: archive.directory.to_string_lossy().to_string(),
+            frame_count: archive.frame_count,
+            timelapse: None,
+        };
+        self.finished.insert(host.to_string(), (archive.job_name, archive.directory));
+        Some(job)
+    }
+
+    /// Takes the folder of the host's last finished archive if it belongs to the given job
+    pub fn take_finished(&mut self, host: &str, job_name: &str) -> Option<PathBuf> {
+        match self.finished.remove(host) {
+            Some((name, directory)) if name == job_name => Some(directory),
+            _ => None,
+        }
+    }
+}
+
+/// Builds a filesystem-safe folder name for a job
+pub(crate) fn job_folder_name(host: &str, job_name: &str) -> String {
+    let stem = Path::new(job_name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("print");
+    let safe: String = format!("{}_{}", host, stem)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+        .collect();
+    format!("{}_{}", chrono::Local::now().format("%Y%m%d-%H%M%S"), safe)
+}
+
+/// Stitches the snapshots of a job folder into an animated GIF
+///
+/// This is CPU heavy; call it from a blocking task.
+///
+/// # Arguments
+/// * `directory` - Job folder with `frame_*.jpg` files
+/// * `frame_delay_ms` - Delay between frames
+/// * `max_width` - Frames wider than this are downscaled
+///
+/// # Returns
+/// * Path of the created GIF
+pub fn build_timelapse(directory: &Path, frame_delay_ms: u32, max_width: u32) -> Result<PathBuf, String> {
+    let frame_paths = frame_paths(directory)?;
+    if frame_paths.len() < 2 {
+        return Err("Not enough snapshots for a timelapse".to_string());
+    }
+
+    let output_path = directory.join(TIMELAPSE_FILE_NAME);
+    let file = fs::File::create(&output_path)
+        .map_err(|e| format!("Failed to create {}: {}", output_path.display(), e))?;
+    encode_gif(file, frame_paths.iter().filter_map(|path| open_frame(path)), frame_delay_ms, max_width)?;
+    Ok(output_path)
+}
+
+/// Builds the completion message GIF from the snapshots of a job folder
+///
+/// Long prints are sampled evenly down to the frame limit, always keeping the
+/// last frame. This is CPU heavy; call it from a blocking task.
+///
+/// # Returns
+/// * GIF bytes
+pub fn completion_gif_from_archive(directory: &Path, settings: &CompletionGifSettings) -> Result<Vec<u8>, String> {
+    let frame_paths = sample_evenly(&frame_paths(directory)?, settings.max_frames as usize);
+    if frame_paths.len() < 2 {
+        return Err("Not enough snapshots for a GIF".to_string());
+    }
+
+    let mut gif = Vec::new();
+    let frames = frame_paths.iter().filter_map(|path| open_frame(path));
+    encode_gif(&mut gif, frames, settings.frame_delay_ms, settings.max_width)?;
+    Ok(gif)
+}
+
+/// Builds the completion message GIF from snapshots taken at the end of the print
+///
+/// This is CPU heavy; call it from a blocking task.
+///
+/// # Returns
+/// * GIF bytes
+pub fn completion_gif_from_snapshots(snapshots: &[Vec<u8>], settings: &CompletionGifSettings) -> Result<Vec<u8>, String> {
+    let frames: Vec<DynamicImage> = snapshots
+        .iter()
+        .filter_map(|data| image::load_from_memory(data).map_err(|e| eprintln!("GIF: skipping snapshot: {}", e)).ok())
+        .collect();
+    if frames.len() < 2 {
+        return Err("Not enough snapshots for a GIF".to_string());
+    }
+
+    let mut gif = Vec::new();
+    encode_gif(&mut gif, frames, settings.frame_delay_ms, settings.max_width)?;
+    Ok(gif)
+}
+
+/// Lists the snapshots of a job folder in capture order
+fn frame_paths(directory: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut frame_paths: Vec<PathBuf> = fs::read_dir(directory)
+        .map_err(|e| format!("Failed to read {}: {}", directory.display(), e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("frame_") && n.ends_with(".jpg"))
+        })
+        .collect();
+    frame_paths.sort();
+    Ok(frame_paths)
+}
+
+fn open_frame(path: &Path) -> Option<DynamicImage> {
+    image::open(path)
+        .map_err(|e| eprintln!("Timelapse: skipping {}: {}", path.display(), e))
+        .ok()
+}
+
+/// Picks at most `max` items spread evenly over the list, including the last one
+fn sample_evenly<T: Clone>(items: &[T], max: usize) -> Vec<T> {
+    if max == 0 || items.len() <= max {
+        return items.to_vec();
+    }
+    if max == 1 {
+        return items[items.len() - 1..].to_vec();
+    }
+    (0..max).map(|i| items[i * (items.len() - 1) / (max - 1)].clone()).collect()
+}
+
+/// Encodes frames into an endlessly looping GIF, downscaling frames wider than `max_width`
+fn encode_gif<W: Write>(
+    writer: W,
+    frames: impl IntoIterator<Item = DynamicImage>,
+    frame_delay_ms: u32,
+    max_width: u32,
+) -> Result<(), String> {
+    let mut encoder = GifEncoder::new_with_speed(writer, 10);
+    encoder.set_repeat(Repeat::Infinite)
+        .map_err(|e| format!("Failed to configure GIF encoder: {}", e))?;
+
+    let delay = Delay::from_numer_denom_ms(frame_delay_ms.max(20), 1);
+    for image in frames {
+        let image = if max_width > 0 && image.width() > max_width {
+            let height = (image.height() as u64 * max_width as u64 / image.width() as u64).max(1) as u32;
+            image.resize_exact(max_width, height, FilterType::Triangle)
+        } else {
+            image
+        };
+        encoder.encode_frame(Frame::from_parts(image.to_rgba8(), 0, 0, delay))
+            .map_err(|e| format!("Failed to encode GIF frame: {}", e))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn snapshot(shade: u8) -> Vec<u8> {
+        let image = DynamicImage::ImageRgb8(image::RgbImage::from_pixel(800, 600, image::Rgb([shade, shade, shade])));
+        let mut data = Cursor::new(Vec::new());
+        image.write_to(&mut data, image::ImageOutputFormat::Png).unwrap();
+        data.into_inner()
+    }
+
+    #[test]
+    fn completion_gif_is_downscaled_and_sampled() {
+        let settings = CompletionGifSettings::default();
+        let gif = completion_gif_from_snapshots(&[snapshot(0), snapshot(128), b"not an image".to_vec()], &settings).unwrap();
+
+        assert!(gif.starts_with(b"GIF89a"));
+        let decoded = image::load_from_memory(&gif).unwrap();
+        assert_eq!(decoded.width(), settings.max_width);
+
+        assert_eq!(sample_evenly(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9], 4), vec![0, 3, 6, 9]);
+        assert!(completion_gif_from_snapshots(&[snapshot(0)], &settings).is_err());
+    }
+}
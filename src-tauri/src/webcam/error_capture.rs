@@ -0,0 +1,143 @@
+//! Snapshot bursts of failed prints
+//!
+//! When a print stops with an error, a few webcam snapshots are taken in
+//! quick succession and saved in one folder per error, together with the
+//! error message and the job. Users who see the alert hours later can
+//! still look at what the printer looked like when it failed.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::models::config::ErrorCaptureSettings;
+use crate::webcam::archive::job_folder_name;
+use crate::webcam::snapshot::fetch_snapshot;
+
+/// File with the error details inside a capture folder
+pub const ERROR_CAPTURE_FILE_NAME: &str = "error.json";
+
+/// Snapshots taken when a print stopped with an error
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ErrorCapture {
+    /// Host IP address
+    pub host: String,
+    pub hostname: String,
+    /// File that was printing
+    pub filename: Option<String>,
+    /// Error reported by Klipper
+    pub error_message: Option<String>,
+    pub captured_at: chrono::DateTime<chrono::Utc>,
+    /// Capture folder
+    pub directory: String,
+    /// Snapshot file names in the folder, oldest first
+    pub frames: Vec<String>,
+}
+
+/// Takes a snapshot burst and saves it with the error
+///
+/// Snapshots that fail to download are skipped; the capture is discarded
+/// when none succeeds (e.g. the printer has no webcam). Older captures of
+/// the host beyond `keep_per_host` are deleted afterwards.
+///
+/// # Arguments
+/// * `host` - Host IP address
+/// * `hostname` - Display name of the host
+/// * `filename` - File that was printing
+/// * `error_message` - Error reported by Klipper
+/// * `client` - HTTP client to use for the snapshots
+/// * `settings` - Burst length and storage
+pub async fn capture_error_burst(
+    host: &str,
+    hostname: &str,
+    filename: Option<String>,
+    error_message: Option<String>,
+    client: &reqwest::Client,
+    settings: &ErrorCaptureSettings,
+) -> Result<ErrorCapture, String> {
+    let base_dir = settings.capture_dir();
+    let directory = base_dir.join(job_folder_name(host, filename.as_deref().unwrap_or("error")));
+    fs::create_dir_all(&directory).map_err(|e| format!("Failed to create {}: {}", directory.display(), e))?;
+
+    let mut frames = Vec::new();
+    for index in 0..settings.frames.max(1) {
+        if index > 0 {
+            tokio::time::sleep(Duration::from_millis(settings.interval_ms)).await;
+        }
+        match fetch_snapshot(host, client).await {
+            Ok(image_data) => {
+                let name = format!("frame_{:02}.jpg", index);
+                let path = directory.join(&name);
+                fs::write(&path, image_data).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+                frames.push(name);
+            }
+            Err(e) => eprintln!("Error capture snapshot of {} failed: {}", host, e),
+        }
+    }
+
+    if frames.is_empty() {
+        let _ = fs::remove_dir_all(&directory);
+        return Err(format!("No snapshot of {} could be taken", host));
+    }
+
+    let capture = ErrorCapture {
+        host: host.to_string(),
+        hostname: hostname.to_string(),
+        filename,
+        error_message,
+        captured_at: chrono::Utc::now(),
+        directory: directory.to_string_lossy().to_string(),
+        frames,
+    };
+    let content = serde_json::to_string_pretty(&capture).map_err(|e| e.to_string())?;
+    fs::write(directory.join(ERROR_CAPTURE_FILE_NAME), content)
+        .map_err(|e| format!("Failed to save error details: {}", e))?;
+
+    if settings.keep_per_host > 0 {
+        prune_captures(&base_dir, host, settings.keep_per_host as usize);
+    }
+    Ok(capture)
+}
+
+/// Lists saved error captures, newest first
+///
+/// # Arguments
+/// * `base_dir` - Capture directory
+/// * `host` - Only captures of this host (None: all hosts)
+pub fn list_error_captures(base_dir: &Path, host: Option<&str>) -> Vec<ErrorCapture> {
+    let Ok(entries) = fs::read_dir(base_dir) else {
+        return Vec::new();
+    };
+    let mut captures: Vec<ErrorCapture> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| fs::read_to_string(entry.path().join(ERROR_CAPTURE_FILE_NAME)).ok())
+        .filter_map(|content| serde_json::from_str::<ErrorCapture>(&content).ok())
+        .filter(|capture| host.is_none_or(|host| capture.host == host))
+        .collect();
+    captures.sort_by_key(|capture| std::cmp::Reverse(capture.captured_at));
+    captures
+}
+
+/// Deletes a capture folder
+///
+/// Only folders inside the capture directory that contain error details
+/// are deleted.
+pub fn delete_error_capture(base_dir: &Path, directory: &str) -> Result<(), String> {
+    let directory = PathBuf::from(directory);
+    // Only a plain folder name below the base directory, so `<base>/..` is refused
+    let in_base_dir = directory
+        .strip_prefix(base_dir)
+        .is_ok_and(|relative| matches!(relative.components().collect::<Vec<_>>()[..], [Component::Normal(_)]));
+    if !in_base_dir || !directory.join(ERROR_CAPTURE_FILE_NAME).is_file() {
+        return Err(format!("{} is not an error capture", directory.display()));
+    }
+    fs::remove_dir_all(&directory).map_err(|e| format!("Failed to delete {}: {}", directory.display(), e))
+}
+
+/// Deletes the oldest captures of a host beyond the given count
+fn prune_captures(base_dir: &Path, host: &str, keep: usize) {
+    for capture in list_error_captures(base_dir, Some(host)).into_iter().skip(keep) {
+        if let Err(e) = delete_error_capture(base_dir, &capture.directory) {
+            eprintln!("Failed to delete old error capture: {}", e);
+        }
+    }
+}
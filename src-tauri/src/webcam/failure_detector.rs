@@ -0,0 +1,211 @@
+//! Heuristic print failure detection
+//!
+//! This module compares consecutive webcam snapshots taken during a print.
+//! It is deliberately simple: a print whose image stops changing (frozen
+//! camera, stalled printer) or suddenly changes a lot (spaghetti, knocked-off
+//! part, camera moved) produces a "print may have failed" warning. It is a
+//! heuristic, not a classifier, and false positives are expected.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use image::imageops::FilterType;
+use serde::{Deserialize, Serialize};
+use crate::i18n::tf;
+use crate::models::config::FailureDetectionSettings;
+
+/// Width of the downscaled analysis frame
+const FRAME_WIDTH: u32 = 64;
+
+/// Height of the downscaled analysis frame
+const FRAME_HEIGHT: u32 = 48;
+
+/// Kind of suspicious change between snapshots
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FailureWarningKind {
+    /// Image has not changed for several snapshots while printing
+    Frozen,
+    /// Image changed much more than normal print movement would explain
+    Chaotic,
+}
+
+/// Metrics computed for one snapshot
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct FrameMetrics {
+    /// Mean absolute pixel difference to the previous frame (0.0 - 100.0)
+    pub difference: Option<f64>,
+    /// Shannon entropy of the grayscale histogram in bits (0.0 - 8.0)
+    pub entropy: f64,
+}
+
+/// "Print may have failed" warning
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrintFailureWarning {
+    /// Host IP address
+    pub host: String,
+    /// Warning kind
+    pub kind: FailureWarningKind,
+    /// Human readable explanation
+    pub message: String,
+    /// Metrics of the snapshot that triggered the warning
+    pub metrics: FrameMetrics,
+    /// Warning time (RFC 3339)
+    pub timestamp: String,
+}
+
+/// Per-host detector state
+#[derive(Debug, Default)]
+struct HostDetectorState {
+    last_frame: Option<Vec<u8>>,
+    last_entropy: Option<f64>,
+    last_capture: Option<Instant>,
+    static_frames: u32,
+    warned: bool,
+}
+
+/// Snapshot differencing detector for all monitored hosts
+#[derive(Debug, Default)]
+pub struct FailureDetector {
+    hosts: HashMap<String, HostDetectorState>,
+}
+
+impl FailureDetector {
+    /// Creates an empty detector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks whether a new snapshot is due for the host
+    pub fn should_capture(&self, host: &str, interval_seconds: u64) -> bool {
+        match self.hosts.get(host).and_then(|s| s.last_capture) {
+            Some(last) => last.elapsed() >= Duration::from_secs(interval_seconds),
+            None => true,
+        }
+    }
+
+    /// Forgets the host history, e.g. when it is no longer printing
+    pub fn reset(&mut self, host: &str) {
+        self.hosts.remove(host);
+    }
+
+    /// Analyzes a new snapshot for the host
+    ///
+    /// # Arguments
+    /// * `host` - Host IP address
+    /// * `frame` - Snapshot reduced by `decode_frame`
+    /// * `settings` - Detection thresholds
+    ///
+    /// # Returns
+    /// * Frame metrics and a warning if the heuristics triggered
+    pub fn analyze(
+        &mut self,
+        host: &str,
+        frame: Vec<u8>,
+        settings: &FailureDetectionSettings,
+    ) -> (FrameMetrics, Option<PrintFailureWarning>) {
+        let entropy = frame_entropy(&frame);
+
+        let state = self.hosts.entry(host.to_string()).or_default();
+        state.last_capture = Some(Instant::now());
+
+        let difference = state.last_frame.as_ref().map(|prev| frame_difference(prev, &frame));
+        let entropy_jump = state.last_entropy.map(|prev| (entropy - prev).abs()).unwrap_or(0.0);
+        state.last_frame = Some(frame);
+        state.last_entropy = Some(entropy);
+
+        let metrics = FrameMetrics { difference, entropy };
+        let difference = match difference {
+            Some(d) => d,
+            None => return (metrics, None), // First frame, nothing to compare yet
+        };
+
+        let kind = if difference >= settings.chaos_threshold || entropy_jump >= settings.entropy_jump_threshold {
+            state.static_frames = 0;
+            Some(FailureWarningKind::Chaotic)
+        } else if difference <= settings.frozen_threshold {
+            state.static_frames += 1;
+            if state.static_frames >= settings.frozen_frame_count {
+                Some(FailureWarningKind::Frozen)
+            } else {
+                None
+            }
+        } else {
+            state.static_frames = 0;
+            None
+        };
+
+        // Only warn once until the image behaves normally again
+        let kind = match kind {
+            Some(kind) if !state.warned => {
+                state.warned = true;
+                kind
+            }
+            Some(_) => return (metrics, None),
+            None => {
+                state.warned = false;
+                return (metrics, None);
+            }
+        };
+
+        let message = match kind {
+            FailureWarningKind::Frozen => tf("notify.print_failure.frozen", &[
+                ("snapshots", &state.static_frames),
+                ("difference", &format!("{:.1}", difference)),
+            ]),
+            FailureWarningKind::Chaotic => tf("notify.print_failure.chaotic", &[
+                ("difference", &format!("{:.1}", difference)),
+                ("entropy", &format!("{:.2}", entropy_jump)),
+            ]),
+        };
+
+        (metrics, Some(PrintFailureWarning {
+            host: host.to_string(),
+            kind,
+            message,
+            metrics,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }))
+    }
+}
+
+/// Decodes an image and reduces it to a small grayscale frame
+///
+/// Decoding takes a while for camera-sized images, so async callers run it
+/// on a blocking thread.
+pub fn decode_frame(image_data: &[u8]) -> Result<Vec<u8>, String> {
+    let image = image::load_from_memory(image_data)
+        .map_err(|e| format!("Failed to decode snapshot: {}", e))?;
+    let gray = image.to_luma8();
+    let small = image::imageops::resize(&gray, FRAME_WIDTH, FRAME_HEIGHT, FilterType::Triangle);
+    Ok(small.into_raw())
+}
+
+/// Mean absolute difference between two frames in percent of full scale
+fn frame_difference(a: &[u8], b: &[u8]) -> f64 {
+    if a.is_empty() || a.len() != b.len() {
+        return 100.0;
+    }
+    let total: u64 = a.iter().zip(b.iter())
+        .map(|(x, y)| (*x as i16 - *y as i16).unsigned_abs() as u64)
+        .sum();
+    total as f64 / a.len() as f64 / 255.0 * 100.0
+}
+
+/// Shannon entropy of the grayscale histogram in bits
+fn frame_entropy(frame: &[u8]) -> f64 {
+    if frame.is_empty() {
+        return 0.0;
+    }
+    let mut histogram = [0u32; 256];
+    for pixel in frame {
+        histogram[*pixel as usize] += 1;
+    }
+    let total = frame.len() as f64;
+    histogram.iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let p = *count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
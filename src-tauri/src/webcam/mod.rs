@@ -0,0 +1,15 @@
+//! Webcam functionality modules
+//!
+//! This module contains webcam snapshot fetching, image analysis,
+//! archival and error capture helpers shared by the Telegram bot and the
+//! background monitor.
+
+pub mod snapshot;
+pub mod failure_detector;
+pub mod archive;
+pub mod error_capture;
+
+pub use snapshot::*;
+pub use failure_detector::*;
+pub use archive::*;
+pub use error_capture::*;
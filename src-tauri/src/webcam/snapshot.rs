@@ -0,0 +1,199 @@
+//! Webcam snapshot fetching
+//!
+//! This module provides functions for downloading still images from
+//! the webcam served next to Moonraker (crowsnest / mjpg-streamer).
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+use image::imageops::FilterType;
+use image::{GenericImageView, ImageOutputFormat};
+use tokio::sync::Mutex;
+use crate::api::client::{build_web_url, host_connection, read_body_limited, with_host_headers};
+use crate::network::ip_utils::split_host_port;
+use crate::api::host_limiter::acquire_host_permit;
+use crate::models::config::{MAX_RESPONSE_BYTES, SNAPSHOT_CACHE_TTL_MS, SNAPSHOT_JPEG_QUALITY, SNAPSHOT_MAX_DIMENSION};
+
+/// Cache slot of one host; its lock is held while the snapshot is being fetched
+type SnapshotSlot = Arc<Mutex<Option<CachedSnapshot>>>;
+
+/// Per-host slots keyed by IP address
+static SNAPSHOT_CACHE: StdMutex<Option<HashMap<String, SnapshotSlot>>> = StdMutex::new(None);
+
+/// Prepared snapshot with the time it was fetched
+struct CachedSnapshot {
+    fetched_at: Instant,
+    image_data: Vec<u8>,
+}
+
+/// Builds the snapshot URL for a host
+///
+/// # Arguments
+/// * `ip_address` - Host IP address
+///
+/// # Returns
+/// * The host's snapshot URL template when configured, otherwise the default
+///   crowsnest / mjpg-streamer URL
+pub fn build_snapshot_url(ip_address: &str) -> String {
+    let template = host_connection(ip_address).and_then(|c| c.snapshot_url);
+    webcam_url(ip_address, template, "webcam/?action=snapshot")
+}
+
+/// Builds the stream URL for a host, see [`build_snapshot_url`]
+pub fn build_stream_url(ip_address: &str) -> String {
+    let template = host_connection(ip_address).and_then(|c| c.stream_url);
+    webcam_url(ip_address, template, "webcam/?action=stream")
+}
+
+/// Fills the `{ip}` placeholder of a webcam URL template, falling back to a
+/// path on the host's web server without a template
+fn webcam_url(ip_address: &str, template: Option<String>, default_path: &str) -> String {
+    match template.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        Some(template) => template.replace("{ip}", split_host_port(ip_address).0),
+        None => build_web_url(ip_address, default_path),
+    }
+}
+
+/// Adds the host's webcam credentials to a request
+///
+/// # Arguments
+/// * `request` - Request to extend
+/// * `ip_address` - Host the request is sent to
+///
+/// # Returns
+/// * Request with a bearer token or basic auth, when configured
+pub fn with_webcam_auth(request: reqwest::RequestBuilder, ip_address: &str) -> reqwest::RequestBuilder {
+    let Some(auth) = host_connection(ip_address).and_then(|c| c.webcam_auth) else {
+        return request;
+    };
+
+    if let Some(token) = auth.token.as_deref().filter(|t| !t.is_empty()) {
+        request.bearer_auth(token)
+    } else if let Some(username) = auth.username.as_deref().filter(|u| !u.is_empty()) {
+        request.basic_auth(username, auth.password.as_deref())
+    } else {
+        request
+    }
+}
+
+/// Downloads a webcam snapshot from a host
+///
+/// # Arguments
+/// * `ip_address` - Host IP address
+/// * `client` - HTTP client to use for the request
+///
+/// # Returns
+/// * Raw image bytes (usually JPEG)
+pub async fn fetch_snapshot(ip_address: &str, client: &reqwest::Client) -> Result<Vec<u8>, String> {
+    let url = build_snapshot_url(ip_address);
+
+    let _permit = acquire_host_permit(ip_address).await;
+    let response = with_webcam_auth(with_host_headers(client.get(&url), ip_address), ip_address)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to request image: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    let image_data = read_body_limited(response, MAX_RESPONSE_BYTES)
+        .await
+        .map_err(|e| format!("Failed to read image data: {}", e))?;
+
+    Ok(image_data)
+}
+
+/// Gets a snapshot sized for sending, reusing one fetched in the last few seconds
+///
+/// A notification broadcast to several users, or several notifications of
+/// the same host, then cost one webcam request and one re-encode.
+///
+/// # Arguments
+/// * `ip_address` - Host IP address
+/// * `client` - HTTP client to use for the request
+///
+/// # Returns
+/// * Image bytes, downscaled to `SNAPSHOT_MAX_DIMENSION` when larger
+pub async fn get_cached_snapshot(ip_address: &str, client: &reqwest::Client) -> Result<Vec<u8>, String> {
+    let slot = {
+        let mut cache = SNAPSHOT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        cache.get_or_insert_with(HashMap::new)
+            .entry(ip_address.to_string())
+            .or_default()
+            .clone()
+    };
+    let mut cached = slot.lock().await;
+
+    if let Some(entry) = cached.as_ref() {
+        if entry.fetched_at.elapsed() < Duration::from_millis(SNAPSHOT_CACHE_TTL_MS) {
+            return Ok(entry.image_data.clone());
+        }
+    }
+
+    let image_data = fetch_snapshot(ip_address, client).await?;
+    let image_data = tokio::task::spawn_blocking(move || downscale_snapshot(image_data, SNAPSHOT_MAX_DIMENSION))
+        .await
+        .map_err(|e| format!("Failed to process image: {}", e))?;
+
+    *cached = Some(CachedSnapshot {
+        fetched_at: Instant::now(),
+        image_data: image_data.clone(),
+    });
+    Ok(image_data)
+}
+
+/// Shrinks an image so its longest side is at most `max_dimension` and re-encodes it as JPEG
+///
+/// Images that are already small enough, or cannot be decoded, are returned unchanged.
+pub fn downscale_snapshot(image_data: Vec<u8>, max_dimension: u32) -> Vec<u8> {
+    let image = match image::load_from_memory(&image_data) {
+        Ok(image) => image,
+        Err(_) => return image_data,
+    };
+    let (width, height) = image.dimensions();
+    if width.max(height) <= max_dimension {
+        return image_data;
+    }
+
+    let resized = image.resize(max_dimension, max_dimension, FilterType::Triangle).to_rgb8();
+    let mut encoded = Cursor::new(Vec::new());
+    match image::DynamicImage::ImageRgb8(resized).write_to(&mut encoded, ImageOutputFormat::Jpeg(SNAPSHOT_JPEG_QUALITY)) {
+        Ok(()) => encoded.into_inner(),
+        Err(_) => image_data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = Cursor::new(Vec::new());
+        image::DynamicImage::new_rgb8(width, height)
+            .write_to(&mut data, ImageOutputFormat::Png)
+            .unwrap();
+        data.into_inner()
+    }
+
+    #[test]
+    fn large_snapshots_are_downscaled_keeping_aspect_ratio() {
+        let small = downscale_snapshot(png(1920, 1080), 1280);
+        let image = image::load_from_memory(&small).unwrap();
+        assert_eq!(image.dimensions(), (1280, 720));
+    }
+
+    #[test]
+    fn webcam_url_templates_get_the_host_address() {
+        let template = Some("http://{ip}:1984/api/frame.jpeg?src=cam".to_string());
+        assert_eq!(webcam_url("192.168.1.50:7126", template, "webcam/?action=snapshot"), "http://192.168.1.50:1984/api/frame.jpeg?src=cam");
+        assert_eq!(webcam_url("192.168.1.50", Some(" ".to_string()), "webcam/?action=snapshot"), "http://192.168.1.50/webcam/?action=snapshot");
+    }
+
+    #[test]
+    fn small_snapshots_are_left_alone() {
+        let original = png(640, 480);
+        assert_eq!(downscale_snapshot(original.clone(), 1280), original);
+    }
+}
@@ -0,0 +1,312 @@
+//! Mock Moonraker server for integration tests
+//!
+//! Each `MockMoonraker` is a small axum server on a random local port that
+//! answers the endpoints the scanner uses with configurable JSON payloads.
+//! The server is registered as a host connection override, so the code under
+//! test reaches it through its normal host name based URL building.
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::{json, Value};
+
+use moonrakerhostscanner_lib::api::client::set_host_connections;
+use moonrakerhostscanner_lib::models::config::HostConnectionSettings;
+
+/// Connection overrides of every mock started in this test binary
+static REGISTERED: Mutex<Option<HashMap<String, HostConnectionSettings>>> = Mutex::new(None);
+
+/// Payloads served by a mock and the requests it received
+pub struct MockState {
+    /// `server/info` response (None: respond with HTTP 503)
+    pub server_info: Option<Value>,
+    /// `printer/info` response (None: respond with HTTP 503)
+    pub printer_info: Option<Value>,
+    /// `api/printer` response (None: respond with HTTP 503)
+    pub api_printer: Option<Value>,
+    /// `printer/objects/query` response (None: respond with HTTP 503)
+    pub objects: Option<Value>,
+    /// `printer/objects/list` response (None: respond with HTTP 503)
+    pub objects_list: Option<Value>,
+    /// "METHOD path?query" of every received request
+    pub requests: Vec<String>,
+    /// User-Agent header of every received request
+    pub user_agents: Vec<String>,
+}
+
+/// Running mock Moonraker instance
+pub struct MockMoonraker {
+    /// Local address the server listens on
+    pub addr: SocketAddr,
+    /// Host name to pass to the scanner functions
+    pub host: String,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockMoonraker {
+    /// Starts a mock of an idle, ready printer
+    pub async fn start() -> Self {
+        let state = Arc::new(Mutex::new(MockState {
+            server_info: Some(server_info("ready")),
+            printer_info: Some(printer_info("voron-test")),
+            api_printer: Some(api_printer(flags_standby())),
+            objects: Some(objects_idle()),
+            objects_list: None,
+            requests: Vec::new(),
+            user_agents: Vec::new(),
+        }));
+
+        let app = Router::new()
+            .route("/server/info", get(serve_server_info))
+            .route("/printer/info", get(serve_printer_info))
+            .route("/api/printer", get(serve_api_printer))
+            .route("/printer/objects/query", get(serve_objects))
+            .route("/printer/objects/list", get(serve_objects_list))
+            .route("/printer/print/start", post(serve_action))
+            .route("/printer/print/pause", post(serve_action))
+            .route("/printer/print/resume", post(serve_action))
+            .route("/printer/print/cancel", post(serve_action))
+            .route("/printer/emergency_stop", post(serve_action))
+            .route("/printer/gcode/script", post(serve_action))
+            .layer(axum::middleware::from_fn_with_state(state.clone(), record_request))
+            .with_state(state.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind mock server");
+        let addr = listener.local_addr().expect("mock server address");
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.ok();
+        });
+
+        let host = format!("mock-moonraker-{}", addr.port());
+        register_host(&host, addr);
+
+        Self { addr, host, state }
+    }
+
+    /// Changes the served payloads
+    pub fn update(&self, change: impl FnOnce(&mut MockState)) {
+        change(&mut self.state.lock().unwrap());
+    }
+
+    /// Gets the requests received so far
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Gets the User-Agent headers of the requests received so far
+    pub fn user_agents(&self) -> Vec<String> {
+        self.state.lock().unwrap().user_agents.clone()
+    }
+}
+
+/// Points a host name at a mock server, keeping the other mocks registered
+fn register_host(host: &str, addr: SocketAddr) {
+    let mut registered = REGISTERED.lock().unwrap();
+    let connections = registered.get_or_insert_with(HashMap::new);
+    connections.insert(host.to_string(), HostConnectionSettings {
+        base_url: Some(format!("http://{}", addr)),
+        ..Default::default()
+    });
+    set_host_connections(connections.clone());
+}
+
+async fn record_request(
+    State(state): State<Arc<Mutex<MockState>>>,
+    request: Request,
+    next: axum::middleware::Next,
+) -> Response {
+    let uri = request.uri();
+    let line = match uri.query() {
+        Some(query) => format!("{} {}?{}", request.method(), uri.path(), query),
+        None => format!("{} {}", request.method(), uri.path()),
+    };
+    let user_agent = request
+        .headers()
+        .get(axum::http::header::USER_AGENT)
+        .and_then(|ua| ua.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    {
+        let mut state = state.lock().unwrap();
+        state.requests.push(line);
+        state.user_agents.push(user_agent);
+    }
+    next.run(request).await
+}
+
+fn respond(payload: Option<Value>) -> Response {
+    match payload {
+        Some(value) => Json(value).into_response(),
+        None => (StatusCode::SERVICE_UNAVAILABLE, "Service Unavailable").into_response(),
+    }
+}
+
+async fn serve_server_info(State(state): State<Arc<Mutex<MockState>>>) -> Response {
+    respond(state.lock().unwrap().server_info.clone())
+}
+
+async fn serve_printer_info(State(state): State<Arc<Mutex<MockState>>>) -> Response {
+    respond(state.lock().unwrap().printer_info.clone())
+}
+
+async fn serve_api_printer(State(state): State<Arc<Mutex<MockState>>>) -> Response {
+    respond(state.lock().unwrap().api_printer.clone())
+}
+
+async fn serve_objects(State(state): State<Arc<Mutex<MockState>>>) -> Response {
+    respond(state.lock().unwrap().objects.clone())
+}
+
+async fn serve_objects_list(State(state): State<Arc<Mutex<MockState>>>) -> Response {
+    respond(state.lock().unwrap().objects_list.clone())
+}
+
+async fn serve_action() -> Response {
+    Json(json!({ "result": "ok" })).into_response()
+}
+
+/// `server/info` payload as sent by Moonraker 0.9
+pub fn server_info(klippy_state: &str) -> Value {
+    json!({
+        "result": {
+            "klippy_connected": klippy_state != "disconnected",
+            "klippy_state": klippy_state,
+            "components": ["database", "file_manager", "klippy_apis", "machine", "data_store"],
+            "failed_components": [],
+            "registered_directories": ["config", "logs", "gcodes"],
+            "warnings": [],
+            "websocket_count": 2,
+            "moonraker_version": "v0.9.3-1-g0f2ea4c",
+            "api_version": [1, 5, 0],
+            "api_version_string": "1.5.0"
+        }
+    })
+}
+
+/// `printer/info` payload
+pub fn printer_info(hostname: &str) -> Value {
+    json!({
+        "result": {
+            "state": "ready",
+            "state_message": "Printer is ready",
+            "hostname": hostname,
+            "software_version": "v0.12.0-85-gd785b396",
+            "cpu_info": "4 core ARMv7 Processor rev 4 (v7l)",
+            "klipper_path": "/home/pi/klipper",
+            "python_path": "/home/pi/klippy-env/bin/python",
+            "log_file": "/home/pi/printer_data/logs/klippy.log",
+            "config_file": "/home/pi/printer_data/config/printer.cfg"
+        }
+    })
+}
+
+/// OctoPrint compatible `api/printer` payload with the given state flags
+pub fn api_printer(flags: Value) -> Value {
+    json!({
+        "temperature": {
+            "tool0": { "actual": 22.5, "target": 0.0, "offset": 0 },
+            "bed": { "actual": 21.9, "target": 0.0, "offset": 0 }
+        },
+        "state": {
+            "text": "Operational",
+            "flags": flags
+        }
+    })
+}
+
+/// Flags of an idle printer
+pub fn flags_standby() -> Value {
+    flags(&[("operational", true), ("ready", true)])
+}
+
+/// Flags of a printing printer
+pub fn flags_printing() -> Value {
+    flags(&[("operational", true), ("printing", true), ("ready", true)])
+}
+
+/// Full flag object with the given flags set
+pub fn flags(set: &[(&str, bool)]) -> Value {
+    let mut value = json!({
+        "operational": false,
+        "paused": false,
+        "printing": false,
+        "cancelling": false,
+        "pausing": false,
+        "error": false,
+        "ready": false,
+        "closedOrError": false
+    });
+    for (name, flag) in set {
+        value[*name] = json!(flag);
+    }
+    value
+}
+
+/// `printer/objects/list` payload listing the given objects
+pub fn objects_list(objects: &[&str]) -> Value {
+    json!({ "result": { "objects": objects } })
+}
+
+/// `printer/objects/query` payload of an idle printer
+pub fn objects_idle() -> Value {
+    json!({
+        "result": {
+            "eventtime": 1234.56,
+            "status": {
+                "print_stats": {
+                    "filename": "",
+                    "total_duration": 0.0,
+                    "print_duration": 0.0,
+                    "filament_used": 0.0,
+                    "state": "standby",
+                    "message": "",
+                    "info": { "total_layer": null, "current_layer": null }
+                },
+                "virtual_sdcard": {
+                    "file_path": null,
+                    "progress": 0.0,
+                    "is_active": false,
+                    "file_position": 0,
+                    "file_size": 0
+                }
+            }
+        }
+    })
+}
+
+/// `printer/objects/query` payload of a print at the given progress (0.0 - 1.0)
+pub fn objects_printing(filename: &str, progress: f64) -> Value {
+    json!({
+        "result": {
+            "eventtime": 5678.9,
+            "status": {
+                "print_stats": {
+                    "filename": filename,
+                    "total_duration": 3720.5,
+                    "print_duration": 3600.0,
+                    "filament_used": 1523.7,
+                    "state": "printing",
+                    "message": "",
+                    "info": { "total_layer": 120, "current_layer": 42 }
+                },
+                "virtual_sdcard": {
+                    "file_path": format!("/home/pi/printer_data/gcodes/{}", filename),
+                    "progress": progress,
+                    "is_active": true,
+                    "file_position": 1048576,
+                    "file_size": 4194304
+                },
+                "toolhead": { "position": [100.0, 120.0, 8.4, 1523.7], "homed_axes": "xyz" },
+                "extruder": { "temperature": 215.1, "target": 215.0, "power": 0.42, "can_extrude": true }
+            }
+        }
+    })
+}
@@ -0,0 +1,33 @@
+//! Per-host request limiting tests against a mock Moonraker server
+//!
+//! Kept in their own test binary because the limit is process wide.
+
+mod common;
+
+use std::time::Duration;
+
+use common::{objects_list, objects_printing, MockMoonraker};
+use moonrakerhostscanner_lib::api::host_limiter::set_host_request_concurrency;
+use moonrakerhostscanner_lib::api::print_info::get_print_info;
+
+#[tokio::test]
+async fn get_print_info_completes_with_one_request_per_host() {
+    set_host_request_concurrency(1);
+    let mock = MockMoonraker::start().await;
+    mock.update(|state| {
+        state.objects = Some(objects_printing("benchy.gcode", 0.5));
+        state.objects_list = Some(objects_list(&["print_stats", "virtual_sdcard", "toolhead", "extruder", "extruder1"]));
+    });
+
+    let info = tokio::time::timeout(Duration::from_secs(10), get_print_info(&mock.host, None))
+        .await
+        .expect("get_print_info must not wait for its own permit")
+        .expect("request")
+        .expect("print info");
+
+    assert_eq!(info.filename, "benchy.gcode");
+    assert!(mock
+        .requests()
+        .iter()
+        .any(|r| r == "GET /printer/objects/query?print_stats&virtual_sdcard&toolhead&extruder&extruder1"));
+}
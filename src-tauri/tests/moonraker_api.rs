@@ -0,0 +1,329 @@
+//! Scanner and API parsing tests against a mock Moonraker server
+
+mod common;
+
+use common::{api_printer, flags, flags_printing, flags_standby, objects_idle, objects_printing, server_info, MockMoonraker};
+use moonrakerhostscanner_lib::api::print_info::get_print_info;
+use moonrakerhostscanner_lib::diagnostics::get_diagnostics;
+use moonrakerhostscanner_lib::fleet::get_fleet_overview;
+use moonrakerhostscanner_lib::api::printer::control_printer_with_string;
+use moonrakerhostscanner_lib::models::{HostInfo, HostWarningKind, KlippyState, PrinterState};
+use moonrakerhostscanner_lib::network::scanner::{check_host_status, scan_host};
+use serde_json::json;
+
+#[tokio::test]
+async fn scan_host_reads_hostname_version_and_state() {
+    let mock = MockMoonraker::start().await;
+
+    let host = scan_host(&mock.host).await.expect("mock host should be found");
+
+    assert_eq!(host.hostname, "voron-test");
+    assert_eq!(host.original_hostname, "voron-test");
+    assert_eq!(host.ip_address, mock.host);
+    assert_eq!(host.status, "online");
+    assert_eq!(host.moonraker_version.as_deref(), Some("v0.9.3-1-g0f2ea4c"));
+    assert_eq!(host.klippy_state, Some(KlippyState::Ready));
+    assert_eq!(host.printer_state, Some(PrinterState::Standby));
+    assert_eq!(host.failed_attempts, Some(0));
+}
+
+#[tokio::test]
+async fn scan_host_falls_back_to_address_without_printer_info() {
+    let mock = MockMoonraker::start().await;
+    mock.update(|state| state.printer_info = None);
+
+    let host = scan_host(&mock.host).await.expect("mock host should be found");
+
+    assert_eq!(host.hostname, mock.host);
+}
+
+#[tokio::test]
+async fn scan_host_ignores_hosts_without_server_info() {
+    let mock = MockMoonraker::start().await;
+    mock.update(|state| state.server_info = None);
+
+    assert!(scan_host(&mock.host).await.is_none());
+}
+
+#[tokio::test]
+async fn scan_host_accepts_unparseable_server_info() {
+    let mock = MockMoonraker::start().await;
+    // Older Moonraker releases lack several of the fields
+    mock.update(|state| state.server_info = Some(json!({ "result": { "klippy_state": "ready" } })));
+
+    let host = scan_host(&mock.host).await.expect("mock host should be found");
+
+    assert_eq!(host.moonraker_version.as_deref(), Some("unknown"));
+}
+
+#[tokio::test]
+async fn check_host_status_reports_printing() {
+    let mock = MockMoonraker::start().await;
+    mock.update(|state| state.api_printer = Some(api_printer(flags_printing())));
+
+    let status = check_host_status(&mock.host).await;
+
+    assert!(status.success);
+    assert_eq!(status.status, "online");
+    assert_eq!(status.printer_state, Some(PrinterState::Printing));
+    assert!(status.printer_flags.expect("flags").printing);
+}
+
+#[tokio::test]
+async fn check_host_status_reports_moonraker_warnings_and_failed_components() {
+    let mock = MockMoonraker::start().await;
+    mock.update(|state| {
+        let mut info = server_info("ready");
+        info["result"]["warnings"] = json!(["Unparsed config option 'foo' in section [server]"]);
+        info["result"]["failed_components"] = json!(["timelapse"]);
+        state.server_info = Some(info);
+    });
+
+    let status = check_host_status(&mock.host).await;
+
+    let warnings: Vec<(HostWarningKind, &str)> = status.warnings.iter().map(|w| (w.kind, w.message.as_str())).collect();
+    assert_eq!(warnings, vec![
+        (HostWarningKind::Moonraker, "Unparsed config option 'foo' in section [server]"),
+        (HostWarningKind::FailedComponent, "timelapse"),
+    ]);
+}
+
+#[tokio::test]
+async fn diagnostics_list_missing_klippy_requirements_with_remediation() {
+    let mock = MockMoonraker::start().await;
+    mock.update(|state| {
+        let mut info = server_info("ready");
+        info["result"]["failed_components"] = json!(["update_manager"]);
+        info["result"]["missing_klippy_requirements"] = json!(["virtual_sdcard"]);
+        state.server_info = Some(info);
+    });
+    let host = HostInfo {
+        ip_address: mock.host.clone(),
+        hostname: "voron-test".to_string(),
+        ..HostInfo::default()
+    };
+
+    let diagnostics = get_diagnostics(&[host]).await;
+
+    assert!(diagnostics[0].reachable);
+    let issues: Vec<(HostWarningKind, &str)> = diagnostics[0].issues.iter().map(|i| (i.kind, i.name.as_str())).collect();
+    assert_eq!(issues, vec![
+        (HostWarningKind::FailedComponent, "update_manager"),
+        (HostWarningKind::MissingKlippyRequirement, "virtual_sdcard"),
+    ]);
+    assert!(diagnostics[0].issues[1].remediation.contains("[virtual_sdcard]"));
+}
+
+#[tokio::test]
+async fn check_host_status_reports_error_flags() {
+    let mock = MockMoonraker::start().await;
+    mock.update(|state| {
+        state.server_info = Some(server_info("shutdown"));
+        state.api_printer = Some(api_printer(flags(&[("error", true), ("closedOrError", true)])));
+    });
+
+    let status = check_host_status(&mock.host).await;
+
+    assert!(status.success);
+    assert_eq!(status.klippy_state, Some(KlippyState::Shutdown));
+    assert_eq!(status.printer_state, Some(PrinterState::Error));
+}
+
+#[tokio::test]
+async fn check_host_status_includes_klippy_error_message() {
+    let mock = MockMoonraker::start().await;
+    mock.update(|state| {
+        state.server_info = Some(server_info("shutdown"));
+        state.printer_info.as_mut().unwrap()["result"]["state"] = json!("shutdown");
+        state.printer_info.as_mut().unwrap()["result"]["state_message"] = json!("MCU 'mcu' shutdown: Timer too close");
+        state.api_printer = Some(api_printer(flags(&[("error", true), ("closedOrError", true)])));
+    });
+
+    let status = check_host_status(&mock.host).await;
+
+    assert_eq!(status.error_message.as_deref(), Some("MCU 'mcu' shutdown: Timer too close"));
+}
+
+#[tokio::test]
+async fn check_host_status_has_no_error_message_when_ready() {
+    let mock = MockMoonraker::start().await;
+
+    let status = check_host_status(&mock.host).await;
+
+    assert!(status.error_message.is_none());
+    assert!(!mock.requests().iter().any(|r| r == "GET /printer/info"));
+}
+
+#[tokio::test]
+async fn check_host_status_treats_disconnected_klippy_as_offline() {
+    let mock = MockMoonraker::start().await;
+    mock.update(|state| state.server_info = Some(server_info("disconnected")));
+
+    let status = check_host_status(&mock.host).await;
+
+    assert!(!status.success);
+    assert_eq!(status.status, "offline");
+    assert_eq!(status.device_status.as_deref(), Some("klippy_disconnected"));
+    assert!(status.printer_flags.is_none());
+    // Flags are not requested when Klippy is gone
+    assert!(!mock.requests().iter().any(|r| r.starts_with("GET /api/printer")));
+}
+
+#[tokio::test]
+async fn check_host_status_defaults_to_standby_on_partial_flags() {
+    let mock = MockMoonraker::start().await;
+    mock.update(|state| {
+        state.api_printer = Some(api_printer(json!({ "operational": true, "printing": true })));
+    });
+
+    let status = check_host_status(&mock.host).await;
+
+    assert!(status.success);
+    assert_eq!(status.printer_state, Some(PrinterState::Standby));
+}
+
+#[tokio::test]
+async fn check_host_status_reports_offline_when_api_fails() {
+    let mock = MockMoonraker::start().await;
+    mock.update(|state| state.server_info = None);
+
+    let status = check_host_status(&mock.host).await;
+
+    assert!(!status.success);
+    assert_eq!(status.status, "offline");
+    assert_eq!(status.printer_state, Some(PrinterState::Offline));
+}
+
+#[tokio::test]
+async fn get_print_info_reads_progress_and_layers() {
+    let mock = MockMoonraker::start().await;
+    mock.update(|state| state.objects = Some(objects_printing("benchy.gcode", 0.425)));
+
+    let info = get_print_info(&mock.host, None).await.expect("request").expect("print info");
+
+    assert_eq!(info.filename, "benchy.gcode");
+    assert_eq!(info.status, "printing");
+    assert_eq!(info.total_size, 4194304);
+    assert!((info.progress.progress - 42.5).abs() < 1e-9);
+    assert_eq!(info.progress.print_duration, 3600.0);
+    assert_eq!(info.progress.current_layer, Some(42));
+    assert_eq!(info.progress.total_layers, Some(120));
+    assert_eq!(info.filament_used, Some(1523.7));
+    assert!(mock
+        .requests()
+        .iter()
+        .any(|r| r == "GET /printer/objects/query?print_stats&virtual_sdcard&toolhead&extruder"));
+}
+
+#[tokio::test]
+async fn get_print_info_accepts_idle_printer() {
+    let mock = MockMoonraker::start().await;
+
+    let info = get_print_info(&mock.host, None).await.expect("request").expect("print info");
+
+    assert_eq!(info.status, "standby");
+    assert_eq!(info.progress.progress, 0.0);
+    assert_eq!(info.progress.current_layer, None);
+}
+
+#[tokio::test]
+async fn get_print_info_uses_explicit_port() {
+    let mock = MockMoonraker::start().await;
+    mock.update(|state| state.objects = Some(objects_printing("cube.gcode", 0.1)));
+
+    let info = get_print_info("127.0.0.1", Some(mock.addr.port()))
+        .await
+        .expect("request")
+        .expect("print info");
+
+    assert_eq!(info.filename, "cube.gcode");
+}
+
+#[tokio::test]
+async fn get_print_info_is_none_on_http_error() {
+    let mock = MockMoonraker::start().await;
+    mock.update(|state| state.objects = None);
+
+    assert!(get_print_info(&mock.host, None).await.expect("request").is_none());
+}
+
+#[tokio::test]
+async fn fleet_overview_counts_states_and_lists_jobs() {
+    let printing = MockMoonraker::start().await;
+    printing.update(|state| {
+        state.api_printer = Some(api_printer(flags_printing()));
+        state.objects = Some(objects_printing("benchy.gcode", 0.5));
+    });
+    let idle = MockMoonraker::start().await;
+
+    let hosts = vec![
+        scan_host(&printing.host).await.expect("printing host"),
+        scan_host(&idle.host).await.expect("idle host"),
+    ];
+    let overview = get_fleet_overview(&hosts).await;
+
+    assert_eq!(overview.total_hosts, 2);
+    assert_eq!(overview.status_counts.get(&PrinterState::Printing), Some(&1));
+    assert_eq!(overview.status_counts.get(&PrinterState::Standby), Some(&1));
+    assert_eq!(overview.jobs.len(), 1);
+    assert_eq!(overview.jobs[0].filename, "benchy.gcode");
+    assert_eq!(overview.jobs[0].remaining_seconds, Some(3600.0));
+    assert!(overview.errors.is_empty());
+    // The mock has no history component
+    assert_eq!(overview.history_hosts, 0);
+}
+
+#[tokio::test]
+async fn control_printer_posts_to_action_endpoints() {
+    let mock = MockMoonraker::start().await;
+
+    for action in ["pause", "resume", "cancel", "emergency_stop"] {
+        control_printer_with_string(&mock.host, action).await.expect(action);
+    }
+
+    let posts: Vec<String> = mock.requests().into_iter().filter(|r| r.starts_with("POST")).collect();
+    assert_eq!(posts, vec![
+        "POST /printer/print/pause",
+        "POST /printer/print/resume",
+        "POST /printer/print/cancel",
+        "POST /printer/emergency_stop",
+    ]);
+}
+
+#[tokio::test]
+async fn control_printer_rejects_unknown_actions() {
+    let mock = MockMoonraker::start().await;
+
+    assert!(control_printer_with_string(&mock.host, "explode").await.is_err());
+    assert!(mock.requests().is_empty());
+}
+
+#[tokio::test]
+async fn moonraker_requests_identify_the_app() {
+    let mock = MockMoonraker::start().await;
+
+    scan_host(&mock.host).await.expect("mock host should be found");
+
+    let user_agents = mock.user_agents();
+    assert!(!user_agents.is_empty());
+    let expected = format!("MoonrakerHostScanner/{} (", env!("CARGO_PKG_VERSION"));
+    assert!(user_agents.iter().all(|ua| ua.starts_with(&expected)), "{:?}", user_agents);
+}
+
+#[tokio::test]
+async fn check_host_status_reports_unsaved_config_changes() {
+    let mock = MockMoonraker::start().await;
+    mock.update(|state| {
+        state.server_info = Some(server_info("ready"));
+        state.api_printer = Some(api_printer(flags_standby()));
+        let mut objects = objects_idle();
+        objects["result"]["status"]["configfile"] = json!({ "save_config_pending": true });
+        state.objects = Some(objects);
+    });
+
+    let status = check_host_status(&mock.host).await;
+
+    assert!(status.success);
+    assert!(status.save_config_pending);
+    assert!(mock.requests().iter().any(|r| r.contains("configfile=save_config_pending")));
+}
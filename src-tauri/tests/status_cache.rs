@@ -0,0 +1,30 @@
+//! Host status cache tests against a mock Moonraker server
+//!
+//! Kept in their own test binary because the cache TTL is process wide.
+
+mod common;
+
+use common::MockMoonraker;
+use futures::future::join_all;
+use moonrakerhostscanner_lib::network::status_cache::{get_host_status, set_status_cache_ttl};
+
+/// Status requests the mock received
+fn status_requests(mock: &MockMoonraker) -> usize {
+    mock.requests().iter().filter(|r| r.as_str() == "GET /server/info").count()
+}
+
+#[tokio::test]
+async fn zero_ttl_still_shares_running_requests() {
+    set_status_cache_ttl(0);
+    let mock = MockMoonraker::start().await;
+
+    let lookups = join_all((0..5).map(|_| get_host_status(&mock.host))).await;
+
+    assert!(lookups.iter().all(|lookup| lookup.status.success));
+    assert_eq!(lookups.iter().filter(|lookup| lookup.latency_ms.is_some()).count(), 1);
+    assert_eq!(status_requests(&mock), 1);
+
+    // Nothing is kept once the request has finished
+    get_host_status(&mock.host).await;
+    assert_eq!(status_requests(&mock), 2);
+}
@@ -61,11 +61,14 @@ import {
   ChevronUp,
   Layers,
   Send,
+  Wrench,
+  Save,
 } from "lucide-react"
 import { useTranslation } from "@/lib/i18n"
 import { useUpdater } from "@/hooks/use-updater"
-import { useTelegramBot } from "@/hooks/useTelegramBot"
+import { useTelegramBot, type TelegramRole } from "@/hooks/useTelegramBot"
 import { useSmartNotifications } from "@/hooks/useSmartNotifications"
+import { useNetworkChange } from "@/hooks/useNetworkChange"
 
 /**
  * Network subnet configuration for scanning
@@ -103,8 +106,18 @@ interface HostInfo {
     ready: boolean
     closedOrError: boolean
   }
+  error_message?: string
   last_seen?: string
   failed_attempts?: number // Counter for consecutive failed attempts
+  maintenance?: boolean // Planned downtime, offline/error notifications are suppressed
+  web_port?: number // Mainsail/Fluidd port detected during the scan
+  printer_model?: { name?: string | null; kinematics?: string | null; build_volume?: [number, number, number] | null } | null
+  capabilities?: { exclude_object: boolean; timelapse: boolean; extruders: number; toolchanger?: boolean; heater_bed: boolean; part_fan: boolean; led?: string | null; sensors: boolean } | null // Features from the Klipper object list
+  mac_addresses?: string[] // MACs of the printer's interfaces
+  network_interface?: string | null // Interface the printer is reached through, e.g. "eth0"
+  alternate_addresses?: string[] // Other addresses of the same printer
+  warnings?: HostWarning[] // Moonraker warnings, failed components and important announcements
+  save_config_pending?: boolean // Klipper has changes not yet saved with SAVE_CONFIG
   print_progress?: number // Current print progress percentage (0-100)
   print_info?: {
     filename: string
@@ -114,6 +127,21 @@ interface HostInfo {
   order?: number // Order index for manual sorting
 }
 
+interface HostWarning {
+  kind: 'moonraker' | 'failed_component' | 'missing_klippy_requirement' | 'announcement'
+  message: string
+  url?: string | null
+}
+
+// Model name from printer.cfg, otherwise kinematics and build volume, e.g. "corexy 350×350×340"
+function printerModelLabel(host: HostInfo): string | null {
+  const model = host.printer_model
+  if (!model) return null
+  if (model.name) return model.name
+  const volume = model.build_volume?.map((v) => Math.round(v)).join("×")
+  return [model.kinematics, volume].filter(Boolean).join(" ") || null
+}
+
 interface MoonrakerServerInfo {
   result: {
     klippy_connected: boolean
@@ -254,7 +282,11 @@ export function NetworkScanner() {
   const { 
     status: telegramStatus, 
     users: telegramUsers, 
+    pendingRegistrations,
+    approveRegistration,
+    denyRegistration,
     registrationCode, 
+    registrationLink,
     registrationTimeLeft,
     hasToken,
     startBot, 
@@ -268,10 +300,16 @@ export function NetworkScanner() {
     saveToken,
     clearToken
   } = useTelegramBot(settings.telegram.enabled)
+  const [pendingRoles, setPendingRoles] = useState<Record<number, TelegramRole>>({})
+  const { change: networkChange, autoScan: networkAutoScan, dismiss: dismissNetworkChange } = useNetworkChange()
+  const [scanAfterSubnetUpdate, setScanAfterSubnetUpdate] = useState(false)
+  // Разрешение на системные уведомления (спрашивает только macOS)
+  const [notificationPermission, setNotificationPermission] = useState<string | null>(null)
   
   const { 
     getPrinterStatus: smartGetPrinterStatus,
     checkStatusChangeAndNotify: smartCheckStatusChangeAndNotify,
+    renderStatusChangeBody,
     resetHostTimeout,
     getHostNotificationState
   } = useSmartNotifications()
@@ -330,15 +368,15 @@ export function NetworkScanner() {
   }
 
   // Отправка системного уведомления
-  const sendNotification = async (title: string, body: string) => {
+  const sendNotification = async (title: string, body: string, host?: string) => {
     try {
       // Send system notification
-      await invokeTauri('send_system_notification_command', { title, body })
+      await invokeTauri('send_system_notification_command', { title, body, host })
       
       // Send Telegram notification if bot is enabled and running
       if (settings.telegram.enabled && telegramStatus.isRunning) {
         try {
-          await invokeTauri('send_telegram_notification', { title, body, hostIp: undefined })
+          await invokeTauri('send_telegram_notification', { title, body, hostIp: host })
         } catch (error) {
           console.error('Failed to send Telegram notification:', error);
         }
@@ -380,6 +418,21 @@ export function NetworkScanner() {
     }
   }, [])
 
+  // Проверяем, разрешены ли системные уведомления
+  useEffect(() => {
+    invokeTauri('check_notification_status_command')
+      .then(setNotificationPermission)
+      .catch(error => console.error('Failed to check notification permission:', error))
+  }, [])
+
+  const requestNotificationPermission = async () => {
+    try {
+      setNotificationPermission(await invokeTauri('request_notification_permission_command'))
+    } catch (error) {
+      console.error('Failed to request notification permission:', error)
+    }
+  }
+
   // Save settings to localStorage
   useEffect(() => {
     localStorage.setItem('networkScanner_settings', JSON.stringify(settings))
@@ -499,13 +552,17 @@ export function NetworkScanner() {
 
   useEffect(() => {
     const savedLanguage = localStorage.getItem("networkScanner_language")
-    if (savedLanguage && ["en", "ru"].includes(savedLanguage)) {
+    if (savedLanguage && ["en", "ru", "de"].includes(savedLanguage)) {
       setSettings((prev) => ({ ...prev, language: savedLanguage }))
     }
   }, [])
 
   useEffect(() => {
     localStorage.setItem("networkScanner_language", settings.language)
+    // Notifications and the Telegram bot are worded by the backend
+    invokeTauri('set_language_command', { language: settings.language }).catch((error) => {
+      console.error('Failed to set backend language:', error)
+    })
   }, [settings.language])
 
   // Auto-refresh webcam when dialog is open
@@ -625,15 +682,28 @@ export function NetworkScanner() {
             const newHosts: HostInfo[] = []
             let newHostIndex = 0
             
+            // Принтер с Ethernet и Wi-Fi мог быть сохранен под любым из своих адресов,
+            // а после смены DHCP-аренды - под старым адресом (узнаем по id или MAC)
+            const isSamePrinter = (newHost: any, host: HostInfo) =>
+              newHost.ip_address === host.ip_address
+              || (newHost.alternate_addresses ?? []).includes(host.ip_address)
+              || (result.relinked ?? []).some((r: any) => r.address === newHost.ip_address && r.previous_address === host.ip_address)
+            const mergedPrinters = new Set<string>()
+
             // Сначала обрабатываем все существующие хосты
             sortedPrevHosts.forEach(existingHost => {
-              const foundHost = result.hosts.find((newHost: any) => newHost.ip_address === existingHost.ip_address)
+              const foundHost = result.hosts.find((newHost: any) => isSamePrinter(newHost, existingHost))
               
               if (foundHost) {
+                // Дубликат того же принтера под другим адресом - удаляем
+                if (mergedPrinters.has(foundHost.ip_address)) {
+                  return
+                }
+                mergedPrinters.add(foundHost.ip_address)
                 // Хост найден при сканировании - обновляем его данные
                 updatedHosts.push({
                   ...foundHost,
-                  original_hostname: foundHost.hostname,
+                  original_hostname: foundHost.original_hostname || foundHost.hostname,
                   hostname: (existingHost.hostname !== existingHost.original_hostname)
                     ? existingHost.hostname // Сохраняем пользовательское имя, если оно было изменено
                     : foundHost.hostname, // Используем новое имя с сервера, если пользователь не изменял
@@ -654,13 +724,13 @@ export function NetworkScanner() {
             
             // Затем добавляем новые хосты, которых не было в списке
             result.hosts.forEach((newHost: any) => {
-              const existingHost = sortedPrevHosts.find(h => h.ip_address === newHost.ip_address)
+              const existingHost = sortedPrevHosts.find(h => isSamePrinter(newHost, h))
               
               if (!existingHost) {
                 // Новый хост - добавляем в конец
                 newHosts.push({
                   ...newHost,
-                  original_hostname: newHost.hostname,
+                  original_hostname: newHost.original_hostname || newHost.hostname,
                   failed_attempts: 0,
                   order: maxOrder + newHostIndex + 1
                 })
@@ -681,6 +751,44 @@ export function NetworkScanner() {
     }
   }
 
+  // Enables the subnets of newly joined networks (adding unknown ones), disables those of
+  // networks the machine has left and optionally scans once the subnet list is updated
+  const applyNetworkChange = (scan: boolean) => {
+    if (!networkChange) return
+    const joinedRanges = new Set(networkChange.joined.map(n => n.subnet))
+    const leftRanges = new Set(networkChange.left.map(n => n.subnet))
+    setSettings((prev) => {
+      const knownRanges = new Set(prev.subnets.map(s => s.range))
+      const added: Subnet[] = networkChange.joined
+        .filter(n => !knownRanges.has(n.subnet))
+        .map((n, index) => ({ id: `${Date.now()}-${index}`, range: n.subnet, name: n.interface, enabled: true }))
+      return {
+        ...prev,
+        subnets: [
+          ...prev.subnets.map(s =>
+            joinedRanges.has(s.range) ? { ...s, enabled: true } : leftRanges.has(s.range) ? { ...s, enabled: false } : s
+          ),
+          ...added,
+        ],
+      }
+    })
+    setScanAfterSubnetUpdate(scan)
+    dismissNetworkChange()
+  }
+
+  useEffect(() => {
+    if (scanAfterSubnetUpdate && !isScanning) {
+      setScanAfterSubnetUpdate(false)
+      handleScan()
+    }
+  }, [scanAfterSubnetUpdate, settings.subnets])
+
+  useEffect(() => {
+    if (networkChange && networkAutoScan && networkChange.joined.length > 0) {
+      applyNetworkChange(true)
+    }
+  }, [networkChange, networkAutoScan])
+
   const toggleRowExpansion = (hostId: string) => {
     setExpandedRows((prev) => {
       const newRows = new Set(prev)
@@ -751,6 +859,26 @@ export function NetworkScanner() {
     }
   }
 
+  const handleSaveConfig = async (host: HostInfo) => {
+    if (!confirm(`${t.saveConfigConfirm} (${host.hostname})`)) {
+      return
+    }
+    const buttonKey = `${host.id}-save_config`
+    setLoadingButtons(prev => new Set([...prev, buttonKey]))
+    try {
+      await invokeTauri('save_config_command', { host: host.ip_address })
+      setHosts(prev => prev.map(h => h.id === host.id ? { ...h, save_config_pending: false } : h))
+    } catch (error) {
+      alert(`SAVE_CONFIG failed: ${error}`)
+    } finally {
+      setLoadingButtons(prev => {
+        const newSet = new Set(prev)
+        newSet.delete(buttonKey)
+        return newSet
+      })
+    }
+  }
+
   const handleSSHConnect = async (host: HostInfo) => {
     try {
       await invokeTauri('open_ssh_connection_command', { 
@@ -816,6 +944,18 @@ export function NetworkScanner() {
     } : h)))
   }
 
+  const handleToggleMaintenance = (host: HostInfo) => {
+    const enabled = !host.maintenance
+    setHosts((prev) => prev.map((h) => (h.id === host.id ? { ...h, maintenance: enabled } : h)))
+    invokeTauri('set_host_maintenance_command', { ip: host.ip_address, enabled }).catch(() => {})
+  }
+
+  // Persist the name in the backend so Telegram and notifications use it too
+  const handleSaveHostname = (host: HostInfo) => {
+    const name = host.hostname === host.original_hostname ? null : host.hostname
+    invokeTauri('rename_host_command', { ip: host.ip_address, name }).catch(() => {})
+  }
+
   // Функция для проверки доступности шлюза подсети
   const checkSubnetGatewayAvailability = async (subnetRange: string): Promise<boolean> => {
     try {
@@ -918,6 +1058,17 @@ export function NetworkScanner() {
       setUnavailableSubnets(new Set(currentUnavailableSubnets))
       setSubnetFailureCounts(new Map(currentFailureCounts))
       
+      // Проверяем все доступные хосты одним параллельным запросом
+      let statusMap: Record<string, any> = {}
+      try {
+        const reachableIps = hosts
+          .filter(host => !currentUnavailableSubnets.has(host.subnet))
+          .map(host => host.ip_address)
+        statusMap = await invokeTauri('refresh_all_hosts_command', { ips: reachableIps })
+      } catch (error) {
+        console.error('Failed to refresh hosts status:', error)
+      }
+      
       // Обновляем хосты по одному
       for (let i = 0; i < hosts.length; i++) {
         const host = hosts[i]
@@ -939,7 +1090,7 @@ export function NetworkScanner() {
         
         try {
           // Проверяем состояние хоста через Tauri API
-          const result = await invokeTauri('check_host_status_command', { ip: host.ip_address })
+          const result = statusMap[host.ip_address] ?? await invokeTauri('check_host_status_command', { ip: host.ip_address })
           
           if (result.success) {
             // Хост ответил успешно - сбрасываем счетчик неудачных попыток
@@ -963,6 +1114,9 @@ export function NetworkScanner() {
               klippy_state: result.klippy_state || host.klippy_state,
               printer_state: result.printer_state || host.printer_state,
               printer_flags: result.printer_flags || host.printer_flags,
+              error_message: result.error_message,
+              warnings: result.warnings ?? [],
+              save_config_pending: result.save_config_pending ?? false,
               last_seen: new Date().toISOString(),
               failed_attempts: 0, // Сбрасываем счетчик неудачных попыток
               // Добавляем информацию о печати
@@ -1015,6 +1169,7 @@ export function NetworkScanner() {
               klippy_state: updatedHost.klippy_state,
               printer_state: updatedHost.printer_state,
               printer_flags: updatedHost.printer_flags,
+              error_message: updatedHost.error_message,
               last_seen: updatedHost.last_seen,
               failed_attempts: updatedHost.failed_attempts,
               // Добавляем информацию о печати
@@ -1080,7 +1235,9 @@ export function NetworkScanner() {
     const oldStatus = getPrinterStatus(oldHost)
     const newStatus = getPrinterStatus(newHost)
     
-    if (oldStatus !== newStatus) {
+    const suppressedByMaintenance = newHost.maintenance && (newStatus === 'offline' || newStatus === 'error')
+    
+    if (oldStatus !== newStatus && !suppressedByMaintenance) {
       const telegramNotifications = currentSettings.telegram?.notifications || {
         printing: true,
         paused: true,
@@ -1096,11 +1253,22 @@ export function NetworkScanner() {
       
       if (telegramNotificationEnabled && currentSettings.telegram?.enabled) {
         const title = `${t.networkScanner} - ${oldHost.hostname}`
-        const body = `${t.status}: ${t[statusKey as keyof typeof t] || newStatus}`
+        let body = `${t.status}: ${t[statusKey as keyof typeof t] || newStatus}`
+        if (newStatus === 'error' && newHost.error_message) {
+          body += `\n${newHost.error_message}`
+        }
         
-        invokeTauri('send_telegram_notification', { title, body, hostIp: oldHost.ip_address }).catch(error => {
-          console.error('Failed to send Telegram notification:', error);
-        });
+        renderStatusChangeBody(newHost, oldStatus, newStatus, body)
+          .then(text => invokeTauri('send_telegram_notification', {
+            title,
+            body: text,
+            hostIp: oldHost.ip_address,
+            // Отключение приходит с кнопками «отложить» и «подтвердить»
+            alert: newStatus === 'offline' ? 'offline' : undefined,
+          }))
+          .catch(error => {
+            console.error('Failed to send Telegram notification:', error);
+          });
       }
     }
   }
@@ -1120,8 +1288,9 @@ export function NetworkScanner() {
     const Icon = config.icon
 
     return (
-      <span className={`inline-flex items-center gap-1 px-2 py-1 rounded-full text-xs font-medium ${config.color}`}>
-        <Icon className="h-3 w-3" />
+      <span title={status === 'error' ? host?.error_message : undefined} className={`inline-flex items-center gap-1 px-2 py-1 rounded-full text-xs font-medium ${host?.maintenance ? "bg-purple-100 text-purple-800" : config.color}`}>
+        {host?.maintenance ? <Wrench className="h-3 w-3" /> : <Icon className="h-3 w-3" />}
+        {host?.maintenance && <span>{t.maintenance} ·</span>}
         {t[status as keyof typeof t] || status}
         {status === 'printing' && host?.print_progress && (
           <span className="ml-1 font-bold">
@@ -1548,6 +1717,22 @@ export function NetworkScanner() {
                         </div>
                       </div>
                           
+                      {/* Notification permission (macOS) */}
+                      {(notificationPermission === 'not_determined' || notificationPermission === 'denied') && (
+                        <div className="pt-4 border-t flex items-center justify-between gap-2">
+                          <p className="text-sm text-muted-foreground">
+                            {notificationPermission === 'denied'
+                              ? t.notificationPermissionDenied
+                              : t.notificationPermissionNotDetermined}
+                          </p>
+                          {notificationPermission === 'not_determined' && (
+                            <Button onClick={requestNotificationPermission} variant="outline">
+                              {t.allowNotifications}
+                            </Button>
+                          )}
+                        </div>
+                      )}
+
                       {/* Test notification buttons */}
                       <div className="pt-4 border-t space-y-2">
                         <div className="flex gap-2">
@@ -1739,9 +1924,53 @@ export function NetworkScanner() {
                                       <div className="text-2xl font-bold text-blue-600 dark:text-blue-400 font-mono bg-white dark:bg-gray-800 px-3 py-2 rounded border border-gray-300 dark:border-gray-600 text-center">
                                         {registrationCode}
                                       </div>
+                                      {registrationLink && (
+                                        <div className="flex flex-col items-center gap-2 pt-2">
+                                          <div className="text-sm text-blue-800 dark:text-blue-200">{t.telegramScanToRegister}</div>
+                                          <img
+                                            src={`data:image/svg+xml;utf8,${encodeURIComponent(registrationLink.qr_svg)}`}
+                                            alt={registrationLink.url}
+                                            className="w-48 h-48 bg-white rounded"
+                                          />
+                                          <span className="text-xs font-mono text-blue-700 dark:text-blue-300 break-all">{registrationLink.url}</span>
+                                        </div>
+                                      )}
                                     </div>
                                   </div>
                                 )}
+                                {pendingRegistrations.map((pending) => (
+                                  <div key={pending.user_id} className="flex items-center justify-between p-3 bg-yellow-50 dark:bg-yellow-950 rounded-lg border border-yellow-200 dark:border-yellow-800">
+                                    <div className="flex-1">
+                                      <div className="font-medium text-gray-900 dark:text-gray-100">
+                                        {pending.username ? `@${pending.username}` : `${pending.first_name || ''} ${pending.last_name || ''}`.trim() || `User ${pending.user_id}`}
+                                      </div>
+                                      <div className="text-xs text-gray-500 dark:text-gray-400 mt-1">
+                                        {t.telegramPendingApproval} • ID: {pending.user_id}
+                                      </div>
+                                    </div>
+                                    <div className="flex items-center space-x-2">
+                                      <Select
+                                        value={pendingRoles[pending.user_id] || pending.role}
+                                        onValueChange={(role) => setPendingRoles(prev => ({ ...prev, [pending.user_id]: role as TelegramRole }))}
+                                      >
+                                        <SelectTrigger className="w-28 h-8">
+                                          <SelectValue />
+                                        </SelectTrigger>
+                                        <SelectContent>
+                                          <SelectItem value="admin">{t.telegramRoleAdmin}</SelectItem>
+                                          <SelectItem value="operator">{t.telegramRoleOperator}</SelectItem>
+                                          <SelectItem value="viewer">{t.telegramRoleViewer}</SelectItem>
+                                        </SelectContent>
+                                      </Select>
+                                      <Button size="sm" onClick={() => approveRegistration(pending.user_id, pendingRoles[pending.user_id] || pending.role)}>
+                                        {t.approve}
+                                      </Button>
+                                      <Button variant="destructive" size="sm" onClick={() => denyRegistration(pending.user_id)}>
+                                        {t.deny}
+                                      </Button>
+                                    </div>
+                                  </div>
+                                ))}
                               </div>
                             </div>
                           )}
@@ -1774,6 +2003,11 @@ export function NetworkScanner() {
                                             ({user.first_name} {user.last_name || ''})
                                           </span>
                                         )}
+                                        {user.blocked_reason && (
+                                          <span title={user.blocked_reason} className="inline-flex items-center px-2 py-0.5 rounded-full text-xs font-medium bg-red-100 text-red-800">
+                                            {t.telegramUserBlocked}
+                                          </span>
+                                        )}
                                       </div>
                                       <div className="text-xs text-gray-500 dark:text-gray-400 mt-1">
                                         ID: {user.user_id} • Registered: {new Date(user.registered_at).toLocaleDateString()}
@@ -1919,6 +2153,28 @@ export function NetworkScanner() {
 
         
 
+        {/* Network change suggestion */}
+        {networkChange && networkChange.joined.length > 0 && (
+          <Card className="border-blue-500/50">
+            <CardContent className="pt-6">
+              <div className="flex items-center gap-4 flex-wrap">
+                <Network className="h-5 w-5 text-blue-500" />
+                <div className="flex-1 text-sm">
+                  <span className="font-medium">{t.networkChanged}</span>{" "}
+                  {networkChange.joined.map(n => `${n.subnet} (${n.interface})`).join(", ")}
+                </div>
+                <Button size="sm" onClick={() => applyNetworkChange(true)} disabled={isScanning}>
+                  <Wifi className="h-4 w-4 mr-2" />
+                  {t.networkChangedScan}
+                </Button>
+                <Button size="sm" variant="outline" onClick={() => dismissNetworkChange()}>
+                  {t.networkChangedDismiss}
+                </Button>
+              </div>
+            </CardContent>
+          </Card>
+        )}
+
         {/* Scan Controls and Batch Tasks */}
         <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
           {/* Scan Controls */}
@@ -2013,8 +2269,12 @@ export function NetworkScanner() {
                         <Input
                           value={host.hostname}
                           onChange={(e) => handleEditHostname(host.id, e.target.value)}
+                          onBlur={() => handleSaveHostname(host)}
                           className="border-none bg-transparent p-0 h-auto focus-visible:ring-0 flex-1"
                         />
+                        {printerModelLabel(host) && (
+                          <div className="text-xs text-muted-foreground">{printerModelLabel(host)}</div>
+                        )}
                       </TableCell>
                       <TableCell>
                         <Button
@@ -2024,8 +2284,36 @@ export function NetworkScanner() {
                         >
                           {host.ip_address}
                         </Button>
+                        {host.alternate_addresses && host.alternate_addresses.length > 0 && (
+                          <div className="text-xs text-muted-foreground">
+                            {t.alsoReachableAt} {host.alternate_addresses.join(', ')}
+                          </div>
+                        )}
+                      </TableCell>
+                      <TableCell>
+                        {getStatusBadge(getPrinterStatus(host), host)}
+                        {host.warnings && host.warnings.length > 0 && (
+                          <span
+                            title={host.warnings.map(w => w.message).join('\n')}
+                            className="ml-1 inline-flex items-center gap-1 text-xs text-amber-600"
+                          >
+                            <AlertTriangle className="h-3 w-3" />
+                            {host.warnings.length}
+                          </span>
+                        )}
+                        {host.save_config_pending && (
+                          <button
+                            type="button"
+                            title={t.saveConfigPending}
+                            onClick={() => handleSaveConfig(host)}
+                            disabled={loadingButtons.has(`${host.id}-save_config`)}
+                            className="ml-1 inline-flex items-center gap-1 text-xs text-amber-600 hover:underline"
+                          >
+                            <Save className="h-3 w-3" />
+                            SAVE_CONFIG
+                          </button>
+                        )}
                       </TableCell>
-                      <TableCell>{getStatusBadge(getPrinterStatus(host), host)}</TableCell>
                       <TableCell>
                         <Button
                           variant="ghost"
@@ -2073,10 +2361,20 @@ export function NetworkScanner() {
                         </div>
                       </TableCell>
                       <TableCell>
-                        <Button variant="destructive" size="sm" onClick={() => handleDeleteHost(host.id)}>
-                          <Trash2 className="h-4 w-4 mr-2" />
-                          {t.delete}
-                        </Button>
+                        <div className="flex items-center gap-1">
+                          <Button
+                            variant={host.maintenance ? "secondary" : "ghost"}
+                            size="sm"
+                            onClick={() => handleToggleMaintenance(host)}
+                            title={t.maintenance}
+                          >
+                            <Wrench className="h-4 w-4" />
+                          </Button>
+                          <Button variant="destructive" size="sm" onClick={() => handleDeleteHost(host.id)}>
+                            <Trash2 className="h-4 w-4 mr-2" />
+                            {t.delete}
+                          </Button>
+                        </div>
                       </TableCell>
                     </TableRow>
                     {expandedRows.has(host.id) && (
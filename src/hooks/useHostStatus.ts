@@ -21,9 +21,13 @@ export function useHostStatus() {
     setIsRefreshing(true)
 
     try {
+      const statusMap: Record<string, any> = await invokeTauri('refresh_all_hosts_command', {
+        ips: hosts.map(host => host.ip_address)
+      })
+
       const updatedHosts = await Promise.all(
         hosts.map(async (host) => {
-          const result = await checkHostStatus(host.ip_address)
+          const result = statusMap[host.ip_address] ?? null
           
           if (result?.success) {
             return {
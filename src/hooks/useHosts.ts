@@ -1,5 +1,22 @@
 import { useState, useEffect, useCallback, useMemo } from 'react'
 
+export interface PrinterModel {
+  name?: string | null
+  kinematics?: string | null
+  build_volume?: [number, number, number] | null
+}
+
+export interface PrinterCapabilities {
+  exclude_object: boolean
+  timelapse: boolean
+  extruders: number
+  toolchanger?: boolean
+  heater_bed: boolean
+  part_fan: boolean
+  led?: string | null
+  sensors: boolean
+}
+
 export interface HostInfo {
   id: string
   hostname: string
@@ -23,8 +40,18 @@ export interface HostInfo {
     ready: boolean
     closedOrError: boolean
   }
+  error_message?: string
   last_seen?: string
   failed_attempts?: number
+  maintenance?: boolean
+  web_port?: number
+  printer_model?: PrinterModel | null
+  capabilities?: PrinterCapabilities | null
+  mac_addresses?: string[]
+  network_interface?: string | null
+  alternate_addresses?: string[]
+  warnings?: { kind: string; message: string; url?: string | null }[]
+  save_config_pending?: boolean
 }
 
 export function useHosts() {
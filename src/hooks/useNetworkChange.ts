@@ -0,0 +1,53 @@
+import { useState, useEffect, useCallback } from 'react'
+import { tauriCommands } from '@/lib/tauri'
+
+// IPv4 network the machine has an address in
+export interface LocalNetwork {
+  interface: string
+  address: string
+  subnet: string // CIDR, e.g. "192.168.1.0/24"
+}
+
+// Networks joined and left, detected by the background monitor
+export interface NetworkChange {
+  detected_at: string
+  joined: LocalNetwork[]
+  left: LocalNetwork[]
+}
+
+const POLL_INTERVAL_MS = 15000
+
+export function useNetworkChange() {
+  const [change, setChange] = useState<NetworkChange | null>(null)
+  const [autoScan, setAutoScan] = useState(false)
+
+  useEffect(() => {
+    tauriCommands.getNetworkChangeSettings()
+      .then((settings: { auto_scan: boolean }) => setAutoScan(settings.auto_scan))
+      .catch((error: unknown) => console.error('Failed to load network change settings:', error))
+  }, [])
+
+  useEffect(() => {
+    const poll = async () => {
+      try {
+        setChange(await tauriCommands.getNetworkChange())
+      } catch (error) {
+        console.error('Failed to check for network changes:', error)
+      }
+    }
+    poll()
+    const interval = setInterval(poll, POLL_INTERVAL_MS)
+    return () => clearInterval(interval)
+  }, [])
+
+  const dismiss = useCallback(async () => {
+    setChange(null)
+    try {
+      await tauriCommands.dismissNetworkChange()
+    } catch (error) {
+      console.error('Failed to dismiss network change:', error)
+    }
+  }, [])
+
+  return { change, autoScan, dismiss }
+}
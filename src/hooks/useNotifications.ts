@@ -3,9 +3,9 @@ import { invokeTauri } from '@/lib/tauri'
 import { HostInfo } from './useHosts'
 
 export function useNotifications() {
-  const sendNotification = useCallback(async (title: string, body: string) => {
+  const sendNotification = useCallback(async (title: string, body: string, host?: string, alert?: string) => {
     try {
-      await invokeTauri('send_system_notification_command', { title, body })
+      await invokeTauri('send_system_notification_command', { title, body, host, alert })
     } catch (error) {
       console.error('Failed to send notification:', error)
     }
@@ -83,7 +83,8 @@ export function useNotifications() {
         const title = `${t.networkScanner} - ${oldHost.hostname}`
         const body = `${t.status}: ${t[statusKey as keyof typeof t] || newStatus}`
         
-        sendNotification(title, body)
+        // Отключение можно отложить или подтвердить, тогда уведомление не приходит
+        sendNotification(title, body, newHost.ip_address, newStatus === 'offline' ? 'offline' : undefined)
       }
     }
   }, [getPrinterStatus, sendNotification])
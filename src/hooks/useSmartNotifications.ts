@@ -1,5 +1,5 @@
 import { useCallback, useRef } from 'react'
-import { invokeTauri } from '@/lib/tauri'
+import { invokeTauri, tauriCommands } from '@/lib/tauri'
 import { HostInfo } from './useHosts'
 
 interface NotificationState {
@@ -36,14 +36,34 @@ export function useSmartNotifications() {
   const globalOfflineCount = useRef<number>(0)
   const lastGlobalOfflineTime = useRef<number>(0)
 
-  const sendNotification = useCallback(async (title: string, body: string) => {
+  const sendNotification = useCallback(async (title: string, body: string, host?: string, alert?: string) => {
     try {
-      await invokeTauri('send_system_notification_command', { title, body })
+      await invokeTauri('send_system_notification_command', { title, body, host, alert })
     } catch (error) {
       console.error('Failed to send notification:', error)
     }
   }, [])
 
+  // Текст по шаблону пользователя, если он задан, иначе встроенный текст
+  const renderStatusChangeBody = useCallback(async (
+    host: HostInfo,
+    oldStatus: string,
+    newStatus: string,
+    defaultBody: string
+  ): Promise<string> => {
+    try {
+      const rendered: string | null = await tauriCommands.renderStatusChangeNotification(
+        host.ip_address, host.hostname, oldStatus, newStatus
+      )
+      if (rendered) {
+        return newStatus === 'error' && host.error_message ? `${rendered}\n${host.error_message}` : rendered
+      }
+    } catch (error) {
+      console.error('Failed to render notification template:', error)
+    }
+    return defaultBody
+  }, [])
+
   const getPrinterStatus = useCallback((host: HostInfo): string => {
     // First check if host is marked as offline
     if (host.status === 'offline') {
@@ -212,6 +232,11 @@ export function useSmartNotifications() {
     const newStatus = getPrinterStatus(newHost)
     
     if (oldStatus !== newStatus) {
+      // Хосты на обслуживании не присылают уведомления об отключении и ошибках
+      if (newHost.maintenance && (newStatus === 'offline' || newStatus === 'error')) {
+        return
+      }
+
       // Проверяем, нужно ли отправлять уведомление
       if (!shouldSendNotification(newHost.id, newStatus, allHosts)) {
         return
@@ -223,12 +248,15 @@ export function useSmartNotifications() {
       
       if (notificationEnabled) {
         const title = `${t.networkScanner} - ${oldHost.hostname}`
-        const body = `${t.status}: ${t[statusKey as keyof typeof t] || newStatus}`
+        let body = `${t.status}: ${t[statusKey as keyof typeof t] || newStatus}`
+        if (newStatus === 'error' && newHost.error_message) {
+          body += `\n${newHost.error_message}`
+        }
         
-        sendNotification(title, body)
+        renderStatusChangeBody(newHost, oldStatus, newStatus, body).then(text => sendNotification(title, text, newHost.ip_address, newStatus === 'offline' ? 'offline' : undefined))
       }
     }
-  }, [getPrinterStatus, shouldSendNotification, sendNotification])
+  }, [getPrinterStatus, shouldSendNotification, sendNotification, renderStatusChangeBody])
 
   const resetHostTimeout = useCallback((hostId: string) => {
     const state = notificationStates.current.get(hostId)
@@ -246,6 +274,7 @@ export function useSmartNotifications() {
 
   return {
     sendNotification,
+    renderStatusChangeBody,
     getPrinterStatus,
     checkStatusChangeAndNotify,
     resetHostTimeout,
@@ -1,6 +1,24 @@
 import { useState, useEffect, useCallback } from 'react'
 import { tauriCommands } from '@/lib/tauri'
 
+export type TelegramRole = 'admin' | 'operator' | 'viewer'
+
+// User who entered a valid registration code and waits for approval
+export interface PendingRegistration {
+  user_id: number
+  username?: string
+  first_name?: string
+  last_name?: string
+  requested_at: string
+  role: TelegramRole // Role of the registration code that was used
+}
+
+// Deep link that registers the user who opens it, with its QR code
+export interface RegistrationLink {
+  url: string
+  qr_svg: string
+}
+
 export interface TelegramUser {
   user_id: number
   username?: string
@@ -8,6 +26,25 @@ export interface TelegramUser {
   last_name?: string
   registered_at: string
   notifications_enabled: boolean
+  role?: TelegramRole
+  blocked_reason?: string | null // Set when the user blocked the bot or the chat is gone
+  blocked_at?: string | null
+}
+
+export interface TelegramError {
+  kind: 'network' | 'api' | 'unauthorized' | 'config' | 'not_running' | 'already_running'
+    | 'registration_active' | 'user_exists' | 'user_not_found' | 'printer'
+  message: string
+}
+
+const isTelegramError = (error: unknown): error is TelegramError =>
+  typeof error === 'object' && error !== null && 'kind' in error && 'message' in error
+
+const telegramErrorMessage = (error: unknown, fallback: string): string => {
+  if (isTelegramError(error)) return error.message
+  if (error instanceof Error) return error.message
+  if (typeof error === 'string') return error
+  return fallback
 }
 
 export interface TelegramBotStatus {
@@ -23,7 +60,9 @@ export function useTelegramBot(enabled: boolean) {
     error: null,
   })
   const [users, setUsers] = useState<TelegramUser[]>([])
+  const [pendingRegistrations, setPendingRegistrations] = useState<PendingRegistration[]>([])
   const [registrationCode, setRegistrationCode] = useState<string | null>(null)
+  const [registrationLink, setRegistrationLink] = useState<RegistrationLink | null>(null)
   const [registrationTimeLeft, setRegistrationTimeLeft] = useState<number | null>(null)
   const [hasToken, setHasToken] = useState<boolean>(false)
 
@@ -38,7 +77,7 @@ export function useTelegramBot(enabled: boolean) {
     } catch (error) {
       setStatus(prev => ({ 
         ...prev, 
-        error: error instanceof Error ? error.message : 'Unknown error',
+        error: telegramErrorMessage(error, 'Unknown error'),
         isRunning: false 
       }))
     }
@@ -53,6 +92,40 @@ export function useTelegramBot(enabled: boolean) {
     }
   }, [])
 
+  const loadPendingRegistrations = useCallback(async () => {
+    try {
+      setPendingRegistrations(await tauriCommands.getTelegramPendingRegistrations())
+    } catch (error) {
+      // Not running bots have no pending registrations
+      setPendingRegistrations([])
+    }
+  }, [])
+
+  const approveRegistration = useCallback(async (userId: number, role: TelegramRole) => {
+    try {
+      await tauriCommands.approveTelegramRegistration(userId, role)
+      await loadUsers()
+    } catch (error) {
+      setStatus(prev => ({
+        ...prev,
+        error: telegramErrorMessage(error, 'Failed to approve registration')
+      }))
+    }
+    await loadPendingRegistrations()
+  }, [loadUsers, loadPendingRegistrations])
+
+  const denyRegistration = useCallback(async (userId: number) => {
+    try {
+      await tauriCommands.denyTelegramRegistration(userId)
+    } catch (error) {
+      setStatus(prev => ({
+        ...prev,
+        error: telegramErrorMessage(error, 'Failed to deny registration')
+      }))
+    }
+    await loadPendingRegistrations()
+  }, [loadPendingRegistrations])
+
   const saveUsersToBackend = useCallback(async (users: TelegramUser[]) => {
     try {
       await tauriCommands.saveTelegramUsers(users)
@@ -67,13 +140,20 @@ export function useTelegramBot(enabled: boolean) {
     try {
       const code = await tauriCommands.startTelegramRegistration()
       setRegistrationCode(code)
+      try {
+        setRegistrationLink(await tauriCommands.getTelegramRegistrationLink())
+      } catch (error) {
+        // The numeric code still works without the link
+        console.error('Failed to get registration link:', error)
+        setRegistrationLink(null)
+      }
       setRegistrationTimeLeft(60) // 60 seconds
       setStatus(prev => ({ ...prev, isLoading: false, error: null }))
     } catch (error) {
       console.error('Failed to start registration:', error);
       setStatus(prev => ({ 
         ...prev, 
-        error: error instanceof Error ? error.message : 'Failed to start registration',
+        error: telegramErrorMessage(error, 'Failed to start registration'),
         isLoading: false 
       }))
     }
@@ -83,11 +163,12 @@ export function useTelegramBot(enabled: boolean) {
     try {
       await tauriCommands.stopTelegramRegistration()
       setRegistrationCode(null)
+      setRegistrationLink(null)
       setRegistrationTimeLeft(null)
     } catch (error) {
       setStatus(prev => ({ 
         ...prev, 
-        error: error instanceof Error ? error.message : 'Failed to stop registration'
+        error: telegramErrorMessage(error, 'Failed to stop registration')
       }))
     }
   }, [])
@@ -109,7 +190,7 @@ export function useTelegramBot(enabled: boolean) {
       console.error('Failed to remove user:', error);
       setStatus(prev => ({ 
         ...prev, 
-        error: error instanceof Error ? error.message : 'Failed to remove user'
+        error: telegramErrorMessage(error, 'Failed to remove user')
       }))
     }
   }, [saveUsersToBackend])
@@ -135,7 +216,7 @@ export function useTelegramBot(enabled: boolean) {
       console.error('Failed to update user notifications:', error);
       setStatus(prev => ({ 
         ...prev, 
-        error: error instanceof Error ? error.message : 'Failed to update user notifications'
+        error: telegramErrorMessage(error, 'Failed to update user notifications')
       }))
     }
   }, [saveUsersToBackend])
@@ -188,7 +269,7 @@ export function useTelegramBot(enabled: boolean) {
     } catch (error) {
       setStatus(prev => ({ 
         ...prev, 
-        error: error instanceof Error ? error.message : 'Failed to start bot',
+        error: telegramErrorMessage(error, 'Failed to start bot'),
         isLoading: false,
         isRunning: false 
       }))
@@ -202,9 +283,13 @@ export function useTelegramBot(enabled: boolean) {
       await tauriCommands.stopTelegramBot()
       setStatus(prev => ({ ...prev, isRunning: false, isLoading: false, error: null }))
     } catch (error) {
+      if (isTelegramError(error) && error.kind === 'not_running') {
+        setStatus(prev => ({ ...prev, isRunning: false, isLoading: false, error: null }))
+        return
+      }
       setStatus(prev => ({ 
         ...prev, 
-        error: error instanceof Error ? error.message : 'Failed to stop bot',
+        error: telegramErrorMessage(error, 'Failed to stop bot'),
         isLoading: false 
       }))
     }
@@ -237,6 +322,7 @@ export function useTelegramBot(enabled: boolean) {
     if (status.isRunning) {
       const interval = setInterval(async () => {
         await loadUsers()
+        await loadPendingRegistrations()
         
         // Check if registration is still active
         if (registrationCode) {
@@ -244,6 +330,7 @@ export function useTelegramBot(enabled: boolean) {
             const isActive = await tauriCommands.isTelegramRegistrationActive()
             if (!isActive) {
               setRegistrationCode(null)
+              setRegistrationLink(null)
               setRegistrationTimeLeft(null)
               // Force reload users when registration completes
               const updatedUsers = await tauriCommands.getTelegramUsers()
@@ -258,7 +345,7 @@ export function useTelegramBot(enabled: boolean) {
 
       return () => clearInterval(interval)
     }
-  }, [status.isRunning, loadUsers, registrationCode, saveUsersToBackend])
+  }, [status.isRunning, loadUsers, loadPendingRegistrations, registrationCode, saveUsersToBackend])
 
   // Function to sync hosts with Telegram bot
   const syncHostsWithBot = useCallback(async (hosts: any[]) => {
@@ -296,7 +383,11 @@ export function useTelegramBot(enabled: boolean) {
   return {
     status,
     users,
+    pendingRegistrations,
+    approveRegistration,
+    denyRegistration,
     registrationCode,
+    registrationLink,
     registrationTimeLeft,
     hasToken,
     startBot,
@@ -28,22 +28,145 @@ export const tauriCommands = {
   // Network scanning
   scanNetwork: (subnets: string[]) => invokeTauri('scan_network_command', { subnets }),
   getHostInfo: (ip: string) => invokeTauri('get_host_info_command', { ip }),
+  getDhcpReservation: (host: string) => invokeTauri('get_dhcp_reservation_command', { host }),
   checkHostStatus: (ip: string) => invokeTauri('check_host_status_command', { ip }),
+  refreshAllHosts: (ips?: string[]) => invokeTauri('refresh_all_hosts_command', { ips }),
+  getStatusCacheTtl: () => invokeTauri('get_status_cache_ttl_command'),
+  setStatusCacheTtl: (ttlMs: number) => invokeTauri('set_status_cache_ttl_command', { ttlMs }),
+  getHostRequestConcurrency: () => invokeTauri('get_host_request_concurrency_command'),
+  setHostRequestConcurrency: (limit: number) => invokeTauri('set_host_request_concurrency_command', { limit }),
+  getScanLimits: () => invokeTauri('get_scan_limits_command'),
+  updateScanLimits: (scanLimits: any) => invokeTauri('update_scan_limits_command', { scanLimits }),
+  getScanMethod: () => invokeTauri('get_scan_method_command'),
+  setScanMethod: (scanMethod: 'connect' | 'syn') => invokeTauri('set_scan_method_command', { scanMethod }),
+  getOverlayScanSettings: () => invokeTauri('get_overlay_scan_settings_command'),
+  updateOverlayScanSettings: (overlayScan: any) => invokeTauri('update_overlay_scan_settings_command', { overlayScan }),
+  getMoonrakerPorts: () => invokeTauri('get_moonraker_ports_command'),
+  setMoonrakerPorts: (ip: string, ports: number[]) => invokeTauri('set_moonraker_ports_command', { ip, ports }),
+  isSynScanAvailable: () => invokeTauri('is_syn_scan_available_command'),
+  getPortCacheStats: () => invokeTauri('get_port_cache_stats_command'),
+  clearPortCache: () => invokeTauri('clear_port_cache_command'),
+  getLocalNetworks: () => invokeTauri('get_local_networks_command'),
+  getNetworkChange: () => invokeTauri('get_network_change_command'),
+  dismissNetworkChange: () => invokeTauri('dismiss_network_change_command'),
+  getNetworkChangeSettings: () => invokeTauri('get_network_change_settings_command'),
+  updateNetworkChangeSettings: (networkChange: any) => invokeTauri('update_network_change_settings_command', { networkChange }),
   
   // Printer control
   controlPrinter: (ip: string, action: string) => invokeTauri('control_printer_command', { ip, action }),
   getPrinterStatus: (ip: string) => invokeTauri('get_printer_status_command', { ip }),
+  getPrinterCapabilities: (host: string, refresh?: boolean) => invokeTauri('get_printer_capabilities_command', { host, refresh }),
+  firmwareRestart: (host: string, timeoutSeconds?: number) => invokeTauri('firmware_restart_command', { host, timeoutSeconds }),
+  saveConfig: (host: string, timeoutSeconds?: number) => invokeTauri('save_config_command', { host, timeoutSeconds }),
+  setFanSpeed: (host: string, speed: number, fan?: string) => invokeTauri('set_fan_speed_command', { host, fan, speed }),
+  setLed: (host: string, led: string, red: number, green: number, blue: number, white?: number) => invokeTauri('set_led_command', { host, led, red, green, blue, white }),
+  homeAxes: (host: string, axes: string) => invokeTauri('home_axes_command', { host, axes }),
+  jog: (host: string, axis: string, distance: number, feedrate: number) => invokeTauri('jog_command', { host, axis, distance, feedrate }),
+  getZOffset: (host: string) => invokeTauri('get_z_offset_command', { host }),
+  adjustZOffset: (host: string, delta: number) => invokeTauri('adjust_z_offset_command', { host, delta }),
+  getObjectQueryPresets: () => invokeTauri('get_object_query_presets_command'),
+  saveObjectQueryPreset: (preset: { name: string, objects: string[] }) => invokeTauri('save_object_query_preset_command', { preset }),
+  deleteObjectQueryPreset: (name: string) => invokeTauri('delete_object_query_preset_command', { name }),
+  queryPrinterObjects: (host: string, preset?: string, objects?: string[]) => invokeTauri('query_printer_objects_command', { host, preset, objects }),
+  getPowerDevices: (host: string) => invokeTauri('get_power_devices_command', { host }),
+  setPowerDevice: (host: string, device: string, on: boolean) => invokeTauri('set_power_device_command', { host, device, on }),
+  getAutoPowerOff: (host: string) => invokeTauri('get_auto_power_off_command', { host }),
+  setAutoPowerOff: (host: string, config: { device: string; temperature_threshold: number } | null) =>
+    invokeTauri('set_auto_power_off_command', { host, config }),
+  getSensors: (host: string) => invokeTauri('get_sensors_command', { host }),
+  getMcuStats: (host: string) => invokeTauri('get_mcu_stats_command', { host }),
+  listCanbusDevices: (host: string, iface?: string, sshUser?: string) =>
+    invokeTauri('list_canbus_devices_command', { host, interface: iface, sshUser }),
+  runPreflight: (host: string, bedClearConfirmed: boolean) => invokeTauri('run_preflight_command', { host, bedClearConfirmed }),
+  startPrint: (host: string, fileName: string, bedClearConfirmed: boolean) =>
+    invokeTauri('start_print_command', { host, fileName, bedClearConfirmed }),
+  getPreflightSettings: () => invokeTauri('get_preflight_settings_command'),
+  updatePreflightSettings: (preflight: any) => invokeTauri('update_preflight_settings_command', { preflight }),
+  getSensorSettings: (host: string) => invokeTauri('get_sensor_settings_command', { host }),
+  setSensorSettings: (
+    host: string,
+    sensor: string,
+    config: { label?: string | null; max_temperature?: number | null; min_temperature?: number | null; max_humidity?: number | null } | null,
+  ) => invokeTauri('set_sensor_settings_command', { host, sensor, config }),
+  
+  // Print statistics and costs
+  getHistoryStats: (host: string) => invokeTauri('get_history_stats_command', { host }),
+  getCostSettings: () => invokeTauri('get_cost_settings_command'),
+  updateCostSettings: (costs: any) => invokeTauri('update_cost_settings_command', { costs }),
+  getJobNotes: (host?: string) => invokeTauri('get_job_notes_command', { host }),
+  setJobNote: (host: string, note: string, labels: string[], jobId?: string, filename?: string) =>
+    invokeTauri('set_job_note_command', { host, jobId, filename, note, labels }),
+  formatDuration: (seconds: number) => invokeTauri('format_duration_command', { seconds }),
+  formatTimestamp: (timestamp: number) => invokeTauri('format_timestamp_command', { timestamp }),
+  formatFinishTime: (remainingSeconds: number) => invokeTauri('format_finish_time_command', { remainingSeconds }),
   
   // System operations
   openWebcam: (ip: string) => invokeTauri('open_webcam_command', { ip }),
-  openHostInBrowser: (ip: string) => invokeTauri('open_host_in_browser_command', { ip }),
+  openHostInBrowser: (ip: string) => invokeTauri('open_host_in_browser_command', { host: ip }),
   openSSHConnection: (ip: string, user: string) => invokeTauri('open_ssh_connection_command', { ip, user }),
-  sendNotification: (title: string, body: string) => invokeTauri('send_system_notification_command', { title, body }),
+  sendNotification: (title: string, body: string, host?: string, alert?: string) =>
+    invokeTauri('send_system_notification_command', { title, body, host, alert }),
+  checkNotificationStatus: () => invokeTauri('check_notification_status_command'),
+  requestNotificationPermission: () => invokeTauri('request_notification_permission_command'),
+  listNotifications: (unreadOnly?: boolean) => invokeTauri('list_notifications_command', { unreadOnly }),
+  getUnreadNotificationCount: () => invokeTauri('get_unread_notification_count_command'),
+  markNotificationRead: (id: number) => invokeTauri('mark_notification_read_command', { id }),
+  markAllNotificationsRead: () => invokeTauri('mark_all_notifications_read_command'),
+  clearNotifications: () => invokeTauri('clear_notifications_command'),
+  getLanguage: () => invokeTauri('get_language_command'),
+  setLanguage: (language: string) => invokeTauri('set_language_command', { language }),
+  getUse24HourClock: () => invokeTauri('get_use_24_hour_clock_command'),
+  setUse24HourClock: (use24Hour: boolean) => invokeTauri('set_use_24_hour_clock_command', { use24Hour }),
+  getOutboundHttpSettings: () => invokeTauri('get_outbound_http_settings_command'),
+  updateOutboundHttpSettings: (outboundHttp: any) => invokeTauri('update_outbound_http_settings_command', { outboundHttp }),
+  previewUserAgent: (template?: string) => invokeTauri('preview_user_agent_command', { template }),
   
   // Background monitoring
   startBackgroundMonitoring: (intervalSeconds: number) => invokeTauri('start_background_monitoring_command', { intervalSeconds }),
   stopBackgroundMonitoring: () => invokeTauri('stop_background_monitoring_command'),
   getBackgroundMonitoringStatus: () => invokeTauri('get_background_monitoring_status_command'),
+  getHostHealth: (host?: string) => invokeTauri('get_host_health_command', { host }),
+  getFailureDetectionSettings: () => invokeTauri('get_failure_detection_settings_command'),
+  updateFailureDetectionSettings: (failureDetection: any) => invokeTauri('update_failure_detection_settings_command', { failureDetection }),
+  getJobComparisonSettings: () => invokeTauri('get_job_comparison_settings_command'),
+  updateJobComparisonSettings: (jobComparison: any) => invokeTauri('update_job_comparison_settings_command', { jobComparison }),
+  getSnapshotArchiveSettings: () => invokeTauri('get_snapshot_archive_settings_command'),
+  updateSnapshotArchiveSettings: (snapshotArchive: any) => invokeTauri('update_snapshot_archive_settings_command', { snapshotArchive }),
+  getErrorCaptureSettings: () => invokeTauri('get_error_capture_settings_command'),
+  updateErrorCaptureSettings: (errorCapture: any) => invokeTauri('update_error_capture_settings_command', { errorCapture }),
+  getErrorCaptures: (host?: string) => invokeTauri('get_error_captures_command', { host }),
+  deleteErrorCapture: (directory: string) => invokeTauri('delete_error_capture_command', { directory }),
+  getCompletionGif: (host: string) => invokeTauri('get_completion_gif_command', { host }),
+  setCompletionGif: (host: string, config: { max_frames: number; end_frames: number; frame_delay_ms: number; max_width: number } | null) =>
+    invokeTauri('set_completion_gif_command', { host, config }),
+  getCompletionWebhooks: () => invokeTauri('get_completion_webhooks_command'),
+  updateCompletionWebhooks: (webhooks: any[]) => invokeTauri('update_completion_webhooks_command', { webhooks }),
+  getCooldownSettings: () => invokeTauri('get_cooldown_settings_command'),
+  updateCooldownSettings: (cooldown: any) => invokeTauri('update_cooldown_settings_command', { cooldown }),
+  getHeaterWatchSettings: () => invokeTauri('get_heater_watch_settings_command'),
+  updateHeaterWatchSettings: (heaterWatch: any) => invokeTauri('update_heater_watch_settings_command', { heaterWatch }),
+  getHeaterIdleSettings: () => invokeTauri('get_heater_idle_settings_command'),
+  updateHeaterIdleSettings: (heaterIdle: any) => invokeTauri('update_heater_idle_settings_command', { heaterIdle }),
+  snoozeAlert: (host: string, kind: string, minutes: number) => invokeTauri('snooze_alert_command', { host, kind, minutes }),
+  acknowledgeAlert: (host: string, kind: string) => invokeTauri('acknowledge_alert_command', { host, kind }),
+  unsilenceAlert: (host: string, kind: string) => invokeTauri('unsilence_alert_command', { host, kind }),
+  getAlertSilences: () => invokeTauri('get_alert_silences_command'),
+  getAckEscalationSettings: () => invokeTauri('get_ack_escalation_settings_command'),
+  updateAckEscalationSettings: (ackEscalation: any) => invokeTauri('update_ack_escalation_settings_command', { ackEscalation }),
+  getMcuWatchSettings: () => invokeTauri('get_mcu_watch_settings_command'),
+  updateMcuWatchSettings: (mcuWatch: any) => invokeTauri('update_mcu_watch_settings_command', { mcuWatch }),
+  getOfflineEscalationSettings: () => invokeTauri('get_offline_escalation_settings_command'),
+  updateOfflineEscalationSettings: (offlineEscalation: any) => invokeTauri('update_offline_escalation_settings_command', { offlineEscalation }),
+  getFleetIdleNotificationSettings: () => invokeTauri('get_fleet_idle_notification_settings_command'),
+  updateFleetIdleNotificationSettings: (fleetIdle: any) => invokeTauri('update_fleet_idle_notification_settings_command', { fleetIdle }),
+  getNotificationTemplates: () => invokeTauri('get_notification_templates_command'),
+  updateNotificationTemplates: (templates: any) => invokeTauri('update_notification_templates_command', { templates }),
+  validateNotificationTemplate: (template: string) => invokeTauri('validate_notification_template_command', { template }),
+  previewNotificationTemplate: (template: string) => invokeTauri('preview_notification_template_command', { template }),
+  renderStatusChangeNotification: (host: string, hostname: string, previousStatus: string, status: string) =>
+    invokeTauri('render_status_change_notification_command', { host, hostname, previousStatus, status }),
+  getDoNotDisturbState: () => invokeTauri('get_do_not_disturb_state_command'),
+  setRespectDoNotDisturb: (respect: boolean) => invokeTauri('set_respect_do_not_disturb_command', { respect }),
   
   // Telegram bot
   startTelegramBot: () => invokeTauri('start_telegram_bot'),
@@ -52,16 +175,143 @@ export const tauriCommands = {
   startTelegramRegistration: () => invokeTauri('start_telegram_registration'),
   stopTelegramRegistration: () => invokeTauri('stop_telegram_registration'),
   isTelegramRegistrationActive: () => invokeTauri('is_telegram_registration_active'),
+  getTelegramRegistrationLink: (code?: string) => invokeTauri('get_telegram_registration_link', { code }),
+  createTelegramRegistrationCode: (role: 'admin' | 'operator' | 'viewer', expiresMinutes: number, label?: string, singleUse?: boolean) =>
+    invokeTauri('create_telegram_registration_code', { role, expiresMinutes, label, singleUse }),
+  getTelegramRegistrationCodes: () => invokeTauri('get_telegram_registration_codes'),
+  revokeTelegramRegistrationCode: (code: string) => invokeTauri('revoke_telegram_registration_code', { code }),
+  getTelegramPendingRegistrations: () => invokeTauri('get_telegram_pending_registrations'),
+  approveTelegramRegistration: (userId: number, role?: 'admin' | 'operator' | 'viewer') => invokeTauri('approve_telegram_registration', { userId, role }),
+  denyTelegramRegistration: (userId: number) => invokeTauri('deny_telegram_registration', { userId }),
   getTelegramUsers: () => invokeTauri('get_telegram_users'),
   removeTelegramUser: (userId: number) => invokeTauri('remove_telegram_user', { userId }),
   getTelegramHosts: () => invokeTauri('get_telegram_hosts'),
   updateTelegramHosts: (hosts: any[]) => invokeTauri('update_telegram_hosts', { hosts }),
-  sendTelegramNotification: (title: string, body: string, hostIp?: string) => invokeTauri('send_telegram_notification', { title, body, hostIp }),
+  sendTelegramNotification: (title: string, body: string, hostIp?: string, alert?: string) =>
+    invokeTauri('send_telegram_notification', { title, body, hostIp, alert }),
   updateTelegramUserNotifications: (userId: number, notificationsEnabled: boolean) => invokeTauri('update_telegram_user_notifications', { userId, notificationsEnabled }),
   saveTelegramBotToken: (token: string) => invokeTauri('save_telegram_bot_token', { token }),
   getTelegramBotToken: () => invokeTauri('get_telegram_bot_token'),
   clearTelegramBotToken: () => invokeTauri('clear_telegram_bot_token'),
   loadTelegramSettings: () => invokeTauri('load_telegram_settings'),
   getTelegramRegistrationInfo: () => invokeTauri('get_telegram_registration_info'),
-  saveTelegramUsers: (users: any[]) => invokeTauri('save_telegram_users', { users })
+  saveTelegramUsers: (users: any[]) => invokeTauri('save_telegram_users', { users }),
+  getTelegramAuditLog: (limit?: number) => invokeTauri('get_telegram_audit_log', { limit }),
+  setTelegramNotifyEmergencyStop: (enabled: boolean) => invokeTauri('set_telegram_notify_emergency_stop', { enabled }),
+  getTelegramAllowedNetworks: () => invokeTauri('get_telegram_allowed_networks'),
+  setTelegramAllowedNetworks: (networks: string[]) => invokeTauri('set_telegram_allowed_networks', { networks }),
+  
+  // REST API server
+  startRestApi: () => invokeTauri('start_rest_api_command'),
+  stopRestApi: () => invokeTauri('stop_rest_api_command'),
+  getRestApiStatus: () => invokeTauri('get_rest_api_status_command'),
+  getRestApiSettings: () => invokeTauri('get_rest_api_settings_command'),
+  updateRestApiSettings: (restApi: any) => invokeTauri('update_rest_api_settings_command', { restApi }),
+  
+  // Host configuration
+  getHostConnections: () => invokeTauri('get_host_connections_command'),
+  updateHostConnection: (host: string, connection: any | null) => invokeTauri('update_host_connection_command', { host, connection }),
+  getHostNames: () => invokeTauri('get_host_names_command'),
+  renameHost: (ip: string, name: string | null) => invokeTauri('rename_host_command', { ip, name }),
+  setHostMaintenance: (ip: string, enabled: boolean) => invokeTauri('set_host_maintenance_command', { ip, enabled }),
+  exportHosts: (format: 'csv' | 'json', path: string) => invokeTauri('export_hosts_command', { format, path }),
+  importHosts: (path: string) => invokeTauri('import_hosts_command', { path }),
+  addRemoteHost: (url: string, name?: string, auth?: { api_key?: string; token?: string }) =>
+    invokeTauri('add_remote_host_command', { url, name, auth }),
+  removeRemoteHost: (host: string) => invokeTauri('remove_remote_host_command', { host }),
+  getFleetOverview: () => invokeTauri('get_fleet_overview_command'),
+  getAvailabilityForecast: () => invokeTauri('get_availability_forecast_command'),
+  getHostDiagnostics: (hosts?: string[]) => invokeTauri('get_host_diagnostics_command', { hosts: hosts ?? null }),
+  
+  // Maintenance schedule
+  getHostMaintenance: (host: string) => invokeTauri('get_host_maintenance_command', { host }),
+  addMaintenanceTask: (host: string, name: string, intervalHours: number) => invokeTauri('add_maintenance_task_command', { host, name, intervalHours }),
+  removeMaintenanceTask: (host: string, taskId: string) => invokeTauri('remove_maintenance_task_command', { host, taskId }),
+  completeMaintenanceTask: (host: string, taskId: string, note?: string) => invokeTauri('complete_maintenance_task_command', { host, taskId, note }),
+  addMaintenanceLogEntry: (host: string, title: string, note?: string) => invokeTauri('add_maintenance_log_entry_command', { host, title, note }),
+  setHostTags: (ip: string, tags: string[]) => invokeTauri('set_host_tags_command', { ip, tags }),
+  
+  // Print queue
+  getPrintQueue: () => invokeTauri('get_print_queue_command'),
+  addQueueJob: (filePath: string, requiredTags: string[]) => invokeTauri('add_queue_job_command', { filePath, requiredTags }),
+  removeQueueJob: (jobId: string) => invokeTauri('remove_queue_job_command', { jobId }),
+  clearFinishedQueueJobs: () => invokeTauri('clear_finished_queue_jobs_command'),
+  markBedCleared: (host: string) => invokeTauri('mark_bed_cleared_command', { host }),
+  getGcodeMetadata: (filePath: string) => invokeTauri('get_gcode_metadata_command', { filePath }),
+  
+  // Scheduled pause/cancel of running prints
+  getScheduledActions: (host?: string) => invokeTauri('get_scheduled_actions_command', { host }),
+  addScheduledAction: (host: string, action: 'pause' | 'cancel', atTime?: string, atProgress?: number, belowProgress?: number) =>
+    invokeTauri('add_scheduled_action_command', { host, action, atTime, atProgress, belowProgress }),
+  removeScheduledAction: (id: string) => invokeTauri('remove_scheduled_action_command', { id }),
+
+  // Automation rules
+  getRules: () => invokeTauri('get_rules_command'),
+  saveRule: (rule: {
+    id: string
+    name: string
+    enabled: boolean
+    hosts: string[]
+    conditions: Array<Record<string, unknown> & { metric: string }>
+    actions: Array<Record<string, unknown> & { type: string }>
+  }) => invokeTauri('save_rule_command', { rule }),
+  setRuleEnabled: (id: string, enabled: boolean) => invokeTauri('set_rule_enabled_command', { id, enabled }),
+  removeRule: (id: string) => invokeTauri('remove_rule_command', { id }),
+
+  // Custom quick actions
+  getQuickActions: (host: string) => invokeTauri('get_quick_actions_command', { host }),
+  saveQuickAction: (host: string, action: {
+    id?: string
+    label: string
+    action:
+      | { type: 'gcode'; script: string }
+      | { type: 'http'; method?: string; url: string; body?: string | null }
+      | { type: 'ssh'; user: string; command: string }
+    confirm: boolean
+    telegram: boolean
+  }) => invokeTauri('save_quick_action_command', { host, action }),
+  removeQuickAction: (host: string, id: string) => invokeTauri('remove_quick_action_command', { host, id }),
+  runQuickAction: (host: string, id: string) => invokeTauri('run_quick_action_command', { host, id }),
+  
+  // Resonance calibration
+  startCalibration: (host: string, kind: 'shaper_calibrate' | 'test_resonances', axis?: string) => invokeTauri('start_calibration_command', { host, kind, axis }),
+  getCalibrationRun: (host: string) => invokeTauri('get_calibration_run_command', { host }),
+  startPidCalibration: (host: string, heater: string, target: number) => invokeTauri('start_pid_calibration_command', { host, heater, target }),
+  getPidCalibrationRun: (host: string) => invokeTauri('get_pid_calibration_run_command', { host }),
+  downloadCalibrationFile: (host: string, root: string, path: string, destination: string) => invokeTauri('download_calibration_file_command', { host, root, path, destination }),
+
+  // Printer configuration
+  listConfigFiles: (host: string) => invokeTauri('list_config_files_command', { host }),
+  readConfigFile: (host: string, path: string) => invokeTauri('read_config_file_command', { host, path }),
+  diffConfigFile: (host: string, path: string, content: string) => invokeTauri('diff_config_file_command', { host, path, content }),
+  saveConfigFile: (host: string, path: string, content: string, original?: string) => invokeTauri('save_config_file_command', { host, path, content, original }),
+  restartConfigService: (host: string, service: 'klipper' | 'moonraker', timeoutSeconds?: number) => invokeTauri('restart_config_service_command', { host, service, timeoutSeconds }),
+
+  // Config backups
+  getConfigBackupSettings: () => invokeTauri('get_config_backup_settings_command'),
+  updateConfigBackupSettings: (configBackup: {
+    enabled: boolean
+    interval_hours: number
+    keep_per_host: number
+    directory?: string | null
+    github: { enabled: boolean; token?: string | null; repository: string; branch: string; path_prefix: string }
+  }) => invokeTauri('update_config_backup_settings_command', { configBackup }),
+  backupConfigs: (host?: string) => invokeTauri('backup_configs_command', { host }),
+  pushConfigBackup: (directory: string) => invokeTauri('push_config_backup_command', { directory }),
+  getConfigBackups: (host?: string) => invokeTauri('get_config_backups_command', { host }),
+  restoreConfigBackup: (directory: string, host: string, files?: string[]) => invokeTauri('restore_config_backup_command', { directory, host, files }),
+  deleteConfigBackup: (directory: string) => invokeTauri('delete_config_backup_command', { directory }),
+
+  // Telemetry
+  getTelemetrySettings: () => invokeTauri('get_telemetry_settings_command'),
+  updateTelemetrySettings: (telemetry: {
+    enabled: boolean
+    sample_interval_seconds: number
+    retention_days: number
+    database?: string | null
+  }) => invokeTauri('update_telemetry_settings_command', { telemetry }),
+  queryTelemetry: (host?: string, from?: string, to?: string, limit?: number) =>
+    invokeTauri('query_telemetry_command', { host, from, to, limit }),
+  getTemperatureSeries: (host: string, window: number, resolution: number) =>
+    invokeTauri('get_temperature_series_command', { host, window, resolution })
 } as const
@@ -52,6 +52,7 @@ export const de = {
   ssh: "SSH",
   webcam: "Webcam",
   order: "Reihenfolge",
+  maintenance: "Wartung",
   delete: "Löschen",
 
   // Expanded Row
@@ -166,6 +167,20 @@ export const de = {
   registrationActive: "Registrierung aktiv",
   startRegistration: "Registrierung starten",
   stopRegistration: "Registrierung stoppen",
+  telegramPendingApproval: "Wartet auf Freigabe",
+  telegramRoleAdmin: "Admin",
+  telegramRoleOperator: "Bediener",
+  telegramRoleViewer: "Betrachter",
+  approve: "Freigeben",
+  deny: "Ablehnen",
+  telegramScanToRegister: "Oder diesen Code mit dem Handy scannen:",
+  networkChanged: "Mit einem neuen Netzwerk verbunden:",
+  networkChangedScan: "Hinzufügen und scannen",
+  networkChangedDismiss: "Ausblenden",
+  alsoReachableAt: "Auch erreichbar unter",
+  saveConfigPending: "Ungespeicherte Konfigurationsänderungen, klicken für SAVE_CONFIG",
+  saveConfigConfirm: "Konfigurationsänderungen speichern und Klipper neu starten?",
+  telegramUserBlocked: "Nicht erreichbar",
   registeredUsers: "Registrierte Benutzer",
   botIsRunning: "✓ Bot läuft",
   loading: "Laden...",
@@ -175,6 +190,9 @@ export const de = {
   testTelegramNotification: "Test-Telegram-Benachrichtigung",
   testSystemNotificationBody: "Dies ist eine Test-Systembenachrichtigung zur Überprüfung der Systemfunktionalität",
   testTelegramNotificationBody: "Dies ist eine Test-Telegram-Benachrichtigung zur Überprüfung der Bot-Funktionalität",
+  notificationPermissionNotDetermined: "Systembenachrichtigungen benötigen Ihre Erlaubnis, bevor sie angezeigt werden",
+  notificationPermissionDenied: "Systembenachrichtigungen sind ausgeschaltet. Erlauben Sie sie in den Systemeinstellungen → Mitteilungen",
+  allowNotifications: "Benachrichtigungen erlauben",
   telegramNotifications: "Telegram Benachrichtigungen",
   
   // Telegram Bot Messages
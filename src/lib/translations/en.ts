@@ -52,6 +52,7 @@ export const en = {
   ssh: "SSH",
   webcam: "Webcam",
   order: "Order",
+  maintenance: "Maintenance",
   delete: "Delete",
 
   // Expanded Row
@@ -165,6 +166,20 @@ export const en = {
   registrationActive: "Registration Active",
   startRegistration: "Start Registration",
   stopRegistration: "Stop Registration",
+  telegramPendingApproval: "Waiting for approval",
+  telegramRoleAdmin: "Admin",
+  telegramRoleOperator: "Operator",
+  telegramRoleViewer: "Viewer",
+  approve: "Approve",
+  deny: "Deny",
+  telegramScanToRegister: "Or scan this code with the phone:",
+  networkChanged: "Connected to a new network:",
+  networkChangedScan: "Add and scan",
+  networkChangedDismiss: "Dismiss",
+  alsoReachableAt: "Also reachable at",
+  saveConfigPending: "Unsaved configuration changes, click to run SAVE_CONFIG",
+  saveConfigConfirm: "Save the configuration changes and restart Klipper?",
+  telegramUserBlocked: "Unreachable",
   registeredUsers: "Registered Users",
   botIsRunning: "✓ Bot is running",
   loading: "Loading...",
@@ -174,6 +189,9 @@ export const en = {
   testTelegramNotification: "Test Telegram Notification",
   testSystemNotificationBody: "This is a test system notification to verify the system is working",
   testTelegramNotificationBody: "This is a test Telegram notification to verify the bot is working",
+  notificationPermissionNotDetermined: "System notifications need your permission before they can be shown",
+  notificationPermissionDenied: "System notifications are turned off. Allow them in System Settings → Notifications",
+  allowNotifications: "Allow notifications",
   telegramNotifications: "Telegram Notifications",
   
   // Telegram Bot Messages
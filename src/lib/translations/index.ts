@@ -56,6 +56,7 @@ export interface Translations {
   ssh: string
   webcam: string
   order: string
+  maintenance: string
   delete: string
 
   // Expanded Row
@@ -167,6 +168,20 @@ export interface Translations {
   startRegistration: string
   stopRegistration: string
   registeredUsers: string
+  networkChanged: string
+  networkChangedScan: string
+  networkChangedDismiss: string
+  alsoReachableAt: string
+  saveConfigPending: string
+  saveConfigConfirm: string
+  telegramUserBlocked: string
+  telegramScanToRegister: string
+  telegramPendingApproval: string
+  telegramRoleAdmin: string
+  telegramRoleOperator: string
+  telegramRoleViewer: string
+  approve: string
+  deny: string
   botIsRunning: string
   loading: string
   testSystem: string
@@ -175,6 +190,9 @@ export interface Translations {
   testTelegramNotification: string
   testSystemNotificationBody: string
   testTelegramNotificationBody: string
+  notificationPermissionNotDetermined: string
+  notificationPermissionDenied: string
+  allowNotifications: string
   telegramNotifications: string
   
   // Additional UI elements
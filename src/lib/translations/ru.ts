@@ -52,6 +52,7 @@ export const ru = {
   ssh: "SSH",
   webcam: "Веб-камера",
   order: "Порядок",
+  maintenance: "Обслуживание",
   delete: "Удалить",
 
   // Expanded Row
@@ -166,6 +167,20 @@ export const ru = {
   registrationActive: "Регистрация активна",
   startRegistration: "Начать регистрацию",
   stopRegistration: "Остановить регистрацию",
+  telegramPendingApproval: "Ожидает подтверждения",
+  telegramRoleAdmin: "Администратор",
+  telegramRoleOperator: "Оператор",
+  telegramRoleViewer: "Наблюдатель",
+  approve: "Одобрить",
+  deny: "Отклонить",
+  telegramScanToRegister: "Или отсканируйте этот код телефоном:",
+  networkChanged: "Подключено к новой сети:",
+  networkChangedScan: "Добавить и сканировать",
+  networkChangedDismiss: "Скрыть",
+  alsoReachableAt: "Также доступен по",
+  saveConfigPending: "Несохранённые изменения конфигурации, нажмите для SAVE_CONFIG",
+  saveConfigConfirm: "Сохранить изменения конфигурации и перезапустить Klipper?",
+  telegramUserBlocked: "Недоступен",
   registeredUsers: "Зарегистрированные пользователи",
   botIsRunning: "✓ Бот работает",
   loading: "Загрузка...",
@@ -175,6 +190,9 @@ export const ru = {
   testTelegramNotification: "Тестовое Telegram уведомление",
   testSystemNotificationBody: "Это тестовое системное уведомление для проверки работы системы",
   testTelegramNotificationBody: "Это тестовое Telegram уведомление для проверки работы бота",
+  notificationPermissionNotDetermined: "Для показа системных уведомлений нужно ваше разрешение",
+  notificationPermissionDenied: "Системные уведомления выключены. Разрешите их в Системных настройках → Уведомления",
+  allowNotifications: "Разрешить уведомления",
   telegramNotifications: "Telegram уведомления",
   
   // Telegram Bot Messages